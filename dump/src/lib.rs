@@ -1,11 +1,14 @@
 #![allow(clippy::type_complexity)]
 #![allow(clippy::wrong_self_convention)]
 
+use meilisearch_types::compression::DumpCompression;
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::keys::Key;
 use meilisearch_types::milli::update::IndexDocumentsMethod;
 use meilisearch_types::settings::Unchecked;
-use meilisearch_types::tasks::{Details, IndexSwap, KindWithContent, Status, Task, TaskId};
+use meilisearch_types::tasks::{
+    Details, IndexSwap, KindWithContent, MergeConflictPolicy, Status, Task, TaskId,
+};
 use meilisearch_types::InstanceUid;
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
@@ -65,6 +68,8 @@ pub struct TaskDump {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub canceled_by: Option<TaskId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retried_from: Option<TaskId>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub details: Option<Details>,
@@ -130,8 +135,30 @@ pub enum KindDump {
     DumpCreation {
         keys: Vec<Key>,
         instance_uid: Option<InstanceUid>,
+        #[serde(default)]
+        compression: DumpCompression,
     },
     SnapshotCreation,
+    DumpImport {
+        dump_uid: String,
+        #[serde(default)]
+        index_uid: Option<String>,
+    },
+    IndexExport,
+    IndexPreviewCreation {
+        source_index_uid: String,
+        settings: Box<meilisearch_types::settings::Settings<Unchecked>>,
+        #[serde(with = "time::serde::rfc3339")]
+        expires_at: OffsetDateTime,
+    },
+    IndexMerge {
+        source_index_uid: String,
+        on_conflict: MergeConflictPolicy,
+    },
+    IndexSplit {
+        source_index_uid: String,
+        filter_expr: serde_json::Value,
+    },
 }
 
 impl From<Task> for TaskDump {
@@ -142,6 +169,7 @@ impl From<Task> for TaskDump {
             status: task.status,
             kind: task.kind.into(),
             canceled_by: task.canceled_by,
+            retried_from: task.retried_from,
             details: task.details,
             error: task.error,
             enqueued_at: task.enqueued_at,
@@ -193,10 +221,26 @@ impl From<KindWithContent> for KindDump {
             KindWithContent::TaskDeletion { query, tasks } => {
                 KindDump::TasksDeletion { query, tasks }
             }
-            KindWithContent::DumpCreation { keys, instance_uid } => {
-                KindDump::DumpCreation { keys, instance_uid }
+            KindWithContent::DumpCreation { keys, instance_uid, compression } => {
+                KindDump::DumpCreation { keys, instance_uid, compression }
             }
             KindWithContent::SnapshotCreation => KindDump::SnapshotCreation,
+            KindWithContent::DumpImport { dump_uid, index_uid } => {
+                KindDump::DumpImport { dump_uid, index_uid }
+            }
+            KindWithContent::IndexExport { .. } => KindDump::IndexExport,
+            KindWithContent::IndexPreviewCreation {
+                source_index_uid,
+                settings,
+                expires_at,
+                ..
+            } => KindDump::IndexPreviewCreation { source_index_uid, settings, expires_at },
+            KindWithContent::IndexMerge { source_index_uid, on_conflict, .. } => {
+                KindDump::IndexMerge { source_index_uid, on_conflict }
+            }
+            KindWithContent::IndexSplit { source_index_uid, filter_expr, .. } => {
+                KindDump::IndexSplit { source_index_uid, filter_expr }
+            }
         }
     }
 }
@@ -204,11 +248,12 @@ impl From<KindWithContent> for KindDump {
 #[cfg(test)]
 pub(crate) mod test {
     use std::fs::File;
-    use std::io::Seek;
+    use std::io::{Read, Seek};
     use std::str::FromStr;
 
     use big_s::S;
     use maplit::{btreemap, btreeset};
+    use meilisearch_types::compression::{DumpCodec, DumpCompression};
     use meilisearch_types::facet_values_sort::FacetValuesSort;
     use meilisearch_types::features::RuntimeTogglableFeatures;
     use meilisearch_types::index_uid_pattern::IndexUidPattern;
@@ -262,6 +307,9 @@ pub(crate) mod test {
             sortable_attributes: Setting::Set(btreeset! { S("age") }),
             ranking_rules: Setting::NotSet,
             stop_words: Setting::NotSet,
+            stemming: Setting::NotSet,
+            decompounding: Setting::NotSet,
+            prefix_search: Setting::NotSet,
             non_separator_tokens: Setting::NotSet,
             separator_tokens: Setting::NotSet,
             dictionary: Setting::NotSet,
@@ -276,6 +324,13 @@ pub(crate) mod test {
                 ),
             }),
             pagination: Setting::NotSet,
+            query_plan_hints: Setting::NotSet,
+            search_exclusions: Setting::NotSet,
+            enrichment_webhook: Setting::NotSet,
+            unit_conversions: Setting::NotSet,
+            search_cutoff_ms: Setting::NotSet,
+            search_templates: Setting::NotSet,
+            localized_attributes: Setting::NotSet,
             embedders: Setting::NotSet,
             _kind: std::marker::PhantomData,
         };
@@ -296,6 +351,7 @@ pub(crate) mod test {
                         documents_count: 12,
                     },
                     canceled_by: None,
+                    retried_from: None,
                     details: Some(Details::DocumentAdditionOrUpdate {
                         received_documents: 12,
                         indexed_documents: Some(10),
@@ -319,6 +375,7 @@ pub(crate) mod test {
                         documents_count: 2,
                     },
                     canceled_by: None,
+                    retried_from: None,
                     details: Some(Details::DocumentAdditionOrUpdate {
                         received_documents: 2,
                         indexed_documents: None,
@@ -340,6 +397,7 @@ pub(crate) mod test {
                     status: Status::Enqueued,
                     kind: KindDump::IndexDeletion,
                     canceled_by: None,
+                    retried_from: None,
                     details: None,
                     error: None,
                     enqueued_at: datetime!(2022-11-15 0:00 UTC),
@@ -360,6 +418,9 @@ pub(crate) mod test {
                 actions: vec![Action::DocumentsAll],
                 indexes: vec![IndexUidPattern::from_str("doggos").unwrap()],
                 expires_at: Some(datetime!(4130-03-14 12:21 UTC)),
+                rate_limits: None,
+                allowed_ips: None,
+                displayed_attributes: None,
                 created_at: datetime!(1960-11-15 0:00 UTC),
                 updated_at: datetime!(2022-11-10 0:00 UTC),
             },
@@ -370,6 +431,9 @@ pub(crate) mod test {
                 actions: vec![Action::All],
                 indexes: vec![IndexUidPattern::all()],
                 expires_at: None,
+                rate_limits: None,
+                allowed_ips: None,
+                displayed_attributes: None,
                 created_at: datetime!(0000-01-01 00:01 UTC),
                 updated_at: datetime!(1964-05-04 17:25 UTC),
             },
@@ -380,6 +444,9 @@ pub(crate) mod test {
                 actions: vec![],
                 indexes: vec![],
                 expires_at: None,
+                rate_limits: None,
+                allowed_ips: None,
+                displayed_attributes: None,
                 created_at: datetime!(400-02-29 0:00 UTC),
                 updated_at: datetime!(1024-02-29 0:00 UTC),
             },
@@ -387,8 +454,14 @@ pub(crate) mod test {
     }
 
     pub fn create_test_dump() -> File {
+        create_test_dump_with_compression(DumpCompression::default())
+    }
+
+    fn create_test_dump_with_compression(compression: DumpCompression) -> File {
         let instance_uid = create_test_instance_uid();
-        let dump = DumpWriter::new(Some(instance_uid)).unwrap();
+        let mut file = tempfile::tempfile().unwrap();
+        let mut dump =
+            DumpWriter::with_compression(Some(instance_uid), &mut file, compression).unwrap();
 
         // ========== Adding an index
         let documents = create_test_documents();
@@ -429,9 +502,8 @@ pub(crate) mod test {
 
         dump.create_experimental_features(features).unwrap();
 
-        // create the dump
-        let mut file = tempfile::tempfile().unwrap();
-        dump.persist_to(&mut file).unwrap();
+        // terminate the dump
+        dump.finish().unwrap();
         file.rewind().unwrap();
 
         file
@@ -441,6 +513,31 @@ pub(crate) mod test {
         RuntimeTogglableFeatures { vector_store: true, ..Default::default() }
     }
 
+    #[test]
+    fn read_a_dump_of_every_codec() {
+        for compression in ["gzip:9", "zstd", "zstd:1", "none"] {
+            let compression: DumpCompression = compression.parse().unwrap();
+            let mut file = create_test_dump_with_compression(compression);
+            let mut magic = [0; 4];
+            file.read_exact(&mut magic).unwrap();
+            file.rewind().unwrap();
+            match compression.codec {
+                DumpCodec::Gzip => assert_eq!(magic[..2], [0x1f, 0x8b]),
+                DumpCodec::Zstd => assert_eq!(magic, [0x28, 0xb5, 0x2f, 0xfd]),
+                DumpCodec::None => assert_eq!(&magic, b"inst"),
+            }
+
+            let mut dump = DumpReader::open(&mut file).unwrap();
+            assert_eq!(dump.version(), Version::V6, "{compression}");
+            assert_eq!(dump.instance_uid().unwrap().unwrap(), create_test_instance_uid());
+            let mut indexes = dump.indexes().unwrap();
+            let mut index = indexes.next().unwrap().unwrap();
+            assert!(indexes.next().is_none());
+            let documents: Vec<_> = index.documents().unwrap().map(|d| d.unwrap()).collect();
+            assert_eq!(documents, create_test_documents(), "{compression}");
+        }
+    }
+
     #[test]
     fn test_creating_and_read_dump() {
         let mut file = create_test_dump();
@@ -123,10 +123,15 @@ impl CompatV5ToV6 {
                         },
                         v5::tasks::TaskContent::Dump { uid: _ } => {
                             // in v6 we compute the dump_uid from the started_at processing time
-                            v6::Kind::DumpCreation { keys: keys.clone(), instance_uid }
+                            v6::Kind::DumpCreation {
+                                keys: keys.clone(),
+                                instance_uid,
+                                compression: Default::default(),
+                            }
                         }
                     },
                     canceled_by: None,
+                    retried_from: None,
                     details: task_view.details.map(|details| match details {
                         v5::Details::DocumentAddition { received_documents, indexed_documents } => {
                             v6::Details::DocumentAdditionOrUpdate {
@@ -186,6 +191,9 @@ impl CompatV5ToV6 {
                     })
                     .collect(),
                 expires_at: key.expires_at,
+                rate_limits: None,
+                allowed_ips: None,
+                displayed_attributes: None,
                 created_at: key.created_at,
                 updated_at: key.updated_at,
             })
@@ -339,7 +347,14 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
                     v5::settings::Setting::NotSet => v6::Setting::NotSet,
                 }
             },
-            stop_words: settings.stop_words.into(),
+            stop_words: match settings.stop_words {
+                v5::Setting::Set(stop_words) => v6::Setting::Set(v6::StopWords::Global(stop_words)),
+                v5::Setting::Reset => v6::Setting::Reset,
+                v5::Setting::NotSet => v6::Setting::NotSet,
+            },
+            stemming: v6::Setting::NotSet,
+            decompounding: v6::Setting::NotSet,
+            prefix_search: v6::Setting::NotSet,
             non_separator_tokens: v6::Setting::NotSet,
             separator_tokens: v6::Setting::NotSet,
             dictionary: v6::Setting::NotSet,
@@ -359,6 +374,7 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
                     },
                     disable_on_words: typo.disable_on_words.into(),
                     disable_on_attributes: typo.disable_on_attributes.into(),
+                    disable_on_numbers: v6::Setting::NotSet,
                 }),
                 v5::Setting::Reset => v6::Setting::Reset,
                 v5::Setting::NotSet => v6::Setting::NotSet,
@@ -378,6 +394,13 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
                 v5::Setting::Reset => v6::Setting::Reset,
                 v5::Setting::NotSet => v6::Setting::NotSet,
             },
+            query_plan_hints: v6::Setting::NotSet,
+            search_exclusions: v6::Setting::NotSet,
+            enrichment_webhook: v6::Setting::NotSet,
+            unit_conversions: v6::Setting::NotSet,
+            search_cutoff_ms: v6::Setting::NotSet,
+            search_templates: v6::Setting::NotSet,
+            localized_attributes: v6::Setting::NotSet,
             embedders: v6::Setting::NotSet,
             _kind: std::marker::PhantomData,
         }
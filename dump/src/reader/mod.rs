@@ -1,5 +1,5 @@
 use std::fs::File;
-use std::io::{BufReader, Read};
+use std::io::{BufRead, BufReader, Read};
 
 use flate2::bufread::GzDecoder;
 use serde::Deserialize;
@@ -20,6 +20,9 @@ mod v4;
 mod v5;
 mod v6;
 
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
 pub type Document = serde_json::Map<String, serde_json::Value>;
 pub type UpdateFile = dyn Iterator<Item = Result<Document>>;
 
@@ -32,9 +35,17 @@ impl DumpReader {
     pub fn open(dump: impl Read) -> Result<DumpReader> {
         let path = TempDir::new()?;
         let mut dump = BufReader::new(dump);
-        let gz = GzDecoder::new(&mut dump);
-        let mut archive = tar::Archive::new(gz);
-        archive.unpack(path.path())?;
+
+        // The codec of the dump is recognized by its magic number, the dumps were only compressed
+        // with gzip before it could be chosen.
+        let magic = dump.fill_buf()?;
+        if magic.starts_with(ZSTD_MAGIC) {
+            tar::Archive::new(zstd::Decoder::with_buffer(dump)?).unpack(path.path())?;
+        } else if magic.starts_with(GZIP_MAGIC) || magic.is_empty() {
+            tar::Archive::new(GzDecoder::new(dump)).unpack(path.path())?;
+        } else {
+            tar::Archive::new(dump).unpack(path.path())?;
+        }
 
         #[derive(Deserialize)]
         #[serde(rename_all = "camelCase")]
@@ -33,6 +33,7 @@ pub type TypoTolerance = meilisearch_types::settings::TypoSettings;
 pub type MinWordSizeForTypos = meilisearch_types::settings::MinWordSizeTyposSetting;
 pub type FacetingSettings = meilisearch_types::settings::FacetingSettings;
 pub type PaginationSettings = meilisearch_types::settings::PaginationSettings;
+pub type StopWords = meilisearch_types::settings::StopWords;
 
 // everything related to the api keys
 pub type Action = meilisearch_types::keys::Action;
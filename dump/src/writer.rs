@@ -1,33 +1,97 @@
-use std::fs::{self, File};
-use std::io::{BufWriter, Write};
-use std::path::PathBuf;
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Seek, Write};
+use std::path::{Path, PathBuf};
 
 use flate2::write::GzEncoder;
 use flate2::Compression;
+use meilisearch_types::compression::{DumpCodec, DumpCompression};
 use meilisearch_types::features::RuntimeTogglableFeatures;
 use meilisearch_types::keys::Key;
 use meilisearch_types::settings::{Checked, Settings};
 use serde_json::{Map, Value};
-use tempfile::TempDir;
+use tar::{EntryType, Header};
 use time::OffsetDateTime;
 use uuid::Uuid;
 
 use crate::reader::Document;
 use crate::{IndexMetadata, Metadata, Result, TaskDump, CURRENT_DUMP_VERSION};
 
-pub struct DumpWriter {
-    dir: TempDir,
+type Tarball<W> = tar::Builder<Encoder<W>>;
+
+/// Compresses the tarball with the codec selected when creating the dump.
+pub(crate) enum Encoder<W: Write> {
+    Gzip(GzEncoder<W>),
+    Zstd(zstd::Encoder<'static, W>),
+    None(W),
+}
+
+impl<W: Write> Encoder<W> {
+    fn new(compression: DumpCompression, writer: W) -> Result<Self> {
+        Ok(match compression.codec {
+            DumpCodec::Gzip => {
+                let level = compression.level.map_or_else(Compression::default, Compression::new);
+                Encoder::Gzip(GzEncoder::new(writer, level))
+            }
+            DumpCodec::Zstd => {
+                let level = compression.level.map_or(zstd::DEFAULT_COMPRESSION_LEVEL, |l| l as i32);
+                Encoder::Zstd(zstd::Encoder::new(writer, level)?)
+            }
+            DumpCodec::None => Encoder::None(writer),
+        })
+    }
+
+    fn finish(self) -> io::Result<W> {
+        match self {
+            Encoder::Gzip(encoder) => encoder.finish(),
+            Encoder::Zstd(encoder) => encoder.finish(),
+            Encoder::None(writer) => Ok(writer),
+        }
+    }
+}
+
+impl<W: Write> Write for Encoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Encoder::Gzip(encoder) => encoder.write(buf),
+            Encoder::Zstd(encoder) => encoder.write(buf),
+            Encoder::None(writer) => writer.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Encoder::Gzip(encoder) => encoder.flush(),
+            Encoder::Zstd(encoder) => encoder.flush(),
+            Encoder::None(writer) => writer.flush(),
+        }
+    }
 }
 
-impl DumpWriter {
-    pub fn new(instance_uuid: Option<Uuid>) -> Result<DumpWriter> {
-        let dir = TempDir::new()?;
+/// Writes a dump directly into its compressed tarball.
+///
+/// A tarball entry must know its size before its content is written, so the files that grow
+/// while the dump is created, like the documents of an index or the task queue, are written in a
+/// temporary file and appended to the tarball as soon as they are complete. The scratch space
+/// needed to create a dump is thus the size of its largest file, not the size of the whole dump.
+pub struct DumpWriter<W: Write> {
+    tarball: Tarball<W>,
+}
+
+impl<W: Write> DumpWriter<W> {
+    pub fn new(instance_uuid: Option<Uuid>, writer: W) -> Result<DumpWriter<W>> {
+        Self::with_compression(instance_uuid, writer, DumpCompression::default())
+    }
+
+    pub fn with_compression(
+        instance_uuid: Option<Uuid>,
+        writer: W,
+        compression: DumpCompression,
+    ) -> Result<DumpWriter<W>> {
+        let mut tarball = tar::Builder::new(Encoder::new(compression, writer)?);
 
         if let Some(instance_uuid) = instance_uuid {
-            fs::write(
-                dir.path().join("instance_uid.uuid"),
-                instance_uuid.as_hyphenated().to_string(),
-            )?;
+            let instance_uuid = instance_uuid.as_hyphenated().to_string();
+            append_bytes(&mut tarball, "instance_uid.uuid", instance_uuid.as_bytes())?;
         }
 
         let metadata = Metadata {
@@ -35,52 +99,98 @@ impl DumpWriter {
             db_version: env!("CARGO_PKG_VERSION").to_string(),
             dump_date: OffsetDateTime::now_utc(),
         };
-        fs::write(dir.path().join("metadata.json"), serde_json::to_string(&metadata)?)?;
+        append_bytes(&mut tarball, "metadata.json", &serde_json::to_vec(&metadata)?)?;
 
-        std::fs::create_dir(dir.path().join("indexes"))?;
+        append_dir(&mut tarball, "indexes")?;
 
-        Ok(DumpWriter { dir })
+        Ok(DumpWriter { tarball })
     }
 
-    pub fn create_index(&self, index_name: &str, metadata: &IndexMetadata) -> Result<IndexWriter> {
-        IndexWriter::new(self.dir.path().join("indexes").join(index_name), metadata)
+    pub fn create_index(
+        &mut self,
+        index_name: &str,
+        metadata: &IndexMetadata,
+    ) -> Result<IndexWriter<'_, W>> {
+        IndexWriter::new(&mut self.tarball, Path::new("indexes").join(index_name), metadata)
     }
 
-    pub fn create_keys(&self) -> Result<KeyWriter> {
-        KeyWriter::new(self.dir.path().to_path_buf())
+    pub fn create_keys(&mut self) -> Result<KeyWriter<'_, W>> {
+        KeyWriter::new(&mut self.tarball)
     }
 
-    pub fn create_tasks_queue(&self) -> Result<TaskWriter> {
-        TaskWriter::new(self.dir.path().join("tasks"))
+    pub fn create_tasks_queue(&mut self) -> Result<TaskWriter<'_, W>> {
+        TaskWriter::new(&mut self.tarball)
     }
 
-    pub fn create_experimental_features(&self, features: RuntimeTogglableFeatures) -> Result<()> {
-        Ok(std::fs::write(
-            self.dir.path().join("experimental-features.json"),
-            serde_json::to_string(&features)?,
-        )?)
+    pub fn create_experimental_features(
+        &mut self,
+        features: RuntimeTogglableFeatures,
+    ) -> Result<()> {
+        append_bytes(
+            &mut self.tarball,
+            "experimental-features.json",
+            &serde_json::to_vec(&features)?,
+        )
     }
 
-    pub fn persist_to(self, mut writer: impl Write) -> Result<()> {
-        let gz_encoder = GzEncoder::new(&mut writer, Compression::default());
-        let mut tar_encoder = tar::Builder::new(gz_encoder);
-        tar_encoder.append_dir_all(".", self.dir.path())?;
-        let gz_encoder = tar_encoder.into_inner()?;
-        gz_encoder.finish()?;
+    /// Terminates the tarball and returns the writer it was written into.
+    pub fn finish(self) -> Result<W> {
+        let encoder = self.tarball.into_inner()?;
+        let mut writer = encoder.finish()?;
         writer.flush()?;
 
-        Ok(())
+        Ok(writer)
     }
 }
 
-pub struct KeyWriter {
+fn header(entry_type: EntryType, mode: u32, size: u64) -> Header {
+    let mut header = Header::new_gnu();
+    header.set_entry_type(entry_type);
+    header.set_mode(mode);
+    header.set_size(size);
+    header.set_mtime(OffsetDateTime::now_utc().unix_timestamp().max(0) as u64);
+    header
+}
+
+fn append_dir<W: Write>(tarball: &mut Tarball<W>, path: impl AsRef<Path>) -> Result<()> {
+    let mut header = header(EntryType::Directory, 0o755, 0);
+    tarball.append_data(&mut header, path, io::empty())?;
+    Ok(())
+}
+
+fn append_bytes<W: Write>(
+    tarball: &mut Tarball<W>,
+    path: impl AsRef<Path>,
+    bytes: &[u8],
+) -> Result<()> {
+    let mut header = header(EntryType::Regular, 0o644, bytes.len() as u64);
+    tarball.append_data(&mut header, path, bytes)?;
+    Ok(())
+}
+
+/// Appends the content of a temporary file to the tarball, the file is deleted once dropped.
+fn append_temp_file<W: Write>(
+    tarball: &mut Tarball<W>,
+    path: impl AsRef<Path>,
+    file: BufWriter<File>,
+) -> Result<()> {
+    let mut file = file.into_inner().map_err(|e| e.into_error())?;
+    let size = file.stream_position()?;
+    file.rewind()?;
+    let mut header = header(EntryType::Regular, 0o644, size);
+    tarball.append_data(&mut header, path, file.take(size))?;
+    Ok(())
+}
+
+pub struct KeyWriter<'a, W: Write> {
+    tarball: &'a mut Tarball<W>,
     keys: BufWriter<File>,
 }
 
-impl KeyWriter {
-    pub(crate) fn new(path: PathBuf) -> Result<Self> {
-        let keys = File::create(path.join("keys.jsonl"))?;
-        Ok(KeyWriter { keys: BufWriter::new(keys) })
+impl<'a, W: Write> KeyWriter<'a, W> {
+    pub(crate) fn new(tarball: &'a mut Tarball<W>) -> Result<Self> {
+        let keys = tempfile::tempfile()?;
+        Ok(KeyWriter { tarball, keys: BufWriter::new(keys) })
     }
 
     pub fn push_key(&mut self, key: &Key) -> Result<()> {
@@ -89,89 +199,101 @@ impl KeyWriter {
         Ok(())
     }
 
-    pub fn flush(mut self) -> Result<()> {
-        self.keys.flush()?;
-        Ok(())
+    pub fn flush(self) -> Result<()> {
+        append_temp_file(self.tarball, "keys.jsonl", self.keys)
     }
 }
 
-pub struct TaskWriter {
+pub struct TaskWriter<'a, W: Write> {
+    tarball: &'a mut Tarball<W>,
     queue: BufWriter<File>,
-    update_files: PathBuf,
+    /// The update file of the last pushed task, appended to the tarball when the next task is pushed.
+    update_file: Option<(String, BufWriter<File>)>,
 }
 
-impl TaskWriter {
-    pub(crate) fn new(path: PathBuf) -> Result<Self> {
-        std::fs::create_dir(&path)?;
-
-        let queue = File::create(path.join("queue.jsonl"))?;
-        let update_files = path.join("update_files");
-        std::fs::create_dir(&update_files)?;
+impl<'a, W: Write> TaskWriter<'a, W> {
+    pub(crate) fn new(tarball: &'a mut Tarball<W>) -> Result<Self> {
+        append_dir(tarball, "tasks")?;
+        append_dir(tarball, "tasks/update_files")?;
 
-        Ok(TaskWriter { queue: BufWriter::new(queue), update_files })
+        let queue = tempfile::tempfile()?;
+        Ok(TaskWriter { tarball, queue: BufWriter::new(queue), update_file: None })
     }
 
     /// Pushes tasks in the dump.
     /// If the tasks has an associated `update_file` it'll use the `task_id` as its name.
-    pub fn push_task(&mut self, task: &TaskDump) -> Result<UpdateFile> {
+    pub fn push_task(&mut self, task: &TaskDump) -> Result<UpdateFile<'_>> {
+        self.append_update_file()?;
+
         self.queue.write_all(&serde_json::to_vec(task)?)?;
         self.queue.write_all(b"\n")?;
 
-        Ok(UpdateFile::new(self.update_files.join(format!("{}.jsonl", task.uid))))
+        Ok(UpdateFile::new(format!("tasks/update_files/{}.jsonl", task.uid), &mut self.update_file))
+    }
+
+    fn append_update_file(&mut self) -> Result<()> {
+        match self.update_file.take() {
+            Some((path, file)) => append_temp_file(self.tarball, path, file),
+            None => Ok(()),
+        }
     }
 
     pub fn flush(mut self) -> Result<()> {
-        self.queue.flush()?;
-        Ok(())
+        self.append_update_file()?;
+        append_temp_file(self.tarball, "tasks/queue.jsonl", self.queue)
     }
 }
 
-pub struct UpdateFile {
-    path: PathBuf,
-    writer: Option<BufWriter<File>>,
+pub struct UpdateFile<'a> {
+    path: String,
+    writer: &'a mut Option<(String, BufWriter<File>)>,
 }
 
-impl UpdateFile {
-    pub(crate) fn new(path: PathBuf) -> UpdateFile {
-        UpdateFile { path, writer: None }
+impl<'a> UpdateFile<'a> {
+    pub(crate) fn new(
+        path: String,
+        writer: &'a mut Option<(String, BufWriter<File>)>,
+    ) -> UpdateFile<'a> {
+        UpdateFile { path, writer }
     }
 
     pub fn push_document(&mut self, document: &Document) -> Result<()> {
-        if let Some(writer) = self.writer.as_mut() {
+        if let Some((_, writer)) = self.writer.as_mut() {
             writer.write_all(&serde_json::to_vec(document)?)?;
             writer.write_all(b"\n")?;
         } else {
-            let file = File::create(&self.path).unwrap();
-            self.writer = Some(BufWriter::new(file));
+            let file = tempfile::tempfile()?;
+            *self.writer = Some((self.path.clone(), BufWriter::new(file)));
             self.push_document(document)?;
         }
         Ok(())
     }
 
     pub fn flush(self) -> Result<()> {
-        if let Some(mut writer) = self.writer {
+        if let Some((_, writer)) = self.writer {
             writer.flush()?;
         }
         Ok(())
     }
 }
 
-pub struct IndexWriter {
+pub struct IndexWriter<'a, W: Write> {
+    tarball: &'a mut Tarball<W>,
+    path: PathBuf,
     documents: BufWriter<File>,
-    settings: File,
 }
 
-impl IndexWriter {
-    pub(self) fn new(path: PathBuf, metadata: &IndexMetadata) -> Result<Self> {
-        std::fs::create_dir(&path)?;
-
-        let metadata_file = File::create(path.join("metadata.json"))?;
-        serde_json::to_writer(metadata_file, metadata)?;
-
-        let documents = File::create(path.join("documents.jsonl"))?;
-        let settings = File::create(path.join("settings.json"))?;
-
-        Ok(IndexWriter { documents: BufWriter::new(documents), settings })
+impl<'a, W: Write> IndexWriter<'a, W> {
+    pub(self) fn new(
+        tarball: &'a mut Tarball<W>,
+        path: PathBuf,
+        metadata: &IndexMetadata,
+    ) -> Result<Self> {
+        append_dir(tarball, &path)?;
+        append_bytes(tarball, path.join("metadata.json"), &serde_json::to_vec(metadata)?)?;
+
+        let documents = tempfile::tempfile()?;
+        Ok(IndexWriter { tarball, path, documents: BufWriter::new(documents) })
     }
 
     pub fn push_document(&mut self, document: &Map<String, Value>) -> Result<()> {
@@ -185,15 +307,18 @@ impl IndexWriter {
         Ok(())
     }
 
-    pub fn settings(mut self, settings: &Settings<Checked>) -> Result<()> {
-        self.settings.write_all(&serde_json::to_vec(&settings)?)?;
-        Ok(())
+    /// Writes the settings of the index, the documents are appended to the tarball at this point
+    /// so no document can be pushed afterward.
+    pub fn settings(self, settings: &Settings<Checked>) -> Result<()> {
+        append_temp_file(self.tarball, self.path.join("documents.jsonl"), self.documents)?;
+        append_bytes(self.tarball, self.path.join("settings.json"), &serde_json::to_vec(&settings)?)
     }
 }
 
 #[cfg(test)]
 pub(crate) mod test {
     use std::fmt::Write;
+    use std::fs;
     use std::io::BufReader;
     use std::path::Path;
     use std::str::FromStr;
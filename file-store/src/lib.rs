@@ -76,6 +76,17 @@ impl FileStore {
         self.path.join(uuid.to_string())
     }
 
+    /// Creates a new update file with the content of the file at `src` and returns its Uuid.
+    /// The content is shared with a hard link when possible instead of being copied.
+    pub fn link_update(&self, src: impl AsRef<Path>) -> Result<Uuid> {
+        let uuid = Uuid::new_v4();
+        let dst = self.get_update_path(uuid);
+        if std::fs::hard_link(&src, &dst).is_err() {
+            std::fs::copy(&src, &dst)?;
+        }
+        Ok(uuid)
+    }
+
     /// Copies the content of the update file pointed to by `uuid` to the `dst` directory.
     pub fn snapshot(&self, uuid: Uuid, dst: impl AsRef<Path>) -> Result<()> {
         let src = self.path.join(uuid.to_string());
@@ -2,6 +2,7 @@
 //!
 //! ```text
 //! condition      = value ("==" | ">" ...) value
+//! approximately  = value "~=" value "+-" value
 //! to             = value value TO value
 //! ```
 
@@ -9,10 +10,11 @@ use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::character::complete::multispace1;
 use nom::combinator::cut;
-use nom::sequence::{terminated, tuple};
+use nom::sequence::{preceded, terminated, tuple};
 use Condition::*;
 
-use crate::{parse_value, FilterCondition, IResult, Span, Token};
+use crate::error::cut_with_err;
+use crate::{parse_value, Error, ErrorKind, FilterCondition, IResult, Span, Token};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Condition<'a> {
@@ -26,6 +28,7 @@ pub enum Condition<'a> {
     LowerThan(Token<'a>),
     LowerThanOrEqual(Token<'a>),
     Between { from: Token<'a>, to: Token<'a> },
+    Approximately { value: Token<'a>, tolerance: Token<'a> },
 }
 
 /// condition      = value ("==" | ">" ...) value
@@ -46,6 +49,17 @@ pub fn parse_condition(input: Span) -> IResult<FilterCondition> {
     Ok((input, condition))
 }
 
+/// approximately  = value "~=" value "+-" value
+pub fn parse_approximately(input: Span) -> IResult<FilterCondition> {
+    let (input, (fid, _, value)) = tuple((parse_value, tag("~="), cut(parse_value)))(input)?;
+    let (input, tolerance) = preceded(
+        cut_with_err(tag("+-"), |_| Error::new_from_kind(input, ErrorKind::ExpectedTolerance)),
+        cut(parse_value),
+    )(input)?;
+
+    Ok((input, FilterCondition::Condition { fid, op: Approximately { value, tolerance } }))
+}
+
 /// null          = value "IS" WS+ "NULL"
 pub fn parse_is_null(input: Span) -> IResult<FilterCondition> {
     let (input, key) = parse_value(input)?;
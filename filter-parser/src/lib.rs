@@ -6,9 +6,10 @@
 //! or             = and ("OR" WS+ and)*
 //! and            = not ("AND" WS+ not)*
 //! not            = ("NOT" WS+ not) | primary
-//! primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | in | condition | exists | not_exists | to
+//! primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | in | condition | approximately | exists | not_exists | to
 //! in             = value "IN" WS* "[" value_list "]"
 //! condition      = value ("=" | "!=" | ">" | ">=" | "<" | "<=") value
+//! approximately  = value "~=" value "+-" value
 //! exists         = value "EXISTS"
 //! not_exists     = value "NOT" WS+ "EXISTS"
 //! to             = value value "TO" WS+ value
@@ -16,9 +17,10 @@
 //! value_list     = (value ("," value)* ","?)?
 //! singleQuoted   = "'" .* all but quotes "'"
 //! doubleQuoted   = "\"" .* all but double quotes "\""
-//! word           = (alphanumeric | _ | - | .)+
+//! word           = (alphanumeric | _ | - | . | :)+
 //! geoRadius      = "_geoRadius(" WS* float WS* "," WS* float WS* "," float WS* ")"
 //! geoBoundingBox = "_geoBoundingBox([" WS * float WS* "," WS* float WS* "], [" WS* float WS* "," WS* float WS* "]")
+//! plugin         = "_plugin(" value "," value ")"
 //! ```
 //!
 //! Other BNF grammar used to handle some specific errors:
@@ -48,8 +50,8 @@ use std::fmt::Debug;
 
 pub use condition::{parse_condition, parse_to, Condition};
 use condition::{
-    parse_exists, parse_is_empty, parse_is_not_empty, parse_is_not_null, parse_is_null,
-    parse_not_exists,
+    parse_approximately, parse_exists, parse_is_empty, parse_is_not_empty, parse_is_not_null,
+    parse_is_null, parse_not_exists,
 };
 use error::{cut_with_err, ExpectedValueKind, NomErrorExt};
 pub use error::{Error, ErrorKind};
@@ -71,6 +73,11 @@ type IResult<'a, Ret> = nom::IResult<Span<'a>, Ret, Error<'a>>;
 
 const MAX_FILTER_DEPTH: usize = 200;
 
+/// The deepest [`FilterCondition::depth`] a filter expression can be parsed with: every level of
+/// parentheses is parsed by four nested functions limited to [`MAX_FILTER_DEPTH`] and can hold an
+/// `OR` of `AND`s.
+pub const MAX_FILTER_CONDITION_DEPTH: usize = MAX_FILTER_DEPTH / 4 * 2;
+
 #[derive(Debug, Clone, Eq)]
 pub struct Token<'a> {
     /// The token in the original input, it should be used when possible.
@@ -145,6 +152,7 @@ pub enum FilterCondition<'a> {
     And(Vec<Self>),
     GeoLowerThan { point: [Token<'a>; 2], radius: Token<'a> },
     GeoBoundingBox { top_right_point: [Token<'a>; 2], bottom_left_point: [Token<'a>; 2] },
+    Plugin { name: Token<'a>, argument: Token<'a> },
 }
 
 impl<'a> FilterCondition<'a> {
@@ -175,6 +183,67 @@ impl<'a> FilterCondition<'a> {
         }
     }
 
+    /// Returns the number of nested `OR` and `AND` levels of the filter, a single condition has a depth of 0.
+    pub fn depth(&self) -> usize {
+        match self {
+            FilterCondition::Not(filter) => filter.depth(),
+            FilterCondition::Or(subfilters) | FilterCondition::And(subfilters) => {
+                1 + subfilters.iter().map(|f| f.depth()).max().unwrap_or(0)
+            }
+            _ => 0,
+        }
+    }
+
+    /// Returns the number of conditions the filter is made of, every value of an `IN` counts as a condition.
+    pub fn complexity(&self) -> usize {
+        match self {
+            FilterCondition::Not(filter) => filter.complexity(),
+            FilterCondition::In { els, .. } => els.len().max(1),
+            FilterCondition::Or(subfilters) | FilterCondition::And(subfilters) => {
+                subfilters.iter().map(|f| f.complexity()).sum()
+            }
+            _ => 1,
+        }
+    }
+
+    /// Returns the names of the fields the filter is made of, the geo conditions use the `_geo` field.
+    pub fn fields(&self) -> Vec<&str> {
+        match self {
+            FilterCondition::Not(filter) => filter.fields(),
+            FilterCondition::Condition { fid, .. } | FilterCondition::In { fid, .. } => {
+                vec![fid.value()]
+            }
+            FilterCondition::Or(subfilters) | FilterCondition::And(subfilters) => {
+                subfilters.iter().flat_map(|f| f.fields()).collect()
+            }
+            FilterCondition::GeoLowerThan { .. } | FilterCondition::GeoBoundingBox { .. } => {
+                vec!["_geo"]
+            }
+            FilterCondition::Plugin { .. } => vec![],
+        }
+    }
+
+    /// Returns the filter without the clauses of its top-level `AND` made only of the specified
+    /// field, `None` if no clause remains.
+    pub fn without_field(&self, field: &str) -> Option<Self> {
+        match self {
+            FilterCondition::And(subfilters) => {
+                let mut subfilters: Vec<_> =
+                    subfilters.iter().filter_map(|f| f.without_field(field)).collect();
+                match subfilters.len() {
+                    0 => None,
+                    1 => subfilters.pop(),
+                    _ => Some(FilterCondition::And(subfilters)),
+                }
+            }
+            filter => {
+                let fields = filter.fields();
+                let only_field = !fields.is_empty() && fields.iter().all(|f| *f == field);
+                (!only_field).then(|| filter.clone())
+            }
+        }
+    }
+
     pub fn parse(input: &'a str) -> Result<Option<Self>, Error> {
         if input.trim().is_empty() {
             return Ok(None);
@@ -371,6 +440,29 @@ fn parse_geo_bounding_box(input: Span) -> IResult<FilterCondition> {
     Ok((input, res))
 }
 
+/// plugin      = WS* "_plugin(" value "," value ")"
+/// If we parse `_plugin` we MUST parse the rest of the expression.
+#[cfg(feature = "plugins")]
+fn parse_plugin(input: Span) -> IResult<FilterCondition> {
+    // we want to allow space BEFORE the _plugin but not after
+    let parsed = preceded(
+        tuple((multispace0, word_exact("_plugin"))),
+        // if we were able to parse `_plugin` and can't parse the rest of the input we return a failure
+        cut(delimited(char('('), tuple((parse_value, preceded(tag(","), parse_value))), char(')'))),
+    )(input)
+    .map_err(|e| e.map(|_| Error::new_from_kind(input, ErrorKind::Plugin)));
+
+    let (input, (name, argument)) = parsed?;
+
+    Ok((input, FilterCondition::Plugin { name, argument }))
+}
+
+/// Without the `plugins` feature `_plugin` is not a valid filter expression.
+#[cfg(not(feature = "plugins"))]
+fn parse_plugin(input: Span) -> IResult<FilterCondition> {
+    Err(nom::Err::Error(Error::new_from_kind(input, ErrorKind::InvalidPrimary)))
+}
+
 /// geoPoint      = WS* "_geoPoint(float WS* "," WS* float WS* "," WS* float)
 fn parse_geo_point(input: Span) -> IResult<FilterCondition> {
     // we want to forbid space BEFORE the _geoPoint but not after
@@ -442,9 +534,11 @@ fn parse_primary(input: Span, depth: usize) -> IResult<FilterCondition> {
         ),
         parse_geo_radius,
         parse_geo_bounding_box,
+        parse_plugin,
         parse_in,
         parse_not_in,
         parse_condition,
+        parse_approximately,
         parse_is_null,
         parse_is_not_null,
         parse_is_empty,
@@ -518,6 +612,9 @@ impl<'a> std::fmt::Display for FilterCondition<'a> {
                     bottom_right_point[1]
                 )
             }
+            FilterCondition::Plugin { name, argument } => {
+                write!(f, "_plugin({name}, {argument})")
+            }
         }
     }
 }
@@ -534,6 +631,9 @@ impl<'a> std::fmt::Display for Condition<'a> {
             Condition::LowerThan(token) => write!(f, "< {token}"),
             Condition::LowerThanOrEqual(token) => write!(f, "<= {token}"),
             Condition::Between { from, to } => write!(f, "{from} TO {to}"),
+            Condition::Approximately { value, tolerance } => {
+                write!(f, "~= {value} +- {tolerance}")
+            }
         }
     }
 }
@@ -614,6 +714,14 @@ pub mod tests {
         insta::assert_display_snapshot!(p("subscribers <= 1000"), @"{subscribers} <= {1000}");
         insta::assert_display_snapshot!(p("subscribers 100 TO 1000"), @"{subscribers} {100} TO {1000}");
 
+        // Test approximately
+        insta::assert_display_snapshot!(p("temperature ~= 10.5 +- 0.2"), @"{temperature} ~= {10.5} +- {0.2}");
+        insta::assert_display_snapshot!(p("NOT temperature~=10.5+-0.2"), @"NOT ({temperature} ~= {10.5} +- {0.2})");
+
+        // Test fields converted into a unit
+        insta::assert_display_snapshot!(p("price:usd < 100"), @"{price:usd} < {100}");
+        insta::assert_display_snapshot!(p("price:usd 10 TO 100"), @"{price:usd} {10} TO {100}");
+
         // Test NOT
         insta::assert_display_snapshot!(p("NOT subscribers < 1000"), @"NOT ({subscribers} < {1000})");
         insta::assert_display_snapshot!(p("NOT subscribers 100 TO 1000"), @"NOT ({subscribers} {100} TO {1000})");
@@ -710,7 +818,7 @@ pub mod tests {
         "###);
 
         insta::assert_display_snapshot!(p("'OR'"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `\'OR\'`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `~=`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `\'OR\'`.
         1:5 'OR'
         "###);
 
@@ -720,12 +828,12 @@ pub mod tests {
         "###);
 
         insta::assert_display_snapshot!(p("channel Ponce"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `channel Ponce`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `~=`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `channel Ponce`.
         1:14 channel Ponce
         "###);
 
         insta::assert_display_snapshot!(p("channel = Ponce OR"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` but instead got nothing.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `~=`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` but instead got nothing.
         19:19 channel = Ponce OR
         "###);
 
@@ -810,12 +918,12 @@ pub mod tests {
         "###);
 
         insta::assert_display_snapshot!(p("colour NOT EXIST"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `colour NOT EXIST`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `~=`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `colour NOT EXIST`.
         1:17 colour NOT EXIST
         "###);
 
         insta::assert_display_snapshot!(p("subscribers 100 TO1000"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `subscribers 100 TO1000`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `~=`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `subscribers 100 TO1000`.
         1:23 subscribers 100 TO1000
         "###);
 
@@ -829,6 +937,11 @@ pub mod tests {
         11:23 colour IN blue, green]
         "###);
 
+        insta::assert_display_snapshot!(p("temperature ~= 10.5 0.2"), @r###"
+        Expected `+-` followed by a tolerance after the value of the `~=` operation.
+        21:24 temperature ~= 10.5 0.2
+        "###);
+
         insta::assert_display_snapshot!(p("colour IN [blue, green, 'blue' > 2]"), @r###"
         Expected only comma-separated field names inside `IN[..]` but instead found `> 2]`.
         32:36 colour IN [blue, green, 'blue' > 2]
@@ -878,35 +991,35 @@ pub mod tests {
         "###);
 
         insta::assert_display_snapshot!(p(r#"value NULL"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `value NULL`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `~=`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `value NULL`.
         1:11 value NULL
         "###);
         insta::assert_display_snapshot!(p(r#"value NOT NULL"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `value NOT NULL`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `~=`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `value NOT NULL`.
         1:15 value NOT NULL
         "###);
         insta::assert_display_snapshot!(p(r#"value EMPTY"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `value EMPTY`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `~=`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `value EMPTY`.
         1:12 value EMPTY
         "###);
         insta::assert_display_snapshot!(p(r#"value NOT EMPTY"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `value NOT EMPTY`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `~=`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `value NOT EMPTY`.
         1:16 value NOT EMPTY
         "###);
         insta::assert_display_snapshot!(p(r#"value IS"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `value IS`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `~=`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `value IS`.
         1:9 value IS
         "###);
         insta::assert_display_snapshot!(p(r#"value IS NOT"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `value IS NOT`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `~=`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `value IS NOT`.
         1:13 value IS NOT
         "###);
         insta::assert_display_snapshot!(p(r#"value IS EXISTS"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `value IS EXISTS`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `~=`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `value IS EXISTS`.
         1:16 value IS EXISTS
         "###);
         insta::assert_display_snapshot!(p(r#"value IS NOT EXISTS"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `value IS NOT EXISTS`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `~=`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `value IS NOT EXISTS`.
         1:20 value IS NOT EXISTS
         "###);
     }
@@ -926,6 +1039,66 @@ pub mod tests {
         assert!(filter.token_at_depth(3).is_none());
     }
 
+    #[test]
+    fn complexity() {
+        let filter = FilterCondition::parse("channel = ponce").unwrap().unwrap();
+        assert_eq!(filter.depth(), 0);
+        assert_eq!(filter.complexity(), 1);
+
+        let filter =
+            FilterCondition::parse("channel = ponce OR NOT (dog = bernese AND age IN [1, 2, 3])")
+                .unwrap()
+                .unwrap();
+        assert_eq!(filter.depth(), 2);
+        assert_eq!(filter.complexity(), 5);
+
+        let filter = FilterCondition::parse("account_ids IN []").unwrap().unwrap();
+        assert_eq!(filter.depth(), 0);
+        assert_eq!(filter.complexity(), 1);
+    }
+
+    #[test]
+    fn deepest_filter_expression() {
+        // every level of parentheses holds an `OR` of `AND`s
+        let nested = |levels: usize| {
+            (1..levels).fold(String::from("a = 1 OR b = 1 AND c = 1"), |inner, _| {
+                format!("a = 1 OR b = 1 AND ({inner})")
+            })
+        };
+
+        let filter =
+            FilterCondition::parse(&nested(MAX_FILTER_CONDITION_DEPTH / 2)).unwrap().unwrap();
+        assert_eq!(filter.depth(), MAX_FILTER_CONDITION_DEPTH);
+        assert!(FilterCondition::parse(&nested(MAX_FILTER_CONDITION_DEPTH / 2 + 1)).is_err());
+    }
+
+    #[test]
+    fn fields() {
+        let filter = FilterCondition::parse(
+            "channel = ponce OR NOT (dog = bernese AND age IN [1, 2, 3]) OR _geoRadius(12, 13, 14)",
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(filter.fields(), vec!["channel", "dog", "age", "_geo"]);
+    }
+
+    #[test]
+    fn without_field() {
+        let filter = FilterCondition::parse(
+            "(color = red OR color = blue) AND size = M AND (color = red OR size = S) AND NOT color = green",
+        )
+        .unwrap()
+        .unwrap();
+        let without_color = filter.without_field("color").unwrap();
+        assert_eq!(without_color.fields(), vec!["size", "color", "size"]);
+        let without_size = filter.without_field("size").unwrap();
+        assert_eq!(without_size.fields(), vec!["color", "color", "color", "size", "color"]);
+
+        let filter = FilterCondition::parse("color = red OR color = blue").unwrap().unwrap();
+        assert_eq!(filter.without_field("color"), None);
+        assert_eq!(filter.without_field("size"), Some(filter.clone()));
+    }
+
     #[test]
     fn token_from_str() {
         let s = "test string that should not be parsed";
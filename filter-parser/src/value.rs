@@ -51,7 +51,7 @@ fn quoted_by(quote: char, input: Span) -> IResult<Token> {
     ))
 }
 
-// word           = (alphanumeric | _ | - | .)+    except for reserved keywords
+// word           = (alphanumeric | _ | - | . | :)+    except for reserved keywords
 pub fn word_not_keyword<'a>(input: Span<'a>) -> IResult<Token<'a>> {
     let (input, word): (_, Token<'a>) =
         take_while1(is_value_component)(input).map(|(s, t)| (s, t.into()))?;
@@ -192,7 +192,7 @@ pub fn parse_value(input: Span) -> IResult<Token> {
 }
 
 fn is_value_component(c: char) -> bool {
-    c.is_alphanumeric() || ['_', '-', '.'].contains(&c)
+    c.is_alphanumeric() || ['_', '-', '.', ':'].contains(&c)
 }
 
 fn is_syntax_component(c: char) -> bool {
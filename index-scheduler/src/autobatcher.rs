@@ -34,6 +34,10 @@ enum AutobatchKind {
     IndexDeletion,
     IndexUpdate,
     IndexSwap,
+    IndexExport,
+    IndexPreviewCreation,
+    IndexMerge,
+    IndexSplit,
 }
 
 impl AutobatchKind {
@@ -77,9 +81,14 @@ impl From<KindWithContent> for AutobatchKind {
             KindWithContent::IndexCreation { .. } => AutobatchKind::IndexCreation,
             KindWithContent::IndexUpdate { .. } => AutobatchKind::IndexUpdate,
             KindWithContent::IndexSwap { .. } => AutobatchKind::IndexSwap,
+            KindWithContent::IndexExport { .. } => AutobatchKind::IndexExport,
+            KindWithContent::IndexPreviewCreation { .. } => AutobatchKind::IndexPreviewCreation,
+            KindWithContent::IndexMerge { .. } => AutobatchKind::IndexMerge,
+            KindWithContent::IndexSplit { .. } => AutobatchKind::IndexSplit,
             KindWithContent::TaskCancelation { .. }
             | KindWithContent::TaskDeletion { .. }
             | KindWithContent::DumpCreation { .. }
+            | KindWithContent::DumpImport { .. }
             | KindWithContent::SnapshotCreation => {
                 panic!("The autobatcher should never be called with tasks that don't apply to an index.")
             }
@@ -132,6 +141,18 @@ pub enum BatchKind {
     IndexSwap {
         id: TaskId,
     },
+    IndexExport {
+        id: TaskId,
+    },
+    IndexPreviewCreation {
+        id: TaskId,
+    },
+    IndexMerge {
+        id: TaskId,
+    },
+    IndexSplit {
+        id: TaskId,
+    },
 }
 
 impl BatchKind {
@@ -175,6 +196,12 @@ impl BatchKind {
             K::IndexDeletion => (Break(BatchKind::IndexDeletion { ids: vec![task_id] }), false),
             K::IndexUpdate => (Break(BatchKind::IndexUpdate { id: task_id }), false),
             K::IndexSwap => (Break(BatchKind::IndexSwap { id: task_id }), false),
+            K::IndexExport => (Break(BatchKind::IndexExport { id: task_id }), false),
+            K::IndexPreviewCreation => {
+                (Break(BatchKind::IndexPreviewCreation { id: task_id }), false)
+            }
+            K::IndexMerge => (Break(BatchKind::IndexMerge { id: task_id }), false),
+            K::IndexSplit => (Break(BatchKind::IndexSplit { id: task_id }), false),
             K::DocumentClear => (Continue(BatchKind::DocumentClear { ids: vec![task_id] }), false),
             K::DocumentImport { method, allow_index_creation, primary_key: pk }
                 if primary_key.is_none() || pk.is_none() || primary_key == pk.as_deref() =>
@@ -222,7 +249,7 @@ impl BatchKind {
 
         match (self, kind) {
             // We don't batch any of these operations
-            (this, K::IndexCreation | K::IndexUpdate | K::IndexSwap | K::DocumentDeletionByFilter) => Break(this),
+            (this, K::IndexCreation | K::IndexUpdate | K::IndexSwap | K::IndexExport | K::IndexPreviewCreation | K::IndexMerge | K::IndexSplit | K::DocumentDeletionByFilter) => Break(this),
             // We must not batch tasks that don't have the same index creation rights if the index doesn't already exists.
             (this, kind) if !index_already_exists && this.allow_index_creation() == Some(false) && kind.allow_index_creation() == Some(true) => {
                 Break(this)
@@ -519,6 +546,10 @@ impl BatchKind {
                 | BatchKind::IndexDeletion { .. }
                 | BatchKind::IndexUpdate { .. }
                 | BatchKind::IndexSwap { .. }
+                | BatchKind::IndexExport { .. }
+                | BatchKind::IndexPreviewCreation { .. }
+                | BatchKind::IndexMerge { .. }
+                | BatchKind::IndexSplit { .. }
                 | BatchKind::DocumentDeletionByFilter { .. },
                 _,
             ) => {
@@ -569,7 +600,7 @@ pub fn autobatch(
 
 #[cfg(test)]
 mod tests {
-    use meilisearch_types::tasks::IndexSwap;
+    use meilisearch_types::tasks::{IndexSwap, MergeConflictPolicy};
     use uuid::Uuid;
 
     use super::*;
@@ -634,6 +665,35 @@ mod tests {
         KindWithContent::IndexDeletion { index_uid: String::from("doggo") }
     }
 
+    fn idx_export() -> KindWithContent {
+        KindWithContent::IndexExport { index_uid: String::from("doggo") }
+    }
+
+    fn idx_preview() -> KindWithContent {
+        KindWithContent::IndexPreviewCreation {
+            index_uid: String::from("doggo"),
+            source_index_uid: String::from("catto"),
+            settings: Default::default(),
+            expires_at: time::OffsetDateTime::UNIX_EPOCH,
+        }
+    }
+
+    fn idx_merge() -> KindWithContent {
+        KindWithContent::IndexMerge {
+            index_uid: String::from("doggo"),
+            source_index_uid: String::from("catto"),
+            on_conflict: MergeConflictPolicy::Replace,
+        }
+    }
+
+    fn idx_split() -> KindWithContent {
+        KindWithContent::IndexSplit {
+            index_uid: String::from("doggo"),
+            source_index_uid: String::from("catto"),
+            filter_expr: serde_json::json!("age > 2"),
+        }
+    }
+
     fn idx_swap() -> KindWithContent {
         KindWithContent::IndexSwap {
             swaps: vec![IndexSwap { indexes: (String::from("doggo"), String::from("catto")) }],
@@ -734,6 +794,19 @@ mod tests {
         debug_snapshot!(autobatch_from(true, None, [doc_imp(ReplaceDocuments, true, None), idx_swap()]), @"Some((DocumentOperation { method: ReplaceDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0] }, true))");
         debug_snapshot!(autobatch_from(true, None, [doc_imp(UpdateDocuments, true, None), idx_swap()]), @"Some((DocumentOperation { method: UpdateDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0] }, true))");
         debug_snapshot!(autobatch_from(true, None, [doc_del(), idx_swap()]), @"Some((DocumentDeletion { deletion_ids: [0] }, false))");
+
+        debug_snapshot!(autobatch_from(true, None, [doc_imp(ReplaceDocuments, true, None), idx_export()]), @"Some((DocumentOperation { method: ReplaceDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, None, [settings(true), idx_export()]), @"Some((Settings { allow_index_creation: true, settings_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, None, [idx_export(), doc_del()]), @"Some((IndexExport { id: 0 }, false))");
+
+        debug_snapshot!(autobatch_from(false, None, [idx_preview(), settings(true)]), @"Some((IndexPreviewCreation { id: 0 }, false))");
+        debug_snapshot!(autobatch_from(true, None, [settings(true), idx_preview()]), @"Some((Settings { allow_index_creation: true, settings_ids: [0] }, true))");
+
+        debug_snapshot!(autobatch_from(true, None, [idx_merge(), doc_imp(ReplaceDocuments, true, None)]), @"Some((IndexMerge { id: 0 }, false))");
+        debug_snapshot!(autobatch_from(true, None, [doc_imp(ReplaceDocuments, true, None), idx_merge()]), @"Some((DocumentOperation { method: ReplaceDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0] }, true))");
+
+        debug_snapshot!(autobatch_from(false, None, [idx_split(), doc_imp(ReplaceDocuments, true, None)]), @"Some((IndexSplit { id: 0 }, false))");
+        debug_snapshot!(autobatch_from(true, None, [settings(true), idx_split()]), @"Some((Settings { allow_index_creation: true, settings_ids: [0] }, true))");
     }
 
     #[test]
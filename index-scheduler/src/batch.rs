@@ -17,30 +17,39 @@ tasks individally, but should be much faster since we are only performing
 one indexing operation.
 */
 
-use std::collections::{BTreeSet, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::ffi::OsStr;
 use std::fmt;
-use std::fs::{self, File};
+use std::fs;
 use std::io::BufWriter;
+use std::path::Path;
 
 use dump::IndexMetadata;
 use meilisearch_types::error::Code;
 use meilisearch_types::heed::{RoTxn, RwTxn};
-use meilisearch_types::milli::documents::{obkv_to_object, DocumentsBatchReader};
+use meilisearch_types::milli::documents::{
+    obkv_to_object, DocumentsBatchBuilder, DocumentsBatchReader,
+};
 use meilisearch_types::milli::heed::CompactionOption;
 use meilisearch_types::milli::update::{
     IndexDocumentsConfig, IndexDocumentsMethod, IndexerConfig, Settings as MilliSettings,
 };
 use meilisearch_types::milli::{self, Filter};
 use meilisearch_types::settings::{apply_settings_to_builder, Settings, Unchecked};
-use meilisearch_types::tasks::{Details, IndexSwap, Kind, KindWithContent, Status, Task};
-use meilisearch_types::{compression, Index, VERSION_FILE_NAME};
+use meilisearch_types::snapshot::{self, SnapshotManifest};
+use meilisearch_types::tasks::{
+    Details, IndexSwap, Kind, KindWithContent, MergeConflictPolicy, Status, Task,
+};
+use meilisearch_types::{Index, VERSION_FILE_NAME};
 use roaring::RoaringBitmap;
 use time::macros::format_description;
 use time::OffsetDateTime;
 use uuid::Uuid;
 
 use crate::autobatcher::{self, BatchKind};
+use crate::enrichment;
+use crate::journal::JournalEntry;
+use crate::previews::IndexPreview;
 use crate::utils::{self, swap_index_uid_in_task};
 use crate::{Error, IndexScheduler, MustStopProcessing, ProcessingTasks, Result, TaskId};
 
@@ -54,14 +63,13 @@ pub(crate) enum Batch {
     TaskCancelation {
         /// The task cancelation itself.
         task: Task,
-        /// The date and time at which the previously processing tasks started.
-        previous_started_at: OffsetDateTime,
-        /// The list of tasks that were processing when this task cancelation appeared.
-        previous_processing_tasks: RoaringBitmap,
+        /// The batches that were processing when this task cancelation appeared.
+        previous_processing_tasks: ProcessingTasks,
     },
     TaskDeletions(Vec<Task>),
     SnapshotCreation(Vec<Task>),
     Dump(Task),
+    DumpImport(Task),
     IndexOperation {
         op: IndexOperation,
         must_create_index: bool,
@@ -76,6 +84,22 @@ pub(crate) enum Batch {
         primary_key: Option<String>,
         task: Task,
     },
+    IndexExport {
+        index_uid: String,
+        task: Task,
+    },
+    IndexPreviewCreation {
+        index_uid: String,
+        task: Task,
+    },
+    IndexMerge {
+        index_uid: String,
+        task: Task,
+    },
+    IndexSplit {
+        index_uid: String,
+        task: Task,
+    },
     IndexDeletion {
         index_uid: String,
         tasks: Vec<Task>,
@@ -146,8 +170,13 @@ impl Batch {
         match self {
             Batch::TaskCancelation { task, .. }
             | Batch::Dump(task)
+            | Batch::DumpImport(task)
             | Batch::IndexCreation { task, .. }
-            | Batch::IndexUpdate { task, .. } => {
+            | Batch::IndexUpdate { task, .. }
+            | Batch::IndexExport { task, .. }
+            | Batch::IndexPreviewCreation { task, .. }
+            | Batch::IndexMerge { task, .. }
+            | Batch::IndexSplit { task, .. } => {
                 RoaringBitmap::from_sorted_iter(std::iter::once(task.uid)).unwrap()
             }
             Batch::SnapshotCreation(tasks)
@@ -189,10 +218,15 @@ impl Batch {
             | TaskDeletions(_)
             | SnapshotCreation(_)
             | Dump(_)
+            | DumpImport(_)
             | IndexSwap { .. } => None,
             IndexOperation { op, .. } => Some(op.index_uid()),
             IndexCreation { index_uid, .. }
             | IndexUpdate { index_uid, .. }
+            | IndexExport { index_uid, .. }
+            | IndexPreviewCreation { index_uid, .. }
+            | IndexMerge { index_uid, .. }
+            | IndexSplit { index_uid, .. }
             | IndexDeletion { index_uid, .. } => Some(index_uid),
         }
     }
@@ -208,9 +242,14 @@ impl fmt::Display for Batch {
             Batch::TaskDeletions(_) => f.write_str("TaskDeletion")?,
             Batch::SnapshotCreation(_) => f.write_str("SnapshotCreation")?,
             Batch::Dump(_) => f.write_str("Dump")?,
+            Batch::DumpImport(_) => f.write_str("DumpImport")?,
             Batch::IndexOperation { op, .. } => write!(f, "{op}")?,
             Batch::IndexCreation { .. } => f.write_str("IndexCreation")?,
             Batch::IndexUpdate { .. } => f.write_str("IndexUpdate")?,
+            Batch::IndexExport { .. } => f.write_str("IndexExport")?,
+            Batch::IndexPreviewCreation { .. } => f.write_str("IndexPreviewCreation")?,
+            Batch::IndexMerge { .. } => f.write_str("IndexMerge")?,
+            Batch::IndexSplit { .. } => f.write_str("IndexSplit")?,
             Batch::IndexDeletion { .. } => f.write_str("IndexDeletion")?,
             Batch::IndexSwap { .. } => f.write_str("IndexSwap")?,
         };
@@ -503,6 +542,22 @@ impl IndexScheduler {
                 };
                 Ok(Some(Batch::IndexUpdate { index_uid, primary_key, task }))
             }
+            BatchKind::IndexExport { id } => {
+                let task = self.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+                Ok(Some(Batch::IndexExport { index_uid, task }))
+            }
+            BatchKind::IndexPreviewCreation { id } => {
+                let task = self.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+                Ok(Some(Batch::IndexPreviewCreation { index_uid, task }))
+            }
+            BatchKind::IndexMerge { id } => {
+                let task = self.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+                Ok(Some(Batch::IndexMerge { index_uid, task }))
+            }
+            BatchKind::IndexSplit { id } => {
+                let task = self.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+                Ok(Some(Batch::IndexSplit { index_uid, task }))
+            }
             BatchKind::IndexDeletion { ids } => Ok(Some(Batch::IndexDeletion {
                 index_uid,
                 index_has_been_created: must_create_index,
@@ -521,6 +576,9 @@ impl IndexScheduler {
     /// 3. We get the *next* snapshot to process.
     /// 4. We get the *next* dump to process.
     /// 5. We get the *next* tasks to process for a specific index.
+    ///
+    /// While other batches are running, only the tasks of the indexes that are not
+    /// already processing can be batched, all the other batches must run alone.
     #[tracing::instrument(level = "trace", skip(self, rtxn), target = "indexing::scheduler")]
     pub(crate) fn create_next_batch(&self, rtxn: &RoTxn) -> Result<Option<Batch>> {
         #[cfg(test)]
@@ -528,46 +586,74 @@ impl IndexScheduler {
 
         puffin::profile_function!();
 
+        let processing_tasks = self.processing_tasks.read().unwrap().clone();
+        let running_batches = processing_tasks.running().count();
+        if running_batches >= self.max_concurrent_batches {
+            return Ok(None);
+        }
+
         let enqueued = &self.get_status(rtxn, Status::Enqueued)?;
+        self.indexing_throttle.retain_enqueued(enqueued, self.next_task_id(rtxn)?);
         let to_cancel = self.get_kind(rtxn, Kind::TaskCancelation)? & enqueued;
+        let to_delete = self.get_kind(rtxn, Kind::TaskDeletion)? & enqueued;
+        let to_snapshot = self.get_kind(rtxn, Kind::SnapshotCreation)? & enqueued;
+        let to_dump = self.get_kind(rtxn, Kind::DumpCreation)? & enqueued;
+        let to_import = self.get_kind(rtxn, Kind::DumpImport)? & enqueued;
+
+        if running_batches > 0 {
+            // The prioritised tasks wait for the running batches to finish,
+            // we don't start new batches in the meantime so that they don't starve.
+            if !to_cancel.is_empty()
+                || !to_delete.is_empty()
+                || !to_snapshot.is_empty()
+                || !to_dump.is_empty()
+                || !to_import.is_empty()
+            {
+                return Ok(None);
+            }
+            return self.create_next_concurrent_batch(rtxn, enqueued, &processing_tasks);
+        }
 
         // 1. we get the last task to cancel.
         if let Some(task_id) = to_cancel.max() {
             // We retrieve the tasks that were processing before this tasks cancelation started.
             // We must *not* reset the processing tasks before calling this method.
-            let ProcessingTasks { started_at, processing } =
-                &*self.processing_tasks.read().unwrap();
             return Ok(Some(Batch::TaskCancelation {
                 task: self.get_task(rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?,
-                previous_started_at: *started_at,
-                previous_processing_tasks: processing.clone(),
+                previous_processing_tasks: processing_tasks,
             }));
         }
 
         // 2. we get the next task to delete
-        let to_delete = self.get_kind(rtxn, Kind::TaskDeletion)? & enqueued;
         if !to_delete.is_empty() {
             let tasks = self.get_existing_tasks(rtxn, to_delete)?;
             return Ok(Some(Batch::TaskDeletions(tasks)));
         }
 
         // 3. we batch the snapshot.
-        let to_snapshot = self.get_kind(rtxn, Kind::SnapshotCreation)? & enqueued;
         if !to_snapshot.is_empty() {
             return Ok(Some(Batch::SnapshotCreation(self.get_existing_tasks(rtxn, to_snapshot)?)));
         }
 
         // 4. we batch the dumps.
-        let to_dump = self.get_kind(rtxn, Kind::DumpCreation)? & enqueued;
         if let Some(to_dump) = to_dump.min() {
             return Ok(Some(Batch::Dump(
                 self.get_task(rtxn, to_dump)?.ok_or(Error::CorruptedTaskQueue)?,
             )));
         }
 
-        // 5. We make a batch from the unprioritised tasks. Start by taking the next enqueued task.
-        let task_id = if let Some(task_id) = enqueued.min() { task_id } else { return Ok(None) };
-        let task = self.get_task(rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
+        // 5. we import the dumps, one at a time.
+        if let Some(to_import) = to_import.min() {
+            return Ok(Some(Batch::DumpImport(
+                self.get_task(rtxn, to_import)?.ok_or(Error::CorruptedTaskQueue)?,
+            )));
+        }
+
+        // 6. We make a batch from the unprioritised tasks. Start by taking the next enqueued task
+        //    that is not throttled.
+        let Some(task) = self.next_unthrottled_task(rtxn, enqueued, enqueued)? else {
+            return Ok(None);
+        };
 
         // If the task is not associated with any index, verify that it is an index swap and
         // create the batch directly. Otherwise, get the index name associated with the task
@@ -580,6 +666,44 @@ impl IndexScheduler {
             return Ok(Some(Batch::IndexSwap { task }));
         };
 
+        self.create_next_batch_for_index(rtxn, enqueued, index_name)
+    }
+
+    /// Create the next batch to be processed while other batches are running.
+    ///
+    /// We take the *next* enqueued task that doesn't touch an index already processing.
+    /// If it only works on a single index we batch the enqueued tasks of this index,
+    /// otherwise it must wait for the running batches to finish.
+    fn create_next_concurrent_batch(
+        &self,
+        rtxn: &RoTxn,
+        enqueued: &RoaringBitmap,
+        processing_tasks: &ProcessingTasks,
+    ) -> Result<Option<Batch>> {
+        let mut candidates = enqueued.clone();
+        for index_uid in processing_tasks.running().filter_map(|batch| batch.index_uid.as_deref()) {
+            candidates -= self.index_tasks(rtxn, index_uid)?;
+        }
+
+        let Some(task) = self.next_unthrottled_task(rtxn, enqueued, &candidates)? else {
+            return Ok(None);
+        };
+        match task.indexes().as_slice() {
+            // The index swaps must wait for the running batches to finish.
+            [index_name] if !matches!(task.kind, KindWithContent::IndexSwap { .. }) => {
+                self.create_next_batch_for_index(rtxn, enqueued, index_name)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Use the autobatcher to batch the enqueued tasks associated with the given index.
+    fn create_next_batch_for_index(
+        &self,
+        rtxn: &RoTxn,
+        enqueued: &RoaringBitmap,
+        index_name: &str,
+    ) -> Result<Option<Batch>> {
         let index_already_exists = self.index_mapper.exists(rtxn, index_name)?;
         let mut primary_key = None;
         if index_already_exists {
@@ -592,18 +716,30 @@ impl IndexScheduler {
 
         // If autobatching is disabled we only take one task at a time.
         // Otherwise, we take only a maximum of tasks to create batches.
-        let tasks_limit =
-            if self.autobatching_enabled { self.max_number_of_batched_tasks } else { 1 };
-
-        let enqueued = index_tasks
-            .into_iter()
-            .take(tasks_limit)
-            .map(|task_id| {
-                self.get_task(rtxn, task_id)
-                    .and_then(|task| task.ok_or(Error::CorruptedTaskQueue))
-                    .map(|task| (task.uid, task.kind))
-            })
-            .collect::<Result<Vec<_>>>()?;
+        let limits = self.autobatching_limits();
+        let tasks_limit = if limits.enabled { limits.max_batched_tasks } else { 1 };
+
+        let mut enqueued = Vec::new();
+        let mut total_payload_size = 0u64;
+        for task_id in index_tasks.into_iter().take(tasks_limit) {
+            let task = self.get_task(rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
+            if let (Some(max_payload_size), Some(content_uuid)) =
+                (limits.max_batched_payload_size, task.content_uuid())
+            {
+                // a missing update file will make the task fail later, it doesn't weigh anything here.
+                let payload_size = self.file_store.compute_size(content_uuid).unwrap_or_default();
+                total_payload_size = total_payload_size.saturating_add(payload_size);
+                // We always keep the first task even if its payload alone is too big.
+                if !enqueued.is_empty() && total_payload_size > max_payload_size {
+                    break;
+                }
+            }
+            // The tasks of a key indexing above its limits wait for its bucket to be refilled.
+            if !self.indexing_throttle.draw(task.uid) {
+                break;
+            }
+            enqueued.push((task.uid, task.kind));
+        }
 
         if let Some((batchkind, create_index)) =
             autobatcher::autobatch(enqueued, index_already_exists, primary_key.as_deref())
@@ -639,7 +775,7 @@ impl IndexScheduler {
         puffin::profile_function!(batch.to_string());
 
         match batch {
-            Batch::TaskCancelation { mut task, previous_started_at, previous_processing_tasks } => {
+            Batch::TaskCancelation { mut task, previous_processing_tasks } => {
                 // 1. Retrieve the tasks that matched the query at enqueue-time.
                 let matched_tasks =
                     if let KindWithContent::TaskCancelation { tasks, query: _ } = &task.kind {
@@ -653,7 +789,6 @@ impl IndexScheduler {
                     &mut wtxn,
                     task.uid,
                     matched_tasks,
-                    previous_started_at,
                     &previous_processing_tasks,
                 )?;
 
@@ -669,24 +804,9 @@ impl IndexScheduler {
                     _ => unreachable!(),
                 }
 
-                // We must only remove the content files if the transaction is successfully committed
-                // and if errors occurs when we are deleting files we must do our best to delete
-                // everything. We do not return the encountered errors when deleting the content
-                // files as it is not a breaking operation and we can safely continue our job.
-                match wtxn.commit() {
-                    Ok(()) => {
-                        for content_uuid in canceled_tasks_content_uuids {
-                            if let Err(error) = self.delete_update_file(content_uuid) {
-                                tracing::error!(
-                                    file_content_uuid = %content_uuid,
-                                    %error,
-                                    "Failed deleting content file"
-                                )
-                            }
-                        }
-                    }
-                    Err(e) => return Err(e.into()),
-                }
+                // The content files of the canceled tasks are kept to retry them, they're deleted
+                // along with the tasks or once the retry payload retention is over.
+                wtxn.commit()?;
 
                 Ok(vec![task])
             }
@@ -703,9 +823,30 @@ impl IndexScheduler {
                 }
 
                 let mut wtxn = self.env.write_txn()?;
-                let mut deleted_tasks = self.delete_matched_tasks(&mut wtxn, &matched_tasks)?;
+                let (mut deleted_tasks, content_files) =
+                    self.delete_matched_tasks(&mut wtxn, &matched_tasks)?;
                 wtxn.commit()?;
 
+                // The content files of the deleted tasks can't be retried anymore, whether they're
+                // still in the file store or were moved to the dead letters. We don't return the
+                // errors encountered when deleting them as we can safely continue our job.
+                for content_uuid in content_files {
+                    let result = if self.file_store.get_update_path(content_uuid).exists() {
+                        self.delete_update_file(content_uuid)
+                    } else if self.dead_letter_files.get_update_path(content_uuid).exists() {
+                        self.dead_letter_files.delete(content_uuid).map_err(Error::from)
+                    } else {
+                        continue;
+                    };
+                    if let Err(error) = result {
+                        tracing::error!(
+                            file_content_uuid = %content_uuid,
+                            %error,
+                            "Failed deleting content file"
+                        )
+                    }
+                }
+
                 for task in tasks.iter_mut() {
                     task.status = Status::Succeeded;
                     let KindWithContent::TaskDeletion { tasks, query: _ } = &task.kind else {
@@ -732,6 +873,30 @@ impl IndexScheduler {
                 fs::create_dir_all(&self.snapshots_path)?;
                 let temp_snapshot_dir = tempfile::tempdir()?;
 
+                // 0. Find the original name of the database and the name of the snapshot,
+                //    the snapshots are timestamped when several of them are kept.
+                // TODO find a better way to get this path
+                let mut base_path = self.env.path().to_owned();
+                base_path.pop();
+                let db_name = base_path.file_name().and_then(OsStr::to_str).unwrap_or("data.ms");
+                let created_at = OffsetDateTime::now_utc();
+                let snapshot_name =
+                    if self.snapshot_retention.is_some() || self.snapshot_incremental {
+                        format!("{db_name}-{}.snapshot", dump_uid(created_at))
+                    } else {
+                        format!("{db_name}.snapshot")
+                    };
+
+                // An incremental snapshot is based on the most recent snapshot.
+                let base = if self.snapshot_incremental {
+                    match timestamped_snapshots(&self.snapshots_path, db_name)?.pop() {
+                        Some(name) => snapshot::read_manifest(self.snapshots_path.join(name))?,
+                        None => None,
+                    }
+                } else {
+                    None
+                };
+
                 // 1. Snapshot the version file.
                 let dst = temp_snapshot_dir.path().join(VERSION_FILE_NAME);
                 fs::copy(&self.version_file_path, dst)?;
@@ -770,13 +935,39 @@ impl IndexScheduler {
                     }
                 }
 
-                // 3. Snapshot every indexes
+                // 2.5 Copy the payloads of the dead letters, they live as long as their task
+                let dead_letters_dir = temp_snapshot_dir.path().join("dead_letters");
+                fs::create_dir_all(&dead_letters_dir)?;
+                for entry in self.dead_letters.iter(&rtxn)? {
+                    let (_, dead_letter) = entry?;
+                    let src = self.dead_letter_files.get_update_path(dead_letter.content_file);
+                    if src.exists() {
+                        fs::copy(src, dead_letters_dir.join(dead_letter.content_file.to_string()))?;
+                    }
+                }
+
+                // 3. Snapshot every indexes, an incremental snapshot only copies the ones updated
+                //    since its base snapshot and references the others
+                let mut indexes = BTreeMap::new();
                 for result in self.index_mapper.index_mapping.iter(&rtxn)? {
                     let (name, uuid) = result?;
                     let index = self.index_mapper.index(&rtxn, name)?;
+                    let unchanged_in = match &base {
+                        Some(base) => {
+                            let index_rtxn = index.read_txn()?;
+                            let updated_at = index.updated_at(&index_rtxn)?;
+                            base.indexes.get(&uuid).filter(|_| updated_at <= base.created_at)
+                        }
+                        None => None,
+                    };
+                    if let Some(source) = unchanged_in {
+                        indexes.insert(uuid, source.clone());
+                        continue;
+                    }
                     let dst = temp_snapshot_dir.path().join("indexes").join(uuid.to_string());
                     fs::create_dir_all(&dst)?;
                     index.copy_to_file(dst.join("data.mdb"), CompactionOption::Enabled)?;
+                    indexes.insert(uuid, snapshot_name.clone());
                 }
 
                 drop(rtxn);
@@ -787,24 +978,29 @@ impl IndexScheduler {
                 // TODO We can't use the open_auth_store_env function here but we should
                 let auth = milli::heed::EnvOpenOptions::new()
                     .map_size(1024 * 1024 * 1024) // 1 GiB
-                    .max_dbs(2)
+                    .max_dbs(4)
                     .open(&self.auth_path)?;
                 auth.copy_to_file(dst.join("data.mdb"), CompactionOption::Enabled)?;
 
                 // 5. Copy and tarball the flat snapshot
-                // 5.1 Find the original name of the database
-                // TODO find a better way to get this path
-                let mut base_path = self.env.path().to_owned();
-                base_path.pop();
-                let db_name = base_path.file_name().and_then(OsStr::to_str).unwrap_or("data.ms");
-
-                // 5.2 Tarball the content of the snapshot in a tempfile with a .snapshot extension
-                let snapshot_path = self.snapshots_path.join(format!("{}.snapshot", db_name));
+                // 5.1 Tarball the content of the snapshot and its manifest in a tempfile
+                //     with a .snapshot extension
+                let manifest = SnapshotManifest {
+                    name: snapshot_name.clone(),
+                    created_at,
+                    base: base.map(|base| base.name),
+                    indexes,
+                };
+                let snapshot_path = self.snapshots_path.join(&snapshot_name);
                 let temp_snapshot_file = tempfile::NamedTempFile::new_in(&self.snapshots_path)?;
-                compression::to_tar_gz(temp_snapshot_dir.path(), temp_snapshot_file.path())?;
-                let file = temp_snapshot_file.persist(snapshot_path)?;
+                snapshot::to_tar_gz(
+                    &manifest,
+                    temp_snapshot_dir.path(),
+                    temp_snapshot_file.path(),
+                )?;
+                let file = temp_snapshot_file.persist(&snapshot_path)?;
 
-                // 5.3 Change the permission to make the snapshot readonly
+                // 5.2 Change the permission to make the snapshot readonly
                 let mut permissions = file.metadata()?.permissions();
                 permissions.set_readonly(true);
                 #[cfg(unix)]
@@ -817,6 +1013,27 @@ impl IndexScheduler {
 
                 file.set_permissions(permissions)?;
 
+                // 6. Delete the oldest snapshots once there are more than the retention
+                if let Some(retention) = self.snapshot_retention {
+                    delete_oldest_snapshots(&self.snapshots_path, db_name, retention)?;
+                }
+
+                // 7. Upload the snapshot and its bases to the object storage in the background,
+                //    the files are opened right away to survive their deletion by a later retention
+                if self.snapshot_s3.is_some() {
+                    let file = fs::File::open(&snapshot_path)?;
+                    let bases: BTreeSet<_> =
+                        manifest.indexes.values().filter(|name| **name != snapshot_name).collect();
+                    let bases = bases
+                        .into_iter()
+                        .map(|name| -> Result<_> {
+                            Ok((name.clone(), fs::File::open(self.snapshots_path.join(name))?))
+                        })
+                        .collect::<Result<_>>()?;
+                    let task_ids = tasks.iter().map(|task| task.uid).collect();
+                    self.upload_snapshot(snapshot_name, file, bases, task_ids);
+                }
+
                 for task in &mut tasks {
                     task.status = Status::Succeeded;
                 }
@@ -825,13 +1042,24 @@ impl IndexScheduler {
             }
             Batch::Dump(mut task) => {
                 let started_at = OffsetDateTime::now_utc();
-                let (keys, instance_uid) =
-                    if let KindWithContent::DumpCreation { keys, instance_uid } = &task.kind {
-                        (keys, instance_uid)
+                let (keys, instance_uid, compression) =
+                    if let KindWithContent::DumpCreation { keys, instance_uid, compression } =
+                        &task.kind
+                    {
+                        (keys, instance_uid, *compression)
                     } else {
                         unreachable!();
                     };
-                let dump = dump::DumpWriter::new(*instance_uid)?;
+                let dump_uid = dump_uid(started_at);
+
+                // The dump is streamed into a temporary file of the dumps directory that is only
+                // renamed once complete, it's deleted if anything goes wrong in the meantime.
+                let mut file = tempfile::NamedTempFile::new_in(&self.dumps_path)?;
+                let mut dump = dump::DumpWriter::with_compression(
+                    *instance_uid,
+                    BufWriter::new(&mut file),
+                    compression,
+                )?;
 
                 // 1. dump the keys
                 let mut dump_keys = dump.create_keys()?;
@@ -868,6 +1096,7 @@ impl IndexScheduler {
                     let mut dump_content_file = dump_tasks.push_task(&t.into())?;
 
                     // 2.1. Dump the `content_file` associated with the task if there is one and the task is not finished yet.
+                    //      The dead letters of the failed tasks aren't dumped and are lost on import.
                     if let Some(content_file) = content_file {
                         if self.must_stop_processing.get() {
                             return Err(Error::AbortedTask);
@@ -896,55 +1125,36 @@ impl IndexScheduler {
                 dump_tasks.flush()?;
 
                 // 3. Dump the indexes
-                self.index_mapper.try_for_each_index(&rtxn, |uid, index| -> Result<()> {
-                    let rtxn = index.read_txn()?;
-                    let metadata = IndexMetadata {
-                        uid: uid.to_owned(),
-                        primary_key: index.primary_key(&rtxn)?.map(String::from),
-                        created_at: index.created_at(&rtxn)?,
-                        updated_at: index.updated_at(&rtxn)?,
-                    };
-                    let mut index_dumper = dump.create_index(uid, &metadata)?;
-
-                    let fields_ids_map = index.fields_ids_map(&rtxn)?;
-                    let all_fields: Vec<_> = fields_ids_map.iter().map(|(id, _)| id).collect();
-
-                    // 3.1. Dump the documents
-                    for ret in index.all_documents(&rtxn)? {
-                        if self.must_stop_processing.get() {
-                            return Err(Error::AbortedTask);
-                        }
-                        let (_id, doc) = ret?;
-                        let document = milli::obkv_to_json(&all_fields, &fields_ids_map, doc)?;
-                        index_dumper.push_document(&document)?;
-                    }
-
-                    // 3.2. Dump the settings
-                    let settings = meilisearch_types::settings::settings(index, &rtxn)?;
-                    index_dumper.settings(&settings)?;
-                    Ok(())
+                self.index_mapper.try_for_each_index(&rtxn, |uid, index| {
+                    self.dump_index(&mut dump, uid, index)
                 })?;
 
                 // 4. Dump experimental feature settings
                 let features = self.features().runtime_features();
                 dump.create_experimental_features(features)?;
 
-                let dump_uid = started_at.format(format_description!(
-                    "[year repr:full][month repr:numerical][day padding:zero]-[hour padding:zero][minute padding:zero][second padding:zero][subsecond digits:3]"
-                )).unwrap();
-
                 if self.must_stop_processing.get() {
                     return Err(Error::AbortedTask);
                 }
+                dump.finish()?;
                 let path = self.dumps_path.join(format!("{}.dump", dump_uid));
-                let file = File::create(path)?;
-                dump.persist_to(BufWriter::new(file))?;
+                file.persist(path)?;
 
                 // if we reached this step we can tell the scheduler we succeeded to dump ourselves.
                 task.status = Status::Succeeded;
                 task.details = Some(Details::Dump { dump_uid: Some(dump_uid) });
                 Ok(vec![task])
             }
+            Batch::DumpImport(mut task) => {
+                let (dump_uid, index_uid) = match &task.kind {
+                    KindWithContent::DumpImport { dump_uid, index_uid } => (dump_uid, index_uid),
+                    _ => unreachable!(),
+                };
+                self.import_dump(dump_uid, index_uid.as_deref())?;
+
+                task.status = Status::Succeeded;
+                Ok(vec![task])
+            }
             Batch::IndexOperation { op, must_create_index } => {
                 let index_uid = op.index_uid().to_string();
                 let index = if must_create_index {
@@ -957,11 +1167,21 @@ impl IndexScheduler {
                 };
 
                 // the index operation can take a long time, so save this handle to make it available to the search for the duration of the tick
-                self.index_mapper
-                    .set_currently_updating_index(Some((index_uid.clone(), index.clone())));
+                self.index_mapper.set_currently_updating_index(index_uid.clone(), index.clone());
+
+                // the webhook can take a long time to answer, the documents are enriched
+                // before the write transaction is opened so it doesn't block the index.
+                let mut enriched = self.enrich_documents(&index, &op)?;
 
                 let mut index_wtxn = index.write_txn()?;
-                let tasks = self.apply_index_operation(&mut index_wtxn, &index, op)?;
+                let tasks =
+                    self.apply_index_operation(&mut index_wtxn, &index, op, &mut enriched)?;
+
+                // The tasks may have been canceled after the last checkpoint of the indexation,
+                // we check one last time before committing so that all their work is rolled back.
+                if self.must_stop_processing.get() {
+                    return Err(Error::AbortedTask);
+                }
                 index_wtxn.commit()?;
 
                 // if the update processed successfully, we're going to store the new
@@ -1045,6 +1265,158 @@ impl IndexScheduler {
 
                 Ok(vec![task])
             }
+            Batch::IndexExport { index_uid, mut task } => {
+                let rtxn = self.env.read_txn()?;
+                let index = self.index_mapper.index(&rtxn, &index_uid)?;
+                drop(rtxn);
+                let dump_uid = dump_uid(OffsetDateTime::now_utc());
+
+                // An export is a dump of the index alone, without any key nor task,
+                // that can be imported into another instance.
+                let mut file = tempfile::NamedTempFile::new_in(&self.dumps_path)?;
+                let mut dump = dump::DumpWriter::new(None, BufWriter::new(&mut file))?;
+                dump.create_keys()?.flush()?;
+                dump.create_tasks_queue()?.flush()?;
+                self.dump_index(&mut dump, &index_uid, &index)?;
+
+                if self.must_stop_processing.get() {
+                    return Err(Error::AbortedTask);
+                }
+                dump.finish()?;
+                file.persist(self.dumps_path.join(format!("{dump_uid}.dump")))?;
+
+                task.status = Status::Succeeded;
+                task.details = Some(Details::Dump { dump_uid: Some(dump_uid) });
+                Ok(vec![task])
+            }
+            Batch::IndexPreviewCreation { index_uid, mut task } => {
+                let (source_index_uid, settings, expires_at) = match &task.kind {
+                    KindWithContent::IndexPreviewCreation {
+                        source_index_uid,
+                        settings,
+                        expires_at,
+                        ..
+                    } => (source_index_uid.clone(), settings.clone(), *expires_at),
+                    _ => unreachable!(),
+                };
+
+                let rtxn = self.env.read_txn()?;
+                let source = self.index_mapper.index(&rtxn, &source_index_uid)?;
+                drop(rtxn);
+
+                // The preview starts as a copy of the source index on which the new settings
+                // are applied, the source index itself is never modified.
+                let wtxn = self.env.write_txn()?;
+                let index = self.index_mapper.create_index_from(wtxn, &index_uid, &source)?;
+                let res = || -> Result<()> {
+                    let mut index_wtxn = index.write_txn()?;
+                    let mut builder = MilliSettings::new(
+                        &mut index_wtxn,
+                        &index,
+                        self.index_mapper.indexer_config(),
+                    );
+                    apply_settings_to_builder(&settings.clone().check(), &mut builder);
+                    let must_stop_processing = self.must_stop_processing.clone();
+                    builder.execute(
+                        |indexing_step| tracing::debug!(update = ?indexing_step),
+                        || must_stop_processing.get(),
+                    )?;
+                    index_wtxn.commit()?;
+                    Ok(())
+                }();
+                if let Err(e) = res {
+                    // an half-configured preview must not be left behind.
+                    drop(index);
+                    self.index_mapper.delete_index(self.env.write_txn()?, &index_uid)?;
+                    return Err(e);
+                }
+
+                let mut wtxn = self.env.write_txn()?;
+                let preview = IndexPreview {
+                    uid: index_uid.clone(),
+                    source_index_uid: source_index_uid.clone(),
+                    created_at: OffsetDateTime::now_utc(),
+                    expires_at,
+                };
+                self.previews.put(&mut wtxn, &index_uid, &preview)?;
+                let index_rtxn = index.read_txn()?;
+                let stats = crate::index_mapper::IndexStats::new(&index, &index_rtxn)?;
+                self.index_mapper.store_stats_of(&mut wtxn, &index_uid, &stats)?;
+                wtxn.commit()?;
+
+                task.status = Status::Succeeded;
+                task.details = Some(Details::IndexPreview { source_index_uid, expires_at });
+                Ok(vec![task])
+            }
+            Batch::IndexMerge { index_uid, mut task } => {
+                let (source_index_uid, on_conflict) = match &task.kind {
+                    KindWithContent::IndexMerge { source_index_uid, on_conflict, .. } => {
+                        (source_index_uid.clone(), *on_conflict)
+                    }
+                    _ => unreachable!(),
+                };
+
+                let rtxn = self.env.read_txn()?;
+                let source = self.index_mapper.index(&rtxn, &source_index_uid)?;
+                let index = self.index_mapper.index(&rtxn, &index_uid)?;
+                drop(rtxn);
+
+                let merged_documents = self.merge_index(&index, &source, on_conflict)?;
+
+                let index_rtxn = index.read_txn()?;
+                let stats = crate::index_mapper::IndexStats::new(&index, &index_rtxn)?;
+                let mut wtxn = self.env.write_txn()?;
+                self.index_mapper.store_stats_of(&mut wtxn, &index_uid, &stats)?;
+                wtxn.commit()?;
+
+                task.status = Status::Succeeded;
+                task.details = Some(Details::IndexMerge {
+                    source_index_uid,
+                    on_conflict,
+                    merged_documents: Some(merged_documents),
+                });
+                Ok(vec![task])
+            }
+            Batch::IndexSplit { index_uid, mut task } => {
+                let (source_index_uid, filter_expr) = match &task.kind {
+                    KindWithContent::IndexSplit { source_index_uid, filter_expr, .. } => {
+                        (source_index_uid.clone(), filter_expr.clone())
+                    }
+                    _ => unreachable!(),
+                };
+
+                let rtxn = self.env.read_txn()?;
+                let source = self.index_mapper.index(&rtxn, &source_index_uid)?;
+                drop(rtxn);
+
+                // The new index starts as a copy of the source index, settings included, from
+                // which the documents not matching the filter are removed.
+                let wtxn = self.env.write_txn()?;
+                let index = self.index_mapper.create_index_from(wtxn, &index_uid, &source)?;
+                let copied_documents = match self.split_index(&index, &filter_expr) {
+                    Ok(copied_documents) => copied_documents,
+                    Err(e) => {
+                        // a copy of the whole source index must not be left behind.
+                        drop(index);
+                        self.index_mapper.delete_index(self.env.write_txn()?, &index_uid)?;
+                        return Err(e);
+                    }
+                };
+
+                let index_rtxn = index.read_txn()?;
+                let stats = crate::index_mapper::IndexStats::new(&index, &index_rtxn)?;
+                let mut wtxn = self.env.write_txn()?;
+                self.index_mapper.store_stats_of(&mut wtxn, &index_uid, &stats)?;
+                wtxn.commit()?;
+
+                task.status = Status::Succeeded;
+                task.details = Some(Details::IndexSplit {
+                    source_index_uid,
+                    original_filter: filter_expr.to_string(),
+                    copied_documents: Some(copied_documents),
+                });
+                Ok(vec![task])
+            }
             Batch::IndexDeletion { index_uid, index_has_been_created, mut tasks } => {
                 let wtxn = self.env.write_txn()?;
 
@@ -1155,13 +1527,351 @@ impl IndexScheduler {
         Ok(())
     }
 
+    /// Writes the metadata, documents and settings of an index into a dump.
+    fn dump_index<W: std::io::Write>(
+        &self,
+        dump: &mut dump::DumpWriter<W>,
+        uid: &str,
+        index: &Index,
+    ) -> Result<()> {
+        let rtxn = index.read_txn()?;
+        let metadata = IndexMetadata {
+            uid: uid.to_owned(),
+            primary_key: index.primary_key(&rtxn)?.map(String::from),
+            created_at: index.created_at(&rtxn)?,
+            updated_at: index.updated_at(&rtxn)?,
+        };
+        let mut index_dumper = dump.create_index(uid, &metadata)?;
+
+        let fields_ids_map = index.fields_ids_map(&rtxn)?;
+        let all_fields: Vec<_> = fields_ids_map.iter().map(|(id, _)| id).collect();
+
+        // 1. Dump the documents
+        for ret in index.all_documents(&rtxn)? {
+            if self.must_stop_processing.get() {
+                return Err(Error::AbortedTask);
+            }
+            let (_id, doc) = ret?;
+            let document = milli::obkv_to_json(&all_fields, &fields_ids_map, doc)?;
+            index_dumper.push_document(&document)?;
+        }
+
+        // 2. Dump the settings
+        let settings = meilisearch_types::settings::settings(index, &rtxn)?;
+        index_dumper.settings(&settings)?;
+        Ok(())
+    }
+
+    /// Adds the documents of the `source` index into the `index` and returns how many were added.
+    ///
+    /// The documents of the source whose primary key is already used in the index replace,
+    /// update or leave the documents of the index depending on the conflict policy.
+    /// The index takes the primary key of the source when it doesn't have one yet.
+    fn merge_index(
+        &self,
+        index: &Index,
+        source: &Index,
+        on_conflict: MergeConflictPolicy,
+    ) -> Result<u64> {
+        let source_rtxn = source.read_txn()?;
+        let Some(source_primary_key) = source.primary_key(&source_rtxn)? else {
+            // an index without a primary key doesn't have any document to merge.
+            return Ok(0);
+        };
+
+        let indexer_config = self.index_mapper.indexer_config();
+        let must_stop_processing = self.must_stop_processing.clone();
+        let mut index_wtxn = index.write_txn()?;
+
+        // 1. Give the primary key of the source to the index if it doesn't have one.
+        if index.primary_key(&index_wtxn)?.is_none() {
+            let mut builder = MilliSettings::new(&mut index_wtxn, index, indexer_config);
+            builder.set_primary_key(source_primary_key.to_string());
+            builder.execute(
+                |indexing_step| tracing::debug!(update = ?indexing_step),
+                || must_stop_processing.get(),
+            )?;
+        }
+
+        // 2. Find the documents of the source that must leave the ones of the index untouched.
+        let mut skipped = RoaringBitmap::new();
+        if on_conflict == MergeConflictPolicy::Skip {
+            let external_documents_ids = index.external_documents_ids();
+            for ret in source.external_documents_ids().iter(&source_rtxn)? {
+                let (external_id, docid) = ret?;
+                if external_documents_ids.get(&index_wtxn, external_id)?.is_some() {
+                    skipped.insert(docid);
+                }
+            }
+        }
+
+        // 3. Convert the other documents back into the format accepted by the index.
+        let fields_ids_map = source.fields_ids_map(&source_rtxn)?;
+        let all_fields: Vec<_> = fields_ids_map.iter().map(|(id, _)| id).collect();
+        let mut documents = DocumentsBatchBuilder::new(BufWriter::new(tempfile::tempfile()?));
+        for ret in source.all_documents(&source_rtxn)? {
+            if must_stop_processing.get() {
+                return Err(Error::AbortedTask);
+            }
+            let (docid, doc) = ret?;
+            if !skipped.contains(docid) {
+                let document = milli::obkv_to_json(&all_fields, &fields_ids_map, doc)?;
+                documents.append_json_object(&document)?;
+            }
+        }
+        let merged_documents = documents.documents_count() as u64;
+        if merged_documents == 0 {
+            index_wtxn.commit()?;
+            return Ok(0);
+        }
+        let file = documents.into_inner()?.into_inner().map_err(|e| e.into_error())?;
+        let reader = DocumentsBatchReader::from_reader(std::io::BufReader::new(file))
+            .map_err(milli::Error::from)?;
+
+        // 4. Index them.
+        let update_method = match on_conflict {
+            MergeConflictPolicy::Replace | MergeConflictPolicy::Skip => {
+                IndexDocumentsMethod::ReplaceDocuments
+            }
+            MergeConflictPolicy::Update => IndexDocumentsMethod::UpdateDocuments,
+        };
+        let embedders = self.embedders(index.embedding_configs(&index_wtxn)?)?;
+        let builder = milli::update::IndexDocuments::new(
+            &mut index_wtxn,
+            index,
+            indexer_config,
+            IndexDocumentsConfig { update_method, ..Default::default() },
+            |indexing_step| tracing::trace!(?indexing_step, "Update"),
+            || must_stop_processing.get(),
+        )?;
+        let (builder, user_result) = builder.with_embedders(embedders).add_documents(reader)?;
+        user_result.map_err(milli::Error::from)?;
+        builder.execute()?;
+        index_wtxn.commit()?;
+
+        Ok(merged_documents)
+    }
+
+    /// Removes the documents of the `index` that don't match the filter and returns how many
+    /// documents are left.
+    fn split_index(&self, index: &Index, filter_expr: &serde_json::Value) -> Result<u64> {
+        let mut index_wtxn = index.write_txn()?;
+        let all_documents = index.documents_ids(&index_wtxn)?;
+        let matching = match Filter::from_json(filter_expr)? {
+            Some(filter) => filter.evaluate(&index_wtxn, index).map_err(|err| match err {
+                milli::Error::UserError(milli::UserError::InvalidFilter(_)) => {
+                    Error::from(err).with_custom_error_code(Code::InvalidIndexSplitFilter)
+                }
+                e => e.into(),
+            })?,
+            None => all_documents.clone(),
+        };
+
+        let removed = all_documents - &matching;
+        if !removed.is_empty() {
+            let must_stop_processing = self.must_stop_processing.clone();
+            let builder = milli::update::IndexDocuments::new(
+                &mut index_wtxn,
+                index,
+                self.index_mapper.indexer_config(),
+                IndexDocumentsConfig::default(),
+                |indexing_step| tracing::debug!(update = ?indexing_step),
+                || must_stop_processing.get(),
+            )?;
+            let (builder, _) = builder.remove_documents_from_db_no_batch(&removed)?;
+            builder.execute()?;
+        }
+        index_wtxn.commit()?;
+
+        Ok(matching.len())
+    }
+
+    /// Imports the indexes of a dump of the dumps directory into the running instance.
+    ///
+    /// Only the indexes are imported: the instance keeps its own keys, task queue and
+    /// experimental features. Nothing is imported if an index of the dump already exists,
+    /// and the indexes created by an import that fails midway are deleted.
+    ///
+    /// When a target index uid is given, the dump must contain a single index,
+    /// which is imported under this uid.
+    fn import_dump(&self, dump_uid: &str, target: Option<&str>) -> Result<()> {
+        let dump_path = self.dumps_path.join(format!("{dump_uid}.dump"));
+        let file = match fs::File::open(dump_path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(Error::DumpNotFound(dump_uid.to_string()))
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let dump_reader = dump::DumpReader::open(file)?;
+
+        // the uids the indexes of the dump will have once imported
+        let mut uids = Vec::new();
+        for index_reader in dump_reader.indexes()? {
+            uids.push(index_reader?.metadata().uid.clone());
+        }
+        if let Some(target) = target {
+            if uids.len() != 1 {
+                return Err(Error::DumpNotOfSingleIndex {
+                    dump_uid: dump_uid.to_string(),
+                    indexes: uids.len(),
+                });
+            }
+            uids = vec![target.to_string()];
+        }
+
+        let rtxn = self.env.read_txn()?;
+        for uid in &uids {
+            if self.index_mapper.exists(&rtxn, uid)? {
+                return Err(Error::IndexAlreadyExists(uid.clone()));
+            }
+        }
+        drop(rtxn);
+
+        let indexer_config = self.index_mapper.indexer_config();
+        let must_stop_processing = self.must_stop_processing.clone();
+        let mut imported_indexes = Vec::new();
+        let result = || -> Result<()> {
+            for index_reader in dump_reader.indexes()? {
+                let mut index_reader = index_reader?;
+                let metadata = index_reader.metadata().clone();
+                let uid = target.map_or_else(|| metadata.uid.clone(), String::from);
+                tracing::info!("Importing index `{}` as `{uid}`.", metadata.uid);
+
+                let date = Some((metadata.created_at, metadata.updated_at));
+                let wtxn = self.env.write_txn()?;
+                let index = self.index_mapper.create_index(wtxn, &uid, date)?;
+                imported_indexes.push(uid.clone());
+
+                let mut index_wtxn = index.write_txn()?;
+
+                // 1. Import the primary key and the settings.
+                let mut builder = MilliSettings::new(&mut index_wtxn, &index, indexer_config);
+                if let Some(primary_key) = metadata.primary_key {
+                    builder.set_primary_key(primary_key);
+                }
+                apply_settings_to_builder(&index_reader.settings()?, &mut builder);
+                builder.execute(
+                    |indexing_step| tracing::debug!(update = ?indexing_step),
+                    || must_stop_processing.get(),
+                )?;
+
+                // 2. Import the documents, converted back into the format accepted by the index.
+                let mut documents =
+                    DocumentsBatchBuilder::new(BufWriter::new(tempfile::tempfile()?));
+                for document in index_reader.documents()? {
+                    if must_stop_processing.get() {
+                        return Err(Error::AbortedTask);
+                    }
+                    documents.append_json_object(&document?)?;
+                }
+                let file = documents.into_inner()?.into_inner().map_err(|e| e.into_error())?;
+                let reader = DocumentsBatchReader::from_reader(std::io::BufReader::new(file))
+                    .map_err(milli::Error::from)?;
+
+                let embedders = self.embedders(index.embedding_configs(&index_wtxn)?)?;
+                let builder = milli::update::IndexDocuments::new(
+                    &mut index_wtxn,
+                    &index,
+                    indexer_config,
+                    IndexDocumentsConfig {
+                        update_method: IndexDocumentsMethod::ReplaceDocuments,
+                        ..Default::default()
+                    },
+                    |indexing_step| tracing::trace!(?indexing_step, "Update"),
+                    || must_stop_processing.get(),
+                )?;
+                let (builder, user_result) =
+                    builder.with_embedders(embedders).add_documents(reader)?;
+                user_result.map_err(milli::Error::from)?;
+                builder.execute()?;
+                index_wtxn.commit()?;
+
+                let index_rtxn = index.read_txn()?;
+                let stats = crate::index_mapper::IndexStats::new(&index, &index_rtxn)?;
+                let mut wtxn = self.env.write_txn()?;
+                self.index_mapper.store_stats_of(&mut wtxn, &uid, &stats)?;
+                wtxn.commit()?;
+            }
+            Ok(())
+        }();
+
+        if result.is_err() {
+            for uid in imported_indexes {
+                let wtxn = self.env.write_txn()?;
+                self.index_mapper.delete_index(wtxn, &uid)?;
+            }
+        }
+        result
+    }
+
+    /// Send the documents added by the index operation to the enrichment webhook of the index,
+    /// as it will be configured once the settings of the operation are applied.
+    ///
+    /// ## Return
+    /// The enriched documents, or the error of the webhook, by update file.
+    fn enrich_documents(
+        &self,
+        index: &Index,
+        operation: &IndexOperation,
+    ) -> Result<HashMap<Uuid, Result<fs::File>>> {
+        let (primary_key, operations, settings) = match operation {
+            IndexOperation::DocumentOperation { primary_key, operations, .. } => {
+                (primary_key, operations, &[][..])
+            }
+            IndexOperation::SettingsAndDocumentOperation {
+                primary_key,
+                operations,
+                settings,
+                ..
+            } => (primary_key, operations, &settings[..]),
+            _ => return Ok(HashMap::new()),
+        };
+
+        let rtxn = index.read_txn()?;
+        let Some(webhook) = enrichment::webhook_after_settings(
+            index.enrichment_webhook(&rtxn)?,
+            settings.iter().map(|(_, settings)| settings),
+        ) else {
+            return Ok(HashMap::new());
+        };
+        let primary_key = match primary_key {
+            Some(primary_key) => Some(primary_key.clone()),
+            None => index.primary_key(&rtxn)?.map(String::from),
+        };
+        drop(rtxn);
+
+        let deadline = std::time::Instant::now() + enrichment::ENRICHMENT_TIMEOUT;
+        let mut enriched = HashMap::new();
+        for operation in operations {
+            let DocumentOperation::Add(content_uuid) = operation else { continue };
+            let content_file = self.file_store.get_update(*content_uuid)?;
+            match enrichment::enrich_documents(
+                &webhook,
+                primary_key.as_deref(),
+                content_file,
+                deadline,
+                || self.must_stop_processing.get(),
+            ) {
+                Ok(content_file) => enriched.insert(*content_uuid, Ok(content_file)),
+                // only the task whose documents couldn't be enriched fails
+                Err(e @ Error::EnrichmentWebhook(_)) => enriched.insert(*content_uuid, Err(e)),
+                Err(e) => return Err(e),
+            };
+        }
+        Ok(enriched)
+    }
+
     /// Process the index operation on the given index.
     ///
+    /// The documents of the additions are taken from `enriched` when they went through
+    /// the enrichment webhook of the index.
+    ///
     /// ## Return
     /// The list of processed tasks.
     #[tracing::instrument(
         level = "trace",
-        skip(self, index_wtxn, index),
+        skip(self, index_wtxn, index, enriched),
         target = "indexing::scheduler"
     )]
     fn apply_index_operation<'i>(
@@ -1169,6 +1879,7 @@ impl IndexScheduler {
         index_wtxn: &mut RwTxn<'i>,
         index: &'i Index,
         operation: IndexOperation,
+        enriched: &mut HashMap<Uuid, Result<fs::File>>,
     ) -> Result<Vec<Task>> {
         puffin::profile_function!();
 
@@ -1250,14 +1961,6 @@ impl IndexScheduler {
                 for (operation, task) in operations.into_iter().zip(tasks.iter_mut()) {
                     match operation {
                         DocumentOperation::Add(content_uuid) => {
-                            let content_file = self.file_store.get_update(content_uuid)?;
-                            let reader = DocumentsBatchReader::from_reader(content_file)
-                                .map_err(milli::Error::from)?;
-                            let (new_builder, user_result) = builder.add_documents(reader)?;
-                            builder = new_builder;
-
-                            builder = builder.with_embedders(embedders.clone());
-
                             let received_documents =
                                 if let Some(Details::DocumentAdditionOrUpdate {
                                     received_documents,
@@ -1270,6 +1973,26 @@ impl IndexScheduler {
                                     unreachable!();
                                 };
 
+                            let content_file = match enriched.remove(&content_uuid) {
+                                Some(Ok(content_file)) => content_file,
+                                Some(Err(e)) => {
+                                    task.status = Status::Failed;
+                                    task.details = Some(Details::DocumentAdditionOrUpdate {
+                                        received_documents,
+                                        indexed_documents: Some(0),
+                                    });
+                                    task.error = Some(e.into());
+                                    continue;
+                                }
+                                None => self.file_store.get_update(content_uuid)?,
+                            };
+                            let reader = DocumentsBatchReader::from_reader(content_file)
+                                .map_err(milli::Error::from)?;
+                            let (new_builder, user_result) = builder.add_documents(reader)?;
+                            builder = new_builder;
+
+                            builder = builder.with_embedders(embedders.clone());
+
                             match user_result {
                                 Ok(count) => {
                                     task.status = Status::Succeeded;
@@ -1417,6 +2140,7 @@ impl IndexScheduler {
                         settings,
                         tasks: settings_tasks,
                     },
+                    enriched,
                 )?;
 
                 let mut import_tasks = self.apply_index_operation(
@@ -1430,6 +2154,7 @@ impl IndexScheduler {
                         operations,
                         tasks: document_import_tasks,
                     },
+                    enriched,
                 )?;
 
                 let mut tasks = settings_tasks;
@@ -1449,12 +2174,14 @@ impl IndexScheduler {
                         index_uid: index_uid.clone(),
                         tasks: cleared_tasks,
                     },
+                    enriched,
                 )?;
 
                 let settings_tasks = self.apply_index_operation(
                     index_wtxn,
                     index,
                     IndexOperation::Settings { index_uid, settings, tasks: settings_tasks },
+                    enriched,
                 )?;
 
                 let mut tasks = settings_tasks;
@@ -1466,12 +2193,13 @@ impl IndexScheduler {
 
     /// Delete each given task from all the databases (if it is deleteable).
     ///
-    /// Return the number of tasks that were actually deleted.
+    /// Return the tasks that were actually deleted and the content files that the transaction
+    /// owner must delete if the commit is successful.
     fn delete_matched_tasks(
         &self,
         wtxn: &mut RwTxn,
         matched_tasks: &RoaringBitmap,
-    ) -> Result<RoaringBitmap> {
+    ) -> Result<(RoaringBitmap, Vec<Uuid>)> {
         // 1. Remove from this list the tasks that we are not allowed to delete
         let enqueued_tasks = self.get_status(wtxn, Status::Enqueued)?;
         let processing_tasks = &self.processing_tasks.read().unwrap().processing.clone();
@@ -1487,6 +2215,7 @@ impl IndexScheduler {
         let mut affected_statuses = HashSet::new();
         let mut affected_kinds = HashSet::new();
         let mut affected_canceled_by = RoaringBitmap::new();
+        let mut content_files_to_delete = Vec::new();
 
         for task_id in to_delete_tasks.iter() {
             let task = self.get_task(wtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
@@ -1494,10 +2223,14 @@ impl IndexScheduler {
             affected_indexes.extend(task.indexes().into_iter().map(|x| x.to_owned()));
             affected_statuses.insert(task.status);
             affected_kinds.insert(task.kind.as_kind());
-            // Note: don't delete the persisted task data since
-            // we can only delete succeeded, failed, and canceled tasks.
-            // In each of those cases, the persisted data is supposed to
-            // have been deleted already.
+            // Note: only the failed and canceled tasks still have their
+            // persisted data, the one of the succeeded tasks is deleted
+            // once they're processed.
+            if matches!(task.status, Status::Failed | Status::Canceled) {
+                content_files_to_delete.extend(task.content_uuid());
+            }
+            // the dead letter of a failed document addition lives as long as its task.
+            self.dead_letters.delete(wtxn, &task_id)?;
             utils::remove_task_datetime(wtxn, self.enqueued_at, task.enqueued_at, task.uid)?;
             if let Some(started_at) = task.started_at {
                 utils::remove_task_datetime(wtxn, self.started_at, started_at, task.uid)?;
@@ -1522,6 +2255,7 @@ impl IndexScheduler {
             self.update_kind(wtxn, kind, |bitmap| *bitmap -= &to_delete_tasks)?;
         }
 
+        self.journal.append(to_delete_tasks.iter().map(|task| JournalEntry::new(task, None)))?;
         for task in to_delete_tasks.iter() {
             self.all_tasks.delete(wtxn, &task)?;
         }
@@ -1536,19 +2270,18 @@ impl IndexScheduler {
             }
         }
 
-        Ok(to_delete_tasks)
+        Ok((to_delete_tasks, content_files_to_delete))
     }
 
     /// Cancel each given task from all the databases (if it is cancelable).
     ///
-    /// Returns the content files that the transaction owner must delete if the commit is successful.
+    /// Returns the content files of the canceled tasks.
     fn cancel_matched_tasks(
         &self,
         wtxn: &mut RwTxn,
         cancel_task_id: TaskId,
         matched_tasks: &RoaringBitmap,
-        previous_started_at: OffsetDateTime,
-        previous_processing_tasks: &RoaringBitmap,
+        previous_processing_tasks: &ProcessingTasks,
     ) -> Result<Vec<Uuid>> {
         let now = OffsetDateTime::now_utc();
 
@@ -1559,13 +2292,13 @@ impl IndexScheduler {
         let tasks_to_cancel = cancelable_tasks & matched_tasks;
 
         // 2. We now have a list of tasks to cancel, cancel them
-        let mut content_files_to_delete = Vec::new();
+        let mut content_files = Vec::new();
         for mut task in self.get_existing_tasks(wtxn, tasks_to_cancel.iter())? {
             if let Some(uuid) = task.content_uuid() {
-                content_files_to_delete.push(uuid);
+                content_files.push(uuid);
             }
-            if previous_processing_tasks.contains(task.uid) {
-                task.started_at = Some(previous_started_at);
+            if let Some(started_at) = previous_processing_tasks.started_at(task.uid) {
+                task.started_at = Some(started_at);
             }
             task.status = Status::Canceled;
             task.canceled_by = Some(cancel_task_id);
@@ -1575,8 +2308,54 @@ impl IndexScheduler {
         }
         self.canceled_by.put(wtxn, &cancel_task_id, &tasks_to_cancel)?;
 
-        Ok(content_files_to_delete)
+        Ok(content_files)
+    }
+}
+
+/// Returns the names of the timestamped snapshots of the database, from the oldest to the most recent.
+fn timestamped_snapshots(snapshots_path: &Path, db_name: &str) -> Result<Vec<String>> {
+    let prefix = format!("{db_name}-");
+    let mut snapshots = Vec::new();
+    for entry in fs::read_dir(snapshots_path)? {
+        let name = entry?.file_name();
+        if let Some(name) = name.to_str() {
+            if name.starts_with(&prefix) && name.ends_with(".snapshot") {
+                snapshots.push(name.to_string());
+            }
+        }
+    }
+
+    // The timestamps are formatted to be sorted alphabetically.
+    snapshots.sort_unstable();
+    Ok(snapshots)
+}
+
+/// Deletes the timestamped snapshots of the database but the `retention` most recent ones,
+/// and the ones containing indexes of the incremental snapshots that are kept.
+fn delete_oldest_snapshots(snapshots_path: &Path, db_name: &str, retention: usize) -> Result<()> {
+    let snapshots = timestamped_snapshots(snapshots_path, db_name)?;
+    let (outdated, kept) = snapshots.split_at(snapshots.len().saturating_sub(retention));
+
+    let mut referenced = HashSet::new();
+    for name in kept {
+        if let Some(manifest) = snapshot::read_manifest(snapshots_path.join(name))? {
+            referenced.extend(manifest.indexes.into_values());
+        }
+    }
+
+    for name in outdated.iter().filter(|name| !referenced.contains(*name)) {
+        fs::remove_file(snapshots_path.join(name))?;
     }
+
+    Ok(())
+}
+
+/// Returns the uid of a dump created at this date, which is also the name of its file.
+fn dump_uid(date: OffsetDateTime) -> String {
+    date.format(format_description!(
+        "[year repr:full][month repr:numerical][day padding:zero]-[hour padding:zero][minute padding:zero][second padding:zero][subsecond digits:3]"
+    ))
+    .unwrap()
 }
 
 fn delete_document_by_filter<'a>(
@@ -0,0 +1,187 @@
+/*!
+The dead letters keep the payloads of the document additions that failed.
+
+Once a document addition fails, its update file is moved from the file store to the dead letters
+folder and the information needed to import it again is persisted in the task database. The
+payload is kept there until the users import it again or delete it, see
+[`IndexScheduler::import_dead_letter`], or until its task is deleted, either explicitly or by the
+task retention policy.
+
+The dead letters are part of the snapshots but not of the dumps: importing a dump loses them,
+as it loses the payloads of every finished task.
+*/
+
+use std::path::PathBuf;
+
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::milli::update::IndexDocumentsMethod;
+use meilisearch_types::tasks::{Kind, KindWithContent, Status, Task};
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{Error, IndexScheduler, Result, TaskId};
+
+/// The payload of a failed document addition and what is needed to import it again.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeadLetter {
+    /// The uid of the document addition that failed.
+    pub task_uid: TaskId,
+    pub index_uid: String,
+    pub primary_key: Option<String>,
+    pub method: IndexDocumentsMethod,
+    pub documents_count: u64,
+    pub allow_index_creation: bool,
+    pub content_file: Uuid,
+    pub error: Option<ResponseError>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub enqueued_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub started_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub finished_at: Option<OffsetDateTime>,
+}
+
+impl DeadLetter {
+    fn from_task(task: &Task) -> Option<DeadLetter> {
+        let KindWithContent::DocumentAdditionOrUpdate {
+            index_uid,
+            primary_key,
+            method,
+            content_file,
+            documents_count,
+            allow_index_creation,
+        } = &task.kind
+        else {
+            return None;
+        };
+        Some(DeadLetter {
+            task_uid: task.uid,
+            index_uid: index_uid.clone(),
+            primary_key: primary_key.clone(),
+            method: *method,
+            documents_count: *documents_count,
+            allow_index_creation: *allow_index_creation,
+            content_file: *content_file,
+            error: task.error.clone(),
+            enqueued_at: task.enqueued_at,
+            started_at: task.started_at,
+            finished_at: task.finished_at,
+        })
+    }
+}
+
+impl IndexScheduler {
+    /// Return all the dead letters, sorted by task uid.
+    pub fn get_dead_letters(&self) -> Result<Vec<DeadLetter>> {
+        let rtxn = self.env.read_txn()?;
+        self.dead_letters.iter(&rtxn)?.map(|entry| Ok(entry?.1)).collect()
+    }
+
+    /// Return the dead letter of the given failed task.
+    pub fn get_dead_letter(&self, task_uid: TaskId) -> Result<DeadLetter> {
+        let rtxn = self.env.read_txn()?;
+        self.dead_letters.get(&rtxn, &task_uid)?.ok_or(Error::DeadLetterNotFound(task_uid))
+    }
+
+    /// Delete the dead letter of the given failed task along with its payload.
+    pub fn delete_dead_letter(&self, task_uid: TaskId) -> Result<DeadLetter> {
+        let mut wtxn = self.env.write_txn()?;
+        let dead_letter =
+            self.dead_letters.get(&wtxn, &task_uid)?.ok_or(Error::DeadLetterNotFound(task_uid))?;
+        self.dead_letters.delete(&mut wtxn, &task_uid)?;
+        wtxn.commit()?;
+
+        if self.dead_letter_files.get_update_path(dead_letter.content_file).exists() {
+            self.dead_letter_files.delete(dead_letter.content_file)?;
+        }
+        Ok(dead_letter)
+    }
+
+    /// Register a new document addition with the payload of the given dead letter,
+    /// the dead letter is deleted once the task is registered.
+    pub fn import_dead_letter(&self, task_uid: TaskId) -> Result<Task> {
+        let dead_letter = self.get_dead_letter(task_uid)?;
+        let Some(payload) = self.payload_path(dead_letter.content_file) else {
+            return Err(Error::RetryLostUpdateFile(task_uid));
+        };
+        let content_file = self.file_store.link_update(payload)?;
+
+        let kind = KindWithContent::DocumentAdditionOrUpdate {
+            index_uid: dead_letter.index_uid,
+            primary_key: dead_letter.primary_key,
+            method: dead_letter.method,
+            content_file,
+            documents_count: dead_letter.documents_count,
+            allow_index_creation: dead_letter.allow_index_creation,
+        };
+        let task = match self.register_task(kind, None, false, Some(task_uid), |_| ()) {
+            Ok(task) => task,
+            Err(e) => {
+                if self.file_store.get_update_path(content_file).exists() {
+                    let _ = self.delete_update_file(content_file);
+                }
+                return Err(e);
+            }
+        };
+
+        // the new task has its own link to the payload.
+        if let Err(e) = self.delete_dead_letter(task_uid) {
+            tracing::error!("Failed deleting the dead letter of task {task_uid}: {e}");
+        }
+        Ok(task)
+    }
+
+    /// Return the path of an update file, whether it's still in the file store
+    /// or was moved to the dead letters.
+    pub(crate) fn payload_path(&self, content_file: Uuid) -> Option<PathBuf> {
+        [
+            self.file_store.get_update_path(content_file),
+            self.dead_letter_files.get_update_path(content_file),
+        ]
+        .into_iter()
+        .find(|path| path.exists())
+    }
+
+    /// Move the update files of the document additions that failed among the given tasks
+    /// to the dead letters.
+    ///
+    /// Must be called once the outcome of the tasks is committed.
+    pub(crate) fn register_dead_letters(&self, tasks: &RoaringBitmap) -> Result<()> {
+        let mut wtxn = self.env.write_txn()?;
+        let failed = self.get_status(&wtxn, Status::Failed)?
+            & self.get_kind(&wtxn, Kind::DocumentAdditionOrUpdate)?
+            & tasks;
+
+        let mut moved = Vec::new();
+        for task in self.get_existing_tasks(&wtxn, failed.iter())? {
+            let Some(dead_letter) = DeadLetter::from_task(&task) else { continue };
+            let src = self.file_store.get_update_path(dead_letter.content_file);
+            if !src.exists() {
+                continue;
+            }
+            let dst = self.dead_letter_files.get_update_path(dead_letter.content_file);
+            // the files are moved before the commit so a dead letter never refers to a file
+            // that could be deleted along with its task.
+            if let Err(e) = std::fs::rename(&src, &dst) {
+                tracing::error!(
+                    "Failed moving the update file of task {} to the dead letters: {e}",
+                    task.uid
+                );
+                continue;
+            }
+            moved.push((src, dst));
+            self.dead_letters.put(&mut wtxn, &task.uid, &dead_letter)?;
+        }
+
+        if let Err(e) = wtxn.commit() {
+            for (src, dst) in moved {
+                let _ = std::fs::rename(dst, src);
+            }
+            return Err(e.into());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,190 @@
+/*!
+The enrichment of the documents by the webhook of an index.
+
+Before the documents of an addition are indexed, they're sent by batches to the webhook configured
+in the `enrichmentWebhook` setting of the index. The webhook must answer with a JSON array holding
+one object per document, in the same order. The fields of these objects are merged into the
+documents, overriding the fields of the same name, except the primary key that can't be changed.
+
+The documents are enriched before the write transaction of the index is opened, so a slow webhook
+doesn't block the other writers of the index, and all the requests of a batch must be answered
+within [`ENRICHMENT_TIMEOUT`]. An answer can't be larger than [`MAX_RESPONSE_SIZE`].
+
+When the webhook can't be reached or gives an unexpected answer, the task the documents belong
+to fails and none of its documents are indexed.
+*/
+
+use std::fs::File;
+use std::io::{BufWriter, Read, Seek};
+use std::time::{Duration, Instant};
+
+use meilisearch_types::milli::documents::{
+    composite_primary_key_fields, obkv_to_object, DocumentsBatchBuilder, DocumentsBatchReader,
+};
+use meilisearch_types::milli::update::{EnrichmentWebhook, Setting, DEFAULT_ENRICHMENT_BATCH_SIZE};
+use meilisearch_types::milli::{self, Object};
+use meilisearch_types::settings::{Settings, Unchecked};
+
+use crate::{Error, Result};
+
+/// The time the webhook has to answer a request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The maximum size of an answer of the webhook, in bytes.
+const MAX_RESPONSE_SIZE: u64 = 100 * 1024 * 1024; // 100 MiB
+
+/// The time the webhook has to answer all the requests of a batch of tasks.
+pub(crate) const ENRICHMENT_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// Return the webhook the documents of a batch must be sent to once the given settings,
+/// processed in the same batch, are applied to the current webhook of the index.
+///
+/// Like [`milli::update::Settings`], the last settings of the batch defining a field win.
+pub(crate) fn webhook_after_settings<'a>(
+    current: Option<EnrichmentWebhook>,
+    settings: impl IntoIterator<Item = &'a Settings<Unchecked>>,
+) -> Option<EnrichmentWebhook> {
+    let mut url = Setting::NotSet;
+    let mut batch_size = Setting::NotSet;
+    for settings in settings {
+        match &settings.enrichment_webhook {
+            Setting::Set(value) => {
+                if !value.url.is_not_set() {
+                    url = value.url.clone();
+                }
+                if !value.batch_size.is_not_set() {
+                    batch_size = value.batch_size;
+                }
+            }
+            Setting::Reset => {
+                url = Setting::Reset;
+                batch_size = Setting::Reset;
+            }
+            Setting::NotSet => (),
+        }
+    }
+
+    let url = match url {
+        Setting::Set(url) => url,
+        Setting::Reset => return None,
+        Setting::NotSet => current.as_ref()?.url.clone(),
+    };
+    let batch_size = match batch_size {
+        Setting::Set(batch_size) => batch_size,
+        Setting::Reset => DEFAULT_ENRICHMENT_BATCH_SIZE,
+        Setting::NotSet => current.map_or(DEFAULT_ENRICHMENT_BATCH_SIZE, |w| w.batch_size),
+    };
+    // an invalid webhook makes the settings, and thus the whole batch, fail
+    Some(EnrichmentWebhook { url, batch_size: batch_size.max(1) })
+}
+
+/// Send the documents of the update file to the webhook and return
+/// a temporary file containing the enriched documents.
+///
+/// The answers of the webhook can't change the fields of the given primary key and must all
+/// be received before the deadline.
+pub(crate) fn enrich_documents(
+    webhook: &EnrichmentWebhook,
+    primary_key: Option<&str>,
+    content_file: File,
+    deadline: Instant,
+    must_stop_processing: impl Fn() -> bool,
+) -> Result<File> {
+    let reader = DocumentsBatchReader::from_reader(content_file).map_err(milli::Error::from)?;
+    let (mut cursor, fields_index) = reader.into_cursor_and_fields_index();
+    // the top-level fields holding the primary key, a nested key is changed along its parent
+    let primary_key_fields: Vec<String> = primary_key
+        .into_iter()
+        .flat_map(|primary_key| {
+            composite_primary_key_fields(primary_key).unwrap_or_else(|| vec![primary_key.into()])
+        })
+        .map(|path| path.split('.').next().unwrap_or(&path).to_string())
+        .collect();
+    let webhook = Webhook { webhook, primary_key_fields: &primary_key_fields, deadline };
+
+    let mut builder = DocumentsBatchBuilder::new(BufWriter::new(tempfile::tempfile()?));
+    let mut batch = Vec::with_capacity(webhook.batch_size);
+    while let Some(document) = cursor.next_document().map_err(milli::Error::from)? {
+        batch.push(obkv_to_object(&document, &fields_index)?);
+        if batch.len() == webhook.batch_size {
+            if must_stop_processing() {
+                return Err(Error::AbortedTask);
+            }
+            webhook.send_batch(&mut batch, &mut builder)?;
+        }
+    }
+    if !batch.is_empty() {
+        webhook.send_batch(&mut batch, &mut builder)?;
+    }
+
+    let mut file = builder.into_inner()?.into_inner().map_err(|e| e.into_error())?;
+    file.rewind()?;
+    Ok(file)
+}
+
+/// The webhook along with the constraints on its answers.
+struct Webhook<'a> {
+    webhook: &'a EnrichmentWebhook,
+    primary_key_fields: &'a [String],
+    deadline: Instant,
+}
+
+impl Webhook<'_> {
+    /// Send the batch of documents to the webhook and append them, enriched, to the builder.
+    fn send_batch(
+        &self,
+        batch: &mut Vec<Object>,
+        builder: &mut DocumentsBatchBuilder<BufWriter<File>>,
+    ) -> Result<()> {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(Error::EnrichmentWebhook(format!(
+                "the documents of the batch weren't enriched within {}s",
+                ENRICHMENT_TIMEOUT.as_secs()
+            )));
+        }
+
+        let body = serde_json::to_string(batch)
+            .map_err(|e| milli::Error::from(milli::InternalError::SerdeJson(e)))?;
+        let response = ureq::post(&self.webhook.url)
+            .timeout(remaining.min(REQUEST_TIMEOUT))
+            .set("Content-Type", "application/json")
+            .send_string(&body)
+            .map_err(|e| Error::EnrichmentWebhook(e.to_string()))?;
+        let mut content = Vec::new();
+        response
+            .into_reader()
+            .take(MAX_RESPONSE_SIZE + 1)
+            .read_to_end(&mut content)
+            .map_err(|e| Error::EnrichmentWebhook(e.to_string()))?;
+        if content.len() as u64 > MAX_RESPONSE_SIZE {
+            return Err(Error::EnrichmentWebhook(format!(
+                "the response is larger than {MAX_RESPONSE_SIZE} bytes"
+            )));
+        }
+        let enrichments: Vec<Object> = serde_json::from_slice(&content).map_err(|e| {
+            Error::EnrichmentWebhook(format!("the response is not an array of objects: {e}"))
+        })?;
+        if enrichments.len() != batch.len() {
+            return Err(Error::EnrichmentWebhook(format!(
+                "{} documents were sent but the response contains {} objects",
+                batch.len(),
+                enrichments.len()
+            )));
+        }
+
+        for (mut document, enrichment) in batch.drain(..).zip(enrichments) {
+            for field in self.primary_key_fields {
+                let value = enrichment.get(field);
+                if value.is_some() && value != document.get(field) {
+                    return Err(Error::EnrichmentWebhook(format!(
+                        "the response changes the primary key field `{field}` of a document"
+                    )));
+                }
+            }
+            document.extend(enrichment);
+            builder.append_json_object(&document)?;
+        }
+        Ok(())
+    }
+}
@@ -1,11 +1,13 @@
 use std::fmt::Display;
+use std::time::Duration;
 
 use meilisearch_types::error::{Code, ErrorCode};
 use meilisearch_types::tasks::{Kind, Status};
 use meilisearch_types::{heed, milli};
 use thiserror::Error;
+use uuid::Uuid;
 
-use crate::TaskId;
+use crate::{ScheduleId, TaskId};
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum DateField {
@@ -74,6 +76,10 @@ pub enum Error {
     SwapIndexesNotFound(Vec<String>),
     #[error("Corrupted dump.")]
     CorruptedDump,
+    #[error("Dump `{0}` not found.")]
+    DumpNotFound(String),
+    #[error("Dump `{dump_uid}` contains {indexes} indexes. Only a dump of a single index can be imported into an index.")]
+    DumpNotOfSingleIndex { dump_uid: String, indexes: usize },
     #[error(
         "Task `{field}` `{date}` is invalid. It should follow the YYYY-MM-DD or RFC 3339 date-time format."
     )]
@@ -106,12 +112,32 @@ pub enum Error {
     InvalidIndexUid { index_uid: String },
     #[error("Task `{0}` not found.")]
     TaskNotFound(TaskId),
+    #[error("Schedule `{0}` not found.")]
+    ScheduleNotFound(ScheduleId),
+    #[error("Task `{task_uid}` cannot be retried because it is {status}. Only the failed and canceled tasks can be retried.")]
+    TaskNotRetryable { task_uid: TaskId, status: Status },
+    #[error("Task `{0}` cannot be retried because its payload is not available anymore. The payload must be sent again.")]
+    RetryLostUpdateFile(TaskId),
+    #[error("Dead letter of task `{0}` not found.")]
+    DeadLetterNotFound(TaskId),
+    #[error("Tasks of type `{0}` cannot be scheduled. Only the `snapshotCreation` and `documentDeletion` by filter tasks can be scheduled.")]
+    UnschedulableTask(Kind),
+    #[error("The interval of a schedule must be at least one second.")]
+    InvalidScheduleInterval,
+    #[error("The interval of a schedule can't be of {0} seconds, the date of its next run would be out of range.")]
+    ScheduleIntervalTooLarge(u64),
     #[error("Query parameters to filter the tasks to delete are missing. Available query parameters are: `uids`, `indexUids`, `statuses`, `types`, `canceledBy`, `beforeEnqueuedAt`, `afterEnqueuedAt`, `beforeStartedAt`, `afterStartedAt`, `beforeFinishedAt`, `afterFinishedAt`.")]
     TaskDeletionWithEmptyQuery,
     #[error("Query parameters to filter the tasks to cancel are missing. Available query parameters are: `uids`, `indexUids`, `statuses`, `types`, `canceledBy`, `beforeEnqueuedAt`, `afterEnqueuedAt`, `beforeStartedAt`, `afterStartedAt`, `beforeFinishedAt`, `afterFinishedAt`.")]
     TaskCancelationWithEmptyQuery,
     #[error("Aborted task")]
     AbortedTask,
+    #[error("The snapshot could not be uploaded to the object storage: {0}.")]
+    SnapshotUpload(String),
+    #[error("The enrichment webhook of the index failed: {0}.")]
+    EnrichmentWebhook(String),
+    #[error("The documents added with the provided API key are indexed above its rate limits. Retry later.")]
+    IndexingThrottled { retry_after: Duration },
 
     #[error(transparent)]
     Dump(#[from] dump::Error),
@@ -121,6 +147,8 @@ pub enum Error {
     Milli(#[from] milli::Error),
     #[error("An unexpected crash occurred when processing the task.")]
     ProcessBatchPanicked,
+    #[error("The update file `{0}` of the task was lost during an unclean shutdown.")]
+    LostUpdateFile(Uuid),
     #[error(transparent)]
     FileStore(#[from] file_store::Error),
     #[error(transparent)]
@@ -171,6 +199,8 @@ impl Error {
             | Error::NoSpaceLeftInTaskQueue
             | Error::SwapIndexesNotFound(_)
             | Error::CorruptedDump
+            | Error::DumpNotFound(_)
+            | Error::DumpNotOfSingleIndex { .. }
             | Error::InvalidTaskDate { .. }
             | Error::InvalidTaskUids { .. }
             | Error::InvalidTaskStatuses { .. }
@@ -178,13 +208,24 @@ impl Error {
             | Error::InvalidTaskCanceledBy { .. }
             | Error::InvalidIndexUid { .. }
             | Error::TaskNotFound(_)
+            | Error::ScheduleNotFound(_)
+            | Error::TaskNotRetryable { .. }
+            | Error::RetryLostUpdateFile(_)
+            | Error::DeadLetterNotFound(_)
+            | Error::UnschedulableTask(_)
+            | Error::InvalidScheduleInterval
+            | Error::ScheduleIntervalTooLarge(_)
             | Error::TaskDeletionWithEmptyQuery
             | Error::TaskCancelationWithEmptyQuery
             | Error::AbortedTask
+            | Error::SnapshotUpload(_)
+            | Error::EnrichmentWebhook(_)
+            | Error::IndexingThrottled { .. }
             | Error::Dump(_)
             | Error::Heed(_)
             | Error::Milli(_)
             | Error::ProcessBatchPanicked
+            | Error::LostUpdateFile(_)
             | Error::FileStore(_)
             | Error::IoError(_)
             | Error::Persist(_)
@@ -222,6 +263,16 @@ impl ErrorCode for Error {
             Error::InvalidTaskCanceledBy { .. } => Code::InvalidTaskCanceledBy,
             Error::InvalidIndexUid { .. } => Code::InvalidIndexUid,
             Error::TaskNotFound(_) => Code::TaskNotFound,
+            Error::ScheduleNotFound(_) => Code::ScheduleNotFound,
+            Error::TaskNotRetryable { .. } => Code::TaskNotRetryable,
+            Error::RetryLostUpdateFile(_) => Code::TaskNotRetryable,
+            Error::DeadLetterNotFound(_) => Code::DeadLetterNotFound,
+            Error::DumpNotFound(_) => Code::DumpNotFound,
+            Error::DumpNotOfSingleIndex { .. } => Code::InvalidDumpUid,
+            Error::UnschedulableTask(_) => Code::InvalidScheduleType,
+            Error::InvalidScheduleInterval | Error::ScheduleIntervalTooLarge(_) => {
+                Code::InvalidScheduleInterval
+            }
             Error::TaskDeletionWithEmptyQuery => Code::MissingTaskFilters,
             Error::TaskCancelationWithEmptyQuery => Code::MissingTaskFilters,
             // TODO: not sure of the Code to use
@@ -229,12 +280,14 @@ impl ErrorCode for Error {
             Error::Dump(e) => e.error_code(),
             Error::Milli(e) => e.error_code(),
             Error::ProcessBatchPanicked => Code::Internal,
+            Error::LostUpdateFile(_) => Code::Internal,
             Error::Heed(e) => e.error_code(),
             Error::HeedTransaction(e) => e.error_code(),
             Error::FileStore(e) => e.error_code(),
             Error::IoError(e) => e.error_code(),
             Error::Persist(e) => e.error_code(),
             Error::FeatureNotEnabled(_) => Code::FeatureNotEnabled,
+            Error::IndexingThrottled { .. } => Code::RateLimitExceeded,
 
             // Irrecoverable errors
             Error::Anyhow(_) => Code::Internal,
@@ -245,9 +298,19 @@ impl ErrorCode for Error {
 
             // This one should never be seen by the end user
             Error::AbortedTask => Code::Internal,
+            Error::SnapshotUpload(_) => Code::Internal,
+            Error::EnrichmentWebhook(_) => Code::EnrichmentWebhookFailed,
 
             #[cfg(test)]
             Error::PlannedFailure => Code::Internal,
         }
     }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Error::WithCustomErrorCode(_, e) => e.retry_after(),
+            Error::IndexingThrottled { retry_after } => Some(*retry_after),
+            _ => None,
+        }
+    }
 }
@@ -1,10 +1,11 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
 use std::{fs, thread};
 
 use meilisearch_types::heed::types::{SerdeJson, Str};
-use meilisearch_types::heed::{Database, Env, RoTxn, RwTxn};
+use meilisearch_types::heed::{CompactionOption, Database, Env, RoTxn, RwTxn};
 use meilisearch_types::milli::update::IndexerConfig;
 use meilisearch_types::milli::{FieldDistribution, Index};
 use serde::{Deserialize, Serialize};
@@ -72,7 +73,9 @@ pub struct IndexMapper {
 
     /// A few types of long running batches of tasks that act on a single index set this field
     /// so that a handle to the index is available from other threads (search) in an optimized manner.
-    currently_updating_index: Arc<RwLock<Option<(String, Index)>>>,
+    ///
+    /// There is one entry per index as the batches of different indexes can be processed concurrently.
+    currently_updating_indexes: Arc<RwLock<HashMap<String, Index>>>,
 }
 
 /// Whether the index is available for use or is forbidden to be inserted back in the index map
@@ -105,6 +108,12 @@ pub struct IndexStats {
     /// As the DB backend does not return to the disk the pages that are not currently used by the DB,
     /// this value is typically smaller than `database_size`.
     pub used_database_size: u64,
+    /// Size of the memory map of the index' DB, in bytes.
+    ///
+    /// The DB is resized once it grows beyond this size, so it can be compared with `database_size`
+    /// to know how close the index is to its next resize.
+    #[serde(default)]
+    pub map_size: u64,
     /// Association of every field name with the number of times it occurs in the documents.
     pub field_distribution: FieldDistribution,
     /// Creation date of the index.
@@ -124,6 +133,7 @@ impl IndexStats {
             number_of_documents: index.number_of_documents(rtxn)?,
             database_size: index.on_disk_size()?,
             used_database_size: index.used_size()?,
+            map_size: index.map_size() as u64,
             field_distribution: index.field_distribution(rtxn)?,
             created_at: index.created_at(rtxn)?,
             updated_at: index.updated_at(rtxn)?,
@@ -155,7 +165,7 @@ impl IndexMapper {
             index_growth_amount,
             enable_mdb_writemap,
             indexer_config: Arc::new(indexer_config),
-            currently_updating_index: Default::default(),
+            currently_updating_indexes: Default::default(),
         })
     }
 
@@ -197,6 +207,45 @@ impl IndexMapper {
         }
     }
 
+    /// Create a new index holding a copy of the content of the `source` index.
+    ///
+    /// Returns an error if an index with the same name already exists.
+    pub fn create_index_from(&self, mut wtxn: RwTxn, name: &str, source: &Index) -> Result<Index> {
+        if self.exists(&wtxn, name)? {
+            return Err(Error::IndexAlreadyExists(name.to_string()));
+        }
+        let uuid = Uuid::new_v4();
+        self.index_mapping.put(&mut wtxn, name, &uuid)?;
+
+        let index_path = self.base_path.join(uuid.to_string());
+        fs::create_dir_all(&index_path)?;
+
+        let index = source
+            .copy_to_file(index_path.join("data.mdb"), CompactionOption::Enabled)
+            .map_err(Error::from)
+            .and_then(|_| {
+                self.index_map.write().unwrap().create(
+                    &uuid,
+                    &index_path,
+                    None,
+                    self.enable_mdb_writemap,
+                    self.index_base_map_size,
+                )
+            });
+        let index = match index {
+            Ok(index) => index,
+            Err(e) => {
+                // the mapping is rolled back with the transaction, only the files remain.
+                let _ = fs::remove_dir_all(&index_path);
+                return Err(e);
+            }
+        };
+
+        wtxn.commit()?;
+
+        Ok(index)
+    }
+
     /// Removes the index from the mapping table and the in-memory index map
     /// but keeps the associated tasks.
     pub fn delete_index(&self, mut wtxn: RwTxn, name: &str) -> Result<()> {
@@ -308,12 +357,8 @@ impl IndexMapper {
 
     /// Return an index, may open it if it wasn't already opened.
     pub fn index(&self, rtxn: &RoTxn, name: &str) -> Result<Index> {
-        if let Some((current_name, current_index)) =
-            self.currently_updating_index.read().unwrap().as_ref()
-        {
-            if current_name == name {
-                return Ok(current_index.clone());
-            }
+        if let Some(current_index) = self.currently_updating_indexes.read().unwrap().get(name) {
+            return Ok(current_index.clone());
         }
 
         let uuid = self
@@ -488,7 +533,11 @@ impl IndexMapper {
         &self.indexer_config
     }
 
-    pub fn set_currently_updating_index(&self, index: Option<(String, Index)>) {
-        *self.currently_updating_index.write().unwrap() = index;
+    pub fn set_currently_updating_index(&self, name: String, index: Index) {
+        self.currently_updating_indexes.write().unwrap().insert(name, index);
+    }
+
+    pub fn unset_currently_updating_index(&self, name: &str) {
+        self.currently_updating_indexes.write().unwrap().remove(name);
     }
 }
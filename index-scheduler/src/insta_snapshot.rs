@@ -14,11 +14,15 @@ pub fn snapshot_index_scheduler(scheduler: &IndexScheduler) -> String {
     scheduler.assert_internally_consistent();
 
     let IndexScheduler {
-        autobatching_enabled,
+        autobatching_limits,
         cleanup_enabled: _,
         must_stop_processing: _,
+        max_concurrent_batches: _,
         processing_tasks,
+        indexing_activity: _,
+        indexing_throttle: _,
         file_store,
+        dead_letter_files: _,
         env,
         all_tasks,
         status,
@@ -28,18 +32,36 @@ pub fn snapshot_index_scheduler(scheduler: &IndexScheduler) -> String {
         enqueued_at,
         started_at,
         finished_at,
+        schedules: _,
+        schedule_uids: _,
+        previews: _,
+        processing_batches: _,
+        dead_letters: _,
+        recovery_report: _,
+        journal: _,
         index_mapper,
         features: _,
         max_number_of_tasks: _,
-        max_number_of_batched_tasks: _,
+        task_retention: _,
+        retry_payload_retention: _,
+        retry_payloads_purged_until: _,
         puffin_frame: _,
         wake_up: _,
+        run_loop: _,
+        batch_threads: _,
+        shutting_down: _,
         dumps_path: _,
         snapshots_path: _,
+        snapshot_retention: _,
+        snapshot_s3: _,
+        snapshot_incremental: _,
         auth_path: _,
         version_file_path: _,
         webhook_url: _,
         webhook_authorization_header: _,
+        index_alert_thresholds: _,
+        index_alert_webhook_url: _,
+        alerted_indexes: _,
         test_breakpoint_sdr: _,
         planned_failures: _,
         run_loop_iteration: _,
@@ -51,6 +73,7 @@ pub fn snapshot_index_scheduler(scheduler: &IndexScheduler) -> String {
     let mut snap = String::new();
 
     let processing_tasks = processing_tasks.read().unwrap().processing.clone();
+    let autobatching_enabled = autobatching_limits.read().unwrap().enabled;
     snap.push_str(&format!("### Autobatching Enabled = {autobatching_enabled}\n"));
     snap.push_str("### Processing Tasks:\n");
     snap.push_str(&snapshot_bitmap(&processing_tasks));
@@ -148,7 +171,9 @@ pub fn snapshot_task(task: &Task) -> String {
         finished_at: _,
         error,
         canceled_by,
+        retried_from,
         details,
+        warnings: _,
         status,
         kind,
     } = task;
@@ -158,6 +183,9 @@ pub fn snapshot_task(task: &Task) -> String {
     if let Some(canceled_by) = canceled_by {
         snap.push_str(&format!("canceled_by: {canceled_by}, "));
     }
+    if let Some(retried_from) = retried_from {
+        snap.push_str(&format!("retried_from: {retried_from}, "));
+    }
     if let Some(error) = error {
         snap.push_str(&format!("error: {error:?}, "));
     }
@@ -214,6 +242,15 @@ fn snapshot_details(d: &Details) -> String {
         Details::IndexSwap { swaps } => {
             format!("{{ swaps: {swaps:?} }}")
         }
+        Details::IndexPreview { source_index_uid, expires_at } => {
+            format!("{{ source_index_uid: {source_index_uid:?}, expires_at: {expires_at:?} }}")
+        }
+        Details::IndexMerge { source_index_uid, on_conflict, merged_documents } => {
+            format!("{{ source_index_uid: {source_index_uid:?}, on_conflict: {on_conflict:?}, merged_documents: {merged_documents:?} }}")
+        }
+        Details::IndexSplit { source_index_uid, original_filter, copied_documents } => {
+            format!("{{ source_index_uid: {source_index_uid:?}, original_filter: {original_filter:?}, copied_documents: {copied_documents:?} }}")
+        }
     }
 }
 
@@ -0,0 +1,137 @@
+/*!
+The journal of the state transitions of the tasks.
+
+Every time the scheduler registers a task, starts processing it, stores its outcome or deletes it,
+the transition is appended to a JSON-lines file before being applied to the tasks database. The
+journal thus allows to reconstruct the decisions of the scheduler, and the transitions that were
+journaled but never committed tell exactly what was lost in an unclean shutdown.
+
+The entries are written to the file before the transaction applying them is committed, and the
+file is synced before the outcome of a batch is committed. When the journal grows over
+[`MAX_JOURNAL_SIZE`], it's moved aside and a new one is started: only the previous journal is kept.
+*/
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use meilisearch_types::tasks::Status;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::{Result, TaskId};
+
+const JOURNAL_FILE_NAME: &str = "tasks-journal.jsonl";
+const PREVIOUS_JOURNAL_FILE_NAME: &str = "tasks-journal.1.jsonl";
+
+/// The size in bytes over which the journal is moved aside.
+const MAX_JOURNAL_SIZE: u64 = 64 * 1024 * 1024;
+
+/// A state transition of a task.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalEntry {
+    #[serde(with = "time::serde::rfc3339")]
+    pub at: OffsetDateTime,
+    pub task_uid: TaskId,
+    /// The new status of the task, `None` when it's deleted.
+    pub status: Option<Status>,
+}
+
+impl JournalEntry {
+    pub fn new(task_uid: TaskId, status: Option<Status>) -> Self {
+        Self { at: OffsetDateTime::now_utc(), task_uid, status }
+    }
+}
+
+struct JournalFile {
+    file: File,
+    len: u64,
+}
+
+/// An append-only file of [`JournalEntry`].
+pub struct TaskJournal {
+    dir: PathBuf,
+    current: Mutex<JournalFile>,
+}
+
+impl TaskJournal {
+    /// Open the journal stored in the given directory, creating it if needed.
+    pub fn open(dir: &Path) -> Result<Self> {
+        let path = dir.join(JOURNAL_FILE_NAME);
+        let mut file = open_append(&path)?;
+        let mut len = file.metadata()?.len();
+        // terminate the line that was partially written when the engine stopped,
+        // the next entries would be unreadable otherwise.
+        if len != 0 && last_byte(&path)? != b'\n' {
+            file.write_all(b"\n")?;
+            len += 1;
+        }
+        Ok(Self { dir: dir.to_path_buf(), current: Mutex::new(JournalFile { file, len }) })
+    }
+
+    /// Append the entries to the journal in a single write.
+    pub fn append(&self, entries: impl IntoIterator<Item = JournalEntry>) -> Result<()> {
+        let mut buffer = Vec::new();
+        for entry in entries {
+            serde_json::to_writer(&mut buffer, &entry).map_err(io::Error::from)?;
+            buffer.push(b'\n');
+        }
+        if buffer.is_empty() {
+            return Ok(());
+        }
+
+        let mut current = self.current.lock().unwrap();
+        if current.len >= MAX_JOURNAL_SIZE {
+            current.file.sync_data()?;
+            fs::rename(
+                self.dir.join(JOURNAL_FILE_NAME),
+                self.dir.join(PREVIOUS_JOURNAL_FILE_NAME),
+            )?;
+            *current =
+                JournalFile { file: open_append(&self.dir.join(JOURNAL_FILE_NAME))?, len: 0 };
+        }
+        current.file.write_all(&buffer)?;
+        current.len += buffer.len() as u64;
+        Ok(())
+    }
+
+    /// Make sure the entries written so far are on disk.
+    pub fn sync(&self) -> Result<()> {
+        self.current.lock().unwrap().file.sync_data()?;
+        Ok(())
+    }
+
+    /// Return the entries of the previous and current journals, from the oldest to the most recent.
+    ///
+    /// A line that was only partially written when the engine stopped is ignored.
+    pub fn entries(&self) -> Result<Vec<JournalEntry>> {
+        let mut entries = Vec::new();
+        for name in [PREVIOUS_JOURNAL_FILE_NAME, JOURNAL_FILE_NAME] {
+            let file = match File::open(self.dir.join(name)) {
+                Ok(file) => file,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e.into()),
+            };
+            for line in BufReader::new(file).lines() {
+                if let Ok(entry) = serde_json::from_str(&line?) {
+                    entries.push(entry);
+                }
+            }
+        }
+        Ok(entries)
+    }
+}
+
+fn open_append(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+fn last_byte(path: &Path) -> io::Result<u8> {
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::End(-1))?;
+    let mut byte = [0];
+    file.read_exact(&mut byte)?;
+    Ok(byte[0])
+}
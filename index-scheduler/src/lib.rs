@@ -11,6 +11,7 @@ scheduler waits to be woken up to process new tasks. It wakes up when:
 1. it is launched for the first time
 2. a new task is registered
 3. a batch of tasks has been processed
+4. a schedule is due and must be materialized into a new task
 
 It is only within this thread that the scheduler is allowed to process tasks.
 On the other hand, the publicly accessible methods of the scheduler can be
@@ -20,34 +21,46 @@ content of the scheduler or enqueue new tasks.
 
 mod autobatcher;
 mod batch;
+mod dead_letters;
+mod enrichment;
 pub mod error;
 mod features;
 mod index_mapper;
 #[cfg(test)]
 mod insta_snapshot;
+mod journal;
 mod lru;
+mod previews;
+mod recovery;
+mod s3;
+mod schedules;
+mod throttle;
 mod utils;
 pub mod uuid_codec;
 
 pub type Result<T> = std::result::Result<T, Error>;
 pub type TaskId = u32;
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::File;
 use std::io::{self, BufReader, Read};
 use std::ops::{Bound, RangeBounds};
+use std::panic::{self, AssertUnwindSafe};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::Ordering::{self, Relaxed};
 use std::sync::atomic::{AtomicBool, AtomicU32};
-use std::sync::{Arc, RwLock};
-use std::time::Duration;
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 
+pub use dead_letters::DeadLetter;
 use dump::{KindDump, TaskDump, UpdateFile};
 pub use error::Error;
 pub use features::RoFeatures;
 use file_store::FileStore;
 use flate2::bufread::GzEncoder;
 use flate2::Compression;
+use journal::{JournalEntry, TaskJournal};
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::features::{InstanceTogglableFeatures, RuntimeTogglableFeatures};
 use meilisearch_types::heed::byteorder::BE;
@@ -58,12 +71,19 @@ use meilisearch_types::milli::update::IndexerConfig;
 use meilisearch_types::milli::vector::{Embedder, EmbedderOptions, EmbeddingConfigs};
 use meilisearch_types::milli::{self, CboRoaringBitmapCodec, Index, RoaringBitmapCodec, BEU32};
 use meilisearch_types::task_view::TaskView;
-use meilisearch_types::tasks::{Kind, KindWithContent, Status, Task};
+use meilisearch_types::tasks::{Details, Kind, KindWithContent, Status, Task, TaskWarning};
+pub use previews::IndexPreview;
 use puffin::FrameView;
 use rayon::current_num_threads;
 use rayon::prelude::{IntoParallelIterator, ParallelIterator};
-use roaring::RoaringBitmap;
+use recovery::InterruptedBatch;
+pub use recovery::RecoveryReport;
+use roaring::{MultiOps, RoaringBitmap};
+pub use s3::S3Options;
+pub use schedules::{Schedule, ScheduleId};
+use serde::{Deserialize, Serialize};
 use synchronoise::SignalEvent;
+use throttle::IndexingThrottle;
 use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 use utils::{filter_out_references_to_newer_tasks, keep_tasks_within_datetimes, map_bound};
@@ -156,50 +176,99 @@ impl Query {
 }
 
 #[derive(Debug, Clone)]
-struct ProcessingTasks {
-    /// The date and time at which the indexation started.
+struct ProcessingBatch {
+    /// The date and time at which the batch started.
     started_at: OffsetDateTime,
-    /// The list of tasks ids that are currently running.
+    /// The list of tasks ids of the batch.
+    ids: RoaringBitmap,
+    /// The index the batch works on, `None` if it can't run concurrently to other batches.
+    index_uid: Option<String>,
+    /// The flag raised to stop this batch only.
+    must_stop_processing: MustStopProcessing,
+    /// Set to `true` once the batch stopped without finishing its tasks.
+    ///
+    /// Its tasks are still reported as processing until the next batch starts, this is
+    /// required by the task cancelation to know when the canceled tasks started.
+    aborted: bool,
+}
+
+#[derive(Debug, Clone)]
+struct ProcessingTasks {
+    /// The batches that are currently processing.
+    batches: Vec<ProcessingBatch>,
+    /// The list of tasks ids that are currently running, in any batch.
     processing: RoaringBitmap,
 }
 
 impl ProcessingTasks {
     /// Creates an empty `ProcessingAt` struct.
     fn new() -> ProcessingTasks {
-        ProcessingTasks { started_at: OffsetDateTime::now_utc(), processing: RoaringBitmap::new() }
+        ProcessingTasks { batches: Vec::new(), processing: RoaringBitmap::new() }
+    }
+
+    /// Stores a new processing batch along with the date time at which it started.
+    ///
+    /// The aborted batches are forgotten at this point.
+    fn start_processing(&mut self, batch: ProcessingBatch) {
+        self.batches.retain(|batch| !batch.aborted);
+        self.batches.push(batch);
+        self.processing = self.batches.iter().map(|batch| &batch.ids).union();
     }
 
-    /// Stores the currently processing tasks, and the date time at which it started.
-    fn start_processing_at(&mut self, started_at: OffsetDateTime, processing: RoaringBitmap) {
-        self.started_at = started_at;
-        self.processing = processing;
+    /// Removes the batch containing these tasks from the processing batches
+    /// and returns the tasks that are not processing anymore.
+    fn stop_processing(&mut self, ids: &RoaringBitmap) -> RoaringBitmap {
+        let (stopped, batches): (Vec<_>, Vec<_>) =
+            self.batches.drain(..).partition(|batch| batch.ids.is_subset(ids));
+        self.batches = batches;
+        self.processing = self.batches.iter().map(|batch| &batch.ids).union();
+        stopped.into_iter().map(|batch| batch.ids).union()
     }
 
-    /// Set the processing tasks to an empty list
-    fn stop_processing(&mut self) -> RoaringBitmap {
-        std::mem::take(&mut self.processing)
+    /// Marks the batch containing these tasks as aborted, it doesn't block the other batches anymore.
+    fn abort_processing(&mut self, ids: &RoaringBitmap) {
+        for batch in self.batches.iter_mut().filter(|batch| batch.ids.is_subset(ids)) {
+            batch.aborted = true;
+        }
+    }
+
+    /// Returns the batches that are still running.
+    fn running(&self) -> impl Iterator<Item = &ProcessingBatch> {
+        self.batches.iter().filter(|batch| !batch.aborted)
     }
 
-    /// Returns `true` if there, at least, is one task that is currently processing that we must stop.
-    fn must_cancel_processing_tasks(&self, canceled_tasks: &RoaringBitmap) -> bool {
-        !self.processing.is_disjoint(canceled_tasks)
+    /// Returns the date time at which the batch containing this task started, if it is processing.
+    fn started_at(&self, task_id: TaskId) -> Option<OffsetDateTime> {
+        self.batches.iter().find(|batch| batch.ids.contains(task_id)).map(|batch| batch.started_at)
+    }
+
+    /// Stops the batches containing at least one of the canceled tasks, the other ones keep running.
+    fn cancel_processing_tasks(&self, canceled_tasks: &RoaringBitmap) {
+        for batch in self.batches.iter().filter(|batch| !batch.ids.is_disjoint(canceled_tasks)) {
+            batch.must_stop_processing.must_stop();
+        }
     }
 }
 
 #[derive(Default, Clone, Debug)]
-struct MustStopProcessing(Arc<AtomicBool>);
+struct MustStopProcessing {
+    flag: Arc<AtomicBool>,
+    /// The flag this one was derived from, raising it stops this one too.
+    parent: Option<Arc<AtomicBool>>,
+}
 
 impl MustStopProcessing {
     fn get(&self) -> bool {
-        self.0.load(Relaxed)
+        self.flag.load(Relaxed) || self.parent.as_ref().is_some_and(|parent| parent.load(Relaxed))
     }
 
     fn must_stop(&self) {
-        self.0.store(true, Relaxed);
+        self.flag.store(true, Relaxed);
     }
 
-    fn reset(&self) {
-        self.0.store(false, Relaxed);
+    /// A flag that can be raised on its own or along with this one.
+    fn child(&self) -> MustStopProcessing {
+        MustStopProcessing { flag: Arc::default(), parent: Some(self.flag.clone()) }
     }
 }
 
@@ -213,6 +282,11 @@ mod db_name {
     pub const ENQUEUED_AT: &str = "enqueued-at";
     pub const STARTED_AT: &str = "started-at";
     pub const FINISHED_AT: &str = "finished-at";
+    pub const SCHEDULES: &str = "schedules";
+    pub const SCHEDULE_UIDS: &str = "schedule-uids";
+    pub const PREVIEWS: &str = "previews";
+    pub const PROCESSING_BATCHES: &str = "processing-batches";
+    pub const DEAD_LETTERS: &str = "dead-letters";
 }
 
 #[cfg(test)]
@@ -241,10 +315,21 @@ pub struct IndexSchedulerOptions {
     pub tasks_path: PathBuf,
     /// The path to the file store containing the files associated to the tasks.
     pub update_file_path: PathBuf,
+    /// The path to the folder containing the payloads of the document additions that failed.
+    pub dead_letters_path: PathBuf,
     /// The path to the folder containing meilisearch's indexes.
     pub indexes_path: PathBuf,
     /// The path to the folder containing the snapshots.
     pub snapshots_path: PathBuf,
+    /// The maximum number of snapshots kept in the snapshots folder, the oldest ones are deleted.
+    ///
+    /// When `None`, a single snapshot is kept and overwritten by the next one.
+    pub snapshot_retention: Option<usize>,
+    /// The object storage on which every snapshot is uploaded once created.
+    pub snapshot_s3: Option<S3Options>,
+    /// Set to `true` to only copy the indexes updated since the previous snapshot, the other
+    /// indexes are restored from the previous snapshots. The updated indexes are copied entirely.
+    pub snapshot_incremental: bool,
     /// The path to the folder containing the dumps.
     pub dumps_path: PathBuf,
     /// The URL on which we must send the tasks statuses
@@ -266,34 +351,171 @@ pub struct IndexSchedulerOptions {
     /// Set to `true` iff the index scheduler is allowed to automatically
     /// batch tasks together, to process multiple tasks at once.
     pub autobatching_enabled: bool,
+    /// The maximum number of batches processed at the same time.
+    ///
+    /// Only the batches working on different indexes are processed concurrently,
+    /// they share the indexing memory of the `indexer_config`.
+    pub max_concurrent_batches: usize,
     /// Set to `true` iff the index scheduler is allowed to automatically
     /// delete the finished tasks when there are too many tasks.
     pub cleanup_enabled: bool,
     /// The maximum number of tasks stored in the task queue before starting
     /// to auto schedule task deletions.
     pub max_number_of_tasks: usize,
+    /// The retention policy applied to the finished tasks when the cleanup is enabled.
+    pub task_retention: TaskRetentionPolicy,
+    /// How long the update files of the failed and canceled tasks are kept to retry them.
+    pub retry_payload_retention: Duration,
+    /// The size or number of documents above which an index is reported as reaching its capacity.
+    pub index_alert_thresholds: IndexAlertThresholds,
+    /// The URL notified whenever an index starts exceeding one of the alert thresholds.
+    pub index_alert_webhook_url: Option<String>,
     /// If the autobatcher is allowed to automatically batch tasks
     /// it will only batch this defined number of tasks at once.
     pub max_number_of_batched_tasks: usize,
+    /// If the autobatcher is allowed to automatically batch tasks
+    /// it will stop adding tasks to a batch once the total size, in bytes,
+    /// of their payloads exceeds this limit.
+    pub max_batched_payload_size: Option<u64>,
     /// The experimental features enabled for this instance.
     pub instance_features: InstanceTogglableFeatures,
 }
 
+/// Defines after how long or how many finished tasks the scheduler automatically deletes them.
+///
+/// The policy is only applied when the automatic cleanup of the task queue is enabled.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TaskRetentionPolicy {
+    /// The maximum number of finished tasks to keep.
+    pub max_count: Option<u64>,
+    /// The maximum time a task is kept after it finished.
+    pub max_age: Option<Duration>,
+}
+
+/// Defines the size or number of documents above which an index is reported as reaching its capacity.
+///
+/// It gives the time to grow the instance before an indexation fails with `MaxDatabaseSizeReached`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct IndexAlertThresholds {
+    /// The maximum size, in bytes, of the index' DB.
+    pub database_size: Option<u64>,
+    /// The maximum number of documents of the index.
+    pub number_of_documents: Option<u64>,
+}
+
+impl IndexAlertThresholds {
+    /// Returns `true` if at least one threshold is defined.
+    pub fn is_enabled(&self) -> bool {
+        self.database_size.is_some() || self.number_of_documents.is_some()
+    }
+
+    /// Returns whether the stats of an index exceed one of the thresholds.
+    pub fn exceeded_by(&self, stats: &index_mapper::IndexStats) -> bool {
+        self.database_size.map_or(false, |max| stats.database_size > max)
+            || self.number_of_documents.map_or(false, |max| stats.number_of_documents > max)
+    }
+
+    /// Returns the warnings raised by the stats of an index.
+    ///
+    /// An index whose DB almost fills its memory map is reported even if no threshold is defined.
+    pub fn warnings(&self, stats: &index_mapper::IndexStats) -> Vec<TaskWarning> {
+        let mut warnings = Vec::new();
+        if self.exceeded_by(stats) {
+            warnings.push(TaskWarning::IndexAlertThresholdExceeded {
+                number_of_documents: stats.number_of_documents,
+                database_size: stats.database_size,
+                max_number_of_documents: self.number_of_documents,
+                max_database_size: self.database_size,
+            });
+        }
+        // the map size is unknown for the stats computed before it was stored
+        if stats.map_size != 0
+            && stats.database_size as f64 >= stats.map_size as f64 * INDEX_MAP_SIZE_WARNING_RATIO
+        {
+            warnings.push(TaskWarning::IndexMapSizeAlmostReached {
+                database_size: stats.database_size,
+                map_size: stats.map_size,
+            });
+        }
+        warnings
+    }
+}
+
+/// The part of its memory map an index' DB must fill to be reported as almost full.
+const INDEX_MAP_SIZE_WARNING_RATIO: f64 = 0.9;
+
+/// The limits the autobatcher must honor when building a batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutobatchingLimits {
+    /// Set to `false` to process the tasks one by one.
+    pub enabled: bool,
+    /// The maximum number of tasks that will be batched together.
+    pub max_batched_tasks: usize,
+    /// The maximum total size, in bytes, of the payloads batched together.
+    ///
+    /// A batch always contains at least one task, even if its payload alone exceeds the limit.
+    pub max_batched_payload_size: Option<u64>,
+}
+
+/// Whether the scheduler is ready to serve the requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Readiness {
+    /// The tasks database can be read.
+    pub database: bool,
+    /// The run loop processing the tasks is running.
+    pub scheduler: bool,
+    /// No dump import is enqueued or processing.
+    pub dump_import_finished: bool,
+}
+
+impl Readiness {
+    pub fn is_ready(&self) -> bool {
+        self.database && self.scheduler && self.dump_import_finished
+    }
+}
+
+/// The work done by the scheduler since it started.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct IndexingActivity {
+    /// The number of documents indexed by the document addition tasks.
+    pub indexed_documents: u64,
+    /// The number of batches that finished processing, successfully or not.
+    pub processed_batches: u64,
+    /// The time spent processing these batches.
+    pub processing_time: Duration,
+}
+
 /// Structure which holds meilisearch's indexes and schedules the tasks
 /// to be performed on them.
 pub struct IndexScheduler {
     /// The LMDB environment which the DBs are associated with.
     pub(crate) env: Env,
 
-    /// A boolean that can be set to true to stop the currently processing tasks.
+    /// A boolean that can be set to true to stop the batch processed by this instance.
+    ///
+    /// Raising the one of the scheduler stops all the batches.
     pub(crate) must_stop_processing: MustStopProcessing,
 
+    /// The maximum number of batches processed at the same time.
+    pub(crate) max_concurrent_batches: usize,
+
     /// The list of tasks currently processing
     pub(crate) processing_tasks: Arc<RwLock<ProcessingTasks>>,
 
+    /// The work done since the scheduler started, exposed by the metrics.
+    pub(crate) indexing_activity: Arc<RwLock<IndexingActivity>>,
+
+    /// The pace at which the documents of the keys limiting their indexing throughput are batched.
+    pub(crate) indexing_throttle: Arc<IndexingThrottle>,
+
     /// The list of files referenced by the tasks
     pub(crate) file_store: FileStore,
 
+    /// The payloads of the document additions that failed.
+    pub(crate) dead_letter_files: FileStore,
+
     // The main database, it contains all the tasks accessible by their Id.
     pub(crate) all_tasks: Database<BEU32, SerdeJson<Task>>,
 
@@ -317,6 +539,26 @@ pub struct IndexScheduler {
     /// Store the task ids of tasks which finished at a specific date
     pub(crate) finished_at: Database<BEI128, CboRoaringBitmapCodec>,
 
+    /// Store the recurring tasks registered by the users, by uid.
+    pub(crate) schedules: Database<BEU32, SerdeJson<Schedule>>,
+    /// Store the uid of the next registered schedule, the uids of the deleted schedules are never reused.
+    pub(crate) schedule_uids: Database<Str, BEU32>,
+
+    /// Store the temporary copies of the indexes, by preview index uid.
+    pub(crate) previews: Database<Str, SerdeJson<IndexPreview>>,
+
+    /// Store the batches being processed, by smallest task id.
+    pub(crate) processing_batches: Database<BEU32, SerdeJson<InterruptedBatch>>,
+
+    /// Store the failed document additions whose payload is kept, by task id.
+    pub(crate) dead_letters: Database<BEU32, SerdeJson<DeadLetter>>,
+
+    /// What was done to recover from the last unclean shutdown when the scheduler was opened.
+    recovery_report: Option<Arc<RecoveryReport>>,
+
+    /// The journal of the state transitions of the tasks.
+    pub(crate) journal: Arc<TaskJournal>,
+
     /// In charge of creating, opening, storing and returning indexes.
     pub(crate) index_mapper: IndexMapper,
 
@@ -326,8 +568,20 @@ pub struct IndexScheduler {
     /// Get a signal when a batch needs to be processed.
     pub(crate) wake_up: Arc<SignalEvent>,
 
-    /// Whether auto-batching is enabled or not.
-    pub(crate) autobatching_enabled: bool,
+    /// Notified every time a batch is processed and its tasks are finished.
+    finished_batches: Arc<tokio::sync::watch::Sender<()>>,
+
+    /// The thread of the run loop, once started.
+    run_loop: Arc<OnceLock<JoinHandle<()>>>,
+
+    /// The threads of the batches processed in the background, joined on shutdown.
+    batch_threads: Arc<Mutex<Vec<JoinHandle<()>>>>,
+
+    /// Set once the instance is shutting down, no new batch is started afterward.
+    shutting_down: Arc<AtomicBool>,
+
+    /// The limits applied by the autobatcher, can be updated at runtime.
+    pub(crate) autobatching_limits: Arc<RwLock<AutobatchingLimits>>,
 
     /// Whether we should automatically cleanup the task queue or not.
     pub(crate) cleanup_enabled: bool,
@@ -336,14 +590,27 @@ pub struct IndexScheduler {
     /// the finished tasks automatically.
     pub(crate) max_number_of_tasks: usize,
 
-    /// The maximum number of tasks that will be batched together.
-    pub(crate) max_number_of_batched_tasks: usize,
+    /// The retention policy of the finished tasks.
+    pub(crate) task_retention: TaskRetentionPolicy,
+
+    /// How long the update files of the failed and canceled tasks are kept to retry them.
+    pub(crate) retry_payload_retention: Duration,
+    /// The finish date, in nanoseconds, before which the update files kept to retry the tasks
+    /// have been deleted.
+    retry_payloads_purged_until: Arc<Mutex<i128>>,
 
     /// The webhook url we should send tasks to after processing every batches.
     pub(crate) webhook_url: Option<String>,
     /// The Authorization header to send to the webhook URL.
     pub(crate) webhook_authorization_header: Option<String>,
 
+    /// The thresholds above which an index is reported as reaching its capacity.
+    pub(crate) index_alert_thresholds: IndexAlertThresholds,
+    /// The URL notified when an index starts exceeding the alert thresholds.
+    pub(crate) index_alert_webhook_url: Option<String>,
+    /// The indexes currently exceeding the alert thresholds, they're only reported once.
+    pub(crate) alerted_indexes: Arc<RwLock<HashSet<String>>>,
+
     /// A frame to output the indexation profiling files to disk.
     pub(crate) puffin_frame: Arc<puffin::GlobalFrameView>,
 
@@ -352,6 +619,12 @@ pub struct IndexScheduler {
 
     /// The path used to create the snapshots.
     pub(crate) snapshots_path: PathBuf,
+    /// The maximum number of snapshots kept in the snapshots path.
+    pub(crate) snapshot_retention: Option<usize>,
+    /// The object storage the snapshots are uploaded to.
+    pub(crate) snapshot_s3: Option<S3Options>,
+    /// Whether the snapshots only contain the indexes updated since the previous one.
+    pub(crate) snapshot_incremental: bool,
 
     /// The path to the folder containing the auth LMDB env.
     pub(crate) auth_path: PathBuf,
@@ -385,8 +658,12 @@ impl IndexScheduler {
         IndexScheduler {
             env: self.env.clone(),
             must_stop_processing: self.must_stop_processing.clone(),
+            max_concurrent_batches: self.max_concurrent_batches,
             processing_tasks: self.processing_tasks.clone(),
+            indexing_activity: self.indexing_activity.clone(),
+            indexing_throttle: self.indexing_throttle.clone(),
             file_store: self.file_store.clone(),
+            dead_letter_files: self.dead_letter_files.clone(),
             all_tasks: self.all_tasks,
             status: self.status,
             kind: self.kind,
@@ -395,19 +672,38 @@ impl IndexScheduler {
             enqueued_at: self.enqueued_at,
             started_at: self.started_at,
             finished_at: self.finished_at,
+            schedules: self.schedules,
+            schedule_uids: self.schedule_uids,
+            previews: self.previews,
+            processing_batches: self.processing_batches,
+            dead_letters: self.dead_letters,
+            recovery_report: self.recovery_report.clone(),
+            journal: self.journal.clone(),
             index_mapper: self.index_mapper.clone(),
             wake_up: self.wake_up.clone(),
-            autobatching_enabled: self.autobatching_enabled,
+            finished_batches: self.finished_batches.clone(),
+            run_loop: self.run_loop.clone(),
+            batch_threads: self.batch_threads.clone(),
+            shutting_down: self.shutting_down.clone(),
+            autobatching_limits: self.autobatching_limits.clone(),
             cleanup_enabled: self.cleanup_enabled,
             max_number_of_tasks: self.max_number_of_tasks,
-            max_number_of_batched_tasks: self.max_number_of_batched_tasks,
+            task_retention: self.task_retention,
+            retry_payload_retention: self.retry_payload_retention,
+            retry_payloads_purged_until: self.retry_payloads_purged_until.clone(),
             puffin_frame: self.puffin_frame.clone(),
             snapshots_path: self.snapshots_path.clone(),
+            snapshot_retention: self.snapshot_retention,
+            snapshot_s3: self.snapshot_s3.clone(),
+            snapshot_incremental: self.snapshot_incremental,
             dumps_path: self.dumps_path.clone(),
             auth_path: self.auth_path.clone(),
             version_file_path: self.version_file_path.clone(),
             webhook_url: self.webhook_url.clone(),
             webhook_authorization_header: self.webhook_authorization_header.clone(),
+            index_alert_thresholds: self.index_alert_thresholds,
+            index_alert_webhook_url: self.index_alert_webhook_url.clone(),
+            alerted_indexes: self.alerted_indexes.clone(),
             embedders: self.embedders.clone(),
             #[cfg(test)]
             test_breakpoint_sdr: self.test_breakpoint_sdr.clone(),
@@ -453,14 +749,22 @@ impl IndexScheduler {
             )
         };
 
+        let journal = TaskJournal::open(&options.tasks_path)?;
         let env = heed::EnvOpenOptions::new()
-            .max_dbs(11)
+            .max_dbs(16)
             .map_size(budget.task_db_size)
             .open(options.tasks_path)?;
 
         let features = features::FeatureData::new(&env, options.instance_features)?;
 
+        // The batches processed concurrently share the indexing memory.
+        let max_concurrent_batches = options.max_concurrent_batches.max(1);
+        let mut indexer_config = options.indexer_config;
+        indexer_config.max_memory =
+            indexer_config.max_memory.map(|max_memory| max_memory / max_concurrent_batches);
+
         let file_store = FileStore::new(&options.update_file_path)?;
+        let dead_letter_files = FileStore::new(&options.dead_letters_path)?;
 
         let mut wtxn = env.write_txn()?;
         let all_tasks = env.create_database(&mut wtxn, Some(db_name::ALL_TASKS))?;
@@ -471,13 +775,23 @@ impl IndexScheduler {
         let enqueued_at = env.create_database(&mut wtxn, Some(db_name::ENQUEUED_AT))?;
         let started_at = env.create_database(&mut wtxn, Some(db_name::STARTED_AT))?;
         let finished_at = env.create_database(&mut wtxn, Some(db_name::FINISHED_AT))?;
+        let schedules = env.create_database(&mut wtxn, Some(db_name::SCHEDULES))?;
+        let schedule_uids = env.create_database(&mut wtxn, Some(db_name::SCHEDULE_UIDS))?;
+        let previews = env.create_database(&mut wtxn, Some(db_name::PREVIEWS))?;
+        let processing_batches =
+            env.create_database(&mut wtxn, Some(db_name::PROCESSING_BATCHES))?;
+        let dead_letters = env.create_database(&mut wtxn, Some(db_name::DEAD_LETTERS))?;
         wtxn.commit()?;
 
         // allow unreachable_code to get rids of the warning in the case of a test build.
-        let this = Self {
+        let mut this = Self {
             must_stop_processing: MustStopProcessing::default(),
+            max_concurrent_batches,
             processing_tasks: Arc::new(RwLock::new(ProcessingTasks::new())),
+            indexing_activity: Default::default(),
+            indexing_throttle: Default::default(),
             file_store,
+            dead_letter_files,
             all_tasks,
             status,
             kind,
@@ -486,6 +800,13 @@ impl IndexScheduler {
             enqueued_at,
             started_at,
             finished_at,
+            schedules,
+            schedule_uids,
+            previews,
+            processing_batches,
+            dead_letters,
+            recovery_report: None,
+            journal: Arc::new(journal),
             index_mapper: IndexMapper::new(
                 &env,
                 options.indexes_path,
@@ -493,22 +814,38 @@ impl IndexScheduler {
                 options.index_growth_amount,
                 budget.index_count,
                 options.enable_mdb_writemap,
-                options.indexer_config,
+                indexer_config,
             )?,
             env,
             // we want to start the loop right away in case meilisearch was ctrl+Ced while processing things
             wake_up: Arc::new(SignalEvent::auto(true)),
+            finished_batches: Arc::new(tokio::sync::watch::channel(()).0),
+            run_loop: Default::default(),
+            batch_threads: Default::default(),
+            shutting_down: Default::default(),
             puffin_frame: Arc::new(puffin::GlobalFrameView::default()),
-            autobatching_enabled: options.autobatching_enabled,
+            autobatching_limits: Arc::new(RwLock::new(AutobatchingLimits {
+                enabled: options.autobatching_enabled,
+                max_batched_tasks: options.max_number_of_batched_tasks,
+                max_batched_payload_size: options.max_batched_payload_size,
+            })),
             cleanup_enabled: options.cleanup_enabled,
             max_number_of_tasks: options.max_number_of_tasks,
-            max_number_of_batched_tasks: options.max_number_of_batched_tasks,
+            task_retention: options.task_retention,
+            retry_payload_retention: options.retry_payload_retention,
+            retry_payloads_purged_until: Arc::default(),
             dumps_path: options.dumps_path,
             snapshots_path: options.snapshots_path,
+            snapshot_retention: options.snapshot_retention,
+            snapshot_s3: options.snapshot_s3,
+            snapshot_incremental: options.snapshot_incremental,
             auth_path: options.auth_path,
             version_file_path: options.version_file_path,
             webhook_url: options.webhook_url,
             webhook_authorization_header: options.webhook_authorization_header,
+            index_alert_thresholds: options.index_alert_thresholds,
+            index_alert_webhook_url: options.index_alert_webhook_url,
+            alerted_indexes: Default::default(),
             embedders: Default::default(),
 
             #[cfg(test)]
@@ -520,6 +857,7 @@ impl IndexScheduler {
             features,
         };
 
+        this.recovery_report = this.recover()?.map(Arc::new);
         this.run();
         Ok(this)
     }
@@ -531,6 +869,78 @@ impl IndexScheduler {
         Ok(())
     }
 
+    /// Return whether the scheduler is ready to serve the requests: its database can be read,
+    /// its run loop is running and it's not importing a dump.
+    pub fn readiness(&self) -> Readiness {
+        let scheduler = !self.shutting_down.load(Relaxed)
+            && self.run_loop.get().is_some_and(|run_loop| !run_loop.is_finished());
+        let dump_import_finished = || -> Result<bool> {
+            let rtxn = self.env.read_txn()?;
+            // the processing tasks are still enqueued in the database.
+            let enqueued = self.get_status(&rtxn, Status::Enqueued)?;
+            Ok((self.get_kind(&rtxn, Kind::DumpImport)? & enqueued).is_empty())
+        }();
+        Readiness {
+            database: self.health().is_ok(),
+            scheduler,
+            dump_import_finished: dump_import_finished.unwrap_or(false),
+        }
+    }
+
+    /// Stop starting new batches and wait for the processing ones to finish.
+    ///
+    /// The batches still processing after the timeout are aborted: nothing they did is
+    /// committed and their tasks will be processed again from the start on the next launch.
+    /// Returns `false` if some batches had to be aborted or their threads didn't exit in time.
+    pub fn shutdown(&self, timeout: Duration) -> bool {
+        self.shutting_down.store(true, Relaxed);
+        let batches_are_running =
+            || self.processing_tasks.read().unwrap().running().next().is_some();
+        let wait_until = |deadline: Instant| {
+            while batches_are_running() && Instant::now() < deadline {
+                std::thread::sleep(Duration::from_millis(50));
+            }
+        };
+
+        wait_until(Instant::now() + timeout);
+        if !batches_are_running() {
+            // the batches are finished, their threads are only exiting.
+            return self.join_batch_threads(Instant::now() + Duration::from_secs(10));
+        }
+
+        tracing::warn!("Aborting the batches still processing after {timeout:?}.");
+        // the flag of every batch is derived from the one of the scheduler.
+        self.must_stop_processing.must_stop();
+        // the indexing only checks whether it must stop between its steps.
+        let deadline = Instant::now() + Duration::from_secs(10);
+        wait_until(deadline);
+        self.join_batch_threads(deadline);
+        false
+    }
+
+    /// Wait until the deadline for the threads of the batches processed in the background to exit,
+    /// the ones still running after it are detached.
+    ///
+    /// Returns `false` if some threads had to be detached.
+    fn join_batch_threads(&self, deadline: Instant) -> bool {
+        let threads = std::mem::take(&mut *self.batch_threads.lock().unwrap());
+        while threads.iter().any(|thread| !thread.is_finished()) && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(50));
+        }
+
+        let (finished, running): (Vec<_>, Vec<_>) =
+            threads.into_iter().partition(|thread| thread.is_finished());
+        for thread in finished {
+            if thread.join().is_err() {
+                tracing::error!("A batch thread panicked.");
+            }
+        }
+        if !running.is_empty() {
+            tracing::error!("Detaching {} batch threads still running.", running.len());
+        }
+        running.is_empty()
+    }
+
     fn index_budget(
         tasks_path: &Path,
         base_map_size: usize,
@@ -610,7 +1020,7 @@ impl IndexScheduler {
     /// only once per index scheduler.
     fn run(&self) {
         let run = self.private_clone();
-        std::thread::Builder::new()
+        let run_loop = std::thread::Builder::new()
             .name(String::from("scheduler"))
             .spawn(move || {
                 #[cfg(test)]
@@ -625,7 +1035,18 @@ impl IndexScheduler {
 
                     match run.tick() {
                         Ok(TickOutcome::TickAgain(_)) => (),
-                        Ok(TickOutcome::WaitForSignal) => run.wake_up.wait(),
+                        Ok(TickOutcome::WaitForSignal) => match run.time_until_next_timer() {
+                            // we must wake up by ourselves when the next schedule is due
+                            // or the next preview expires.
+                            Ok(Some(delay)) => {
+                                run.wake_up.wait_timeout(delay);
+                            }
+                            Ok(None) => run.wake_up.wait(),
+                            Err(e) => {
+                                tracing::error!("{e}");
+                                run.wake_up.wait();
+                            }
+                        },
                         Err(e) => {
                             tracing::error!("{e}");
                             // Wait one second when an irrecoverable error occurs.
@@ -662,6 +1083,7 @@ impl IndexScheduler {
                 }
             })
             .unwrap();
+        let _ = self.run_loop.set(run_loop);
     }
 
     pub fn indexer_config(&self) -> &IndexerConfig {
@@ -722,9 +1144,8 @@ impl IndexScheduler {
 
     /// Return the task ids matched by the given query from the index scheduler's point of view.
     pub(crate) fn get_task_ids(&self, rtxn: &RoTxn, query: &Query) -> Result<RoaringBitmap> {
-        let ProcessingTasks {
-            started_at: started_at_processing, processing: processing_tasks, ..
-        } = self.processing_tasks.read().unwrap().clone();
+        let ProcessingTasks { batches: processing_batches, processing: processing_tasks } =
+            self.processing_tasks.read().unwrap().clone();
 
         let mut tasks = self.all_task_ids(rtxn)?;
 
@@ -796,17 +1217,19 @@ impl IndexScheduler {
                 (&tasks - &processing_tasks, &tasks & &processing_tasks);
 
             // special case for Processing tasks
-            // A closure that clears the filtered_processing_tasks if their started_at date falls outside the given bounds
+            // A closure that removes the tasks of the batches whose started_at date falls outside the given bounds
             let mut clear_filtered_processing_tasks =
                 |start: Bound<OffsetDateTime>, end: Bound<OffsetDateTime>| {
                     let start = map_bound(start, |b| b.unix_timestamp_nanos());
                     let end = map_bound(end, |b| b.unix_timestamp_nanos());
-                    let is_within_dates = RangeBounds::contains(
-                        &(start, end),
-                        &started_at_processing.unix_timestamp_nanos(),
-                    );
-                    if !is_within_dates {
-                        filtered_processing_tasks.clear();
+                    for batch in &processing_batches {
+                        let is_within_dates = RangeBounds::contains(
+                            &(start, end),
+                            &batch.started_at.unix_timestamp_nanos(),
+                        );
+                        if !is_within_dates {
+                            filtered_processing_tasks -= &batch.ids;
+                        }
                     }
                 };
             match (query.after_started_at, query.before_started_at) {
@@ -896,6 +1319,11 @@ impl IndexScheduler {
         Ok(res)
     }
 
+    /// Returns the work done by the scheduler since it started.
+    pub fn indexing_activity(&self) -> IndexingActivity {
+        *self.indexing_activity.read().unwrap()
+    }
+
     // Return true if there is at least one task that is processing.
     pub fn is_task_processing(&self) -> Result<bool> {
         Ok(!self.processing_tasks.read().unwrap().processing.is_empty())
@@ -979,20 +1407,19 @@ impl IndexScheduler {
             tasks.into_iter().rev().take(query.limit.unwrap_or(u32::MAX) as usize),
         )?;
 
-        let ProcessingTasks { started_at, processing, .. } =
+        let processing_tasks =
             self.processing_tasks.read().map_err(|_| Error::CorruptedTaskQueue)?.clone();
 
         let ret = tasks.into_iter();
-        if processing.is_empty() {
+        if processing_tasks.processing.is_empty() {
             Ok((ret.collect(), total))
         } else {
             Ok((
-                ret.map(|task| {
-                    if processing.contains(task.uid) {
+                ret.map(|task| match processing_tasks.started_at(task.uid) {
+                    Some(started_at) => {
                         Task { status: Status::Processing, started_at: Some(started_at), ..task }
-                    } else {
-                        task
                     }
+                    None => task,
                 })
                 .collect(),
                 total,
@@ -1000,6 +1427,14 @@ impl IndexScheduler {
         }
     }
 
+    /// Returns a receiver notified every time a batch is processed, i.e. when its tasks succeeded,
+    /// failed or were canceled.
+    ///
+    /// The batches processed before subscribing are already seen by the receiver.
+    pub fn subscribe_to_finished_batches(&self) -> tokio::sync::watch::Receiver<()> {
+        self.finished_batches.subscribe()
+    }
+
     /// Register a new task in the scheduler.
     ///
     /// If it fails and data was associated with the task, it tries to delete the associated data.
@@ -1008,6 +1443,55 @@ impl IndexScheduler {
         kind: KindWithContent,
         task_id: Option<TaskId>,
         dry_run: bool,
+    ) -> Result<Task> {
+        self.register_task(kind, task_id, dry_run, None, |_| ())
+    }
+
+    /// Register a new task doing the same work as the given failed or canceled task.
+    ///
+    /// The update file of the original task, or its dead letter, is shared with the new task,
+    /// so its payload doesn't have to be sent again.
+    pub fn retry(&self, task_uid: TaskId) -> Result<Task> {
+        let rtxn = self.read_txn()?;
+        let task = self.get_task(&rtxn, task_uid)?.ok_or(Error::TaskNotFound(task_uid))?;
+        drop(rtxn);
+
+        if !matches!(task.status, Status::Failed | Status::Canceled) {
+            return Err(Error::TaskNotRetryable { task_uid, status: task.status });
+        }
+
+        let mut kind = task.kind;
+        let mut duplicated_file = None;
+        if let KindWithContent::DocumentAdditionOrUpdate { content_file, .. } = &mut kind {
+            let Some(payload) = self.payload_path(*content_file) else {
+                return Err(Error::RetryLostUpdateFile(task_uid));
+            };
+            *content_file = self.file_store.link_update(payload)?;
+            duplicated_file = Some(*content_file);
+        }
+
+        match self.register_task(kind, None, false, Some(task_uid), |_| ()) {
+            Ok(task) => Ok(task),
+            Err(e) => {
+                if let Some(uuid) = duplicated_file {
+                    if self.file_store.get_update_path(uuid).exists() {
+                        let _ = self.delete_update_file(uuid);
+                    }
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Register a new task in the scheduler, calling `before_commit` with its uid right before
+    /// it's visible to the run loop.
+    fn register_task(
+        &self,
+        kind: KindWithContent,
+        task_id: Option<TaskId>,
+        dry_run: bool,
+        retried_from: Option<TaskId>,
+        before_commit: impl FnOnce(TaskId),
     ) -> Result<Task> {
         let mut wtxn = self.env.write_txn()?;
 
@@ -1033,7 +1517,9 @@ impl IndexScheduler {
             finished_at: None,
             error: None,
             canceled_by: None,
+            retried_from,
             details: kind.default_details(),
+            warnings: Vec::new(),
             status: Status::Enqueued,
             kind: kind.clone(),
         };
@@ -1052,6 +1538,7 @@ impl IndexScheduler {
         // Get rid of the mutability.
         let task = task;
 
+        self.journal.append([JournalEntry::new(task.uid, Some(Status::Enqueued))])?;
         self.all_tasks.put_with_flags(&mut wtxn, PutFlags::APPEND, &task.uid, &task)?;
 
         for index in task.indexes() {
@@ -1070,6 +1557,7 @@ impl IndexScheduler {
 
         utils::insert_task_datetime(&mut wtxn, self.enqueued_at, task.enqueued_at, task.uid)?;
 
+        before_commit(task.uid);
         if let Err(e) = wtxn.commit() {
             self.delete_persisted_task_data(&task)?;
             return Err(e.into());
@@ -1079,10 +1567,7 @@ impl IndexScheduler {
         // we inform the processing tasks to stop (if necessary).
         if let KindWithContent::TaskCancelation { tasks, .. } = kind {
             let tasks_to_cancel = RoaringBitmap::from_iter(tasks);
-            if self.processing_tasks.read().unwrap().must_cancel_processing_tasks(&tasks_to_cancel)
-            {
-                self.must_stop_processing.must_stop();
-            }
+            self.processing_tasks.read().unwrap().cancel_processing_tasks(&tasks_to_cancel);
         }
 
         // notify the scheduler loop to execute a new tick
@@ -1138,9 +1623,19 @@ impl IndexScheduler {
         Ok(self.file_store.delete(uuid)?)
     }
 
+    /// Return how long the run loop can sleep before a schedule is due, a preview expires or a
+    /// throttled key can index again.
+    fn time_until_next_timer(&self) -> Result<Option<Duration>> {
+        let next_schedule = self.time_until_next_schedule()?;
+        let next_expiry = self.time_until_next_preview_expiry()?;
+        let next_refill = self.indexing_throttle.time_until_refilled();
+        Ok(next_schedule.into_iter().chain(next_expiry).chain(next_refill).min())
+    }
+
     /// Perform one iteration of the run loop.
     ///
-    /// 1. See if we need to cleanup the task queue
+    /// 1. See if we need to cleanup the task queue and enqueue the tasks of the due schedules
+    ///    and the deletion of the expired previews.
     /// 2. Find the next batch of tasks to be processed.
     /// 3. Update the information of these tasks following the start of their processing.
     /// 4. Update the in-memory list of processed tasks accordingly.
@@ -1150,7 +1645,11 @@ impl IndexScheduler {
     ///      of their processing.
     /// 6. Reset the in-memory list of processed tasks.
     ///
+    /// When multiple batches can be processed at once, a batch working on a single index
+    /// is processed and finished in the background and the tick returns right away.
+    ///
     /// Returns the number of processed tasks.
+    #[tracing::instrument(level = "trace", skip_all, target = "indexing::scheduler")]
     fn tick(&self) -> Result<TickOutcome> {
         #[cfg(test)]
         {
@@ -1158,9 +1657,17 @@ impl IndexScheduler {
             self.breakpoint(Breakpoint::Start);
         }
 
+        if self.shutting_down.load(Relaxed) {
+            return Ok(TickOutcome::WaitForSignal);
+        }
+
         if self.cleanup_enabled {
             self.cleanup_task_queue()?;
+            self.apply_task_retention_policy()?;
         }
+        self.materialize_due_schedules()?;
+        self.delete_expired_previews()?;
+        self.delete_expired_retry_payloads()?;
 
         let rtxn = self.env.read_txn().map_err(Error::HeedTransaction)?;
         let batch =
@@ -1176,16 +1683,62 @@ impl IndexScheduler {
         let processed_tasks = ids.len();
         let started_at = OffsetDateTime::now_utc();
 
-        // We reset the must_stop flag to be sure that we don't stop processing tasks
-        self.must_stop_processing.reset();
-        self.processing_tasks.write().unwrap().start_processing_at(started_at, ids.clone());
+        // Every batch gets its own must_stop flag so that a cancelation only stops the batches of the canceled tasks
+        let must_stop_processing = self.must_stop_processing.child();
+        {
+            let mut processing_tasks = self.processing_tasks.write().unwrap();
+            // Checked under the lock so that a shutdown either sees this batch running
+            // or prevents it from starting.
+            if self.shutting_down.load(Relaxed) {
+                return Ok(TickOutcome::WaitForSignal);
+            }
+            processing_tasks.start_processing(ProcessingBatch {
+                started_at,
+                ids: ids.clone(),
+                index_uid: index_uid.clone(),
+                must_stop_processing: must_stop_processing.clone(),
+                aborted: false,
+            });
+        }
+        let registered = self
+            .journal
+            .append(ids.iter().map(|id| JournalEntry::new(id, Some(Status::Processing))))
+            .and_then(|()| self.register_processing_batch(&ids, index_uid.clone(), started_at));
+        if let Err(e) = registered {
+            self.processing_tasks.write().unwrap().stop_processing(&ids);
+            return Err(e);
+        }
 
         #[cfg(test)]
         self.breakpoint(Breakpoint::BatchCreated);
 
+        let mut cloned_index_scheduler = self.private_clone();
+        cloned_index_scheduler.must_stop_processing = must_stop_processing;
+
         // 2. Process the tasks
+        if self.max_concurrent_batches > 1 && index_uid.is_some() {
+            let handle = std::thread::Builder::new()
+                .name(String::from("batch-operation"))
+                .spawn(move || {
+                    let index_scheduler = cloned_index_scheduler;
+                    let res = panic::catch_unwind(AssertUnwindSafe(|| {
+                        index_scheduler.process_batch(batch)
+                    }))
+                    .unwrap_or(Err(Error::ProcessBatchPanicked));
+                    if let Err(e) = index_scheduler.finish_batch(res, started_at, &ids, index_uid) {
+                        tracing::error!("{e}");
+                    }
+                    // The run loop may be waiting for this batch to schedule the next ones.
+                    index_scheduler.wake_up.signal();
+                })
+                .unwrap();
+            let mut batch_threads = self.batch_threads.lock().unwrap();
+            batch_threads.retain(|thread| !thread.is_finished());
+            batch_threads.push(handle);
+            return Ok(TickOutcome::TickAgain(processed_tasks));
+        }
+
         let res = {
-            let cloned_index_scheduler = self.private_clone();
             let handle = std::thread::Builder::new()
                 .name(String::from("batch-operation"))
                 .spawn(move || cloned_index_scheduler.process_batch(batch))
@@ -1193,8 +1746,45 @@ impl IndexScheduler {
             handle.join().unwrap_or(Err(Error::ProcessBatchPanicked))
         };
 
+        self.finish_batch(res, started_at, &ids, index_uid)
+    }
+
+    /// Store the outcome of a processed batch in the task queue and remove
+    /// the batch from the in-memory list of processed tasks.
+    ///
+    /// If it fails the batch is considered as aborted, its tasks will be processed again.
+    fn finish_batch(
+        &self,
+        res: Result<Vec<Task>>,
+        started_at: OffsetDateTime,
+        ids: &RoaringBitmap,
+        index_uid: Option<String>,
+    ) -> Result<TickOutcome> {
+        let outcome = self.commit_batch(res, started_at, ids, index_uid);
+        if outcome.is_err() {
+            self.processing_tasks.write().unwrap().abort_processing(ids);
+        }
+        // The batch will be registered again if its tasks are processed again.
+        if let Err(e) = self.forget_processing_batch(ids) {
+            tracing::error!("Could not forget the processed batch: {e}");
+        }
+        outcome
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "indexing::scheduler")]
+    fn commit_batch(
+        &self,
+        res: Result<Vec<Task>>,
+        started_at: OffsetDateTime,
+        ids: &RoaringBitmap,
+        index_uid: Option<String>,
+    ) -> Result<TickOutcome> {
+        let processed_tasks = ids.len();
+
         // Reset the currently updating index to relinquish the index handle
-        self.index_mapper.set_currently_updating_index(None);
+        if let Some(index_uid) = &index_uid {
+            self.index_mapper.unset_currently_updating_index(index_uid);
+        }
 
         #[cfg(test)]
         self.maybe_fail(tests::FailureLocation::AcquiringWtxn)?;
@@ -1202,6 +1792,7 @@ impl IndexScheduler {
         let mut wtxn = self.env.write_txn().map_err(Error::HeedTransaction)?;
 
         let finished_at = OffsetDateTime::now_utc();
+        let mut indexed_documents = 0;
         match res {
             Ok(tasks) => {
                 #[cfg(test)]
@@ -1210,6 +1801,17 @@ impl IndexScheduler {
                 let mut success = 0;
                 let mut failure = 0;
 
+                // The succeeded tasks carry the warnings about the index they left behind.
+                let warnings = match &index_uid {
+                    Some(index_uid) => self.index_warnings(&wtxn, index_uid).unwrap_or_else(|e| {
+                        tracing::error!(
+                            "While computing the warnings of the `{index_uid}` index: {e}"
+                        );
+                        Vec::new()
+                    }),
+                    None => Vec::new(),
+                };
+
                 #[allow(unused_variables)]
                 for (i, mut task) in tasks.into_iter().enumerate() {
                     task.started_at = Some(started_at);
@@ -1224,7 +1826,17 @@ impl IndexScheduler {
 
                     match task.error {
                         Some(_) => failure += 1,
-                        None => success += 1,
+                        None => {
+                            success += 1;
+                            task.warnings = warnings.clone();
+                        }
+                    }
+                    if let Some(Details::DocumentAdditionOrUpdate {
+                        indexed_documents: Some(count),
+                        ..
+                    }) = task.details
+                    {
+                        indexed_documents += count;
                     }
 
                     self.update_task(&mut wtxn, &task)
@@ -1247,6 +1859,7 @@ impl IndexScheduler {
                 // the `started_at` date times and `processings` of the current processing tasks.
                 // This date time is used by the task cancelation to store the right `started_at`
                 // date in the task on disk.
+                self.processing_tasks.write().unwrap().abort_processing(ids);
                 return Ok(TickOutcome::TickAgain(0));
             }
             // If an index said it was full, we need to:
@@ -1264,6 +1877,7 @@ impl IndexScheduler {
                 wtxn.abort();
 
                 tracing::info!("The max database size was reached. Resizing the index.");
+                self.processing_tasks.write().unwrap().abort_processing(ids);
 
                 return Ok(TickOutcome::TickAgain(0));
             }
@@ -1294,13 +1908,25 @@ impl IndexScheduler {
             }
         }
 
-        let processed = self.processing_tasks.write().unwrap().stop_processing();
-
         #[cfg(test)]
         self.maybe_fail(tests::FailureLocation::CommittingWtxn)?;
 
+        // The outcome of the batch must be journaled before being committed.
+        self.journal.sync()?;
         wtxn.commit().map_err(Error::HeedTransaction)?;
 
+        // We only release the tasks once they are commited, otherwise a concurrent
+        // tick could see them as enqueued and batch them a second time.
+        let processed = self.processing_tasks.write().unwrap().stop_processing(ids);
+        self.finished_batches.send_replace(());
+
+        {
+            let mut activity = self.indexing_activity.write().unwrap();
+            activity.indexed_documents += indexed_documents;
+            activity.processed_batches += 1;
+            activity.processing_time += (finished_at - started_at).unsigned_abs();
+        }
+
         // Once the tasks are commited, we should delete all the update files associated ASAP to avoid leaking files in case of a restart
         tracing::debug!("Deleting the upadate files");
 
@@ -1313,6 +1939,10 @@ impl IndexScheduler {
                     .get_task(&rtxn, id)
                     .map_err(|e| Error::TaskDatabaseUpdate(Box::new(e)))?
                     .ok_or(Error::CorruptedTaskQueue)?;
+                // the update files of the failed tasks are moved to the dead letters below
+                if task.status == Status::Failed {
+                    continue;
+                }
                 if let Err(e) = self.delete_persisted_task_data(&task) {
                     tracing::error!(
                         "Failure to delete the content files associated with task {}. Error: {e}",
@@ -1323,9 +1953,21 @@ impl IndexScheduler {
             Ok(())
         })?;
 
+        if let Err(e) = self.register_dead_letters(ids) {
+            tracing::error!("Failure to move the update files of the failed tasks to the dead letters. Error: {e}");
+        }
+
         // We shouldn't crash the tick function if we can't send data to the webhook.
         let _ = self.notify_webhook(&processed);
 
+        if let Some(index_uid) = &index_uid {
+            if let Err(e) = self.check_index_alert_thresholds(index_uid) {
+                tracing::error!(
+                    "While checking the alert thresholds of the `{index_uid}` index: {e}"
+                );
+            }
+        }
+
         #[cfg(test)]
         self.breakpoint(Breakpoint::AfterProcessing);
 
@@ -1411,6 +2053,78 @@ impl IndexScheduler {
         Ok(())
     }
 
+    /// Returns the warnings about the state of an index, none if it doesn't exist anymore.
+    fn index_warnings(&self, rtxn: &RoTxn, index_uid: &str) -> Result<Vec<TaskWarning>> {
+        if !self.index_mapper.index_exists(rtxn, index_uid)? {
+            return Ok(Vec::new());
+        }
+        let stats = self.index_mapper.stats_of(rtxn, index_uid)?;
+        Ok(self.index_alert_thresholds.warnings(&stats))
+    }
+
+    /// Reports the index once it starts exceeding one of the alert thresholds or almost fills its
+    /// memory map, by logging a warning and notifying the index alert webhook if there is one.
+    fn check_index_alert_thresholds(&self, index_uid: &str) -> Result<()> {
+        let rtxn = self.read_txn()?;
+        let warnings = self.index_warnings(&rtxn, index_uid)?;
+        drop(rtxn);
+
+        // the index was deleted or went back below the thresholds, it'll be reported again
+        if warnings.is_empty() {
+            self.alerted_indexes.write().unwrap().remove(index_uid);
+            return Ok(());
+        }
+        if !self.alerted_indexes.write().unwrap().insert(index_uid.to_string()) {
+            return Ok(());
+        }
+
+        for warning in warnings {
+            let body = match warning {
+                TaskWarning::IndexAlertThresholdExceeded {
+                    number_of_documents,
+                    database_size,
+                    max_number_of_documents,
+                    max_database_size,
+                } => {
+                    tracing::warn!(
+                        "The `{index_uid}` index exceeds its alert thresholds with {number_of_documents} documents and a size of {database_size} bytes."
+                    );
+                    serde_json::json!({
+                        "type": "indexAlertThresholdExceeded",
+                        "indexUid": index_uid,
+                        "numberOfDocuments": number_of_documents,
+                        "databaseSize": database_size,
+                        "thresholds": {
+                            "numberOfDocuments": max_number_of_documents,
+                            "databaseSize": max_database_size,
+                        },
+                    })
+                }
+                TaskWarning::IndexMapSizeAlmostReached { database_size, map_size } => {
+                    tracing::warn!(
+                        "The `{index_uid}` index almost fills its memory map with a size of {database_size} bytes out of {map_size} bytes."
+                    );
+                    serde_json::json!({
+                        "type": "indexMapSizeAlmostReached",
+                        "indexUid": index_uid,
+                        "databaseSize": database_size,
+                        "mapSize": map_size,
+                    })
+                }
+                TaskWarning::SnapshotUploadFailed { .. } => continue,
+            };
+
+            if let Some(ref url) = self.index_alert_webhook_url {
+                let request = ureq::post(url).set("Content-Type", "application/json");
+                if let Err(e) = request.send_string(&body.to_string()) {
+                    tracing::error!("While sending data to the index alert webhook: {e}");
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Register a task to cleanup the task queue if needed
     fn cleanup_task_queue(&self) -> Result<()> {
         let rtxn = self.env.read_txn().map_err(Error::HeedTransaction)?;
@@ -1464,12 +2178,161 @@ impl IndexScheduler {
         Ok(())
     }
 
+    /// Register a task to delete the finished tasks that are out of the retention policy.
+    ///
+    /// Some tasks are always kept to preserve the history:
+    /// - a task cancelation is kept as long as one of the tasks it canceled is kept,
+    ///   so the `canceledBy` field never points to a deleted task.
+    /// - the last finished dump creation is kept to let the users retrieve the uid of their last dump.
+    /// Deletes the update files kept to retry the failed and canceled tasks once the retry
+    /// payload retention is over.
+    fn delete_expired_retry_payloads(&self) -> Result<()> {
+        let Some(finished_before) = time::Duration::try_from(self.retry_payload_retention)
+            .ok()
+            .and_then(|retention| OffsetDateTime::now_utc().checked_sub(retention))
+        else {
+            return Ok(());
+        };
+        let finished_before = finished_before.unix_timestamp_nanos();
+        let mut purged_until = self.retry_payloads_purged_until.lock().unwrap();
+        if *purged_until >= finished_before {
+            return Ok(());
+        }
+
+        let rtxn = self.env.read_txn().map_err(Error::HeedTransaction)?;
+        let retryable =
+            self.get_status(&rtxn, Status::Failed)? | self.get_status(&rtxn, Status::Canceled)?;
+        let mut expired = RoaringBitmap::new();
+        for entry in self.finished_at.range(&rtxn, &(*purged_until..finished_before))? {
+            let (_, tasks) = entry?;
+            expired |= tasks & &retryable;
+        }
+
+        // We don't return the errors encountered when deleting the files as we can safely
+        // continue our job.
+        for task in self.get_existing_tasks(&rtxn, expired.iter())? {
+            let Some(content_uuid) = task.content_uuid() else { continue };
+            if !self.file_store.get_update_path(content_uuid).exists() {
+                continue;
+            }
+            if let Err(error) = self.delete_update_file(content_uuid) {
+                tracing::error!(
+                    file_content_uuid = %content_uuid,
+                    %error,
+                    "Failed deleting content file"
+                )
+            }
+        }
+        *purged_until = finished_before;
+
+        Ok(())
+    }
+
+    fn apply_task_retention_policy(&self) -> Result<()> {
+        let TaskRetentionPolicy { max_count, max_age } = self.task_retention;
+        if max_count.is_none() && max_age.is_none() {
+            return Ok(());
+        }
+
+        let rtxn = self.env.read_txn().map_err(Error::HeedTransaction)?;
+
+        // We don't want to enqueue a new task deletion while the previous one hasn't been processed.
+        let enqueued = self.get_status(&rtxn, Status::Enqueued)?;
+        if !(self.get_kind(&rtxn, Kind::TaskDeletion)? & enqueued).is_empty() {
+            return Ok(());
+        }
+
+        let finished = self.get_status(&rtxn, Status::Succeeded)?
+            | self.get_status(&rtxn, Status::Failed)?
+            | self.get_status(&rtxn, Status::Canceled)?;
+
+        let mut to_delete = RoaringBitmap::new();
+        if let Some(max_count) = max_count {
+            let excess = finished.len().saturating_sub(max_count);
+            // the task ids are monotonically increasing, the oldest tasks come first.
+            to_delete.extend(finished.iter().take(excess as usize));
+        }
+        // an age reaching further back than the earliest date keeps every task
+        let finished_before = max_age
+            .and_then(|max_age| time::Duration::try_from(max_age).ok())
+            .and_then(|max_age| OffsetDateTime::now_utc().checked_sub(max_age));
+        if let Some(finished_before) = finished_before {
+            let finished_before = finished_before.unix_timestamp_nanos();
+            for entry in self.finished_at.range(&rtxn, &(..finished_before))? {
+                let (_, tasks) = entry?;
+                to_delete |= tasks;
+            }
+        }
+        to_delete &= &finished;
+
+        // keep the last finished dump creation
+        let finished_dumps = self.get_kind(&rtxn, Kind::DumpCreation)? & &finished;
+        if let Some(last_dump) = finished_dumps.max() {
+            to_delete.remove(last_dump);
+        }
+
+        // keep the cancelations that canceled a task we're keeping
+        let cancelations = self.get_kind(&rtxn, Kind::TaskCancelation)? & &to_delete;
+        for cancelation in cancelations {
+            let canceled = self.canceled_by.get(&rtxn, &cancelation)?.unwrap_or_default();
+            if !canceled.is_subset(&to_delete) {
+                to_delete.remove(cancelation);
+            }
+        }
+
+        // /!\ the len must be at least 2 or else we might enter an infinite loop where we only delete
+        //     the deletion tasks we enqueued ourselves.
+        if to_delete.len() < 2 {
+            return Ok(());
+        }
+
+        // find the finish date of the most recently finished task we're going to delete
+        let mut last_finished_at = None;
+        for entry in self.finished_at.rev_iter(&rtxn)? {
+            let (finished_at, tasks) = entry?;
+            if !tasks.is_disjoint(&to_delete) {
+                last_finished_at = Some(finished_at);
+                break;
+            }
+        }
+        drop(rtxn);
+
+        let last_finished_at = last_finished_at.ok_or(Error::CorruptedTaskQueue)?;
+        // increase time by one nanosecond so that the finishedAt of the last task to delete is also lower than that date.
+        let delete_before = OffsetDateTime::from_unix_timestamp_nanos(last_finished_at)
+            .map_err(|_| Error::CorruptedTaskQueue)?
+            + Duration::from_nanos(1);
+
+        tracing::info!(
+            "Deleting {} finished tasks that are out of the task retention policy.",
+            to_delete.len()
+        );
+
+        self.register(
+            KindWithContent::TaskDeletion {
+                query: format!(
+                    "?beforeFinishedAt={}&statuses=succeeded,failed,canceled",
+                    delete_before.format(&Rfc3339).map_err(|_| Error::CorruptedTaskQueue)?,
+                ),
+                tasks: to_delete,
+            },
+            None,
+            false,
+        )?;
+
+        Ok(())
+    }
+
     pub fn index_stats(&self, index_uid: &str) -> Result<IndexStats> {
         let is_indexing = self.is_index_processing(index_uid)?;
         let rtxn = self.read_txn()?;
         let index_stats = self.index_mapper.stats_of(&rtxn, index_uid)?;
+        let exceeds_alert_thresholds = self
+            .index_alert_thresholds
+            .is_enabled()
+            .then(|| self.index_alert_thresholds.exceeded_by(&index_stats));
 
-        Ok(IndexStats { is_indexing, inner_stats: index_stats })
+        Ok(IndexStats { is_indexing, exceeds_alert_thresholds, inner_stats: index_stats })
     }
 
     pub fn features(&self) -> RoFeatures {
@@ -1482,6 +2345,19 @@ impl IndexScheduler {
         Ok(())
     }
 
+    /// Return the limits currently applied by the autobatcher.
+    pub fn autobatching_limits(&self) -> AutobatchingLimits {
+        // sound to unwrap, the lock is only held to copy or assign the limits
+        *self.autobatching_limits.read().unwrap()
+    }
+
+    /// Update the limits applied by the autobatcher, they're taken into account from the next batch.
+    ///
+    /// These limits are not persisted, the instance options are used again on restart.
+    pub fn update_autobatching_limits(&self, limits: AutobatchingLimits) {
+        *self.autobatching_limits.write().unwrap() = limits;
+    }
+
     pub(crate) fn delete_persisted_task_data(&self, task: &Task) -> Result<()> {
         match task.content_uuid() {
             Some(content_file) => self.delete_update_file(content_file),
@@ -1603,7 +2479,9 @@ impl<'a> Dump<'a> {
             finished_at: task.finished_at,
             error: task.error,
             canceled_by: task.canceled_by,
+            retried_from: task.retried_from,
             details: task.details,
+            warnings: Vec::new(),
             status: task.status,
             kind: match task.kind {
                 KindDump::DocumentImport {
@@ -1658,13 +2536,42 @@ impl<'a> Dump<'a> {
                 KindDump::TasksDeletion { query, tasks } => {
                     KindWithContent::TaskDeletion { query, tasks }
                 }
-                KindDump::DumpCreation { keys, instance_uid } => {
-                    KindWithContent::DumpCreation { keys, instance_uid }
+                KindDump::DumpCreation { keys, instance_uid, compression } => {
+                    KindWithContent::DumpCreation { keys, instance_uid, compression }
+                }
+                KindDump::SnapshotCreation => KindWithContent::SnapshotCreation,
+                KindDump::DumpImport { dump_uid, index_uid } => {
+                    KindWithContent::DumpImport { dump_uid, index_uid }
+                }
+                KindDump::IndexExport => KindWithContent::IndexExport {
+                    index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                },
+                KindDump::IndexPreviewCreation { source_index_uid, settings, expires_at } => {
+                    KindWithContent::IndexPreviewCreation {
+                        index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                        source_index_uid,
+                        settings,
+                        expires_at,
+                    }
+                }
+                KindDump::IndexMerge { source_index_uid, on_conflict } => {
+                    KindWithContent::IndexMerge {
+                        index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                        source_index_uid,
+                        on_conflict,
+                    }
+                }
+                KindDump::IndexSplit { source_index_uid, filter_expr } => {
+                    KindWithContent::IndexSplit {
+                        index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                        source_index_uid,
+                        filter_expr,
+                    }
                 }
-                KindDump::SnapshotCreation => KindWithContent::SnapshotCreation,
             },
         };
 
+        self.index_scheduler.journal.append([JournalEntry::new(task.uid, Some(task.status))])?;
         self.index_scheduler.all_tasks.put(&mut self.wtxn, &task.uid, &task)?;
 
         for index in task.indexes() {
@@ -1759,6 +2666,8 @@ struct IndexBudget {
 pub struct IndexStats {
     /// Whether this index is currently performing indexation, according to the scheduler.
     pub is_indexing: bool,
+    /// Whether this index exceeds one of the alert thresholds, `None` when no threshold is defined.
+    pub exceeds_alert_thresholds: Option<bool>,
     /// Internal stats computed from the index.
     pub inner_stats: index_mapper::IndexStats,
 }
@@ -1825,8 +2734,12 @@ mod tests {
                 auth_path: tempdir.path().join("auth"),
                 tasks_path: tempdir.path().join("db_path"),
                 update_file_path: tempdir.path().join("file_store"),
+                dead_letters_path: tempdir.path().join("dead_letters"),
                 indexes_path: tempdir.path().join("indexes"),
                 snapshots_path: tempdir.path().join("snapshots"),
+                snapshot_retention: None,
+                snapshot_s3: None,
+                snapshot_incremental: false,
                 dumps_path: tempdir.path().join("dumps"),
                 webhook_url: None,
                 webhook_authorization_header: None,
@@ -1837,9 +2750,15 @@ mod tests {
                 index_count: 5,
                 indexer_config,
                 autobatching_enabled: true,
+                max_concurrent_batches: 1,
                 cleanup_enabled: true,
                 max_number_of_tasks: 1_000_000,
+                task_retention: TaskRetentionPolicy::default(),
+                retry_payload_retention: std::time::Duration::from_secs(24 * 60 * 60),
+                index_alert_thresholds: IndexAlertThresholds::default(),
+                index_alert_webhook_url: None,
                 max_number_of_batched_tasks: usize::MAX,
+                max_batched_payload_size: None,
                 instance_features: Default::default(),
             };
             configuration(&mut options);
@@ -1919,7 +2838,11 @@ mod tests {
         let mut buffer = BufWriter::new(temp_file.reopen().unwrap());
         buffer.write_all(bytes).unwrap();
         buffer.flush().unwrap();
-        meilisearch_types::document_formats::read_json(temp_file.as_file(), write)
+        meilisearch_types::document_formats::read_json(
+            temp_file.as_file(),
+            write,
+            &Default::default(),
+        )
     }
 
     /// Create an update file with the given file uuid.
@@ -2095,6 +3018,89 @@ mod tests {
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "everything_is_successfully_registered");
     }
 
+    #[test]
+    fn recover_from_an_interrupted_batch() {
+        let (index_scheduler, mut _handle) = IndexScheduler::test(true, vec![]);
+
+        let (_, file) = index_scheduler.create_update_file_with_uuid(0).unwrap();
+        file.persist().unwrap();
+        // no task refers to this update file
+        let (_, file) = index_scheduler.create_update_file_with_uuid(1).unwrap();
+        file.persist().unwrap();
+
+        let kinds = [
+            index_creation_task("catto", "mouse"),
+            replace_document_import_task("catto", None, 0, 12),
+            // the update file of this task is missing
+            replace_document_import_task("catto", None, 2, 50),
+        ];
+        for kind in kinds {
+            index_scheduler.register(kind, None, false).unwrap();
+        }
+
+        let ids = RoaringBitmap::from_iter([0, 1, 2]);
+        let started_at = OffsetDateTime::now_utc();
+        index_scheduler.register_processing_batch(&ids, Some(S("catto")), started_at).unwrap();
+        // the outcome of the first task was decided but not committed
+        index_scheduler.journal.append([JournalEntry::new(0, Some(Status::Succeeded))]).unwrap();
+
+        let report = index_scheduler.recover().unwrap().unwrap();
+        assert_eq!(report.interrupted_batches.len(), 1);
+        assert_eq!(report.interrupted_batches[0].task_uids, vec![0, 1, 2]);
+        assert_eq!(report.reenqueued_tasks, vec![0, 1]);
+        assert_eq!(report.uncommitted_tasks, vec![0]);
+        assert_eq!(report.failed_tasks, vec![2]);
+        assert_eq!(report.deleted_update_files, vec![Uuid::from_u128(1)]);
+
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        let task = index_scheduler.get_task(&rtxn, 2).unwrap().unwrap();
+        assert_eq!(task.status, Status::Failed);
+        drop(rtxn);
+        index_scheduler.assert_internally_consistent();
+
+        // there is nothing left to recover
+        assert_eq!(index_scheduler.recover().unwrap(), None);
+    }
+
+    #[test]
+    fn journal_the_state_transitions_of_the_tasks() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        index_scheduler.register(index_creation_task("catto", "mouse"), None, false).unwrap();
+        handle.advance_one_successful_batch();
+        index_scheduler
+            .register(
+                KindWithContent::TaskDeletion {
+                    query: S("test_query"),
+                    tasks: [0].into_iter().collect(),
+                },
+                None,
+                false,
+            )
+            .unwrap();
+        handle.advance_one_successful_batch();
+
+        let transitions: Vec<_> = index_scheduler
+            .journal
+            .entries()
+            .unwrap()
+            .into_iter()
+            .map(|entry| (entry.task_uid, entry.status))
+            .collect();
+        assert_eq!(
+            transitions,
+            vec![
+                (0, Some(Status::Enqueued)),
+                (0, Some(Status::Processing)),
+                (0, Some(Status::Succeeded)),
+                (1, Some(Status::Enqueued)),
+                (1, Some(Status::Processing)),
+                (0, None),
+                (1, Some(Status::Succeeded)),
+            ]
+        );
+    }
+
     #[test]
     fn insert_task_while_another_task_is_processing() {
         let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
@@ -2115,6 +3121,112 @@ mod tests {
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "registered_the_third_task");
     }
 
+    #[test]
+    fn create_concurrent_batches_on_independent_indexes() {
+        let (index_scheduler, _handle) =
+            IndexScheduler::test_with_custom_config(vec![], |config| {
+                config.max_concurrent_batches = 2;
+            });
+
+        index_scheduler.register(index_creation_task("catto", "id"), None, false).unwrap();
+        index_scheduler.register(index_creation_task("catto", "id"), None, false).unwrap();
+        index_scheduler.register(index_creation_task("doggo", "id"), None, false).unwrap();
+
+        // the run loop is stuck on its first breakpoint, we simulate the processing of the batches.
+        let start_processing = |batch: &crate::batch::Batch| {
+            index_scheduler.processing_tasks.write().unwrap().start_processing(ProcessingBatch {
+                started_at: OffsetDateTime::now_utc(),
+                ids: batch.ids(),
+                index_uid: batch.index_uid().map(ToOwned::to_owned),
+                must_stop_processing: MustStopProcessing::default(),
+                aborted: false,
+            })
+        };
+        let next_batch = || {
+            let rtxn = index_scheduler.env.read_txn().unwrap();
+            index_scheduler.create_next_batch(&rtxn).unwrap()
+        };
+
+        let catto = next_batch().unwrap();
+        assert_eq!(catto.index_uid(), Some("catto"));
+        start_processing(&catto);
+
+        // the second task of catto must wait, the doggo index is free
+        let doggo = next_batch().unwrap();
+        assert_eq!(doggo.index_uid(), Some("doggo"));
+        assert_eq!(doggo.ids(), RoaringBitmap::from_iter([2]));
+        start_processing(&doggo);
+
+        // we can't process more than two batches at once
+        assert!(next_batch().is_none());
+
+        // the tasks of an aborted batch are still enqueued and can be batched again
+        index_scheduler.processing_tasks.write().unwrap().abort_processing(&doggo.ids());
+        let doggo = next_batch().unwrap();
+        assert_eq!(doggo.ids(), RoaringBitmap::from_iter([2]));
+
+        // a snapshot waits for all the batches to finish and stops the creation of new ones
+        index_scheduler.register(KindWithContent::SnapshotCreation, None, false).unwrap();
+        assert!(next_batch().is_none());
+
+        index_scheduler.processing_tasks.write().unwrap().stop_processing(&catto.ids());
+        let snapshot = next_batch().unwrap();
+        assert_eq!(snapshot.index_uid(), None);
+        assert_eq!(snapshot.ids(), RoaringBitmap::from_iter([3]));
+    }
+
+    #[test]
+    fn process_batches_on_independent_indexes_concurrently() {
+        let (index_scheduler, handle) = IndexScheduler::test_with_custom_config(vec![], |config| {
+            config.max_concurrent_batches = 2;
+        });
+        // the batches reach their breakpoints concurrently, we let them all through.
+        let breakpoints = handle.test_breakpoint_rcv.clone();
+        std::thread::spawn(move || while breakpoints.recv().is_ok() {});
+
+        let wait_for_success = |task_id: TaskId| -> Task {
+            let deadline = Instant::now() + std::time::Duration::from_secs(10);
+            loop {
+                let rtxn = index_scheduler.read_txn().unwrap();
+                let task = index_scheduler.get_task(&rtxn, task_id).unwrap().unwrap();
+                match task.status {
+                    Status::Succeeded => return task,
+                    Status::Failed | Status::Canceled => {
+                        panic!("The task {task_id} didn't succeed.")
+                    }
+                    _ => assert!(Instant::now() < deadline, "The task {task_id} is too long."),
+                }
+                drop(rtxn);
+                std::thread::sleep(std::time::Duration::from_millis(10));
+            }
+        };
+
+        index_scheduler.register(index_creation_task("catto", "id"), None, false).unwrap();
+        index_scheduler.register(index_creation_task("doggo", "id"), None, false).unwrap();
+        wait_for_success(0);
+        wait_for_success(1);
+
+        // holding the write transaction of catto blocks its indexing
+        let catto = index_scheduler.index("catto").unwrap();
+        let wtxn = catto.write_txn().unwrap();
+
+        for (id, index) in [(0, "catto"), (1, "doggo")] {
+            let (file, documents_count) = sample_documents(&index_scheduler, id, id as usize);
+            file.persist().unwrap();
+            let kind = replace_document_import_task(index, Some("id"), id, documents_count);
+            index_scheduler.register(kind, None, false).unwrap();
+        }
+
+        // doggo is indexed while catto is still processing
+        let doggo = wait_for_success(3);
+        assert!(index_scheduler.processing_tasks.read().unwrap().processing.contains(2));
+
+        drop(wtxn);
+        let catto = wait_for_success(2);
+        assert!(catto.started_at.unwrap() <= doggo.started_at.unwrap());
+        assert!(doggo.finished_at.unwrap() < catto.finished_at.unwrap());
+    }
+
     #[test]
     fn test_task_is_processing() {
         let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
@@ -2126,6 +3238,95 @@ mod tests {
         assert!(index_scheduler.is_task_processing().unwrap());
     }
 
+    #[test]
+    fn shutdown_without_processing_batch() {
+        let (index_scheduler, _handle) = IndexScheduler::test(true, vec![]);
+        assert!(index_scheduler.readiness().is_ready());
+
+        assert!(index_scheduler.shutdown(std::time::Duration::from_secs(1)));
+        let readiness = index_scheduler.readiness();
+        assert!(!readiness.scheduler);
+        assert!(!readiness.is_ready());
+    }
+
+    #[test]
+    fn shutdown_waits_for_the_processing_batch() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        index_scheduler.register(index_creation_task("catto", "id"), None, false).unwrap();
+        handle.advance_till([Start, BatchCreated]);
+
+        std::thread::scope(|scope| {
+            let shutdown =
+                scope.spawn(|| index_scheduler.shutdown(std::time::Duration::from_secs(10)));
+            while !index_scheduler.shutting_down.load(Relaxed) {
+                std::thread::sleep(std::time::Duration::from_millis(10));
+            }
+            // the processing batch still finishes
+            loop {
+                match handle.advance() {
+                    InsideProcessBatch => (),
+                    ProcessBatchSucceeded => break,
+                    breakpoint => panic!("Encountered an unexpected breakpoint `{breakpoint:?}`."),
+                }
+            }
+            handle.advance_till([AfterProcessing]);
+            assert!(shutdown.join().unwrap());
+        });
+        let rtxn = index_scheduler.read_txn().unwrap();
+        assert_eq!(index_scheduler.get_task(&rtxn, 0).unwrap().unwrap().status, Status::Succeeded);
+        drop(rtxn);
+
+        // but no new batch is started
+        handle.advance_till([Start]);
+        index_scheduler.register(index_creation_task("doggo", "id"), None, false).unwrap();
+        handle.advance_till([Start]);
+    }
+
+    #[test]
+    fn shutdown_aborts_the_batch_still_processing_after_the_timeout() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        let (file, documents_count) = sample_documents(&index_scheduler, 0, 0);
+        file.persist().unwrap();
+        let kind = replace_document_import_task("catto", None, 0, documents_count);
+        index_scheduler.register(kind, None, false).unwrap();
+        handle.advance_till([Start, BatchCreated, InsideProcessBatch]);
+
+        std::thread::scope(|scope| {
+            let shutdown =
+                scope.spawn(|| index_scheduler.shutdown(std::time::Duration::from_millis(100)));
+            // the batch is stuck on its breakpoint until the shutdown asks it to stop
+            while !index_scheduler.must_stop_processing.get() {
+                std::thread::sleep(std::time::Duration::from_millis(10));
+            }
+            handle.advance_till([AbortedIndexation]);
+            assert!(!shutdown.join().unwrap());
+        });
+
+        // the task is processed again on the next launch
+        let rtxn = index_scheduler.read_txn().unwrap();
+        assert_eq!(index_scheduler.get_task(&rtxn, 0).unwrap().unwrap().status, Status::Enqueued);
+    }
+
+    #[test]
+    fn shutdown_detaches_the_batch_threads_not_exiting() {
+        let (index_scheduler, _handle) = IndexScheduler::test(true, vec![]);
+
+        let (sender, receiver) = std::sync::mpsc::channel::<()>();
+        let stuck = std::thread::spawn(move || {
+            let _ = receiver.recv();
+        });
+        index_scheduler.batch_threads.lock().unwrap().push(stuck);
+
+        let started_at = Instant::now();
+        let deadline = started_at + std::time::Duration::from_millis(100);
+        assert!(!index_scheduler.join_batch_threads(deadline));
+        assert!(started_at.elapsed() < std::time::Duration::from_secs(5));
+        assert!(index_scheduler.batch_threads.lock().unwrap().is_empty());
+        drop(sender);
+    }
+
     /// We send a lot of tasks but notify the tasks scheduler only once as
     /// we send them very fast, we must make sure that they are all processed.
     #[test]
@@ -2760,6 +3961,42 @@ mod tests {
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "cancel_processed");
     }
 
+    #[test]
+    fn canceled_task_payload_deleted_after_retention() {
+        let (index_scheduler, mut handle) =
+            IndexScheduler::test_with_custom_config(vec![], |config| {
+                config.retry_payload_retention = std::time::Duration::ZERO;
+            });
+
+        let (file0, documents_count0) = sample_documents(&index_scheduler, 0, 0);
+        file0.persist().unwrap();
+
+        let to_enqueue = [
+            replace_document_import_task("catto", None, 0, documents_count0),
+            KindWithContent::TaskCancelation {
+                query: "test_query".to_owned(),
+                tasks: RoaringBitmap::from_iter([0]),
+            },
+        ];
+        for task in to_enqueue {
+            let _ = index_scheduler.register(task, None, false).unwrap();
+        }
+        handle.advance_one_successful_batch();
+        // the payload is kept until the next tick
+        assert_eq!(index_scheduler.file_store.all_uuids().unwrap().count(), 1);
+
+        index_scheduler
+            .register(
+                KindWithContent::IndexCreation { index_uid: S("doggo"), primary_key: None },
+                None,
+                false,
+            )
+            .unwrap();
+        handle.advance_one_successful_batch();
+        assert_eq!(index_scheduler.file_store.all_uuids().unwrap().count(), 0);
+        index_scheduler.assert_internally_consistent();
+    }
+
     #[test]
     fn cancel_succeeded_task() {
         let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
@@ -3082,6 +4319,56 @@ mod tests {
         snapshot!(serde_json::to_string_pretty(&documents).unwrap(), name: "documents");
     }
 
+    #[test]
+    fn test_document_addition_with_max_batched_payload_size() {
+        let (index_scheduler, mut handle) =
+            IndexScheduler::test_with_custom_config(vec![], |config| {
+                // every payload registered below weighs more than a single byte
+                config.max_batched_payload_size = Some(1);
+            });
+
+        for i in 0..4 {
+            let content = format!(r#"{{ "id": {i}, "doggo": "bob {i}" }}"#);
+
+            let (uuid, mut file) = index_scheduler.create_update_file_with_uuid(i).unwrap();
+            let documents_count = read_json(content.as_bytes(), &mut file).unwrap();
+            file.persist().unwrap();
+            index_scheduler
+                .register(
+                    KindWithContent::DocumentAdditionOrUpdate {
+                        index_uid: S("doggos"),
+                        primary_key: Some(S("id")),
+                        method: ReplaceDocuments,
+                        content_file: uuid,
+                        documents_count,
+                        allow_index_creation: true,
+                    },
+                    None,
+                    false,
+                )
+                .unwrap();
+            index_scheduler.assert_internally_consistent();
+        }
+
+        // The payload limit is always exceeded, thus the tasks are processed one by one.
+        handle.advance_one_successful_batch();
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        let succeeded = index_scheduler.get_status(&rtxn, Status::Succeeded).unwrap();
+        snapshot!(snapshot_bitmap(&succeeded), @"[0,]");
+        drop(rtxn);
+
+        // Lifting the limit at runtime lets the remaining tasks be batched together.
+        index_scheduler.update_autobatching_limits(AutobatchingLimits {
+            enabled: true,
+            max_batched_tasks: usize::MAX,
+            max_batched_payload_size: None,
+        });
+        handle.advance_one_successful_batch();
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        let succeeded = index_scheduler.get_status(&rtxn, Status::Succeeded).unwrap();
+        snapshot!(snapshot_bitmap(&succeeded), @"[0,1,2,3,]");
+    }
+
     #[test]
     fn test_document_update_without_autobatching() {
         let (index_scheduler, mut handle) = IndexScheduler::test(false, vec![]);
@@ -4443,6 +5730,136 @@ mod tests {
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "index_creation_failed");
     }
 
+    #[test]
+    fn test_task_retention_max_count() {
+        let (index_scheduler, mut handle) =
+            IndexScheduler::test_with_custom_config(vec![], |config| {
+                config.task_retention.max_count = Some(2);
+            });
+
+        index_scheduler
+            .register(
+                KindWithContent::IndexCreation { index_uid: S("doggo"), primary_key: None },
+                None,
+                false,
+            )
+            .unwrap();
+        handle.advance_one_successful_batch();
+
+        // the index already exists, all these tasks are going to fail
+        for _ in 0..4 {
+            index_scheduler
+                .register(
+                    KindWithContent::IndexCreation { index_uid: S("doggo"), primary_key: None },
+                    None,
+                    false,
+                )
+                .unwrap();
+        }
+        // a single extra task is not enough to enqueue a task deletion
+        handle.advance_n_failed_batches(3);
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        let all_tasks = index_scheduler.all_task_ids(&rtxn).unwrap();
+        snapshot!(snapshot_bitmap(&all_tasks), @"[0,1,2,3,4,]");
+        drop(rtxn);
+
+        // the two oldest finished tasks are deleted before processing the last task
+        handle.advance_one_successful_batch();
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        let all_tasks = index_scheduler.all_task_ids(&rtxn).unwrap();
+        snapshot!(snapshot_bitmap(&all_tasks), @"[2,3,4,5,]");
+        let task = index_scheduler.get_task(&rtxn, 5).unwrap().unwrap();
+        assert_eq!(task.kind.as_kind(), Kind::TaskDeletion);
+        assert_eq!(task.status, Status::Succeeded);
+    }
+
+    #[test]
+    fn test_task_retention_max_age_out_of_range() {
+        let (index_scheduler, mut handle) =
+            IndexScheduler::test_with_custom_config(vec![], |config| {
+                config.task_retention.max_age = Some(std::time::Duration::MAX);
+            });
+
+        for _ in 0..3 {
+            index_scheduler
+                .register(
+                    KindWithContent::IndexCreation { index_uid: S("doggo"), primary_key: None },
+                    None,
+                    false,
+                )
+                .unwrap();
+        }
+        handle.advance_one_successful_batch();
+        handle.advance_n_failed_batches(2);
+
+        // no task is old enough to be deleted
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        let all_tasks = index_scheduler.all_task_ids(&rtxn).unwrap();
+        snapshot!(snapshot_bitmap(&all_tasks), @"[0,1,2,]");
+    }
+
+    #[test]
+    fn test_schedule_materialization() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        let kind = KindWithContent::DocumentDeletionByFilter {
+            index_uid: S("doggos"),
+            filter_expr: serde_json::json!("catto EXISTS"),
+        };
+        let start_at = OffsetDateTime::now_utc() - std::time::Duration::from_secs(1);
+        let schedule = index_scheduler
+            .register_schedule(kind, std::time::Duration::from_secs(3600), Some(start_at))
+            .unwrap();
+        snapshot!(format!("{:?}", schedule.last_task_uid), @"None");
+
+        // the schedule is due, its task is enqueued then processed, and fails because the index doesn't exist
+        handle.advance_one_failed_batch();
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        let all_tasks = index_scheduler.all_task_ids(&rtxn).unwrap();
+        snapshot!(snapshot_bitmap(&all_tasks), @"[0,]");
+        let task = index_scheduler.get_task(&rtxn, 0).unwrap().unwrap();
+        assert_eq!(task.kind.as_kind(), Kind::DocumentDeletion);
+        drop(rtxn);
+
+        // the next run is planned one interval after the previous one
+        let schedule = index_scheduler.get_schedule(schedule.uid).unwrap();
+        snapshot!(format!("{:?}", schedule.last_task_uid), @"Some(0)");
+        assert_eq!(schedule.next_run_at, start_at + std::time::Duration::from_secs(3600));
+
+        let schedule = index_scheduler.delete_schedule(schedule.uid).unwrap();
+        snapshot!(index_scheduler.get_schedule(schedule.uid).unwrap_err(), @"Schedule `0` not found.");
+
+        let err = index_scheduler
+            .register_schedule(
+                KindWithContent::IndexCreation { index_uid: S("doggos"), primary_key: None },
+                std::time::Duration::from_secs(3600),
+                None,
+            )
+            .unwrap_err();
+        snapshot!(err, @"Tasks of type `indexCreation` cannot be scheduled. Only the `snapshotCreation` and `documentDeletion` by filter tasks can be scheduled.");
+
+        // the uid of the deleted schedule is not reused
+        let schedule = index_scheduler
+            .register_schedule(
+                KindWithContent::SnapshotCreation,
+                std::time::Duration::from_secs(3600),
+                None,
+            )
+            .unwrap();
+        snapshot!(schedule.uid, @"1");
+
+        // the date of the next run would overflow
+        let err = index_scheduler
+            .register_schedule(
+                KindWithContent::SnapshotCreation,
+                std::time::Duration::from_secs(u64::MAX),
+                None,
+            )
+            .unwrap_err();
+        snapshot!(err, @"The interval of a schedule can't be of 18446744073709551615 seconds, the date of its next run would be out of range.");
+        assert_eq!(err.error_code(), meilisearch_types::error::Code::InvalidScheduleInterval);
+    }
+
     #[test]
     fn test_task_queue_is_full() {
         let (index_scheduler, mut handle) =
@@ -4688,8 +6105,13 @@ mod tests {
             "documentAdditionOrUpdate": 0,
             "documentDeletion": 0,
             "dumpCreation": 0,
+            "dumpImport": 0,
             "indexCreation": 3,
             "indexDeletion": 0,
+            "indexExport": 0,
+            "indexMerge": 0,
+            "indexSplit": 0,
+            "indexPreviewCreation": 0,
             "indexSwap": 0,
             "indexUpdate": 0,
             "settingsUpdate": 0,
@@ -4719,8 +6141,13 @@ mod tests {
             "documentAdditionOrUpdate": 0,
             "documentDeletion": 0,
             "dumpCreation": 0,
+            "dumpImport": 0,
             "indexCreation": 3,
             "indexDeletion": 0,
+            "indexExport": 0,
+            "indexMerge": 0,
+            "indexSplit": 0,
+            "indexPreviewCreation": 0,
             "indexSwap": 0,
             "indexUpdate": 0,
             "settingsUpdate": 0,
@@ -4757,8 +6184,13 @@ mod tests {
             "documentAdditionOrUpdate": 0,
             "documentDeletion": 0,
             "dumpCreation": 0,
+            "dumpImport": 0,
             "indexCreation": 3,
             "indexDeletion": 0,
+            "indexExport": 0,
+            "indexMerge": 0,
+            "indexSplit": 0,
+            "indexPreviewCreation": 0,
             "indexSwap": 0,
             "indexUpdate": 0,
             "settingsUpdate": 0,
@@ -4796,8 +6228,13 @@ mod tests {
             "documentAdditionOrUpdate": 0,
             "documentDeletion": 0,
             "dumpCreation": 0,
+            "dumpImport": 0,
             "indexCreation": 3,
             "indexDeletion": 0,
+            "indexExport": 0,
+            "indexMerge": 0,
+            "indexSplit": 0,
+            "indexPreviewCreation": 0,
             "indexSwap": 0,
             "indexUpdate": 0,
             "settingsUpdate": 0,
@@ -4813,7 +6250,11 @@ mod tests {
     fn cancel_processing_dump() {
         let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
 
-        let dump_creation = KindWithContent::DumpCreation { keys: Vec::new(), instance_uid: None };
+        let dump_creation = KindWithContent::DumpCreation {
+            keys: Vec::new(),
+            instance_uid: None,
+            compression: Default::default(),
+        };
         let dump_cancellation = KindWithContent::TaskCancelation {
             query: "cancel dump".to_owned(),
             tasks: RoaringBitmap::from_iter([0]),
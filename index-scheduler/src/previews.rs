@@ -0,0 +1,99 @@
+/*!
+The previews are temporary copies of an index on which different settings are applied.
+
+A preview is created by an [`KindWithContent::IndexPreviewCreation`] task and is a normal index
+the users can search. Its expiration date is persisted in the task database, and once it's reached
+the run loop enqueues the deletion of the preview, see [`IndexScheduler::delete_expired_previews`].
+*/
+
+use std::time::Duration;
+
+use meilisearch_types::heed::RoTxn;
+use meilisearch_types::tasks::KindWithContent;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::{IndexScheduler, Result};
+
+/// A temporary index copied from another one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexPreview {
+    /// The uid of the preview index.
+    pub uid: String,
+    /// The uid of the index the preview was copied from.
+    pub source_index_uid: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub expires_at: OffsetDateTime,
+}
+
+impl IndexScheduler {
+    /// Return the previews of the given index that still exist, sorted by uid.
+    pub fn get_previews(&self, source_index_uid: &str) -> Result<Vec<IndexPreview>> {
+        let rtxn = self.env.read_txn()?;
+        let mut previews = Vec::new();
+        for preview in self.all_previews(&rtxn)? {
+            // the preview may have been deleted by the user before its expiration.
+            if preview.source_index_uid == source_index_uid
+                && self.index_mapper.exists(&rtxn, &preview.uid)?
+            {
+                previews.push(preview);
+            }
+        }
+        Ok(previews)
+    }
+
+    fn all_previews(&self, rtxn: &RoTxn) -> Result<Vec<IndexPreview>> {
+        self.previews.iter(rtxn)?.map(|entry| Ok(entry?.1)).collect()
+    }
+
+    /// Return how long the run loop can sleep before a preview expires.
+    ///
+    /// Return `None` when there is no preview.
+    pub(crate) fn time_until_next_preview_expiry(&self) -> Result<Option<Duration>> {
+        let rtxn = self.env.read_txn()?;
+        let now = OffsetDateTime::now_utc();
+        let expires_at = self.all_previews(&rtxn)?.into_iter().map(|p| p.expires_at).min();
+        Ok(expires_at.map(|expires_at| {
+            if expires_at > now {
+                (expires_at - now).unsigned_abs()
+            } else {
+                Duration::ZERO
+            }
+        }))
+    }
+
+    /// Enqueue the deletion of every preview that expired.
+    ///
+    /// The previews are forgotten once their deletion is enqueued, a preview the user
+    /// already deleted is only forgotten.
+    pub(crate) fn delete_expired_previews(&self) -> Result<()> {
+        let rtxn = self.env.read_txn()?;
+        let now = OffsetDateTime::now_utc();
+        let expired: Vec<_> = self
+            .all_previews(&rtxn)?
+            .into_iter()
+            .filter(|preview| preview.expires_at <= now)
+            .collect();
+        drop(rtxn);
+
+        for preview in expired {
+            let rtxn = self.env.read_txn()?;
+            let index_exists = self.index_mapper.exists(&rtxn, &preview.uid)?;
+            drop(rtxn);
+            if index_exists {
+                tracing::info!("Deleting the expired preview {}.", preview.uid);
+                let kind = KindWithContent::IndexDeletion { index_uid: preview.uid.clone() };
+                self.register(kind, None, false)?;
+            }
+
+            let mut wtxn = self.env.write_txn()?;
+            self.previews.delete(&mut wtxn, &preview.uid)?;
+            wtxn.commit()?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,207 @@
+/*!
+The recovery of the task queue after an unclean shutdown.
+
+Before a batch is processed, its tasks are stored in the `processing-batches` database and they're
+removed once the outcome of the batch is committed. The batches still stored when the scheduler is
+opened were thus interrupted, their tasks are still enqueued and will be processed again. The
+[journal](crate::journal) tells which of their outcomes were decided but never committed.
+
+When opening the scheduler, the update files are also reconciled with the task queue:
+- the update files no enqueued task refers to are deleted.
+- the enqueued tasks whose update file is missing are marked as failed.
+
+What was done is summarized in a [`RecoveryReport`] that is logged and kept in memory
+until the next restart, see [`IndexScheduler::recovery_report`].
+*/
+
+use std::collections::{BTreeSet, HashMap};
+
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::tasks::{Status, TaskId};
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::journal::JournalEntry;
+use crate::{Error, IndexScheduler, Result};
+
+/// A batch of tasks whose processing was started.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InterruptedBatch {
+    pub task_uids: Vec<TaskId>,
+    pub index_uid: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub started_at: OffsetDateTime,
+}
+
+/// What was done to recover the task queue after an unclean shutdown.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveryReport {
+    #[serde(with = "time::serde::rfc3339")]
+    pub recovered_at: OffsetDateTime,
+    /// The batches that were being processed when the engine stopped.
+    pub interrupted_batches: Vec<InterruptedBatch>,
+    /// The tasks of the interrupted batches that will be processed again.
+    pub reenqueued_tasks: Vec<TaskId>,
+    /// The re-enqueued tasks whose outcome was journaled but not committed.
+    pub uncommitted_tasks: Vec<TaskId>,
+    /// The enqueued tasks that were marked as failed because their update file is missing.
+    pub failed_tasks: Vec<TaskId>,
+    /// The update files that were deleted because no enqueued task refers to them.
+    pub deleted_update_files: Vec<Uuid>,
+}
+
+impl RecoveryReport {
+    fn is_empty(&self) -> bool {
+        self.interrupted_batches.is_empty()
+            && self.failed_tasks.is_empty()
+            && self.deleted_update_files.is_empty()
+    }
+}
+
+impl IndexScheduler {
+    /// Return the report of the recovery made when the scheduler was opened,
+    /// `None` if the previous shutdown was clean.
+    pub fn recovery_report(&self) -> Option<&RecoveryReport> {
+        self.recovery_report.as_deref()
+    }
+
+    /// Store the tasks of a batch before processing it.
+    pub(crate) fn register_processing_batch(
+        &self,
+        ids: &RoaringBitmap,
+        index_uid: Option<String>,
+        started_at: OffsetDateTime,
+    ) -> Result<()> {
+        let Some(key) = ids.min() else { return Ok(()) };
+        let batch = InterruptedBatch { task_uids: ids.iter().collect(), index_uid, started_at };
+        let mut wtxn = self.env.write_txn()?;
+        self.processing_batches.put(&mut wtxn, &key, &batch)?;
+        wtxn.commit()?;
+        Ok(())
+    }
+
+    /// Forget a batch once its outcome is committed or it is aborted.
+    pub(crate) fn forget_processing_batch(&self, ids: &RoaringBitmap) -> Result<()> {
+        let Some(key) = ids.min() else { return Ok(()) };
+        let mut wtxn = self.env.write_txn()?;
+        self.processing_batches.delete(&mut wtxn, &key)?;
+        wtxn.commit()?;
+        Ok(())
+    }
+
+    /// Recover the task queue from the batches that were interrupted and reconcile
+    /// the update files with the enqueued tasks.
+    ///
+    /// Must be called before the run loop is started.
+    pub(crate) fn recover(&self) -> Result<Option<RecoveryReport>> {
+        let mut wtxn = self.env.write_txn()?;
+        let enqueued = self.get_status(&wtxn, Status::Enqueued)?;
+
+        let mut interrupted_batches = Vec::new();
+        let mut reenqueued_tasks = RoaringBitmap::new();
+        for result in self.processing_batches.iter(&wtxn)? {
+            let (_, batch) = result?;
+            let task_uids: RoaringBitmap = batch.task_uids.iter().copied().collect();
+            // the batch may have been committed right before the shutdown.
+            if !task_uids.is_disjoint(&enqueued) {
+                reenqueued_tasks |= task_uids & &enqueued;
+                interrupted_batches.push(batch);
+            }
+        }
+        self.processing_batches.clear(&mut wtxn)?;
+
+        let mut uncommitted_tasks = RoaringBitmap::new();
+        if !interrupted_batches.is_empty() {
+            let mut journaled = HashMap::new();
+            for entry in self.journal.entries()? {
+                journaled.insert(entry.task_uid, entry.status);
+            }
+            uncommitted_tasks = reenqueued_tasks
+                .iter()
+                .filter(|id| {
+                    matches!(
+                        journaled.get(id),
+                        Some(Some(Status::Succeeded | Status::Failed | Status::Canceled))
+                    )
+                })
+                .collect();
+        }
+
+        let mut referenced_files = BTreeSet::new();
+        let mut failed_tasks = RoaringBitmap::new();
+        let now = OffsetDateTime::now_utc();
+        for task_id in &enqueued {
+            let mut task = self.get_task(&wtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
+            let Some(content_uuid) = task.content_uuid() else { continue };
+            if self.file_store.get_update_path(content_uuid).exists() {
+                referenced_files.insert(content_uuid);
+                continue;
+            }
+
+            task.status = Status::Failed;
+            task.started_at = Some(now);
+            task.finished_at = Some(now);
+            task.error = Some(ResponseError::from(Error::LostUpdateFile(content_uuid)));
+            task.details = task.details.map(|d| d.to_failed());
+            self.update_task(&mut wtxn, &task)?;
+            failed_tasks.insert(task_id);
+        }
+        // the update files of the failed and canceled tasks are kept to retry them.
+        let retryable =
+            self.get_status(&wtxn, Status::Failed)? | self.get_status(&wtxn, Status::Canceled)?;
+        for task_id in &retryable {
+            let task = self.get_task(&wtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
+            referenced_files.extend(task.content_uuid());
+        }
+
+        reenqueued_tasks -= &failed_tasks;
+        uncommitted_tasks -= &failed_tasks;
+        self.journal.append(
+            reenqueued_tasks.iter().map(|id| JournalEntry::new(id, Some(Status::Enqueued))),
+        )?;
+
+        let mut deleted_update_files = Vec::new();
+        for uuid in self.file_store.all_uuids()? {
+            let uuid = uuid?;
+            if !referenced_files.contains(&uuid) {
+                self.file_store.delete(uuid)?;
+                deleted_update_files.push(uuid);
+            }
+        }
+
+        wtxn.commit()?;
+
+        let report = RecoveryReport {
+            recovered_at: now,
+            interrupted_batches,
+            reenqueued_tasks: reenqueued_tasks.into_iter().collect(),
+            uncommitted_tasks: uncommitted_tasks.into_iter().collect(),
+            failed_tasks: failed_tasks.into_iter().collect(),
+            deleted_update_files,
+        };
+        if report.is_empty() {
+            return Ok(None);
+        }
+
+        tracing::warn!(
+            "Recovered from an unclean shutdown: {} interrupted batches, {} re-enqueued tasks ({} with an uncommitted outcome), {} failed tasks and {} deleted update files.",
+            report.interrupted_batches.len(),
+            report.reenqueued_tasks.len(),
+            report.uncommitted_tasks.len(),
+            report.failed_tasks.len(),
+            report.deleted_update_files.len(),
+        );
+        for batch in &report.interrupted_batches {
+            tracing::warn!(
+                "The batch of the tasks {:?} started at {} was interrupted.",
+                batch.task_uids,
+                batch.started_at,
+            );
+        }
+        Ok(Some(report))
+    }
+}
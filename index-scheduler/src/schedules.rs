@@ -0,0 +1,193 @@
+/*!
+The schedules are recurring tasks registered by the users.
+
+Each schedule holds the content of the task to enqueue and the interval at which it must be
+enqueued. They are persisted in the task database and materialized into normal tasks by the
+run loop when they are due, see [`IndexScheduler::materialize_due_schedules`].
+*/
+
+use std::time::Duration;
+
+use meilisearch_types::heed::RoTxn;
+use meilisearch_types::tasks::{KindWithContent, Status};
+use serde::{Deserialize, Serialize};
+use time::{OffsetDateTime, PrimitiveDateTime};
+
+use crate::{Error, IndexScheduler, Result, TaskId};
+
+pub type ScheduleId = u32;
+
+/// The key of the uid of the next schedule in the `schedule_uids` database.
+const NEXT_SCHEDULE_UID: &str = "next";
+
+/// A task the scheduler enqueues at a fixed interval.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Schedule {
+    pub uid: ScheduleId,
+    /// The content of the task enqueued every time the schedule is due.
+    pub kind: KindWithContent,
+    /// The time between two runs of the schedule.
+    pub interval: Duration,
+    #[serde(with = "time::serde::rfc3339")]
+    pub next_run_at: OffsetDateTime,
+    /// The uid of the last task enqueued by this schedule.
+    pub last_task_uid: Option<TaskId>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+impl Schedule {
+    /// Return the first date after `now` at which the schedule must run again.
+    ///
+    /// The runs missed while the engine was down or busy are skipped instead of being enqueued all at once.
+    fn following_run_after(&self, now: OffsetDateTime) -> OffsetDateTime {
+        if self.next_run_at > now {
+            return self.next_run_at;
+        }
+        let late_by = (now - self.next_run_at).unsigned_abs();
+        let runs = late_by.as_nanos() / self.interval.as_nanos() + 1;
+        let skipped = self.interval.as_nanos().saturating_mul(runs);
+        let skipped = Duration::from_nanos(skipped.try_into().unwrap_or(u64::MAX));
+        time::Duration::try_from(skipped)
+            .ok()
+            .and_then(|skipped| self.next_run_at.checked_add(skipped))
+            .unwrap_or(PrimitiveDateTime::MAX.assume_utc())
+    }
+}
+
+impl IndexScheduler {
+    /// Register a new schedule that enqueues the given task every `interval`.
+    ///
+    /// The first task is enqueued at `start_at`, or after one interval if it's not specified.
+    /// Only the tasks that don't rely on an update file can be scheduled.
+    pub fn register_schedule(
+        &self,
+        kind: KindWithContent,
+        interval: Duration,
+        start_at: Option<OffsetDateTime>,
+    ) -> Result<Schedule> {
+        if !matches!(
+            kind,
+            KindWithContent::SnapshotCreation | KindWithContent::DocumentDeletionByFilter { .. }
+        ) {
+            return Err(Error::UnschedulableTask(kind.as_kind()));
+        }
+        if interval.is_zero() {
+            return Err(Error::InvalidScheduleInterval);
+        }
+
+        let mut wtxn = self.env.write_txn()?;
+        // the databases created before the counter existed fall back on the last registered uid.
+        let uid = match self.schedule_uids.get(&wtxn, NEXT_SCHEDULE_UID)? {
+            Some(uid) => uid,
+            None => self.schedules.last(&wtxn)?.map_or(0, |(uid, _)| uid + 1),
+        };
+        let now = OffsetDateTime::now_utc();
+        let after_one_interval = time::Duration::try_from(interval)
+            .ok()
+            .and_then(|interval| now.checked_add(interval))
+            .ok_or(Error::ScheduleIntervalTooLarge(interval.as_secs()))?;
+        let schedule = Schedule {
+            uid,
+            kind,
+            interval,
+            next_run_at: start_at.unwrap_or(after_one_interval),
+            last_task_uid: None,
+            created_at: now,
+        };
+        self.schedules.put(&mut wtxn, &uid, &schedule)?;
+        self.schedule_uids.put(&mut wtxn, NEXT_SCHEDULE_UID, &(uid + 1))?;
+        wtxn.commit()?;
+
+        // the run loop must recompute how long it can sleep.
+        self.wake_up.signal();
+
+        Ok(schedule)
+    }
+
+    /// Return all the registered schedules, sorted by uid.
+    pub fn get_schedules(&self) -> Result<Vec<Schedule>> {
+        let rtxn = self.env.read_txn()?;
+        self.all_schedules(&rtxn)
+    }
+
+    /// Return the schedule with the given uid.
+    pub fn get_schedule(&self, uid: ScheduleId) -> Result<Schedule> {
+        let rtxn = self.env.read_txn()?;
+        self.schedules.get(&rtxn, &uid)?.ok_or(Error::ScheduleNotFound(uid))
+    }
+
+    /// Delete the schedule with the given uid, the tasks it already enqueued are kept.
+    pub fn delete_schedule(&self, uid: ScheduleId) -> Result<Schedule> {
+        let mut wtxn = self.env.write_txn()?;
+        let schedule = self.schedules.get(&wtxn, &uid)?.ok_or(Error::ScheduleNotFound(uid))?;
+        self.schedules.delete(&mut wtxn, &uid)?;
+        wtxn.commit()?;
+        Ok(schedule)
+    }
+
+    fn all_schedules(&self, rtxn: &RoTxn) -> Result<Vec<Schedule>> {
+        self.schedules.iter(rtxn)?.map(|entry| Ok(entry?.1)).collect()
+    }
+
+    /// Return how long the run loop can sleep before a schedule is due.
+    ///
+    /// Return `None` when there is no schedule.
+    pub(crate) fn time_until_next_schedule(&self) -> Result<Option<Duration>> {
+        let rtxn = self.env.read_txn()?;
+        let now = OffsetDateTime::now_utc();
+        let next_run_at = self.all_schedules(&rtxn)?.into_iter().map(|s| s.next_run_at).min();
+        Ok(next_run_at.map(|next_run_at| {
+            if next_run_at > now {
+                (next_run_at - now).unsigned_abs()
+            } else {
+                Duration::ZERO
+            }
+        }))
+    }
+
+    /// Enqueue a task for every schedule that is due.
+    ///
+    /// A schedule is skipped if the task it enqueued on its previous run is not finished yet,
+    /// so a slow task doesn't pile up in the task queue.
+    pub(crate) fn materialize_due_schedules(&self) -> Result<()> {
+        let rtxn = self.env.read_txn()?;
+        let now = OffsetDateTime::now_utc();
+        let due: Vec<_> = self
+            .all_schedules(&rtxn)?
+            .into_iter()
+            .filter(|schedule| schedule.next_run_at <= now)
+            .collect();
+        if due.is_empty() {
+            return Ok(());
+        }
+        let processing = self.processing_tasks.read().unwrap().processing.clone();
+        let unfinished = self.get_status(&rtxn, Status::Enqueued)? | processing;
+        drop(rtxn);
+
+        for mut schedule in due {
+            let previous_run_unfinished =
+                schedule.last_task_uid.is_some_and(|uid| unfinished.contains(uid));
+            if previous_run_unfinished {
+                tracing::info!(
+                    "Skipping a run of the schedule {} as its previous task is not finished yet.",
+                    schedule.uid
+                );
+            } else {
+                let task = self.register(schedule.kind.clone(), None, false)?;
+                schedule.last_task_uid = Some(task.uid);
+            }
+            schedule.next_run_at = schedule.following_run_after(now);
+
+            let mut wtxn = self.env.write_txn()?;
+            // the schedule may have been deleted while we were enqueuing its task.
+            if self.schedules.get(&wtxn, &schedule.uid)?.is_some() {
+                self.schedules.put(&mut wtxn, &schedule.uid, &schedule)?;
+            }
+            wtxn.commit()?;
+        }
+
+        Ok(())
+    }
+}
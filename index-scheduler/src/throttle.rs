@@ -0,0 +1,246 @@
+/*!
+The throttling of the documents added with the API keys limiting their indexing throughput.
+
+Each limited key owns a token bucket holding up to one second worth of documents and payload
+bytes, refilled continuously at the rates of the key. The document additions of a key are only
+batched while its bucket is not in debt, and batching them draws their documents and payload
+size from the bucket, which puts it in debt when they weigh more than what remains in it.
+The key can't enqueue new documents while its bucket is in debt.
+
+The buckets and the keys of the enqueued tasks are only kept in memory, the tasks enqueued
+before a restart are not throttled anymore.
+*/
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use meilisearch_types::heed::RoTxn;
+use meilisearch_types::keys::RateLimits;
+use meilisearch_types::tasks::{KindWithContent, Task};
+use roaring::RoaringBitmap;
+use uuid::Uuid;
+
+use crate::{Error, IndexScheduler, Result, TaskId};
+
+/// The documents and payload bytes a key can still index right away, negative when it's in debt.
+struct Bucket {
+    limits: RateLimits,
+    documents: f64,
+    bytes: f64,
+    refilled_at: Instant,
+}
+
+impl Bucket {
+    fn full(limits: RateLimits) -> Self {
+        let documents = limits.indexed_documents_per_second.unwrap_or_default() as f64;
+        let bytes = limits.indexed_bytes_per_second.unwrap_or_default() as f64;
+        Bucket { limits, documents, bytes, refilled_at: Instant::now() }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+        if let Some(rate) = self.limits.indexed_documents_per_second {
+            self.documents = (self.documents + rate as f64 * elapsed).min(rate as f64);
+        }
+        if let Some(rate) = self.limits.indexed_bytes_per_second {
+            self.bytes = (self.bytes + rate as f64 * elapsed).min(rate as f64);
+        }
+        self.refilled_at = now;
+    }
+
+    /// Returns how long the bucket will stay in debt, `None` if it's not.
+    fn debt(&self) -> Option<Duration> {
+        let documents = self.limits.indexed_documents_per_second.map(|rate| (self.documents, rate));
+        let bytes = self.limits.indexed_bytes_per_second.map(|rate| (self.bytes, rate));
+        documents
+            .into_iter()
+            .chain(bytes)
+            .filter(|(tokens, _)| *tokens < 0.0)
+            // a key with a zero rate never gets out of debt, we check it again once in a while.
+            .map(|(tokens, rate)| -tokens / rate.max(1) as f64)
+            .reduce(f64::max)
+            .map(|secs| Duration::from_secs_f64(secs.min(3600.0)))
+    }
+}
+
+/// An enqueued document addition of a limited key.
+struct ThrottledTask {
+    key: Uuid,
+    documents: u64,
+    bytes: u64,
+    /// Whether the task was already drawn from the bucket of its key.
+    drawn: bool,
+}
+
+#[derive(Default)]
+struct Buckets {
+    buckets: HashMap<Uuid, Bucket>,
+    tasks: HashMap<TaskId, ThrottledTask>,
+}
+
+impl Buckets {
+    /// Refills the bucket of the key and returns how long it will stay in debt.
+    fn debt(&mut self, key: Uuid, now: Instant) -> Option<Duration> {
+        let bucket = self.buckets.get_mut(&key)?;
+        bucket.refill(now);
+        bucket.debt()
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct IndexingThrottle {
+    buckets: Mutex<Buckets>,
+}
+
+impl IndexingThrottle {
+    /// Update the limits of the key.
+    ///
+    /// Returns how long the key must wait before adding documents again when it's in debt.
+    fn check(&self, key: Uuid, limits: RateLimits) -> std::result::Result<(), Duration> {
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = buckets.buckets.entry(key).or_insert_with(|| Bucket::full(limits));
+        bucket.refill(now);
+        bucket.limits = limits;
+        match bucket.debt() {
+            Some(retry_after) => Err(retry_after),
+            None => Ok(()),
+        }
+    }
+
+    fn register(&self, task: TaskId, key: Uuid, documents: u64, bytes: u64) {
+        let throttled = ThrottledTask { key, documents, bytes, drawn: false };
+        self.buckets.lock().unwrap().tasks.insert(task, throttled);
+    }
+
+    /// Returns the tasks that can't be batched yet because their key is in debt.
+    pub(crate) fn throttled_tasks(&self) -> RoaringBitmap {
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap();
+        let Buckets { buckets: key_buckets, tasks } = &mut *buckets;
+        let mut throttled = RoaringBitmap::new();
+        for (task_id, task) in tasks.iter().filter(|(_, task)| !task.drawn) {
+            if let Some(bucket) = key_buckets.get_mut(&task.key) {
+                bucket.refill(now);
+                if bucket.debt().is_some() {
+                    throttled.insert(*task_id);
+                }
+            }
+        }
+        throttled
+    }
+
+    /// Draws the task from the bucket of its key so that it can be batched.
+    ///
+    /// Returns `false` when the key is in debt and the task must wait.
+    pub(crate) fn draw(&self, task_id: TaskId) -> bool {
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap();
+        let Some(task) = buckets.tasks.get(&task_id).filter(|task| !task.drawn) else {
+            return true;
+        };
+        let (key, documents, bytes) = (task.key, task.documents, task.bytes);
+        if buckets.debt(key, now).is_some() {
+            return false;
+        }
+        if let Some(bucket) = buckets.buckets.get_mut(&key) {
+            bucket.documents -= documents as f64;
+            bucket.bytes -= bytes as f64;
+        }
+        if let Some(task) = buckets.tasks.get_mut(&task_id) {
+            task.drawn = true;
+        }
+        true
+    }
+
+    /// Returns how long before one of the throttled tasks can be batched.
+    pub(crate) fn time_until_refilled(&self) -> Option<Duration> {
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap();
+        let keys: Vec<_> =
+            buckets.tasks.values().filter(|task| !task.drawn).map(|task| task.key).collect();
+        keys.into_iter().filter_map(|key| buckets.debt(key, now)).min()
+    }
+
+    /// Forget the tasks that are not enqueued anymore, e.g. because they were processed or canceled.
+    ///
+    /// The tasks from `next_task_id` onwards may still be registering and are kept.
+    pub(crate) fn retain_enqueued(&self, enqueued: &RoaringBitmap, next_task_id: TaskId) {
+        let mut buckets = self.buckets.lock().unwrap();
+        buckets.tasks.retain(|task_id, _| *task_id >= next_task_id || enqueued.contains(*task_id));
+        // the full buckets without tasks are the same as new ones.
+        let Buckets { buckets: key_buckets, tasks } = &mut *buckets;
+        let now = Instant::now();
+        key_buckets.retain(|key, bucket| {
+            bucket.refill(now);
+            let limits = bucket.limits;
+            tasks.values().any(|task| task.key == *key)
+                || limits.indexed_documents_per_second.is_some_and(|r| bucket.documents < r as f64)
+                || limits.indexed_bytes_per_second.is_some_and(|r| bucket.bytes < r as f64)
+        });
+    }
+}
+
+impl IndexScheduler {
+    /// Register a new task on behalf of an API key whose rate limits may limit its indexing throughput.
+    ///
+    /// The document additions are refused while the key is indexing above its limits, and their
+    /// documents are then only batched at the pace allowed by the limits.
+    pub fn register_throttled(
+        &self,
+        kind: KindWithContent,
+        task_id: Option<TaskId>,
+        dry_run: bool,
+        key: Uuid,
+        limits: RateLimits,
+    ) -> Result<Task> {
+        let cost = match &kind {
+            KindWithContent::DocumentAdditionOrUpdate { content_file, documents_count, .. }
+                if limits.limits_indexing() =>
+            {
+                // a missing update file will make the task fail later, it doesn't weigh anything here.
+                let bytes = self.file_store.compute_size(*content_file).unwrap_or_default();
+                (*documents_count, bytes)
+            }
+            _ => return self.register(kind, task_id, dry_run),
+        };
+
+        if let Err(retry_after) = self.indexing_throttle.check(key, limits) {
+            return Err(Error::IndexingThrottled { retry_after });
+        }
+
+        let (documents, bytes) = cost;
+        self.register_task(kind, task_id, dry_run, None, |task_id| {
+            self.indexing_throttle.register(task_id, key, documents, bytes)
+        })
+    }
+
+    /// Returns the next enqueued task among the candidates that can be batched.
+    ///
+    /// A task can't be batched while its key is in debt, and neither can the tasks enqueued
+    /// after it on the same index.
+    pub(crate) fn next_unthrottled_task(
+        &self,
+        rtxn: &RoTxn,
+        enqueued: &RoaringBitmap,
+        candidates: &RoaringBitmap,
+    ) -> Result<Option<Task>> {
+        let mut candidates = candidates - self.indexing_throttle.throttled_tasks();
+        while let Some(task_id) = candidates.min() {
+            let task = self.get_task(rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
+            match task.indexes().as_slice() {
+                [index_uid] => {
+                    let index_tasks = self.index_tasks(rtxn, index_uid)? & enqueued;
+                    if index_tasks.min() == Some(task_id) {
+                        return Ok(Some(task));
+                    }
+                    // an earlier task of the index is throttled.
+                    candidates -= index_tasks;
+                }
+                _ => return Ok(Some(task)),
+            }
+        }
+        Ok(None)
+    }
+}
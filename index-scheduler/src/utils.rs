@@ -10,6 +10,7 @@ use meilisearch_types::tasks::{Details, IndexSwap, Kind, KindWithContent, Status
 use roaring::{MultiOps, RoaringBitmap};
 use time::OffsetDateTime;
 
+use crate::journal::JournalEntry;
 use crate::{Error, IndexScheduler, Result, Task, TaskId, BEI128};
 
 impl IndexScheduler {
@@ -54,6 +55,7 @@ impl IndexScheduler {
         }
 
         if old_task.status != task.status {
+            self.journal.append([JournalEntry::new(task.uid, Some(task.status))])?;
             self.update_status(wtxn, old_task.status, |bitmap| {
                 bitmap.remove(task.uid);
             })?;
@@ -245,6 +247,10 @@ pub fn swap_index_uid_in_task(task: &mut Task, swap: (&str, &str)) {
         K::IndexDeletion { index_uid } => index_uids.push(index_uid),
         K::IndexCreation { index_uid, .. } => index_uids.push(index_uid),
         K::IndexUpdate { index_uid, .. } => index_uids.push(index_uid),
+        K::IndexExport { index_uid } => index_uids.push(index_uid),
+        K::IndexPreviewCreation { index_uid, .. } => index_uids.push(index_uid),
+        K::IndexMerge { index_uid, .. } => index_uids.push(index_uid),
+        K::IndexSplit { index_uid, .. } => index_uids.push(index_uid),
         K::IndexSwap { swaps } => {
             for IndexSwap { indexes: (lhs, rhs) } in swaps.iter_mut() {
                 if lhs == swap.0 || lhs == swap.1 {
@@ -258,6 +264,7 @@ pub fn swap_index_uid_in_task(task: &mut Task, swap: (&str, &str)) {
         K::TaskCancelation { .. }
         | K::TaskDeletion { .. }
         | K::DumpCreation { .. }
+        | K::DumpImport { .. }
         | K::SnapshotCreation => (),
     };
     if let Some(Details::IndexSwap { swaps }) = &mut task.details {
@@ -346,7 +353,9 @@ impl IndexScheduler {
                 finished_at,
                 error: _,
                 canceled_by,
+                retried_from,
                 details,
+                warnings: _,
                 status,
                 kind,
             } = task;
@@ -378,6 +387,9 @@ impl IndexScheduler {
                     .unwrap();
                 assert!(db_finished_at.contains(task_id));
             }
+            if let Some(retried_from) = retried_from {
+                assert!(retried_from < uid);
+            }
             if let Some(canceled_by) = canceled_by {
                 let db_canceled_tasks = self.get_status(&rtxn, Status::Canceled).unwrap();
                 assert!(db_canceled_tasks.contains(uid));
@@ -419,6 +431,15 @@ impl IndexScheduler {
                     Details::SettingsUpdate { settings: _ } => {
                         assert_eq!(kind.as_kind(), Kind::SettingsUpdate);
                     }
+                    Details::IndexPreview { .. } => {
+                        assert_eq!(kind.as_kind(), Kind::IndexPreviewCreation);
+                    }
+                    Details::IndexMerge { .. } => {
+                        assert_eq!(kind.as_kind(), Kind::IndexMerge);
+                    }
+                    Details::IndexSplit { .. } => {
+                        assert_eq!(kind.as_kind(), Kind::IndexSplit);
+                    }
                     Details::IndexInfo { primary_key: pk1 } => match &kind {
                         KindWithContent::IndexCreation { index_uid, primary_key: pk2 }
                         | KindWithContent::IndexUpdate { index_uid, primary_key: pk2 } => {
@@ -525,7 +546,10 @@ impl IndexScheduler {
                         }
                     }
                     Details::Dump { dump_uid: _ } => {
-                        assert_eq!(kind.as_kind(), Kind::DumpCreation);
+                        assert!(matches!(
+                            kind.as_kind(),
+                            Kind::DumpCreation | Kind::DumpImport | Kind::IndexExport
+                        ));
                     }
                 }
             }
@@ -545,13 +569,15 @@ impl IndexScheduler {
                             self.file_store.all_uuids().unwrap().collect::<std::result::Result<Vec<_>, file_store::Error>>().unwrap(),
                         );
                     }
-                    Status::Succeeded | Status::Failed | Status::Canceled => {
+                    Status::Succeeded => {
                         assert!(self
                             .file_store
                             .all_uuids()
                             .unwrap()
                             .all(|uuid| uuid.as_ref().unwrap() != &content_file));
                     }
+                    // the update files of these tasks are kept to retry them
+                    Status::Failed | Status::Canceled => (),
                 }
             }
         }
@@ -0,0 +1,144 @@
+/*!
+The audit log of the requests modifying the instance.
+
+Every authenticated request that isn't a read is appended to a JSON-lines file with the key it was
+made with, the client address, the route, a summary of its payload and its outcome. The payload
+itself is never written to the log.
+
+When the log grows over its maximum size, it's moved aside and a new one is started, see
+[`RotatedFile`]. The log is written by a background thread so the requests never wait for the
+disk, the entries are dropped when more than [`MAX_PENDING_ENTRIES`] are waiting to be written.
+*/
+
+use std::io;
+use std::net::IpAddr;
+use std::path::Path;
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+use std::sync::Arc;
+
+use meilisearch_types::rotated_file::RotatedFile;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::error::Result;
+
+/// The number of entries waiting to be written in the log above which the new ones are dropped.
+pub const MAX_PENDING_ENTRIES: usize = 10_000;
+
+/// A request modifying the instance.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    #[serde(with = "time::serde::rfc3339")]
+    pub at: OffsetDateTime,
+    /// The uid of the API key the request was authenticated with, `None` for the master key.
+    pub key_uid: Option<Uuid>,
+    pub client_ip: Option<IpAddr>,
+    pub method: String,
+    pub path: String,
+    pub index_uid: Option<String>,
+    pub payload: PayloadSummary,
+    /// The HTTP status code of the response.
+    pub status: u16,
+}
+
+/// What is known of the payload of a request without reading it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadSummary {
+    pub content_type: Option<String>,
+    /// The size of the payload in bytes, `None` when it was streamed.
+    pub size: Option<u64>,
+}
+
+/// What is sent to the thread writing the log.
+enum Message {
+    Entry(AuditEntry),
+    /// Answered once the entries sent before are written.
+    Flush(SyncSender<()>),
+}
+
+/// An append-only and rotated file of [`AuditEntry`].
+pub struct AuditLog {
+    file: Arc<RotatedFile>,
+    sender: SyncSender<Message>,
+}
+
+impl AuditLog {
+    /// Open the audit log stored in the given directory, creating it if needed, and start the
+    /// thread writing it.
+    pub fn open(dir: impl AsRef<Path>, max_file_size: u64) -> Result<Self> {
+        let file = Arc::new(RotatedFile::open(dir, "audit", "jsonl", max_file_size)?);
+        let (sender, receiver) = mpsc::sync_channel(MAX_PENDING_ENTRIES);
+        let written = file.clone();
+        // stops once the audit log is dropped.
+        std::thread::Builder::new().name(String::from("audit-log")).spawn(move || {
+            for message in receiver {
+                match message {
+                    Message::Entry(entry) => {
+                        if let Err(e) = written.append(&entry) {
+                            tracing::error!("Could not write to the audit log: {e}");
+                        }
+                    }
+                    Message::Flush(done) => {
+                        let _ = done.send(());
+                    }
+                }
+            }
+        })?;
+        Ok(Self { file, sender })
+    }
+
+    /// Send the entry to the thread writing the log, without waiting for it.
+    pub fn append(&self, entry: AuditEntry) -> Result<()> {
+        match self.sender.try_send(Message::Entry(entry)) {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Full(_)) => {
+                Err(io::Error::new(io::ErrorKind::Other, "too many pending entries").into())
+            }
+            Err(TrySendError::Disconnected(_)) => {
+                Err(io::Error::new(io::ErrorKind::Other, "its writer stopped").into())
+            }
+        }
+    }
+
+    /// Return the entries matching the filter, from the most recent to the oldest, skipping the
+    /// `offset` most recent ones and keeping at most `limit` of them, with the number of entries
+    /// matching the filter.
+    ///
+    /// The logs are read twice instead of being loaded in memory. A line that was only partially
+    /// written when the engine stopped is ignored.
+    pub fn entries(
+        &self,
+        offset: usize,
+        limit: usize,
+        filter: impl Fn(&AuditEntry) -> bool,
+    ) -> Result<(Vec<AuditEntry>, usize)> {
+        // waits for the entries already sent to be written.
+        let (done, written) = mpsc::sync_channel(1);
+        if self.sender.send(Message::Flush(done)).is_ok() {
+            let _ = written.recv();
+        }
+
+        let snapshot = self.file.snapshot()?;
+        let mut total = 0;
+        snapshot.for_each(|entry: AuditEntry| total += filter(&entry) as usize)?;
+
+        // the logs are read from the oldest entry to the most recent one.
+        let end = total.saturating_sub(offset);
+        let start = end.saturating_sub(limit);
+        let mut entries = Vec::with_capacity(end - start);
+        let mut position = 0;
+        snapshot.for_each(|entry: AuditEntry| {
+            if filter(&entry) {
+                if (start..end).contains(&position) {
+                    entries.push(entry);
+                }
+                position += 1;
+            }
+        })?;
+        entries.reverse();
+        Ok((entries, total))
+    }
+}
@@ -11,6 +11,10 @@ pub enum AuthControllerError {
     ApiKeyNotFound(String),
     #[error("`uid` field value `{0}` is already an existing API key.")]
     ApiKeyAlreadyExists(String),
+    #[error("An API key can't grant more than the API key creating it.")]
+    ApiKeyExceedsPermissions,
+    #[error("The master key was replaced through a rotation on {0}. Meilisearch must be restarted with the new master key.")]
+    RetiredMasterKey(String),
     #[error("Internal error: {0}")]
     Internal(Box<dyn Error + Send + Sync + 'static>),
 }
@@ -27,6 +31,8 @@ impl ErrorCode for AuthControllerError {
         match self {
             Self::ApiKeyNotFound(_) => Code::ApiKeyNotFound,
             Self::ApiKeyAlreadyExists(_) => Code::ApiKeyAlreadyExists,
+            Self::ApiKeyExceedsPermissions => Code::ApiKeyExceedsPermissions,
+            Self::RetiredMasterKey(_) => Code::RetiredMasterKey,
             Self::Internal(_) => Code::Internal,
         }
     }
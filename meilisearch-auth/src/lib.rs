@@ -1,37 +1,126 @@
+mod audit;
 mod dump;
 pub mod error;
+mod rate_limit;
 mod store;
+mod usage;
 
 use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 
+use audit::AuditLog;
+pub use audit::{AuditEntry, PayloadSummary};
 use error::{AuthControllerError, Result};
 use maplit::hashset;
 use meilisearch_types::index_uid_pattern::IndexUidPattern;
-use meilisearch_types::keys::{Action, CreateApiKey, Key, PatchApiKey};
+use meilisearch_types::ip_network::IpNetwork;
+use meilisearch_types::keys::{Action, CreateApiKey, Key, PatchApiKey, RateLimits};
 use meilisearch_types::milli::update::Setting;
+pub use rate_limit::RateLimitedRequest;
+use rate_limit::RateLimiter;
 use serde::{Deserialize, Serialize};
 pub use store::open_auth_store_env;
-use store::{generate_key_as_hexa, HeedAuthStore};
+use store::{expand_actions, generate_key_as_hexa, HeedAuthStore};
+use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
+use usage::UsageRecorder;
+pub use usage::{KeyUsage, KeyUsageRequest};
 use uuid::Uuid;
 
 #[derive(Clone)]
 pub struct AuthController {
     store: Arc<HeedAuthStore>,
-    master_key: Option<String>,
+    master_keys: Arc<RwLock<MasterKeys>>,
+    rate_limiter: Arc<RateLimiter>,
+    usage_recorder: Arc<UsageRecorder>,
+    audit_log: Option<Arc<AuditLog>>,
+}
+
+/// The master key can be rotated while Meilisearch is running,
+/// the previous one stays valid during a grace period.
+#[derive(Default)]
+struct MasterKeys {
+    current: Option<String>,
+    /// The master key replaced by the current one, with the date until which it's still accepted.
+    previous: Option<(String, OffsetDateTime)>,
+}
+
+impl MasterKeys {
+    /// Return the master keys accepted right now, the current one first.
+    fn accepted(&self) -> Vec<String> {
+        let now = OffsetDateTime::now_utc();
+        let previous = self
+            .previous
+            .as_ref()
+            .filter(|(_, valid_until)| now <= *valid_until)
+            .map(|(master_key, _)| master_key);
+        self.current.iter().chain(previous).cloned().collect()
+    }
 }
 
 impl AuthController {
     pub fn new(db_path: impl AsRef<Path>, master_key: &Option<String>) -> Result<Self> {
         let store = HeedAuthStore::new(db_path)?;
 
+        // The rotations aren't persisted, a restart must not reinstate a replaced master key.
+        if let Some(master_key) = master_key {
+            if let Some(retired_at) = store.master_key_retired_at(master_key.as_bytes())? {
+                let retired_at = retired_at.format(&Rfc3339).unwrap_or_default();
+                return Err(AuthControllerError::RetiredMasterKey(retired_at));
+            }
+        }
+
         if store.is_empty()? {
             generate_default_keys(&store)?;
         }
 
-        Ok(Self { store: Arc::new(store), master_key: master_key.clone() })
+        Ok(Self {
+            store: Arc::new(store),
+            master_keys: Arc::new(RwLock::new(MasterKeys {
+                current: master_key.clone(),
+                previous: None,
+            })),
+            rate_limiter: Arc::default(),
+            usage_recorder: Arc::default(),
+            audit_log: None,
+        })
+    }
+
+    /// Record the requests modifying the instance in an audit log stored in the given directory,
+    /// rotated once it exceeds the given size in bytes.
+    pub fn with_audit_log(mut self, dir: impl AsRef<Path>, max_file_size: u64) -> Result<Self> {
+        self.audit_log = Some(Arc::new(AuditLog::open(dir, max_file_size)?));
+        Ok(self)
+    }
+
+    /// Return `true` if the requests modifying the instance are recorded in the audit log.
+    pub fn is_audit_log_enabled(&self) -> bool {
+        self.audit_log.is_some()
+    }
+
+    /// Append the request to the audit log in the background, if it's enabled.
+    pub fn audit(&self, entry: AuditEntry) -> Result<()> {
+        match &self.audit_log {
+            Some(audit_log) => audit_log.append(entry),
+            None => Ok(()),
+        }
+    }
+
+    /// Return a page of the requests recorded in the audit log matching the filter, from the
+    /// most recent to the oldest, with the number of requests matching the filter.
+    pub fn audit_entries(
+        &self,
+        offset: usize,
+        limit: usize,
+        filter: impl Fn(&AuditEntry) -> bool,
+    ) -> Result<(Vec<AuditEntry>, usize)> {
+        match &self.audit_log {
+            Some(audit_log) => audit_log.entries(offset, limit, filter),
+            None => Ok((Vec::new(), 0)),
+        }
     }
 
     /// Return `Ok(())` if the auth controller is able to access one of its database.
@@ -51,23 +140,116 @@ impl AuthController {
     }
 
     pub fn create_key(&self, create_key: CreateApiKey) -> Result<Key> {
-        match self.store.get_api_key(create_key.uid)? {
-            Some(_) => Err(AuthControllerError::ApiKeyAlreadyExists(create_key.uid.to_string())),
-            None => self.store.put_api_key(create_key.to_key()),
+        self.insert_key(create_key.to_key())
+    }
+
+    /// Create a key on behalf of the key `parent`, the new key can't grant more than its parent.
+    pub fn create_key_within(&self, parent: Uuid, create_key: CreateApiKey) -> Result<Key> {
+        let key = create_key.to_key();
+        if !self.is_key_within(&key, parent)? {
+            return Err(AuthControllerError::ApiKeyExceedsPermissions);
+        }
+        self.insert_key(key)
+    }
+
+    fn insert_key(&self, key: Key) -> Result<Key> {
+        match self.store.get_api_key(key.uid)? {
+            Some(_) => Err(AuthControllerError::ApiKeyAlreadyExists(key.uid.to_string())),
+            None => self.store.put_api_key(key),
         }
     }
 
+    /// Returns `true` if the key grants nothing more than the key `parent`:
+    /// - all its actions are granted to the parent itself on all its indexes,
+    /// - it expires no later than the parent,
+    /// - it's at least as restricted as the parent in terms of rate limits,
+    ///   allowed IPs and displayed attributes.
+    pub fn is_key_within(&self, key: &Key, parent: Uuid) -> Result<bool> {
+        let parent = self.get_key(parent)?;
+
+        if let Some(parent_expires_at) = parent.expires_at {
+            if key.expires_at.map_or(true, |expires_at| expires_at > parent_expires_at) {
+                return Ok(false);
+            }
+        }
+
+        for action in expand_actions(&key.actions) {
+            // the keys actions a parent only gets through `keys.manage` are never handed out,
+            // the new key would manage the keys without being restricted to its own permissions.
+            let coarser = action.implied_by().filter(|coarser| *coarser != Action::KeysManage);
+            for index in &key.indexes {
+                // a pattern is only matched by the patterns it's a part of, `*` by `*` only.
+                let index = Some(&**index);
+                let granted = self.is_action_granted(parent.uid, action, index)?
+                    || match coarser {
+                        Some(coarser) => self.is_action_granted(parent.uid, coarser, index)?,
+                        None => false,
+                    };
+                if !granted {
+                    return Ok(false);
+                }
+            }
+        }
+
+        if let Some(parent_limits) = parent.rate_limits {
+            let Some(limits) = key.rate_limits else { return Ok(false) };
+            fn within<T: PartialOrd>(limit: Option<T>, parent_limit: Option<T>) -> bool {
+                parent_limit.map_or(true, |parent_limit| limit.map_or(false, |l| l <= parent_limit))
+            }
+            if !(within(limits.search_per_second, parent_limits.search_per_second)
+                && within(limits.writes_per_minute, parent_limits.writes_per_minute)
+                && within(
+                    limits.indexed_documents_per_second,
+                    parent_limits.indexed_documents_per_second,
+                )
+                && within(limits.indexed_bytes_per_second, parent_limits.indexed_bytes_per_second))
+            {
+                return Ok(false);
+            }
+        }
+
+        if let Some(parent_networks) = &parent.allowed_ips {
+            let Some(networks) = &key.allowed_ips else { return Ok(false) };
+            if !networks.iter().all(|network| {
+                parent_networks
+                    .iter()
+                    .any(|parent_network| parent_network.contains_network(network))
+            }) {
+                return Ok(false);
+            }
+        }
+
+        if let Some(parent_attributes) =
+            parent.displayed_attributes.filter(|attrs| !attrs.iter().any(|attr| attr == "*"))
+        {
+            let Some(attributes) = &key.displayed_attributes else { return Ok(false) };
+            if !attributes.iter().all(|attr| parent_attributes.contains(attr)) {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
     pub fn update_key(&self, uid: Uuid, patch: PatchApiKey) -> Result<Key> {
-        let mut key = self.get_key(uid)?;
-        match patch.description {
-            Setting::NotSet => (),
-            description => key.description = description.set(),
-        };
-        match patch.name {
-            Setting::NotSet => (),
-            name => key.name = name.set(),
-        };
-        key.updated_at = OffsetDateTime::now_utc();
+        let key = patch_key(self.get_key(uid)?, patch);
+        self.put_updated_key(key)
+    }
+
+    /// Update a key on behalf of the key `parent`, the updated key can't grant more than its parent.
+    pub fn update_key_within(&self, uid: Uuid, patch: PatchApiKey, parent: Uuid) -> Result<Key> {
+        let key = patch_key(self.get_key(uid)?, patch);
+        if !self.is_key_within(&key, parent)? {
+            return Err(AuthControllerError::ApiKeyExceedsPermissions);
+        }
+        self.put_updated_key(key)
+    }
+
+    fn put_updated_key(&self, key: Key) -> Result<Key> {
+        // the requests counted against the previous rate limits don't count against the new ones.
+        if self.get_key(key.uid)?.rate_limits != key.rate_limits {
+            self.rate_limiter.reset(key.uid);
+        }
         self.store.put_api_key(key)
     }
 
@@ -78,12 +260,14 @@ impl AuthController {
     }
 
     pub fn get_optional_uid_from_encoded_key(&self, encoded_key: &[u8]) -> Result<Option<Uuid>> {
-        match &self.master_key {
-            Some(master_key) => {
-                self.store.get_uid_from_encoded_key(encoded_key, master_key.as_bytes())
+        // the keys derived from the previous master key are valid during its grace period.
+        for master_key in self.master_keys.read().unwrap().accepted() {
+            let uid = self.store.get_uid_from_encoded_key(encoded_key, master_key.as_bytes())?;
+            if uid.is_some() {
+                return Ok(uid);
             }
-            None => Ok(None),
         }
+        Ok(None)
     }
 
     pub fn get_uid_from_encoded_key(&self, encoded_key: &str) -> Result<Uuid> {
@@ -102,7 +286,17 @@ impl AuthController {
 
         let allow_index_creation = self.is_key_authorized(uid, Action::IndexesAdd, None)?;
 
-        Ok(AuthFilter { search_rules, key_authorized_indexes, allow_index_creation })
+        let rate_limits = key.rate_limits.map(|limits| (uid, limits));
+
+        Ok(AuthFilter {
+            search_rules,
+            key_authorized_indexes,
+            allow_index_creation,
+            rate_limits,
+            allowed_ips: key.allowed_ips,
+            displayed_attributes: key.displayed_attributes,
+            key_uid: Some(uid),
+        })
     }
 
     pub fn list_keys(&self) -> Result<Vec<Key>> {
@@ -111,20 +305,90 @@ impl AuthController {
 
     pub fn delete_key(&self, uid: Uuid) -> Result<()> {
         if self.store.delete_api_key(uid)? {
+            self.rate_limiter.reset(uid);
+            self.usage_recorder.reset(uid);
             Ok(())
         } else {
             Err(AuthControllerError::ApiKeyNotFound(uid.to_string()))
         }
     }
 
-    pub fn get_master_key(&self) -> Option<&String> {
-        self.master_key.as_ref()
+    pub fn get_master_key(&self) -> Option<String> {
+        self.master_keys.read().unwrap().current.clone()
+    }
+
+    /// Returns `true` if the token is the current master key,
+    /// or the previous one during its grace period.
+    pub fn is_master_key(&self, token: &str) -> bool {
+        self.master_keys.read().unwrap().accepted().iter().any(|master_key| master_key == token)
+    }
+
+    /// Returns `true` if the token is the current master key, the previous one being refused.
+    pub fn is_current_master_key(&self, token: &str) -> bool {
+        self.master_keys.read().unwrap().current.as_deref() == Some(token)
+    }
+
+    /// Replace the master key, the previous one and the keys derived from it
+    /// stay valid until the given date.
+    ///
+    /// The API keys aren't stored but derived from the master key on every request,
+    /// so they're all re-derived from the new master key right away.
+    ///
+    /// The new master key is only kept in memory and must also be set in the configuration.
+    /// The previous one is remembered, hashed, so that a restart with it is refused.
+    pub fn rotate_master_key(&self, master_key: String, valid_until: OffsetDateTime) -> Result<()> {
+        let mut master_keys = self.master_keys.write().unwrap();
+        if let Some(previous) = &master_keys.current {
+            self.store.retire_master_key(previous.as_bytes(), master_key.as_bytes())?;
+        }
+        let previous = master_keys.current.replace(master_key);
+        master_keys.previous = previous.map(|previous| (previous, valid_until));
+        Ok(())
     }
 
     /// Generate a valid key from a key id using the current master key.
     /// Returns None if no master key has been set.
     pub fn generate_key(&self, uid: Uuid) -> Option<String> {
-        self.master_key.as_ref().map(|master_key| generate_key_as_hexa(uid, master_key.as_bytes()))
+        self.get_master_key().map(|master_key| generate_key_as_hexa(uid, master_key.as_bytes()))
+    }
+
+    /// Generate the keys accepted right now from a key id, the one derived
+    /// from the current master key first.
+    pub fn generate_accepted_keys(&self, uid: Uuid) -> Vec<String> {
+        let master_keys = self.master_keys.read().unwrap().accepted();
+        master_keys
+            .iter()
+            .map(|master_key| generate_key_as_hexa(uid, master_key.as_bytes()))
+            .collect()
+    }
+
+    /// Count a request made with the key whose filters are given against its rate limits.
+    ///
+    /// Returns how long to wait before retrying when the key exceeded its limit.
+    pub fn hit_rate_limit(
+        &self,
+        filters: &AuthFilter,
+        request: RateLimitedRequest,
+    ) -> std::result::Result<(), Duration> {
+        match &filters.rate_limits {
+            Some((uid, limits)) => self.rate_limiter.hit(*uid, limits, request),
+            None => Ok(()),
+        }
+    }
+
+    /// Count a request made with the key the filters were generated from in its usage statistics.
+    pub fn record_key_usage(&self, filters: &AuthFilter, request: KeyUsageRequest) -> Result<()> {
+        let Some(uid) = filters.key_uid else { return Ok(()) };
+        match self.usage_recorder.hit(uid, request) {
+            Some(usage) => self.store.add_keys_usage(&usage),
+            None => Ok(()),
+        }
+    }
+
+    /// Return the requests made with the key, up to the last one.
+    pub fn get_key_usage(&self, uid: Uuid) -> Result<KeyUsage> {
+        self.store.add_keys_usage(&self.usage_recorder.take())?;
+        Ok(self.store.get_key_usage(uid)?.unwrap_or_default())
     }
 
     /// Check if the provided key is authorized to make a specific action
@@ -135,6 +399,19 @@ impl AuthController {
         action: Action,
         index: Option<&str>,
     ) -> Result<bool> {
+        if self.is_action_granted(uid, action, index)? {
+            return Ok(true);
+        }
+        // the action may be granted by a coarser one.
+        match action.implied_by() {
+            Some(action) => self.is_key_authorized(uid, action, index),
+            // action or index forbidden.
+            None => Ok(false),
+        }
+    }
+
+    /// Check if the provided key is granted the action itself, ignoring the coarser actions.
+    fn is_action_granted(&self, uid: Uuid, action: Action, index: Option<&str>) -> Result<bool> {
         match self
             .store
             // check if the key has access to all indexes.
@@ -154,6 +431,12 @@ impl AuthController {
         }
     }
 
+    /// Returns `true` if the key isn't granted the action itself but only the coarser
+    /// `keys.manage` action, the keys it manages must then grant no more than itself.
+    pub fn is_key_managing_only(&self, uid: Uuid, action: Action) -> Result<bool> {
+        Ok(!self.is_action_granted(uid, action, None)?)
+    }
+
     /// Delete all the keys in the DB.
     pub fn raw_delete_all_keys(&mut self) -> Result<()> {
         self.store.delete_all_keys()
@@ -170,6 +453,14 @@ pub struct AuthFilter {
     search_rules: Option<SearchRules>,
     key_authorized_indexes: SearchRules,
     allow_index_creation: bool,
+    /// The rate limits of the key the filter was generated from, with its uid.
+    rate_limits: Option<(Uuid, RateLimits)>,
+    /// The networks the key the filter was generated from can be used from.
+    allowed_ips: Option<Vec<IpNetwork>>,
+    /// The only attributes of the documents the key the filter was generated from can retrieve.
+    displayed_attributes: Option<Vec<String>>,
+    /// The uid of the key the filter was generated from, `None` for the master key.
+    key_uid: Option<Uuid>,
 }
 
 impl Default for AuthFilter {
@@ -178,6 +469,10 @@ impl Default for AuthFilter {
             search_rules: None,
             key_authorized_indexes: SearchRules::default(),
             allow_index_creation: true,
+            rate_limits: None,
+            allowed_ips: None,
+            displayed_attributes: None,
+            key_uid: None,
         }
     }
 }
@@ -188,11 +483,38 @@ impl AuthFilter {
         self.allow_index_creation && self.is_index_authorized(index)
     }
 
+    /// The uid of the key the filter was generated from, `None` for the master key.
+    pub fn key_uid(&self) -> Option<Uuid> {
+        self.key_uid
+    }
+
+    /// The rate limits of the key the filter was generated from, with its uid.
+    pub fn rate_limits(&self) -> Option<(Uuid, RateLimits)> {
+        self.rate_limits
+    }
+
     pub fn with_allowed_indexes(allowed_indexes: HashSet<IndexUidPattern>) -> Self {
         Self {
             search_rules: None,
             key_authorized_indexes: SearchRules::Set(allowed_indexes),
             allow_index_creation: false,
+            rate_limits: None,
+            allowed_ips: None,
+            displayed_attributes: None,
+            key_uid: None,
+        }
+    }
+
+    /// Returns `true` if the key can be used from the given address.
+    ///
+    /// A key restricted to some networks can't be used when the address is unknown.
+    pub fn is_ip_authorized(&self, addr: Option<IpAddr>) -> bool {
+        match (&self.allowed_ips, addr) {
+            (None, _) => true,
+            (Some(allowed_ips), Some(addr)) => {
+                allowed_ips.iter().any(|network| network.contains(addr))
+            }
+            (Some(_), None) => false,
         }
     }
 
@@ -221,6 +543,28 @@ impl AuthFilter {
         let search_rules = self.search_rules.as_ref().unwrap_or(&self.key_authorized_indexes);
         search_rules.get_index_search_rules(index)
     }
+
+    /// Returns the only attributes of the documents of this index that can be retrieved, `None`
+    /// when all the displayed attributes can be.
+    ///
+    /// Both the key and the search rules of the tenant token can restrict the attributes, in which
+    /// case only the attributes allowed by both of them can be retrieved.
+    pub fn displayed_attributes(&self, index: &str) -> Option<Vec<String>> {
+        let token_attributes = self
+            .search_rules
+            .as_ref()
+            .and_then(|search_rules| search_rules.get_index_search_rules(index))
+            .and_then(|rules| rules.displayed_attributes);
+
+        match (self.displayed_attributes.clone(), token_attributes) {
+            (None, attributes) | (attributes, None) => attributes,
+            (Some(key), Some(token)) if key.iter().any(|attr| attr == "*") => Some(token),
+            (Some(key), Some(token)) if token.iter().any(|attr| attr == "*") => Some(key),
+            (Some(key), Some(token)) => {
+                Some(key.into_iter().filter(|attr| token.contains(attr)).collect())
+            }
+        }
+    }
 }
 
 /// Transparent wrapper around a list of allowed indexes with the search rules to apply for each.
@@ -299,9 +643,38 @@ impl IntoIterator for SearchRules {
 /// Contains the rules to apply on the top of the search query for a specific index.
 ///
 /// filter: search filter to apply in addition to query filters.
+/// displayedAttributes: the only attributes of the documents that can be retrieved.
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct IndexSearchRules {
     pub filter: Option<serde_json::Value>,
+    #[serde(default)]
+    pub displayed_attributes: Option<Vec<String>>,
+}
+
+fn patch_key(mut key: Key, patch: PatchApiKey) -> Key {
+    match patch.description {
+        Setting::NotSet => (),
+        description => key.description = description.set(),
+    };
+    match patch.name {
+        Setting::NotSet => (),
+        name => key.name = name.set(),
+    };
+    match patch.rate_limits {
+        Setting::NotSet => (),
+        rate_limits => key.rate_limits = rate_limits.set(),
+    };
+    match patch.allowed_ips {
+        Setting::NotSet => (),
+        allowed_ips => key.allowed_ips = allowed_ips.set(),
+    };
+    match patch.displayed_attributes {
+        Setting::NotSet => (),
+        displayed_attributes => key.displayed_attributes = displayed_attributes.set(),
+    };
+    key.updated_at = OffsetDateTime::now_utc();
+    key
 }
 
 fn generate_default_keys(store: &HeedAuthStore) -> Result<()> {
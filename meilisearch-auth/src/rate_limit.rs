@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use meilisearch_types::keys::RateLimits;
+use uuid::Uuid;
+
+/// The kinds of requests that are limited independently of each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateLimitedRequest {
+    Search,
+    Write,
+}
+
+impl RateLimitedRequest {
+    /// Return the maximum number of requests and the period over which they are counted.
+    fn limit(&self, limits: &RateLimits) -> Option<(u32, Duration)> {
+        match self {
+            RateLimitedRequest::Search => {
+                limits.search_per_second.map(|limit| (limit, Duration::from_secs(1)))
+            }
+            RateLimitedRequest::Write => {
+                limits.writes_per_minute.map(|limit| (limit, Duration::from_secs(60)))
+            }
+        }
+    }
+}
+
+struct Window {
+    started_at: Instant,
+    requests: u32,
+}
+
+/// Counts the requests made by the API keys over fixed windows of time.
+///
+/// The counters are only kept in memory, they are reset when the engine restarts.
+#[derive(Default)]
+pub struct RateLimiter {
+    windows: Mutex<HashMap<(Uuid, RateLimitedRequest), Window>>,
+}
+
+impl RateLimiter {
+    /// Count a request made by the key.
+    ///
+    /// Returns how long the key must wait before making this kind of request again
+    /// when it exceeded its limit.
+    pub fn hit(
+        &self,
+        key: Uuid,
+        limits: &RateLimits,
+        request: RateLimitedRequest,
+    ) -> Result<(), Duration> {
+        let Some((limit, period)) = request.limit(limits) else { return Ok(()) };
+
+        let now = Instant::now();
+        let mut windows = self.windows.lock().unwrap();
+        let window =
+            windows.entry((key, request)).or_insert(Window { started_at: now, requests: 0 });
+        let elapsed = now.duration_since(window.started_at);
+        if elapsed >= period {
+            *window = Window { started_at: now, requests: 0 };
+        } else if window.requests >= limit {
+            return Err(period - elapsed);
+        }
+        window.requests += 1;
+        Ok(())
+    }
+
+    /// Forget the requests made by the key, e.g. when it's deleted or its limits change.
+    pub fn reset(&self, key: Uuid) {
+        self.windows.lock().unwrap().retain(|(uid, _), _| *uid != key);
+    }
+}
@@ -1,6 +1,6 @@
 use std::borrow::Cow;
 use std::cmp::Reverse;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs::create_dir_all;
 use std::path::Path;
 use std::result::Result as StdResult;
@@ -22,18 +22,23 @@ use uuid::fmt::Hyphenated;
 use uuid::Uuid;
 
 use super::error::{AuthControllerError, Result};
-use super::{Action, Key};
+use super::{Action, Key, KeyUsage};
 
 const AUTH_STORE_SIZE: usize = 1_073_741_824; //1GiB
 const AUTH_DB_PATH: &str = "auth";
 const KEY_DB_NAME: &str = "api-keys";
 const KEY_ID_ACTION_INDEX_EXPIRATION_DB_NAME: &str = "keyid-action-index-expiration";
+const KEY_USAGE_DB_NAME: &str = "api-keys-usage";
+const RETIRED_MASTER_KEYS_DB_NAME: &str = "retired-master-keys";
 
 #[derive(Clone)]
 pub struct HeedAuthStore {
     env: Arc<Env>,
     keys: Database<Bytes, SerdeJson<Key>>,
     action_keyid_index_expiration: Database<KeyIdActionCodec, SerdeJson<Option<OffsetDateTime>>>,
+    usage: Database<Bytes, SerdeJson<KeyUsage>>,
+    /// The hashes of the master keys replaced through a rotation, with the date they were replaced.
+    retired_master_keys: Database<Bytes, SerdeJson<OffsetDateTime>>,
     should_close_on_drop: bool,
 }
 
@@ -48,7 +53,7 @@ impl Drop for HeedAuthStore {
 pub fn open_auth_store_env(path: &Path) -> milli::heed::Result<milli::heed::Env> {
     let mut options = EnvOpenOptions::new();
     options.map_size(AUTH_STORE_SIZE); // 1GB
-    options.max_dbs(2);
+    options.max_dbs(4);
     options.open(path)
 }
 
@@ -61,8 +66,18 @@ impl HeedAuthStore {
         let keys = env.create_database(&mut wtxn, Some(KEY_DB_NAME))?;
         let action_keyid_index_expiration =
             env.create_database(&mut wtxn, Some(KEY_ID_ACTION_INDEX_EXPIRATION_DB_NAME))?;
+        let usage = env.create_database(&mut wtxn, Some(KEY_USAGE_DB_NAME))?;
+        let retired_master_keys =
+            env.create_database(&mut wtxn, Some(RETIRED_MASTER_KEYS_DB_NAME))?;
         wtxn.commit()?;
-        Ok(Self { env, keys, action_keyid_index_expiration, should_close_on_drop: true })
+        Ok(Self {
+            env,
+            keys,
+            action_keyid_index_expiration,
+            usage,
+            retired_master_keys,
+            should_close_on_drop: true,
+        })
     }
 
     /// Return `Ok(())` if the auth store is able to access one of its database.
@@ -103,51 +118,7 @@ impl HeedAuthStore {
         // create inverted database.
         let db = self.action_keyid_index_expiration;
 
-        let mut actions = HashSet::new();
-        for action in &key.actions {
-            match action {
-                Action::All => actions.extend(enum_iterator::all::<Action>()),
-                Action::DocumentsAll => {
-                    actions.extend(
-                        [Action::DocumentsGet, Action::DocumentsDelete, Action::DocumentsAdd]
-                            .iter(),
-                    );
-                }
-                Action::IndexesAll => {
-                    actions.extend(
-                        [
-                            Action::IndexesAdd,
-                            Action::IndexesDelete,
-                            Action::IndexesGet,
-                            Action::IndexesUpdate,
-                            Action::IndexesSwap,
-                        ]
-                        .iter(),
-                    );
-                }
-                Action::SettingsAll => {
-                    actions.extend([Action::SettingsGet, Action::SettingsUpdate].iter());
-                }
-                Action::DumpsAll => {
-                    actions.insert(Action::DumpsCreate);
-                }
-                Action::SnapshotsAll => {
-                    actions.insert(Action::SnapshotsCreate);
-                }
-                Action::TasksAll => {
-                    actions.extend([Action::TasksGet, Action::TasksDelete, Action::TasksCancel]);
-                }
-                Action::StatsAll => {
-                    actions.insert(Action::StatsGet);
-                }
-                Action::MetricsAll => {
-                    actions.insert(Action::MetricsGet);
-                }
-                other => {
-                    actions.insert(*other);
-                }
-            }
-        }
+        let actions = expand_actions(&key.actions);
 
         let no_index_restriction = key.indexes.iter().any(|p| p.matches_all());
         for action in actions {
@@ -207,6 +178,7 @@ impl HeedAuthStore {
         let mut wtxn = self.env.write_txn()?;
         let existing = self.keys.delete(&mut wtxn, uid.as_bytes())?;
         self.delete_key_from_inverted_db(&mut wtxn, &uid)?;
+        self.usage.delete(&mut wtxn, uid.as_bytes())?;
         wtxn.commit()?;
 
         Ok(existing)
@@ -215,10 +187,31 @@ impl HeedAuthStore {
     pub fn delete_all_keys(&self) -> Result<()> {
         let mut wtxn = self.env.write_txn()?;
         self.keys.clear(&mut wtxn)?;
+        self.usage.clear(&mut wtxn)?;
         wtxn.commit()?;
         Ok(())
     }
 
+    /// Remember that the previous master key was replaced by the new one, only their hashes are
+    /// stored. Rotating back to a retired master key makes it valid again.
+    pub fn retire_master_key(&self, previous: &[u8], new: &[u8]) -> Result<()> {
+        let mut wtxn = self.env.write_txn()?;
+        self.retired_master_keys.delete(&mut wtxn, &hash_master_key(new))?;
+        self.retired_master_keys.put(
+            &mut wtxn,
+            &hash_master_key(previous),
+            &OffsetDateTime::now_utc(),
+        )?;
+        wtxn.commit()?;
+        Ok(())
+    }
+
+    /// Returns the date the master key was replaced through a rotation, if it ever was.
+    pub fn master_key_retired_at(&self, master_key: &[u8]) -> Result<Option<OffsetDateTime>> {
+        let rtxn = self.env.read_txn()?;
+        Ok(self.retired_master_keys.get(&rtxn, &hash_master_key(master_key))?)
+    }
+
     pub fn list_api_keys(&self) -> Result<Vec<Key>> {
         let mut list = Vec::new();
         let rtxn = self.env.read_txn()?;
@@ -230,6 +223,28 @@ impl HeedAuthStore {
         Ok(list)
     }
 
+    pub fn get_key_usage(&self, uid: Uuid) -> Result<Option<KeyUsage>> {
+        let rtxn = self.env.read_txn()?;
+        self.usage.get(&rtxn, uid.as_bytes()).map_err(|e| e.into())
+    }
+
+    /// Add the usage counted since the last flush to the stored one.
+    ///
+    /// The usage of the keys deleted in the meantime is ignored.
+    pub fn add_keys_usage(&self, usage: &HashMap<Uuid, KeyUsage>) -> Result<()> {
+        let mut wtxn = self.env.write_txn()?;
+        for (uid, key_usage) in usage {
+            if self.keys.remap_data_type::<DecodeIgnore>().get(&wtxn, uid.as_bytes())?.is_none() {
+                continue;
+            }
+            let mut stored = self.usage.get(&wtxn, uid.as_bytes())?.unwrap_or_default();
+            stored.merge(key_usage);
+            self.usage.put(&mut wtxn, uid.as_bytes(), &stored)?;
+        }
+        wtxn.commit()?;
+        Ok(())
+    }
+
     pub fn get_expiration_date(
         &self,
         uid: Uuid,
@@ -337,6 +352,68 @@ pub struct InvalidActionError {
     pub action_byte: u8,
 }
 
+/// Returns the actions granted by the given ones, the `*` actions are replaced by the actions
+/// they grant.
+pub(crate) fn expand_actions(actions: &[Action]) -> HashSet<Action> {
+    let mut expanded = HashSet::new();
+    for action in actions {
+        match action {
+            Action::All => expanded.extend(enum_iterator::all::<Action>()),
+            Action::DocumentsAll => {
+                expanded.extend(
+                    [Action::DocumentsGet, Action::DocumentsDelete, Action::DocumentsAdd].iter(),
+                );
+            }
+            Action::IndexesAll => {
+                expanded.extend(
+                    [
+                        Action::IndexesAdd,
+                        Action::IndexesDelete,
+                        Action::IndexesGet,
+                        Action::IndexesUpdate,
+                        Action::IndexesSwap,
+                    ]
+                    .iter(),
+                );
+            }
+            Action::SettingsAll => {
+                expanded.extend([Action::SettingsGet, Action::SettingsUpdate].iter());
+            }
+            Action::DumpsAll => {
+                expanded.insert(Action::DumpsCreate);
+            }
+            Action::SnapshotsAll => {
+                expanded.insert(Action::SnapshotsCreate);
+            }
+            Action::TasksAll => {
+                expanded.extend([
+                    Action::TasksGet,
+                    Action::TasksDelete,
+                    Action::TasksCancel,
+                    Action::TasksRetry,
+                ]);
+            }
+            Action::StatsAll => {
+                expanded.insert(Action::StatsGet);
+            }
+            Action::MetricsAll => {
+                expanded.insert(Action::MetricsGet);
+            }
+            Action::SchedulesAll => {
+                expanded.extend([
+                    Action::SchedulesCreate,
+                    Action::SchedulesGet,
+                    Action::SchedulesDelete,
+                ]);
+            }
+            other => {
+                expanded.insert(*other);
+            }
+        }
+    }
+    expanded
+}
+
 pub fn generate_key_as_hexa(uid: Uuid, master_key: &[u8]) -> String {
     // format uid as hyphenated allowing user to generate their own keys.
     let mut uid_buffer = [0; Hyphenated::LENGTH];
@@ -350,6 +427,11 @@ pub fn generate_key_as_hexa(uid: Uuid, master_key: &[u8]) -> String {
     format!("{:x}", result.into_bytes())
 }
 
+/// The retired master keys are never stored in clear.
+fn hash_master_key(master_key: &[u8]) -> Vec<u8> {
+    <Sha256 as sha2::Digest>::digest(master_key).to_vec()
+}
+
 /// Divides one slice into two at an index, returns `None` if mid is out of bounds.
 pub fn try_split_at<T>(slice: &[T], mid: usize) -> Option<(&[T], &[T])> {
     if mid <= slice.len() {
@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+use std::mem;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// How often the usage counted in memory is written to the auth store.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The kinds of requests counted in the usage statistics of the API keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyUsageRequest {
+    Search,
+    DocumentsWrite,
+    Other,
+}
+
+/// The requests made with an API key, including the tenant tokens signed with it.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyUsage {
+    pub requests: u64,
+    pub searches: u64,
+    pub document_writes: u64,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub last_used_at: Option<OffsetDateTime>,
+}
+
+impl KeyUsage {
+    fn hit(&mut self, request: KeyUsageRequest, at: OffsetDateTime) {
+        self.requests += 1;
+        match request {
+            KeyUsageRequest::Search => self.searches += 1,
+            KeyUsageRequest::DocumentsWrite => self.document_writes += 1,
+            KeyUsageRequest::Other => (),
+        }
+        self.last_used_at = Some(at);
+    }
+
+    pub(crate) fn merge(&mut self, other: &KeyUsage) {
+        self.requests += other.requests;
+        self.searches += other.searches;
+        self.document_writes += other.document_writes;
+        self.last_used_at = self.last_used_at.max(other.last_used_at);
+    }
+}
+
+struct PendingUsage {
+    flushed_at: Instant,
+    keys: HashMap<Uuid, KeyUsage>,
+}
+
+impl PendingUsage {
+    fn take(&mut self) -> HashMap<Uuid, KeyUsage> {
+        self.flushed_at = Instant::now();
+        mem::take(&mut self.keys)
+    }
+}
+
+/// Counts the requests made by the API keys in memory before they're written to the auth store.
+///
+/// The requests counted since the last flush are lost if the engine crashes.
+pub struct UsageRecorder {
+    pending: Mutex<PendingUsage>,
+}
+
+impl Default for UsageRecorder {
+    fn default() -> Self {
+        Self {
+            pending: Mutex::new(PendingUsage { flushed_at: Instant::now(), keys: HashMap::new() }),
+        }
+    }
+}
+
+impl UsageRecorder {
+    /// Count a request made by the key.
+    ///
+    /// Returns the usage to write to the auth store when it's time to flush it.
+    pub fn hit(&self, key: Uuid, request: KeyUsageRequest) -> Option<HashMap<Uuid, KeyUsage>> {
+        let mut pending = self.pending.lock().unwrap();
+        pending.keys.entry(key).or_default().hit(request, OffsetDateTime::now_utc());
+        if pending.flushed_at.elapsed() >= FLUSH_INTERVAL {
+            Some(pending.take())
+        } else {
+            None
+        }
+    }
+
+    /// Take the usage counted since the last flush.
+    pub fn take(&self) -> HashMap<Uuid, KeyUsage> {
+        self.pending.lock().unwrap().take()
+    }
+
+    /// Forget the requests made by the key, e.g. when it's deleted.
+    pub fn reset(&self, key: Uuid) {
+        self.pending.lock().unwrap().keys.remove(&key);
+    }
+}
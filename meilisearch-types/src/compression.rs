@@ -1,10 +1,14 @@
+use std::fmt;
 use std::fs::{create_dir_all, File};
 use std::io::Write;
+use std::ops::RangeInclusive;
 use std::path::Path;
+use std::str::FromStr;
 
 use flate2::read::GzDecoder;
 use flate2::write::GzEncoder;
 use flate2::Compression;
+use serde::{Deserialize, Serialize};
 use tar::{Archive, Builder};
 
 pub fn to_tar_gz(src: impl AsRef<Path>, dest: impl AsRef<Path>) -> anyhow::Result<()> {
@@ -26,3 +30,88 @@ pub fn from_tar_gz(src: impl AsRef<Path>, dest: impl AsRef<Path>) -> anyhow::Res
     ar.unpack(&dest)?;
     Ok(())
 }
+
+/// The codec, and its level, with which a dump is compressed.
+///
+/// It's written `codec` or `codec:level`, e.g. `gzip:9`, `zstd:3` or `none`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DumpCompression {
+    pub codec: DumpCodec,
+    /// The level of the codec, its default level when `None`.
+    pub level: Option<u32>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DumpCodec {
+    #[default]
+    Gzip,
+    Zstd,
+    None,
+}
+
+impl DumpCodec {
+    /// The levels supported by the codec, `None` if it can't be configured.
+    pub fn levels(&self) -> Option<RangeInclusive<u32>> {
+        match self {
+            DumpCodec::Gzip => Some(0..=9),
+            DumpCodec::Zstd => Some(1..=22),
+            DumpCodec::None => None,
+        }
+    }
+}
+
+impl fmt::Display for DumpCodec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DumpCodec::Gzip => f.write_str("gzip"),
+            DumpCodec::Zstd => f.write_str("zstd"),
+            DumpCodec::None => f.write_str("none"),
+        }
+    }
+}
+
+impl fmt::Display for DumpCompression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.level {
+            Some(level) => write!(f, "{}:{level}", self.codec),
+            None => write!(f, "{}", self.codec),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseDumpCompressionError(String);
+
+impl fmt::Display for ParseDumpCompressionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid dump compression `{}`. ", self.0)?;
+        f.write_str("Expected `gzip`, `zstd` or `none`, optionally followed by a level, e.g. `gzip:9` with a level between 0 and 9 or `zstd:3` with a level between 1 and 22.")
+    }
+}
+
+impl std::error::Error for ParseDumpCompressionError {}
+
+impl FromStr for DumpCompression {
+    type Err = ParseDumpCompressionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseDumpCompressionError(s.to_string());
+        let (codec, level) = match s.trim().split_once(':') {
+            Some((codec, level)) => (codec, Some(level.trim().parse().map_err(|_| error())?)),
+            None => (s.trim(), None),
+        };
+        let codec = match codec.trim() {
+            "gzip" => DumpCodec::Gzip,
+            "zstd" => DumpCodec::Zstd,
+            "none" => DumpCodec::None,
+            _ => return Err(error()),
+        };
+        match (codec.levels(), level) {
+            (_, None) => Ok(DumpCompression { codec, level }),
+            (Some(levels), Some(l)) if levels.contains(&l) => Ok(DumpCompression { codec, level }),
+            _ => Err(error()),
+        }
+    }
+}
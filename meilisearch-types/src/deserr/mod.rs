@@ -6,12 +6,15 @@ use std::ops::ControlFlow;
 use deserr::errors::{JsonError, QueryParamError};
 use deserr::{take_cf_content, DeserializeError, IntoValue, MergeWithError, ValuePointerRef};
 
+use crate::compression::ParseDumpCompressionError;
+use crate::document_formats::InvalidFieldMapping;
 use crate::error::deserr_codes::*;
 use crate::error::{
     Code, DeserrParseBoolError, DeserrParseIntError, ErrorCode, InvalidTaskDateError,
     ParseOffsetDateTimeError,
 };
 use crate::index_uid::IndexUidFormatError;
+use crate::locales::ParseLocaleError;
 use crate::tasks::{ParseTaskKindError, ParseTaskStatusError};
 
 pub mod query_params;
@@ -151,6 +154,9 @@ make_missing_field_convenience_builder!(MissingApiKeyExpiresAt, missing_api_key_
 make_missing_field_convenience_builder!(MissingApiKeyIndexes, missing_api_key_indexes);
 make_missing_field_convenience_builder!(MissingSwapIndexes, missing_swap_indexes);
 make_missing_field_convenience_builder!(MissingDocumentFilter, missing_document_filter);
+make_missing_field_convenience_builder!(MissingDumpUid, missing_dump_uid);
+make_missing_field_convenience_builder!(MissingIndexMergeSource, missing_index_merge_source);
+make_missing_field_convenience_builder!(MissingIndexSplitFilter, missing_index_split_filter);
 make_missing_field_convenience_builder!(
     MissingFacetSearchFacetName,
     missing_facet_search_facet_name
@@ -189,3 +195,6 @@ merge_with_error_impl_take_error_message!(ParseTaskKindError);
 merge_with_error_impl_take_error_message!(ParseTaskStatusError);
 merge_with_error_impl_take_error_message!(IndexUidFormatError);
 merge_with_error_impl_take_error_message!(InvalidSearchSemanticRatio);
+merge_with_error_impl_take_error_message!(InvalidFieldMapping);
+merge_with_error_impl_take_error_message!(ParseDumpCompressionError);
+merge_with_error_impl_take_error_message!(ParseLocaleError);
@@ -1,7 +1,9 @@
+use std::collections::HashMap;
 use std::fmt::{self, Debug, Display};
 use std::fs::File;
 use std::io::{self, BufWriter, Write};
 use std::marker::PhantomData;
+use std::str::FromStr;
 
 use memmap2::MmapOptions;
 use milli::documents::{DocumentsBatchBuilder, Error};
@@ -14,7 +16,7 @@ use crate::error::{Code, ErrorCode};
 
 type Result<T> = std::result::Result<T, DocumentFormatError>;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum PayloadType {
     Ndjson,
     Json,
@@ -31,6 +33,99 @@ impl fmt::Display for PayloadType {
     }
 }
 
+/// The rules applied in order to the fields of every document while the payload is read.
+///
+/// A rule is written `source:target` and several rules are separated by commas, the field
+/// `source` is renamed `target`, an empty target drops the field and a source containing dots
+/// lifts the value of the nested field to the top level of the document.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldMapping {
+    rules: Vec<(String, Option<String>)>,
+}
+
+#[derive(Debug)]
+pub struct InvalidFieldMapping(String);
+
+impl fmt::Display for InvalidFieldMapping {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid field mapping rule `{}`. A rule must be written `source:target`, with an empty target to drop the field.",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for InvalidFieldMapping {}
+
+impl FromStr for FieldMapping {
+    type Err = InvalidFieldMapping;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let mut rules = Vec::new();
+        for rule in s.split(',').map(str::trim).filter(|rule| !rule.is_empty()) {
+            match rule.split_once(':') {
+                Some((source, target)) if !source.trim().is_empty() && !target.contains(':') => {
+                    let target = Some(target.trim()).filter(|t| !t.is_empty()).map(String::from);
+                    rules.push((source.trim().to_string(), target));
+                }
+                _ => return Err(InvalidFieldMapping(rule.to_string())),
+            }
+        }
+        Ok(FieldMapping { rules })
+    }
+}
+
+impl<'de> Deserialize<'de> for FieldMapping {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl FieldMapping {
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Renames, drops and lifts the fields of the object according to the rules.
+    pub fn apply(&self, object: &mut Object) {
+        for (source, target) in &self.rules {
+            let value = match object.remove(source) {
+                Some(value) => value,
+                None => match take_nested(object, source) {
+                    Some(value) => value,
+                    None => continue,
+                },
+            };
+            if let Some(target) = target {
+                object.insert(target.clone(), value);
+            }
+        }
+    }
+
+    /// Returns the name of a field once the rules are applied, `None` if it is dropped.
+    fn map_field(&self, field: &str) -> Option<String> {
+        let mut field = field.to_string();
+        for (source, target) in &self.rules {
+            if *source == field {
+                field = target.clone()?;
+            }
+        }
+        Some(field)
+    }
+}
+
+/// Removes and returns the value at the dotted `path` of the nested objects.
+fn take_nested(object: &mut Object, path: &str) -> Option<serde_json::Value> {
+    let (parent, key) = path.split_once('.')?;
+    match object.get_mut(parent)? {
+        serde_json::Value::Object(nested) => {
+            nested.remove(key).or_else(|| take_nested(nested, key))
+        }
+        _ => None,
+    }
+}
+
 #[derive(Debug)]
 pub enum DocumentFormatError {
     Io(io::Error),
@@ -104,25 +199,118 @@ impl ErrorCode for DocumentFormatError {
 }
 
 /// Reads CSV from input and write an obkv batch to writer.
-pub fn read_csv(file: &File, writer: impl Write, delimiter: u8) -> Result<u64> {
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::documents")]
+pub fn read_csv(
+    file: &File,
+    writer: impl Write,
+    delimiter: u8,
+    mapping: &FieldMapping,
+) -> Result<u64> {
     let mut builder = DocumentsBatchBuilder::new(BufWriter::new(writer));
-    let mmap = unsafe { MmapOptions::new().map(file)? };
-    let csv = csv::ReaderBuilder::new().delimiter(delimiter).from_reader(mmap.as_ref());
-    builder.append_csv(csv).map_err(|e| (PayloadType::Csv { delimiter }, e))?;
+    append_csv(&mut builder, file, delimiter, mapping)?;
+    finish_batch(builder)
+}
 
+/// Reads JSON from temporary file and write an obkv batch to writer.
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::documents")]
+pub fn read_json(file: &File, writer: impl Write, mapping: &FieldMapping) -> Result<u64> {
+    let mut builder = DocumentsBatchBuilder::new(BufWriter::new(writer));
+    append_json(&mut builder, file, mapping)?;
+    finish_batch(builder)
+}
+
+/// Reads JSON from temporary file  and write an obkv batch to writer.
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::documents")]
+pub fn read_ndjson(file: &File, writer: impl Write, mapping: &FieldMapping) -> Result<u64> {
+    let mut builder = DocumentsBatchBuilder::new(BufWriter::new(writer));
+    append_ndjson(&mut builder, file, mapping)?;
+    finish_batch(builder)
+}
+
+/// Reads several temporary files, each in its own format, and write them as a single obkv batch to writer.
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::documents")]
+pub fn read_mixed<'a>(
+    files: impl IntoIterator<Item = (&'a File, PayloadType)>,
+    writer: impl Write,
+    mapping: &FieldMapping,
+) -> Result<u64> {
+    let mut builder = DocumentsBatchBuilder::new(BufWriter::new(writer));
+    for (file, payload_type) in files {
+        match payload_type {
+            PayloadType::Json => append_json(&mut builder, file, mapping)?,
+            PayloadType::Csv { delimiter } => append_csv(&mut builder, file, delimiter, mapping)?,
+            PayloadType::Ndjson => append_ndjson(&mut builder, file, mapping)?,
+        }
+    }
+    finish_batch(builder)
+}
+
+fn finish_batch<W: Write>(builder: DocumentsBatchBuilder<W>) -> Result<u64> {
     let count = builder.documents_count();
     let _ = builder.into_inner().map_err(DocumentFormatError::Io)?;
 
     Ok(count as u64)
 }
 
-/// Reads JSON from temporary file and write an obkv batch to writer.
-pub fn read_json(file: &File, writer: impl Write) -> Result<u64> {
-    let mut builder = DocumentsBatchBuilder::new(BufWriter::new(writer));
+fn append_csv<W: Write>(
+    builder: &mut DocumentsBatchBuilder<W>,
+    file: &File,
+    delimiter: u8,
+    mapping: &FieldMapping,
+) -> Result<()> {
+    let mmap = unsafe { MmapOptions::new().map(file)? };
+    let mut csv = csv::ReaderBuilder::new().delimiter(delimiter).from_reader(mmap.as_ref());
+    let payload_type = PayloadType::Csv { delimiter };
+    if mapping.is_empty() {
+        builder.append_csv(csv).map_err(|e| (payload_type, e))?;
+        return Ok(());
+    }
+
+    let headers = csv.headers().map_err(|e| (payload_type, e.into()))?.clone();
+    let names: Vec<&str> = headers.iter().map(csv_field_name).collect();
+    let columns: Vec<_> = names.iter().map(|name| mapping.map_field(name)).collect();
+
+    // A column renamed into the name of another column replaces it, like in a json object.
+    let mut kept: HashMap<&str, usize> = HashMap::new();
+    for (i, column) in columns.iter().enumerate() {
+        let Some(column) = column else { continue };
+        match kept.get(column.as_str()) {
+            Some(&j) if column == names[i] && columns[j].as_deref() != Some(names[j]) => (),
+            _ => {
+                kept.insert(column, i);
+            }
+        }
+    }
+
+    builder
+        .append_csv_with_columns(csv, |i, _| {
+            columns[i].clone().filter(|column| kept.get(column.as_str()) == Some(&i))
+        })
+        .map_err(|e| (payload_type, e))?;
+
+    Ok(())
+}
+
+/// Returns the field name of a csv header, without its type.
+fn csv_field_name(header: &str) -> &str {
+    match header.rsplit_once(':') {
+        Some((name, "string" | "boolean" | "number")) => name,
+        _ => header,
+    }
+}
+
+fn append_json<W: Write>(
+    builder: &mut DocumentsBatchBuilder<W>,
+    file: &File,
+    mapping: &FieldMapping,
+) -> Result<()> {
     let mmap = unsafe { MmapOptions::new().map(file)? };
     let mut deserializer = serde_json::Deserializer::from_slice(&mmap);
 
-    match array_each(&mut deserializer, |obj| builder.append_json_object(&obj)) {
+    match array_each(&mut deserializer, |mut obj: Object| {
+        mapping.apply(&mut obj);
+        builder.append_json_object(&obj)
+    }) {
         // The json data has been deserialized and does not need to be processed again.
         // The data has been transferred to the writer during the deserialization process.
         Ok(Ok(_)) => (),
@@ -137,33 +325,32 @@ pub fn read_json(file: &File, writer: impl Write) -> Result<u64> {
                 ));
             }
 
-            let content: Object = serde_json::from_slice(&mmap)
+            let mut content: Object = serde_json::from_slice(&mmap)
                 .map_err(Error::Json)
                 .map_err(|e| (PayloadType::Json, e))?;
+            mapping.apply(&mut content);
             builder.append_json_object(&content).map_err(DocumentFormatError::Io)?;
         }
     }
 
-    let count = builder.documents_count();
-    let _ = builder.into_inner().map_err(DocumentFormatError::Io)?;
-
-    Ok(count as u64)
+    Ok(())
 }
 
-/// Reads JSON from temporary file  and write an obkv batch to writer.
-pub fn read_ndjson(file: &File, writer: impl Write) -> Result<u64> {
-    let mut builder = DocumentsBatchBuilder::new(BufWriter::new(writer));
+fn append_ndjson<W: Write>(
+    builder: &mut DocumentsBatchBuilder<W>,
+    file: &File,
+    mapping: &FieldMapping,
+) -> Result<()> {
     let mmap = unsafe { MmapOptions::new().map(file)? };
 
     for result in serde_json::Deserializer::from_slice(&mmap).into_iter() {
-        let object = result.map_err(Error::Json).map_err(|e| (PayloadType::Ndjson, e))?;
+        let mut object: Object =
+            result.map_err(Error::Json).map_err(|e| (PayloadType::Ndjson, e))?;
+        mapping.apply(&mut object);
         builder.append_json_object(&object).map_err(Into::into).map_err(DocumentFormatError::Io)?;
     }
 
-    let count = builder.documents_count();
-    let _ = builder.into_inner().map_err(Into::into).map_err(DocumentFormatError::Io)?;
-
-    Ok(count as u64)
+    Ok(())
 }
 
 /// The actual handling of the deserialization process in serde
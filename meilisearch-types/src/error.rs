@@ -19,6 +19,12 @@ pub struct ResponseError {
     error_type: String,
     #[serde(rename = "link")]
     error_link: String,
+    /// The number of seconds to wait before retrying the request, sent in the `Retry-After` header.
+    #[serde(skip)]
+    retry_after: Option<u64>,
+    /// The id the client gave to the request in its `X-Request-Id` header.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
 }
 
 impl ResponseError {
@@ -32,8 +38,22 @@ impl ResponseError {
             error_code: code.name(),
             error_type: code.type_(),
             error_link: code.url(),
+            retry_after: None,
+            request_id: None,
         }
     }
+
+    pub fn with_retry_after(mut self, retry_after: std::time::Duration) -> Self {
+        // a client retrying too early would be refused again.
+        let secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+        self.retry_after = Some(secs);
+        self
+    }
+
+    pub fn with_request_id(mut self, request_id: String) -> Self {
+        self.request_id = Some(request_id);
+        self
+    }
 }
 
 impl fmt::Display for ResponseError {
@@ -49,14 +69,22 @@ where
     T: std::error::Error + ErrorCode,
 {
     fn from(other: T) -> Self {
-        Self::from_msg(other.to_string(), other.error_code())
+        let error = Self::from_msg(other.to_string(), other.error_code());
+        match other.retry_after() {
+            Some(retry_after) => error.with_retry_after(retry_after),
+            None => error,
+        }
     }
 }
 
 impl aweb::error::ResponseError for ResponseError {
     fn error_response(&self) -> aweb::HttpResponse {
         let json = serde_json::to_vec(self).unwrap();
-        HttpResponseBuilder::new(self.status_code()).content_type("application/json").body(json)
+        let mut response = HttpResponseBuilder::new(self.status_code());
+        if let Some(retry_after) = self.retry_after {
+            response.insert_header(("Retry-After", retry_after.to_string()));
+        }
+        response.content_type("application/json").body(json)
     }
 
     fn status_code(&self) -> StatusCode {
@@ -86,6 +114,11 @@ pub trait ErrorCode {
     fn error_type(&self) -> String {
         self.error_code().type_()
     }
+
+    /// returns how long the client should wait before retrying, if it should
+    fn retry_after(&self) -> Option<std::time::Duration> {
+        None
+    }
 }
 
 #[allow(clippy::enum_variant_names)]
@@ -176,15 +209,21 @@ macro_rules! make_error_codes {
 // An exhaustive list of all the error codes used by meilisearch.
 make_error_codes! {
 ApiKeyAlreadyExists                   , InvalidRequest       , CONFLICT ;
+ApiKeyExceedsPermissions              , Auth                 , FORBIDDEN ;
 ApiKeyNotFound                        , InvalidRequest       , NOT_FOUND ;
+AuditLogDisabled                      , InvalidRequest       , BAD_REQUEST ;
 BadParameter                          , InvalidRequest       , BAD_REQUEST;
 BadRequest                            , InvalidRequest       , BAD_REQUEST;
 DatabaseSizeLimitReached              , Internal             , INTERNAL_SERVER_ERROR;
+DeadLetterNotFound                    , InvalidRequest       , NOT_FOUND ;
 DocumentNotFound                      , InvalidRequest       , NOT_FOUND;
 DumpAlreadyProcessing                 , InvalidRequest       , CONFLICT;
 DumpNotFound                          , InvalidRequest       , NOT_FOUND;
 DumpProcessFailed                     , Internal             , INTERNAL_SERVER_ERROR;
 DuplicateIndexFound                   , InvalidRequest       , BAD_REQUEST;
+EnrichmentWebhookFailed               , InvalidRequest       , BAD_REQUEST;
+ForbiddenAttribute                    , Auth                 , FORBIDDEN ;
+ForbiddenIpAddress                    , Auth                 , FORBIDDEN ;
 ImmutableApiKeyActions                , InvalidRequest       , BAD_REQUEST;
 ImmutableApiKeyCreatedAt              , InvalidRequest       , BAD_REQUEST;
 ImmutableApiKeyExpiresAt              , InvalidRequest       , BAD_REQUEST;
@@ -202,18 +241,32 @@ IndexPrimaryKeyAlreadyExists          , InvalidRequest       , BAD_REQUEST ;
 IndexPrimaryKeyMultipleCandidatesFound, InvalidRequest       , BAD_REQUEST;
 IndexPrimaryKeyNoCandidateFound       , InvalidRequest       , BAD_REQUEST ;
 Internal                              , Internal             , INTERNAL_SERVER_ERROR ;
+InvalidAnalyticsFrom                  , InvalidRequest       , BAD_REQUEST ;
+InvalidAnalyticsLimit                 , InvalidRequest       , BAD_REQUEST ;
+InvalidAnalyticsTo                    , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKey                         , Auth                 , FORBIDDEN ;
 InvalidApiKeyActions                  , InvalidRequest       , BAD_REQUEST ;
+InvalidApiKeyAllowedIps               , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyDescription              , InvalidRequest       , BAD_REQUEST ;
+InvalidApiKeyDisplayedAttributes      , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyExpiresAt                , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyIndexes                  , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyLimit                    , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyName                     , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyOffset                   , InvalidRequest       , BAD_REQUEST ;
+InvalidApiKeyRateLimits               , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyUid                      , InvalidRequest       , BAD_REQUEST ;
+InvalidAuditLogKeyUid                 , InvalidRequest       , BAD_REQUEST ;
+InvalidAuditLogLimit                  , InvalidRequest       , BAD_REQUEST ;
+InvalidAuditLogOffset                 , InvalidRequest       , BAD_REQUEST ;
+InvalidAutobatchingLimits             , InvalidRequest       , BAD_REQUEST ;
+InvalidClickDocumentId                , InvalidRequest       , BAD_REQUEST ;
+InvalidClickQ                         , InvalidRequest       , BAD_REQUEST ;
 InvalidContentType                    , InvalidRequest       , UNSUPPORTED_MEDIA_TYPE ;
 InvalidDocumentCsvDelimiter           , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentCsvSeparator           , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentFields                 , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentFieldMapping           , InvalidRequest       , BAD_REQUEST ;
 MissingDocumentFilter                 , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentFilter                 , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentGeoField               , InvalidRequest       , BAD_REQUEST ;
@@ -222,19 +275,43 @@ InvalidVectorsType                    , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentId                     , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentLimit                  , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentOffset                 , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentRoutes                 , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentRoutingField           , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentSort                   , InvalidRequest       , BAD_REQUEST ;
+InvalidDumpUid                        , InvalidRequest       , BAD_REQUEST ;
+InvalidDumpCompression                , InvalidRequest       , BAD_REQUEST ;
+NotAvailableInProxyMode               , InvalidRequest       , BAD_REQUEST ;
+ProxyBackendUnreachable               , System               , BAD_GATEWAY ;
 InvalidEmbedder                       , InvalidRequest       , BAD_REQUEST ;
 InvalidHybridQuery                    , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexLimit                     , InvalidRequest       , BAD_REQUEST ;
+InvalidIndexMergeOnConflict           , InvalidRequest       , BAD_REQUEST ;
+InvalidIndexMergeSource               , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexOffset                    , InvalidRequest       , BAD_REQUEST ;
+InvalidIndexPreviewTtl                , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexPrimaryKey                , InvalidRequest       , BAD_REQUEST ;
+InvalidIndexSplitFilter               , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexUid                       , InvalidRequest       , BAD_REQUEST ;
+InvalidMasterKeyGracePeriod           , InvalidRequest       , BAD_REQUEST ;
+InvalidMultiSearchAllowPartialFailures, InvalidRequest       , BAD_REQUEST ;
+InvalidNewMasterKey                   , InvalidRequest       , BAD_REQUEST ;
+InvalidResponseVersion                , InvalidRequest       , BAD_REQUEST ;
+InvalidScheduleInterval               , InvalidRequest       , BAD_REQUEST ;
+InvalidScheduleStartAt                , InvalidRequest       , BAD_REQUEST ;
+InvalidScheduleType                   , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchAggregates               , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchAttributesToSearchOn     , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchAttributesToCrop         , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchAttributesToHighlight    , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchAttributesToRetrieve     , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchBoosts                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchCropLength               , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchCropMarker               , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchDisjunctiveFacets        , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchFacets                   , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchFacetFilters             , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchGroupBy                  , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchNested                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchSemanticRatio            , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchFacetName           , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchFilter                   , InvalidRequest       , BAD_REQUEST ;
@@ -242,6 +319,7 @@ InvalidSearchHighlightPostTag         , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchHighlightPreTag          , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchHitsPerPage              , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchLimit                    , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchLocales                  , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchMatchingStrategy         , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchOffset                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchPage                     , InvalidRequest       , BAD_REQUEST ;
@@ -253,22 +331,36 @@ InvalidSearchShowMatchesPosition      , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchShowRankingScore         , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchShowRankingScoreDetails  , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchSort                     , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchSortFirst                , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchRetrieveVectors          , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchTemplate                 , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsDisplayedAttributes    , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsDistinctAttribute      , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsProximityPrecision     , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsPrefixSearch           , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsFaceting               , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsFilterableAttributes   , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsPagination             , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsQueryPlanHints         , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsSearchExclusions       , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsEnrichmentWebhook      , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsUnitConversions        , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsSearchCutoffMs         , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsSearchTemplates        , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsLocalizedAttributes    , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsEmbedders              , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsRankingRules           , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsSearchableAttributes   , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsSortableAttributes     , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsStopWords              , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsStemming               , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsDecompounding          , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsNonSeparatorTokens     , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsSeparatorTokens        , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsDictionary             , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsSynonyms               , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsTypoTolerance          , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsTypoToleranceDisableOnNumbers, InvalidRequest       , BAD_REQUEST ;
 InvalidState                          , Internal             , INTERNAL_SERVER_ERROR ;
 InvalidStoreFile                      , Internal             , INTERNAL_SERVER_ERROR ;
 InvalidSwapDuplicateIndexFound        , InvalidRequest       , BAD_REQUEST ;
@@ -285,8 +377,10 @@ InvalidTaskLimit                      , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskStatuses                   , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskTypes                      , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskUids                       , InvalidRequest       , BAD_REQUEST  ;
+InvalidTaskWaitMs                     , InvalidRequest       , BAD_REQUEST ;
 IoError                               , System               , UNPROCESSABLE_ENTITY;
 FeatureNotEnabled                     , InvalidRequest       , BAD_REQUEST ;
+FilterTooComplex                      , InvalidRequest       , BAD_REQUEST ;
 MalformedPayload                      , InvalidRequest       , BAD_REQUEST ;
 MaxFieldsLimitExceeded                , InvalidRequest       , BAD_REQUEST ;
 MissingApiKeyActions                  , InvalidRequest       , BAD_REQUEST ;
@@ -295,7 +389,10 @@ MissingApiKeyIndexes                  , InvalidRequest       , BAD_REQUEST ;
 MissingAuthorizationHeader            , Auth                 , UNAUTHORIZED ;
 MissingContentType                    , InvalidRequest       , UNSUPPORTED_MEDIA_TYPE ;
 MissingDocumentId                     , InvalidRequest       , BAD_REQUEST ;
+MissingDumpUid                        , InvalidRequest       , BAD_REQUEST ;
 MissingFacetSearchFacetName           , InvalidRequest       , BAD_REQUEST ;
+MissingIndexMergeSource               , InvalidRequest       , BAD_REQUEST ;
+MissingIndexSplitFilter               , InvalidRequest       , BAD_REQUEST ;
 MissingIndexUid                       , InvalidRequest       , BAD_REQUEST ;
 MissingMasterKey                      , Auth                 , UNAUTHORIZED ;
 MissingPayload                        , InvalidRequest       , BAD_REQUEST ;
@@ -304,12 +401,20 @@ MissingSwapIndexes                    , InvalidRequest       , BAD_REQUEST ;
 MissingTaskFilters                    , InvalidRequest       , BAD_REQUEST ;
 NoSpaceLeftOnDevice                   , System               , UNPROCESSABLE_ENTITY;
 PayloadTooLarge                       , InvalidRequest       , PAYLOAD_TOO_LARGE ;
+QueryLogDisabled                      , InvalidRequest       , BAD_REQUEST ;
+RateLimitExceeded                     , Auth                 , TOO_MANY_REQUESTS ;
+RecoveryReportNotFound                , InvalidRequest       , NOT_FOUND ;
+RetiredMasterKey                      , Auth                 , UNAUTHORIZED ;
+ScheduleNotFound                      , InvalidRequest       , NOT_FOUND ;
 TaskNotFound                          , InvalidRequest       , NOT_FOUND ;
+TaskNotRetryable                      , InvalidRequest       , BAD_REQUEST ;
 TooManyOpenFiles                      , System               , UNPROCESSABLE_ENTITY ;
+TooManySearchRequests                 , System               , SERVICE_UNAVAILABLE ;
 TooManyVectors                        , InvalidRequest       , BAD_REQUEST ;
 UnretrievableDocument                 , Internal             , BAD_REQUEST ;
 UnretrievableErrorCode                , InvalidRequest       , BAD_REQUEST ;
 UnsupportedMediaType                  , InvalidRequest       , UNSUPPORTED_MEDIA_TYPE ;
+WasmMiddlewareFailed                  , Internal             , INTERNAL_SERVER_ERROR ;
 
 // Experimental features
 VectorEmbeddingError                  , InvalidRequest       , BAD_REQUEST
@@ -342,9 +447,10 @@ impl ErrorCode for milli::Error {
                     UserError::InvalidFilter(_) => Code::InvalidSearchFilter,
                     UserError::InvalidFilterExpression(..) => Code::InvalidSearchFilter,
                     UserError::MissingDocumentId { .. } => Code::MissingDocumentId,
-                    UserError::InvalidDocumentId { .. } | UserError::TooManyDocumentIds { .. } => {
-                        Code::InvalidDocumentId
-                    }
+                    UserError::InvalidDocumentId { .. }
+                    | UserError::InvalidCompositeDocumentId { .. }
+                    | UserError::InvalidFloatDocumentId { .. }
+                    | UserError::TooManyDocumentIds { .. } => Code::InvalidDocumentId,
                     UserError::MissingDocumentField(_) => Code::InvalidDocumentFields,
                     UserError::InvalidFieldForSource { .. }
                     | UserError::MissingFieldForSource { .. }
@@ -363,12 +469,16 @@ impl ErrorCode for milli::Error {
                     UserError::SortRankingRuleMissing => Code::InvalidSearchSort,
                     UserError::InvalidFacetsDistribution { .. } => Code::InvalidSearchFacets,
                     UserError::InvalidSortableAttribute { .. } => Code::InvalidSearchSort,
+                    UserError::InvalidSortUnit { .. } => Code::InvalidSearchSort,
                     UserError::InvalidSearchableAttribute { .. } => {
                         Code::InvalidSearchAttributesToSearchOn
                     }
                     UserError::InvalidFacetSearchFacetName { .. } => {
                         Code::InvalidFacetSearchFacetName
                     }
+                    UserError::InvalidGroupByAttribute { .. } => Code::InvalidSearchGroupBy,
+                    UserError::InvalidAggregateAttribute { .. } => Code::InvalidSearchAggregates,
+                    UserError::InvalidNestedAttribute { .. } => Code::InvalidSearchNested,
                     UserError::CriterionError(_) => Code::InvalidSettingsRankingRules,
                     UserError::InvalidGeoField { .. } => Code::InvalidDocumentGeoField,
                     UserError::InvalidVectorDimensions { .. } => Code::InvalidVectorDimensions,
@@ -379,6 +489,15 @@ impl ErrorCode for milli::Error {
                     UserError::InvalidMinTypoWordLenSetting(_, _) => {
                         Code::InvalidSettingsTypoTolerance
                     }
+                    UserError::InvalidEnrichmentWebhookUrl(_)
+                    | UserError::InvalidEnrichmentWebhookBatchSize
+                    | UserError::MissingEnrichmentWebhookUrl => {
+                        Code::InvalidSettingsEnrichmentWebhook
+                    }
+                    UserError::MissingUnitConversionRates(_)
+                    | UserError::InvalidUnitConversionRate { .. } => {
+                        Code::InvalidSettingsUnitConversions
+                    }
                     UserError::InvalidEmbedder(_) => Code::InvalidEmbedder,
                     UserError::VectorEmbeddingError(_) => Code::VectorEmbeddingError,
                 }
@@ -0,0 +1,111 @@
+use std::error::Error;
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use deserr::Deserr;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Code, ErrorCode};
+
+/// An IPv4 or IPv6 network written in the CIDR notation, e.g. `192.168.0.0/16`.
+/// A single address can be written without its prefix length.
+#[derive(Serialize, Deserialize, Deserr, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(try_from = "String", into = "String")]
+#[deserr(try_from(&String) = FromStr::from_str -> IpNetworkFormatError)]
+pub struct IpNetwork {
+    addr: IpAddr,
+    prefix_len: u8,
+}
+
+impl IpNetwork {
+    /// Returns wether the address is part of this network.
+    pub fn contains(&self, addr: IpAddr) -> bool {
+        match (self.addr, addr) {
+            (IpAddr::V4(network), IpAddr::V4(addr)) => {
+                let mask = u32::MAX.checked_shl(32 - self.prefix_len as u32).unwrap_or(0);
+                u32::from(network) & mask == u32::from(addr) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(addr)) => {
+                let mask = u128::MAX.checked_shl(128 - self.prefix_len as u32).unwrap_or(0);
+                u128::from(network) & mask == u128::from(addr) & mask
+            }
+            // an IPv4 client connected through an IPv6 socket
+            (IpAddr::V4(_), IpAddr::V6(addr)) => {
+                addr.to_ipv4_mapped().map_or(false, |addr| self.contains(IpAddr::V4(addr)))
+            }
+            (IpAddr::V6(_), IpAddr::V4(_)) => false,
+        }
+    }
+
+    /// Returns wether all the addresses of the other network are part of this network.
+    pub fn contains_network(&self, other: &IpNetwork) -> bool {
+        self.prefix_len <= other.prefix_len && self.contains(other.addr)
+    }
+}
+
+impl fmt::Display for IpNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix_len)
+    }
+}
+
+impl FromStr for IpNetwork {
+    type Err = IpNetworkFormatError;
+
+    fn from_str(s: &str) -> Result<IpNetwork, IpNetworkFormatError> {
+        let error = || IpNetworkFormatError { invalid_network: s.to_string() };
+        let (addr, prefix_len) = match s.split_once('/') {
+            Some((addr, prefix_len)) => (addr, Some(prefix_len)),
+            None => (s, None),
+        };
+        let addr = IpAddr::from_str(addr).map_err(|_| error())?;
+        let max_prefix_len = if addr.is_ipv4() { 32 } else { 128 };
+        let prefix_len = match prefix_len {
+            Some(prefix_len) => prefix_len.parse().map_err(|_| error())?,
+            None => max_prefix_len,
+        };
+        if prefix_len > max_prefix_len {
+            return Err(error());
+        }
+        Ok(IpNetwork { addr, prefix_len })
+    }
+}
+
+impl TryFrom<String> for IpNetwork {
+    type Error = IpNetworkFormatError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<IpNetwork> for String {
+    fn from(network: IpNetwork) -> Self {
+        network.to_string()
+    }
+}
+
+#[derive(Debug)]
+pub struct IpNetworkFormatError {
+    pub invalid_network: String,
+}
+
+impl fmt::Display for IpNetworkFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` is not a valid IP network. IP networks can be an IPv4 or IPv6 \
+            address, optionally followed by a slash (/) and a prefix length.",
+            self.invalid_network,
+        )
+    }
+}
+
+impl Error for IpNetworkFormatError {}
+
+impl ErrorCode for IpNetworkFormatError {
+    fn error_code(&self) -> Code {
+        Code::InvalidApiKeyAllowedIps
+    }
+}
@@ -15,6 +15,7 @@ use crate::deserr::{immutable_field_error, DeserrError, DeserrJsonError};
 use crate::error::deserr_codes::*;
 use crate::error::{Code, ErrorCode, ParseOffsetDateTimeError};
 use crate::index_uid_pattern::{IndexUidPattern, IndexUidPatternFormatError};
+use crate::ip_network::{IpNetwork, IpNetworkFormatError};
 
 pub type KeyId = Uuid;
 
@@ -32,6 +33,20 @@ impl<C: Default + ErrorCode> MergeWithError<IndexUidPatternFormatError> for Dese
     }
 }
 
+impl<C: Default + ErrorCode> MergeWithError<IpNetworkFormatError> for DeserrJsonError<C> {
+    fn merge(
+        _self_: Option<Self>,
+        other: IpNetworkFormatError,
+        merge_location: deserr::ValuePointerRef,
+    ) -> std::ops::ControlFlow<Self, Self> {
+        DeserrError::error::<Infallible>(
+            None,
+            deserr::ErrorKind::Unexpected { msg: other.to_string() },
+            merge_location,
+        )
+    }
+}
+
 #[derive(Debug, Deserr)]
 #[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
 pub struct CreateApiKey {
@@ -47,11 +62,27 @@ pub struct CreateApiKey {
     pub indexes: Vec<IndexUidPattern>,
     #[deserr(error = DeserrJsonError<InvalidApiKeyExpiresAt>, try_from(Option<String>) = parse_expiration_date -> ParseOffsetDateTimeError, missing_field_error = DeserrJsonError::missing_api_key_expires_at)]
     pub expires_at: Option<OffsetDateTime>,
+    #[deserr(default, error = DeserrJsonError<InvalidApiKeyRateLimits>)]
+    pub rate_limits: Option<RateLimits>,
+    #[deserr(default, error = DeserrJsonError<InvalidApiKeyAllowedIps>)]
+    pub allowed_ips: Option<Vec<IpNetwork>>,
+    #[deserr(default, error = DeserrJsonError<InvalidApiKeyDisplayedAttributes>)]
+    pub displayed_attributes: Option<Vec<String>>,
 }
 
 impl CreateApiKey {
     pub fn to_key(self) -> Key {
-        let CreateApiKey { description, name, uid, actions, indexes, expires_at } = self;
+        let CreateApiKey {
+            description,
+            name,
+            uid,
+            actions,
+            indexes,
+            expires_at,
+            rate_limits,
+            allowed_ips,
+            displayed_attributes,
+        } = self;
         let now = OffsetDateTime::now_utc();
         Key {
             description,
@@ -60,6 +91,9 @@ impl CreateApiKey {
             actions,
             indexes,
             expires_at,
+            rate_limits,
+            allowed_ips,
+            displayed_attributes,
             created_at: now,
             updated_at: now,
         }
@@ -93,6 +127,68 @@ pub struct PatchApiKey {
     pub description: Setting<String>,
     #[deserr(default, error = DeserrJsonError<InvalidApiKeyName>)]
     pub name: Setting<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidApiKeyRateLimits>)]
+    pub rate_limits: Setting<RateLimits>,
+    #[deserr(default, error = DeserrJsonError<InvalidApiKeyAllowedIps>)]
+    pub allowed_ips: Setting<Vec<IpNetwork>>,
+    #[deserr(default, error = DeserrJsonError<InvalidApiKeyDisplayedAttributes>)]
+    pub displayed_attributes: Setting<Vec<String>>,
+}
+
+/// The maximum number of requests an API key can make, the requests above the limits are refused,
+/// and the maximum throughput at which the documents it adds are indexed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, Deserr)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields, validate = validate_rate_limits -> DeserrJsonError<InvalidApiKeyRateLimits>)]
+pub struct RateLimits {
+    /// The maximum number of search requests per second.
+    #[serde(default)]
+    #[deserr(default)]
+    pub search_per_second: Option<u32>,
+    /// The maximum number of requests modifying the instance per minute.
+    #[serde(default)]
+    #[deserr(default)]
+    pub writes_per_minute: Option<u32>,
+    /// The maximum number of documents indexed per second.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub indexed_documents_per_second: Option<u64>,
+    /// The maximum number of payload bytes indexed per second.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub indexed_bytes_per_second: Option<u64>,
+}
+
+impl RateLimits {
+    /// Returns `true` if the throughput at which the documents are indexed is limited.
+    pub fn limits_indexing(&self) -> bool {
+        self.indexed_documents_per_second.is_some() || self.indexed_bytes_per_second.is_some()
+    }
+}
+
+/// A throughput of zero would never let the documents be indexed.
+fn validate_rate_limits<E: DeserializeError>(
+    limits: RateLimits,
+    location: ValuePointerRef,
+) -> Result<RateLimits, E> {
+    let zero = [
+        ("indexedDocumentsPerSecond", limits.indexed_documents_per_second),
+        ("indexedBytesPerSecond", limits.indexed_bytes_per_second),
+    ]
+    .into_iter()
+    .find(|(_, rate)| *rate == Some(0));
+    match zero {
+        Some((field, _)) => Err(deserr::take_cf_content(E::error::<Infallible>(
+            None,
+            deserr::ErrorKind::Unexpected {
+                msg: format!(
+                    "`{field}` must be greater than 0, omit it to not limit the indexing."
+                ),
+            },
+            location,
+        ))),
+        None => Ok(limits),
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
@@ -106,6 +202,15 @@ pub struct Key {
     pub indexes: Vec<IndexUidPattern>,
     #[serde(with = "time::serde::rfc3339::option")]
     pub expires_at: Option<OffsetDateTime>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limits: Option<RateLimits>,
+    /// The networks the key can be used from, it can be used from anywhere when `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_ips: Option<Vec<IpNetwork>>,
+    /// The only attributes of the documents the key can retrieve, it can retrieve all the displayed
+    /// attributes when `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub displayed_attributes: Option<Vec<String>>,
     #[serde(with = "time::serde::rfc3339")]
     pub created_at: OffsetDateTime,
     #[serde(with = "time::serde::rfc3339")]
@@ -123,6 +228,9 @@ impl Key {
             actions: vec![Action::All],
             indexes: vec![IndexUidPattern::all()],
             expires_at: None,
+            rate_limits: None,
+            allowed_ips: None,
+            displayed_attributes: None,
             created_at: now,
             updated_at: now,
         }
@@ -138,6 +246,9 @@ impl Key {
             actions: vec![Action::Search],
             indexes: vec![IndexUidPattern::all()],
             expires_at: None,
+            rate_limits: None,
+            allowed_ips: None,
+            displayed_attributes: None,
             created_at: now,
             updated_at: now,
         }
@@ -284,6 +395,180 @@ pub enum Action {
     #[serde(rename = "experimental.update")]
     #[deserr(rename = "experimental.update")]
     ExperimentalFeaturesUpdate,
+    #[serde(rename = "schedules.*")]
+    #[deserr(rename = "schedules.*")]
+    SchedulesAll,
+    #[serde(rename = "schedules.create")]
+    #[deserr(rename = "schedules.create")]
+    SchedulesCreate,
+    #[serde(rename = "schedules.get")]
+    #[deserr(rename = "schedules.get")]
+    SchedulesGet,
+    #[serde(rename = "schedules.delete")]
+    #[deserr(rename = "schedules.delete")]
+    SchedulesDelete,
+    #[serde(rename = "settings.filterableAttributes.get")]
+    #[deserr(rename = "settings.filterableAttributes.get")]
+    SettingsFilterableAttributesGet,
+    #[serde(rename = "settings.filterableAttributes.update")]
+    #[deserr(rename = "settings.filterableAttributes.update")]
+    SettingsFilterableAttributesUpdate,
+    #[serde(rename = "settings.sortableAttributes.get")]
+    #[deserr(rename = "settings.sortableAttributes.get")]
+    SettingsSortableAttributesGet,
+    #[serde(rename = "settings.sortableAttributes.update")]
+    #[deserr(rename = "settings.sortableAttributes.update")]
+    SettingsSortableAttributesUpdate,
+    #[serde(rename = "settings.displayedAttributes.get")]
+    #[deserr(rename = "settings.displayedAttributes.get")]
+    SettingsDisplayedAttributesGet,
+    #[serde(rename = "settings.displayedAttributes.update")]
+    #[deserr(rename = "settings.displayedAttributes.update")]
+    SettingsDisplayedAttributesUpdate,
+    #[serde(rename = "settings.typoTolerance.get")]
+    #[deserr(rename = "settings.typoTolerance.get")]
+    SettingsTypoToleranceGet,
+    #[serde(rename = "settings.typoTolerance.update")]
+    #[deserr(rename = "settings.typoTolerance.update")]
+    SettingsTypoToleranceUpdate,
+    #[serde(rename = "settings.searchableAttributes.get")]
+    #[deserr(rename = "settings.searchableAttributes.get")]
+    SettingsSearchableAttributesGet,
+    #[serde(rename = "settings.searchableAttributes.update")]
+    #[deserr(rename = "settings.searchableAttributes.update")]
+    SettingsSearchableAttributesUpdate,
+    #[serde(rename = "settings.stopWords.get")]
+    #[deserr(rename = "settings.stopWords.get")]
+    SettingsStopWordsGet,
+    #[serde(rename = "settings.stopWords.update")]
+    #[deserr(rename = "settings.stopWords.update")]
+    SettingsStopWordsUpdate,
+    #[serde(rename = "settings.nonSeparatorTokens.get")]
+    #[deserr(rename = "settings.nonSeparatorTokens.get")]
+    SettingsNonSeparatorTokensGet,
+    #[serde(rename = "settings.nonSeparatorTokens.update")]
+    #[deserr(rename = "settings.nonSeparatorTokens.update")]
+    SettingsNonSeparatorTokensUpdate,
+    #[serde(rename = "settings.separatorTokens.get")]
+    #[deserr(rename = "settings.separatorTokens.get")]
+    SettingsSeparatorTokensGet,
+    #[serde(rename = "settings.separatorTokens.update")]
+    #[deserr(rename = "settings.separatorTokens.update")]
+    SettingsSeparatorTokensUpdate,
+    #[serde(rename = "settings.dictionary.get")]
+    #[deserr(rename = "settings.dictionary.get")]
+    SettingsDictionaryGet,
+    #[serde(rename = "settings.dictionary.update")]
+    #[deserr(rename = "settings.dictionary.update")]
+    SettingsDictionaryUpdate,
+    #[serde(rename = "settings.synonyms.get")]
+    #[deserr(rename = "settings.synonyms.get")]
+    SettingsSynonymsGet,
+    #[serde(rename = "settings.synonyms.update")]
+    #[deserr(rename = "settings.synonyms.update")]
+    SettingsSynonymsUpdate,
+    #[serde(rename = "settings.distinctAttribute.get")]
+    #[deserr(rename = "settings.distinctAttribute.get")]
+    SettingsDistinctAttributeGet,
+    #[serde(rename = "settings.distinctAttribute.update")]
+    #[deserr(rename = "settings.distinctAttribute.update")]
+    SettingsDistinctAttributeUpdate,
+    #[serde(rename = "settings.proximityPrecision.get")]
+    #[deserr(rename = "settings.proximityPrecision.get")]
+    SettingsProximityPrecisionGet,
+    #[serde(rename = "settings.proximityPrecision.update")]
+    #[deserr(rename = "settings.proximityPrecision.update")]
+    SettingsProximityPrecisionUpdate,
+    #[serde(rename = "settings.rankingRules.get")]
+    #[deserr(rename = "settings.rankingRules.get")]
+    SettingsRankingRulesGet,
+    #[serde(rename = "settings.rankingRules.update")]
+    #[deserr(rename = "settings.rankingRules.update")]
+    SettingsRankingRulesUpdate,
+    #[serde(rename = "settings.faceting.get")]
+    #[deserr(rename = "settings.faceting.get")]
+    SettingsFacetingGet,
+    #[serde(rename = "settings.faceting.update")]
+    #[deserr(rename = "settings.faceting.update")]
+    SettingsFacetingUpdate,
+    #[serde(rename = "settings.pagination.get")]
+    #[deserr(rename = "settings.pagination.get")]
+    SettingsPaginationGet,
+    #[serde(rename = "settings.pagination.update")]
+    #[deserr(rename = "settings.pagination.update")]
+    SettingsPaginationUpdate,
+    #[serde(rename = "settings.queryPlanHints.get")]
+    #[deserr(rename = "settings.queryPlanHints.get")]
+    SettingsQueryPlanHintsGet,
+    #[serde(rename = "settings.queryPlanHints.update")]
+    #[deserr(rename = "settings.queryPlanHints.update")]
+    SettingsQueryPlanHintsUpdate,
+    #[serde(rename = "settings.searchExclusions.get")]
+    #[deserr(rename = "settings.searchExclusions.get")]
+    SettingsSearchExclusionsGet,
+    #[serde(rename = "settings.searchExclusions.update")]
+    #[deserr(rename = "settings.searchExclusions.update")]
+    SettingsSearchExclusionsUpdate,
+    #[serde(rename = "settings.embedders.get")]
+    #[deserr(rename = "settings.embedders.get")]
+    SettingsEmbeddersGet,
+    #[serde(rename = "settings.embedders.update")]
+    #[deserr(rename = "settings.embedders.update")]
+    SettingsEmbeddersUpdate,
+    #[serde(rename = "settings.enrichmentWebhook.get")]
+    #[deserr(rename = "settings.enrichmentWebhook.get")]
+    SettingsEnrichmentWebhookGet,
+    #[serde(rename = "settings.enrichmentWebhook.update")]
+    #[deserr(rename = "settings.enrichmentWebhook.update")]
+    SettingsEnrichmentWebhookUpdate,
+    #[serde(rename = "settings.unitConversions.get")]
+    #[deserr(rename = "settings.unitConversions.get")]
+    SettingsUnitConversionsGet,
+    #[serde(rename = "settings.unitConversions.update")]
+    #[deserr(rename = "settings.unitConversions.update")]
+    SettingsUnitConversionsUpdate,
+    #[serde(rename = "settings.searchCutoffMs.get")]
+    #[deserr(rename = "settings.searchCutoffMs.get")]
+    SettingsSearchCutoffMsGet,
+    #[serde(rename = "settings.searchCutoffMs.update")]
+    #[deserr(rename = "settings.searchCutoffMs.update")]
+    SettingsSearchCutoffMsUpdate,
+    #[serde(rename = "tasks.retry")]
+    #[deserr(rename = "tasks.retry")]
+    TasksRetry,
+    #[serde(rename = "settings.localizedAttributes.get")]
+    #[deserr(rename = "settings.localizedAttributes.get")]
+    SettingsLocalizedAttributesGet,
+    #[serde(rename = "settings.localizedAttributes.update")]
+    #[deserr(rename = "settings.localizedAttributes.update")]
+    SettingsLocalizedAttributesUpdate,
+    #[serde(rename = "keys.manage")]
+    #[deserr(rename = "keys.manage")]
+    KeysManage,
+    #[serde(rename = "settings.stemming.get")]
+    #[deserr(rename = "settings.stemming.get")]
+    SettingsStemmingGet,
+    #[serde(rename = "settings.stemming.update")]
+    #[deserr(rename = "settings.stemming.update")]
+    SettingsStemmingUpdate,
+    #[serde(rename = "settings.decompounding.get")]
+    #[deserr(rename = "settings.decompounding.get")]
+    SettingsDecompoundingGet,
+    #[serde(rename = "settings.decompounding.update")]
+    #[deserr(rename = "settings.decompounding.update")]
+    SettingsDecompoundingUpdate,
+    #[serde(rename = "settings.prefixSearch.get")]
+    #[deserr(rename = "settings.prefixSearch.get")]
+    SettingsPrefixSearchGet,
+    #[serde(rename = "settings.prefixSearch.update")]
+    #[deserr(rename = "settings.prefixSearch.update")]
+    SettingsPrefixSearchUpdate,
+    #[serde(rename = "settings.searchTemplates.get")]
+    #[deserr(rename = "settings.searchTemplates.get")]
+    SettingsSearchTemplatesGet,
+    #[serde(rename = "settings.searchTemplates.update")]
+    #[deserr(rename = "settings.searchTemplates.update")]
+    SettingsSearchTemplatesUpdate,
 }
 
 impl Action {
@@ -323,6 +608,64 @@ impl Action {
             KEYS_DELETE => Some(Self::KeysDelete),
             EXPERIMENTAL_FEATURES_GET => Some(Self::ExperimentalFeaturesGet),
             EXPERIMENTAL_FEATURES_UPDATE => Some(Self::ExperimentalFeaturesUpdate),
+            SCHEDULES_ALL => Some(Self::SchedulesAll),
+            SCHEDULES_CREATE => Some(Self::SchedulesCreate),
+            SCHEDULES_GET => Some(Self::SchedulesGet),
+            SCHEDULES_DELETE => Some(Self::SchedulesDelete),
+            SETTINGS_FILTERABLE_ATTRIBUTES_GET => Some(Self::SettingsFilterableAttributesGet),
+            SETTINGS_FILTERABLE_ATTRIBUTES_UPDATE => Some(Self::SettingsFilterableAttributesUpdate),
+            SETTINGS_SORTABLE_ATTRIBUTES_GET => Some(Self::SettingsSortableAttributesGet),
+            SETTINGS_SORTABLE_ATTRIBUTES_UPDATE => Some(Self::SettingsSortableAttributesUpdate),
+            SETTINGS_DISPLAYED_ATTRIBUTES_GET => Some(Self::SettingsDisplayedAttributesGet),
+            SETTINGS_DISPLAYED_ATTRIBUTES_UPDATE => Some(Self::SettingsDisplayedAttributesUpdate),
+            SETTINGS_TYPO_TOLERANCE_GET => Some(Self::SettingsTypoToleranceGet),
+            SETTINGS_TYPO_TOLERANCE_UPDATE => Some(Self::SettingsTypoToleranceUpdate),
+            SETTINGS_SEARCHABLE_ATTRIBUTES_GET => Some(Self::SettingsSearchableAttributesGet),
+            SETTINGS_SEARCHABLE_ATTRIBUTES_UPDATE => Some(Self::SettingsSearchableAttributesUpdate),
+            SETTINGS_STOP_WORDS_GET => Some(Self::SettingsStopWordsGet),
+            SETTINGS_STOP_WORDS_UPDATE => Some(Self::SettingsStopWordsUpdate),
+            SETTINGS_NON_SEPARATOR_TOKENS_GET => Some(Self::SettingsNonSeparatorTokensGet),
+            SETTINGS_NON_SEPARATOR_TOKENS_UPDATE => Some(Self::SettingsNonSeparatorTokensUpdate),
+            SETTINGS_SEPARATOR_TOKENS_GET => Some(Self::SettingsSeparatorTokensGet),
+            SETTINGS_SEPARATOR_TOKENS_UPDATE => Some(Self::SettingsSeparatorTokensUpdate),
+            SETTINGS_DICTIONARY_GET => Some(Self::SettingsDictionaryGet),
+            SETTINGS_DICTIONARY_UPDATE => Some(Self::SettingsDictionaryUpdate),
+            SETTINGS_SYNONYMS_GET => Some(Self::SettingsSynonymsGet),
+            SETTINGS_SYNONYMS_UPDATE => Some(Self::SettingsSynonymsUpdate),
+            SETTINGS_DISTINCT_ATTRIBUTE_GET => Some(Self::SettingsDistinctAttributeGet),
+            SETTINGS_DISTINCT_ATTRIBUTE_UPDATE => Some(Self::SettingsDistinctAttributeUpdate),
+            SETTINGS_PROXIMITY_PRECISION_GET => Some(Self::SettingsProximityPrecisionGet),
+            SETTINGS_PROXIMITY_PRECISION_UPDATE => Some(Self::SettingsProximityPrecisionUpdate),
+            SETTINGS_RANKING_RULES_GET => Some(Self::SettingsRankingRulesGet),
+            SETTINGS_RANKING_RULES_UPDATE => Some(Self::SettingsRankingRulesUpdate),
+            SETTINGS_FACETING_GET => Some(Self::SettingsFacetingGet),
+            SETTINGS_FACETING_UPDATE => Some(Self::SettingsFacetingUpdate),
+            SETTINGS_PAGINATION_GET => Some(Self::SettingsPaginationGet),
+            SETTINGS_PAGINATION_UPDATE => Some(Self::SettingsPaginationUpdate),
+            SETTINGS_QUERY_PLAN_HINTS_GET => Some(Self::SettingsQueryPlanHintsGet),
+            SETTINGS_QUERY_PLAN_HINTS_UPDATE => Some(Self::SettingsQueryPlanHintsUpdate),
+            SETTINGS_SEARCH_EXCLUSIONS_GET => Some(Self::SettingsSearchExclusionsGet),
+            SETTINGS_SEARCH_EXCLUSIONS_UPDATE => Some(Self::SettingsSearchExclusionsUpdate),
+            SETTINGS_EMBEDDERS_GET => Some(Self::SettingsEmbeddersGet),
+            SETTINGS_EMBEDDERS_UPDATE => Some(Self::SettingsEmbeddersUpdate),
+            SETTINGS_ENRICHMENT_WEBHOOK_GET => Some(Self::SettingsEnrichmentWebhookGet),
+            SETTINGS_ENRICHMENT_WEBHOOK_UPDATE => Some(Self::SettingsEnrichmentWebhookUpdate),
+            SETTINGS_UNIT_CONVERSIONS_GET => Some(Self::SettingsUnitConversionsGet),
+            SETTINGS_UNIT_CONVERSIONS_UPDATE => Some(Self::SettingsUnitConversionsUpdate),
+            SETTINGS_SEARCH_CUTOFF_MS_GET => Some(Self::SettingsSearchCutoffMsGet),
+            SETTINGS_SEARCH_CUTOFF_MS_UPDATE => Some(Self::SettingsSearchCutoffMsUpdate),
+            TASKS_RETRY => Some(Self::TasksRetry),
+            SETTINGS_LOCALIZED_ATTRIBUTES_GET => Some(Self::SettingsLocalizedAttributesGet),
+            SETTINGS_LOCALIZED_ATTRIBUTES_UPDATE => Some(Self::SettingsLocalizedAttributesUpdate),
+            KEYS_MANAGE => Some(Self::KeysManage),
+            SETTINGS_STEMMING_GET => Some(Self::SettingsStemmingGet),
+            SETTINGS_STEMMING_UPDATE => Some(Self::SettingsStemmingUpdate),
+            SETTINGS_DECOMPOUNDING_GET => Some(Self::SettingsDecompoundingGet),
+            SETTINGS_DECOMPOUNDING_UPDATE => Some(Self::SettingsDecompoundingUpdate),
+            SETTINGS_PREFIX_SEARCH_GET => Some(Self::SettingsPrefixSearchGet),
+            SETTINGS_PREFIX_SEARCH_UPDATE => Some(Self::SettingsPrefixSearchUpdate),
+            SETTINGS_SEARCH_TEMPLATES_GET => Some(Self::SettingsSearchTemplatesGet),
+            SETTINGS_SEARCH_TEMPLATES_UPDATE => Some(Self::SettingsSearchTemplatesUpdate),
             _otherwise => None,
         }
     }
@@ -330,12 +673,75 @@ impl Action {
     pub const fn repr(&self) -> u8 {
         *self as u8
     }
+
+    /// Returns the coarser action that grants this one too,
+    /// e.g. `settings.update` for `settings.synonyms.update`.
+    pub const fn implied_by(&self) -> Option<Action> {
+        use Action::*;
+        match self {
+            SettingsFilterableAttributesGet
+            | SettingsSortableAttributesGet
+            | SettingsDisplayedAttributesGet
+            | SettingsTypoToleranceGet
+            | SettingsSearchableAttributesGet
+            | SettingsStopWordsGet
+            | SettingsNonSeparatorTokensGet
+            | SettingsSeparatorTokensGet
+            | SettingsDictionaryGet
+            | SettingsSynonymsGet
+            | SettingsDistinctAttributeGet
+            | SettingsProximityPrecisionGet
+            | SettingsRankingRulesGet
+            | SettingsFacetingGet
+            | SettingsPaginationGet
+            | SettingsQueryPlanHintsGet
+            | SettingsSearchExclusionsGet
+            | SettingsEmbeddersGet
+            | SettingsEnrichmentWebhookGet
+            | SettingsUnitConversionsGet
+            | SettingsSearchCutoffMsGet
+            | SettingsLocalizedAttributesGet
+            | SettingsStemmingGet
+            | SettingsDecompoundingGet
+            | SettingsPrefixSearchGet
+            | SettingsSearchTemplatesGet => Some(SettingsGet),
+            SettingsFilterableAttributesUpdate
+            | SettingsSortableAttributesUpdate
+            | SettingsDisplayedAttributesUpdate
+            | SettingsTypoToleranceUpdate
+            | SettingsSearchableAttributesUpdate
+            | SettingsStopWordsUpdate
+            | SettingsNonSeparatorTokensUpdate
+            | SettingsSeparatorTokensUpdate
+            | SettingsDictionaryUpdate
+            | SettingsSynonymsUpdate
+            | SettingsDistinctAttributeUpdate
+            | SettingsProximityPrecisionUpdate
+            | SettingsRankingRulesUpdate
+            | SettingsFacetingUpdate
+            | SettingsPaginationUpdate
+            | SettingsQueryPlanHintsUpdate
+            | SettingsSearchExclusionsUpdate
+            | SettingsEmbeddersUpdate
+            | SettingsEnrichmentWebhookUpdate
+            | SettingsUnitConversionsUpdate
+            | SettingsSearchCutoffMsUpdate
+            | SettingsLocalizedAttributesUpdate
+            | SettingsStemmingUpdate
+            | SettingsDecompoundingUpdate
+            | SettingsPrefixSearchUpdate
+            | SettingsSearchTemplatesUpdate => Some(SettingsUpdate),
+            // the keys managed this way can't grant more than the key managing them.
+            KeysAdd | KeysGet | KeysUpdate | KeysDelete => Some(KeysManage),
+            _otherwise => None,
+        }
+    }
 }
 
 pub mod actions {
     use super::Action::*;
 
-    pub(crate) const ALL: u8 = All.repr();
+    pub const ALL: u8 = All.repr();
     pub const SEARCH: u8 = Search.repr();
     pub const DOCUMENTS_ALL: u8 = DocumentsAll.repr();
     pub const DOCUMENTS_ADD: u8 = DocumentsAdd.repr();
@@ -368,4 +774,62 @@ pub mod actions {
     pub const KEYS_DELETE: u8 = KeysDelete.repr();
     pub const EXPERIMENTAL_FEATURES_GET: u8 = ExperimentalFeaturesGet.repr();
     pub const EXPERIMENTAL_FEATURES_UPDATE: u8 = ExperimentalFeaturesUpdate.repr();
+    pub const SCHEDULES_ALL: u8 = SchedulesAll.repr();
+    pub const SCHEDULES_CREATE: u8 = SchedulesCreate.repr();
+    pub const SCHEDULES_GET: u8 = SchedulesGet.repr();
+    pub const SCHEDULES_DELETE: u8 = SchedulesDelete.repr();
+    pub const SETTINGS_FILTERABLE_ATTRIBUTES_GET: u8 = SettingsFilterableAttributesGet.repr();
+    pub const SETTINGS_FILTERABLE_ATTRIBUTES_UPDATE: u8 = SettingsFilterableAttributesUpdate.repr();
+    pub const SETTINGS_SORTABLE_ATTRIBUTES_GET: u8 = SettingsSortableAttributesGet.repr();
+    pub const SETTINGS_SORTABLE_ATTRIBUTES_UPDATE: u8 = SettingsSortableAttributesUpdate.repr();
+    pub const SETTINGS_DISPLAYED_ATTRIBUTES_GET: u8 = SettingsDisplayedAttributesGet.repr();
+    pub const SETTINGS_DISPLAYED_ATTRIBUTES_UPDATE: u8 = SettingsDisplayedAttributesUpdate.repr();
+    pub const SETTINGS_TYPO_TOLERANCE_GET: u8 = SettingsTypoToleranceGet.repr();
+    pub const SETTINGS_TYPO_TOLERANCE_UPDATE: u8 = SettingsTypoToleranceUpdate.repr();
+    pub const SETTINGS_SEARCHABLE_ATTRIBUTES_GET: u8 = SettingsSearchableAttributesGet.repr();
+    pub const SETTINGS_SEARCHABLE_ATTRIBUTES_UPDATE: u8 = SettingsSearchableAttributesUpdate.repr();
+    pub const SETTINGS_STOP_WORDS_GET: u8 = SettingsStopWordsGet.repr();
+    pub const SETTINGS_STOP_WORDS_UPDATE: u8 = SettingsStopWordsUpdate.repr();
+    pub const SETTINGS_NON_SEPARATOR_TOKENS_GET: u8 = SettingsNonSeparatorTokensGet.repr();
+    pub const SETTINGS_NON_SEPARATOR_TOKENS_UPDATE: u8 = SettingsNonSeparatorTokensUpdate.repr();
+    pub const SETTINGS_SEPARATOR_TOKENS_GET: u8 = SettingsSeparatorTokensGet.repr();
+    pub const SETTINGS_SEPARATOR_TOKENS_UPDATE: u8 = SettingsSeparatorTokensUpdate.repr();
+    pub const SETTINGS_DICTIONARY_GET: u8 = SettingsDictionaryGet.repr();
+    pub const SETTINGS_DICTIONARY_UPDATE: u8 = SettingsDictionaryUpdate.repr();
+    pub const SETTINGS_SYNONYMS_GET: u8 = SettingsSynonymsGet.repr();
+    pub const SETTINGS_SYNONYMS_UPDATE: u8 = SettingsSynonymsUpdate.repr();
+    pub const SETTINGS_DISTINCT_ATTRIBUTE_GET: u8 = SettingsDistinctAttributeGet.repr();
+    pub const SETTINGS_DISTINCT_ATTRIBUTE_UPDATE: u8 = SettingsDistinctAttributeUpdate.repr();
+    pub const SETTINGS_PROXIMITY_PRECISION_GET: u8 = SettingsProximityPrecisionGet.repr();
+    pub const SETTINGS_PROXIMITY_PRECISION_UPDATE: u8 = SettingsProximityPrecisionUpdate.repr();
+    pub const SETTINGS_RANKING_RULES_GET: u8 = SettingsRankingRulesGet.repr();
+    pub const SETTINGS_RANKING_RULES_UPDATE: u8 = SettingsRankingRulesUpdate.repr();
+    pub const SETTINGS_FACETING_GET: u8 = SettingsFacetingGet.repr();
+    pub const SETTINGS_FACETING_UPDATE: u8 = SettingsFacetingUpdate.repr();
+    pub const SETTINGS_PAGINATION_GET: u8 = SettingsPaginationGet.repr();
+    pub const SETTINGS_PAGINATION_UPDATE: u8 = SettingsPaginationUpdate.repr();
+    pub const SETTINGS_QUERY_PLAN_HINTS_GET: u8 = SettingsQueryPlanHintsGet.repr();
+    pub const SETTINGS_QUERY_PLAN_HINTS_UPDATE: u8 = SettingsQueryPlanHintsUpdate.repr();
+    pub const SETTINGS_SEARCH_EXCLUSIONS_GET: u8 = SettingsSearchExclusionsGet.repr();
+    pub const SETTINGS_SEARCH_EXCLUSIONS_UPDATE: u8 = SettingsSearchExclusionsUpdate.repr();
+    pub const SETTINGS_EMBEDDERS_GET: u8 = SettingsEmbeddersGet.repr();
+    pub const SETTINGS_EMBEDDERS_UPDATE: u8 = SettingsEmbeddersUpdate.repr();
+    pub const SETTINGS_ENRICHMENT_WEBHOOK_GET: u8 = SettingsEnrichmentWebhookGet.repr();
+    pub const SETTINGS_ENRICHMENT_WEBHOOK_UPDATE: u8 = SettingsEnrichmentWebhookUpdate.repr();
+    pub const SETTINGS_UNIT_CONVERSIONS_GET: u8 = SettingsUnitConversionsGet.repr();
+    pub const SETTINGS_UNIT_CONVERSIONS_UPDATE: u8 = SettingsUnitConversionsUpdate.repr();
+    pub const SETTINGS_SEARCH_CUTOFF_MS_GET: u8 = SettingsSearchCutoffMsGet.repr();
+    pub const SETTINGS_SEARCH_CUTOFF_MS_UPDATE: u8 = SettingsSearchCutoffMsUpdate.repr();
+    pub const TASKS_RETRY: u8 = TasksRetry.repr();
+    pub const SETTINGS_LOCALIZED_ATTRIBUTES_GET: u8 = SettingsLocalizedAttributesGet.repr();
+    pub const SETTINGS_LOCALIZED_ATTRIBUTES_UPDATE: u8 = SettingsLocalizedAttributesUpdate.repr();
+    pub const KEYS_MANAGE: u8 = KeysManage.repr();
+    pub const SETTINGS_STEMMING_GET: u8 = SettingsStemmingGet.repr();
+    pub const SETTINGS_STEMMING_UPDATE: u8 = SettingsStemmingUpdate.repr();
+    pub const SETTINGS_DECOMPOUNDING_GET: u8 = SettingsDecompoundingGet.repr();
+    pub const SETTINGS_DECOMPOUNDING_UPDATE: u8 = SettingsDecompoundingUpdate.repr();
+    pub const SETTINGS_PREFIX_SEARCH_GET: u8 = SettingsPrefixSearchGet.repr();
+    pub const SETTINGS_PREFIX_SEARCH_UPDATE: u8 = SettingsPrefixSearchUpdate.repr();
+    pub const SETTINGS_SEARCH_TEMPLATES_GET: u8 = SettingsSearchTemplatesGet.repr();
+    pub const SETTINGS_SEARCH_TEMPLATES_UPDATE: u8 = SettingsSearchTemplatesUpdate.repr();
 }
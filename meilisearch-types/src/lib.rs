@@ -6,8 +6,12 @@ pub mod facet_values_sort;
 pub mod features;
 pub mod index_uid;
 pub mod index_uid_pattern;
+pub mod ip_network;
 pub mod keys;
+pub mod locales;
+pub mod rotated_file;
 pub mod settings;
+pub mod snapshot;
 pub mod star_or;
 pub mod task_view;
 pub mod tasks;
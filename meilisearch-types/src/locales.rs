@@ -0,0 +1,132 @@
+use std::convert::Infallible;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use deserr::{DeserializeError, Deserr, ErrorKind, IntoValue, ValuePointerRef};
+use milli::localized_attributes_rules::LocalizedAttributesRule;
+use milli::stemming::stemming_algorithm;
+use milli::tokenizer::Language;
+use serde::{Deserialize, Serialize};
+
+/// A language identified by its ISO 639-3 code, e.g. `jpn` for Japanese or `cmn` for Chinese.
+#[derive(Serialize, Deserialize, Deserr, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(try_from = "String", into = "String")]
+#[deserr(try_from(&String) = FromStr::from_str -> ParseLocaleError)]
+pub struct Locale(Language);
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0.name())
+    }
+}
+
+impl FromStr for Locale {
+    type Err = ParseLocaleError;
+
+    fn from_str(s: &str) -> Result<Locale, ParseLocaleError> {
+        // the unknown codes fall back to another language.
+        let language = Language::from_name(s);
+        if language.name() == s {
+            Ok(Locale(language))
+        } else {
+            Err(ParseLocaleError(s.to_string()))
+        }
+    }
+}
+
+impl TryFrom<String> for Locale {
+    type Error = ParseLocaleError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Locale> for String {
+    fn from(locale: Locale) -> Self {
+        locale.to_string()
+    }
+}
+
+impl From<Locale> for Language {
+    fn from(locale: Locale) -> Self {
+        locale.0
+    }
+}
+
+impl From<Language> for Locale {
+    fn from(language: Language) -> Self {
+        Locale(language)
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseLocaleError(pub String);
+
+impl fmt::Display for ParseLocaleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Unsupported locale `{}`, expected an ISO 639-3 language code such as `eng`, `jpn` or `cmn`.",
+            self.0
+        )
+    }
+}
+
+impl Error for ParseLocaleError {}
+
+/// A locale whose words can be reduced to their stem, e.g. `eng` or `fra`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct StemmingLocale(pub Locale);
+
+impl<E: DeserializeError> Deserr<E> for StemmingLocale {
+    fn deserialize_from_value<V: IntoValue>(
+        value: deserr::Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        let name = String::deserialize_from_value(value, location)?;
+        let msg = match Locale::from_str(&name) {
+            Ok(locale) if stemming_algorithm(locale.into()).is_some() => {
+                return Ok(StemmingLocale(locale))
+            }
+            Ok(locale) => format!(
+                "Unsupported stemming locale `{locale}`, there is no stemmer for this language."
+            ),
+            Err(error) => error.to_string(),
+        };
+        Err(deserr::take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::Unexpected { msg },
+            location,
+        )))
+    }
+}
+
+/// The locales of the fields matching any of the attribute patterns.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct LocalizedAttributesRuleView {
+    pub attribute_patterns: Vec<String>,
+    pub locales: Vec<Locale>,
+}
+
+impl From<LocalizedAttributesRule> for LocalizedAttributesRuleView {
+    fn from(rule: LocalizedAttributesRule) -> Self {
+        Self {
+            attribute_patterns: rule.attribute_patterns,
+            locales: rule.locales.into_iter().map(Locale::from).collect(),
+        }
+    }
+}
+
+impl From<LocalizedAttributesRuleView> for LocalizedAttributesRule {
+    fn from(view: LocalizedAttributesRuleView) -> Self {
+        Self {
+            attribute_patterns: view.attribute_patterns,
+            locales: view.locales.into_iter().map(Language::from).collect(),
+        }
+    }
+}
@@ -0,0 +1,146 @@
+/*!
+An append-only JSON-lines file, moved aside once it grows over its maximum size.
+
+When appending a line would make the current file grow over its maximum size, every file is
+shifted by one, `name.jsonl` becoming `name.1.jsonl` and so on, and a new one is started. Only
+the [`MAX_ROTATED_FILES`] most recent files are kept on top of the current one.
+*/
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// The number of files kept once they've been moved aside, on top of the current one.
+pub const MAX_ROTATED_FILES: usize = 9;
+
+struct CurrentFile {
+    file: File,
+    len: u64,
+}
+
+/// An append-only and rotated file of JSON lines.
+pub struct RotatedFile {
+    dir: PathBuf,
+    /// The name of the current file, without its extension.
+    name: &'static str,
+    extension: &'static str,
+    max_file_size: u64,
+    current: Mutex<CurrentFile>,
+}
+
+impl RotatedFile {
+    /// Open the `{name}.{extension}` file stored in the given directory, creating it if needed.
+    pub fn open(
+        dir: impl AsRef<Path>,
+        name: &'static str,
+        extension: &'static str,
+        max_file_size: u64,
+    ) -> io::Result<Self> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        let file = open_append(&dir.join(format!("{name}.{extension}")))?;
+        let len = file.metadata()?.len();
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            name,
+            extension,
+            max_file_size,
+            current: Mutex::new(CurrentFile { file, len }),
+        })
+    }
+
+    /// Append the value as a line, moving the files aside first if the current one is full.
+    pub fn append(&self, value: &impl Serialize) -> io::Result<()> {
+        let mut line = serde_json::to_vec(value)?;
+        line.push(b'\n');
+
+        let mut current = self.current.lock().unwrap();
+        if current.len != 0 && current.len + line.len() as u64 > self.max_file_size {
+            self.rotate()?;
+            *current = CurrentFile { file: open_append(&self.path(0))?, len: 0 };
+        }
+        current.file.write_all(&line)?;
+        current.len += line.len() as u64;
+        Ok(())
+    }
+
+    /// Calls `f` on the values of all the files kept, from the oldest to the most recent.
+    ///
+    /// A line that was only partially written when the engine stopped is ignored.
+    pub fn for_each<T: DeserializeOwned>(&self, f: impl FnMut(T)) -> io::Result<()> {
+        self.snapshot()?.for_each(f)
+    }
+
+    /// Opens all the files kept, to read them as they are now as many times as needed.
+    pub fn snapshot(&self) -> io::Result<RotatedFileSnapshot> {
+        // The files are opened under the lock, so no rotation happens in between, but read
+        // without it to not block the writers. An opened file can still be read once it's
+        // moved aside and the current one is only read up to its length at that time.
+        let mut files = Vec::new();
+        let current = self.current.lock().unwrap();
+        for n in (0..=MAX_ROTATED_FILES).rev() {
+            match File::open(self.path(n)) {
+                Ok(file) if n == 0 => files.push((file, current.len)),
+                Ok(file) => files.push((file, u64::MAX)),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(RotatedFileSnapshot { files })
+    }
+
+    /// Shift every file by one, the oldest one is deleted.
+    fn rotate(&self) -> io::Result<()> {
+        ignore_not_found(fs::remove_file(self.path(MAX_ROTATED_FILES)))?;
+        for n in (0..MAX_ROTATED_FILES).rev() {
+            ignore_not_found(fs::rename(self.path(n), self.path(n + 1)))?;
+        }
+        Ok(())
+    }
+
+    /// The path of the file moved aside `n` times, `0` being the current file.
+    fn path(&self, n: usize) -> PathBuf {
+        match n {
+            0 => self.dir.join(format!("{}.{}", self.name, self.extension)),
+            n => self.dir.join(format!("{}.{n}.{}", self.name, self.extension)),
+        }
+    }
+}
+
+/// The files of a [`RotatedFile`] as they were when it was taken, see [`RotatedFile::snapshot`].
+pub struct RotatedFileSnapshot {
+    /// The files from the oldest to the most recent, with the length to read.
+    files: Vec<(File, u64)>,
+}
+
+impl RotatedFileSnapshot {
+    /// Calls `f` on the values of all the files, from the oldest to the most recent.
+    ///
+    /// A line that was only partially written when the engine stopped is ignored.
+    pub fn for_each<T: DeserializeOwned>(&self, mut f: impl FnMut(T)) -> io::Result<()> {
+        for (mut file, len) in self.files.iter().map(|(file, len)| (file, *len)) {
+            file.seek(SeekFrom::Start(0))?;
+            for line in BufReader::new(file.take(len)).lines() {
+                if let Ok(value) = serde_json::from_str(&line?) {
+                    f(value);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn open_append(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+fn ignore_not_found(result: io::Result<()>) -> io::Result<()> {
+    match result {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        otherwise => otherwise,
+    }
+}
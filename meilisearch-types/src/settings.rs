@@ -6,16 +6,24 @@ use std::num::NonZeroUsize;
 use std::ops::ControlFlow;
 use std::str::FromStr;
 
-use deserr::{DeserializeError, Deserr, ErrorKind, MergeWithError, ValuePointerRef};
+use deserr::{
+    DeserializeError, Deserr, ErrorKind, IntoValue, MergeWithError, ValueKind, ValuePointerRef,
+};
 use fst::IntoStreamer;
 use milli::proximity::ProximityPrecision;
+use milli::tokenizer::Language;
+use milli::unit_conversion::UnitConversion;
 use milli::update::Setting;
-use milli::{Criterion, CriterionError, Index, DEFAULT_VALUES_PER_FACET};
+use milli::{
+    Criterion, CriterionError, ExclusionMode, FilterResolution, Index, PrefixSearch,
+    DEFAULT_VALUES_PER_FACET,
+};
 use serde::{Deserialize, Serialize, Serializer};
 
 use crate::deserr::DeserrJsonError;
 use crate::error::deserr_codes::*;
 use crate::facet_values_sort::FacetValuesSort;
+use crate::locales::{Locale, LocalizedAttributesRuleView, StemmingLocale};
 
 /// The maximum number of results that the engine
 /// will be able to return in one search call.
@@ -81,7 +89,7 @@ pub struct MinWordSizeTyposSetting {
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
-#[deserr(deny_unknown_fields, rename_all = camelCase, where_predicate = __Deserr_E: deserr::MergeWithError<DeserrJsonError<InvalidSettingsTypoTolerance>>)]
+#[deserr(deny_unknown_fields, rename_all = camelCase, where_predicate = __Deserr_E: deserr::MergeWithError<DeserrJsonError<InvalidSettingsTypoTolerance>> + deserr::MergeWithError<DeserrJsonError<InvalidSettingsTypoToleranceDisableOnNumbers>>)]
 pub struct TypoSettings {
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
@@ -95,6 +103,10 @@ pub struct TypoSettings {
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
     pub disable_on_attributes: Setting<BTreeSet<String>>,
+    /// Whether the words containing a digit, e.g. `A1023`, are only matched without typos.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsTypoToleranceDisableOnNumbers>)]
+    pub disable_on_numbers: Setting<bool>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
@@ -118,6 +130,87 @@ pub struct PaginationSettings {
     pub max_total_hits: Setting<usize>,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct QueryPlanHintsSettings {
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub filter_resolution: Setting<FilterResolutionView>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub proximity_max_candidates: Setting<u64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct SearchExclusionsSettings {
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub document_ids: Setting<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub filter: Setting<String>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub mode: Setting<ExclusionModeView>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct EnrichmentWebhookSettings {
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub url: Setting<String>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub batch_size: Setting<usize>,
+}
+
+/// The search parameters stored under a name, applied to the searches made with this template.
+///
+/// A parameter given in the search request itself takes precedence over the one of the template.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct SearchTemplate {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub filter: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub sort: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub facets: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub limit: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub attributes_to_retrieve: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub attributes_to_highlight: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub highlight_pre_tag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub highlight_post_tag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub attributes_to_crop: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub crop_length: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub crop_marker: Option<String>,
+}
+
 impl MergeWithError<milli::CriterionError> for DeserrJsonError<InvalidSettingsRankingRules> {
     fn merge(
         _self_: Option<Self>,
@@ -132,6 +225,95 @@ impl MergeWithError<milli::CriterionError> for DeserrJsonError<InvalidSettingsRa
     }
 }
 
+/// The stop words, either ignored in any text or only in the text of their language.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum StopWords {
+    /// e.g. `["the", "a"]`
+    Global(BTreeSet<String>),
+    /// The stop words keyed by the ISO 639-3 code of their language, e.g. `{ "eng": ["the"] }`
+    Localized(BTreeMap<String, BTreeSet<String>>),
+}
+
+impl StopWords {
+    /// The number of stop words, whatever their language.
+    pub fn word_count(&self) -> usize {
+        match self {
+            StopWords::Global(stop_words) => stop_words.len(),
+            StopWords::Localized(stop_words) => stop_words.values().map(BTreeSet::len).sum(),
+        }
+    }
+}
+
+impl<E: DeserializeError> Deserr<E> for StopWords {
+    fn deserialize_from_value<V: IntoValue>(
+        value: deserr::Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        match value {
+            value @ deserr::Value::Sequence(_) => {
+                BTreeSet::deserialize_from_value(value, location).map(StopWords::Global)
+            }
+            value @ deserr::Value::Map(_) => {
+                let stop_words =
+                    BTreeMap::<String, BTreeSet<String>>::deserialize_from_value(value, location)?;
+                check_locale_keys(&stop_words, location)?;
+                Ok(StopWords::Localized(stop_words))
+            }
+            value => Err(deserr::take_cf_content(E::error::<V>(
+                None,
+                ErrorKind::IncorrectValueKind {
+                    actual: value,
+                    accepted: &[ValueKind::Sequence, ValueKind::Map],
+                },
+                location,
+            ))),
+        }
+    }
+}
+
+/// The words the compound words of each language are made of, keyed by the ISO 639-3 code of
+/// their language, e.g. `{ "deu": ["haftpflicht", "versicherung"] }`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct DecompoundingDictionaries(pub BTreeMap<String, BTreeSet<String>>);
+
+impl DecompoundingDictionaries {
+    /// The number of words, whatever their language.
+    pub fn word_count(&self) -> usize {
+        self.0.values().map(BTreeSet::len).sum()
+    }
+}
+
+impl<E: DeserializeError> Deserr<E> for DecompoundingDictionaries {
+    fn deserialize_from_value<V: IntoValue>(
+        value: deserr::Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        let dictionaries =
+            BTreeMap::<String, BTreeSet<String>>::deserialize_from_value(value, location)?;
+        check_locale_keys(&dictionaries, location)?;
+        Ok(DecompoundingDictionaries(dictionaries))
+    }
+}
+
+/// Ensures the words are keyed by a known locale.
+fn check_locale_keys<E: DeserializeError>(
+    words: &BTreeMap<String, BTreeSet<String>>,
+    location: ValuePointerRef,
+) -> Result<(), E> {
+    for language in words.keys() {
+        if let Err(error) = Locale::from_str(language) {
+            return Err(deserr::take_cf_content(E::error::<Infallible>(
+                None,
+                ErrorKind::Unexpected { msg: error.to_string() },
+                location.push_key(language),
+            )));
+        }
+    }
+    Ok(())
+}
+
 /// Holds all the settings for an index. `T` can either be `Checked` if they represents settings
 /// whose validity is guaranteed, or `Unchecked` if they need to be validated. In the later case, a
 /// call to `check` will return a `Settings<Checked>` from a `Settings<Unchecked>`.
@@ -170,7 +352,13 @@ pub struct Settings<T> {
     pub ranking_rules: Setting<Vec<RankingRuleView>>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsStopWords>)]
-    pub stop_words: Setting<BTreeSet<String>>,
+    pub stop_words: Setting<StopWords>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsStemming>)]
+    pub stemming: Setting<Vec<StemmingLocale>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsDecompounding>)]
+    pub decompounding: Setting<DecompoundingDictionaries>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsNonSeparatorTokens>)]
     pub non_separator_tokens: Setting<BTreeSet<String>>,
@@ -190,6 +378,9 @@ pub struct Settings<T> {
     #[deserr(default, error = DeserrJsonError<InvalidSettingsProximityPrecision>)]
     pub proximity_precision: Setting<ProximityPrecisionView>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsPrefixSearch>)]
+    pub prefix_search: Setting<PrefixSearchView>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsTypoTolerance>)]
     pub typo_tolerance: Setting<TypoSettings>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
@@ -198,6 +389,27 @@ pub struct Settings<T> {
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsPagination>)]
     pub pagination: Setting<PaginationSettings>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsQueryPlanHints>)]
+    pub query_plan_hints: Setting<QueryPlanHintsSettings>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsSearchExclusions>)]
+    pub search_exclusions: Setting<SearchExclusionsSettings>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsEnrichmentWebhook>)]
+    pub enrichment_webhook: Setting<EnrichmentWebhookSettings>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsUnitConversions>)]
+    pub unit_conversions: Setting<BTreeMap<String, UnitConversion>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsSearchCutoffMs>)]
+    pub search_cutoff_ms: Setting<u64>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsSearchTemplates>)]
+    pub search_templates: Setting<BTreeMap<String, SearchTemplate>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsLocalizedAttributes>)]
+    pub localized_attributes: Setting<Vec<LocalizedAttributesRuleView>>,
 
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsEmbedders>)]
@@ -217,15 +429,25 @@ impl Settings<Checked> {
             sortable_attributes: Setting::Reset,
             ranking_rules: Setting::Reset,
             stop_words: Setting::Reset,
+            stemming: Setting::Reset,
+            decompounding: Setting::Reset,
             synonyms: Setting::Reset,
             non_separator_tokens: Setting::Reset,
             separator_tokens: Setting::Reset,
             dictionary: Setting::Reset,
             distinct_attribute: Setting::Reset,
             proximity_precision: Setting::Reset,
+            prefix_search: Setting::Reset,
             typo_tolerance: Setting::Reset,
             faceting: Setting::Reset,
             pagination: Setting::Reset,
+            query_plan_hints: Setting::Reset,
+            search_exclusions: Setting::Reset,
+            enrichment_webhook: Setting::Reset,
+            unit_conversions: Setting::Reset,
+            search_cutoff_ms: Setting::Reset,
+            search_templates: Setting::Reset,
+            localized_attributes: Setting::Reset,
             embedders: Setting::Reset,
             _kind: PhantomData,
         }
@@ -239,15 +461,25 @@ impl Settings<Checked> {
             sortable_attributes,
             ranking_rules,
             stop_words,
+            stemming,
+            decompounding,
             non_separator_tokens,
             separator_tokens,
             dictionary,
             synonyms,
             distinct_attribute,
             proximity_precision,
+            prefix_search,
             typo_tolerance,
             faceting,
             pagination,
+            query_plan_hints,
+            search_exclusions,
+            enrichment_webhook,
+            unit_conversions,
+            search_cutoff_ms,
+            search_templates,
+            localized_attributes,
             embedders,
             ..
         } = self;
@@ -259,15 +491,25 @@ impl Settings<Checked> {
             sortable_attributes,
             ranking_rules,
             stop_words,
+            stemming,
+            decompounding,
             non_separator_tokens,
             separator_tokens,
             dictionary,
             synonyms,
             distinct_attribute,
             proximity_precision,
+            prefix_search,
             typo_tolerance,
             faceting,
             pagination,
+            query_plan_hints,
+            search_exclusions,
+            enrichment_webhook,
+            unit_conversions,
+            search_cutoff_ms,
+            search_templates,
+            localized_attributes,
             embedders,
             _kind: PhantomData,
         }
@@ -305,15 +547,25 @@ impl Settings<Unchecked> {
             sortable_attributes: self.sortable_attributes,
             ranking_rules: self.ranking_rules,
             stop_words: self.stop_words,
+            stemming: self.stemming,
+            decompounding: self.decompounding,
             synonyms: self.synonyms,
             non_separator_tokens: self.non_separator_tokens,
             separator_tokens: self.separator_tokens,
             dictionary: self.dictionary,
             distinct_attribute: self.distinct_attribute,
             proximity_precision: self.proximity_precision,
+            prefix_search: self.prefix_search,
             typo_tolerance: self.typo_tolerance,
             faceting: self.faceting,
             pagination: self.pagination,
+            query_plan_hints: self.query_plan_hints,
+            search_exclusions: self.search_exclusions,
+            enrichment_webhook: self.enrichment_webhook,
+            unit_conversions: self.unit_conversions,
+            search_cutoff_ms: self.search_cutoff_ms,
+            search_templates: self.search_templates,
+            localized_attributes: self.localized_attributes,
             embedders: self.embedders,
             _kind: PhantomData,
         }
@@ -382,8 +634,34 @@ pub fn apply_settings_to_builder(
     }
 
     match settings.stop_words {
-        Setting::Set(ref stop_words) => builder.set_stop_words(stop_words.clone()),
-        Setting::Reset => builder.reset_stop_words(),
+        Setting::Set(StopWords::Global(ref stop_words)) => {
+            builder.set_stop_words(stop_words.clone());
+            builder.reset_localized_stop_words();
+        }
+        Setting::Set(StopWords::Localized(ref stop_words)) => {
+            builder.reset_stop_words();
+            builder.set_localized_stop_words(stop_words.clone());
+        }
+        Setting::Reset => {
+            builder.reset_stop_words();
+            builder.reset_localized_stop_words();
+        }
+        Setting::NotSet => (),
+    }
+
+    match settings.stemming {
+        Setting::Set(ref locales) => builder.set_stemming(
+            locales.iter().map(|StemmingLocale(locale)| locale.to_string()).collect(),
+        ),
+        Setting::Reset => builder.reset_stemming(),
+        Setting::NotSet => (),
+    }
+
+    match settings.decompounding {
+        Setting::Set(DecompoundingDictionaries(ref dictionaries)) => {
+            builder.set_decompounding(dictionaries.clone())
+        }
+        Setting::Reset => builder.reset_decompounding(),
         Setting::NotSet => (),
     }
 
@@ -427,6 +705,12 @@ pub fn apply_settings_to_builder(
         Setting::NotSet => (),
     }
 
+    match settings.prefix_search {
+        Setting::Set(prefix_search) => builder.set_prefix_search(prefix_search.into()),
+        Setting::Reset => builder.reset_prefix_search(),
+        Setting::NotSet => (),
+    }
+
     match settings.typo_tolerance {
         Setting::Set(ref value) => {
             match value.enabled {
@@ -470,6 +754,12 @@ pub fn apply_settings_to_builder(
                 Setting::Reset => builder.reset_exact_attributes(),
                 Setting::NotSet => (),
             }
+
+            match value.disable_on_numbers {
+                Setting::Set(val) => builder.set_disable_typos_on_numbers(val),
+                Setting::Reset => builder.reset_disable_typos_on_numbers(),
+                Setting::NotSet => (),
+            }
         }
         Setting::Reset => {
             // all typo settings need to be reset here.
@@ -478,6 +768,7 @@ pub fn apply_settings_to_builder(
             builder.reset_min_word_len_two_typos();
             builder.reset_exact_words();
             builder.reset_exact_attributes();
+            builder.reset_disable_typos_on_numbers();
         }
         Setting::NotSet => (),
     }
@@ -514,6 +805,104 @@ pub fn apply_settings_to_builder(
         Setting::NotSet => (),
     }
 
+    match settings.query_plan_hints {
+        Setting::Set(ref value) => {
+            match value.filter_resolution {
+                Setting::Set(val) => builder.set_filter_resolution(val.into()),
+                Setting::Reset => builder.reset_filter_resolution(),
+                Setting::NotSet => (),
+            }
+            match value.proximity_max_candidates {
+                Setting::Set(val) => builder.set_proximity_max_candidates(val),
+                Setting::Reset => builder.reset_proximity_max_candidates(),
+                Setting::NotSet => (),
+            }
+        }
+        Setting::Reset => {
+            builder.reset_filter_resolution();
+            builder.reset_proximity_max_candidates();
+        }
+        Setting::NotSet => (),
+    }
+
+    match settings.search_exclusions {
+        Setting::Set(ref value) => {
+            match value.document_ids {
+                Setting::Set(ref val) => builder.set_excluded_document_ids(val.clone()),
+                Setting::Reset => builder.reset_excluded_document_ids(),
+                Setting::NotSet => (),
+            }
+            match value.filter {
+                Setting::Set(ref val) => builder.set_exclusion_filter(val.clone()),
+                Setting::Reset => builder.reset_exclusion_filter(),
+                Setting::NotSet => (),
+            }
+            match value.mode {
+                Setting::Set(val) => builder.set_exclusion_mode(val.into()),
+                Setting::Reset => builder.reset_exclusion_mode(),
+                Setting::NotSet => (),
+            }
+        }
+        Setting::Reset => {
+            builder.reset_excluded_document_ids();
+            builder.reset_exclusion_filter();
+            builder.reset_exclusion_mode();
+        }
+        Setting::NotSet => (),
+    }
+
+    match settings.enrichment_webhook {
+        Setting::Set(ref value) => {
+            match value.url {
+                Setting::Set(ref val) => builder.set_enrichment_webhook_url(val.clone()),
+                Setting::Reset => builder.reset_enrichment_webhook_url(),
+                Setting::NotSet => (),
+            }
+            match value.batch_size {
+                Setting::Set(val) => builder.set_enrichment_webhook_batch_size(val),
+                Setting::Reset => builder.reset_enrichment_webhook_batch_size(),
+                Setting::NotSet => (),
+            }
+        }
+        Setting::Reset => {
+            builder.reset_enrichment_webhook_url();
+            builder.reset_enrichment_webhook_batch_size();
+        }
+        Setting::NotSet => (),
+    }
+
+    match settings.unit_conversions {
+        Setting::Set(ref conversions) => builder.set_unit_conversions(conversions.clone()),
+        Setting::Reset => builder.reset_unit_conversions(),
+        Setting::NotSet => (),
+    }
+
+    match settings.search_cutoff_ms {
+        Setting::Set(cutoff) => builder.set_search_cutoff(cutoff),
+        Setting::Reset => builder.reset_search_cutoff(),
+        Setting::NotSet => (),
+    }
+
+    match settings.search_templates {
+        Setting::Set(ref templates) => builder.set_search_templates(
+            templates
+                .iter()
+                // serializing the parameters of a template cannot fail
+                .map(|(name, template)| (name.clone(), serde_json::to_value(template).unwrap()))
+                .collect(),
+        ),
+        Setting::Reset => builder.reset_search_templates(),
+        Setting::NotSet => (),
+    }
+
+    match settings.localized_attributes {
+        Setting::Set(ref rules) => {
+            builder.set_localized_attributes_rules(rules.iter().cloned().map(Into::into).collect())
+        }
+        Setting::Reset => builder.reset_localized_attributes_rules(),
+        Setting::NotSet => (),
+    }
+
     match settings.embedders.clone() {
         Setting::Set(value) => builder.set_embedder_settings(value),
         Setting::Reset => builder.reset_embedder_settings(),
@@ -538,13 +927,33 @@ pub fn settings(
 
     let criteria = index.criteria(rtxn)?;
 
-    let stop_words = index
-        .stop_words(rtxn)?
-        .map(|stop_words| -> Result<BTreeSet<_>, milli::Error> {
-            Ok(stop_words.stream().into_strs()?.into_iter().collect())
-        })
-        .transpose()?
-        .unwrap_or_default();
+    let stop_words = match index.localized_stop_words(rtxn)? {
+        Some(stop_words) => StopWords::Localized(stop_words),
+        None => StopWords::Global(
+            index
+                .stop_words(rtxn)?
+                .map(|stop_words| -> Result<BTreeSet<_>, milli::Error> {
+                    Ok(stop_words.stream().into_strs()?.into_iter().collect())
+                })
+                .transpose()?
+                .unwrap_or_default(),
+        ),
+    };
+
+    // The stemming is only displayed when it is enabled for some languages.
+    let stemming = match index.stemming(rtxn)? {
+        Some(languages) => {
+            let locales = languages.iter().map(|name| Language::from_name(name).into());
+            Setting::Set(locales.map(StemmingLocale).collect())
+        }
+        None => Setting::NotSet,
+    };
+
+    // The decompounding is only displayed when some dictionaries are given.
+    let decompounding = match index.decompounding(rtxn)? {
+        Some(dictionaries) => Setting::Set(DecompoundingDictionaries(dictionaries)),
+        None => Setting::NotSet,
+    };
 
     let non_separator_tokens = index.non_separator_tokens(rtxn)?.unwrap_or_default();
     let separator_tokens = index.separator_tokens(rtxn)?.unwrap_or_default();
@@ -554,6 +963,12 @@ pub fn settings(
 
     let proximity_precision = index.proximity_precision(rtxn)?.map(ProximityPrecisionView::from);
 
+    // The prefix search is only displayed when it was explicitly set.
+    let prefix_search = match index.prefix_search(rtxn)? {
+        Some(prefix_search) => Setting::Set(prefix_search.into()),
+        None => Setting::NotSet,
+    };
+
     let synonyms = index.user_defined_synonyms(rtxn)?;
 
     let min_typo_word_len = MinWordSizeTyposSetting {
@@ -573,6 +988,7 @@ pub fn settings(
         min_word_size_for_typos: Setting::Set(min_typo_word_len),
         disable_on_words: Setting::Set(disabled_words),
         disable_on_attributes: Setting::Set(disabled_attributes),
+        disable_on_numbers: Setting::Set(index.disable_typos_on_numbers(rtxn)?),
     };
 
     let faceting = FacetingSettings {
@@ -600,6 +1016,64 @@ pub fn settings(
         ),
     };
 
+    let query_plan_hints = index.query_plan_hints(rtxn)?.unwrap_or_default();
+    let query_plan_hints = QueryPlanHintsSettings {
+        filter_resolution: Setting::Set(query_plan_hints.filter_resolution.into()),
+        proximity_max_candidates: match query_plan_hints.proximity_max_candidates {
+            Some(max) => Setting::Set(max),
+            None => Setting::Reset,
+        },
+    };
+
+    // The exclusions are only displayed when some documents are concerned.
+    let search_exclusions = match index.search_exclusions(rtxn)? {
+        Some(exclusions) => Setting::Set(SearchExclusionsSettings {
+            document_ids: Setting::Set(exclusions.document_ids),
+            filter: match exclusions.filter {
+                Some(filter) => Setting::Set(filter),
+                None => Setting::Reset,
+            },
+            mode: Setting::Set(exclusions.mode.into()),
+        }),
+        None => Setting::NotSet,
+    };
+
+    // The enrichment webhook is only displayed when it's configured.
+    let enrichment_webhook = match index.enrichment_webhook(rtxn)? {
+        Some(webhook) => Setting::Set(EnrichmentWebhookSettings {
+            url: Setting::Set(webhook.url),
+            batch_size: Setting::Set(webhook.batch_size),
+        }),
+        None => Setting::NotSet,
+    };
+
+    // The unit conversions are only displayed when there are some.
+    let unit_conversions = index.unit_conversions(rtxn)?;
+    let unit_conversions =
+        if unit_conversions.is_empty() { Setting::NotSet } else { Setting::Set(unit_conversions) };
+
+    // The search cutoff is only displayed when it is set.
+    let search_cutoff_ms = match index.search_cutoff(rtxn)? {
+        Some(cutoff) => Setting::Set(cutoff),
+        None => Setting::NotSet,
+    };
+
+    // The search templates are only displayed when there are some.
+    let search_templates = index
+        .search_templates(rtxn)?
+        .into_iter()
+        .map(|(name, template)| Ok((name, serde_json::from_value(template)?)))
+        .collect::<Result<BTreeMap<_, _>, serde_json::Error>>()
+        .map_err(milli::InternalError::from)?;
+    let search_templates =
+        if search_templates.is_empty() { Setting::NotSet } else { Setting::Set(search_templates) };
+
+    // The localized attributes are only displayed when there are some rules.
+    let localized_attributes = match index.localized_attributes_rules(rtxn)? {
+        Some(rules) => Setting::Set(rules.into_iter().map(Into::into).collect()),
+        None => Setting::NotSet,
+    };
+
     let embedders: BTreeMap<_, _> = index
         .embedding_configs(rtxn)?
         .into_iter()
@@ -620,6 +1094,8 @@ pub fn settings(
         sortable_attributes: Setting::Set(sortable_attributes),
         ranking_rules: Setting::Set(criteria.iter().map(|c| c.clone().into()).collect()),
         stop_words: Setting::Set(stop_words),
+        stemming,
+        decompounding,
         non_separator_tokens: Setting::Set(non_separator_tokens),
         separator_tokens: Setting::Set(separator_tokens),
         dictionary: Setting::Set(dictionary),
@@ -628,10 +1104,18 @@ pub fn settings(
             None => Setting::Reset,
         },
         proximity_precision: Setting::Set(proximity_precision.unwrap_or_default()),
+        prefix_search,
         synonyms: Setting::Set(synonyms),
         typo_tolerance: Setting::Set(typo_tolerance),
         faceting: Setting::Set(faceting),
         pagination: Setting::Set(pagination),
+        query_plan_hints: Setting::Set(query_plan_hints),
+        search_exclusions,
+        enrichment_webhook,
+        unit_conversions,
+        search_cutoff_ms,
+        search_templates,
+        localized_attributes,
         embedders,
         _kind: PhantomData,
     })
@@ -659,6 +1143,11 @@ pub enum RankingRuleView {
     Asc(String),
     /// Sorted by the decreasing value of the field specified.
     Desc(String),
+    /// Sorted by the age of the timestamp of the field specified, halving every `half_life` seconds.
+    Recency { field: String, half_life: u64 },
+    /// Sorted by the ranking rule plugin registered under the name specified.
+    #[cfg(feature = "plugins")]
+    Plugin(String),
 }
 impl Serialize for RankingRuleView {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -715,6 +1204,11 @@ impl From<Criterion> for RankingRuleView {
             Criterion::Exactness => RankingRuleView::Exactness,
             Criterion::Asc(x) => RankingRuleView::Asc(x),
             Criterion::Desc(x) => RankingRuleView::Desc(x),
+            Criterion::Recency { field, half_life } => {
+                RankingRuleView::Recency { field, half_life }
+            }
+            #[cfg(feature = "plugins")]
+            Criterion::Plugin(x) => RankingRuleView::Plugin(x),
         }
     }
 }
@@ -729,6 +1223,11 @@ impl From<RankingRuleView> for Criterion {
             RankingRuleView::Exactness => Criterion::Exactness,
             RankingRuleView::Asc(x) => Criterion::Asc(x),
             RankingRuleView::Desc(x) => Criterion::Desc(x),
+            RankingRuleView::Recency { field, half_life } => {
+                Criterion::Recency { field, half_life }
+            }
+            #[cfg(feature = "plugins")]
+            RankingRuleView::Plugin(x) => Criterion::Plugin(x),
         }
     }
 }
@@ -759,6 +1258,84 @@ impl From<ProximityPrecisionView> for ProximityPrecision {
     }
 }
 
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Deserr, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(error = DeserrJsonError<InvalidSettingsPrefixSearch>, rename_all = camelCase, deny_unknown_fields)]
+pub enum PrefixSearchView {
+    #[default]
+    IndexingTime,
+    Disabled,
+}
+
+impl From<PrefixSearch> for PrefixSearchView {
+    fn from(value: PrefixSearch) -> Self {
+        match value {
+            PrefixSearch::IndexingTime => PrefixSearchView::IndexingTime,
+            PrefixSearch::Disabled => PrefixSearchView::Disabled,
+        }
+    }
+}
+impl From<PrefixSearchView> for PrefixSearch {
+    fn from(value: PrefixSearchView) -> Self {
+        match value {
+            PrefixSearchView::IndexingTime => PrefixSearch::IndexingTime,
+            PrefixSearchView::Disabled => PrefixSearch::Disabled,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Deserr, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub enum FilterResolutionView {
+    #[default]
+    BeforeWords,
+    AfterWords,
+}
+
+impl From<FilterResolution> for FilterResolutionView {
+    fn from(value: FilterResolution) -> Self {
+        match value {
+            FilterResolution::BeforeWords => FilterResolutionView::BeforeWords,
+            FilterResolution::AfterWords => FilterResolutionView::AfterWords,
+        }
+    }
+}
+impl From<FilterResolutionView> for FilterResolution {
+    fn from(value: FilterResolutionView) -> Self {
+        match value {
+            FilterResolutionView::BeforeWords => FilterResolution::BeforeWords,
+            FilterResolutionView::AfterWords => FilterResolution::AfterWords,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Deserr, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub enum ExclusionModeView {
+    #[default]
+    Exclude,
+    Deboost,
+}
+
+impl From<ExclusionMode> for ExclusionModeView {
+    fn from(value: ExclusionMode) -> Self {
+        match value {
+            ExclusionMode::Exclude => ExclusionModeView::Exclude,
+            ExclusionMode::Deboost => ExclusionModeView::Deboost,
+        }
+    }
+}
+impl From<ExclusionModeView> for ExclusionMode {
+    fn from(value: ExclusionModeView) -> Self {
+        match value {
+            ExclusionModeView::Exclude => ExclusionMode::Exclude,
+            ExclusionModeView::Deboost => ExclusionMode::Deboost,
+        }
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod test {
     use super::*;
@@ -773,15 +1350,25 @@ pub(crate) mod test {
             sortable_attributes: Setting::NotSet,
             ranking_rules: Setting::NotSet,
             stop_words: Setting::NotSet,
+            stemming: Setting::NotSet,
+            decompounding: Setting::NotSet,
             non_separator_tokens: Setting::NotSet,
             separator_tokens: Setting::NotSet,
             dictionary: Setting::NotSet,
             synonyms: Setting::NotSet,
             distinct_attribute: Setting::NotSet,
             proximity_precision: Setting::NotSet,
+            prefix_search: Setting::NotSet,
             typo_tolerance: Setting::NotSet,
             faceting: Setting::NotSet,
             pagination: Setting::NotSet,
+            query_plan_hints: Setting::NotSet,
+            search_exclusions: Setting::NotSet,
+            enrichment_webhook: Setting::NotSet,
+            unit_conversions: Setting::NotSet,
+            search_cutoff_ms: Setting::NotSet,
+            search_templates: Setting::NotSet,
+            localized_attributes: Setting::NotSet,
             embedders: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
@@ -799,15 +1386,25 @@ pub(crate) mod test {
             sortable_attributes: Setting::NotSet,
             ranking_rules: Setting::NotSet,
             stop_words: Setting::NotSet,
+            stemming: Setting::NotSet,
+            decompounding: Setting::NotSet,
             non_separator_tokens: Setting::NotSet,
             separator_tokens: Setting::NotSet,
             dictionary: Setting::NotSet,
             synonyms: Setting::NotSet,
             distinct_attribute: Setting::NotSet,
             proximity_precision: Setting::NotSet,
+            prefix_search: Setting::NotSet,
             typo_tolerance: Setting::NotSet,
             faceting: Setting::NotSet,
             pagination: Setting::NotSet,
+            query_plan_hints: Setting::NotSet,
+            search_exclusions: Setting::NotSet,
+            enrichment_webhook: Setting::NotSet,
+            unit_conversions: Setting::NotSet,
+            search_cutoff_ms: Setting::NotSet,
+            search_templates: Setting::NotSet,
+            localized_attributes: Setting::NotSet,
             embedders: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
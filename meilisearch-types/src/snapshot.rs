@@ -0,0 +1,212 @@
+use std::collections::BTreeMap;
+use std::fs::{create_dir_all, File};
+use std::io::Write;
+use std::path::{Component, Path};
+
+use anyhow::{bail, Context};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use tar::{Archive, Builder, Header};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// The name of the manifest, always the first entry of a snapshot.
+pub const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// Describes where the data of every index of a snapshot is stored.
+///
+/// An incremental snapshot only contains the indexes updated since the snapshot it's based on,
+/// the other indexes are restored from the snapshots they are referenced in. An updated index is
+/// always copied entirely, the snapshots never contain a part of an index.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotManifest {
+    /// The file name of the snapshot.
+    pub name: String,
+    /// The date at which the indexes started to be copied.
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    /// The snapshot this one is based on, `None` for a full snapshot.
+    pub base: Option<String>,
+    /// The file name of the snapshot containing the data of each index, by uuid.
+    pub indexes: BTreeMap<Uuid, String>,
+}
+
+/// Tarballs the content of `src` in `dest` with the manifest as the first entry.
+pub fn to_tar_gz(
+    manifest: &SnapshotManifest,
+    src: impl AsRef<Path>,
+    dest: impl AsRef<Path>,
+) -> anyhow::Result<()> {
+    let mut f = File::create(dest)?;
+    let gz_encoder = GzEncoder::new(&mut f, Compression::default());
+    let mut tar_encoder = Builder::new(gz_encoder);
+
+    let manifest = serde_json::to_vec_pretty(manifest)?;
+    let mut header = Header::new_gnu();
+    header.set_size(manifest.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(OffsetDateTime::now_utc().unix_timestamp().try_into().unwrap_or_default());
+    header.set_cksum();
+    tar_encoder.append_data(&mut header, MANIFEST_FILE_NAME, manifest.as_slice())?;
+    tar_encoder.append_dir_all(".", src)?;
+
+    let gz_encoder = tar_encoder.into_inner()?;
+    gz_encoder.finish()?;
+    f.flush()?;
+    Ok(())
+}
+
+/// Reads the manifest of a snapshot, `None` for the snapshots created before the manifests.
+///
+/// Only the beginning of the snapshot is decompressed.
+pub fn read_manifest(snapshot: impl AsRef<Path>) -> anyhow::Result<Option<SnapshotManifest>> {
+    let mut archive = Archive::new(GzDecoder::new(File::open(snapshot)?));
+    let mut entries = archive.entries()?;
+    match entries.next() {
+        Some(entry) => {
+            let entry = entry?;
+            if entry_path_is(&entry.path()?, Path::new(MANIFEST_FILE_NAME)) {
+                Ok(Some(serde_json::from_reader(entry)?))
+            } else {
+                Ok(None)
+            }
+        }
+        None => Ok(None),
+    }
+}
+
+/// Unpacks the snapshot in `dest` along with the indexes it references in the other snapshots
+/// of its directory.
+pub fn restore(snapshot: impl AsRef<Path>, dest: impl AsRef<Path>) -> anyhow::Result<()> {
+    let snapshot = snapshot.as_ref();
+    let dest = dest.as_ref();
+    crate::compression::from_tar_gz(snapshot, dest)?;
+
+    let manifest_path = dest.join(MANIFEST_FILE_NAME);
+    if !manifest_path.exists() {
+        return Ok(());
+    }
+    let manifest: SnapshotManifest = serde_json::from_reader(File::open(&manifest_path)?)?;
+    std::fs::remove_file(manifest_path)?;
+
+    // Group the indexes by the snapshot they're stored in to decompress each of them once.
+    let mut sources: BTreeMap<&str, Vec<Uuid>> = BTreeMap::new();
+    for (uuid, name) in &manifest.indexes {
+        if *name != manifest.name {
+            sources.entry(name).or_default().push(*uuid);
+        }
+    }
+
+    for (name, uuids) in sources {
+        let source = snapshot.with_file_name(name);
+        if !source.exists() {
+            bail!(
+                "snapshot `{name}`, containing {} indexes of the incremental snapshot `{}`, doesn't exist at {}",
+                uuids.len(),
+                manifest.name,
+                source.display()
+            );
+        }
+        let prefixes: Vec<_> =
+            uuids.iter().map(|uuid| Path::new("indexes").join(uuid.to_string())).collect();
+
+        let mut archive = Archive::new(GzDecoder::new(File::open(&source)?));
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let path: std::path::PathBuf = entry
+                .path()?
+                .components()
+                .filter(|component| !matches!(component, Component::CurDir))
+                .collect();
+            if prefixes.iter().any(|prefix| path.starts_with(prefix)) {
+                let dst = dest.join(&path);
+                if let Some(parent) = dst.parent() {
+                    create_dir_all(parent)?;
+                }
+                entry
+                    .unpack(&dst)
+                    .with_context(|| format!("while restoring {}", path.display()))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn entry_path_is(path: &Path, expected: &Path) -> bool {
+    path.components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .eq(expected.components())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn manifest(
+        name: &str,
+        created_at: OffsetDateTime,
+        indexes: &[(Uuid, &str)],
+    ) -> SnapshotManifest {
+        SnapshotManifest {
+            name: name.to_string(),
+            created_at,
+            base: None,
+            indexes: indexes.iter().map(|(uuid, name)| (*uuid, name.to_string())).collect(),
+        }
+    }
+
+    #[test]
+    fn restore_an_incremental_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let src = tempfile::tempdir().unwrap();
+        for uuid in [first, second] {
+            let path = src.path().join("indexes").join(uuid.to_string());
+            create_dir_all(&path).unwrap();
+            std::fs::write(path.join("data.mdb"), format!("full {uuid}")).unwrap();
+        }
+        let full = manifest(
+            "db-1.snapshot",
+            OffsetDateTime::now_utc(),
+            &[(first, "db-1.snapshot"), (second, "db-1.snapshot")],
+        );
+        to_tar_gz(&full, src.path(), dir.path().join("db-1.snapshot")).unwrap();
+
+        // only the second index changed since the full snapshot
+        let src = tempfile::tempdir().unwrap();
+        let path = src.path().join("indexes").join(second.to_string());
+        create_dir_all(&path).unwrap();
+        std::fs::write(path.join("data.mdb"), "incremental").unwrap();
+        let mut incremental = manifest(
+            "db-2.snapshot",
+            OffsetDateTime::now_utc(),
+            &[(first, "db-1.snapshot"), (second, "db-2.snapshot")],
+        );
+        incremental.base = Some(full.name.clone());
+        to_tar_gz(&incremental, src.path(), dir.path().join("db-2.snapshot")).unwrap();
+
+        assert_eq!(read_manifest(dir.path().join("db-2.snapshot")).unwrap(), Some(incremental));
+
+        let db = tempfile::tempdir().unwrap();
+        restore(dir.path().join("db-2.snapshot"), db.path()).unwrap();
+        let read = |uuid: Uuid| {
+            std::fs::read_to_string(
+                db.path().join("indexes").join(uuid.to_string()).join("data.mdb"),
+            )
+            .unwrap()
+        };
+        assert_eq!(read(first), format!("full {first}"));
+        assert_eq!(read(second), "incremental");
+        assert!(!db.path().join(MANIFEST_FILE_NAME).exists());
+
+        // the incremental snapshot can't be restored without the full one
+        std::fs::remove_file(dir.path().join("db-1.snapshot")).unwrap();
+        let db = tempfile::tempdir().unwrap();
+        assert!(restore(dir.path().join("db-2.snapshot"), db.path()).is_err());
+    }
+}
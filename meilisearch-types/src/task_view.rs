@@ -1,9 +1,13 @@
+use milli::documents::composite_primary_key_fields;
 use serde::Serialize;
 use time::{Duration, OffsetDateTime};
 
 use crate::error::ResponseError;
 use crate::settings::{Settings, Unchecked};
-use crate::tasks::{serialize_duration, Details, IndexSwap, Kind, Status, Task, TaskId};
+use crate::tasks::{
+    serialize_duration, Details, IndexSwap, Kind, MergeConflictPolicy, Status, Task, TaskId,
+    TaskWarning,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -16,6 +20,8 @@ pub struct TaskView {
     pub kind: Kind,
     pub canceled_by: Option<TaskId>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub retried_from: Option<TaskId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub details: Option<DetailsView>,
     pub error: Option<ResponseError>,
     #[serde(serialize_with = "serialize_duration", default)]
@@ -36,7 +42,8 @@ impl TaskView {
             status: task.status,
             kind: task.kind.as_kind(),
             canceled_by: task.canceled_by,
-            details: task.details.clone().map(DetailsView::from),
+            retried_from: task.retried_from,
+            details: DetailsView::from_task(task),
             error: task.error.clone(),
             duration: task.started_at.zip(task.finished_at).map(|(start, end)| end - start),
             enqueued_at: task.enqueued_at,
@@ -46,6 +53,23 @@ impl TaskView {
     }
 }
 
+/// A primary key as shown to the users, the list of its fields if it's composite.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+#[serde(untagged)]
+pub enum PrimaryKeyView {
+    Field(String),
+    Composite(Vec<String>),
+}
+
+impl PrimaryKeyView {
+    pub fn new(primary_key: &str) -> Self {
+        match composite_primary_key_fields(primary_key) {
+            Some(fields) => PrimaryKeyView::Composite(fields),
+            None => PrimaryKeyView::Field(primary_key.to_string()),
+        }
+    }
+}
+
 #[derive(Default, Debug, PartialEq, Eq, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DetailsView {
@@ -54,7 +78,7 @@ pub struct DetailsView {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub indexed_documents: Option<Option<u64>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub primary_key: Option<Option<String>>,
+    pub primary_key: Option<Option<PrimaryKeyView>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub provided_ids: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -74,6 +98,32 @@ pub struct DetailsView {
     pub settings: Option<Box<Settings<Unchecked>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub swaps: Option<Vec<IndexSwap>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_index_uid: Option<String>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "time::serde::rfc3339::option::serialize"
+    )]
+    pub expires_at: Option<OffsetDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_conflict: Option<MergeConflictPolicy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merged_documents: Option<Option<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub copied_documents: Option<Option<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warnings: Option<Vec<TaskWarning>>,
+}
+
+impl DetailsView {
+    /// Returns the details of the task along with its warnings, if any.
+    fn from_task(task: &Task) -> Option<DetailsView> {
+        let mut details = task.details.clone().map(DetailsView::from);
+        if !task.warnings.is_empty() {
+            details.get_or_insert_with(DetailsView::default).warnings = Some(task.warnings.clone());
+        }
+        details
+    }
 }
 
 impl From<Details> for DetailsView {
@@ -89,9 +139,10 @@ impl From<Details> for DetailsView {
             Details::SettingsUpdate { settings } => {
                 DetailsView { settings: Some(settings), ..DetailsView::default() }
             }
-            Details::IndexInfo { primary_key } => {
-                DetailsView { primary_key: Some(primary_key), ..DetailsView::default() }
-            }
+            Details::IndexInfo { primary_key } => DetailsView {
+                primary_key: Some(primary_key.as_deref().map(PrimaryKeyView::new)),
+                ..DetailsView::default()
+            },
             Details::DocumentDeletion {
                 provided_ids: received_document_ids,
                 deleted_documents,
@@ -134,6 +185,25 @@ impl From<Details> for DetailsView {
             Details::IndexSwap { swaps } => {
                 DetailsView { swaps: Some(swaps), ..Default::default() }
             }
+            Details::IndexPreview { source_index_uid, expires_at } => DetailsView {
+                source_index_uid: Some(source_index_uid),
+                expires_at: Some(expires_at),
+                ..DetailsView::default()
+            },
+            Details::IndexMerge { source_index_uid, on_conflict, merged_documents } => DetailsView {
+                source_index_uid: Some(source_index_uid),
+                on_conflict: Some(on_conflict),
+                merged_documents: Some(merged_documents),
+                ..DetailsView::default()
+            },
+            Details::IndexSplit { source_index_uid, original_filter, copied_documents } => {
+                DetailsView {
+                    source_index_uid: Some(source_index_uid),
+                    original_filter: Some(Some(original_filter)),
+                    copied_documents: Some(copied_documents),
+                    ..DetailsView::default()
+                }
+            }
         }
     }
 }
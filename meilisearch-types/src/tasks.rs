@@ -3,6 +3,7 @@ use std::collections::HashSet;
 use std::fmt::{Display, Write};
 use std::str::FromStr;
 
+use deserr::Deserr;
 use enum_iterator::Sequence;
 use milli::update::IndexDocumentsMethod;
 use roaring::RoaringBitmap;
@@ -10,6 +11,7 @@ use serde::{Deserialize, Serialize, Serializer};
 use time::{Duration, OffsetDateTime};
 use uuid::Uuid;
 
+use crate::compression::DumpCompression;
 use crate::error::ResponseError;
 use crate::keys::Key;
 use crate::settings::{Settings, Unchecked};
@@ -31,7 +33,13 @@ pub struct Task {
 
     pub error: Option<ResponseError>,
     pub canceled_by: Option<TaskId>,
+    /// The failed or canceled task this one retries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retried_from: Option<TaskId>,
     pub details: Option<Details>,
+    /// The warnings about the state of the index once the task succeeded.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<TaskWarning>,
 
     pub status: Status,
     pub kind: KindWithContent,
@@ -43,6 +51,7 @@ impl Task {
 
         match &self.kind {
             DumpCreation { .. }
+            | DumpImport { .. }
             | SnapshotCreation
             | TaskCancelation { .. }
             | TaskDeletion { .. }
@@ -54,7 +63,11 @@ impl Task {
             | SettingsUpdate { index_uid, .. }
             | IndexCreation { index_uid, .. }
             | IndexUpdate { index_uid, .. }
-            | IndexDeletion { index_uid } => Some(index_uid),
+            | IndexDeletion { index_uid }
+            | IndexExport { index_uid }
+            | IndexPreviewCreation { index_uid, .. }
+            | IndexMerge { index_uid, .. }
+            | IndexSplit { index_uid, .. } => Some(index_uid),
         }
     }
 
@@ -78,6 +91,11 @@ impl Task {
             | KindWithContent::TaskCancelation { .. }
             | KindWithContent::TaskDeletion { .. }
             | KindWithContent::DumpCreation { .. }
+            | KindWithContent::DumpImport { .. }
+            | KindWithContent::IndexExport { .. }
+            | KindWithContent::IndexPreviewCreation { .. }
+            | KindWithContent::IndexMerge { .. }
+            | KindWithContent::IndexSplit { .. }
             | KindWithContent::SnapshotCreation => None,
         }
     }
@@ -136,8 +154,44 @@ pub enum KindWithContent {
     DumpCreation {
         keys: Vec<Key>,
         instance_uid: Option<InstanceUid>,
+        #[serde(default)]
+        compression: DumpCompression,
     },
     SnapshotCreation,
+    /// Imports the indexes of a dump of the dumps directory into the running instance.
+    DumpImport {
+        dump_uid: String,
+        /// When set, the dump must contain a single index that is imported under this uid.
+        #[serde(default)]
+        index_uid: Option<String>,
+    },
+    /// Exports the documents and settings of an index into a dump of the dumps directory.
+    IndexExport {
+        index_uid: String,
+    },
+    /// Creates the `index_uid` preview, a copy of another index with different settings
+    /// that is deleted once it expires.
+    IndexPreviewCreation {
+        index_uid: String,
+        source_index_uid: String,
+        settings: Box<Settings<Unchecked>>,
+        #[serde(with = "time::serde::rfc3339")]
+        expires_at: OffsetDateTime,
+    },
+    /// Adds the documents of another index into the `index_uid` index, the source index
+    /// itself is left untouched.
+    IndexMerge {
+        index_uid: String,
+        source_index_uid: String,
+        on_conflict: MergeConflictPolicy,
+    },
+    /// Creates the `index_uid` index from the documents of another index matching a filter,
+    /// along with its settings. The source index itself is left untouched.
+    IndexSplit {
+        index_uid: String,
+        source_index_uid: String,
+        filter_expr: serde_json::Value,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -146,6 +200,31 @@ pub struct IndexSwap {
     pub indexes: (String, String),
 }
 
+/// What to do with a document of the source index of a merge whose primary key is already
+/// used by a document of the target index.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Deserr)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum MergeConflictPolicy {
+    /// The document of the source index replaces the one of the target index.
+    #[default]
+    Replace,
+    /// The fields of the document of the source index are added to the one of the target index.
+    Update,
+    /// The document of the target index is kept as is.
+    Skip,
+}
+
+impl Display for MergeConflictPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MergeConflictPolicy::Replace => write!(f, "replace"),
+            MergeConflictPolicy::Update => write!(f, "update"),
+            MergeConflictPolicy::Skip => write!(f, "skip"),
+        }
+    }
+}
+
 impl KindWithContent {
     pub fn as_kind(&self) -> Kind {
         match self {
@@ -162,6 +241,11 @@ impl KindWithContent {
             KindWithContent::TaskDeletion { .. } => Kind::TaskDeletion,
             KindWithContent::DumpCreation { .. } => Kind::DumpCreation,
             KindWithContent::SnapshotCreation => Kind::SnapshotCreation,
+            KindWithContent::DumpImport { .. } => Kind::DumpImport,
+            KindWithContent::IndexExport { .. } => Kind::IndexExport,
+            KindWithContent::IndexPreviewCreation { .. } => Kind::IndexPreviewCreation,
+            KindWithContent::IndexMerge { .. } => Kind::IndexMerge,
+            KindWithContent::IndexSplit { .. } => Kind::IndexSplit,
         }
     }
 
@@ -170,6 +254,7 @@ impl KindWithContent {
 
         match self {
             DumpCreation { .. }
+            | DumpImport { .. }
             | SnapshotCreation
             | TaskCancelation { .. }
             | TaskDeletion { .. } => vec![],
@@ -180,7 +265,11 @@ impl KindWithContent {
             | SettingsUpdate { index_uid, .. }
             | IndexCreation { index_uid, .. }
             | IndexUpdate { index_uid, .. }
-            | IndexDeletion { index_uid } => vec![index_uid],
+            | IndexDeletion { index_uid }
+            | IndexExport { index_uid }
+            | IndexPreviewCreation { index_uid, .. }
+            | IndexMerge { index_uid, .. }
+            | IndexSplit { index_uid, .. } => vec![index_uid],
             IndexSwap { swaps } => {
                 let mut indexes = HashSet::<&str>::default();
                 for swap in swaps {
@@ -239,6 +328,28 @@ impl KindWithContent {
             }),
             KindWithContent::DumpCreation { .. } => Some(Details::Dump { dump_uid: None }),
             KindWithContent::SnapshotCreation => None,
+            KindWithContent::DumpImport { dump_uid, .. } => {
+                Some(Details::Dump { dump_uid: Some(dump_uid.clone()) })
+            }
+            KindWithContent::IndexExport { .. } => Some(Details::Dump { dump_uid: None }),
+            KindWithContent::IndexPreviewCreation { source_index_uid, expires_at, .. } => Some(Details::IndexPreview {
+                source_index_uid: source_index_uid.clone(),
+                expires_at: *expires_at,
+            }),
+            KindWithContent::IndexMerge { source_index_uid, on_conflict, .. } => {
+                Some(Details::IndexMerge {
+                    source_index_uid: source_index_uid.clone(),
+                    on_conflict: *on_conflict,
+                    merged_documents: None,
+                })
+            }
+            KindWithContent::IndexSplit { source_index_uid, filter_expr, .. } => {
+                Some(Details::IndexSplit {
+                    source_index_uid: source_index_uid.clone(),
+                    original_filter: filter_expr.to_string(),
+                    copied_documents: None,
+                })
+            }
         }
     }
 
@@ -288,6 +399,28 @@ impl KindWithContent {
             }),
             KindWithContent::DumpCreation { .. } => Some(Details::Dump { dump_uid: None }),
             KindWithContent::SnapshotCreation => None,
+            KindWithContent::DumpImport { dump_uid, .. } => {
+                Some(Details::Dump { dump_uid: Some(dump_uid.clone()) })
+            }
+            KindWithContent::IndexExport { .. } => Some(Details::Dump { dump_uid: None }),
+            KindWithContent::IndexPreviewCreation { source_index_uid, expires_at, .. } => Some(Details::IndexPreview {
+                source_index_uid: source_index_uid.clone(),
+                expires_at: *expires_at,
+            }),
+            KindWithContent::IndexMerge { source_index_uid, on_conflict, .. } => {
+                Some(Details::IndexMerge {
+                    source_index_uid: source_index_uid.clone(),
+                    on_conflict: *on_conflict,
+                    merged_documents: Some(0),
+                })
+            }
+            KindWithContent::IndexSplit { source_index_uid, filter_expr, .. } => {
+                Some(Details::IndexSplit {
+                    source_index_uid: source_index_uid.clone(),
+                    original_filter: filter_expr.to_string(),
+                    copied_documents: Some(0),
+                })
+            }
         }
     }
 }
@@ -327,6 +460,28 @@ impl From<&KindWithContent> for Option<Details> {
             }),
             KindWithContent::DumpCreation { .. } => Some(Details::Dump { dump_uid: None }),
             KindWithContent::SnapshotCreation => None,
+            KindWithContent::DumpImport { dump_uid, .. } => {
+                Some(Details::Dump { dump_uid: Some(dump_uid.clone()) })
+            }
+            KindWithContent::IndexExport { .. } => Some(Details::Dump { dump_uid: None }),
+            KindWithContent::IndexPreviewCreation { source_index_uid, expires_at, .. } => Some(Details::IndexPreview {
+                source_index_uid: source_index_uid.clone(),
+                expires_at: *expires_at,
+            }),
+            KindWithContent::IndexMerge { source_index_uid, on_conflict, .. } => {
+                Some(Details::IndexMerge {
+                    source_index_uid: source_index_uid.clone(),
+                    on_conflict: *on_conflict,
+                    merged_documents: None,
+                })
+            }
+            KindWithContent::IndexSplit { source_index_uid, filter_expr, .. } => {
+                Some(Details::IndexSplit {
+                    source_index_uid: source_index_uid.clone(),
+                    original_filter: filter_expr.to_string(),
+                    copied_documents: None,
+                })
+            }
         }
     }
 }
@@ -404,6 +559,11 @@ pub enum Kind {
     TaskDeletion,
     DumpCreation,
     SnapshotCreation,
+    DumpImport,
+    IndexExport,
+    IndexPreviewCreation,
+    IndexMerge,
+    IndexSplit,
 }
 
 impl Kind {
@@ -414,12 +574,17 @@ impl Kind {
             | Kind::SettingsUpdate
             | Kind::IndexCreation
             | Kind::IndexDeletion
-            | Kind::IndexUpdate => true,
+            | Kind::IndexUpdate
+            | Kind::IndexExport
+            | Kind::IndexPreviewCreation
+            | Kind::IndexMerge
+            | Kind::IndexSplit => true,
             Kind::IndexSwap
             | Kind::TaskCancelation
             | Kind::TaskDeletion
             | Kind::DumpCreation
-            | Kind::SnapshotCreation => false,
+            | Kind::SnapshotCreation
+            | Kind::DumpImport => false,
         }
     }
 }
@@ -437,6 +602,11 @@ impl Display for Kind {
             Kind::TaskDeletion => write!(f, "taskDeletion"),
             Kind::DumpCreation => write!(f, "dumpCreation"),
             Kind::SnapshotCreation => write!(f, "snapshotCreation"),
+            Kind::DumpImport => write!(f, "dumpImport"),
+            Kind::IndexExport => write!(f, "indexExport"),
+            Kind::IndexPreviewCreation => write!(f, "indexPreviewCreation"),
+            Kind::IndexMerge => write!(f, "indexMerge"),
+            Kind::IndexSplit => write!(f, "indexSplit"),
         }
     }
 }
@@ -466,6 +636,16 @@ impl FromStr for Kind {
             Ok(Kind::DumpCreation)
         } else if kind.eq_ignore_ascii_case("snapshotCreation") {
             Ok(Kind::SnapshotCreation)
+        } else if kind.eq_ignore_ascii_case("dumpImport") {
+            Ok(Kind::DumpImport)
+        } else if kind.eq_ignore_ascii_case("indexExport") {
+            Ok(Kind::IndexExport)
+        } else if kind.eq_ignore_ascii_case("indexPreviewCreation") {
+            Ok(Kind::IndexPreviewCreation)
+        } else if kind.eq_ignore_ascii_case("indexMerge") {
+            Ok(Kind::IndexMerge)
+        } else if kind.eq_ignore_ascii_case("indexSplit") {
+            Ok(Kind::IndexSplit)
         } else {
             Err(ParseTaskKindError(kind.to_owned()))
         }
@@ -505,6 +685,17 @@ pub enum Details {
     TaskDeletion { matched_tasks: u64, deleted_tasks: Option<u64>, original_filter: String },
     Dump { dump_uid: Option<String> },
     IndexSwap { swaps: Vec<IndexSwap> },
+    IndexPreview { source_index_uid: String, expires_at: OffsetDateTime },
+    IndexMerge {
+        source_index_uid: String,
+        on_conflict: MergeConflictPolicy,
+        merged_documents: Option<u64>,
+    },
+    IndexSplit {
+        source_index_uid: String,
+        original_filter: String,
+        copied_documents: Option<u64>,
+    },
 }
 
 impl Details {
@@ -521,16 +712,40 @@ impl Details {
             Self::ClearAll { deleted_documents } => *deleted_documents = Some(0),
             Self::TaskCancelation { canceled_tasks, .. } => *canceled_tasks = Some(0),
             Self::TaskDeletion { deleted_tasks, .. } => *deleted_tasks = Some(0),
+            Self::IndexMerge { merged_documents, .. } => *merged_documents = Some(0),
+            Self::IndexSplit { copied_documents, .. } => *copied_documents = Some(0),
             Self::SettingsUpdate { .. }
             | Self::IndexInfo { .. }
             | Self::Dump { .. }
-            | Self::IndexSwap { .. } => (),
+            | Self::IndexSwap { .. }
+            | Self::IndexPreview { .. } => (),
         }
 
         details
     }
 }
 
+/// A warning attached to a succeeded task when its index is getting close to a limit,
+/// or when the snapshot it created could not be uploaded.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum TaskWarning {
+    /// The index exceeds one of the configured alert thresholds.
+    #[serde(rename_all = "camelCase")]
+    IndexAlertThresholdExceeded {
+        number_of_documents: u64,
+        database_size: u64,
+        max_number_of_documents: Option<u64>,
+        max_database_size: Option<u64>,
+    },
+    /// The index' DB almost fills its memory map.
+    #[serde(rename_all = "camelCase")]
+    IndexMapSizeAlmostReached { database_size: u64, map_size: u64 },
+    /// The snapshot was created but its upload to the object storage failed.
+    #[serde(rename_all = "camelCase")]
+    SnapshotUploadFailed { snapshot: String, error: String },
+}
+
 /// Serialize a `time::Duration` as a best effort ISO 8601 while waiting for
 /// https://github.com/time-rs/time/issues/378.
 /// This code is a port of the old code of time that was removed in 0.2.
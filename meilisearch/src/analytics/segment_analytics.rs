@@ -1,6 +1,7 @@
 use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::fs;
 use std::mem::take;
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -257,6 +258,15 @@ struct Infos {
     experimental_enable_logs_route: bool,
     experimental_reduce_indexing_memory_usage: bool,
     experimental_max_number_of_batched_tasks: usize,
+    experimental_max_batched_payload_size: Option<Byte>,
+    experimental_disable_autobatching: bool,
+    experimental_max_concurrent_batches: usize,
+    experimental_max_filter_depth: usize,
+    experimental_max_filter_complexity: usize,
+    experimental_proxy: bool,
+    experimental_trusted_proxies: bool,
+    experimental_otlp: bool,
+    experimental_wasm_middleware: bool,
     gpu_enabled: bool,
     db_path: bool,
     import_dump: bool,
@@ -266,12 +276,35 @@ struct Infos {
     import_snapshot: bool,
     schedule_snapshot: Option<u64>,
     snapshot_dir: bool,
+    snapshot_retention: Option<usize>,
+    snapshot_incremental: bool,
+    snapshot_s3: bool,
     ignore_missing_snapshot: bool,
     ignore_snapshot_if_db_exists: bool,
     http_addr: bool,
+    http_unix_socket: bool,
+    http_unix_socket_only: bool,
     http_payload_size_limit: Byte,
+    http_compression_min_size: Byte,
     task_queue_webhook: bool,
     task_webhook_authorization_header: bool,
+    task_retention_max_count: Option<u64>,
+    task_retention_max_age_sec: Option<u64>,
+    task_retry_payload_retention_sec: u64,
+    index_alert_size_threshold: Option<Byte>,
+    index_alert_documents_threshold: Option<u64>,
+    index_alert_webhook: bool,
+    audit_log: bool,
+    audit_log_max_file_size: Byte,
+    query_log: bool,
+    query_log_sample_rate: f64,
+    query_log_max_file_size: Byte,
+    query_log_diagnostics_sample_rate: f64,
+    shutdown_timeout_sec: u64,
+    max_concurrent_searches: usize,
+    search_queue_size: usize,
+    search_queue_timeout_ms: u64,
+    search_cutoff_ms: Option<u64>,
     log_level: String,
     max_indexing_memory: MaxMemory,
     max_indexing_threads: MaxThreads,
@@ -283,6 +316,7 @@ struct Infos {
     ssl_require_auth: bool,
     ssl_resumption: bool,
     ssl_tickets: bool,
+    ssl_client_identities_path: bool,
 }
 
 impl From<Opt> for Infos {
@@ -298,14 +332,46 @@ impl From<Opt> for Infos {
             experimental_enable_logs_route,
             experimental_reduce_indexing_memory_usage,
             experimental_max_number_of_batched_tasks,
+            experimental_max_batched_payload_size,
+            experimental_disable_autobatching,
+            experimental_max_concurrent_batches,
+            experimental_max_filter_depth,
+            experimental_max_filter_complexity,
+            experimental_proxy_backends,
+            experimental_trusted_proxies,
+            experimental_otlp_endpoint,
+            experimental_otlp_filter: _,
+            experimental_wasm_middleware_dir,
+            experimental_wasm_middleware_fuel: _,
+            experimental_wasm_middleware_max_memory: _,
             http_addr,
+            http_unix_socket,
+            http_unix_socket_only,
             master_key: _,
             env,
             task_webhook_url,
             task_webhook_authorization_header,
+            task_retention_max_count,
+            task_retention_max_age_sec,
+            task_retry_payload_retention_sec,
+            index_alert_size_threshold,
+            index_alert_documents_threshold,
+            index_alert_webhook_url,
+            audit_log,
+            audit_log_max_file_size,
+            query_log,
+            query_log_sample_rate,
+            query_log_max_file_size,
+            query_log_diagnostics_sample_rate,
+            shutdown_timeout_sec,
+            max_concurrent_searches,
+            search_queue_size,
+            search_queue_timeout_ms,
+            search_cutoff_ms,
             max_index_size: _,
             max_task_db_size: _,
             http_payload_size_limit,
+            http_compression_min_size,
             ssl_cert_path,
             ssl_key_path,
             ssl_auth_path,
@@ -313,11 +379,20 @@ impl From<Opt> for Infos {
             ssl_require_auth,
             ssl_resumption,
             ssl_tickets,
+            ssl_client_identities_path,
             import_snapshot,
             ignore_missing_snapshot,
             ignore_snapshot_if_db_exists,
             snapshot_dir,
             schedule_snapshot,
+            snapshot_retention,
+            snapshot_incremental,
+            snapshot_s3_endpoint,
+            snapshot_s3_bucket: _,
+            snapshot_s3_region: _,
+            snapshot_s3_prefix: _,
+            snapshot_s3_access_key: _,
+            snapshot_s3_secret_key: _,
             import_dump,
             ignore_missing_dump,
             ignore_dump_if_db_exists,
@@ -355,13 +430,45 @@ impl From<Opt> for Infos {
             import_snapshot: import_snapshot.is_some(),
             schedule_snapshot,
             snapshot_dir: snapshot_dir != PathBuf::from("snapshots/"),
+            snapshot_retention: snapshot_retention.map(NonZeroUsize::get),
+            snapshot_incremental,
+            snapshot_s3: snapshot_s3_endpoint.is_some(),
             ignore_missing_snapshot,
             ignore_snapshot_if_db_exists,
             http_addr: http_addr != default_http_addr(),
+            http_unix_socket: http_unix_socket.is_some(),
+            http_unix_socket_only,
             http_payload_size_limit,
+            http_compression_min_size,
             experimental_max_number_of_batched_tasks,
+            experimental_max_batched_payload_size,
+            experimental_disable_autobatching,
+            experimental_max_concurrent_batches,
+            experimental_max_filter_depth,
+            experimental_max_filter_complexity,
+            experimental_proxy: !experimental_proxy_backends.is_empty(),
+            experimental_trusted_proxies: !experimental_trusted_proxies.is_empty(),
+            experimental_otlp: experimental_otlp_endpoint.is_some(),
+            experimental_wasm_middleware: experimental_wasm_middleware_dir.is_some(),
             task_queue_webhook: task_webhook_url.is_some(),
             task_webhook_authorization_header: task_webhook_authorization_header.is_some(),
+            task_retention_max_count,
+            task_retention_max_age_sec,
+            task_retry_payload_retention_sec,
+            index_alert_size_threshold,
+            index_alert_documents_threshold,
+            index_alert_webhook: index_alert_webhook_url.is_some(),
+            audit_log,
+            audit_log_max_file_size,
+            query_log,
+            query_log_sample_rate,
+            query_log_max_file_size,
+            query_log_diagnostics_sample_rate,
+            shutdown_timeout_sec,
+            max_concurrent_searches,
+            search_queue_size,
+            search_queue_timeout_ms,
+            search_cutoff_ms,
             log_level: log_level.to_string(),
             max_indexing_memory,
             max_indexing_threads,
@@ -373,6 +480,7 @@ impl From<Opt> for Infos {
             ssl_require_auth,
             ssl_resumption,
             ssl_tickets,
+            ssl_client_identities_path: ssl_client_identities_path.is_some(),
         }
     }
 }
@@ -583,6 +691,7 @@ pub struct SearchAggregator {
 
     // sort
     sort_with_geo_point: bool,
+    sort_with_random: bool,
     // every time a request has a filter, this field must be incremented by the number of terms it contains
     sort_sum_of_criteria_terms: usize,
     // every time a request has a filter, this field must be incremented by one
@@ -601,6 +710,22 @@ pub struct SearchAggregator {
     // every time a search is done using attributes_to_search_on
     attributes_to_search_on_total_number_of_uses: usize,
 
+    // boosts
+    // every time a search is done using boosts
+    boosts_total_number_of_uses: usize,
+
+    // group_by
+    // every time a search is done using group_by
+    group_by_total_number_of_uses: usize,
+
+    // aggregates
+    // every time a search is done using aggregates
+    aggregates_total_number_of_uses: usize,
+
+    // nested
+    // every time a search is done using nested
+    nested_total_number_of_uses: usize,
+
     // q
     // The maximum number of terms in a q request
     max_terms_number: usize,
@@ -660,13 +785,22 @@ impl SearchAggregator {
             show_ranking_score_details,
             filter,
             sort,
+            sort_first: _,
+            retrieve_vectors: _,
             facets: _,
+            facet_filters: _,
+            disjunctive_facets: _,
+            aggregates,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
             matching_strategy,
             attributes_to_search_on,
+            locales: _,
             hybrid,
+            boosts,
+            group_by,
+            nested,
         } = query;
 
         let mut ret = Self::default();
@@ -678,6 +812,7 @@ impl SearchAggregator {
         if let Some(ref sort) = sort {
             ret.sort_total_number_of_criteria = 1;
             ret.sort_with_geo_point = sort.iter().any(|s| s.contains("_geoPoint("));
+            ret.sort_with_random = sort.iter().any(|s| s.starts_with("_random("));
             ret.sort_sum_of_criteria_terms = sort.len();
         }
 
@@ -710,6 +845,26 @@ impl SearchAggregator {
             ret.attributes_to_search_on_total_number_of_uses = 1;
         }
 
+        // boosts
+        if !boosts.is_empty() {
+            ret.boosts_total_number_of_uses = 1;
+        }
+
+        // group_by
+        if group_by.is_some() {
+            ret.group_by_total_number_of_uses = 1;
+        }
+
+        // aggregates
+        if aggregates.is_some() {
+            ret.aggregates_total_number_of_uses = 1;
+        }
+
+        // nested
+        if nested.is_some() {
+            ret.nested_total_number_of_uses = 1;
+        }
+
         if let Some(ref q) = q {
             ret.max_terms_number = q.split_whitespace().count();
         }
@@ -758,6 +913,9 @@ impl SearchAggregator {
             hits_info: _,
             facet_distribution: _,
             facet_stats: _,
+            aggregates: _,
+            degraded: _,
+            diagnostics: _,
         } = result;
 
         self.total_succeeded = self.total_succeeded.saturating_add(1);
@@ -773,6 +931,7 @@ impl SearchAggregator {
             total_succeeded,
             ref mut time_spent,
             sort_with_geo_point,
+            sort_with_random,
             sort_sum_of_criteria_terms,
             sort_total_number_of_criteria,
             filter_with_geo_radius,
@@ -781,6 +940,10 @@ impl SearchAggregator {
             filter_total_number_of_criteria,
             used_syntax,
             attributes_to_search_on_total_number_of_uses,
+            boosts_total_number_of_uses,
+            group_by_total_number_of_uses,
+            aggregates_total_number_of_uses,
+            nested_total_number_of_uses,
             max_terms_number,
             max_vector_size,
             matching_strategy,
@@ -820,6 +983,7 @@ impl SearchAggregator {
 
         // sort
         self.sort_with_geo_point |= sort_with_geo_point;
+        self.sort_with_random |= sort_with_random;
         self.sort_sum_of_criteria_terms =
             self.sort_sum_of_criteria_terms.saturating_add(sort_sum_of_criteria_terms);
         self.sort_total_number_of_criteria =
@@ -842,6 +1006,22 @@ impl SearchAggregator {
             .attributes_to_search_on_total_number_of_uses
             .saturating_add(attributes_to_search_on_total_number_of_uses);
 
+        // boosts
+        self.boosts_total_number_of_uses =
+            self.boosts_total_number_of_uses.saturating_add(boosts_total_number_of_uses);
+
+        // group_by
+        self.group_by_total_number_of_uses =
+            self.group_by_total_number_of_uses.saturating_add(group_by_total_number_of_uses);
+
+        // aggregates
+        self.aggregates_total_number_of_uses =
+            self.aggregates_total_number_of_uses.saturating_add(aggregates_total_number_of_uses);
+
+        // nested
+        self.nested_total_number_of_uses =
+            self.nested_total_number_of_uses.saturating_add(nested_total_number_of_uses);
+
         // q
         self.max_terms_number = self.max_terms_number.max(max_terms_number);
 
@@ -892,6 +1072,7 @@ impl SearchAggregator {
             total_succeeded,
             time_spent,
             sort_with_geo_point,
+            sort_with_random,
             sort_sum_of_criteria_terms,
             sort_total_number_of_criteria,
             filter_with_geo_radius,
@@ -900,6 +1081,10 @@ impl SearchAggregator {
             filter_total_number_of_criteria,
             used_syntax,
             attributes_to_search_on_total_number_of_uses,
+            boosts_total_number_of_uses,
+            group_by_total_number_of_uses,
+            aggregates_total_number_of_uses,
+            nested_total_number_of_uses,
             max_terms_number,
             max_vector_size,
             matching_strategy,
@@ -943,6 +1128,7 @@ impl SearchAggregator {
                 },
                 "sort": {
                     "with_geoPoint": sort_with_geo_point,
+                    "with_random": sort_with_random,
                     "avg_criteria_number": format!("{:.2}", sort_sum_of_criteria_terms as f64 / sort_total_number_of_criteria as f64),
                 },
                 "filter": {
@@ -954,6 +1140,18 @@ impl SearchAggregator {
                 "attributes_to_search_on": {
                    "total_number_of_uses": attributes_to_search_on_total_number_of_uses,
                 },
+                "boosts": {
+                   "total_number_of_uses": boosts_total_number_of_uses,
+                },
+                "group_by": {
+                   "total_number_of_uses": group_by_total_number_of_uses,
+                },
+                "aggregates": {
+                   "total_number_of_uses": aggregates_total_number_of_uses,
+                },
+                "nested": {
+                   "total_number_of_uses": nested_total_number_of_uses,
+                },
                 "q": {
                    "max_terms_number": max_terms_number,
                 },
@@ -1055,12 +1253,16 @@ impl MultiSearchAggregator {
                     filter: _,
                     sort: _,
                     facets: _,
+                    aggregates: _,
                     highlight_pre_tag: _,
                     highlight_post_tag: _,
                     crop_marker: _,
                     matching_strategy: _,
                     attributes_to_search_on: _,
                     hybrid: _,
+                    boosts: _,
+                    group_by: _,
+                    nested: _,
                 } = query;
 
                 index_uid.as_str()
@@ -1335,7 +1537,8 @@ impl DocumentsAggregator {
         index_creation: bool,
         request: &HttpRequest,
     ) -> Self {
-        let UpdateDocumentsQuery { primary_key, csv_delimiter: _ } = documents_query;
+        let UpdateDocumentsQuery { primary_key, csv_delimiter: _, field_mapping: _ } =
+            documents_query;
 
         let mut primary_keys = HashSet::new();
         if let Some(primary_key) = primary_key.clone() {
@@ -1667,6 +1870,8 @@ pub struct DocumentsFetchAggregator {
     per_document_id: bool,
     // if a filter was used
     per_filter: bool,
+    // if a sort was used
+    per_sort: bool,
 
     // pagination
     #[serde(rename = "pagination.max_limit")]
@@ -1687,6 +1892,7 @@ impl DocumentsFetchAggregator {
             total_received: 1,
             per_document_id: matches!(query, DocumentFetchKind::PerDocumentId),
             per_filter: matches!(query, DocumentFetchKind::Normal { with_filter, .. } if *with_filter),
+            per_sort: matches!(query, DocumentFetchKind::Normal { with_sort, .. } if *with_sort),
             max_limit: limit,
             max_offset: offset,
         }
@@ -1700,6 +1906,7 @@ impl DocumentsFetchAggregator {
             total_received,
             per_document_id,
             per_filter,
+            per_sort,
             max_limit,
             max_offset,
         } = other;
@@ -1714,6 +1921,7 @@ impl DocumentsFetchAggregator {
         self.total_received = self.total_received.saturating_add(total_received);
         self.per_document_id |= per_document_id;
         self.per_filter |= per_filter;
+        self.per_sort |= per_sort;
 
         self.max_limit = self.max_limit.max(max_limit);
         self.max_offset = self.max_offset.max(max_offset);
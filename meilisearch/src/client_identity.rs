@@ -0,0 +1,66 @@
+/*!
+The API keys granted to the clients authenticated with a certificate.
+
+When the clients are verified with `--ssl-auth-path`, the `--ssl-client-identities-path` file maps
+the common name of their certificate to the uid of an API key. The key is resolved once per TLS
+connection and stored in its data, the requests without an `Authorization` header are then
+authenticated as if they were made with this key: its actions, indexes, expiration, allowed IPs
+and rate limits apply.
+*/
+
+use std::any::Any;
+use std::collections::HashMap;
+
+use actix_tls::accept::rustls_0_21::TlsStream;
+use actix_web::dev::Extensions;
+use actix_web::rt::net::TcpStream;
+use anyhow::Context;
+use uuid::Uuid;
+
+use crate::option::Opt;
+
+/// The uid of the API key granted to the client certificate of a connection.
+#[derive(Debug, Clone, Copy)]
+pub struct ClientKey(pub Uuid);
+
+pub struct ClientIdentities {
+    /// The uid of the API keys by common name.
+    keys: HashMap<String, Uuid>,
+}
+
+impl ClientIdentities {
+    pub fn from_opt(opt: &Opt) -> anyhow::Result<Option<Self>> {
+        let Some(path) = &opt.ssl_client_identities_path else { return Ok(None) };
+        let file = std::fs::read(path).with_context(|| {
+            format!("Could not read the client identities file `{}`", path.display())
+        })?;
+        let keys = serde_json::from_slice(&file).with_context(|| {
+            format!(
+                "The client identities file `{}` must map common names to API key uids",
+                path.display()
+            )
+        })?;
+        Ok(Some(Self { keys }))
+    }
+
+    /// Stores the [`ClientKey`] of the connection if its client certificate is known.
+    ///
+    /// Meant to be given to [`actix_web::HttpServer::on_connect`].
+    pub fn on_connect(&self, connection: &dyn Any, data: &mut Extensions) {
+        let Some(stream) = connection.downcast_ref::<TlsStream<TcpStream>>() else { return };
+        // the first certificate is the one of the client, the others are its chain.
+        let Some(certificate) = stream.get_ref().1.peer_certificates().and_then(|c| c.first())
+        else {
+            return;
+        };
+        if let Some(key_uid) = self.key_uid(&certificate.0) {
+            data.insert(ClientKey(key_uid));
+        }
+    }
+
+    fn key_uid(&self, certificate: &[u8]) -> Option<Uuid> {
+        let (_, certificate) = x509_parser::parse_x509_certificate(certificate).ok()?;
+        let common_name = certificate.subject().iter_common_name().next()?.as_str().ok()?;
+        self.keys.get(common_name).copied()
+    }
+}
@@ -0,0 +1,193 @@
+/*!
+The CSV responses of the routes returning documents or facet distributions.
+
+A route answers in CSV when the `Accept` header of the request contains `text/csv`. The nested
+objects of the documents are flattened into one column per field, named after the path of the
+field joined by the nested separator, and the arrays of values are joined into a single cell by
+the array separator. The arrays containing objects or other arrays are kept as JSON.
+*/
+
+use std::collections::BTreeMap;
+
+use actix_web::http::header::ACCEPT;
+use actix_web::{HttpRequest, HttpResponse};
+use indexmap::{IndexMap, IndexSet};
+use meilisearch_types::Document;
+use serde_json::{Map, Value};
+
+use crate::error::MeilisearchHttpError;
+
+pub const DEFAULT_CSV_DELIMITER: u8 = b',';
+pub const DEFAULT_CSV_NESTED_SEPARATOR: &str = ".";
+pub const DEFAULT_CSV_ARRAY_SEPARATOR: &str = "|";
+
+/// How the documents are written as CSV.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvFormat {
+    /// The character separating the cells of a row.
+    pub delimiter: u8,
+    /// The string joining the keys of the nested objects into a column name.
+    pub nested_separator: String,
+    /// The string joining the values of an array into a single cell.
+    pub array_separator: String,
+}
+
+impl Default for CsvFormat {
+    fn default() -> Self {
+        CsvFormat {
+            delimiter: DEFAULT_CSV_DELIMITER,
+            nested_separator: DEFAULT_CSV_NESTED_SEPARATOR.to_string(),
+            array_separator: DEFAULT_CSV_ARRAY_SEPARATOR.to_string(),
+        }
+    }
+}
+
+/// Returns `true` if one of the media types of the `Accept` header of the request is `text/csv`.
+pub fn accepts_csv(req: &HttpRequest) -> bool {
+    req.headers().get_all(ACCEPT).filter_map(|value| value.to_str().ok()).any(|value| {
+        value.split(',').any(|media_type| {
+            let media_type = media_type.split(';').next().unwrap_or_default().trim();
+            media_type.eq_ignore_ascii_case("text/csv")
+        })
+    })
+}
+
+/// Wraps the CSV into a response.
+pub fn csv_response(csv: Vec<u8>) -> HttpResponse {
+    HttpResponse::Ok().content_type("text/csv; charset=utf-8").body(csv)
+}
+
+/// Writes the documents as CSV, one row per document. The columns are the flattened fields of
+/// all the documents, in the order they first appear.
+pub fn documents_to_csv(
+    documents: &[Document],
+    format: &CsvFormat,
+) -> Result<Vec<u8>, MeilisearchHttpError> {
+    let rows: Vec<_> =
+        documents.iter().map(|document| flatten_document(document, format)).collect();
+    let columns: IndexSet<&str> =
+        rows.iter().flat_map(|row| row.keys()).map(String::as_str).collect();
+
+    let mut writer = csv::WriterBuilder::new().delimiter(format.delimiter).from_writer(Vec::new());
+    if !columns.is_empty() {
+        writer.write_record(&columns)?;
+    }
+    for row in &rows {
+        writer.write_record(
+            columns.iter().map(|column| row.get(*column).map(String::as_str).unwrap_or_default()),
+        )?;
+    }
+    writer.into_inner().map_err(|err| csv::Error::from(err.into_error()).into())
+}
+
+/// Writes a facet distribution as CSV, with one `facet,value,count` row per facet value.
+pub fn facet_distribution_to_csv(
+    distribution: &BTreeMap<String, IndexMap<String, u64>>,
+) -> Result<Vec<u8>, MeilisearchHttpError> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(["facet", "value", "count"])?;
+    for (facet, values) in distribution {
+        for (value, count) in values {
+            writer.write_record([facet.as_str(), value.as_str(), &count.to_string()])?;
+        }
+    }
+    writer.into_inner().map_err(|err| csv::Error::from(err.into_error()).into())
+}
+
+fn flatten_document(document: &Document, format: &CsvFormat) -> IndexMap<String, String> {
+    let mut row = IndexMap::new();
+    flatten_object(&mut row, None, document, format);
+    row
+}
+
+fn flatten_object(
+    row: &mut IndexMap<String, String>,
+    base_key: Option<&str>,
+    object: &Map<String, Value>,
+    format: &CsvFormat,
+) {
+    for (key, value) in object {
+        let key = match base_key {
+            Some(base_key) => format!("{base_key}{}{key}", format.nested_separator),
+            None => key.clone(),
+        };
+        match value {
+            Value::Object(object) => flatten_object(row, Some(&key), object, format),
+            Value::Array(values) if values.iter().any(|v| v.is_object() || v.is_array()) => {
+                row.insert(key, value.to_string());
+            }
+            Value::Array(values) => {
+                let values: Vec<_> = values.iter().map(cell).collect();
+                row.insert(key, values.join(&format.array_separator));
+            }
+            value => {
+                row.insert(key, cell(value));
+            }
+        }
+    }
+}
+
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::test::TestRequest;
+    use serde_json::json;
+
+    use super::*;
+
+    fn to_csv(documents: Value, format: &CsvFormat) -> String {
+        let documents: Vec<Document> = serde_json::from_value(documents).unwrap();
+        String::from_utf8(documents_to_csv(&documents, format).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn flatten_nested_fields() {
+        let documents = json!([
+            {
+                "id": 1,
+                "title": "Carol",
+                "author": { "name": "Patricia", "born": 1921 },
+                "tags": ["romance", "classic"],
+            },
+            { "id": 2, "title": "Dune, Messiah", "reviews": [{ "stars": 4 }], "price": null },
+        ]);
+
+        meili_snap::snapshot!(to_csv(documents.clone(), &CsvFormat::default()), @r###"
+        id,title,author.name,author.born,tags,reviews,price
+        1,Carol,Patricia,1921,romance|classic,,
+        2,"Dune, Messiah",,,,"[{""stars"":4}]",
+        "###);
+
+        let format = CsvFormat {
+            delimiter: b';',
+            nested_separator: "__".to_string(),
+            array_separator: ", ".to_string(),
+        };
+        meili_snap::snapshot!(to_csv(documents, &format), @r###"
+        id;title;author__name;author__born;tags;reviews;price
+        1;Carol;Patricia;1921;romance, classic;;
+        2;Dune, Messiah;;;;"[{""stars"":4}]";
+        "###);
+    }
+
+    #[test]
+    fn accept_header() {
+        let req = TestRequest::default().insert_header((ACCEPT, "text/csv")).to_http_request();
+        assert!(accepts_csv(&req));
+        let req = TestRequest::default()
+            .insert_header((ACCEPT, "application/json;q=0.9, TEXT/CSV; charset=utf-8"))
+            .to_http_request();
+        assert!(accepts_csv(&req));
+        let req =
+            TestRequest::default().insert_header((ACCEPT, "application/json")).to_http_request();
+        assert!(!accepts_csv(&req));
+        assert!(!accepts_csv(&TestRequest::default().to_http_request()));
+    }
+}
@@ -4,6 +4,7 @@ use byte_unit::Byte;
 use meilisearch_types::document_formats::{DocumentFormatError, PayloadType};
 use meilisearch_types::error::{Code, ErrorCode, ResponseError};
 use meilisearch_types::index_uid::{IndexUid, IndexUidFormatError};
+use meilisearch_types::tasks::TaskId;
 use serde_json::Value;
 use tokio::task::JoinError;
 
@@ -21,14 +22,24 @@ pub enum MeilisearchHttpError {
         .1.iter().map(|s| format!("`{}`", s)).collect::<Vec<_>>().join(", ")
     )]
     InvalidContentType(String, Vec<String>),
+    #[error("The `{0}` parameter must not be empty.")]
+    EmptyCsvSeparator(&'static str),
+    #[error(transparent)]
+    Csv(#[from] csv::Error),
     #[error("Document `{0}` not found.")]
     DocumentNotFound(String),
     #[error("Sending an empty filter is forbidden.")]
     EmptyFilter,
     #[error("Invalid syntax for the filter parameter: `expected {}, found: {1}`.", .0.join(", "))]
     InvalidExpression(&'static [&'static str], Value),
+    #[error("The filter is too complex: it has a depth of {depth} and is made of {complexity} conditions. The maximum depth is {max_depth} and the maximum number of conditions is {max_complexity}.")]
+    FilterTooComplex { depth: usize, complexity: usize, max_depth: usize, max_complexity: usize },
     #[error("A {0} payload is missing.")]
     MissingPayload(PayloadType),
+    #[error("A multipart/form-data payload must contain at least one file.")]
+    MissingMultipartFiles,
+    #[error("The multipart/form-data payload provided is malformed: `{0}`.")]
+    Multipart(#[from] actix_multipart::MultipartError),
     #[error("The provided payload reached the size limit. The maximum accepted payload size is {}.",  Byte::from_bytes(*.0 as u64).get_appropriate_unit(true))]
     PayloadTooLarge(usize),
     #[error("Two indexes must be given for each swap. The list `[{}]` contains {} indexes.",
@@ -55,6 +66,36 @@ pub enum MeilisearchHttpError {
     Join(#[from] JoinError),
     #[error("Invalid request: missing `hybrid` parameter when both `q` and `vector` are present.")]
     MissingSearchHybrid,
+    #[error("The `sortFirst` parameter can only be used along with `sort`.")]
+    SortFirstWithoutSort,
+    #[error("Invalid facet filter for `{0}`: {1}")]
+    InvalidFacetFilter(String, String),
+    #[error("Invalid boost: {0}")]
+    InvalidSearchBoost(String),
+    #[error("The `groupBy` parameter can't be used along with a hybrid search.")]
+    GroupByWithHybrid,
+    #[error("Invalid nested filter: {0}")]
+    InvalidSearchNested(String),
+    #[error("No proxy backend matches the index `{0}`.")]
+    ProxyNoBackend(String),
+    #[error("The proxy backend `{0}` could not be reached: {1}.")]
+    ProxyBackendUnreachable(String, reqwest::Error),
+    #[error("Task `{0}` exists on several proxy backends. Use the `indexUid` parameter to select the backend of its index.")]
+    ProxyAmbiguousTask(TaskId),
+    #[error("The `{0}` route is not available in proxy mode.")]
+    NotAvailableInProxyMode(String),
+    #[error("Too many search requests are waiting to be processed, the search queue holds at most {0} of them. Try again later.")]
+    TooManySearchRequests(usize),
+    #[error("The search request waited more than {0}ms in the search queue. Try again later.")]
+    SearchQueueTimeout(u128),
+    #[error("The WASM middleware of the index `{0}` failed: {1}.")]
+    WasmMiddleware(String, String),
+    #[error("{0}")]
+    DocumentRouting(String),
+    #[error("The API key or the tenant token can't access the attribute `{0}`.")]
+    ForbiddenAttribute(String),
+    #[error("The index `{0}` can't be exported with an API key or a tenant token restricting its attributes.")]
+    ForbiddenIndexExport(String),
 }
 
 impl ErrorCode for MeilisearchHttpError {
@@ -64,10 +105,18 @@ impl ErrorCode for MeilisearchHttpError {
             MeilisearchHttpError::AlreadyUsedLogRoute => Code::BadRequest,
             MeilisearchHttpError::CsvDelimiterWithWrongContentType(_) => Code::InvalidContentType,
             MeilisearchHttpError::MissingPayload(_) => Code::MissingPayload,
+            MeilisearchHttpError::MissingMultipartFiles => Code::MissingPayload,
+            MeilisearchHttpError::Multipart(actix_multipart::MultipartError::Payload(
+                aweb::error::PayloadError::Overflow,
+            )) => Code::PayloadTooLarge,
+            MeilisearchHttpError::Multipart(_) => Code::MalformedPayload,
             MeilisearchHttpError::InvalidContentType(_, _) => Code::InvalidContentType,
+            MeilisearchHttpError::EmptyCsvSeparator(_) => Code::InvalidDocumentCsvSeparator,
+            MeilisearchHttpError::Csv(_) => Code::Internal,
             MeilisearchHttpError::DocumentNotFound(_) => Code::DocumentNotFound,
             MeilisearchHttpError::EmptyFilter => Code::InvalidDocumentFilter,
             MeilisearchHttpError::InvalidExpression(_, _) => Code::InvalidSearchFilter,
+            MeilisearchHttpError::FilterTooComplex { .. } => Code::FilterTooComplex,
             MeilisearchHttpError::PayloadTooLarge(_) => Code::PayloadTooLarge,
             MeilisearchHttpError::SwapIndexPayloadWrongLength(_) => Code::InvalidSwapIndexes,
             MeilisearchHttpError::IndexUid(e) => e.error_code(),
@@ -80,6 +129,32 @@ impl ErrorCode for MeilisearchHttpError {
             MeilisearchHttpError::DocumentFormat(e) => e.error_code(),
             MeilisearchHttpError::Join(_) => Code::Internal,
             MeilisearchHttpError::MissingSearchHybrid => Code::MissingSearchHybrid,
+            MeilisearchHttpError::SortFirstWithoutSort => Code::InvalidSearchSortFirst,
+            MeilisearchHttpError::InvalidFacetFilter(_, _) => Code::InvalidSearchFacetFilters,
+            MeilisearchHttpError::InvalidSearchBoost(_) => Code::InvalidSearchBoosts,
+            MeilisearchHttpError::GroupByWithHybrid => Code::InvalidSearchGroupBy,
+            MeilisearchHttpError::InvalidSearchNested(_) => Code::InvalidSearchNested,
+            MeilisearchHttpError::ProxyNoBackend(_) => Code::IndexNotFound,
+            MeilisearchHttpError::ProxyBackendUnreachable(_, _) => Code::ProxyBackendUnreachable,
+            MeilisearchHttpError::ProxyAmbiguousTask(_) => Code::BadRequest,
+            MeilisearchHttpError::NotAvailableInProxyMode(_) => Code::NotAvailableInProxyMode,
+            MeilisearchHttpError::TooManySearchRequests(_)
+            | MeilisearchHttpError::SearchQueueTimeout(_) => Code::TooManySearchRequests,
+            MeilisearchHttpError::WasmMiddleware(_, _) => Code::WasmMiddlewareFailed,
+            MeilisearchHttpError::DocumentRouting(_) => Code::InvalidDocumentRoutingField,
+            MeilisearchHttpError::ForbiddenAttribute(_)
+            | MeilisearchHttpError::ForbiddenIndexExport(_) => Code::ForbiddenAttribute,
+        }
+    }
+
+    fn retry_after(&self) -> Option<std::time::Duration> {
+        match self {
+            MeilisearchHttpError::IndexScheduler(e) => e.retry_after(),
+            MeilisearchHttpError::TooManySearchRequests(_)
+            | MeilisearchHttpError::SearchQueueTimeout(_) => {
+                Some(std::time::Duration::from_secs(1))
+            }
+            _ => None,
         }
     }
 }
@@ -11,6 +11,10 @@ pub enum AuthenticationError {
     IrretrievableState,
     #[error("Meilisearch is running without a master key. To access this API endpoint, you must have set a master key at launch.")]
     MissingMasterKey,
+    #[error("Too many requests were made with the provided API key. Retry later.")]
+    RateLimitExceeded,
+    #[error("The provided API key can't be used from this IP address.")]
+    ForbiddenIpAddress,
 }
 
 impl ErrorCode for AuthenticationError {
@@ -20,6 +24,8 @@ impl ErrorCode for AuthenticationError {
             AuthenticationError::InvalidToken => Code::InvalidApiKey,
             AuthenticationError::IrretrievableState => Code::Internal,
             AuthenticationError::MissingMasterKey => Code::MissingMasterKey,
+            AuthenticationError::RateLimitExceeded => Code::RateLimitExceeded,
+            AuthenticationError::ForbiddenIpAddress => Code::ForbiddenIpAddress,
         }
     }
 }
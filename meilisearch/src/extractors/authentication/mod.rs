@@ -1,16 +1,31 @@
 mod error;
 
 use std::marker::PhantomData;
+use std::net::{IpAddr, SocketAddr};
 use std::ops::Deref;
 use std::pin::Pin;
 
+use actix_web::http::header::{self, HeaderMap, X_FORWARDED_FOR};
 use actix_web::web::Data;
 use actix_web::FromRequest;
 pub use error::AuthenticationError;
 use futures::future::err;
 use futures::Future;
-use meilisearch_auth::{AuthController, AuthFilter};
+use meilisearch_auth::{AuthController, AuthFilter, KeyUsageRequest, RateLimitedRequest};
 use meilisearch_types::error::{Code, ResponseError};
+use uuid::Uuid;
+
+use crate::client_identity::ClientKey;
+use crate::option::Opt;
+
+/// Inserted in the extensions of a request once it's authenticated.
+#[derive(Debug, Clone, Copy)]
+pub struct Authenticated {
+    /// The uid of the API key the request was made with, `None` for the master key.
+    pub key_uid: Option<Uuid>,
+    /// Whether the route modifies the instance, see [`Policy::is_write`].
+    pub write: bool,
+}
 
 pub struct GuardedData<P, D> {
     data: D,
@@ -27,6 +42,7 @@ impl<P, D> GuardedData<P, D> {
         auth: Data<AuthController>,
         token: String,
         index: Option<String>,
+        client_ip: Option<IpAddr>,
         data: Option<D>,
     ) -> Result<Self, ResponseError>
     where
@@ -34,9 +50,25 @@ impl<P, D> GuardedData<P, D> {
     {
         let missing_master_key = auth.get_master_key().is_none();
 
-        match Self::authenticate(auth, token, index).await? {
+        match Self::authenticate(auth.clone(), token, index).await? {
             Some(filters) => match data {
-                Some(data) => Ok(Self { data, filters, _marker: PhantomData }),
+                Some(data) => {
+                    if !filters.is_ip_authorized(client_ip) {
+                        return Err(AuthenticationError::ForbiddenIpAddress.into());
+                    }
+                    if let Some(request) = P::rate_limited_request() {
+                        if let Err(retry_after) = auth.hit_rate_limit(&filters, request) {
+                            return Err(ResponseError::from(
+                                AuthenticationError::RateLimitExceeded,
+                            )
+                            .with_retry_after(retry_after));
+                        }
+                    }
+                    if let Err(e) = auth.record_key_usage(&filters, P::key_usage_request()) {
+                        tracing::error!("Could not record the usage of an API key: {e}");
+                    }
+                    Ok(Self { data, filters, _marker: PhantomData })
+                }
                 None => Err(AuthenticationError::IrretrievableState.into()),
             },
             None if missing_master_key => Err(AuthenticationError::MissingMasterKey.into()),
@@ -91,6 +123,21 @@ impl<P: Policy + 'static, D: 'static + Clone> FromRequest for GuardedData<P, D>
         req: &actix_web::HttpRequest,
         _payload: &mut actix_web::dev::Payload,
     ) -> Self::Future {
+        let guarded = Self::guard(req);
+        let req = req.clone();
+        Box::pin(async move {
+            let guarded = guarded.await?;
+            req.extensions_mut()
+                .insert(Authenticated { key_uid: guarded.filters.key_uid(), write: P::is_write() });
+            Ok(guarded)
+        })
+    }
+}
+
+impl<P: Policy + 'static, D: 'static + Clone> GuardedData<P, D> {
+    fn guard(
+        req: &actix_web::HttpRequest,
+    ) -> Pin<Box<dyn Future<Output = Result<Self, ResponseError>>>> {
         match req.app_data::<Data<AuthController>>().cloned() {
             Some(auth) => match req
                 .headers()
@@ -106,6 +153,7 @@ impl<P: Policy + 'static, D: 'static + Clone> FromRequest for GuardedData<P, D>
                                 auth,
                                 token.to_string(),
                                 index.map(String::from),
+                                client_ip(req),
                                 req.app_data::<D>().cloned(),
                             )),
                             None => Box::pin(err(AuthenticationError::InvalidToken.into())),
@@ -115,25 +163,109 @@ impl<P: Policy + 'static, D: 'static + Clone> FromRequest for GuardedData<P, D>
                         Box::pin(err(AuthenticationError::MissingAuthorizationHeader.into()))
                     }
                 },
-                None => Box::pin(Self::auth_token(auth, req.app_data::<D>().cloned())),
+                // the clients authenticated with a certificate get the permissions of its API key.
+                None => match req.conn_data::<ClientKey>().and_then(|key| auth.generate_key(key.0))
+                {
+                    Some(token) => Box::pin(Self::auth_bearer(
+                        auth,
+                        token,
+                        req.match_info().get("index_uid").map(String::from),
+                        client_ip(req),
+                        req.app_data::<D>().cloned(),
+                    )),
+                    None => Box::pin(Self::auth_token(auth, req.app_data::<D>().cloned())),
+                },
             },
             None => Box::pin(err(AuthenticationError::IrretrievableState.into())),
         }
     }
 }
 
+/// Returns the address of the client.
+///
+/// The `Forwarded` and `X-Forwarded-For` headers are only used when the peer is one of the trusted
+/// proxies, the client being then the last forwarded address that isn't a trusted proxy itself.
+pub fn client_ip(req: &actix_web::HttpRequest) -> Option<IpAddr> {
+    let peer = req.peer_addr()?.ip();
+    let trusted_proxies = match req.app_data::<Data<Opt>>() {
+        Some(opt) if opt.experimental_trusted_proxies.contains(peer) => {
+            &opt.experimental_trusted_proxies
+        }
+        _ => return Some(peer),
+    };
+
+    let mut client = peer;
+    for addr in forwarded_addrs(req.headers()).into_iter().rev() {
+        // we can't go further than a proxy that forwarded an invalid address.
+        let Some(addr) = addr else { break };
+        client = addr;
+        if !trusted_proxies.contains(addr) {
+            break;
+        }
+    }
+    Some(client)
+}
+
+/// Returns the addresses the request was forwarded for, from the client to the last proxy.
+fn forwarded_addrs(headers: &HeaderMap) -> Vec<Option<IpAddr>> {
+    let forwarded: Vec<_> = headers
+        .get_all(header::FORWARDED)
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|element| {
+            element.split(';').find_map(|pair| {
+                let (name, value) = pair.trim().split_once('=')?;
+                name.eq_ignore_ascii_case("for").then_some(value)
+            })
+        })
+        .map(parse_forwarded_addr)
+        .collect();
+    if !forwarded.is_empty() {
+        return forwarded;
+    }
+
+    headers
+        .get_all(X_FORWARDED_FOR)
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(parse_forwarded_addr)
+        .collect()
+}
+
+fn parse_forwarded_addr(addr: &str) -> Option<IpAddr> {
+    let addr = addr.trim().trim_matches('"');
+    // the address may contain a port, and the IPv6 addresses of the `Forwarded` header are
+    // enclosed in brackets.
+    addr.parse()
+        .ok()
+        .or_else(|| addr.parse::<SocketAddr>().ok().map(|addr| addr.ip()))
+        .or_else(|| addr.strip_prefix('[')?.strip_suffix(']')?.parse().ok())
+}
+
 pub trait Policy {
     fn authenticate(
         auth: Data<AuthController>,
         token: &str,
         index: Option<&str>,
     ) -> Option<AuthFilter>;
+
+    /// The kind of request the rate limits of the API keys apply to, if any.
+    fn rate_limited_request() -> Option<RateLimitedRequest>;
+
+    /// The kind of request counted in the usage statistics of the API keys.
+    fn key_usage_request() -> KeyUsageRequest;
+
+    /// Whether the routes guarded by this policy can modify the instance,
+    /// the requests made to them are recorded in the audit log.
+    fn is_write() -> bool;
 }
 
 pub mod policies {
     use actix_web::web::Data;
     use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
-    use meilisearch_auth::{AuthController, AuthFilter, SearchRules};
+    use meilisearch_auth::{
+        AuthController, AuthFilter, KeyUsageRequest, RateLimitedRequest, SearchRules,
+    };
     // reexport actions in policies in order to be used in routes configuration.
     pub use meilisearch_types::keys::{actions, Action};
     use serde::{Deserialize, Serialize};
@@ -174,6 +306,85 @@ pub mod policies {
         matches!(action, KEYS_GET | KEYS_CREATE | KEYS_UPDATE | KEYS_DELETE)
     }
 
+    fn is_write_action(action: u8) -> bool {
+        use actions::*;
+        matches!(
+            action,
+            ALL | DOCUMENTS_ADD
+                | DOCUMENTS_DELETE
+                | INDEXES_CREATE
+                | INDEXES_UPDATE
+                | INDEXES_DELETE
+                | INDEXES_SWAP
+                | TASKS_CANCEL
+                | TASKS_DELETE
+                | TASKS_RETRY
+                | SETTINGS_UPDATE
+                | DUMPS_CREATE
+                | SNAPSHOTS_CREATE
+                | KEYS_CREATE
+                | KEYS_UPDATE
+                | KEYS_DELETE
+                | EXPERIMENTAL_FEATURES_UPDATE
+                | SCHEDULES_CREATE
+                | SCHEDULES_DELETE
+        ) || matches!(
+            Action::from_repr(action).and_then(|action| action.implied_by()),
+            Some(Action::SettingsUpdate)
+        )
+    }
+
+    /// Only grants the access to the requests authenticated with the master key.
+    pub struct MasterPolicy;
+
+    impl Policy for MasterPolicy {
+        fn authenticate(
+            auth: Data<AuthController>,
+            token: &str,
+            _index: Option<&str>,
+        ) -> Option<AuthFilter> {
+            auth.is_master_key(token).then(AuthFilter::default)
+        }
+
+        fn rate_limited_request() -> Option<RateLimitedRequest> {
+            None
+        }
+
+        fn key_usage_request() -> KeyUsageRequest {
+            KeyUsageRequest::Other
+        }
+
+        fn is_write() -> bool {
+            true
+        }
+    }
+
+    /// Only grants the access to the requests authenticated with the current master key,
+    /// the previous one being refused during its grace period.
+    pub struct CurrentMasterKeyPolicy;
+
+    impl Policy for CurrentMasterKeyPolicy {
+        fn authenticate(
+            auth: Data<AuthController>,
+            token: &str,
+            _index: Option<&str>,
+        ) -> Option<AuthFilter> {
+            auth.is_current_master_key(token).then(AuthFilter::default)
+        }
+
+        fn rate_limited_request() -> Option<RateLimitedRequest> {
+            None
+        }
+
+        fn key_usage_request() -> KeyUsageRequest {
+            KeyUsageRequest::Other
+        }
+
+        fn is_write() -> bool {
+            true
+        }
+    }
+
     pub struct ActionPolicy<const A: u8>;
 
     impl<const A: u8> Policy for ActionPolicy<A> {
@@ -190,7 +401,10 @@ pub mod policies {
         ) -> Option<AuthFilter> {
             // authenticate if token is the master key.
             // Without a master key, all routes are accessible except the key-related routes.
-            if auth.get_master_key().map_or_else(|| !is_keys_action(A), |mk| mk == token) {
+            if auth
+                .get_master_key()
+                .map_or_else(|| !is_keys_action(A), |_| auth.is_master_key(token))
+            {
                 return Some(AuthFilter::default());
             }
 
@@ -217,6 +431,30 @@ pub mod policies {
 
             None
         }
+
+        fn rate_limited_request() -> Option<RateLimitedRequest> {
+            if A == actions::SEARCH {
+                Some(RateLimitedRequest::Search)
+            } else if is_write_action(A) {
+                Some(RateLimitedRequest::Write)
+            } else {
+                None
+            }
+        }
+
+        fn key_usage_request() -> KeyUsageRequest {
+            match A {
+                actions::SEARCH => KeyUsageRequest::Search,
+                actions::DOCUMENTS_ADD | actions::DOCUMENTS_DELETE => {
+                    KeyUsageRequest::DocumentsWrite
+                }
+                _ => KeyUsageRequest::Other,
+            }
+        }
+
+        fn is_write() -> bool {
+            is_write_action(A)
+        }
     }
 
     impl<const A: u8> ActionPolicy<A> {
@@ -232,18 +470,17 @@ pub mod policies {
                 return TenantTokenOutcome::NotATenantToken;
             };
 
-            // Check if tenant token is valid.
-            let key = if let Some(key) = auth.generate_key(uid) {
-                key
-            } else {
-                return TenantTokenOutcome::Invalid;
-            };
-
-            let data = if let Ok(data) = decode::<Claims>(
-                token,
-                &DecodingKey::from_secret(key.as_bytes()),
-                &tenant_token_validation(),
-            ) {
+            // Check if tenant token is valid, it may have been signed with a key
+            // derived from the previous master key during its grace period.
+            let data = auth.generate_accepted_keys(uid).into_iter().find_map(|key| {
+                decode::<Claims>(
+                    token,
+                    &DecodingKey::from_secret(key.as_bytes()),
+                    &tenant_token_validation(),
+                )
+                .ok()
+            });
+            let data = if let Some(data) = data {
                 data
             } else {
                 return TenantTokenOutcome::Invalid;
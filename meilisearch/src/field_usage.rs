@@ -0,0 +1,88 @@
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use meilisearch_types::milli::{AscDesc, Member};
+use parking_lot::{Mutex, RwLock};
+
+use crate::search::{parse_filter, FilterLimits, SearchQuery};
+
+/// The number of searches kept per index to suggest the settings of its fields.
+pub const RECENT_SEARCHES_PER_INDEX: usize = 1000;
+
+/// Keeps track of the fields used by the most recent searches made on every index.
+///
+/// Every index has its own lock, so the searches of different indexes never wait for each other,
+/// the lock of the map is only written when the first search of an index is recorded.
+///
+/// It lives in memory only and is reset when the instance restarts.
+#[derive(Debug, Default)]
+pub struct FieldUsage {
+    indexes: RwLock<HashMap<String, Arc<Mutex<VecDeque<UsedFields>>>>>,
+}
+
+impl FieldUsage {
+    /// Registers the fields used by a search made on the index, forgetting the oldest one
+    /// when more than [`RECENT_SEARCHES_PER_INDEX`] searches are registered for it.
+    ///
+    /// Only the searches made on an existing index must be registered.
+    pub fn record(&self, index_uid: &str, used: UsedFields) {
+        let searches = self.indexes.read().get(index_uid).cloned();
+        let searches = match searches {
+            Some(searches) => searches,
+            None => self.indexes.write().entry(index_uid.to_string()).or_default().clone(),
+        };
+        let mut searches = searches.lock();
+        if searches.len() == RECENT_SEARCHES_PER_INDEX {
+            searches.pop_front();
+        }
+        searches.push_back(used);
+    }
+
+    /// Returns the fields used by the recent searches made on the index.
+    pub fn recent(&self, index_uid: &str) -> Vec<UsedFields> {
+        let searches = self.indexes.read().get(index_uid).cloned();
+        searches.map(|searches| searches.lock().iter().cloned().collect()).unwrap_or_default()
+    }
+}
+
+/// The fields a search relies on, by the way it uses them.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct UsedFields {
+    pub filter: BTreeSet<String>,
+    pub sort: BTreeSet<String>,
+    pub facets: BTreeSet<String>,
+    pub search_on: BTreeSet<String>,
+}
+
+impl UsedFields {
+    /// Extracts the fields used by the query, the malformed filters and sorts are ignored
+    /// as they are rejected by the search anyway.
+    pub fn from_query(query: &SearchQuery, filter_limits: FilterLimits) -> Self {
+        let mut used = UsedFields::default();
+
+        if let Some(Ok(Some(filter))) =
+            query.filter.as_ref().map(|f| parse_filter(f, filter_limits))
+        {
+            used.filter.extend(filter.fields().into_iter().map(String::from));
+        }
+
+        for sort in query.sort.iter().flatten() {
+            match AscDesc::from_str(sort).as_ref().map(AscDesc::member) {
+                Ok(Member::Field(field)) => {
+                    used.sort.insert(field.clone());
+                }
+                Ok(Member::Geo(_)) => {
+                    used.sort.insert("_geo".to_string());
+                }
+                Ok(Member::Random(_)) | Err(_) => (),
+            }
+        }
+
+        used.facets
+            .extend(query.facets.iter().flat_map(|f| &f.names).filter(|f| *f != "*").cloned());
+        used.search_on.extend(query.attributes_to_search_on.iter().flatten().cloned());
+
+        used
+    }
+}
@@ -2,16 +2,24 @@
 #[macro_use]
 pub mod error;
 pub mod analytics;
+pub mod client_identity;
+pub mod csv_export;
 #[macro_use]
 pub mod extractors;
+pub mod field_usage;
 pub mod metrics;
 pub mod middleware;
 pub mod option;
+pub mod proxy;
+pub mod query_log;
 pub mod routes;
 pub mod search;
+pub mod search_queue;
+pub mod wasm_middleware;
 
 use std::fs::File;
 use std::io::{BufReader, BufWriter};
+use std::num::NonZeroUsize;
 use std::path::Path;
 use std::sync::Arc;
 use std::thread;
@@ -27,19 +35,25 @@ use analytics::Analytics;
 use anyhow::bail;
 use error::PayloadError;
 use extractors::payload::PayloadConfig;
+use field_usage::FieldUsage;
 use http::header::CONTENT_TYPE;
-use index_scheduler::{IndexScheduler, IndexSchedulerOptions};
+use index_scheduler::{
+    IndexAlertThresholds, IndexScheduler, IndexSchedulerOptions, S3Options, TaskRetentionPolicy,
+};
 use meilisearch_auth::AuthController;
 use meilisearch_types::milli::documents::{DocumentsBatchBuilder, DocumentsBatchReader};
 use meilisearch_types::milli::update::{IndexDocumentsConfig, IndexDocumentsMethod};
 use meilisearch_types::settings::apply_settings_to_builder;
 use meilisearch_types::tasks::KindWithContent;
 use meilisearch_types::versioning::{check_version_file, create_version_file};
-use meilisearch_types::{compression, milli, VERSION_FILE_NAME};
+use meilisearch_types::{milli, snapshot, VERSION_FILE_NAME};
 pub use option::Opt;
 use option::ScheduleSnapshot;
+use query_log::QueryLog;
+use search_queue::SearchQueue;
 use tracing::{error, info_span};
 use tracing_subscriber::filter::Targets;
+use wasm_middleware::WasmMiddlewares;
 
 use crate::error::MeilisearchHttpError;
 
@@ -117,6 +131,10 @@ pub fn create_app(
     opt: Opt,
     logs: (LogRouteHandle, LogStderrHandle),
     analytics: Arc<dyn Analytics>,
+    field_usage: Data<FieldUsage>,
+    query_log: Data<QueryLog>,
+    search_queue: Data<SearchQueue>,
+    wasm_middlewares: Data<WasmMiddlewares>,
     enable_dashboard: bool,
 ) -> actix_web::App<
     impl ServiceFactory<
@@ -136,12 +154,27 @@ pub fn create_app(
                 &opt,
                 logs,
                 analytics.clone(),
+                field_usage.clone(),
+                query_log.clone(),
+                search_queue.clone(),
+                wasm_middlewares.clone(),
             )
         })
-        .configure(routes::configure)
+        .configure(|s| {
+            if opt.experimental_proxy_backends.is_empty() {
+                routes::configure(s)
+            } else {
+                proxy::configure(s, &opt)
+            }
+        })
         .configure(|s| dashboard(s, enable_dashboard));
 
-    let app = app.wrap(middleware::RouteMetrics);
+    let compression_min_size = opt.http_compression_min_size.get_bytes() as u64;
+    let app = app
+        .wrap(middleware::RewriteSearch)
+        .wrap(middleware::RouteMetrics)
+        .wrap(middleware::AuditLogger)
+        .wrap(middleware::PropagateRequestId);
     app.wrap(
         Cors::default()
             .send_wildcard()
@@ -151,6 +184,7 @@ pub fn create_app(
             .max_age(86_400), // 24h
     )
     .wrap(tracing_actix_web::TracingLogger::<AwebTracingLogger>::new())
+    .wrap(middleware::CompressionThreshold::new(compression_min_size))
     .wrap(actix_web::middleware::Compress::default())
     .wrap(actix_web::middleware::NormalizePath::new(actix_web::middleware::TrailingSlash::Trim))
 }
@@ -167,7 +201,8 @@ impl tracing_actix_web::RootSpanBuilder for AwebTracingLogger {
             .get(http::header::USER_AGENT)
             .map(|value| String::from_utf8_lossy(value.as_bytes()).into_owned())
             .unwrap_or_default();
-        info_span!("HTTP request", method = %request.method(), host = conn_info.host(), route = %request.path(), query_parameters = %request.query_string(), %user_agent, status_code = Empty, error = Empty)
+        let request_id = middleware::RequestId::of(request).id;
+        info_span!("HTTP request", method = %request.method(), host = conn_info.host(), route = %request.path(), query_parameters = %request.query_string(), %user_agent, %request_id, status_code = Empty, error = Empty)
     }
 
     fn on_request_end<B: MessageBody>(
@@ -204,7 +239,7 @@ pub fn setup_meilisearch(opt: &Opt) -> anyhow::Result<(Arc<IndexScheduler>, Arc<
         let snapshot_path_exists = snapshot_path.exists();
         // the db is empty and the snapshot exists, import it
         if empty_db && snapshot_path_exists {
-            match compression::from_tar_gz(snapshot_path, &opt.db_path) {
+            match snapshot::restore(snapshot_path, &opt.db_path) {
                 Ok(()) => open_or_create_database_unchecked(opt, OnFailure::RemoveDb)?,
                 Err(e) => {
                     std::fs::remove_dir_all(&opt.db_path)?;
@@ -284,7 +319,14 @@ fn open_or_create_database_unchecked(
 ) -> anyhow::Result<(IndexScheduler, AuthController)> {
     // we don't want to create anything in the data.ms yet, thus we
     // wrap our two builders in a closure that'll be executed later.
-    let auth_controller = AuthController::new(&opt.db_path, &opt.master_key);
+    let auth_controller = AuthController::new(&opt.db_path, &opt.master_key).and_then(|auth| {
+        if opt.audit_log {
+            let max_file_size = opt.audit_log_max_file_size.get_bytes() as u64;
+            auth.with_audit_log(opt.db_path.join("audit-log"), max_file_size)
+        } else {
+            Ok(auth)
+        }
+    });
     let instance_features = opt.to_instance_features();
     let index_scheduler_builder = || -> anyhow::Result<_> {
         Ok(IndexScheduler::new(IndexSchedulerOptions {
@@ -292,8 +334,29 @@ fn open_or_create_database_unchecked(
             auth_path: opt.db_path.join("auth"),
             tasks_path: opt.db_path.join("tasks"),
             update_file_path: opt.db_path.join("update_files"),
+            dead_letters_path: opt.db_path.join("dead_letters"),
             indexes_path: opt.db_path.join("indexes"),
             snapshots_path: opt.snapshot_dir.clone(),
+            snapshot_retention: opt.snapshot_retention.map(NonZeroUsize::get),
+            snapshot_incremental: opt.snapshot_incremental,
+            snapshot_s3: match (
+                &opt.snapshot_s3_endpoint,
+                &opt.snapshot_s3_bucket,
+                &opt.snapshot_s3_access_key,
+                &opt.snapshot_s3_secret_key,
+            ) {
+                (Some(endpoint), Some(bucket), Some(access_key), Some(secret_key)) => {
+                    Some(S3Options {
+                        endpoint: endpoint.to_string(),
+                        bucket: bucket.clone(),
+                        region: opt.snapshot_s3_region.clone(),
+                        prefix: opt.snapshot_s3_prefix.clone(),
+                        access_key: access_key.clone(),
+                        secret_key: secret_key.clone(),
+                    })
+                }
+                _ => None,
+            },
             dumps_path: opt.dump_dir.clone(),
             webhook_url: opt.task_webhook_url.as_ref().map(|url| url.to_string()),
             webhook_authorization_header: opt.task_webhook_authorization_header.clone(),
@@ -301,10 +364,27 @@ fn open_or_create_database_unchecked(
             index_base_map_size: opt.max_index_size.get_bytes() as usize,
             enable_mdb_writemap: opt.experimental_reduce_indexing_memory_usage,
             indexer_config: (&opt.indexer_options).try_into()?,
-            autobatching_enabled: true,
+            autobatching_enabled: !opt.experimental_disable_autobatching,
+            max_concurrent_batches: opt.experimental_max_concurrent_batches,
             cleanup_enabled: !opt.experimental_replication_parameters,
             max_number_of_tasks: 1_000_000,
+            task_retention: TaskRetentionPolicy {
+                max_count: opt.task_retention_max_count,
+                max_age: opt.task_retention_max_age_sec.map(Duration::from_secs),
+            },
+            retry_payload_retention: Duration::from_secs(opt.task_retry_payload_retention_sec),
+            index_alert_thresholds: IndexAlertThresholds {
+                database_size: opt.index_alert_size_threshold.map(|size| size.get_bytes() as u64),
+                number_of_documents: opt.index_alert_documents_threshold,
+            },
+            index_alert_webhook_url: opt
+                .index_alert_webhook_url
+                .as_ref()
+                .map(|url| url.to_string()),
             max_number_of_batched_tasks: opt.experimental_max_number_of_batched_tasks,
+            max_batched_payload_size: opt
+                .experimental_max_batched_payload_size
+                .map(|size| size.get_bytes() as u64),
             index_growth_amount: byte_unit::Byte::from_str("10GiB").unwrap().get_bytes() as usize,
             index_count: DEFAULT_INDEX_COUNT,
             instance_features,
@@ -468,6 +548,10 @@ pub fn configure_data(
     opt: &Opt,
     (logs_route, logs_stderr): (LogRouteHandle, LogStderrHandle),
     analytics: Arc<dyn Analytics>,
+    field_usage: Data<FieldUsage>,
+    query_log: Data<QueryLog>,
+    search_queue: Data<SearchQueue>,
+    wasm_middlewares: Data<WasmMiddlewares>,
 ) {
     let http_payload_size_limit = opt.http_payload_size_limit.get_bytes() as usize;
     config
@@ -477,6 +561,10 @@ pub fn configure_data(
         .app_data(web::Data::new(logs_route))
         .app_data(web::Data::new(logs_stderr))
         .app_data(web::Data::new(opt.clone()))
+        .app_data(field_usage)
+        .app_data(query_log)
+        .app_data(search_queue)
+        .app_data(wasm_middlewares)
         .app_data(
             web::JsonConfig::default()
                 .limit(http_payload_size_limit)
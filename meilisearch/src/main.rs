@@ -3,19 +3,27 @@ use std::io::{stderr, LineWriter, Write};
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use actix_web::http::KeepAlive;
 use actix_web::web::Data;
 use actix_web::HttpServer;
+use anyhow::Context as _;
 use index_scheduler::IndexScheduler;
 use is_terminal::IsTerminal;
 use meilisearch::analytics::Analytics;
+use meilisearch::client_identity::ClientIdentities;
+use meilisearch::field_usage::FieldUsage;
 use meilisearch::option::LogMode;
+use meilisearch::query_log::QueryLog;
+use meilisearch::search_queue::SearchQueue;
+use meilisearch::wasm_middleware::WasmMiddlewares;
 use meilisearch::{
     analytics, create_app, setup_meilisearch, LogRouteHandle, LogRouteType, LogStderrHandle,
     LogStderrType, Opt, SubscriberForSecondLayer,
 };
 use meilisearch_auth::{generate_master_key, AuthController, MASTER_KEY_MIN_SIZE};
+use meilisearch_types::milli::MAX_FILTER_CONDITION_DEPTH;
 use mimalloc::MiMalloc;
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 use tracing::level_filters::LevelFilter;
@@ -47,6 +55,33 @@ fn default_log_stderr_layer(opt: &Opt) -> LogStderrType {
     )
 }
 
+/// Creates the layer exporting the spans to the OpenTelemetry collector, when one is configured.
+///
+/// It must be called from within the Tokio runtime that sends the batches of spans.
+fn otlp_layer<S>(opt: &Opt) -> anyhow::Result<Option<impl Layer<S>>>
+where
+    S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+{
+    let Some(endpoint) = &opt.experimental_otlp_endpoint else { return Ok(None) };
+    let filter = tracing_subscriber::filter::Targets::from_str(&opt.experimental_otlp_filter)
+        .with_context(|| {
+            format!("Invalid OpenTelemetry filter `{}`", opt.experimental_otlp_filter)
+        })?;
+
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(opentelemetry_otlp::new_exporter().tonic().with_endpoint(endpoint.as_str()))
+        .with_trace_config(opentelemetry_sdk::trace::config().with_resource(
+            opentelemetry_sdk::Resource::new([opentelemetry::KeyValue::new(
+                "service.name",
+                "meilisearch",
+            )]),
+        ))
+        .install_batch(opentelemetry_sdk::runtime::Tokio)?;
+
+    Ok(Some(tracing_opentelemetry::layer().with_tracer(tracer).with_filter(filter)))
+}
+
 /// does all the setup before meilisearch is launched
 fn setup(opt: &Opt) -> anyhow::Result<(LogRouteHandle, LogStderrHandle)> {
     let (route_layer, route_layer_handle) =
@@ -58,6 +93,7 @@ fn setup(opt: &Opt) -> anyhow::Result<(LogRouteHandle, LogStderrHandle)> {
     let route_layer: tracing_subscriber::reload::Layer<_, _> = route_layer;
 
     let subscriber = tracing_subscriber::registry().with(route_layer).with(stderr_layer);
+    let subscriber = subscriber.with(otlp_layer(opt)?);
 
     // set the subscriber as the default for the application
     tracing::subscriber::set_global_default(subscriber).unwrap();
@@ -80,6 +116,14 @@ async fn main() -> anyhow::Result<()> {
         !(cfg!(windows) && opt.experimental_reduce_indexing_memory_usage),
         "The `experimental-reduce-indexing-memory-usage` flag is not supported on Windows"
     );
+    anyhow::ensure!(
+        !(cfg!(not(unix)) && opt.http_unix_socket.is_some()),
+        "The `http-unix-socket` option is only supported on unix platforms"
+    );
+    anyhow::ensure!(
+        opt.experimental_max_filter_depth <= MAX_FILTER_CONDITION_DEPTH,
+        "The `experimental-max-filter-depth` option can't be above {MAX_FILTER_CONDITION_DEPTH}, the deepest filters are rejected by the filter parser anyway"
+    );
 
     let log_handle = setup(&opt)?;
 
@@ -118,9 +162,16 @@ async fn main() -> anyhow::Result<()> {
     let analytics = analytics::MockAnalytics::new(&opt);
 
     print_launch_resume(&opt, analytics.clone(), config_read_from);
+    match opt.effective_configuration() {
+        Ok(config) => tracing::info!("Effective configuration:\n{config}"),
+        Err(e) => tracing::warn!("Could not display the effective configuration: {e}"),
+    }
 
     run_http(index_scheduler, auth_controller, opt, log_handle, analytics).await?;
 
+    // Sends the spans that are still waiting to be exported.
+    opentelemetry::global::shutdown_tracer_provider();
+
     Ok(())
 }
 
@@ -133,8 +184,15 @@ async fn run_http(
 ) -> anyhow::Result<()> {
     let enable_dashboard = &opt.env == "development";
     let opt_clone = opt.clone();
+    let shutdown_timeout = Duration::from_secs(opt.shutdown_timeout_sec);
+    let scheduler = index_scheduler.clone();
     let index_scheduler = Data::from(index_scheduler);
     let auth_controller = Data::from(auth_controller);
+    let field_usage = Data::new(FieldUsage::default());
+    let query_log = Data::new(QueryLog::from_opt(&opt)?);
+    let search_queue = Data::new(SearchQueue::from_opt(&opt));
+    let wasm_middlewares = Data::new(WasmMiddlewares::from_opt(&opt)?);
+    let client_identities = ClientIdentities::from_opt(&opt)?;
 
     let http_server = HttpServer::new(move || {
         create_app(
@@ -143,18 +201,90 @@ async fn run_http(
             opt.clone(),
             logs.clone(),
             analytics.clone(),
+            field_usage.clone(),
+            query_log.clone(),
+            search_queue.clone(),
+            wasm_middlewares.clone(),
             enable_dashboard,
         )
     })
-    // Disable signals allows the server to terminate immediately when a user enter CTRL-C
+    // Disable signals allows the server to terminate immediately when a user enter CTRL-C,
+    // only SIGTERM shuts it down gracefully.
     .disable_signals()
-    .keep_alive(KeepAlive::Os);
+    .shutdown_timeout(opt_clone.shutdown_timeout_sec)
+    .keep_alive(KeepAlive::Os)
+    .on_connect(move |connection, data| {
+        if let Some(client_identities) = &client_identities {
+            client_identities.on_connect(connection, data);
+        }
+    });
 
-    if let Some(config) = opt_clone.get_ssl_config()? {
-        http_server.bind_rustls_021(opt_clone.http_addr, config)?.run().await?;
+    let http_server = if opt_clone.http_unix_socket_only {
+        http_server
+    } else if let Some(config) = opt_clone.get_ssl_config()? {
+        http_server.bind_rustls_021(&opt_clone.http_addr, config)?
     } else {
-        http_server.bind(&opt_clone.http_addr)?.run().await?;
+        http_server.bind(&opt_clone.http_addr)?
+    };
+    #[cfg(unix)]
+    let http_server = match &opt_clone.http_unix_socket {
+        Some(path) => {
+            remove_stale_unix_socket(path)?;
+            http_server.bind_uds(path)?
+        }
+        None => http_server,
+    };
+    let server = http_server.run();
+
+    let server_handle = server.handle();
+    tokio::spawn(async move {
+        if let Err(e) = sigterm().await {
+            tracing::error!("Could not listen to SIGTERM: {e}");
+            return;
+        }
+        tracing::info!("Shutting down gracefully, waiting for the requests being answered.");
+        // stops accepting new connections and waits for the requests being answered.
+        server_handle.stop(true).await;
+    });
+    server.await?;
+
+    #[cfg(unix)]
+    if let Some(path) = &opt_clone.http_unix_socket {
+        if let Err(e) = std::fs::remove_file(path) {
+            tracing::warn!("Could not remove the unix socket `{}`: {e}", path.display());
+        }
+    }
+
+    tracing::info!("Waiting for the batch being processed.");
+    let drained = tokio::task::spawn_blocking(move || scheduler.shutdown(shutdown_timeout)).await?;
+    if !drained {
+        tracing::warn!(
+            "The batch being processed was aborted, it will be processed again on the next launch."
+        );
+    }
+    Ok(())
+}
+
+/// Removes the unix socket left at this path by a previous run, binding the socket fails otherwise.
+#[cfg(unix)]
+fn remove_stale_unix_socket(path: &std::path::Path) -> anyhow::Result<()> {
+    use std::os::unix::fs::FileTypeExt as _;
+
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => std::fs::remove_file(path)
+            .with_context(|| format!("Could not remove the unix socket `{}`", path.display())),
+        Ok(_) => anyhow::bail!("`{}` already exists and is not a unix socket", path.display()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e.into()),
     }
+}
+
+/// Resolves once the process receives a `SIGTERM`, never on the platforms without it.
+async fn sigterm() -> std::io::Result<()> {
+    #[cfg(unix)]
+    tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())?.recv().await;
+    #[cfg(not(unix))]
+    std::future::pending::<()>().await;
     Ok(())
 }
 
@@ -187,7 +317,12 @@ pub fn print_launch_resume(
             .unwrap_or_else(|| "none".to_string())
     );
     eprintln!("Database path:\t\t{:?}", opt.db_path);
-    eprintln!("Server listening on:\t\"{}://{}\"", protocol, opt.http_addr);
+    if !opt.http_unix_socket_only {
+        eprintln!("Server listening on:\t\"{}://{}\"", protocol, opt.http_addr);
+    }
+    if let Some(path) = &opt.http_unix_socket {
+        eprintln!("Server listening on:\t\"unix:{}\"", path.display());
+    }
     eprintln!("Environment:\t\t{:?}", opt.env);
     eprintln!("Commit SHA:\t\t{:?}", build_info.commit_sha1.unwrap_or("unknown"));
     eprintln!(
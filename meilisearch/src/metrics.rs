@@ -1,7 +1,9 @@
+use index_scheduler::RoFeatures;
 use lazy_static::lazy_static;
 use prometheus::{
-    opts, register_histogram_vec, register_int_counter_vec, register_int_gauge,
-    register_int_gauge_vec, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec,
+    opts, register_counter, register_histogram_vec, register_int_counter, register_int_counter_vec,
+    register_int_gauge, register_int_gauge_vec, Counter, HistogramTimer, HistogramVec, IntCounter,
+    IntCounterVec, IntGauge, IntGaugeVec,
 };
 
 /// Create evenly distributed buckets
@@ -22,6 +24,11 @@ lazy_static! {
         &["method", "path"]
     )
     .expect("Can't create a metric");
+    pub static ref MEILISEARCH_HTTP_RESPONSES_TOTAL: IntCounterVec = register_int_counter_vec!(
+        opts!("meilisearch_http_responses_total", "Meilisearch HTTP responses total"),
+        &["method", "path", "status"]
+    )
+    .expect("Can't create a metric");
     pub static ref MEILISEARCH_DB_SIZE_BYTES: IntGauge =
         register_int_gauge!(opts!("meilisearch_db_size_bytes", "Meilisearch DB Size In Bytes"))
             .expect("Can't create a metric");
@@ -38,6 +45,21 @@ lazy_static! {
         &["index"]
     )
     .expect("Can't create a metric");
+    pub static ref MEILISEARCH_INDEX_DB_SIZE_BYTES: IntGaugeVec = register_int_gauge_vec!(
+        opts!("meilisearch_index_db_size_bytes", "Meilisearch Index DB Size In Bytes"),
+        &["index"]
+    )
+    .expect("Can't create a metric");
+    pub static ref MEILISEARCH_INDEX_USED_DB_SIZE_BYTES: IntGaugeVec = register_int_gauge_vec!(
+        opts!("meilisearch_index_used_db_size_bytes", "Meilisearch Index Used DB Size In Bytes"),
+        &["index"]
+    )
+    .expect("Can't create a metric");
+    pub static ref MEILISEARCH_INDEX_MAP_SIZE_BYTES: IntGaugeVec = register_int_gauge_vec!(
+        opts!("meilisearch_index_map_size_bytes", "Meilisearch Index Map Size In Bytes"),
+        &["index"]
+    )
+    .expect("Can't create a metric");
     pub static ref MEILISEARCH_HTTP_RESPONSE_TIME_SECONDS: HistogramVec = register_histogram_vec!(
         "meilisearch_http_response_time_seconds",
         "Meilisearch HTTP response times",
@@ -45,6 +67,13 @@ lazy_static! {
         MEILISEARCH_HTTP_RESPONSE_TIME_CUSTOM_BUCKETS.to_vec()
     )
     .expect("Can't create a metric");
+    pub static ref MEILISEARCH_SEARCH_LATENCY_SECONDS: HistogramVec = register_histogram_vec!(
+        "meilisearch_search_latency_seconds",
+        "Meilisearch search latency",
+        &["index"],
+        MEILISEARCH_HTTP_RESPONSE_TIME_CUSTOM_BUCKETS.to_vec()
+    )
+    .expect("Can't create a metric");
     pub static ref MEILISEARCH_NB_TASKS: IntGaugeVec = register_int_gauge_vec!(
         opts!("meilisearch_nb_tasks", "Meilisearch Number of tasks"),
         &["kind", "value"]
@@ -56,4 +85,32 @@ lazy_static! {
     pub static ref MEILISEARCH_IS_INDEXING: IntGauge =
         register_int_gauge!(opts!("meilisearch_is_indexing", "Meilisearch Is Indexing"))
             .expect("Can't create a metric");
+    pub static ref MEILISEARCH_TASK_QUEUE_DEPTH: IntGauge = register_int_gauge!(opts!(
+        "meilisearch_task_queue_depth",
+        "Meilisearch Number of enqueued tasks"
+    ))
+    .expect("Can't create a metric");
+    pub static ref MEILISEARCH_INDEXED_DOCUMENTS_TOTAL: IntCounter = register_int_counter!(opts!(
+        "meilisearch_indexed_documents_total",
+        "Meilisearch Indexed Documents total"
+    ))
+    .expect("Can't create a metric");
+    pub static ref MEILISEARCH_PROCESSED_BATCHES_TOTAL: IntCounter = register_int_counter!(opts!(
+        "meilisearch_processed_batches_total",
+        "Meilisearch Processed Batches total"
+    ))
+    .expect("Can't create a metric");
+    pub static ref MEILISEARCH_BATCH_PROCESSING_SECONDS_TOTAL: Counter = register_counter!(opts!(
+        "meilisearch_batch_processing_seconds_total",
+        "Meilisearch Time Spent Processing Batches"
+    ))
+    .expect("Can't create a metric");
+}
+
+/// Starts measuring the latency of a search on the given index, when the metrics are enabled.
+pub fn search_latency_timer(features: RoFeatures, index_uid: &str) -> Option<HistogramTimer> {
+    features
+        .check_metrics()
+        .is_ok()
+        .then(|| MEILISEARCH_SEARCH_LATENCY_SECONDS.with_label_values(&[index_uid]).start_timer())
 }
@@ -1,13 +1,27 @@
 //! Contains all the custom middleware used in meilisearch
 
 use std::future::{ready, Ready};
+use std::rc::Rc;
 
+use actix_web::body::{BodySize, EitherBody, MessageBody};
 use actix_web::dev::{self, Service, ServiceRequest, ServiceResponse, Transform};
-use actix_web::web::Data;
-use actix_web::Error;
+use actix_web::http::header::{
+    ContentEncoding, HeaderMap, HeaderName, HeaderValue, CONTENT_ENCODING, CONTENT_LENGTH,
+    CONTENT_TYPE,
+};
+use actix_web::http::Method;
+use actix_web::web::{Bytes, Data};
+use actix_web::{Error, HttpMessage, HttpRequest, HttpResponse};
 use futures_util::future::LocalBoxFuture;
 use index_scheduler::IndexScheduler;
+use meilisearch_auth::{AuditEntry, AuthController, PayloadSummary};
+use meilisearch_types::error::ResponseError;
 use prometheus::HistogramTimer;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::extractors::authentication::{client_ip, Authenticated};
+use crate::wasm_middleware::{Hook, WasmMiddlewares};
 
 pub struct RouteMetrics;
 
@@ -49,6 +63,7 @@ where
 
     fn call(&self, req: ServiceRequest) -> Self::Future {
         let mut histogram_timer: Option<HistogramTimer> = None;
+        let mut route: Option<(String, String)> = None;
 
         // calling unwrap here is safe because index scheduler is added to app data while creating actix app.
         // also, the tests will fail if this is not present.
@@ -68,6 +83,7 @@ where
                 crate::metrics::MEILISEARCH_HTTP_REQUESTS_TOTAL
                     .with_label_values(&[&request_method, request_path])
                     .inc();
+                route = Some((request_method, request_path.to_string()));
             }
         };
 
@@ -79,7 +95,353 @@ where
             if let Some(histogram_timer) = histogram_timer {
                 histogram_timer.observe_duration();
             };
+            if let Some((method, path)) = route {
+                crate::metrics::MEILISEARCH_HTTP_RESPONSES_TOTAL
+                    .with_label_values(&[&method, &path, res.status().as_str()])
+                    .inc();
+            }
             Ok(res)
         })
     }
 }
+
+/// Records the authenticated requests made to the routes modifying the instance in the audit log.
+///
+/// The requests are recorded once answered, even when they failed, but the ones rejected
+/// before being authenticated aren't.
+pub struct AuditLogger;
+
+impl<S, B> Transform<S, ServiceRequest> for AuditLogger
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = AuditLoggerMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(AuditLoggerMiddleware { service }))
+    }
+}
+
+pub struct AuditLoggerMiddleware<S> {
+    service: S,
+}
+
+impl<S, B> Service<ServiceRequest> for AuditLoggerMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let fut = self.service.call(req);
+
+        Box::pin(async move {
+            let res = fut.await?;
+
+            let request = res.request();
+            // the extractors authenticate the request and tell if it modifies the instance.
+            let authenticated = request.extensions().get::<Authenticated>().copied();
+            if let Some(Authenticated { key_uid, write: true }) = authenticated {
+                // calling unwrap here is safe because the auth controller is added to app data while creating actix app.
+                let auth = request.app_data::<Data<AuthController>>().unwrap();
+                if auth.is_audit_log_enabled() && request.method() != Method::GET {
+                    let entry = audit_entry(request, key_uid, res.status().as_u16());
+                    if let Err(e) = auth.audit(entry) {
+                        tracing::error!("Could not write to the audit log: {e}");
+                    }
+                }
+            }
+
+            Ok(res)
+        })
+    }
+}
+
+fn audit_entry(request: &HttpRequest, key_uid: Option<Uuid>, status: u16) -> AuditEntry {
+    let header =
+        |name: HeaderName| request.headers().get(name).and_then(|value| value.to_str().ok());
+    AuditEntry {
+        at: OffsetDateTime::now_utc(),
+        key_uid,
+        client_ip: client_ip(request),
+        method: request.method().to_string(),
+        path: request.path().to_string(),
+        index_uid: request.match_info().get("index_uid").map(String::from),
+        payload: PayloadSummary {
+            content_type: header(CONTENT_TYPE).map(String::from),
+            size: header(CONTENT_LENGTH).and_then(|length| length.parse().ok()),
+        },
+        status,
+    }
+}
+
+const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// The id identifying a request in the logs, given by the client or generated.
+#[derive(Debug, Clone)]
+pub struct RequestId {
+    pub id: String,
+    /// Whether the client gave the id in its `X-Request-Id` header.
+    given: bool,
+}
+
+impl RequestId {
+    /// Returns the id of the request, generating it the first time if the client didn't give a valid one.
+    pub fn of(req: &ServiceRequest) -> RequestId {
+        if let Some(request_id) = req.extensions().get::<RequestId>() {
+            return request_id.clone();
+        }
+        let given =
+            req.headers().get(REQUEST_ID_HEADER).and_then(|value| value.to_str().ok()).filter(
+                |id| !id.is_empty() && id.len() <= 128 && id.bytes().all(|b| b.is_ascii_graphic()),
+            );
+        let request_id = match given {
+            Some(id) => RequestId { id: id.to_string(), given: true },
+            None => RequestId { id: Uuid::new_v4().to_string(), given: false },
+        };
+        req.extensions_mut().insert(request_id.clone());
+        request_id
+    }
+}
+
+/// Sends back the id of the request in the `X-Request-Id` header of the response.
+///
+/// When the client gave the id, it's also added to the body of the errors.
+pub struct PropagateRequestId;
+
+impl<S, B> Transform<S, ServiceRequest> for PropagateRequestId
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = PropagateRequestIdMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(PropagateRequestIdMiddleware { service }))
+    }
+}
+
+pub struct PropagateRequestIdMiddleware<S> {
+    service: S,
+}
+
+impl<S, B> Service<ServiceRequest> for PropagateRequestIdMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let request_id = RequestId::of(&req);
+        let fut = self.service.call(req);
+
+        Box::pin(async move {
+            let mut res = fut.await?;
+            // the id is made of visible ASCII characters only, it's always a valid header value.
+            if let Ok(value) = HeaderValue::from_str(&request_id.id) {
+                res.headers_mut().insert(REQUEST_ID_HEADER, value);
+            }
+
+            let error = res.response().error().and_then(|error| error.as_error::<ResponseError>());
+            match error {
+                Some(error) if request_id.given => {
+                    let error = error.clone().with_request_id(request_id.id);
+                    let headers = res.headers().clone();
+                    let mut response = HttpResponse::from_error(error);
+                    *response.headers_mut() = headers;
+                    Ok(res.into_response(response).map_into_right_body())
+                }
+                _ => Ok(res.map_into_left_body()),
+            }
+        })
+    }
+}
+
+/// Prevents the responses smaller than the minimum size from being compressed, compressing them
+/// costs more than the bandwidth it saves.
+///
+/// Must be wrapped by the `Compress` middleware, which doesn't encode the responses that already
+/// have a `Content-Encoding` header.
+pub struct CompressionThreshold {
+    min_size: u64,
+}
+
+impl CompressionThreshold {
+    pub fn new(min_size: u64) -> Self {
+        Self { min_size }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for CompressionThreshold
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = CompressionThresholdMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(CompressionThresholdMiddleware { service, min_size: self.min_size }))
+    }
+}
+
+pub struct CompressionThresholdMiddleware<S> {
+    service: S,
+    min_size: u64,
+}
+
+impl<S, B> Service<ServiceRequest> for CompressionThresholdMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let min_size = self.min_size;
+        let fut = self.service.call(req);
+
+        Box::pin(async move {
+            let mut res = fut.await?;
+            // the streamed responses, like the document exports, are always compressed.
+            let too_small = match res.response().body().size() {
+                BodySize::Sized(size) => size < min_size,
+                BodySize::None | BodySize::Stream => false,
+            };
+            if too_small && !res.headers().contains_key(CONTENT_ENCODING) {
+                res.headers_mut()
+                    .insert(CONTENT_ENCODING, ContentEncoding::Identity.to_header_value());
+            }
+            Ok(res)
+        })
+    }
+}
+
+/// Rewrites the search responses of the indexes having a WASM middleware.
+///
+/// Only the JSON bodies of the successful responses are rewritten. The requests are rewritten by
+/// the search route itself, once they're authenticated and rate limited.
+pub struct RewriteSearch;
+
+impl<S, B> Transform<S, ServiceRequest> for RewriteSearch
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = RewriteSearchMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RewriteSearchMiddleware { service: Rc::new(service) }))
+    }
+}
+
+pub struct RewriteSearchMiddleware<S> {
+    // the request body is rewritten before calling the service, it must be moved in the future.
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for RewriteSearchMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        // calling unwrap here is safe because the WASM middlewares are added to app data while creating actix app.
+        let wasm = req.app_data::<Data<WasmMiddlewares>>().unwrap().clone();
+        let index_uid = searched_index(req.path())
+            .filter(|index_uid| wasm.has_hook(index_uid, Hook::Response))
+            .map(String::from);
+        let Some(index_uid) = index_uid else {
+            return Box::pin(async move { Ok(service.call(req).await?.map_into_left_body()) });
+        };
+
+        Box::pin(async move {
+            let headers = req.headers().clone();
+
+            let res = service.call(req).await?;
+            if !res.status().is_success() {
+                return Ok(res.map_into_left_body());
+            }
+
+            let (req, res) = res.into_parts();
+            let (res, body) = res.into_parts();
+            let body = actix_web::body::to_bytes(body).await.map_err(|e| {
+                let e: Box<dyn std::error::Error> = e.into();
+                actix_web::error::ErrorInternalServerError(e.to_string())
+            })?;
+            // the responses which aren't JSON, like the CSV exports, are not rewritten.
+            let body = match serde_json::from_slice(&body) {
+                Ok(json) => {
+                    Bytes::from(rewrite(&wasm, &index_uid, Hook::Response, &headers, json).await?)
+                }
+                Err(_) => body,
+            };
+            let res = ServiceResponse::new(req, res.set_body(body));
+            Ok(res.map_into_boxed_body().map_into_right_body())
+        })
+    }
+}
+
+/// Returns the uid of the index searched by the request, if any.
+fn searched_index(path: &str) -> Option<&str> {
+    let index_uid = path.strip_prefix("/indexes/")?.strip_suffix("/search")?;
+    (!index_uid.contains('/')).then_some(index_uid)
+}
+
+/// Runs the WASM middleware outside of the HTTP workers, it can use a lot of CPU.
+async fn rewrite(
+    wasm: &Data<WasmMiddlewares>,
+    index_uid: &str,
+    hook: Hook,
+    headers: &HeaderMap,
+    body: serde_json::Value,
+) -> Result<Vec<u8>, Error> {
+    let (wasm, index_uid, headers) = (wasm.clone(), index_uid.to_string(), headers.clone());
+    let output = actix_web::web::block(move || wasm.rewrite(&index_uid, hook, &headers, body));
+    Ok(output.await??)
+}
@@ -2,7 +2,8 @@ use std::env::VarError;
 use std::ffi::OsStr;
 use std::fmt::Display;
 use std::io::{BufReader, Read};
-use std::num::ParseIntError;
+use std::net::IpAddr;
+use std::num::{NonZeroUsize, ParseIntError};
 use std::ops::Deref;
 use std::path::PathBuf;
 use std::str::FromStr;
@@ -12,6 +13,7 @@ use std::{env, fmt, fs};
 use byte_unit::{Byte, ByteError};
 use clap::Parser;
 use meilisearch_types::features::InstanceTogglableFeatures;
+use meilisearch_types::ip_network::{IpNetwork, IpNetworkFormatError};
 use meilisearch_types::milli::update::IndexerConfig;
 use rustls::server::{
     AllowAnyAnonymousOrAuthenticatedClient, AllowAnyAuthenticatedClient, ServerSessionMemoryCache,
@@ -22,17 +24,39 @@ use serde::{Deserialize, Serialize};
 use sysinfo::{MemoryRefreshKind, RefreshKind, System};
 use url::Url;
 
+use crate::proxy::ProxyBackends;
+
 const POSSIBLE_ENV: [&str; 2] = ["development", "production"];
 
 const MEILI_DB_PATH: &str = "MEILI_DB_PATH";
 const MEILI_HTTP_ADDR: &str = "MEILI_HTTP_ADDR";
+const MEILI_HTTP_UNIX_SOCKET: &str = "MEILI_HTTP_UNIX_SOCKET";
+const MEILI_HTTP_UNIX_SOCKET_ONLY: &str = "MEILI_HTTP_UNIX_SOCKET_ONLY";
 const MEILI_MASTER_KEY: &str = "MEILI_MASTER_KEY";
 const MEILI_ENV: &str = "MEILI_ENV";
 const MEILI_TASK_WEBHOOK_URL: &str = "MEILI_TASK_WEBHOOK_URL";
 const MEILI_TASK_WEBHOOK_AUTHORIZATION_HEADER: &str = "MEILI_TASK_WEBHOOK_AUTHORIZATION_HEADER";
+const MEILI_TASK_RETENTION_MAX_COUNT: &str = "MEILI_TASK_RETENTION_MAX_COUNT";
+const MEILI_TASK_RETENTION_MAX_AGE_SEC: &str = "MEILI_TASK_RETENTION_MAX_AGE_SEC";
+const MEILI_TASK_RETRY_PAYLOAD_RETENTION_SEC: &str = "MEILI_TASK_RETRY_PAYLOAD_RETENTION_SEC";
+const MEILI_INDEX_ALERT_SIZE_THRESHOLD: &str = "MEILI_INDEX_ALERT_SIZE_THRESHOLD";
+const MEILI_INDEX_ALERT_DOCUMENTS_THRESHOLD: &str = "MEILI_INDEX_ALERT_DOCUMENTS_THRESHOLD";
+const MEILI_INDEX_ALERT_WEBHOOK_URL: &str = "MEILI_INDEX_ALERT_WEBHOOK_URL";
+const MEILI_AUDIT_LOG: &str = "MEILI_AUDIT_LOG";
+const MEILI_AUDIT_LOG_MAX_FILE_SIZE: &str = "MEILI_AUDIT_LOG_MAX_FILE_SIZE";
+const MEILI_QUERY_LOG: &str = "MEILI_QUERY_LOG";
+const MEILI_QUERY_LOG_SAMPLE_RATE: &str = "MEILI_QUERY_LOG_SAMPLE_RATE";
+const MEILI_QUERY_LOG_MAX_FILE_SIZE: &str = "MEILI_QUERY_LOG_MAX_FILE_SIZE";
+const MEILI_QUERY_LOG_DIAGNOSTICS_SAMPLE_RATE: &str = "MEILI_QUERY_LOG_DIAGNOSTICS_SAMPLE_RATE";
+const MEILI_SHUTDOWN_TIMEOUT_SEC: &str = "MEILI_SHUTDOWN_TIMEOUT_SEC";
+const MEILI_MAX_CONCURRENT_SEARCHES: &str = "MEILI_MAX_CONCURRENT_SEARCHES";
+const MEILI_SEARCH_QUEUE_SIZE: &str = "MEILI_SEARCH_QUEUE_SIZE";
+const MEILI_SEARCH_QUEUE_TIMEOUT_MS: &str = "MEILI_SEARCH_QUEUE_TIMEOUT_MS";
+const MEILI_SEARCH_CUTOFF_MS: &str = "MEILI_SEARCH_CUTOFF_MS";
 #[cfg(feature = "analytics")]
 const MEILI_NO_ANALYTICS: &str = "MEILI_NO_ANALYTICS";
 const MEILI_HTTP_PAYLOAD_SIZE_LIMIT: &str = "MEILI_HTTP_PAYLOAD_SIZE_LIMIT";
+const MEILI_HTTP_COMPRESSION_MIN_SIZE: &str = "MEILI_HTTP_COMPRESSION_MIN_SIZE";
 const MEILI_SSL_CERT_PATH: &str = "MEILI_SSL_CERT_PATH";
 const MEILI_SSL_KEY_PATH: &str = "MEILI_SSL_KEY_PATH";
 const MEILI_SSL_AUTH_PATH: &str = "MEILI_SSL_AUTH_PATH";
@@ -40,11 +64,20 @@ const MEILI_SSL_OCSP_PATH: &str = "MEILI_SSL_OCSP_PATH";
 const MEILI_SSL_REQUIRE_AUTH: &str = "MEILI_SSL_REQUIRE_AUTH";
 const MEILI_SSL_RESUMPTION: &str = "MEILI_SSL_RESUMPTION";
 const MEILI_SSL_TICKETS: &str = "MEILI_SSL_TICKETS";
+const MEILI_SSL_CLIENT_IDENTITIES_PATH: &str = "MEILI_SSL_CLIENT_IDENTITIES_PATH";
 const MEILI_IMPORT_SNAPSHOT: &str = "MEILI_IMPORT_SNAPSHOT";
 const MEILI_IGNORE_MISSING_SNAPSHOT: &str = "MEILI_IGNORE_MISSING_SNAPSHOT";
 const MEILI_IGNORE_SNAPSHOT_IF_DB_EXISTS: &str = "MEILI_IGNORE_SNAPSHOT_IF_DB_EXISTS";
 const MEILI_SNAPSHOT_DIR: &str = "MEILI_SNAPSHOT_DIR";
 const MEILI_SCHEDULE_SNAPSHOT: &str = "MEILI_SCHEDULE_SNAPSHOT";
+const MEILI_SNAPSHOT_RETENTION: &str = "MEILI_SNAPSHOT_RETENTION";
+const MEILI_SNAPSHOT_INCREMENTAL: &str = "MEILI_SNAPSHOT_INCREMENTAL";
+const MEILI_SNAPSHOT_S3_ENDPOINT: &str = "MEILI_SNAPSHOT_S3_ENDPOINT";
+const MEILI_SNAPSHOT_S3_BUCKET: &str = "MEILI_SNAPSHOT_S3_BUCKET";
+const MEILI_SNAPSHOT_S3_REGION: &str = "MEILI_SNAPSHOT_S3_REGION";
+const MEILI_SNAPSHOT_S3_PREFIX: &str = "MEILI_SNAPSHOT_S3_PREFIX";
+const MEILI_SNAPSHOT_S3_ACCESS_KEY: &str = "MEILI_SNAPSHOT_S3_ACCESS_KEY";
+const MEILI_SNAPSHOT_S3_SECRET_KEY: &str = "MEILI_SNAPSHOT_S3_SECRET_KEY";
 const MEILI_IMPORT_DUMP: &str = "MEILI_IMPORT_DUMP";
 const MEILI_IGNORE_MISSING_DUMP: &str = "MEILI_IGNORE_MISSING_DUMP";
 const MEILI_IGNORE_DUMP_IF_DB_EXISTS: &str = "MEILI_IGNORE_DUMP_IF_DB_EXISTS";
@@ -58,20 +91,50 @@ const MEILI_EXPERIMENTAL_REDUCE_INDEXING_MEMORY_USAGE: &str =
     "MEILI_EXPERIMENTAL_REDUCE_INDEXING_MEMORY_USAGE";
 const MEILI_EXPERIMENTAL_MAX_NUMBER_OF_BATCHED_TASKS: &str =
     "MEILI_EXPERIMENTAL_MAX_NUMBER_OF_BATCHED_TASKS";
+const MEILI_EXPERIMENTAL_MAX_BATCHED_PAYLOAD_SIZE: &str =
+    "MEILI_EXPERIMENTAL_MAX_BATCHED_PAYLOAD_SIZE";
+const MEILI_EXPERIMENTAL_DISABLE_AUTOBATCHING: &str = "MEILI_EXPERIMENTAL_DISABLE_AUTOBATCHING";
+const MEILI_EXPERIMENTAL_MAX_CONCURRENT_BATCHES: &str = "MEILI_EXPERIMENTAL_MAX_CONCURRENT_BATCHES";
+const MEILI_EXPERIMENTAL_MAX_FILTER_DEPTH: &str = "MEILI_EXPERIMENTAL_MAX_FILTER_DEPTH";
+const MEILI_EXPERIMENTAL_MAX_FILTER_COMPLEXITY: &str = "MEILI_EXPERIMENTAL_MAX_FILTER_COMPLEXITY";
+const MEILI_EXPERIMENTAL_PROXY_BACKENDS: &str = "MEILI_EXPERIMENTAL_PROXY_BACKENDS";
+const MEILI_EXPERIMENTAL_TRUSTED_PROXIES: &str = "MEILI_EXPERIMENTAL_TRUSTED_PROXIES";
+const MEILI_EXPERIMENTAL_OTLP_ENDPOINT: &str = "MEILI_EXPERIMENTAL_OTLP_ENDPOINT";
+const MEILI_EXPERIMENTAL_OTLP_FILTER: &str = "MEILI_EXPERIMENTAL_OTLP_FILTER";
+const MEILI_EXPERIMENTAL_WASM_MIDDLEWARE_DIR: &str = "MEILI_EXPERIMENTAL_WASM_MIDDLEWARE_DIR";
+const MEILI_EXPERIMENTAL_WASM_MIDDLEWARE_FUEL: &str = "MEILI_EXPERIMENTAL_WASM_MIDDLEWARE_FUEL";
+const MEILI_EXPERIMENTAL_WASM_MIDDLEWARE_MAX_MEMORY: &str =
+    "MEILI_EXPERIMENTAL_WASM_MIDDLEWARE_MAX_MEMORY";
 
 const DEFAULT_CONFIG_FILE_PATH: &str = "./config.toml";
 const DEFAULT_DB_PATH: &str = "./data.ms";
 const DEFAULT_HTTP_ADDR: &str = "localhost:7700";
 const DEFAULT_ENV: &str = "development";
 const DEFAULT_HTTP_PAYLOAD_SIZE_LIMIT: &str = "100 MB";
+const DEFAULT_HTTP_COMPRESSION_MIN_SIZE: &str = "1 KB";
+const DEFAULT_AUDIT_LOG_MAX_FILE_SIZE: &str = "100 MB";
+const DEFAULT_QUERY_LOG_SAMPLE_RATE: f64 = 1.0;
+const DEFAULT_QUERY_LOG_MAX_FILE_SIZE: &str = "100 MB";
+const DEFAULT_QUERY_LOG_DIAGNOSTICS_SAMPLE_RATE: f64 = 0.01;
+const DEFAULT_SHUTDOWN_TIMEOUT_SEC: u64 = 30;
+const DEFAULT_TASK_RETRY_PAYLOAD_RETENTION_SEC: u64 = 24 * 60 * 60;
+const DEFAULT_SEARCH_QUEUE_SIZE: usize = 1000;
+const DEFAULT_SEARCH_QUEUE_TIMEOUT_MS: u64 = 10_000;
+const DEFAULT_OTLP_FILTER: &str = "info,search=trace,indexing=trace";
+const DEFAULT_WASM_MIDDLEWARE_FUEL: u64 = 100_000_000;
+const DEFAULT_WASM_MIDDLEWARE_MAX_MEMORY: &str = "64 MiB";
 const DEFAULT_SNAPSHOT_DIR: &str = "snapshots/";
 const DEFAULT_SNAPSHOT_INTERVAL_SEC: u64 = 86400;
 const DEFAULT_SNAPSHOT_INTERVAL_SEC_STR: &str = "86400";
+const DEFAULT_SNAPSHOT_S3_REGION: &str = "us-east-1";
 const DEFAULT_DUMP_DIR: &str = "dumps/";
 
 const MEILI_MAX_INDEXING_MEMORY: &str = "MEILI_MAX_INDEXING_MEMORY";
 const MEILI_MAX_INDEXING_THREADS: &str = "MEILI_MAX_INDEXING_THREADS";
 const DEFAULT_LOG_EVERY_N: usize = 100_000;
+const DEFAULT_MAX_CONCURRENT_BATCHES: usize = 1;
+const DEFAULT_MAX_FILTER_DEPTH: usize = 100;
+const DEFAULT_MAX_FILTER_COMPLEXITY: usize = 10_000;
 
 // Each environment (index and task-db) is taking space in the virtual address space.
 // Ideally, indexes can occupy 2TiB each to avoid having to manually resize them.
@@ -171,7 +234,7 @@ impl FromStr for LogLevel {
     }
 }
 
-#[derive(Debug, Clone, Parser, Deserialize)]
+#[derive(Debug, Clone, Parser, Deserialize, Serialize)]
 #[clap(version, next_display_order = None)]
 #[serde(rename_all = "snake_case", deny_unknown_fields)]
 pub struct Opt {
@@ -185,7 +248,18 @@ pub struct Opt {
     #[serde(default = "default_http_addr")]
     pub http_addr: String,
 
-    /// Sets the instance's master key, automatically protecting all routes except `GET /health`.
+    /// Sets the path of a unix socket Meilisearch will listen on, in addition to the HTTP address.
+    ///
+    /// A file left at this path by a previous run is removed when Meilisearch starts.
+    #[clap(long, env = MEILI_HTTP_UNIX_SOCKET)]
+    pub http_unix_socket: Option<PathBuf>,
+
+    /// Only listens on the unix socket set with `--http-unix-socket`, the HTTP address is not bound.
+    #[serde(default)]
+    #[clap(long, env = MEILI_HTTP_UNIX_SOCKET_ONLY, requires = "http_unix_socket")]
+    pub http_unix_socket_only: bool,
+
+    /// Sets the instance's master key, automatically protecting all routes except the `GET /health` ones.
     #[clap(long, env = MEILI_MASTER_KEY)]
     pub master_key: Option<String>,
 
@@ -202,6 +276,113 @@ pub struct Opt {
     #[clap(long, env = MEILI_TASK_WEBHOOK_AUTHORIZATION_HEADER)]
     pub task_webhook_authorization_header: Option<String>,
 
+    /// Sets the maximum number of finished tasks kept in the task queue. Once exceeded,
+    /// the oldest finished tasks are automatically deleted.
+    ///
+    /// This option has no effect when the replication parameters are enabled.
+    #[clap(long, env = MEILI_TASK_RETENTION_MAX_COUNT)]
+    pub task_retention_max_count: Option<u64>,
+
+    /// Sets the maximum time, in seconds, a finished task is kept in the task queue
+    /// before being automatically deleted.
+    ///
+    /// This option has no effect when the replication parameters are enabled.
+    #[clap(long, env = MEILI_TASK_RETENTION_MAX_AGE_SEC, value_name = "TASK_RETENTION_MAX_AGE_SEC")]
+    pub task_retention_max_age_sec: Option<u64>,
+
+    /// Sets the time, in seconds, the payloads of the canceled tasks are kept after they finished
+    /// so they can be retried. They take as much disk space as the payloads of the tasks canceled
+    /// during this time, 0 deletes them as soon as possible.
+    #[clap(long, env = MEILI_TASK_RETRY_PAYLOAD_RETENTION_SEC, default_value_t = default_task_retry_payload_retention_sec(), value_name = "TASK_RETRY_PAYLOAD_RETENTION_SEC")]
+    #[serde(default = "default_task_retry_payload_retention_sec")]
+    pub task_retry_payload_retention_sec: u64,
+
+    /// Sets the size of an index above which a warning is logged, the index alert webhook is called,
+    /// and the index is flagged in the stats. Value must be given in bytes or explicitly stating a
+    /// base unit (for instance: 107374182400, '107.7Gb', or '107374 Mb').
+    #[clap(long, env = MEILI_INDEX_ALERT_SIZE_THRESHOLD)]
+    #[serde(serialize_with = "serialize_option_to_string")]
+    pub index_alert_size_threshold: Option<Byte>,
+
+    /// Sets the number of documents of an index above which a warning is logged, the index alert
+    /// webhook is called, and the index is flagged in the stats.
+    #[clap(long, env = MEILI_INDEX_ALERT_DOCUMENTS_THRESHOLD)]
+    pub index_alert_documents_threshold: Option<u64>,
+
+    /// Called whenever an index starts exceeding one of the index alert thresholds.
+    #[clap(long, env = MEILI_INDEX_ALERT_WEBHOOK_URL)]
+    pub index_alert_webhook_url: Option<Url>,
+
+    /// Records every authenticated request modifying the instance in an audit log
+    /// stored in the `audit-log` directory of the database.
+    #[clap(long, env = MEILI_AUDIT_LOG)]
+    #[serde(default)]
+    pub audit_log: bool,
+
+    /// Sets the size over which the audit log is rotated, only the 10 most recent logs are kept.
+    /// Value must be given in bytes or explicitly stating a base unit (for instance: 104857600,
+    /// '100Mb', or '100 MiB').
+    #[clap(long, env = MEILI_AUDIT_LOG_MAX_FILE_SIZE, default_value_t = default_audit_log_max_file_size())]
+    #[serde(default = "default_audit_log_max_file_size", serialize_with = "serialize_to_string")]
+    pub audit_log_max_file_size: Byte,
+
+    /// Records the searches, with their anonymized query, their parameters, latency and number of hits,
+    /// in a query log stored in the `query-log` directory of the database. The search analytics of
+    /// the indexes are computed from it.
+    #[clap(long, env = MEILI_QUERY_LOG)]
+    #[serde(default)]
+    pub query_log: bool,
+
+    /// Sets the ratio of the searches recorded in the query log, between 0 and 1.
+    #[clap(long, env = MEILI_QUERY_LOG_SAMPLE_RATE, default_value_t = default_query_log_sample_rate())]
+    #[serde(default = "default_query_log_sample_rate")]
+    pub query_log_sample_rate: f64,
+
+    /// Sets the size over which the query log is rotated, only the 10 most recent logs are kept.
+    /// Value must be given in bytes or explicitly stating a base unit (for instance: 104857600,
+    /// '100Mb', or '100 MiB').
+    #[clap(long, env = MEILI_QUERY_LOG_MAX_FILE_SIZE, default_value_t = default_query_log_max_file_size())]
+    #[serde(default = "default_query_log_max_file_size", serialize_with = "serialize_to_string")]
+    pub query_log_max_file_size: Byte,
+
+    /// Sets the ratio of the searches recorded in the query log that also record, for each ranking
+    /// rule, the number of buckets it computed, the number of documents in them and the time it took.
+    #[clap(long, env = MEILI_QUERY_LOG_DIAGNOSTICS_SAMPLE_RATE, default_value_t = default_query_log_diagnostics_sample_rate())]
+    #[serde(default = "default_query_log_diagnostics_sample_rate")]
+    pub query_log_diagnostics_sample_rate: f64,
+
+    /// Sets the maximum time, in seconds, the instance waits on `SIGTERM` for the requests being
+    /// answered to finish, and then as long for the batch being processed. The batch is aborted
+    /// once this time is elapsed, and its tasks are processed again on the next launch.
+    #[clap(long, env = MEILI_SHUTDOWN_TIMEOUT_SEC, default_value_t = default_shutdown_timeout_sec(), value_name = "SHUTDOWN_TIMEOUT_SEC")]
+    #[serde(default = "default_shutdown_timeout_sec")]
+    pub shutdown_timeout_sec: u64,
+
+    /// Sets the maximum number of searches executed at the same time, defaults to the number of
+    /// cores of the machine. The other searches wait for their turn in the search queue.
+    #[clap(long, env = MEILI_MAX_CONCURRENT_SEARCHES, default_value_t = default_max_concurrent_searches())]
+    #[serde(default = "default_max_concurrent_searches")]
+    pub max_concurrent_searches: usize,
+
+    /// Sets the maximum number of searches waiting for their turn in the search queue. The searches
+    /// received once the queue is full are rejected with a `too_many_search_requests` error.
+    #[clap(long, env = MEILI_SEARCH_QUEUE_SIZE, default_value_t = default_search_queue_size())]
+    #[serde(default = "default_search_queue_size")]
+    pub search_queue_size: usize,
+
+    /// Sets the maximum time, in milliseconds, a search waits in the search queue before being
+    /// rejected with a `too_many_search_requests` error.
+    #[clap(long, env = MEILI_SEARCH_QUEUE_TIMEOUT_MS, default_value_t = default_search_queue_timeout_ms())]
+    #[serde(default = "default_search_queue_timeout_ms")]
+    pub search_queue_timeout_ms: u64,
+
+    /// Sets the maximum time, in milliseconds, a search spends ranking the documents. Once exceeded,
+    /// the search returns the best documents found so far and is flagged as `degraded`.
+    ///
+    /// The `searchCutoffMs` setting of an index takes precedence over this option.
+    #[clap(long, env = MEILI_SEARCH_CUTOFF_MS)]
+    pub search_cutoff_ms: Option<u64>,
+
     /// Deactivates Meilisearch's built-in telemetry when provided.
     ///
     /// Meilisearch automatically collects data from all instances that do not opt out using this flag.
@@ -226,9 +407,16 @@ pub struct Opt {
     /// Sets the maximum size of accepted payloads. Value must be given in bytes or explicitly stating a
     /// base unit (for instance: 107374182400, '107.7Gb', or '107374 Mb').
     #[clap(long, env = MEILI_HTTP_PAYLOAD_SIZE_LIMIT, default_value_t = default_http_payload_size_limit())]
-    #[serde(default = "default_http_payload_size_limit")]
+    #[serde(default = "default_http_payload_size_limit", serialize_with = "serialize_to_string")]
     pub http_payload_size_limit: Byte,
 
+    /// Sets the minimum size of the responses compressed with gzip or brotli when the client sends
+    /// an `Accept-Encoding` header, the smaller responses are sent uncompressed. Value must be given
+    /// in bytes or explicitly stating a base unit (for instance: 1024, '1Kb', or '1 KiB').
+    #[clap(long, env = MEILI_HTTP_COMPRESSION_MIN_SIZE, default_value_t = default_http_compression_min_size())]
+    #[serde(default = "default_http_compression_min_size", serialize_with = "serialize_to_string")]
+    pub http_compression_min_size: Byte,
+
     /// Sets the server's SSL certificates.
     #[clap(long, env = MEILI_SSL_CERT_PATH, value_parser)]
     pub ssl_cert_path: Option<PathBuf>,
@@ -262,6 +450,14 @@ pub struct Opt {
     #[clap(long, env = MEILI_SSL_TICKETS)]
     pub ssl_tickets: bool,
 
+    /// Authenticates the clients with their certificate, verified with `--ssl-auth-path`.
+    ///
+    /// The file is a JSON object mapping the common names of the client certificates to the uid
+    /// of the API key whose permissions they're granted. The requests made with an
+    /// `Authorization` header keep using its token.
+    #[clap(long, env = MEILI_SSL_CLIENT_IDENTITIES_PATH, requires = "ssl_auth_path")]
+    pub ssl_client_identities_path: Option<PathBuf>,
+
     /// Launches Meilisearch after importing a previously-generated snapshot at the given filepath.
     #[clap(long, env = MEILI_IMPORT_SNAPSHOT)]
     pub import_snapshot: Option<PathBuf>,
@@ -300,9 +496,69 @@ pub struct Opt {
     ///
     /// When provided with a value, defines the interval between each snapshot, in seconds.
     #[clap(long,env = MEILI_SCHEDULE_SNAPSHOT, num_args(0..=1), value_parser=parse_schedule_snapshot, default_value_t, default_missing_value=default_snapshot_interval_sec(),  value_name = "SNAPSHOT_INTERVAL_SEC")]
-    #[serde(default, deserialize_with = "schedule_snapshot_deserialize")]
+    #[serde(
+        default,
+        deserialize_with = "schedule_snapshot_deserialize",
+        serialize_with = "schedule_snapshot_serialize"
+    )]
     pub schedule_snapshot: ScheduleSnapshot,
 
+    /// Sets the maximum number of snapshots kept in the snapshot directory. Once exceeded,
+    /// the oldest snapshots are automatically deleted.
+    ///
+    /// When provided, the snapshots are named after their creation date instead of overwriting
+    /// a single `.snapshot` file.
+    #[clap(long, env = MEILI_SNAPSHOT_RETENTION)]
+    pub snapshot_retention: Option<NonZeroUsize>,
+
+    /// Only copies the indexes updated since the previous snapshot in the new snapshots, the other
+    /// indexes are restored from the previous snapshots of the snapshot directory.
+    ///
+    /// The snapshots are only incremental per index: an updated index is copied entirely, however
+    /// small the update, and the tasks and the API keys are always copied. It only saves space
+    /// when most of the indexes aren't updated between two snapshots.
+    ///
+    /// The snapshots are named after their creation date and the ones still needed to restore
+    /// the most recent snapshots are never deleted by `--snapshot-retention`.
+    #[clap(long, env = MEILI_SNAPSHOT_INCREMENTAL)]
+    #[serde(default)]
+    pub snapshot_incremental: bool,
+
+    /// Uploads every snapshot to the S3-compatible object storage at this URL once created,
+    /// e.g. `https://s3.eu-west-3.amazonaws.com`.
+    ///
+    /// The snapshots are still written in the snapshot directory. With `--snapshot-incremental`,
+    /// the previous snapshots an incremental snapshot relies on are uploaded too if the bucket
+    /// doesn't have them yet.
+    #[clap(
+        long,
+        env = MEILI_SNAPSHOT_S3_ENDPOINT,
+        requires_all = ["snapshot_s3_bucket", "snapshot_s3_access_key", "snapshot_s3_secret_key"]
+    )]
+    pub snapshot_s3_endpoint: Option<Url>,
+
+    /// Sets the bucket the snapshots are uploaded to.
+    #[clap(long, env = MEILI_SNAPSHOT_S3_BUCKET, requires = "snapshot_s3_endpoint")]
+    pub snapshot_s3_bucket: Option<String>,
+
+    /// Sets the region of the bucket the snapshots are uploaded to.
+    #[clap(long, env = MEILI_SNAPSHOT_S3_REGION, default_value_t = default_snapshot_s3_region())]
+    #[serde(default = "default_snapshot_s3_region")]
+    pub snapshot_s3_region: String,
+
+    /// Sets the prefix of the keys of the uploaded snapshots, e.g. `backups/`.
+    #[clap(long, env = MEILI_SNAPSHOT_S3_PREFIX, default_value_t)]
+    #[serde(default)]
+    pub snapshot_s3_prefix: String,
+
+    /// Sets the access key used to upload the snapshots.
+    #[clap(long, env = MEILI_SNAPSHOT_S3_ACCESS_KEY, requires = "snapshot_s3_endpoint")]
+    pub snapshot_s3_access_key: Option<String>,
+
+    /// Sets the secret key used to upload the snapshots.
+    #[clap(long, env = MEILI_SNAPSHOT_S3_SECRET_KEY, requires = "snapshot_s3_endpoint")]
+    pub snapshot_s3_secret_key: Option<String>,
+
     /// Imports the dump file located at the specified path. Path must point to a `.dump` file.
     /// If a database already exists, Meilisearch will throw an error and abort launch.
     #[clap(long, env = MEILI_IMPORT_DUMP, conflicts_with = "import_snapshot")]
@@ -378,13 +634,92 @@ pub struct Opt {
     #[serde(default = "default_limit_batched_tasks")]
     pub experimental_max_number_of_batched_tasks: usize,
 
+    /// Experimentally limits the total size of the payloads of the tasks that will be processed at once.
+    /// Value must be given in bytes or explicitly stating a base unit (for instance: 104857600, '100Mb', or '100 MiB').
+    ///
+    /// A batch always contains at least one task, even if its payload alone exceeds this limit.
+    #[clap(long, env = MEILI_EXPERIMENTAL_MAX_BATCHED_PAYLOAD_SIZE)]
+    #[serde(serialize_with = "serialize_option_to_string")]
+    pub experimental_max_batched_payload_size: Option<Byte>,
+
+    /// Experimentally disables the autobatching, every task is processed in its own batch.
+    #[clap(long, env = MEILI_EXPERIMENTAL_DISABLE_AUTOBATCHING)]
+    #[serde(default)]
+    pub experimental_disable_autobatching: bool,
+
+    /// Experimentally sets the maximum number of batches that can be processed at the same time.
+    /// Only the batches working on different indexes run concurrently, and they share the indexing memory.
+    #[clap(long, env = MEILI_EXPERIMENTAL_MAX_CONCURRENT_BATCHES, default_value_t = default_max_concurrent_batches())]
+    #[serde(default = "default_max_concurrent_batches")]
+    pub experimental_max_concurrent_batches: usize,
+
+    /// Experimentally sets the maximum number of nested `AND` and `OR` levels a filter can have.
+    /// The search and documents requests using a deeper filter are rejected with a `filter_too_complex` error.
+    /// It can't be above 100, the deepest filter expressions the filter parser accepts.
+    #[clap(long, env = MEILI_EXPERIMENTAL_MAX_FILTER_DEPTH, default_value_t = default_max_filter_depth())]
+    #[serde(default = "default_max_filter_depth")]
+    pub experimental_max_filter_depth: usize,
+
+    /// Experimentally sets the maximum number of conditions a filter can be made of, every value of an `IN` counts as a condition.
+    /// The search and documents requests using a bigger filter are rejected with a `filter_too_complex` error.
+    #[clap(long, env = MEILI_EXPERIMENTAL_MAX_FILTER_COMPLEXITY, default_value_t = default_max_filter_complexity())]
+    #[serde(default = "default_max_filter_complexity")]
+    pub experimental_max_filter_complexity: usize,
+
+    /// Experimental proxy mode: the instance holds no data and routes the requests on an index to the
+    /// first backend whose pattern matches its uid, while merging the `/indexes`, `/stats` and `/tasks`
+    /// views of every backend. Given as a comma-separated list of `pattern=url`, for instance
+    /// `movies*=http://10.0.0.1:7700,*=http://10.0.0.2:7700`.
+    #[clap(long, env = MEILI_EXPERIMENTAL_PROXY_BACKENDS, default_value_t)]
+    #[serde(default, serialize_with = "serialize_to_string")]
+    pub experimental_proxy_backends: ProxyBackends,
+
+    /// Experimentally sets the networks of the reverse proxies whose `Forwarded` and `X-Forwarded-For`
+    /// headers are trusted to find the address of the clients, given as a comma-separated list, for
+    /// instance `10.0.0.0/8,::1`. The headers sent by any other peer are ignored.
+    #[clap(long, env = MEILI_EXPERIMENTAL_TRUSTED_PROXIES, default_value_t)]
+    #[serde(default, serialize_with = "serialize_to_string")]
+    pub experimental_trusted_proxies: TrustedProxies,
+
+    /// Experimentally exports the tracing spans, such as the steps of the searches and of the batches,
+    /// to the OpenTelemetry collector listening for OTLP over gRPC at this URL, for instance `http://localhost:4317`.
+    /// The standard `OTEL_EXPORTER_OTLP_*` environment variables can be used to configure the exporter further.
+    #[clap(long, env = MEILI_EXPERIMENTAL_OTLP_ENDPOINT)]
+    pub experimental_otlp_endpoint: Option<Url>,
+
+    /// Experimentally selects the spans exported to the OpenTelemetry collector, given as a comma-separated
+    /// list of `target=level` directives, for instance `info,search=trace`.
+    #[clap(long, env = MEILI_EXPERIMENTAL_OTLP_FILTER, default_value_t = default_otlp_filter())]
+    #[serde(default = "default_otlp_filter")]
+    pub experimental_otlp_filter: String,
+
+    /// Experimentally loads the WASM modules of this directory to rewrite the search requests and
+    /// responses of the indexes, the module of an index being named after its uid, for instance `movies.wasm`.
+    #[clap(long, env = MEILI_EXPERIMENTAL_WASM_MIDDLEWARE_DIR)]
+    pub experimental_wasm_middleware_dir: Option<PathBuf>,
+
+    /// Experimentally sets the fuel, the number of WASM instructions roughly, a WASM middleware can
+    /// consume to rewrite a request or a response before being interrupted.
+    #[clap(long, env = MEILI_EXPERIMENTAL_WASM_MIDDLEWARE_FUEL, default_value_t = default_wasm_middleware_fuel())]
+    #[serde(default = "default_wasm_middleware_fuel")]
+    pub experimental_wasm_middleware_fuel: u64,
+
+    /// Experimentally sets the maximum memory a WASM middleware can use to rewrite a request or a response.
+    #[clap(long, env = MEILI_EXPERIMENTAL_WASM_MIDDLEWARE_MAX_MEMORY, default_value_t = default_wasm_middleware_max_memory())]
+    #[serde(
+        default = "default_wasm_middleware_max_memory",
+        serialize_with = "serialize_to_string"
+    )]
+    pub experimental_wasm_middleware_max_memory: Byte,
+
     #[serde(flatten)]
     #[clap(flatten)]
     pub indexer_options: IndexerOpts,
 
     /// Set the path to a configuration file that should be used to setup the engine.
-    /// Format must be TOML.
+    /// Format must be TOML, or YAML when the file ends with `.yaml` or `.yml`.
     #[clap(long)]
+    #[serde(skip_serializing)]
     pub config_file_path: Option<PathBuf>,
 }
 
@@ -410,8 +745,14 @@ impl Opt {
 
         match std::fs::read_to_string(&config_file_path) {
             Ok(config) => {
-                // If the file is successfully read, we deserialize it with `toml`.
-                let opt_from_config = toml::from_str::<Opt>(&config)?;
+                // If the file is successfully read, we deserialize it with `toml`, or `serde_yaml`
+                // when it's a YAML file.
+                let opt_from_config = match config_file_path.extension() {
+                    Some(ext) if ext == "yaml" || ext == "yml" => {
+                        serde_yaml::from_str::<Opt>(&config)?
+                    }
+                    _ => toml::from_str::<Opt>(&config)?,
+                };
                 // Return an error if config file contains 'config_file_path'
                 // Using that key in the config file doesn't make sense bc it creates a logical loop (config file referencing itself)
                 if opt_from_config.config_file_path.is_some() {
@@ -443,14 +784,46 @@ impl Opt {
         let Opt {
             db_path,
             http_addr,
+            http_unix_socket,
+            http_unix_socket_only,
             master_key,
             env,
             task_webhook_url,
             task_webhook_authorization_header,
+            task_retention_max_count,
+            task_retention_max_age_sec,
+            task_retry_payload_retention_sec,
+            index_alert_size_threshold,
+            index_alert_documents_threshold,
+            index_alert_webhook_url,
+            audit_log,
+            audit_log_max_file_size,
+            query_log,
+            query_log_sample_rate,
+            query_log_max_file_size,
+            query_log_diagnostics_sample_rate,
+            shutdown_timeout_sec,
+            max_concurrent_searches,
+            search_queue_size,
+            search_queue_timeout_ms,
+            search_cutoff_ms,
             max_index_size: _,
             max_task_db_size: _,
             http_payload_size_limit,
+            http_compression_min_size,
             experimental_max_number_of_batched_tasks,
+            experimental_max_batched_payload_size,
+            experimental_disable_autobatching,
+            experimental_max_concurrent_batches,
+            experimental_max_filter_depth,
+            experimental_max_filter_complexity,
+            experimental_proxy_backends,
+            experimental_trusted_proxies,
+            experimental_otlp_endpoint,
+            experimental_otlp_filter,
+            experimental_wasm_middleware_dir,
+            experimental_wasm_middleware_fuel,
+            experimental_wasm_middleware_max_memory,
             ssl_cert_path,
             ssl_key_path,
             ssl_auth_path,
@@ -458,17 +831,26 @@ impl Opt {
             ssl_require_auth,
             ssl_resumption,
             ssl_tickets,
+            ssl_client_identities_path,
             snapshot_dir,
             schedule_snapshot,
+            snapshot_retention,
+            snapshot_incremental,
+            snapshot_s3_endpoint,
+            snapshot_s3_bucket,
+            snapshot_s3_region,
+            snapshot_s3_prefix,
+            snapshot_s3_access_key,
+            snapshot_s3_secret_key,
             dump_dir,
             log_level,
             indexer_options,
-            import_snapshot: _,
-            ignore_missing_snapshot: _,
-            ignore_snapshot_if_db_exists: _,
-            import_dump: _,
-            ignore_missing_dump: _,
-            ignore_dump_if_db_exists: _,
+            import_snapshot,
+            ignore_missing_snapshot,
+            ignore_snapshot_if_db_exists,
+            import_dump,
+            ignore_missing_dump,
+            ignore_dump_if_db_exists,
             config_file_path: _,
             #[cfg(feature = "analytics")]
             no_analytics,
@@ -480,6 +862,13 @@ impl Opt {
         } = self;
         export_to_env_if_not_present(MEILI_DB_PATH, db_path);
         export_to_env_if_not_present(MEILI_HTTP_ADDR, http_addr);
+        if let Some(http_unix_socket) = http_unix_socket {
+            export_to_env_if_not_present(MEILI_HTTP_UNIX_SOCKET, http_unix_socket);
+        }
+        export_to_env_if_not_present(
+            MEILI_HTTP_UNIX_SOCKET_ONLY,
+            http_unix_socket_only.to_string(),
+        );
         if let Some(master_key) = master_key {
             export_to_env_if_not_present(MEILI_MASTER_KEY, master_key);
         }
@@ -493,6 +882,71 @@ impl Opt {
                 task_webhook_authorization_header,
             );
         }
+        if let Some(task_retention_max_count) = task_retention_max_count {
+            export_to_env_if_not_present(
+                MEILI_TASK_RETENTION_MAX_COUNT,
+                task_retention_max_count.to_string(),
+            );
+        }
+        if let Some(task_retention_max_age_sec) = task_retention_max_age_sec {
+            export_to_env_if_not_present(
+                MEILI_TASK_RETENTION_MAX_AGE_SEC,
+                task_retention_max_age_sec.to_string(),
+            );
+        }
+        export_to_env_if_not_present(
+            MEILI_TASK_RETRY_PAYLOAD_RETENTION_SEC,
+            task_retry_payload_retention_sec.to_string(),
+        );
+        if let Some(index_alert_size_threshold) = index_alert_size_threshold {
+            export_to_env_if_not_present(
+                MEILI_INDEX_ALERT_SIZE_THRESHOLD,
+                index_alert_size_threshold.to_string(),
+            );
+        }
+        if let Some(index_alert_documents_threshold) = index_alert_documents_threshold {
+            export_to_env_if_not_present(
+                MEILI_INDEX_ALERT_DOCUMENTS_THRESHOLD,
+                index_alert_documents_threshold.to_string(),
+            );
+        }
+        if let Some(index_alert_webhook_url) = index_alert_webhook_url {
+            export_to_env_if_not_present(
+                MEILI_INDEX_ALERT_WEBHOOK_URL,
+                index_alert_webhook_url.to_string(),
+            );
+        }
+        export_to_env_if_not_present(MEILI_AUDIT_LOG, audit_log.to_string());
+        export_to_env_if_not_present(
+            MEILI_AUDIT_LOG_MAX_FILE_SIZE,
+            audit_log_max_file_size.to_string(),
+        );
+        export_to_env_if_not_present(MEILI_QUERY_LOG, query_log.to_string());
+        export_to_env_if_not_present(
+            MEILI_QUERY_LOG_SAMPLE_RATE,
+            query_log_sample_rate.to_string(),
+        );
+        export_to_env_if_not_present(
+            MEILI_QUERY_LOG_MAX_FILE_SIZE,
+            query_log_max_file_size.to_string(),
+        );
+        export_to_env_if_not_present(
+            MEILI_QUERY_LOG_DIAGNOSTICS_SAMPLE_RATE,
+            query_log_diagnostics_sample_rate.to_string(),
+        );
+        export_to_env_if_not_present(MEILI_SHUTDOWN_TIMEOUT_SEC, shutdown_timeout_sec.to_string());
+        export_to_env_if_not_present(
+            MEILI_MAX_CONCURRENT_SEARCHES,
+            max_concurrent_searches.to_string(),
+        );
+        export_to_env_if_not_present(MEILI_SEARCH_QUEUE_SIZE, search_queue_size.to_string());
+        export_to_env_if_not_present(
+            MEILI_SEARCH_QUEUE_TIMEOUT_MS,
+            search_queue_timeout_ms.to_string(),
+        );
+        if let Some(search_cutoff_ms) = search_cutoff_ms {
+            export_to_env_if_not_present(MEILI_SEARCH_CUTOFF_MS, search_cutoff_ms.to_string());
+        }
 
         #[cfg(feature = "analytics")]
         {
@@ -502,10 +956,69 @@ impl Opt {
             MEILI_HTTP_PAYLOAD_SIZE_LIMIT,
             http_payload_size_limit.to_string(),
         );
+        export_to_env_if_not_present(
+            MEILI_HTTP_COMPRESSION_MIN_SIZE,
+            http_compression_min_size.to_string(),
+        );
         export_to_env_if_not_present(
             MEILI_EXPERIMENTAL_MAX_NUMBER_OF_BATCHED_TASKS,
             experimental_max_number_of_batched_tasks.to_string(),
         );
+        if let Some(experimental_max_batched_payload_size) = experimental_max_batched_payload_size {
+            export_to_env_if_not_present(
+                MEILI_EXPERIMENTAL_MAX_BATCHED_PAYLOAD_SIZE,
+                experimental_max_batched_payload_size.to_string(),
+            );
+        }
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_DISABLE_AUTOBATCHING,
+            experimental_disable_autobatching.to_string(),
+        );
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_MAX_CONCURRENT_BATCHES,
+            experimental_max_concurrent_batches.to_string(),
+        );
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_MAX_FILTER_DEPTH,
+            experimental_max_filter_depth.to_string(),
+        );
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_MAX_FILTER_COMPLEXITY,
+            experimental_max_filter_complexity.to_string(),
+        );
+        if !experimental_proxy_backends.is_empty() {
+            export_to_env_if_not_present(
+                MEILI_EXPERIMENTAL_PROXY_BACKENDS,
+                experimental_proxy_backends.to_string(),
+            );
+        }
+        if !experimental_trusted_proxies.is_empty() {
+            export_to_env_if_not_present(
+                MEILI_EXPERIMENTAL_TRUSTED_PROXIES,
+                experimental_trusted_proxies.to_string(),
+            );
+        }
+        if let Some(experimental_otlp_endpoint) = experimental_otlp_endpoint {
+            export_to_env_if_not_present(
+                MEILI_EXPERIMENTAL_OTLP_ENDPOINT,
+                experimental_otlp_endpoint.to_string(),
+            );
+        }
+        export_to_env_if_not_present(MEILI_EXPERIMENTAL_OTLP_FILTER, experimental_otlp_filter);
+        if let Some(experimental_wasm_middleware_dir) = experimental_wasm_middleware_dir {
+            export_to_env_if_not_present(
+                MEILI_EXPERIMENTAL_WASM_MIDDLEWARE_DIR,
+                experimental_wasm_middleware_dir,
+            );
+        }
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_WASM_MIDDLEWARE_FUEL,
+            experimental_wasm_middleware_fuel.to_string(),
+        );
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_WASM_MIDDLEWARE_MAX_MEMORY,
+            experimental_wasm_middleware_max_memory.to_string(),
+        );
         if let Some(ssl_cert_path) = ssl_cert_path {
             export_to_env_if_not_present(MEILI_SSL_CERT_PATH, ssl_cert_path);
         }
@@ -521,11 +1034,57 @@ impl Opt {
         export_to_env_if_not_present(MEILI_SSL_REQUIRE_AUTH, ssl_require_auth.to_string());
         export_to_env_if_not_present(MEILI_SSL_RESUMPTION, ssl_resumption.to_string());
         export_to_env_if_not_present(MEILI_SSL_TICKETS, ssl_tickets.to_string());
+        if let Some(ssl_client_identities_path) = ssl_client_identities_path {
+            export_to_env_if_not_present(
+                MEILI_SSL_CLIENT_IDENTITIES_PATH,
+                ssl_client_identities_path,
+            );
+        }
+        // the `ignore_*` flags require their import path, they're only exported when set.
+        if let Some(import_snapshot) = import_snapshot {
+            export_to_env_if_not_present(MEILI_IMPORT_SNAPSHOT, import_snapshot);
+        }
+        if ignore_missing_snapshot {
+            export_to_env_if_not_present(MEILI_IGNORE_MISSING_SNAPSHOT, "true");
+        }
+        if ignore_snapshot_if_db_exists {
+            export_to_env_if_not_present(MEILI_IGNORE_SNAPSHOT_IF_DB_EXISTS, "true");
+        }
         export_to_env_if_not_present(MEILI_SNAPSHOT_DIR, snapshot_dir);
         if let Some(snapshot_interval) = schedule_snapshot_to_env(schedule_snapshot) {
             export_to_env_if_not_present(MEILI_SCHEDULE_SNAPSHOT, snapshot_interval)
         }
+        if let Some(snapshot_retention) = snapshot_retention {
+            export_to_env_if_not_present(MEILI_SNAPSHOT_RETENTION, snapshot_retention.to_string());
+        }
+        export_to_env_if_not_present(MEILI_SNAPSHOT_INCREMENTAL, snapshot_incremental.to_string());
+        if let Some(snapshot_s3_endpoint) = snapshot_s3_endpoint {
+            export_to_env_if_not_present(
+                MEILI_SNAPSHOT_S3_ENDPOINT,
+                snapshot_s3_endpoint.to_string(),
+            );
+        }
+        if let Some(snapshot_s3_bucket) = snapshot_s3_bucket {
+            export_to_env_if_not_present(MEILI_SNAPSHOT_S3_BUCKET, snapshot_s3_bucket);
+        }
+        export_to_env_if_not_present(MEILI_SNAPSHOT_S3_REGION, snapshot_s3_region);
+        export_to_env_if_not_present(MEILI_SNAPSHOT_S3_PREFIX, snapshot_s3_prefix);
+        if let Some(snapshot_s3_access_key) = snapshot_s3_access_key {
+            export_to_env_if_not_present(MEILI_SNAPSHOT_S3_ACCESS_KEY, snapshot_s3_access_key);
+        }
+        if let Some(snapshot_s3_secret_key) = snapshot_s3_secret_key {
+            export_to_env_if_not_present(MEILI_SNAPSHOT_S3_SECRET_KEY, snapshot_s3_secret_key);
+        }
 
+        if let Some(import_dump) = import_dump {
+            export_to_env_if_not_present(MEILI_IMPORT_DUMP, import_dump);
+        }
+        if ignore_missing_dump {
+            export_to_env_if_not_present(MEILI_IGNORE_MISSING_DUMP, "true");
+        }
+        if ignore_dump_if_db_exists {
+            export_to_env_if_not_present(MEILI_IGNORE_DUMP_IF_DB_EXISTS, "true");
+        }
         export_to_env_if_not_present(MEILI_DUMP_DIR, dump_dir);
         export_to_env_if_not_present(MEILI_LOG_LEVEL, log_level.to_string());
         export_to_env_if_not_present(
@@ -551,6 +1110,24 @@ impl Opt {
         indexer_options.export_to_env();
     }
 
+    /// Returns the configuration the engine runs with, in the TOML format of the configuration
+    /// file, with the secrets redacted.
+    pub fn effective_configuration(&self) -> anyhow::Result<String> {
+        const REDACTED: &str = "[redacted]";
+        let mut opt = self.clone();
+        for secret in [
+            &mut opt.master_key,
+            &mut opt.task_webhook_authorization_header,
+            &mut opt.snapshot_s3_access_key,
+            &mut opt.snapshot_s3_secret_key,
+        ] {
+            if secret.is_some() {
+                *secret = Some(REDACTED.to_string());
+            }
+        }
+        Ok(toml::to_string(&opt)?)
+    }
+
     pub fn get_ssl_config(&self) -> anyhow::Result<Option<rustls::ServerConfig>> {
         if let (Some(cert_path), Some(key_path)) = (&self.ssl_cert_path, &self.ssl_key_path) {
             let config = rustls::ServerConfig::builder().with_safe_defaults();
@@ -605,12 +1182,12 @@ impl Opt {
     }
 }
 
-#[derive(Debug, Default, Clone, Parser, Deserialize)]
+#[derive(Debug, Default, Clone, Parser, Deserialize, Serialize)]
 pub struct IndexerOpts {
     /// Sets the maximum amount of RAM Meilisearch can use when indexing. By default, Meilisearch
-    /// uses no more than two thirds of available memory.
+    /// uses no more than two thirds of available memory, or of the memory limit of its container.
     #[clap(long, env = MEILI_MAX_INDEXING_MEMORY, default_value_t)]
-    #[serde(default)]
+    #[serde(default, serialize_with = "serialize_max_memory")]
     pub max_indexing_memory: MaxMemory,
 
     /// Sets the maximum number of threads Meilisearch can use during indexation. By default, the
@@ -709,17 +1286,73 @@ impl MaxMemory {
 }
 
 /// Returns the total amount of bytes available or `None` if this system isn't supported.
+///
+/// The memory limit of the cgroup of the process, e.g. of its container, is used when it's lower
+/// than the memory of the machine.
 fn total_memory_bytes() -> Option<u64> {
     if sysinfo::IS_SUPPORTED_SYSTEM {
         let memory_kind = RefreshKind::new().with_memory(MemoryRefreshKind::new().with_ram());
         let mut system = System::new_with_specifics(memory_kind);
         system.refresh_memory();
-        Some(system.total_memory())
+        let total_memory = system.total_memory();
+        match system.cgroup_limits() {
+            Some(limits) if limits.total_memory != 0 => Some(total_memory.min(limits.total_memory)),
+            _ => Some(total_memory),
+        }
     } else {
         None
     }
 }
 
+/// The networks of the reverse proxies trusted to forward the address of the clients.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(try_from = "String")]
+pub struct TrustedProxies(Vec<IpNetwork>);
+
+impl TrustedProxies {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns wether the address is one of a trusted proxy.
+    pub fn contains(&self, addr: IpAddr) -> bool {
+        self.0.iter().any(|network| network.contains(addr))
+    }
+}
+
+impl FromStr for TrustedProxies {
+    type Err = IpNetworkFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(str::trim)
+            .filter(|network| !network.is_empty())
+            .map(IpNetwork::from_str)
+            .collect::<Result<_, _>>()
+            .map(TrustedProxies)
+    }
+}
+
+impl TryFrom<String> for TrustedProxies {
+    type Error = IpNetworkFormatError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl fmt::Display for TrustedProxies {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, network) in self.0.iter().enumerate() {
+            if i != 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{network}")?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, Copy, Deserialize, Serialize)]
 pub struct MaxThreads(usize);
 
@@ -839,14 +1472,82 @@ fn default_http_payload_size_limit() -> Byte {
     Byte::from_str(DEFAULT_HTTP_PAYLOAD_SIZE_LIMIT).unwrap()
 }
 
+fn default_http_compression_min_size() -> Byte {
+    Byte::from_str(DEFAULT_HTTP_COMPRESSION_MIN_SIZE).unwrap()
+}
+
+fn default_otlp_filter() -> String {
+    DEFAULT_OTLP_FILTER.to_string()
+}
+
+fn default_wasm_middleware_fuel() -> u64 {
+    DEFAULT_WASM_MIDDLEWARE_FUEL
+}
+
+fn default_wasm_middleware_max_memory() -> Byte {
+    Byte::from_str(DEFAULT_WASM_MIDDLEWARE_MAX_MEMORY).unwrap()
+}
+
+fn default_audit_log_max_file_size() -> Byte {
+    Byte::from_str(DEFAULT_AUDIT_LOG_MAX_FILE_SIZE).unwrap()
+}
+
+fn default_query_log_sample_rate() -> f64 {
+    DEFAULT_QUERY_LOG_SAMPLE_RATE
+}
+
+fn default_shutdown_timeout_sec() -> u64 {
+    DEFAULT_SHUTDOWN_TIMEOUT_SEC
+}
+
+fn default_task_retry_payload_retention_sec() -> u64 {
+    DEFAULT_TASK_RETRY_PAYLOAD_RETENTION_SEC
+}
+
+fn default_max_concurrent_searches() -> usize {
+    num_cpus::get()
+}
+
+fn default_search_queue_size() -> usize {
+    DEFAULT_SEARCH_QUEUE_SIZE
+}
+
+fn default_search_queue_timeout_ms() -> u64 {
+    DEFAULT_SEARCH_QUEUE_TIMEOUT_MS
+}
+
+fn default_query_log_max_file_size() -> Byte {
+    Byte::from_str(DEFAULT_QUERY_LOG_MAX_FILE_SIZE).unwrap()
+}
+
+fn default_query_log_diagnostics_sample_rate() -> f64 {
+    DEFAULT_QUERY_LOG_DIAGNOSTICS_SAMPLE_RATE
+}
+
 fn default_limit_batched_tasks() -> usize {
     usize::MAX
 }
 
+fn default_max_concurrent_batches() -> usize {
+    DEFAULT_MAX_CONCURRENT_BATCHES
+}
+
+fn default_max_filter_depth() -> usize {
+    DEFAULT_MAX_FILTER_DEPTH
+}
+
+fn default_max_filter_complexity() -> usize {
+    DEFAULT_MAX_FILTER_COMPLEXITY
+}
+
 fn default_snapshot_dir() -> PathBuf {
     PathBuf::from(DEFAULT_SNAPSHOT_DIR)
 }
 
+fn default_snapshot_s3_region() -> String {
+    DEFAULT_SNAPSHOT_S3_REGION.to_string()
+}
+
 fn default_snapshot_interval_sec() -> &'static str {
     DEFAULT_SNAPSHOT_INTERVAL_SEC_STR
 }
@@ -951,6 +1652,47 @@ where
     deserializer.deserialize_any(BoolOrInt)
 }
 
+fn schedule_snapshot_serialize<S>(
+    schedule_snapshot: &ScheduleSnapshot,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    match schedule_snapshot {
+        ScheduleSnapshot::Disabled => serializer.serialize_bool(false),
+        ScheduleSnapshot::Enabled(interval) => serializer.serialize_u64(*interval),
+    }
+}
+
+/// Serializes the values that are parsed from a string, e.g. the sizes, as this string.
+fn serialize_to_string<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Display,
+    S: serde::Serializer,
+{
+    serializer.collect_str(value)
+}
+
+fn serialize_option_to_string<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Display,
+    S: serde::Serializer,
+{
+    match value {
+        Some(value) => serializer.collect_str(value),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Serializes the exact amount of memory, unlike the rounded one of its `Display` implementation.
+fn serialize_max_memory<S>(max_memory: &MaxMemory, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serialize_option_to_string(&max_memory.0, serializer)
+}
+
 #[cfg(test)]
 mod test {
 
@@ -961,6 +1703,61 @@ mod test {
         assert!(Opt::try_parse_from(Some("")).is_ok());
     }
 
+    #[test]
+    fn test_yaml_config_file() {
+        let opt = serde_yaml::from_str::<Opt>(
+            "db_path: ./yaml.ms\nhttp_payload_size_limit: 10 MB\nschedule_snapshot: 3600\n",
+        )
+        .unwrap();
+        assert_eq!(opt.db_path, PathBuf::from("./yaml.ms"));
+        assert_eq!(opt.http_payload_size_limit.get_bytes(), 10_000_000);
+        assert!(matches!(opt.schedule_snapshot, ScheduleSnapshot::Enabled(3600)));
+    }
+
+    #[test]
+    fn test_effective_configuration() {
+        let opt = Opt::try_parse_from([
+            "meilisearch",
+            "--master-key",
+            "a-secret-master-key",
+            "--schedule-snapshot",
+            "60",
+        ])
+        .unwrap();
+        let config = opt.effective_configuration().unwrap();
+        assert!(!config.contains("a-secret-master-key"), "{config}");
+        assert!(config.contains("master_key = \"[redacted]\""), "{config}");
+
+        // the dump can be used as a configuration file.
+        let opt = toml::from_str::<Opt>(&config).unwrap();
+        assert!(matches!(opt.schedule_snapshot, ScheduleSnapshot::Enabled(60)));
+    }
+
+    #[test]
+    fn test_http_unix_socket_only_requires_a_socket() {
+        assert!(Opt::try_parse_from(["meilisearch", "--http-unix-socket-only"]).is_err());
+
+        let opt = Opt::try_parse_from([
+            "meilisearch",
+            "--http-unix-socket",
+            "./meilisearch.sock",
+            "--http-unix-socket-only",
+        ])
+        .unwrap();
+        assert_eq!(opt.http_unix_socket, Some(PathBuf::from("./meilisearch.sock")));
+        assert!(opt.http_unix_socket_only);
+    }
+
+    #[test]
+    fn test_ssl_client_identities_require_client_authentication() {
+        let args = ["meilisearch", "--ssl-client-identities-path", "./identities.json"];
+        assert!(Opt::try_parse_from(args).is_err());
+
+        let opt =
+            Opt::try_parse_from(args.into_iter().chain(["--ssl-auth-path", "./ca.pem"])).unwrap();
+        assert_eq!(opt.ssl_client_identities_path, Some(PathBuf::from("./identities.json")));
+    }
+
     #[test]
     #[ignore]
     fn test_meilli_config_file_path_valid() {
@@ -0,0 +1,653 @@
+//! The proxy mode, in which the instance holds no data and routes the requests to the backend
+//! instances by index pattern.
+//!
+//! The requests on an index are forwarded to the first backend whose pattern matches its uid.
+//! The `/indexes`, `/stats` and `/tasks` views are fetched from every backend and merged, and the
+//! API keys are created, updated and deleted on every backend so the same key can be used with
+//! all of them, the backends it succeeded on being restored when it fails on one of them. The
+//! headers are forwarded as is, except the hop-by-hop ones, and the backends are in charge of
+//! checking the `Authorization` header.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use actix_web::http::header::{HeaderName, HeaderValue, CONNECTION};
+use actix_web::http::{Method, StatusCode};
+use actix_web::web::{self, Bytes, Data};
+use actix_web::{HttpRequest, HttpResponse};
+use futures::future::try_join_all;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid_pattern::IndexUidPattern;
+use meilisearch_types::tasks::TaskId;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use url::Url;
+
+use crate::error::MeilisearchHttpError;
+use crate::option::Opt;
+use crate::routes;
+
+const PAGINATION_DEFAULT_LIMIT: usize = 20;
+
+/// The headers only meaningful for a single connection, along with the ones set by the HTTP
+/// clients themselves, that are neither forwarded to the backends nor to the clients.
+const HOP_BY_HOP_HEADERS: &[&str] = &[
+    "connection",
+    "keep-alive",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "te",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+    "host",
+    "content-length",
+];
+
+/// The fields of a key its creation accepts.
+const KEY_CREATION_FIELDS: &[&str] = &[
+    "uid",
+    "name",
+    "description",
+    "actions",
+    "indexes",
+    "expiresAt",
+    "rateLimits",
+    "allowedIps",
+    "displayedAttributes",
+];
+
+/// The fields of a key its update accepts.
+const KEY_UPDATE_FIELDS: &[&str] =
+    &["name", "description", "rateLimits", "allowedIps", "displayedAttributes"];
+
+/// A backend instance and the pattern of the indexes it holds.
+#[derive(Debug, Clone)]
+pub struct ProxyBackend {
+    pub pattern: IndexUidPattern,
+    pub url: Url,
+}
+
+/// The backends of the proxy mode, written as a comma-separated list of `pattern=url`,
+/// e.g. `movies*=http://10.0.0.1:7700,*=http://10.0.0.2:7700`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(try_from = "String")]
+pub struct ProxyBackends(Vec<ProxyBackend>);
+
+impl ProxyBackends {
+    /// Returns `true` when the proxy mode is disabled.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromStr for ProxyBackends {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(str::trim)
+            .filter(|backend| !backend.is_empty())
+            .map(|backend| {
+                let (pattern, url) = backend.split_once('=').ok_or_else(|| {
+                    format!("Invalid proxy backend `{backend}`. It must be written `pattern=url`.")
+                })?;
+                let pattern =
+                    IndexUidPattern::from_str(pattern.trim()).map_err(|e| e.to_string())?;
+                let url = Url::parse(url.trim())
+                    .map_err(|e| format!("Invalid URL for the proxy backend `{backend}`: {e}."))?;
+                Ok(ProxyBackend { pattern, url })
+            })
+            .collect::<Result<_, _>>()
+            .map(ProxyBackends)
+    }
+}
+
+impl TryFrom<String> for ProxyBackends {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl fmt::Display for ProxyBackends {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, ProxyBackend { pattern, url }) in self.0.iter().enumerate() {
+            if i != 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{}={url}", &**pattern)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Proxy {
+    client: reqwest::Client,
+    backends: ProxyBackends,
+}
+
+/// The response of a backend, returned as is to the client unless it's merged with the others.
+struct BackendResponse {
+    status: StatusCode,
+    headers: Vec<(HeaderName, HeaderValue)>,
+    body: Bytes,
+}
+
+impl BackendResponse {
+    fn is_success(&self) -> bool {
+        self.status.is_success()
+    }
+
+    fn json(&self) -> Result<Value, MeilisearchHttpError> {
+        Ok(serde_json::from_slice(&self.body)?)
+    }
+
+    fn into_http_response(self) -> HttpResponse {
+        let mut response = HttpResponse::build(self.status);
+        for header in self.headers {
+            response.append_header(header);
+        }
+        response.body(self.body)
+    }
+}
+
+impl Proxy {
+    pub fn new(backends: ProxyBackends) -> Self {
+        Proxy { client: reqwest::Client::new(), backends }
+    }
+
+    /// Returns the URL of the first backend whose pattern matches the index.
+    fn backend_of(&self, index_uid: &str) -> Result<&Url, MeilisearchHttpError> {
+        self.backends
+            .0
+            .iter()
+            .find(|backend| backend.pattern.matches_str(index_uid))
+            .map(|backend| &backend.url)
+            .ok_or_else(|| MeilisearchHttpError::ProxyNoBackend(index_uid.to_string()))
+    }
+
+    /// Returns the URLs of the backends, without duplicates.
+    fn urls(&self) -> Vec<&Url> {
+        let mut urls: Vec<&Url> = Vec::new();
+        for ProxyBackend { url, .. } in &self.backends.0 {
+            if !urls.contains(&url) {
+                urls.push(url);
+            }
+        }
+        urls
+    }
+
+    /// Prepares a request to the backend with the end-to-end headers of the client request.
+    fn request(
+        &self,
+        url: &Url,
+        req: &HttpRequest,
+        method: &Method,
+        path_and_query: &str,
+    ) -> reqwest::RequestBuilder {
+        let method = reqwest::Method::from_bytes(method.as_str().as_bytes())
+            .expect("a method of actix is always a valid method");
+        let mut request = self
+            .client
+            .request(method, format!("{}{path_and_query}", url.as_str().trim_end_matches('/')));
+        let connection = req.headers().get(CONNECTION).and_then(|value| value.to_str().ok());
+        for (name, value) in req.headers() {
+            if is_end_to_end(name.as_str(), connection) {
+                request = request.header(name.as_str(), value.as_bytes());
+            }
+        }
+        request
+    }
+
+    /// Sends the request to the backend and reads its response.
+    async fn execute(
+        &self,
+        url: &Url,
+        request: reqwest::RequestBuilder,
+    ) -> Result<BackendResponse, MeilisearchHttpError> {
+        let unreachable =
+            |e: reqwest::Error| MeilisearchHttpError::ProxyBackendUnreachable(url.to_string(), e);
+        let response = request.send().await.map_err(unreachable)?;
+        let status = StatusCode::from_u16(response.status().as_u16())
+            .expect("a status of reqwest is always a valid status");
+        let connection =
+            response.headers().get(CONNECTION.as_str()).and_then(|value| value.to_str().ok());
+        let headers = response
+            .headers()
+            .iter()
+            .filter(|(name, _)| is_end_to_end(name.as_str(), connection))
+            .filter_map(|(name, value)| {
+                let name = HeaderName::from_bytes(name.as_str().as_bytes()).ok()?;
+                Some((name, HeaderValue::from_bytes(value.as_bytes()).ok()?))
+            })
+            .collect();
+        let body = response.bytes().await.map_err(unreachable)?;
+        Ok(BackendResponse { status, headers, body })
+    }
+
+    /// Sends the request to the backend, with the given path and query instead of the original ones.
+    async fn send(
+        &self,
+        url: &Url,
+        req: &HttpRequest,
+        path_and_query: &str,
+        body: Bytes,
+    ) -> Result<BackendResponse, MeilisearchHttpError> {
+        let request = self.request(url, req, req.method(), path_and_query).body(body);
+        self.execute(url, request).await
+    }
+
+    /// Forwards the request to the backend as is.
+    async fn forward(
+        &self,
+        url: &Url,
+        req: &HttpRequest,
+        body: Bytes,
+    ) -> Result<HttpResponse, MeilisearchHttpError> {
+        let path_and_query = req.uri().path_and_query().map_or("/", |pq| pq.as_str());
+        Ok(self.send(url, req, path_and_query, body).await?.into_http_response())
+    }
+
+    /// Restores a key on the backends, the failures are only logged as the request already failed.
+    async fn restore_key(&self, urls: &[&Url], req: &HttpRequest, restoration: &KeyRestoration) {
+        for url in urls {
+            let mut request = self.request(url, req, &restoration.method, &restoration.path);
+            if let Some(body) = &restoration.body {
+                request = request.json(body);
+            }
+            match self.execute(url, request).await {
+                Ok(response) if response.is_success() => (),
+                Ok(response) => tracing::error!(
+                    "Could not restore the key on {url}: the backend answered {}",
+                    response.status
+                ),
+                Err(e) => tracing::error!("Could not restore the key on {url}: {e}"),
+            }
+        }
+    }
+
+    /// Sends the same request to every backend.
+    async fn fan_out(
+        &self,
+        req: &HttpRequest,
+        path_and_query: &str,
+    ) -> Result<Vec<BackendResponse>, MeilisearchHttpError> {
+        try_join_all(
+            self.urls().into_iter().map(|url| self.send(url, req, path_and_query, Bytes::new())),
+        )
+        .await
+    }
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig, opt: &Opt) {
+    let http_payload_size_limit = opt.http_payload_size_limit.get_bytes() as usize;
+    cfg.app_data(Data::new(Proxy::new(opt.experimental_proxy_backends.clone())))
+        // the payloads are forwarded as is, they are limited like the ones of the routes
+        .app_data(web::PayloadConfig::new(http_payload_size_limit))
+        .service(web::resource("/health").route(web::get().to(routes::get_health)))
+        .service(web::resource("/health/live").route(web::get().to(routes::get_liveness)))
+        .service(web::resource("/health/ready").route(web::get().to(routes::get_readiness)))
+        .service(web::resource("/version").route(web::get().to(routes::get_version)))
+        .service(web::resource("/stats").route(web::get().to(get_stats)))
+        .service(web::resource("/tasks").route(web::get().to(get_tasks)))
+        .service(web::resource("/tasks/{task_uid}").route(web::get().to(get_task)))
+        .service(
+            web::resource("/indexes")
+                .route(web::get().to(list_indexes))
+                .route(web::post().to(create_index)),
+        )
+        .service(web::resource("/indexes/{index_uid}").to(forward_to_index))
+        .service(web::resource("/indexes/{index_uid}/{tail:.*}").to(forward_to_index))
+        .service(web::resource("/multi-search").route(web::post().to(multi_search)))
+        .service(web::scope("/keys").default_service(web::to(federate_keys)));
+
+    for scope in [
+        "/dumps",
+        "/snapshots",
+        "/schedules",
+        "/swap-indexes",
+        "/experimental-features",
+        "/logs",
+        "/metrics",
+    ] {
+        cfg.service(web::scope(scope).default_service(web::to(not_available)));
+    }
+}
+
+async fn not_available(req: HttpRequest) -> Result<HttpResponse, ResponseError> {
+    Err(MeilisearchHttpError::NotAvailableInProxyMode(req.path().to_string()).into())
+}
+
+async fn forward_to_index(
+    proxy: Data<Proxy>,
+    req: HttpRequest,
+    body: Bytes,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = req.match_info().get("index_uid").unwrap_or_default();
+    let url = proxy.backend_of(index_uid)?;
+    Ok(proxy.forward(url, &req, body).await?)
+}
+
+async fn create_index(
+    proxy: Data<Proxy>,
+    req: HttpRequest,
+    body: Bytes,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = serde_json::from_slice::<Value>(&body)
+        .ok()
+        .and_then(|body| body.get("uid").and_then(Value::as_str).map(String::from));
+    let url = match index_uid {
+        Some(index_uid) => proxy.backend_of(&index_uid)?,
+        // let a backend return the error about the invalid payload
+        None => proxy.urls()[0],
+    };
+    Ok(proxy.forward(url, &req, body).await?)
+}
+
+#[derive(Debug, Deserialize)]
+struct Pagination {
+    #[serde(default)]
+    offset: usize,
+    #[serde(default = "pagination_default_limit")]
+    limit: usize,
+}
+
+fn pagination_default_limit() -> usize {
+    PAGINATION_DEFAULT_LIMIT
+}
+
+async fn list_indexes(
+    proxy: Data<Proxy>,
+    req: HttpRequest,
+    pagination: web::Query<Pagination>,
+) -> Result<HttpResponse, ResponseError> {
+    let Pagination { offset, limit } = pagination.into_inner();
+    let path_and_query = format!("/indexes?offset=0&limit={}", offset.saturating_add(limit));
+    let responses = proxy.fan_out(&req, &path_and_query).await?;
+    if let Some(error) = responses.iter().position(|response| !response.is_success()) {
+        return Ok(responses.into_iter().nth(error).unwrap().into_http_response());
+    }
+
+    let mut total = 0;
+    let mut indexes = Vec::new();
+    for response in responses {
+        let mut response = response.json()?;
+        total += response["total"].as_u64().unwrap_or_default();
+        if let Value::Array(results) = response["results"].take() {
+            indexes.extend(results);
+        }
+    }
+    indexes.sort_by(|a, b| a["uid"].as_str().cmp(&b["uid"].as_str()));
+    let results: Vec<_> = indexes.into_iter().skip(offset).take(limit).collect();
+
+    Ok(HttpResponse::Ok()
+        .json(json!({ "results": results, "offset": offset, "limit": limit, "total": total })))
+}
+
+async fn get_stats(proxy: Data<Proxy>, req: HttpRequest) -> Result<HttpResponse, ResponseError> {
+    let responses = proxy.fan_out(&req, "/stats").await?;
+    if let Some(error) = responses.iter().position(|response| !response.is_success()) {
+        return Ok(responses.into_iter().nth(error).unwrap().into_http_response());
+    }
+
+    let mut database_size = 0;
+    let mut last_update: Option<OffsetDateTime> = None;
+    let mut indexes = Map::new();
+    for response in responses {
+        let response = response.json()?;
+        database_size += response["databaseSize"].as_u64().unwrap_or_default();
+        let update = response["lastUpdate"].as_str().and_then(parse_date);
+        last_update = last_update.max(update);
+        if let Value::Object(stats) = &response["indexes"] {
+            for (index_uid, stats) in stats {
+                indexes.entry(index_uid.clone()).or_insert_with(|| stats.clone());
+            }
+        }
+    }
+    let last_update = last_update.map(|date| date.format(&Rfc3339)).transpose().ok().flatten();
+
+    Ok(HttpResponse::Ok().json(json!({
+        "databaseSize": database_size,
+        "lastUpdate": last_update,
+        "indexes": indexes,
+    })))
+}
+
+/// Lists the tasks of every backend, sorted by enqueue date.
+///
+/// The uids of the tasks are only unique in their backend, so the `from` parameter can't be used
+/// to paginate over the merged list.
+async fn get_tasks(proxy: Data<Proxy>, req: HttpRequest) -> Result<HttpResponse, ResponseError> {
+    let mut limit = PAGINATION_DEFAULT_LIMIT;
+    let mut query = Vec::new();
+    for param in req.query_string().split('&').filter(|param| !param.is_empty()) {
+        match param.split_once('=') {
+            Some(("from", _)) => (),
+            Some(("limit", value)) => {
+                limit = value.parse().unwrap_or(limit);
+                query.push(param);
+            }
+            _ => query.push(param),
+        }
+    }
+    let path_and_query = if query.is_empty() {
+        String::from("/tasks")
+    } else {
+        format!("/tasks?{}", query.join("&"))
+    };
+
+    let responses = proxy.fan_out(&req, &path_and_query).await?;
+    if let Some(error) = responses.iter().position(|response| !response.is_success()) {
+        return Ok(responses.into_iter().nth(error).unwrap().into_http_response());
+    }
+
+    let mut total = 0;
+    let mut tasks = Vec::new();
+    for response in responses {
+        let mut response = response.json()?;
+        total += response["total"].as_u64().unwrap_or_default();
+        if let Value::Array(results) = response["results"].take() {
+            tasks.extend(results);
+        }
+    }
+    tasks.sort_by_cached_key(|task| {
+        std::cmp::Reverse(task["enqueuedAt"].as_str().and_then(parse_date))
+    });
+    tasks.truncate(limit);
+
+    Ok(HttpResponse::Ok().json(json!({
+        "results": tasks,
+        "total": total,
+        "limit": limit,
+        "from": null,
+        "next": null,
+    })))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GetTaskQuery {
+    index_uid: Option<String>,
+}
+
+/// Returns the task from the backend of the `indexUid` parameter, or from the only backend that
+/// knows a task with this uid.
+async fn get_task(
+    proxy: Data<Proxy>,
+    req: HttpRequest,
+    task_uid: web::Path<String>,
+    query: web::Query<GetTaskQuery>,
+) -> Result<HttpResponse, ResponseError> {
+    let path = format!("/tasks/{}", task_uid.as_str());
+    if let Some(index_uid) = &query.index_uid {
+        let url = proxy.backend_of(index_uid)?;
+        return Ok(proxy.send(url, &req, &path, Bytes::new()).await?.into_http_response());
+    }
+
+    let mut responses = proxy.fan_out(&req, &path).await?;
+    let found = responses.iter().filter(|response| response.is_success()).count();
+    match found {
+        0 => Ok(responses.remove(0).into_http_response()),
+        1 => {
+            let task = responses.into_iter().find(BackendResponse::is_success).unwrap();
+            Ok(task.into_http_response())
+        }
+        _ => {
+            let task_uid = task_uid.parse::<TaskId>().unwrap_or_default();
+            Err(MeilisearchHttpError::ProxyAmbiguousTask(task_uid).into())
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct MultiSearch {
+    queries: Vec<Value>,
+    #[serde(default)]
+    allow_partial_failures: bool,
+}
+
+/// Sends the queries of every backend to it concurrently and returns the results in the queries
+/// order.
+async fn multi_search(
+    proxy: Data<Proxy>,
+    req: HttpRequest,
+    body: Bytes,
+) -> Result<HttpResponse, ResponseError> {
+    let Ok(MultiSearch { queries, allow_partial_failures }) = serde_json::from_slice(&body) else {
+        // let a backend return the error about the invalid payload
+        return Ok(proxy.forward(proxy.urls()[0], &req, body).await?);
+    };
+
+    // the queries of each backend along with their position in the request
+    let mut groups: BTreeMap<&Url, Vec<(usize, Value)>> = BTreeMap::new();
+    for (position, query) in queries.into_iter().enumerate() {
+        let url = match query.get("indexUid").and_then(Value::as_str) {
+            Some(index_uid) => proxy.backend_of(index_uid)?,
+            None => proxy.urls()[0],
+        };
+        groups.entry(url).or_default().push((position, query));
+    }
+
+    let mut requests = Vec::new();
+    for (url, queries) in groups {
+        let (positions, queries): (Vec<_>, Vec<_>) = queries.into_iter().unzip();
+        let body = json!({ "queries": queries, "allowPartialFailures": allow_partial_failures });
+        requests.push((url, positions, Bytes::from(serde_json::to_vec(&body)?)));
+    }
+    let responses = try_join_all(
+        requests.iter().map(|(url, _, body)| proxy.send(url, &req, "/multi-search", body.clone())),
+    )
+    .await?;
+
+    let mut results = Vec::new();
+    let mut statuses = Vec::new();
+    for ((_, positions, _), response) in requests.into_iter().zip(responses) {
+        if !response.is_success() {
+            return Ok(response.into_http_response());
+        }
+        let mut response = response.json()?;
+        statuses.push(response["status"].take());
+        if let Value::Array(backend_results) = response["results"].take() {
+            results.extend(positions.into_iter().zip(backend_results));
+        }
+    }
+    results.sort_by_key(|(position, _)| *position);
+    let results: Vec<_> = results.into_iter().map(|(_, result)| result).collect();
+
+    let mut response = json!({ "results": results });
+    if allow_partial_failures {
+        // the queries failed partially as soon as the backends don't agree
+        response["status"] = match statuses.first() {
+            Some(status) if statuses.iter().all(|s| s == status) => status.clone(),
+            Some(_) => json!("partiallyFailed"),
+            None => json!("succeeded"),
+        };
+    }
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// The request restoring a key on the backends it was created, updated or deleted on when this
+/// failed on another backend.
+struct KeyRestoration {
+    method: Method,
+    path: String,
+    body: Option<Value>,
+}
+
+/// Reads the keys from the first backend, and creates, updates or deletes them on every backend.
+///
+/// A key is derived from its uid and the master key, so the backends sharing a master key end up
+/// with the same key once created with the same uid. When a backend fails, the key is restored
+/// on the backends it already succeeded on so the request can be retried as is.
+async fn federate_keys(
+    proxy: Data<Proxy>,
+    req: HttpRequest,
+    body: Bytes,
+) -> Result<HttpResponse, ResponseError> {
+    let urls = proxy.urls();
+    let path_and_query = req.uri().path_and_query().map_or("/keys", |pq| pq.as_str());
+    let (body, restoration) = match *req.method() {
+        Method::POST => match serde_json::from_slice::<Value>(&body) {
+            Ok(Value::Object(mut key)) => {
+                let uid = key.entry("uid").or_insert_with(|| json!(uuid::Uuid::new_v4()));
+                let path = format!("/keys/{}", uid.as_str().unwrap_or_default());
+                let body = Bytes::from(serde_json::to_vec(&key)?);
+                (body, KeyRestoration { method: Method::DELETE, path, body: None })
+            }
+            // let a backend return the error about the invalid payload
+            _ => return Ok(proxy.forward(urls[0], &req, body).await?),
+        },
+        Method::PATCH | Method::DELETE => {
+            let request = proxy.request(urls[0], &req, &Method::GET, path_and_query);
+            let previous = proxy.execute(urls[0], request).await?;
+            if !previous.is_success() {
+                return Ok(previous.into_http_response());
+            }
+            let previous = previous.json()?;
+            let (method, path, fields) = if req.method() == Method::PATCH {
+                (Method::PATCH, path_and_query.to_string(), KEY_UPDATE_FIELDS)
+            } else {
+                (Method::POST, String::from("/keys"), KEY_CREATION_FIELDS)
+            };
+            let previous = fields
+                .iter()
+                .filter_map(|field| Some((field.to_string(), previous.get(field)?.clone())))
+                .collect();
+            (body, KeyRestoration { method, path, body: Some(Value::Object(previous)) })
+        }
+        _ => return Ok(proxy.forward(urls[0], &req, body).await?),
+    };
+
+    let mut first = None;
+    for (i, url) in urls.iter().enumerate() {
+        let response = match proxy.send(url, &req, path_and_query, body.clone()).await {
+            Ok(response) if response.is_success() => response,
+            failure => {
+                proxy.restore_key(&urls[..i], &req, &restoration).await;
+                return Ok(failure?.into_http_response());
+            }
+        };
+        first.get_or_insert(response);
+    }
+    Ok(first.map_or_else(|| HttpResponse::NoContent().finish(), |r| r.into_http_response()))
+}
+
+fn parse_date(date: &str) -> Option<OffsetDateTime> {
+    OffsetDateTime::parse(date, &Rfc3339).ok()
+}
+
+/// Returns `true` if the header isn't a hop-by-hop header nor one listed by the `Connection`
+/// header, and must be forwarded.
+fn is_end_to_end(name: &str, connection: Option<&str>) -> bool {
+    !HOP_BY_HOP_HEADERS.iter().any(|header| header.eq_ignore_ascii_case(name))
+        && !connection.map_or(false, |connection| {
+            connection.split(',').any(|header| header.trim().eq_ignore_ascii_case(name))
+        })
+}
@@ -0,0 +1,425 @@
+/*!
+The log of the searches made on the instance, to analyse their relevancy offline.
+
+When enabled, every search, or a random sample of them, is appended as a JSON line to the
+`queries.ndjson` file of the `query-log` directory of the database, with its parameters, its
+latency and its number of hits. The log can be loaded as is into most data warehouses.
+
+A sample of the logged searches, 1% by default, also records the number of buckets computed by
+each ranking rule, the number of documents in them and the time it took, to find out which
+ranking rules are slow or don't discriminate the documents on the production traffic.
+
+The queries and the filters are anonymized: their words containing a digit or an `@`, that are likely to be
+identifiers, phone numbers or email addresses, are replaced by `***`. Neither the API key, the
+filters of the tenant tokens nor the address of the client are logged.
+
+When the log grows over its maximum size, it's moved aside and a new one is started, see
+[`RotatedFile`]. The log is written by a background thread so the searches never wait for the
+disk, the events are dropped when more than [`MAX_PENDING_EVENTS`] are waiting to be written.
+
+The clicks on the hits, reported by the front-ends, are sampled at the same rate as the searches
+and written in the log along with them. Both are aggregated by query over a time window to find
+out the most frequent queries, the ones returning no hits and the ones whose hits are never clicked.
+*/
+
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use crossbeam_channel::{Sender, TrySendError};
+use meilisearch_types::milli::SearchDiagnostics;
+use meilisearch_types::rotated_file::RotatedFile;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use time::OffsetDateTime;
+
+use crate::option::Opt;
+use crate::search::{HitsInfo, MatchingStrategy, SearchQuery, SearchResult};
+
+/// The number of searches and clicks waiting to be written in the log above which the new ones
+/// are dropped.
+pub const MAX_PENDING_EVENTS: usize = 10_000;
+
+/// A search, as it's written in the query log.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoggedSearch {
+    #[serde(with = "time::serde::rfc3339")]
+    pub at: OffsetDateTime,
+    pub index_uid: String,
+    /// The anonymized query.
+    pub q: Option<String>,
+    /// The filter given by the client, anonymized like the query.
+    pub filter: Option<Value>,
+    pub sort: Option<Vec<String>>,
+    pub facets: Option<Vec<String>>,
+    pub attributes_to_search_on: Option<Vec<String>>,
+    pub matching_strategy: &'static str,
+    pub offset: usize,
+    pub limit: usize,
+    pub page: Option<usize>,
+    pub hits_per_page: Option<usize>,
+    /// Whether the search relied on the embeddings of the documents.
+    pub vector: bool,
+    pub processing_time_ms: u128,
+    /// The number of hits returned.
+    pub hits: usize,
+    /// The number of hits matching the search, exhaustive or estimated depending on the pagination.
+    pub total_hits: usize,
+    /// The work done by each ranking rule, only recorded for a sample of the searches.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ranking_rules: Option<Vec<LoggedRankingRule>>,
+    #[serde(skip)]
+    diagnostics: bool,
+}
+
+/// The work done by a ranking rule during a search, as it's written in the query log.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoggedRankingRule {
+    pub name: String,
+    pub buckets: usize,
+    pub bucket_documents: u64,
+    pub duration_ms: f64,
+}
+
+impl LoggedRankingRule {
+    fn from_diagnostics(diagnostics: &SearchDiagnostics) -> Vec<Self> {
+        diagnostics
+            .ranking_rules
+            .iter()
+            .map(|rule| LoggedRankingRule {
+                name: rule.name.clone(),
+                buckets: rule.buckets,
+                bucket_documents: rule.bucket_documents,
+                duration_ms: rule.duration.as_secs_f64() * 1000.0,
+            })
+            .collect()
+    }
+}
+
+impl LoggedSearch {
+    fn new(index_uid: &str, query: &SearchQuery, diagnostics: bool) -> Self {
+        LoggedSearch {
+            at: OffsetDateTime::now_utc(),
+            index_uid: index_uid.to_string(),
+            q: query.q.as_deref().map(anonymize),
+            filter: query.filter.as_ref().map(anonymize_filter),
+            sort: query.sort.clone(),
+            facets: query.facets.as_ref().map(|facets| facets.names.clone()),
+            attributes_to_search_on: query.attributes_to_search_on.clone(),
+            matching_strategy: match query.matching_strategy {
+                MatchingStrategy::Last => "last",
+                MatchingStrategy::All => "all",
+            },
+            offset: query.offset,
+            limit: query.limit,
+            page: query.page,
+            hits_per_page: query.hits_per_page,
+            vector: query.vector.is_some() || query.hybrid.is_some(),
+            processing_time_ms: 0,
+            hits: 0,
+            total_hits: 0,
+            ranking_rules: None,
+            diagnostics,
+        }
+    }
+
+    /// Whether the search must record the work done by its ranking rules.
+    pub fn diagnostics(&self) -> bool {
+        self.diagnostics
+    }
+
+    /// Fills the outcome of the search.
+    fn succeed(&mut self, result: &SearchResult) {
+        self.processing_time_ms = result.processing_time_ms;
+        self.hits = result.hits.len();
+        self.total_hits = match result.hits_info {
+            HitsInfo::Pagination { total_hits, .. } => total_hits,
+            HitsInfo::OffsetLimit { estimated_total_hits, .. } => estimated_total_hits,
+        };
+        if self.diagnostics {
+            self.ranking_rules =
+                result.diagnostics.as_ref().map(LoggedRankingRule::from_diagnostics);
+        }
+    }
+}
+
+/// A click on a hit of a search, as it's written in the query log.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoggedClick {
+    #[serde(with = "time::serde::rfc3339")]
+    pub at: OffsetDateTime,
+    pub index_uid: String,
+    /// The anonymized query of the search the hit was returned by.
+    pub q: String,
+    pub clicked_document_id: Value,
+}
+
+/// A search or a click read back from the query log, with only the fields the analytics need.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LoggedEvent {
+    #[serde(with = "time::serde::rfc3339")]
+    at: OffsetDateTime,
+    index_uid: String,
+    q: Option<String>,
+    #[serde(default)]
+    total_hits: usize,
+    /// Only set on the clicks.
+    clicked_document_id: Option<Value>,
+}
+
+/// The searches and clicks of an index over a time window, aggregated by query.
+///
+/// Only the searches and clicks sampled in the query log are counted, the placeholder searches
+/// are not part of any query.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchAnalytics {
+    pub searches: usize,
+    pub zero_result_searches: usize,
+    pub clicks: usize,
+    /// The most frequent queries.
+    pub top_queries: Vec<QueryAnalytics>,
+    /// The queries most frequently returning no hits.
+    pub zero_result_queries: Vec<QueryAnalytics>,
+    /// The most frequent queries returning hits that were never clicked.
+    pub clickless_queries: Vec<QueryAnalytics>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryAnalytics {
+    /// The anonymized and lowercased query.
+    pub q: String,
+    pub searches: usize,
+    pub zero_result_searches: usize,
+    pub clicks: usize,
+}
+
+/// Returns the `limit` queries with the highest count, the ones with a count of zero are ignored.
+fn most_frequent(
+    queries: &[QueryAnalytics],
+    limit: usize,
+    count: impl Fn(&QueryAnalytics) -> usize,
+) -> Vec<QueryAnalytics> {
+    let mut queries: Vec<_> = queries.iter().filter(|query| count(query) > 0).cloned().collect();
+    queries.sort_by(|a, b| count(b).cmp(&count(a)).then_with(|| a.q.cmp(&b.q)));
+    queries.truncate(limit);
+    queries
+}
+
+/// Replaces the words of the query that are likely to identify someone by `***`.
+fn anonymize(query: &str) -> String {
+    query
+        .split_whitespace()
+        .map(|word| if word.chars().any(|c| c.is_ascii_digit() || c == '@') { "***" } else { word })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Anonymizes the filter expressions like the queries, a filter being a string or an array of them.
+fn anonymize_filter(filter: &Value) -> Value {
+    match filter {
+        Value::String(filter) => Value::String(anonymize(filter)),
+        Value::Array(filters) => Value::Array(filters.iter().map(anonymize_filter).collect()),
+        filter => filter.clone(),
+    }
+}
+
+/// What is sent to the thread writing the log.
+enum Message {
+    Search(LoggedSearch),
+    Click(LoggedClick),
+    /// Answered once the events sent before are written.
+    Flush(Sender<()>),
+}
+
+struct Writer {
+    file: Arc<RotatedFile>,
+    sender: Sender<Message>,
+}
+
+/// An append-only and rotated file of [`LoggedSearch`], that logs nothing when disabled.
+#[derive(Default)]
+pub struct QueryLog {
+    writer: Option<Writer>,
+    sample_rate: f64,
+    diagnostics_sample_rate: f64,
+}
+
+impl QueryLog {
+    /// Open the query log stored in the given directory, creating it if needed, and start the
+    /// thread writing it.
+    ///
+    /// Only the given ratio of the searches, between 0 and 1, is logged, and only the given ratio
+    /// of the logged searches records the work done by its ranking rules.
+    pub fn open(
+        dir: impl AsRef<Path>,
+        max_file_size: u64,
+        sample_rate: f64,
+        diagnostics_sample_rate: f64,
+    ) -> io::Result<Self> {
+        let file = Arc::new(RotatedFile::open(dir, "queries", "ndjson", max_file_size)?);
+        let (sender, receiver) = crossbeam_channel::bounded(MAX_PENDING_EVENTS);
+        let written = file.clone();
+        // stops once the query log is dropped.
+        std::thread::Builder::new().name(String::from("query-log")).spawn(move || {
+            for message in receiver {
+                let result = match message {
+                    Message::Search(search) => written.append(&search),
+                    Message::Click(click) => written.append(&click),
+                    Message::Flush(done) => {
+                        let _ = done.send(());
+                        continue;
+                    }
+                };
+                if let Err(e) = result {
+                    tracing::error!("Could not write in the query log: {e}");
+                }
+            }
+        })?;
+
+        let writer = Writer { file, sender };
+        Ok(Self { writer: Some(writer), sample_rate, diagnostics_sample_rate })
+    }
+
+    /// Open the query log of the instance if it's enabled.
+    pub fn from_opt(opt: &Opt) -> anyhow::Result<Self> {
+        if !opt.query_log {
+            return Ok(Self::default());
+        }
+        anyhow::ensure!(
+            (0.0..=1.0).contains(&opt.query_log_sample_rate),
+            "The sample rate of the query log must be between 0 and 1, but found `{}`",
+            opt.query_log_sample_rate
+        );
+        anyhow::ensure!(
+            (0.0..=1.0).contains(&opt.query_log_diagnostics_sample_rate),
+            "The diagnostics sample rate of the query log must be between 0 and 1, but found `{}`",
+            opt.query_log_diagnostics_sample_rate
+        );
+        let max_file_size = opt.query_log_max_file_size.get_bytes() as u64;
+        Ok(Self::open(
+            opt.db_path.join("query-log"),
+            max_file_size,
+            opt.query_log_sample_rate,
+            opt.query_log_diagnostics_sample_rate,
+        )?)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.writer.is_some()
+    }
+
+    /// Starts logging the search, returns `None` when it's not part of the sample.
+    pub fn start(&self, index_uid: &str, query: &SearchQuery) -> Option<LoggedSearch> {
+        if self.writer.is_none() || rand::random::<f64>() >= self.sample_rate {
+            return None;
+        }
+        let diagnostics = rand::random::<f64>() < self.diagnostics_sample_rate;
+        Some(LoggedSearch::new(index_uid, query, diagnostics))
+    }
+
+    /// Send the search or the click to the thread writing the log, without waiting for it.
+    fn send(&self, message: Message) {
+        let Some(writer) = &self.writer else { return };
+        match writer.sender.try_send(message) {
+            Ok(()) => (),
+            Err(TrySendError::Full(_)) => {
+                tracing::error!("Could not write in the query log: too many pending events")
+            }
+            Err(TrySendError::Disconnected(_)) => {
+                tracing::error!("Could not write in the query log: its writer stopped")
+            }
+        }
+    }
+
+    /// Logs the search if it was sampled and succeeded.
+    pub fn finish<E>(&self, search: Option<LoggedSearch>, result: &Result<SearchResult, E>) {
+        let (Some(mut search), Ok(result)) = (search, result) else { return };
+        search.succeed(result);
+        self.send(Message::Search(search));
+    }
+
+    /// Logs a click on a hit of a search if it's part of the sample.
+    ///
+    /// The clicks are sampled like the searches so the ratio of clicks per search is preserved.
+    pub fn click(&self, index_uid: &str, q: &str, document_id: Value) {
+        if self.writer.is_none() || rand::random::<f64>() >= self.sample_rate {
+            return;
+        }
+        let click = LoggedClick {
+            at: OffsetDateTime::now_utc(),
+            index_uid: index_uid.to_string(),
+            q: anonymize(q),
+            clicked_document_id: document_id,
+        };
+        self.send(Message::Click(click));
+    }
+
+    /// Aggregates the searches and clicks of the index logged from `from` until `to` excluded,
+    /// keeping the `limit` most frequent queries of each list.
+    pub fn analytics(
+        &self,
+        index_uid: &str,
+        from: Option<OffsetDateTime>,
+        to: Option<OffsetDateTime>,
+        limit: usize,
+    ) -> io::Result<SearchAnalytics> {
+        let Some(writer) = &self.writer else { return Ok(SearchAnalytics::default()) };
+
+        // waits for the events already sent to be written.
+        let (done, written) = crossbeam_channel::bounded(1);
+        if writer.sender.send(Message::Flush(done)).is_ok() {
+            let _ = written.recv();
+        }
+
+        let mut analytics = SearchAnalytics::default();
+        let mut queries: HashMap<String, QueryAnalytics> = HashMap::new();
+        writer.file.for_each(|event: LoggedEvent| {
+            if event.index_uid != index_uid
+                || from.map_or(false, |from| event.at < from)
+                || to.map_or(false, |to| event.at >= to)
+            {
+                return;
+            }
+            let q = event.q.map(|q| q.trim().to_lowercase()).filter(|q| !q.is_empty());
+            let query = q.map(|q| {
+                queries
+                    .entry(q.clone())
+                    .or_insert_with(|| QueryAnalytics { q, ..Default::default() })
+            });
+            if event.clicked_document_id.is_some() {
+                analytics.clicks += 1;
+                if let Some(query) = query {
+                    query.clicks += 1;
+                }
+            } else {
+                let zero_result = (event.total_hits == 0) as usize;
+                analytics.searches += 1;
+                analytics.zero_result_searches += zero_result;
+                if let Some(query) = query {
+                    query.searches += 1;
+                    query.zero_result_searches += zero_result;
+                }
+            }
+        })?;
+
+        let queries: Vec<_> = queries.into_values().collect();
+        analytics.top_queries = most_frequent(&queries, limit, |query| query.searches);
+        analytics.zero_result_queries =
+            most_frequent(&queries, limit, |query| query.zero_result_searches);
+        analytics.clickless_queries = most_frequent(&queries, limit, |query| {
+            if query.clicks == 0 {
+                query.searches - query.zero_result_searches
+            } else {
+                0
+            }
+        });
+        Ok(analytics)
+    }
+}
@@ -5,12 +5,12 @@ use actix_web::{web, HttpRequest, HttpResponse};
 use deserr::actix_web::{AwebJson, AwebQueryParameter};
 use deserr::Deserr;
 use meilisearch_auth::error::AuthControllerError;
-use meilisearch_auth::AuthController;
+use meilisearch_auth::{AuthController, KeyUsage};
 use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::{Code, ResponseError};
-use meilisearch_types::keys::{CreateApiKey, Key, PatchApiKey};
+use meilisearch_types::keys::{CreateApiKey, Key, PatchApiKey, RateLimits};
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 use uuid::Uuid;
@@ -32,7 +32,8 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
             .route(web::get().to(SeqHandler(get_api_key)))
             .route(web::patch().to(SeqHandler(patch_api_key)))
             .route(web::delete().to(SeqHandler(delete_api_key))),
-    );
+    )
+    .service(web::resource("/{key}/stats").route(web::get().to(SeqHandler(get_api_key_stats))));
 }
 
 pub async fn create_api_key(
@@ -41,8 +42,12 @@ pub async fn create_api_key(
     _req: HttpRequest,
 ) -> Result<HttpResponse, ResponseError> {
     let v = body.into_inner();
+    let uid = auth_controller.filters().key_uid();
     let res = tokio::task::spawn_blocking(move || -> Result<_, AuthControllerError> {
-        let key = auth_controller.create_key(v)?;
+        let key = match managing_key(&auth_controller, uid, Action::KeysAdd)? {
+            Some(parent) => auth_controller.create_key_within(parent, v)?,
+            None => auth_controller.create_key(v)?,
+        };
         Ok(KeyView::from_key(key, &auth_controller))
     })
     .await
@@ -71,8 +76,18 @@ pub async fn list_api_keys(
     list_api_keys: AwebQueryParameter<ListApiKeys, DeserrQueryParamError>,
 ) -> Result<HttpResponse, ResponseError> {
     let paginate = list_api_keys.into_inner().as_pagination();
+    let uid = auth_controller.filters().key_uid();
     let page_view = tokio::task::spawn_blocking(move || -> Result<_, AuthControllerError> {
-        let keys = auth_controller.list_keys()?;
+        let mut keys = auth_controller.list_keys()?;
+        if let Some(parent) = managing_key(&auth_controller, uid, Action::KeysGet)? {
+            let mut visible_keys = Vec::new();
+            for key in keys {
+                if auth_controller.is_key_within(&key, parent)? {
+                    visible_keys.push(key);
+                }
+            }
+            keys = visible_keys;
+        }
         let page_view = paginate
             .auto_paginate_sized(keys.into_iter().map(|k| KeyView::from_key(k, &auth_controller)));
 
@@ -89,13 +104,32 @@ pub async fn get_api_key(
     path: web::Path<AuthParam>,
 ) -> Result<HttpResponse, ResponseError> {
     let key = path.into_inner().key;
+    let uid = auth_controller.filters().key_uid();
 
     let res = tokio::task::spawn_blocking(move || -> Result<_, AuthControllerError> {
-        let uid =
-            Uuid::parse_str(&key).or_else(|_| auth_controller.get_uid_from_encoded_key(&key))?;
-        let key = auth_controller.get_key(uid)?;
+        let parent = managing_key(&auth_controller, uid, Action::KeysGet)?;
+        let key = visible_key(&auth_controller, parent, &key)?;
+        let usage = auth_controller.get_key_usage(key.uid)?;
 
-        Ok(KeyView::from_key(key, &auth_controller))
+        Ok(KeyView { usage: Some(usage), ..KeyView::from_key(key, &auth_controller) })
+    })
+    .await
+    .map_err(|e| ResponseError::from_msg(e.to_string(), Code::Internal))??;
+
+    Ok(HttpResponse::Ok().json(res))
+}
+
+pub async fn get_api_key_stats(
+    auth_controller: GuardedData<ActionPolicy<{ actions::KEYS_GET }>, Data<AuthController>>,
+    path: web::Path<AuthParam>,
+) -> Result<HttpResponse, ResponseError> {
+    let key = path.into_inner().key;
+    let uid = auth_controller.filters().key_uid();
+
+    let res = tokio::task::spawn_blocking(move || -> Result<_, AuthControllerError> {
+        let parent = managing_key(&auth_controller, uid, Action::KeysGet)?;
+        let key = visible_key(&auth_controller, parent, &key)?;
+        auth_controller.get_key_usage(key.uid)
     })
     .await
     .map_err(|e| ResponseError::from_msg(e.to_string(), Code::Internal))??;
@@ -110,10 +144,14 @@ pub async fn patch_api_key(
 ) -> Result<HttpResponse, ResponseError> {
     let key = path.into_inner().key;
     let patch_api_key = body.into_inner();
+    let uid = auth_controller.filters().key_uid();
     let res = tokio::task::spawn_blocking(move || -> Result<_, AuthControllerError> {
-        let uid =
-            Uuid::parse_str(&key).or_else(|_| auth_controller.get_uid_from_encoded_key(&key))?;
-        let key = auth_controller.update_key(uid, patch_api_key)?;
+        let parent = managing_key(&auth_controller, uid, Action::KeysUpdate)?;
+        let uid = visible_key(&auth_controller, parent, &key)?.uid;
+        let key = match parent {
+            Some(parent) => auth_controller.update_key_within(uid, patch_api_key, parent)?,
+            None => auth_controller.update_key(uid, patch_api_key)?,
+        };
 
         Ok(KeyView::from_key(key, &auth_controller))
     })
@@ -128,9 +166,10 @@ pub async fn delete_api_key(
     path: web::Path<AuthParam>,
 ) -> Result<HttpResponse, ResponseError> {
     let key = path.into_inner().key;
+    let uid = auth_controller.filters().key_uid();
     tokio::task::spawn_blocking(move || {
-        let uid =
-            Uuid::parse_str(&key).or_else(|_| auth_controller.get_uid_from_encoded_key(&key))?;
+        let parent = managing_key(&auth_controller, uid, Action::KeysDelete)?;
+        let uid = visible_key(&auth_controller, parent, &key)?.uid;
         auth_controller.delete_key(uid)
     })
     .await
@@ -139,6 +178,38 @@ pub async fn delete_api_key(
     Ok(HttpResponse::NoContent().finish())
 }
 
+/// Return the uid of the API key the request was made with when it's only authorized through
+/// `keys.manage`, the keys it creates, sees, updates and deletes are then restricted to the ones
+/// granting no more than itself. The master key and the keys granted the action itself, e.g.
+/// `keys.create`, aren't restricted.
+fn managing_key(
+    auth_controller: &AuthController,
+    uid: Option<Uuid>,
+    action: Action,
+) -> Result<Option<Uuid>, AuthControllerError> {
+    match uid {
+        Some(uid) if auth_controller.is_key_managing_only(uid, action)? => Ok(Some(uid)),
+        _ => Ok(None),
+    }
+}
+
+/// Return the key identified by its uid or its value, the keys granting more than the API key
+/// the request was made with are hidden from it.
+fn visible_key(
+    auth_controller: &AuthController,
+    parent: Option<Uuid>,
+    key: &str,
+) -> Result<Key, AuthControllerError> {
+    let uid = Uuid::parse_str(key).or_else(|_| auth_controller.get_uid_from_encoded_key(key))?;
+    let found = auth_controller.get_key(uid)?;
+    match parent {
+        Some(parent) if !auth_controller.is_key_within(&found, parent)? => {
+            Err(AuthControllerError::ApiKeyNotFound(key.to_string()))
+        }
+        _ => Ok(found),
+    }
+}
+
 #[derive(Deserialize)]
 pub struct AuthParam {
     key: String,
@@ -155,10 +226,19 @@ struct KeyView {
     indexes: Vec<String>,
     #[serde(serialize_with = "time::serde::rfc3339::option::serialize")]
     expires_at: Option<OffsetDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rate_limits: Option<RateLimits>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allowed_ips: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    displayed_attributes: Option<Vec<String>>,
     #[serde(serialize_with = "time::serde::rfc3339::serialize")]
     created_at: OffsetDateTime,
     #[serde(serialize_with = "time::serde::rfc3339::serialize")]
     updated_at: OffsetDateTime,
+    /// Only returned when a single key is fetched.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    usage: Option<KeyUsage>,
 }
 
 impl KeyView {
@@ -173,8 +253,14 @@ impl KeyView {
             actions: key.actions,
             indexes: key.indexes.into_iter().map(|x| x.to_string()).collect(),
             expires_at: key.expires_at,
+            rate_limits: key.rate_limits,
+            allowed_ips: key
+                .allowed_ips
+                .map(|networks| networks.into_iter().map(|x| x.to_string()).collect()),
+            displayed_attributes: key.displayed_attributes,
             created_at: key.created_at,
             updated_at: key.updated_at,
+            usage: None,
         }
     }
 }
@@ -0,0 +1,72 @@
+use actix_web::web::Data;
+use actix_web::{web, HttpResponse};
+use deserr::actix_web::AwebQueryParameter;
+use deserr::Deserr;
+use meilisearch_auth::error::AuthControllerError;
+use meilisearch_auth::AuthController;
+use meilisearch_types::deserr::query_params::Param;
+use meilisearch_types::deserr::DeserrQueryParamError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::{Code, ResponseError};
+use tracing::debug;
+use uuid::Uuid;
+
+use super::{Pagination, PAGINATION_DEFAULT_LIMIT};
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::get().to(SeqHandler(list_audit_entries))));
+}
+
+#[derive(Deserr, Debug)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+pub struct ListAuditEntries {
+    #[deserr(default, error = DeserrQueryParamError<InvalidAuditLogOffset>)]
+    pub offset: Param<usize>,
+    #[deserr(default = Param(PAGINATION_DEFAULT_LIMIT), error = DeserrQueryParamError<InvalidAuditLogLimit>)]
+    pub limit: Param<usize>,
+    /// Only returns the requests made with this API key.
+    #[deserr(default, error = DeserrQueryParamError<InvalidAuditLogKeyUid>)]
+    pub key_uid: Option<String>,
+}
+
+/// Returns the requests recorded in the audit log, from the most recent to the oldest.
+pub async fn list_audit_entries(
+    auth_controller: GuardedData<MasterPolicy, Data<AuthController>>,
+    params: AwebQueryParameter<ListAuditEntries, DeserrQueryParamError>,
+) -> Result<HttpResponse, ResponseError> {
+    if !auth_controller.is_audit_log_enabled() {
+        return Err(ResponseError::from_msg(
+            "The audit log is disabled. Launch Meilisearch with the `--audit-log` option to enable it."
+                .to_string(),
+            Code::AuditLogDisabled,
+        ));
+    }
+
+    let ListAuditEntries { offset, limit, key_uid } = params.into_inner();
+    let key_uid = match key_uid {
+        Some(key_uid) => Some(Uuid::parse_str(&key_uid).map_err(|_| {
+            ResponseError::from_msg(
+                format!("Invalid value in parameter `keyUid`: `{key_uid}` is not a valid uuid."),
+                Code::InvalidAuditLogKeyUid,
+            )
+        })?),
+        None => None,
+    };
+    let paginate = Pagination { offset: offset.0, limit: limit.0 };
+
+    let page_view = tokio::task::spawn_blocking(move || -> Result<_, AuthControllerError> {
+        let (entries, total) =
+            auth_controller.audit_entries(paginate.offset, paginate.limit, |entry| {
+                key_uid.map_or(true, |key_uid| entry.key_uid == Some(key_uid))
+            })?;
+        Ok(paginate.format_with(total, entries))
+    })
+    .await
+    .map_err(|e| ResponseError::from_msg(e.to_string(), Code::Internal))??;
+
+    debug!(returns = ?page_view, "List audit log entries");
+    Ok(HttpResponse::Ok().json(page_view))
+}
@@ -0,0 +1,150 @@
+use actix_web::web::Data;
+use actix_web::{web, HttpRequest, HttpResponse};
+use index_scheduler::{DeadLetter, IndexScheduler, TaskId};
+use meilisearch_auth::AuthFilter;
+use meilisearch_types::error::ResponseError;
+use serde::Serialize;
+use serde_json::json;
+use time::OffsetDateTime;
+use tracing::debug;
+
+use super::SummarizedTaskView;
+use crate::analytics::Analytics;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::get().to(SeqHandler(get_dead_letters))))
+        .service(
+            web::resource("/{task_uid}")
+                .route(web::get().to(SeqHandler(get_dead_letter)))
+                .route(web::delete().to(SeqHandler(delete_dead_letter))),
+        )
+        .service(
+            web::resource("/{task_uid}/import")
+                .route(web::post().to(SeqHandler(import_dead_letter))),
+        );
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeadLetterView {
+    task_uid: TaskId,
+    index_uid: String,
+    primary_key: Option<String>,
+    documents_count: u64,
+    error: Option<ResponseError>,
+    #[serde(with = "time::serde::rfc3339")]
+    enqueued_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339::option")]
+    started_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    finished_at: Option<OffsetDateTime>,
+}
+
+impl DeadLetterView {
+    fn from_dead_letter(dead_letter: DeadLetter) -> Self {
+        let DeadLetter {
+            task_uid,
+            index_uid,
+            primary_key,
+            method: _,
+            documents_count,
+            allow_index_creation: _,
+            content_file: _,
+            error,
+            enqueued_at,
+            started_at,
+            finished_at,
+        } = dead_letter;
+        DeadLetterView {
+            task_uid,
+            index_uid,
+            primary_key,
+            documents_count,
+            error,
+            enqueued_at,
+            started_at,
+            finished_at,
+        }
+    }
+}
+
+pub async fn get_dead_letters(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_GET }>, Data<IndexScheduler>>,
+) -> Result<HttpResponse, ResponseError> {
+    let filters = index_scheduler.filters();
+    let dead_letters: Vec<_> = index_scheduler
+        .get_dead_letters()?
+        .into_iter()
+        .filter(|dead_letter| filters.is_index_authorized(&dead_letter.index_uid))
+        .map(DeadLetterView::from_dead_letter)
+        .collect();
+
+    debug!(returns = ?dead_letters, "Get dead letters");
+    Ok(HttpResponse::Ok().json(json!({ "results": dead_letters })))
+}
+
+pub async fn get_dead_letter(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_GET }>, Data<IndexScheduler>>,
+    task_uid: web::Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let task_uid = parse_task_uid(task_uid.into_inner())?;
+    let dead_letter =
+        authorized_dead_letter(&index_scheduler, index_scheduler.filters(), task_uid)?;
+    let dead_letter = DeadLetterView::from_dead_letter(dead_letter);
+
+    debug!(returns = ?dead_letter, "Get dead letter");
+    Ok(HttpResponse::Ok().json(dead_letter))
+}
+
+pub async fn delete_dead_letter(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_DELETE }>, Data<IndexScheduler>>,
+    task_uid: web::Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let task_uid = parse_task_uid(task_uid.into_inner())?;
+    authorized_dead_letter(&index_scheduler, index_scheduler.filters(), task_uid)?;
+    let dead_letter =
+        tokio::task::spawn_blocking(move || index_scheduler.delete_dead_letter(task_uid)).await??;
+    let dead_letter = DeadLetterView::from_dead_letter(dead_letter);
+
+    debug!(returns = ?dead_letter, "Delete dead letter");
+    Ok(HttpResponse::Ok().json(dead_letter))
+}
+
+pub async fn import_dead_letter(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_RETRY }>, Data<IndexScheduler>>,
+    task_uid: web::Path<String>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let task_uid = parse_task_uid(task_uid.into_inner())?;
+    authorized_dead_letter(&index_scheduler, index_scheduler.filters(), task_uid)?;
+
+    analytics.publish("Dead Letter Imported".to_string(), json!({}), Some(&req));
+
+    let task =
+        tokio::task::spawn_blocking(move || index_scheduler.import_dead_letter(task_uid)).await??;
+    let task: SummarizedTaskView = task.into();
+
+    debug!(returns = ?task, "Import dead letter");
+    Ok(HttpResponse::Accepted().json(task))
+}
+
+/// Return the dead letter if its index is visible with the restrictions of the API key.
+fn authorized_dead_letter(
+    index_scheduler: &IndexScheduler,
+    filters: &AuthFilter,
+    task_uid: TaskId,
+) -> Result<DeadLetter, ResponseError> {
+    let dead_letter = index_scheduler.get_dead_letter(task_uid)?;
+    if !filters.is_index_authorized(&dead_letter.index_uid) {
+        return Err(index_scheduler::Error::DeadLetterNotFound(task_uid).into());
+    }
+    Ok(dead_letter)
+}
+
+fn parse_task_uid(task_uid: String) -> Result<TaskId, ResponseError> {
+    task_uid.parse().map_err(|_| index_scheduler::Error::InvalidTaskUids { task_uid }.into())
+}
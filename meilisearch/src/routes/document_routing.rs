@@ -0,0 +1,244 @@
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::io::{BufReader, BufWriter, Seek, Write};
+
+use actix_web::web::Data;
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
+use deserr::actix_web::AwebQueryParameter;
+use deserr::Deserr;
+use index_scheduler::IndexScheduler;
+use meilisearch_types::deserr::query_params::Param;
+use meilisearch_types::deserr::DeserrQueryParamError;
+use meilisearch_types::document_formats::{
+    read_ndjson, DocumentFormatError, FieldMapping, PayloadType,
+};
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::{Code, ResponseError};
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::milli::update::IndexDocumentsMethod;
+use meilisearch_types::milli::{self, Object};
+use meilisearch_types::serde_cs::vec::CS;
+use meilisearch_types::tasks::KindWithContent;
+use serde_json::{json, Value};
+use tracing::debug;
+
+use crate::analytics::Analytics;
+use crate::error::MeilisearchHttpError;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::{AuthenticationError, GuardedData};
+use crate::extractors::payload::Payload;
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::routes::indexes::documents::receive_payload;
+use crate::routes::{is_dry_run, SummarizedTaskView};
+use crate::Opt;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::post().to(SeqHandler(route_documents))));
+}
+
+#[derive(Debug, Deserr)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+pub struct RouteDocumentsQuery {
+    #[deserr(error = DeserrQueryParamError<InvalidDocumentRoutingField>)]
+    routing_field: String,
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentRoutes>)]
+    routes: Option<CS<String>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidIndexUid>)]
+    default_index: Option<Param<IndexUid>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidIndexPrimaryKey>)]
+    primary_key: Option<String>,
+}
+
+/// How the documents are dispatched to the indexes according to the value of their routing field.
+struct Routing {
+    field: String,
+    /// The indexes by value of the routing field, when empty the value is the index uid.
+    routes: HashMap<String, IndexUid>,
+    default_index: Option<IndexUid>,
+}
+
+impl Routing {
+    fn from_query(query: RouteDocumentsQuery) -> Result<Self, ResponseError> {
+        let RouteDocumentsQuery { routing_field, routes, default_index, primary_key: _ } = query;
+        let routes = routes
+            .map_or_else(Vec::new, |routes| routes.into_inner())
+            .into_iter()
+            .map(|route| {
+                let (value, index_uid) = route.rsplit_once(':').ok_or_else(|| {
+                    ResponseError::from_msg(
+                        format!("Invalid route `{route}`, a route is written `value:indexUid`."),
+                        Code::InvalidDocumentRoutes,
+                    )
+                })?;
+                let index_uid = IndexUid::try_from(index_uid.to_string()).map_err(|e| {
+                    ResponseError::from_msg(
+                        format!("Invalid route `{route}`: {e}"),
+                        Code::InvalidDocumentRoutes,
+                    )
+                })?;
+                Ok((value.to_string(), index_uid))
+            })
+            .collect::<Result<_, ResponseError>>()?;
+
+        Ok(Self { field: routing_field, routes, default_index: default_index.map(|p| p.0) })
+    }
+
+    /// Returns the uid of the index of the document, `line` is its position in the payload.
+    fn route(&self, document: &Object, line: usize) -> Result<String, MeilisearchHttpError> {
+        let value = match document.get(&self.field) {
+            Some(Value::String(value)) => Some(value.clone()),
+            Some(Value::Number(value)) => Some(value.to_string()),
+            Some(value) => {
+                return Err(MeilisearchHttpError::DocumentRouting(format!(
+                    "The document at line {line} can't be routed with its `{}` field, \
+                    a string or a number is expected but found `{value}`.",
+                    self.field
+                )))
+            }
+            None => None,
+        };
+
+        let index_uid = match &value {
+            Some(value) if self.routes.is_empty() => {
+                Some(IndexUid::try_from(value.clone()).map_err(|e| {
+                    MeilisearchHttpError::DocumentRouting(format!(
+                        "The document at line {line} can't be routed to `{value}`: {e}"
+                    ))
+                })?)
+            }
+            Some(value) => self.routes.get(value).cloned(),
+            None => None,
+        };
+
+        match (index_uid.or_else(|| self.default_index.clone()), value) {
+            (Some(index_uid), _) => Ok(index_uid.into_inner()),
+            (None, Some(value)) => Err(MeilisearchHttpError::DocumentRouting(format!(
+                "The document at line {line} has no route for its `{}` value `{value}` \
+                and no `defaultIndex` was given.",
+                self.field
+            ))),
+            (None, None) => Err(MeilisearchHttpError::DocumentRouting(format!(
+                "The document at line {line} has no `{}` field to route it \
+                and no `defaultIndex` was given.",
+                self.field
+            ))),
+        }
+    }
+
+    /// Splits the NDJSON payload into one NDJSON file per index uid.
+    fn split(
+        &self,
+        payload: std::fs::File,
+    ) -> Result<BTreeMap<String, std::fs::File>, MeilisearchHttpError> {
+        let mut files = BTreeMap::new();
+        let documents = serde_json::Deserializer::from_reader(BufReader::new(payload));
+        for (line, document) in documents.into_iter::<Object>().enumerate() {
+            let document = document.map_err(|e| {
+                DocumentFormatError::MalformedPayload(
+                    milli::documents::Error::Json(e),
+                    PayloadType::Ndjson,
+                )
+            })?;
+            let file = match files.entry(self.route(&document, line + 1)?) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    let file = tempfile::tempfile().map_err(DocumentFormatError::Io)?;
+                    entry.insert(BufWriter::new(file))
+                }
+            };
+            serde_json::to_writer(&mut *file, &document)?;
+            file.write_all(b"\n").map_err(DocumentFormatError::Io)?;
+        }
+
+        files
+            .into_iter()
+            .map(|(index_uid, file)| {
+                let mut file = file.into_inner().map_err(|e| e.into_error())?;
+                file.rewind()?;
+                Ok((index_uid, file))
+            })
+            .collect::<std::io::Result<_>>()
+            .map_err(|e| DocumentFormatError::Io(e).into())
+    }
+}
+
+/// Registers one document addition per index the documents of the NDJSON payload are routed to.
+pub async fn route_documents(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
+    params: AwebQueryParameter<RouteDocumentsQuery, DeserrQueryParamError>,
+    body: Payload,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let params = params.into_inner();
+    debug!(parameters = ?params, "Route documents");
+
+    let accepted = vec!["application/x-ndjson".to_string()];
+    match req.mime_type() {
+        Ok(Some(mime)) if mime.essence_str() == "application/x-ndjson" => (),
+        Ok(Some(mime)) => {
+            return Err(MeilisearchHttpError::InvalidContentType(mime.to_string(), accepted).into())
+        }
+        _ => return Err(MeilisearchHttpError::MissingContentType(accepted).into()),
+    }
+
+    let primary_key = params.primary_key.clone();
+    let routing = Routing::from_query(params)?;
+    let payload = receive_payload(body, PayloadType::Ndjson).await?;
+    let files = tokio::task::spawn_blocking(move || routing.split(payload)).await??;
+
+    let filters = index_scheduler.filters();
+    if let Some(index_uid) = files.keys().find(|index_uid| !filters.is_index_authorized(index_uid))
+    {
+        debug!(index = %index_uid, "Document routed to a forbidden index");
+        return Err(AuthenticationError::InvalidToken.into());
+    }
+
+    analytics.publish(
+        "Documents Routed".to_string(),
+        json!({ "indexes": files.len(), "primary_key": primary_key.is_some() }),
+        Some(&req),
+    );
+
+    let dry_run = is_dry_run(&req, &opt)?;
+    let mut tasks = Vec::with_capacity(files.len());
+    for (index_uid, file) in files {
+        let allow_index_creation = index_scheduler.filters().allow_index_creation(&index_uid);
+        let primary_key = primary_key.clone();
+        let scheduler = index_scheduler.clone();
+        let rate_limits = index_scheduler.filters().rate_limits();
+        let task = tokio::task::spawn_blocking(move || -> Result<_, MeilisearchHttpError> {
+            let (uuid, mut update_file) = scheduler.create_update_file(dry_run)?;
+            let documents_count = read_ndjson(&file, &mut update_file, &FieldMapping::default())?;
+            update_file.persist()?;
+
+            let task = KindWithContent::DocumentAdditionOrUpdate {
+                method: IndexDocumentsMethod::ReplaceDocuments,
+                content_file: uuid,
+                documents_count,
+                primary_key,
+                allow_index_creation,
+                index_uid,
+            };
+            let task = match rate_limits {
+                Some((key, limits)) => {
+                    scheduler.register_throttled(task, None, dry_run, key, limits)
+                }
+                None => scheduler.register(task, None, dry_run),
+            };
+            match task {
+                Ok(task) => Ok(SummarizedTaskView::from(task)),
+                Err(e) => {
+                    scheduler.delete_update_file(uuid)?;
+                    Err(e.into())
+                }
+            }
+        })
+        .await??;
+        tasks.push(task);
+    }
+
+    debug!(returns = ?tasks, "Route documents");
+    Ok(HttpResponse::Accepted().json(tasks))
+}
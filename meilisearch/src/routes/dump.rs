@@ -1,8 +1,15 @@
+use std::str::FromStr;
+
 use actix_web::web::Data;
 use actix_web::{web, HttpRequest, HttpResponse};
+use deserr::actix_web::{AwebJson, AwebQueryParameter};
+use deserr::Deserr;
 use index_scheduler::IndexScheduler;
 use meilisearch_auth::AuthController;
-use meilisearch_types::error::ResponseError;
+use meilisearch_types::compression::{DumpCompression, ParseDumpCompressionError};
+use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::tasks::KindWithContent;
 use serde_json::json;
 use tracing::debug;
@@ -15,21 +22,37 @@ use crate::routes::{get_task_id, is_dry_run, SummarizedTaskView};
 use crate::Opt;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
-    cfg.service(web::resource("").route(web::post().to(SeqHandler(create_dump))));
+    cfg.service(web::resource("").route(web::post().to(SeqHandler(create_dump))))
+        .service(web::resource("/import").route(web::post().to(SeqHandler(import_dump))));
+}
+
+#[derive(Debug, Deserr)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+pub struct CreateDumpQuery {
+    /// The codec of the dump, optionally followed by its level, e.g. `zstd:3`.
+    #[deserr(default, try_from(&String) = FromStr::from_str -> ParseDumpCompressionError, error = DeserrQueryParamError<InvalidDumpCompression>)]
+    pub compression: DumpCompression,
 }
 
 pub async fn create_dump(
     index_scheduler: GuardedData<ActionPolicy<{ actions::DUMPS_CREATE }>, Data<IndexScheduler>>,
     auth_controller: GuardedData<ActionPolicy<{ actions::DUMPS_CREATE }>, Data<AuthController>>,
+    params: AwebQueryParameter<CreateDumpQuery, DeserrQueryParamError>,
     req: HttpRequest,
     opt: web::Data<Opt>,
     analytics: web::Data<dyn Analytics>,
 ) -> Result<HttpResponse, ResponseError> {
-    analytics.publish("Dump Created".to_string(), json!({}), Some(&req));
+    let CreateDumpQuery { compression } = params.into_inner();
+    analytics.publish(
+        "Dump Created".to_string(),
+        json!({ "compression": compression.codec.to_string() }),
+        Some(&req),
+    );
 
     let task = KindWithContent::DumpCreation {
         keys: auth_controller.list_keys()?,
         instance_uid: analytics.instance_uid().cloned(),
+        compression,
     };
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
@@ -41,3 +64,55 @@ pub async fn create_dump(
     debug!(returns = ?task, "Create dump");
     Ok(HttpResponse::Accepted().json(task))
 }
+
+#[derive(Debug, Deserr)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct DumpImport {
+    /// The uid of a dump of the dumps directory, as returned in the details of its creation task.
+    #[deserr(error = DeserrJsonError<InvalidDumpUid>, missing_field_error = DeserrJsonError::missing_dump_uid)]
+    pub dump_uid: String,
+}
+
+/// Registers a task importing the indexes of a dump into the running instance,
+/// without having to restart it with `--import-dump`.
+pub async fn import_dump(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::ALL }>, Data<IndexScheduler>>,
+    body: AwebJson<DumpImport, DeserrJsonError>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let DumpImport { dump_uid } = body.into_inner();
+    analytics.publish("Dump Imported".to_string(), json!({}), Some(&req));
+    check_dump_exists(&dump_uid, &opt)?;
+
+    let task = KindWithContent::DumpImport { dump_uid, index_uid: None };
+    let uid = get_task_id(&req, &opt)?;
+    let dry_run = is_dry_run(&req, &opt)?;
+    let task: SummarizedTaskView =
+        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
+            .await??
+            .into();
+
+    debug!(returns = ?task, "Import dump");
+    Ok(HttpResponse::Accepted().json(task))
+}
+
+/// Ensures the uid names a dump of the dumps directory, and nothing else.
+pub fn check_dump_exists(dump_uid: &str, opt: &Opt) -> Result<(), ResponseError> {
+    if dump_uid.is_empty()
+        || !dump_uid.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(ResponseError::from_msg(
+            format!("Dump uid `{dump_uid}` is invalid. It should only contain alphanumeric characters, hyphens (-) and underscores (_)."),
+            Code::InvalidDumpUid,
+        ));
+    }
+    if !opt.dump_dir.join(format!("{dump_uid}.dump")).is_file() {
+        return Err(ResponseError::from_msg(
+            format!("Dump `{dump_uid}` not found."),
+            Code::DumpNotFound,
+        ));
+    }
+    Ok(())
+}
@@ -1,11 +1,15 @@
+use std::convert::Infallible;
+
 use actix_web::web::{self, Data};
 use actix_web::{HttpRequest, HttpResponse};
 use deserr::actix_web::AwebJson;
-use deserr::Deserr;
-use index_scheduler::IndexScheduler;
+use deserr::{DeserializeError, Deserr, ErrorKind, ValuePointerRef};
+use index_scheduler::{AutobatchingLimits, IndexScheduler};
 use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::InvalidAutobatchingLimits;
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::keys::actions;
+use meilisearch_types::milli::update::Setting;
 use serde_json::json;
 use tracing::debug;
 
@@ -19,6 +23,11 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
         web::resource("")
             .route(web::get().to(SeqHandler(get_features)))
             .route(web::patch().to(SeqHandler(patch_features))),
+    )
+    .service(
+        web::resource("/autobatching")
+            .route(web::get().to(SeqHandler(get_autobatching)))
+            .route(web::patch().to(SeqHandler(patch_autobatching))),
     );
 }
 
@@ -98,3 +107,85 @@ async fn patch_features(
     debug!(returns = ?new_features, "Patch features");
     Ok(HttpResponse::Ok().json(new_features))
 }
+
+async fn get_autobatching(
+    index_scheduler: GuardedData<
+        ActionPolicy<{ actions::EXPERIMENTAL_FEATURES_GET }>,
+        Data<IndexScheduler>,
+    >,
+) -> HttpResponse {
+    let limits = index_scheduler.autobatching_limits();
+    debug!(returns = ?limits, "Get autobatching limits");
+    HttpResponse::Ok().json(limits)
+}
+
+#[derive(Debug, Deserr)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields, validate = validate_autobatching_limits -> DeserrJsonError<InvalidAutobatchingLimits>)]
+pub struct AutobatchingLimitsUpdate {
+    #[deserr(default, error = DeserrJsonError<InvalidAutobatchingLimits>)]
+    pub enabled: Option<bool>,
+    #[deserr(default, error = DeserrJsonError<InvalidAutobatchingLimits>)]
+    pub max_batched_tasks: Option<usize>,
+    #[deserr(default, error = DeserrJsonError<InvalidAutobatchingLimits>)]
+    pub max_batched_payload_size: Setting<u64>,
+}
+
+/// A limit of zero would never let a batch be built, the scheduler would stop processing the tasks.
+fn validate_autobatching_limits<E: DeserializeError>(
+    update: AutobatchingLimitsUpdate,
+    location: ValuePointerRef,
+) -> Result<AutobatchingLimitsUpdate, E> {
+    let zero = if update.max_batched_tasks == Some(0) {
+        Some("maxBatchedTasks")
+    } else if update.max_batched_payload_size == Setting::Set(0) {
+        Some("maxBatchedPayloadSize")
+    } else {
+        None
+    };
+    match zero {
+        Some(field) => Err(deserr::take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::Unexpected { msg: format!("`{field}` must be greater than 0.") },
+            location,
+        ))),
+        None => Ok(update),
+    }
+}
+
+/// The new limits are only kept in memory, the instance options are used again on restart.
+async fn patch_autobatching(
+    index_scheduler: GuardedData<
+        ActionPolicy<{ actions::EXPERIMENTAL_FEATURES_UPDATE }>,
+        Data<IndexScheduler>,
+    >,
+    update: AwebJson<AutobatchingLimitsUpdate, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let update = update.into_inner();
+    debug!(parameters = ?update, "Patch autobatching limits");
+
+    let old_limits = index_scheduler.autobatching_limits();
+    let new_limits = AutobatchingLimits {
+        enabled: update.enabled.unwrap_or(old_limits.enabled),
+        max_batched_tasks: update.max_batched_tasks.unwrap_or(old_limits.max_batched_tasks),
+        max_batched_payload_size: match update.max_batched_payload_size {
+            Setting::Set(size) => Some(size),
+            Setting::Reset => None,
+            Setting::NotSet => old_limits.max_batched_payload_size,
+        },
+    };
+
+    analytics.publish(
+        "Autobatching limits Updated".to_string(),
+        json!({
+            "enabled": new_limits.enabled,
+            "max_batched_tasks": new_limits.max_batched_tasks,
+            "max_batched_payload_size": new_limits.max_batched_payload_size,
+        }),
+        Some(&req),
+    );
+    index_scheduler.update_autobatching_limits(new_limits);
+    debug!(returns = ?new_limits, "Patch autobatching limits");
+    Ok(HttpResponse::Ok().json(new_limits))
+}
@@ -0,0 +1,116 @@
+use actix_web::web::Data;
+use actix_web::{web, HttpResponse};
+use deserr::actix_web::{AwebJson, AwebQueryParameter};
+use deserr::Deserr;
+use index_scheduler::IndexScheduler;
+use meilisearch_types::deserr::query_params::Param;
+use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::{Code, InvalidTaskDateError, ResponseError};
+use meilisearch_types::index_uid::IndexUid;
+use serde_json::Value;
+use time::OffsetDateTime;
+use tracing::debug;
+
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::query_log::QueryLog;
+use crate::routes::tasks::{deserialize_date, DeserializeDateOption};
+use crate::routes::PAGINATION_DEFAULT_LIMIT;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::get().to(SeqHandler(get_search_analytics))))
+        .service(web::resource("/clicks").route(web::post().to(SeqHandler(record_click))));
+}
+
+#[derive(Deserr, Debug)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+pub struct SearchAnalyticsQuery {
+    /// Only aggregates the searches made from this date or datetime.
+    #[deserr(default, error = DeserrQueryParamError<InvalidAnalyticsFrom>, try_from(Option<String>) = deserialize_from -> InvalidTaskDateError)]
+    pub from: Option<OffsetDateTime>,
+    /// Only aggregates the searches made until this date included, or this datetime excluded.
+    #[deserr(default, error = DeserrQueryParamError<InvalidAnalyticsTo>, try_from(Option<String>) = deserialize_to -> InvalidTaskDateError)]
+    pub to: Option<OffsetDateTime>,
+    /// The number of queries in each list.
+    #[deserr(default = Param(PAGINATION_DEFAULT_LIMIT), error = DeserrQueryParamError<InvalidAnalyticsLimit>)]
+    pub limit: Param<usize>,
+}
+
+fn deserialize_from(
+    value: Option<String>,
+) -> std::result::Result<Option<OffsetDateTime>, InvalidTaskDateError> {
+    value.map(|value| deserialize_date(&value, DeserializeDateOption::Before)).transpose()
+}
+
+fn deserialize_to(
+    value: Option<String>,
+) -> std::result::Result<Option<OffsetDateTime>, InvalidTaskDateError> {
+    value.map(|value| deserialize_date(&value, DeserializeDateOption::After)).transpose()
+}
+
+/// A click on a hit, reported by a front-end.
+#[derive(Deserr, Debug)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct Click {
+    /// The query of the search that returned the hit.
+    #[deserr(error = DeserrJsonError<InvalidClickQ>)]
+    pub q: String,
+    #[deserr(error = DeserrJsonError<InvalidClickDocumentId>)]
+    pub document_id: Value,
+}
+
+fn ensure_query_log_is_enabled(query_log: &QueryLog) -> Result<(), ResponseError> {
+    if query_log.is_enabled() {
+        Ok(())
+    } else {
+        Err(ResponseError::from_msg(
+            "The search analytics are computed from the query log, which is disabled. Launch Meilisearch with the `--query-log` option to enable it."
+                .to_string(),
+            Code::QueryLogDisabled,
+        ))
+    }
+}
+
+/// Returns the most frequent queries of the index, the ones returning no hits and the ones whose
+/// hits are never clicked, computed from the searches and clicks recorded in the query log.
+pub async fn get_search_analytics(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::STATS_GET }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: AwebQueryParameter<SearchAnalyticsQuery, DeserrQueryParamError>,
+    query_log: web::Data<QueryLog>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    ensure_query_log_is_enabled(&query_log)?;
+    // returns an error if the index doesn't exist
+    index_scheduler.index(&index_uid)?;
+
+    let SearchAnalyticsQuery { from, to, limit } = params.into_inner();
+    let analytics =
+        tokio::task::spawn_blocking(move || query_log.analytics(&index_uid, from, to, limit.0))
+            .await??;
+
+    debug!(returns = ?analytics, "Get search analytics");
+    Ok(HttpResponse::Ok().json(analytics))
+}
+
+/// Records a click on a hit of a search in the query log, to find out the queries whose hits are
+/// never clicked.
+pub async fn record_click(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: AwebJson<Click, DeserrJsonError>,
+    query_log: web::Data<QueryLog>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    ensure_query_log_is_enabled(&query_log)?;
+    // returns an error if the index doesn't exist
+    index_scheduler.index(&index_uid)?;
+
+    let Click { q, document_id } = params.into_inner();
+    debug!(q, ?document_id, "Record click");
+    query_log.click(&index_uid, &q, document_id);
+
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -1,22 +1,27 @@
 use std::io::ErrorKind;
+use std::str::FromStr;
 
+use actix_multipart::Multipart;
 use actix_web::http::header::CONTENT_TYPE;
 use actix_web::web::Data;
 use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
 use bstr::ByteSlice as _;
 use deserr::actix_web::{AwebJson, AwebQueryParameter};
 use deserr::Deserr;
-use futures::StreamExt;
+use futures::{Stream, StreamExt};
 use index_scheduler::{IndexScheduler, TaskId};
 use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
-use meilisearch_types::document_formats::{read_csv, read_json, read_ndjson, PayloadType};
+use meilisearch_types::document_formats::{
+    read_csv, read_json, read_mixed, read_ndjson, FieldMapping, InvalidFieldMapping, PayloadType,
+};
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::heed::RoTxn;
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::milli::update::IndexDocumentsMethod;
-use meilisearch_types::milli::DocumentId;
+use meilisearch_types::milli::{AscDesc, DocumentId, SortError};
+use meilisearch_types::serde_cs::vec::CS;
 use meilisearch_types::star_or::OptionStarOrList;
 use meilisearch_types::tasks::KindWithContent;
 use meilisearch_types::{milli, Document, Index};
@@ -30,6 +35,10 @@ use tokio::io::{AsyncSeekExt, AsyncWriteExt, BufWriter};
 use tracing::debug;
 
 use crate::analytics::{Analytics, DocumentDeletionKind, DocumentFetchKind};
+use crate::csv_export::{
+    accepts_csv, csv_response, documents_to_csv, CsvFormat, DEFAULT_CSV_ARRAY_SEPARATOR,
+    DEFAULT_CSV_DELIMITER, DEFAULT_CSV_NESTED_SEPARATOR,
+};
 use crate::error::MeilisearchHttpError;
 use crate::error::PayloadError::ReceivePayload;
 use crate::extractors::authentication::policies::*;
@@ -39,11 +48,16 @@ use crate::extractors::sequential_extractor::SeqHandler;
 use crate::routes::{
     get_task_id, is_dry_run, PaginationView, SummarizedTaskView, PAGINATION_DEFAULT_LIMIT,
 };
-use crate::search::parse_filter;
+use crate::search::{check_restricted_attributes, parse_filter, FilterLimits, SearchQuery};
 use crate::Opt;
 
 static ACCEPTED_CONTENT_TYPE: Lazy<Vec<String>> = Lazy::new(|| {
-    vec!["application/json".to_string(), "application/x-ndjson".to_string(), "text/csv".to_string()]
+    vec![
+        "application/json".to_string(),
+        "application/x-ndjson".to_string(),
+        "text/csv".to_string(),
+        "multipart/form-data".to_string(),
+    ]
 });
 
 /// Extracts the mime type from the content type and return
@@ -114,6 +128,8 @@ pub async fn get_document(
 
     let index = index_scheduler.index(&index_uid)?;
     let document = retrieve_document(&index, &document_id, attributes_to_retrieve)?;
+    let restricted_attributes = index_scheduler.filters().displayed_attributes(&index_uid);
+    let document = restrict_document(document, restricted_attributes.as_deref());
     debug!(returns = ?document, "Get document");
     Ok(HttpResponse::Ok().json(document))
 }
@@ -155,6 +171,14 @@ pub struct BrowseQueryGet {
     fields: OptionStarOrList<String>,
     #[deserr(default, error = DeserrQueryParamError<InvalidDocumentFilter>)]
     filter: Option<String>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentSort>)]
+    sort: Option<CS<String>>,
+    #[deserr(default, try_from(char) = from_char_csv_delimiter -> DeserrQueryParamError<InvalidDocumentCsvDelimiter>, error = DeserrQueryParamError<InvalidDocumentCsvDelimiter>)]
+    csv_delimiter: Option<u8>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentCsvSeparator>)]
+    csv_nested_separator: Option<String>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentCsvSeparator>)]
+    csv_array_separator: Option<String>,
 }
 
 #[derive(Debug, Deserr)]
@@ -168,6 +192,8 @@ pub struct BrowseQuery {
     fields: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidDocumentFilter>)]
     filter: Option<Value>,
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentSort>)]
+    sort: Option<Vec<String>>,
 }
 
 pub async fn documents_by_query_post(
@@ -175,6 +201,7 @@ pub async fn documents_by_query_post(
     index_uid: web::Path<String>,
     body: AwebJson<BrowseQuery, DeserrJsonError>,
     req: HttpRequest,
+    opt: web::Data<Opt>,
     analytics: web::Data<dyn Analytics>,
 ) -> Result<HttpResponse, ResponseError> {
     let body = body.into_inner();
@@ -183,13 +210,24 @@ pub async fn documents_by_query_post(
     analytics.post_fetch_documents(
         &DocumentFetchKind::Normal {
             with_filter: body.filter.is_some(),
+            with_sort: body.sort.is_some(),
             limit: body.limit,
             offset: body.offset,
         },
         &req,
     );
 
-    documents_by_query(&index_scheduler, index_uid, body)
+    // the documents fetched with a POST are written with the default CSV format
+    let csv_format = accepts_csv(&req).then(CsvFormat::default);
+    let restricted_attributes = index_scheduler.filters().displayed_attributes(&index_uid);
+    documents_by_query(
+        &index_scheduler,
+        index_uid,
+        body,
+        FilterLimits::from(opt.as_ref()),
+        restricted_attributes,
+        csv_format,
+    )
 }
 
 pub async fn get_documents(
@@ -197,11 +235,38 @@ pub async fn get_documents(
     index_uid: web::Path<String>,
     params: AwebQueryParameter<BrowseQueryGet, DeserrQueryParamError>,
     req: HttpRequest,
+    opt: web::Data<Opt>,
     analytics: web::Data<dyn Analytics>,
 ) -> Result<HttpResponse, ResponseError> {
     debug!(parameters = ?params, "Get documents GET");
 
-    let BrowseQueryGet { limit, offset, fields, filter } = params.into_inner();
+    let BrowseQueryGet {
+        limit,
+        offset,
+        fields,
+        filter,
+        sort,
+        csv_delimiter,
+        csv_nested_separator,
+        csv_array_separator,
+    } = params.into_inner();
+
+    let csv_format = if accepts_csv(&req) {
+        let nested_separator = csv_separator(
+            "csvNestedSeparator",
+            csv_nested_separator,
+            DEFAULT_CSV_NESTED_SEPARATOR,
+        )?;
+        let array_separator =
+            csv_separator("csvArraySeparator", csv_array_separator, DEFAULT_CSV_ARRAY_SEPARATOR)?;
+        Some(CsvFormat {
+            delimiter: csv_delimiter.unwrap_or(DEFAULT_CSV_DELIMITER),
+            nested_separator,
+            array_separator,
+        })
+    } else {
+        None
+    };
 
     let filter = match filter {
         Some(f) => match serde_json::from_str(&f) {
@@ -216,30 +281,75 @@ pub async fn get_documents(
         limit: limit.0,
         fields: fields.merge_star_and_none(),
         filter,
+        sort: sort.map(CS::into_inner),
     };
 
     analytics.get_fetch_documents(
         &DocumentFetchKind::Normal {
             with_filter: query.filter.is_some(),
+            with_sort: query.sort.is_some(),
             limit: query.limit,
             offset: query.offset,
         },
         &req,
     );
 
-    documents_by_query(&index_scheduler, index_uid, query)
+    let restricted_attributes = index_scheduler.filters().displayed_attributes(&index_uid);
+    documents_by_query(
+        &index_scheduler,
+        index_uid,
+        query,
+        FilterLimits::from(opt.as_ref()),
+        restricted_attributes,
+        csv_format,
+    )
+}
+
+/// Returns the separator or its default value when it's not specified, an empty separator is
+/// rejected as it would merge the columns or the values.
+fn csv_separator(
+    name: &'static str,
+    separator: Option<String>,
+    default: &str,
+) -> Result<String, MeilisearchHttpError> {
+    match separator {
+        Some(separator) if separator.is_empty() => {
+            Err(MeilisearchHttpError::EmptyCsvSeparator(name))
+        }
+        Some(separator) => Ok(separator),
+        None => Ok(default.to_string()),
+    }
 }
 
+/// Returns the documents matching the query, in JSON or, when a CSV format is given, as CSV.
 fn documents_by_query(
     index_scheduler: &IndexScheduler,
     index_uid: web::Path<String>,
     query: BrowseQuery,
+    filter_limits: FilterLimits,
+    restricted_attributes: Option<Vec<String>>,
+    csv_format: Option<CsvFormat>,
 ) -> Result<HttpResponse, ResponseError> {
     let index_uid = IndexUid::try_from(index_uid.into_inner())?;
-    let BrowseQuery { offset, limit, fields, filter } = query;
+    let BrowseQuery { offset, limit, fields, filter, sort } = query;
+
+    // filtering or sorting on a hidden attribute would reveal its values.
+    let used_attributes =
+        SearchQuery { filter: filter.clone(), sort: sort.clone(), ..Default::default() };
+    check_restricted_attributes(&used_attributes, restricted_attributes.as_deref(), filter_limits)?;
 
     let index = index_scheduler.index(&index_uid)?;
-    let (total, documents) = retrieve_documents(&index, offset, limit, filter, fields)?;
+    let (total, documents) =
+        retrieve_documents(&index, offset, limit, filter, sort, fields, filter_limits)?;
+    let documents: Vec<_> = documents
+        .into_iter()
+        .map(|document| restrict_document(document, restricted_attributes.as_deref()))
+        .collect();
+
+    if let Some(csv_format) = csv_format {
+        debug!(returns = documents.len(), "Get documents as CSV");
+        return Ok(csv_response(documents_to_csv(&documents, &csv_format)?));
+    }
 
     let ret = PaginationView::new(offset, limit, total as usize, documents);
 
@@ -254,6 +364,9 @@ pub struct UpdateDocumentsQuery {
     pub primary_key: Option<String>,
     #[deserr(default, try_from(char) = from_char_csv_delimiter -> DeserrQueryParamError<InvalidDocumentCsvDelimiter>, error = DeserrQueryParamError<InvalidDocumentCsvDelimiter>)]
     pub csv_delimiter: Option<u8>,
+    #[deserr(default, try_from(&String) = FromStr::from_str -> InvalidFieldMapping, error = DeserrQueryParamError<InvalidDocumentFieldMapping>)]
+    #[serde(default)]
+    pub field_mapping: FieldMapping,
 }
 
 fn from_char_csv_delimiter(
@@ -289,11 +402,12 @@ pub async fn replace_documents(
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
     let task = document_addition(
-        extract_mime_type(&req)?,
+        &req,
         index_scheduler,
         index_uid,
         params.primary_key,
         params.csv_delimiter,
+        params.field_mapping,
         body,
         IndexDocumentsMethod::ReplaceDocuments,
         uid,
@@ -326,11 +440,12 @@ pub async fn update_documents(
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
     let task = document_addition(
-        extract_mime_type(&req)?,
+        &req,
         index_scheduler,
         index_uid,
         params.primary_key,
         params.csv_delimiter,
+        params.field_mapping,
         body,
         IndexDocumentsMethod::UpdateDocuments,
         uid,
@@ -343,51 +458,76 @@ pub async fn update_documents(
     Ok(HttpResponse::Accepted().json(task))
 }
 
-#[allow(clippy::too_many_arguments)]
-async fn document_addition(
-    mime_type: Option<Mime>,
-    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
-    index_uid: IndexUid,
-    primary_key: Option<String>,
+/// Returns the format of a payload according to its mime type.
+fn payload_type(
+    mime_type: Option<&Mime>,
     csv_delimiter: Option<u8>,
-    mut body: Payload,
-    method: IndexDocumentsMethod,
-    task_id: Option<TaskId>,
-    dry_run: bool,
-    allow_index_creation: bool,
-) -> Result<SummarizedTaskView, MeilisearchHttpError> {
-    let format = match (
-        mime_type.as_ref().map(|m| (m.type_().as_str(), m.subtype().as_str())),
-        csv_delimiter,
-    ) {
-        (Some(("application", "json")), None) => PayloadType::Json,
-        (Some(("application", "x-ndjson")), None) => PayloadType::Ndjson,
-        (Some(("text", "csv")), None) => PayloadType::Csv { delimiter: b',' },
-        (Some(("text", "csv")), Some(delimiter)) => PayloadType::Csv { delimiter },
-
-        (Some(("application", "json")), Some(_)) => {
-            return Err(MeilisearchHttpError::CsvDelimiterWithWrongContentType(String::from(
-                "application/json",
-            )))
-        }
-        (Some(("application", "x-ndjson")), Some(_)) => {
-            return Err(MeilisearchHttpError::CsvDelimiterWithWrongContentType(String::from(
-                "application/x-ndjson",
-            )))
-        }
-        (Some((type_, subtype)), _) => {
-            return Err(MeilisearchHttpError::InvalidContentType(
-                format!("{}/{}", type_, subtype),
-                ACCEPTED_CONTENT_TYPE.clone(),
-            ))
-        }
-        (None, _) => {
-            return Err(MeilisearchHttpError::MissingContentType(ACCEPTED_CONTENT_TYPE.clone()))
-        }
-    };
+) -> Result<PayloadType, MeilisearchHttpError> {
+    let format =
+        match (mime_type.map(|m| (m.type_().as_str(), m.subtype().as_str())), csv_delimiter) {
+            (Some(("application", "json")), None) => PayloadType::Json,
+            (Some(("application", "x-ndjson")), None) => PayloadType::Ndjson,
+            (Some(("text", "csv")), None) => PayloadType::Csv { delimiter: b',' },
+            (Some(("text", "csv")), Some(delimiter)) => PayloadType::Csv { delimiter },
+
+            (Some(("application", "json")), Some(_)) => {
+                return Err(MeilisearchHttpError::CsvDelimiterWithWrongContentType(String::from(
+                    "application/json",
+                )))
+            }
+            (Some(("application", "x-ndjson")), Some(_)) => {
+                return Err(MeilisearchHttpError::CsvDelimiterWithWrongContentType(String::from(
+                    "application/x-ndjson",
+                )))
+            }
+            (Some((type_, subtype)), _) => {
+                return Err(MeilisearchHttpError::InvalidContentType(
+                    format!("{}/{}", type_, subtype),
+                    ACCEPTED_CONTENT_TYPE.clone(),
+                ))
+            }
+            (None, _) => {
+                return Err(MeilisearchHttpError::MissingContentType(ACCEPTED_CONTENT_TYPE.clone()))
+            }
+        };
 
-    let (uuid, mut update_file) = index_scheduler.create_update_file(dry_run)?;
+    Ok(format)
+}
 
+/// Returns the format of a file sent in a multipart form, according to its own Content-Type
+/// or, when it is missing or too generic, to the extension of its file name.
+fn multipart_file_payload_type(
+    field: &actix_multipart::Field,
+    csv_delimiter: Option<u8>,
+) -> Result<PayloadType, MeilisearchHttpError> {
+    let mime_type = field.content_type().filter(|mime| **mime != mime::APPLICATION_OCTET_STREAM);
+    if let Some(mime_type) = mime_type {
+        // the csv delimiter only applies to the csv files of the form
+        let csv_delimiter = csv_delimiter.filter(|_| mime_type.subtype() == mime::CSV);
+        return payload_type(Some(mime_type), csv_delimiter);
+    }
+
+    let extension = field
+        .content_disposition()
+        .get_filename()
+        .and_then(|filename| filename.rsplit_once('.'))
+        .map(|(_, extension)| extension.to_lowercase());
+    match extension.as_deref() {
+        Some("json") => Ok(PayloadType::Json),
+        Some("ndjson" | "jsonl") => Ok(PayloadType::Ndjson),
+        Some("csv") => Ok(PayloadType::Csv { delimiter: csv_delimiter.unwrap_or(b',') }),
+        _ => Err(MeilisearchHttpError::MissingContentType(ACCEPTED_CONTENT_TYPE.clone())),
+    }
+}
+
+/// Writes the whole payload into a temporary file and returns it rewinded.
+pub(crate) async fn receive_payload<E>(
+    payload: impl Stream<Item = Result<web::Bytes, E>>,
+    format: PayloadType,
+) -> Result<std::fs::File, MeilisearchHttpError>
+where
+    MeilisearchHttpError: From<E>,
+{
     let temp_file = match tempfile() {
         Ok(file) => file,
         Err(e) => return Err(MeilisearchHttpError::Payload(ReceivePayload(Box::new(e)))),
@@ -396,8 +536,9 @@ async fn document_addition(
     let async_file = File::from_std(temp_file);
     let mut buffer = BufWriter::new(async_file);
 
+    futures::pin_mut!(payload);
     let mut buffer_write_size: usize = 0;
-    while let Some(result) = body.next().await {
+    while let Some(result) = payload.next().await {
         let byte = result?;
 
         if byte.is_empty() && buffer_write_size == 0 {
@@ -422,12 +563,87 @@ async fn document_addition(
         return Err(MeilisearchHttpError::Payload(ReceivePayload(Box::new(e))));
     }
 
-    let read_file = buffer.into_inner().into_std().await;
+    Ok(buffer.into_inner().into_std().await)
+}
+
+/// Receives every file of a `multipart/form-data` payload, the parts that are not files are ignored.
+async fn receive_multipart_payloads(
+    req: &HttpRequest,
+    body: Payload,
+    csv_delimiter: Option<u8>,
+) -> Result<Vec<(std::fs::File, PayloadType)>, MeilisearchHttpError> {
+    // The multipart parser expects the errors of the raw actix payload, we make sure
+    // to keep the payload size limit error as is, so it can be reported properly.
+    let body = body.map(|result| {
+        result.map_err(|e| match e {
+            MeilisearchHttpError::PayloadTooLarge(_) => actix_web::error::PayloadError::Overflow,
+            MeilisearchHttpError::Payload(crate::error::PayloadError::Payload(e)) => e,
+            e => actix_web::error::PayloadError::Io(std::io::Error::other(e.to_string())),
+        })
+    });
+    let mut multipart = Multipart::new(req.headers(), body);
+
+    let mut payloads = Vec::new();
+    while let Some(field) = multipart.next().await {
+        let field = field?;
+        if field.content_disposition().get_filename().is_none() {
+            continue;
+        }
+        let format = multipart_file_payload_type(&field, csv_delimiter)?;
+        let file = receive_payload(field, format).await?;
+        payloads.push((file, format));
+    }
+
+    if payloads.is_empty() {
+        return Err(MeilisearchHttpError::MissingMultipartFiles);
+    }
+
+    Ok(payloads)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn document_addition(
+    req: &HttpRequest,
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
+    index_uid: IndexUid,
+    primary_key: Option<String>,
+    csv_delimiter: Option<u8>,
+    field_mapping: FieldMapping,
+    body: Payload,
+    method: IndexDocumentsMethod,
+    task_id: Option<TaskId>,
+    dry_run: bool,
+    allow_index_creation: bool,
+) -> Result<SummarizedTaskView, MeilisearchHttpError> {
+    let mime_type = extract_mime_type(req)?;
+    let payloads = match &mime_type {
+        Some(mime) if mime.type_() == mime::MULTIPART && mime.subtype() == mime::FORM_DATA => {
+            receive_multipart_payloads(req, body, csv_delimiter).await?
+        }
+        _ => {
+            let format = payload_type(mime_type.as_ref(), csv_delimiter)?;
+            vec![(receive_payload(body, format).await?, format)]
+        }
+    };
+
+    let (uuid, mut update_file) = index_scheduler.create_update_file(dry_run)?;
+
     let documents_count = tokio::task::spawn_blocking(move || {
-        let documents_count = match format {
-            PayloadType::Json => read_json(&read_file, &mut update_file)?,
-            PayloadType::Csv { delimiter } => read_csv(&read_file, &mut update_file, delimiter)?,
-            PayloadType::Ndjson => read_ndjson(&read_file, &mut update_file)?,
+        let documents_count = match payloads.as_slice() {
+            [(read_file, PayloadType::Json)] => {
+                read_json(read_file, &mut update_file, &field_mapping)?
+            }
+            [(read_file, PayloadType::Csv { delimiter })] => {
+                read_csv(read_file, &mut update_file, *delimiter, &field_mapping)?
+            }
+            [(read_file, PayloadType::Ndjson)] => {
+                read_ndjson(read_file, &mut update_file, &field_mapping)?
+            }
+            payloads => read_mixed(
+                payloads.iter().map(|(read_file, format)| (read_file, *format)),
+                &mut update_file,
+                &field_mapping,
+            )?,
         };
         // we NEED to persist the file here because we moved the `udpate_file` in another task.
         update_file.persist()?;
@@ -468,8 +684,12 @@ async fn document_addition(
     };
 
     let scheduler = index_scheduler.clone();
-    let task = match tokio::task::spawn_blocking(move || scheduler.register(task, task_id, dry_run))
-        .await?
+    let rate_limits = index_scheduler.filters().rate_limits();
+    let task = match tokio::task::spawn_blocking(move || match rate_limits {
+        Some((key, limits)) => scheduler.register_throttled(task, task_id, dry_run, key, limits),
+        None => scheduler.register(task, task_id, dry_run),
+    })
+    .await?
     {
         Ok(task) => task,
         Err(e) => {
@@ -534,12 +754,16 @@ pub async fn delete_documents_by_filter(
 
     analytics.delete_documents(DocumentDeletionKind::PerFilter, &req);
 
-    // we ensure the filter is well formed before enqueuing it
-    || -> Result<_, ResponseError> {
-        Ok(crate::search::parse_filter(&filter)?.ok_or(MeilisearchHttpError::EmptyFilter)?)
+    // we ensure the filter is well formed and not too complex before enqueuing it
+    || -> Result<_, MeilisearchHttpError> {
+        parse_filter(&filter, FilterLimits::from(opt.as_ref()))?
+            .ok_or(MeilisearchHttpError::EmptyFilter)
     }()
-    // and whatever was the error, the error code should always be an InvalidDocumentFilter
-    .map_err(|err| ResponseError::from_msg(err.message, Code::InvalidDocumentFilter))?;
+    // and except for a too complex filter, the error code should always be an InvalidDocumentFilter
+    .map_err(|err| match err {
+        MeilisearchHttpError::FilterTooComplex { .. } => ResponseError::from(err),
+        err => ResponseError::from_msg(err.to_string(), Code::InvalidDocumentFilter),
+    })?;
     let task = KindWithContent::DocumentDeletionByFilter { index_uid, filter_expr: filter };
 
     let uid = get_task_id(&req, &opt)?;
@@ -595,13 +819,17 @@ fn retrieve_documents<S: AsRef<str>>(
     offset: usize,
     limit: usize,
     filter: Option<Value>,
+    sort: Option<Vec<String>>,
     attributes_to_retrieve: Option<Vec<S>>,
+    filter_limits: FilterLimits,
 ) -> Result<(u64, Vec<Document>), ResponseError> {
     let rtxn = index.read_txn()?;
     let filter = &filter;
     let filter = if let Some(filter) = filter {
-        parse_filter(filter)
-            .map_err(|err| ResponseError::from_msg(err.to_string(), Code::InvalidDocumentFilter))?
+        parse_filter(filter, filter_limits).map_err(|err| match err {
+            MeilisearchHttpError::FilterTooComplex { .. } => err.into(),
+            err => ResponseError::from_msg(err.to_string(), Code::InvalidDocumentFilter),
+        })?
     } else {
         None
     };
@@ -617,13 +845,32 @@ fn retrieve_documents<S: AsRef<str>>(
         index.documents_ids(&rtxn)?
     };
 
-    let (it, number_of_documents) = {
-        let number_of_documents = candidates.len();
-        (
-            some_documents(index, &rtxn, candidates.into_iter().skip(offset).take(limit))?,
-            number_of_documents,
-        )
+    let number_of_documents = candidates.len();
+    let documents_ids: Vec<_> = match sort {
+        Some(sort) => {
+            let sort = sort
+                .iter()
+                .map(|s| AscDesc::from_str(s).map_err(SortError::from))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|err| {
+                    ResponseError::from_msg(err.to_string(), Code::InvalidDocumentSort)
+                })?;
+            // the documents are only sorted up to the last one of the page
+            milli::sort_documents(index, &rtxn, candidates, &sort, offset.saturating_add(limit))
+                .map_err(|err| match err {
+                    milli::Error::UserError(
+                        milli::UserError::SortError(_)
+                        | milli::UserError::InvalidSortableAttribute { .. },
+                    ) => ResponseError::from_msg(err.to_string(), Code::InvalidDocumentSort),
+                    e => e.into(),
+                })?
+                .into_iter()
+                .skip(offset)
+                .collect()
+        }
+        None => candidates.into_iter().skip(offset).take(limit).collect(),
     };
+    let it = some_documents(index, &rtxn, documents_ids)?;
 
     let documents: Result<Vec<_>, ResponseError> = it
         .map(|document| {
@@ -640,6 +887,16 @@ fn retrieve_documents<S: AsRef<str>>(
     Ok((number_of_documents, documents?))
 }
 
+/// Only keeps the attributes of the document that the API key or the tenant token can retrieve.
+fn restrict_document(document: Document, restricted_attributes: Option<&[String]>) -> Document {
+    match restricted_attributes {
+        Some(attributes) if !attributes.iter().any(|attr| attr == "*") => {
+            permissive_json_pointer::select_values(&document, attributes.iter().map(String::as_str))
+        }
+        _ => document,
+    }
+}
+
 fn retrieve_document<S: AsRef<str>>(
     index: &Index,
     doc_id: &str,
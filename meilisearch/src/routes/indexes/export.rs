@@ -0,0 +1,77 @@
+use actix_web::web::Data;
+use actix_web::{web, HttpRequest, HttpResponse};
+use deserr::actix_web::AwebJson;
+use index_scheduler::IndexScheduler;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::tasks::KindWithContent;
+use serde_json::json;
+use tracing::debug;
+
+use crate::analytics::Analytics;
+use crate::error::MeilisearchHttpError;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::routes::dump::{check_dump_exists, DumpImport};
+use crate::routes::{get_task_id, is_dry_run, SummarizedTaskView};
+use crate::Opt;
+
+/// Registers a task writing the documents and settings of the index into a dump of the dumps
+/// directory. The uid of the dump is in the details of the task once it succeeded.
+pub async fn export_index(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DUMPS_CREATE }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    // the dump would contain the attributes the API key or the tenant token can't access.
+    if index_scheduler
+        .filters()
+        .displayed_attributes(&index_uid)
+        .is_some_and(|attributes| !attributes.iter().any(|attr| attr == "*"))
+    {
+        return Err(MeilisearchHttpError::ForbiddenIndexExport(index_uid.into_inner()).into());
+    }
+    analytics.publish("Index Exported".to_string(), json!({}), Some(&req));
+
+    let task = KindWithContent::IndexExport { index_uid: index_uid.into_inner() };
+    let uid = get_task_id(&req, &opt)?;
+    let dry_run = is_dry_run(&req, &opt)?;
+    let task: SummarizedTaskView =
+        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
+            .await??
+            .into();
+
+    debug!(returns = ?task, "Export index");
+    Ok(HttpResponse::Accepted().json(task))
+}
+
+/// Registers a task creating the index from the single index of a dump of the dumps directory,
+/// usually exported from another instance.
+pub async fn import_index(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::ALL }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    body: AwebJson<DumpImport, DeserrJsonError>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let DumpImport { dump_uid } = body.into_inner();
+    analytics.publish("Index Imported".to_string(), json!({}), Some(&req));
+    check_dump_exists(&dump_uid, &opt)?;
+
+    let task = KindWithContent::DumpImport { dump_uid, index_uid: Some(index_uid.into_inner()) };
+    let uid = get_task_id(&req, &opt)?;
+    let dry_run = is_dry_run(&req, &opt)?;
+    let task: SummarizedTaskView =
+        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
+            .await??
+            .into();
+
+    debug!(returns = ?task, "Import index");
+    Ok(HttpResponse::Accepted().json(task))
+}
@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+
+use actix_web::web::Data;
+use actix_web::{web, HttpRequest, HttpResponse};
+use deserr::actix_web::AwebQueryParameter;
+use index_scheduler::IndexScheduler;
+use indexmap::IndexMap;
+use meilisearch_types::deserr::DeserrQueryParamError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::serde_cs::vec::CS;
+use serde::Serialize;
+use serde_json::{json, Value};
+use tracing::debug;
+
+use crate::analytics::Analytics;
+use crate::csv_export::{accepts_csv, csv_response, facet_distribution_to_csv};
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::search::{
+    add_search_rules, check_restricted_attributes, perform_search, FacetStats, FilterLimits,
+    SearchQuery,
+};
+use crate::search_queue::SearchQueue;
+use crate::Opt;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::get().to(SeqHandler(get_facet_distribution))));
+}
+
+#[derive(Debug, deserr::Deserr)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+pub struct FacetDistributionQuery {
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchFacets>)]
+    facets: Option<CS<String>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchFilter>)]
+    filter: Option<String>,
+}
+
+/// The number of documents matching the filter for each value of the facets.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FacetDistributionResult {
+    facet_distribution: BTreeMap<String, IndexMap<String, u64>>,
+    facet_stats: BTreeMap<String, FacetStats>,
+}
+
+/// Returns the facet distribution of the documents matching the filter, without any hit. All the
+/// filterable attributes are distributed when no facets are given.
+///
+/// The distribution is returned as `facet,value,count` rows when the request accepts `text/csv`.
+pub async fn get_facet_distribution(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: AwebQueryParameter<FacetDistributionQuery, DeserrQueryParamError>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<dyn Analytics>,
+    search_queue: web::Data<SearchQueue>,
+) -> Result<HttpResponse, ResponseError> {
+    debug!(parameters = ?params, "Facet distribution");
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let FacetDistributionQuery { facets, filter } = params.into_inner();
+    let csv = accepts_csv(&req);
+    analytics.publish("Facet Distribution Fetched".to_string(), json!({ "csv": csv }), Some(&req));
+
+    let filter = filter.map(|f| serde_json::from_str(&f).unwrap_or(Value::String(f)));
+    let facets: Vec<String> = match facets {
+        Some(facets) => facets.into_iter().collect(),
+        None => vec!["*".to_string()],
+    };
+    let mut query =
+        SearchQuery { facets: Some(facets.into()), filter, limit: 0, ..Default::default() };
+
+    let filter_limits = FilterLimits::from(opt.as_ref());
+    let restricted_attributes = index_scheduler.filters().displayed_attributes(&index_uid);
+    check_restricted_attributes(&query, restricted_attributes.as_deref(), filter_limits)?;
+
+    // Tenant token search_rules.
+    if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
+        add_search_rules(&mut query, search_rules);
+    }
+
+    let index = index_scheduler.index(&index_uid)?;
+    let features = index_scheduler.features();
+    let search_cutoff_ms = opt.search_cutoff_ms;
+    let permit = search_queue.try_get_search_permit().await?;
+    let search_result = tokio::task::spawn_blocking(move || {
+        let _permit = permit;
+        perform_search(
+            &index,
+            query,
+            features,
+            filter_limits,
+            None,
+            restricted_attributes,
+            search_cutoff_ms,
+            false,
+        )
+    })
+    .await??;
+
+    let result = FacetDistributionResult {
+        facet_distribution: search_result.facet_distribution.unwrap_or_default(),
+        facet_stats: search_result.facet_stats.unwrap_or_default(),
+    };
+
+    debug!(returns = ?result, "Facet distribution");
+    if csv {
+        Ok(csv_response(facet_distribution_to_csv(&result.facet_distribution)?))
+    } else {
+        Ok(HttpResponse::Ok().json(result))
+    }
+}
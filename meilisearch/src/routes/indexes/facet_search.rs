@@ -10,13 +10,17 @@ use serde_json::Value;
 use tracing::debug;
 
 use crate::analytics::{Analytics, FacetSearchAggregator};
+use crate::error::MeilisearchHttpError;
 use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
 use crate::search::{
-    add_search_rules, perform_facet_search, HybridQuery, MatchingStrategy, SearchQuery,
-    DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG,
-    DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET,
+    add_search_rules, check_restricted_attributes, is_attribute_allowed, perform_facet_search,
+    FilterLimits, HybridQuery, MatchingStrategy, SearchQuery, DEFAULT_CROP_LENGTH,
+    DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG,
+    DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET,
 };
+use crate::search_queue::SearchQueue;
+use crate::Opt;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(web::resource("").route(web::post().to(search)));
@@ -51,7 +55,9 @@ pub async fn search(
     index_uid: web::Path<String>,
     params: AwebJson<FacetSearchQuery, DeserrJsonError>,
     req: HttpRequest,
+    opt: web::Data<Opt>,
     analytics: web::Data<dyn Analytics>,
+    search_queue: web::Data<SearchQueue>,
 ) -> Result<HttpResponse, ResponseError> {
     let index_uid = IndexUid::try_from(index_uid.into_inner())?;
 
@@ -64,6 +70,13 @@ pub async fn search(
     let facet_name = query.facet_name.clone();
     let mut search_query = SearchQuery::from(query);
 
+    let filter_limits = FilterLimits::from(opt.as_ref());
+    let restricted_attributes = index_scheduler.filters().displayed_attributes(&index_uid);
+    if !is_attribute_allowed(restricted_attributes.as_deref(), &facet_name) {
+        return Err(MeilisearchHttpError::ForbiddenAttribute(facet_name).into());
+    }
+    check_restricted_attributes(&search_query, restricted_attributes.as_deref(), filter_limits)?;
+
     // Tenant token search_rules.
     if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
         add_search_rules(&mut search_query, search_rules);
@@ -71,8 +84,10 @@ pub async fn search(
 
     let index = index_scheduler.index(&index_uid)?;
     let features = index_scheduler.features();
+    let permit = search_queue.try_get_search_permit().await?;
     let search_result = tokio::task::spawn_blocking(move || {
-        perform_facet_search(&index, search_query, facet_query, facet_name, features)
+        let _permit = permit;
+        perform_facet_search(&index, search_query, facet_query, facet_name, features, filter_limits)
     })
     .await?;
 
@@ -115,13 +130,22 @@ impl From<FacetSearchQuery> for SearchQuery {
             show_ranking_score_details: false,
             filter,
             sort: None,
+            sort_first: None,
+            retrieve_vectors: None,
             facets: None,
+            facet_filters: None,
+            disjunctive_facets: false,
+            aggregates: None,
             highlight_pre_tag: DEFAULT_HIGHLIGHT_PRE_TAG(),
             highlight_post_tag: DEFAULT_HIGHLIGHT_POST_TAG(),
             crop_marker: DEFAULT_CROP_MARKER(),
             matching_strategy,
             vector,
             attributes_to_search_on,
+            locales: None,
+            boosts: Vec::new(),
+            group_by: None,
+            nested: None,
             hybrid,
         }
     }
@@ -0,0 +1,142 @@
+use std::collections::BTreeSet;
+
+use actix_web::web::Data;
+use actix_web::{web, HttpRequest, HttpResponse};
+use index_scheduler::IndexScheduler;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::milli::{self, FieldDistribution};
+use serde::Serialize;
+use serde_json::json;
+use tracing::debug;
+
+use crate::analytics::Analytics;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::field_usage::FieldUsage;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::get().to(SeqHandler(get_field_suggestions))));
+}
+
+/// The changes suggested to the settings of an index, by analyzing the fields used by its recent
+/// searches and the fields its documents are made of. It's only advisory: nothing is applied.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldSuggestions {
+    /// The number of recent searches the suggestions are based on.
+    analyzed_searches: usize,
+    filterable_attributes: AttributesSuggestion,
+    sortable_attributes: AttributesSuggestion,
+    searchable_attributes: AttributesSuggestion,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttributesSuggestion {
+    /// The fields used by the searches that should be added to the setting.
+    add: BTreeSet<String>,
+    /// The fields of the setting that aren't in any document or that no recent search used.
+    unused: BTreeSet<String>,
+}
+
+pub async fn get_field_suggestions(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_GET }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    field_usage: web::Data<FieldUsage>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    analytics.publish("Field Suggestions Seen".to_string(), json!({}), Some(&req));
+
+    let index = index_scheduler.index(&index_uid)?;
+    let rtxn = index.read_txn()?;
+    let field_distribution = index.field_distribution(&rtxn)?;
+    let filterable: BTreeSet<_> = index.filterable_fields(&rtxn)?.into_iter().collect();
+    let sortable: BTreeSet<_> = index.sortable_fields(&rtxn)?.into_iter().collect();
+    let searchable: Option<BTreeSet<_>> = index
+        .user_defined_searchable_fields(&rtxn)?
+        .map(|fields| fields.into_iter().map(String::from).collect());
+
+    let searches = field_usage.recent(&index_uid);
+    let mut used_in_filters = BTreeSet::new();
+    let mut used_in_sorts = BTreeSet::new();
+    let mut used_in_search_on = BTreeSet::new();
+    for used in &searches {
+        used_in_filters.extend(used.filter.iter().chain(&used.facets).cloned());
+        used_in_sorts.extend(used.sort.iter().cloned());
+        used_in_search_on.extend(used.search_on.iter().cloned());
+    }
+
+    let searchable_attributes = match searchable {
+        Some(searchable) => AttributesSuggestion {
+            add: used_in_search_on
+                .into_iter()
+                .filter(|field| !searchable.contains(field) && exists(field, &field_distribution))
+                .collect(),
+            // a searchable field is used by all the searches without `attributesToSearchOn`,
+            // so we only report the ones that are in no document.
+            unused: searchable
+                .into_iter()
+                .filter(|field| !exists(field, &field_distribution))
+                .collect(),
+        },
+        // all the fields are searchable
+        None => AttributesSuggestion::default(),
+    };
+
+    let suggestions = FieldSuggestions {
+        analyzed_searches: searches.len(),
+        filterable_attributes: suggest(
+            &filterable,
+            &used_in_filters,
+            &field_distribution,
+            searches.len(),
+        ),
+        sortable_attributes: suggest(
+            &sortable,
+            &used_in_sorts,
+            &field_distribution,
+            searches.len(),
+        ),
+        searchable_attributes,
+    };
+
+    debug!(returns = ?suggestions, "Get field suggestions");
+    Ok(HttpResponse::Ok().json(suggestions))
+}
+
+/// Suggests to add the used fields that are not configured yet and to remove the configured fields
+/// that are in no document or, when there are searches to analyze, that no search used.
+fn suggest(
+    configured: &BTreeSet<String>,
+    used: &BTreeSet<String>,
+    field_distribution: &FieldDistribution,
+    analyzed_searches: usize,
+) -> AttributesSuggestion {
+    let add = used
+        .iter()
+        .filter(|field| !milli::is_faceted(field, configured) && exists(field, field_distribution))
+        .cloned()
+        .collect();
+    let unused = configured
+        .iter()
+        .filter(|field| {
+            !exists(field, field_distribution)
+                || (analyzed_searches != 0
+                    && !used.iter().any(|used| milli::is_faceted_by(used, field)))
+        })
+        .cloned()
+        .collect();
+
+    AttributesSuggestion { add, unused }
+}
+
+/// Returns `true` if the field, one of its parents or one of its children is in a document.
+fn exists(field: &str, field_distribution: &FieldDistribution) -> bool {
+    field_distribution.keys().any(|distributed| {
+        milli::is_faceted_by(distributed, field) || milli::is_faceted_by(field, distributed)
+    })
+}
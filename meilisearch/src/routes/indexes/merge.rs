@@ -0,0 +1,70 @@
+use actix_web::web::Data;
+use actix_web::{web, HttpRequest, HttpResponse};
+use deserr::actix_web::AwebJson;
+use deserr::Deserr;
+use index_scheduler::IndexScheduler;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::{Code, ResponseError};
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::tasks::{KindWithContent, MergeConflictPolicy};
+use serde_json::json;
+use tracing::debug;
+
+use crate::analytics::Analytics;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::{AuthenticationError, GuardedData};
+use crate::routes::{get_task_id, is_dry_run, SummarizedTaskView};
+use crate::Opt;
+
+#[derive(Debug, Deserr)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct IndexMerge {
+    #[deserr(error = DeserrJsonError<InvalidIndexMergeSource>, missing_field_error = DeserrJsonError::missing_index_merge_source)]
+    source: IndexUid,
+    #[deserr(default, error = DeserrJsonError<InvalidIndexMergeOnConflict>)]
+    on_conflict: MergeConflictPolicy,
+}
+
+/// Registers a task adding the documents of the source index into the index.
+pub async fn merge_index(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    body: AwebJson<IndexMerge, DeserrJsonError>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let IndexMerge { source, on_conflict } = body.into_inner();
+    if source == index_uid {
+        return Err(ResponseError::from_msg(
+            format!("Index `{index_uid}` can't be merged into itself."),
+            Code::InvalidIndexMergeSource,
+        ));
+    }
+    if !index_scheduler.filters().is_index_authorized(&source) {
+        return Err(AuthenticationError::InvalidToken.into());
+    }
+
+    analytics.publish(
+        "Index Merged".to_string(),
+        json!({ "on_conflict": on_conflict.to_string() }),
+        Some(&req),
+    );
+
+    let task = KindWithContent::IndexMerge {
+        index_uid: index_uid.into_inner(),
+        source_index_uid: source.into_inner(),
+        on_conflict,
+    };
+    let uid = get_task_id(&req, &opt)?;
+    let dry_run = is_dry_run(&req, &opt)?;
+    let task: SummarizedTaskView =
+        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
+            .await??
+            .into();
+
+    debug!(returns = ?task, "Merge index");
+    Ok(HttpResponse::Accepted().json(task))
+}
@@ -3,18 +3,21 @@ use std::convert::Infallible;
 use actix_web::web::Data;
 use actix_web::{web, HttpRequest, HttpResponse};
 use deserr::actix_web::{AwebJson, AwebQueryParameter};
-use deserr::{DeserializeError, Deserr, ValuePointerRef};
-use index_scheduler::IndexScheduler;
+use deserr::{DeserializeError, Deserr, ErrorKind, IntoValue, ValueKind, ValuePointerRef};
+use index_scheduler::{IndexScheduler, Query};
+use meilisearch_auth::AuthFilter;
 use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::{immutable_field_error, DeserrJsonError, DeserrQueryParamError};
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::milli::documents::{composite_primary_key, composite_primary_key_fields};
 use meilisearch_types::milli::{self, FieldDistribution, Index};
-use meilisearch_types::tasks::KindWithContent;
+use meilisearch_types::task_view::PrimaryKeyView;
+use meilisearch_types::tasks::{serialize_duration, KindWithContent, Status};
 use serde::Serialize;
 use serde_json::json;
-use time::OffsetDateTime;
+use time::{Duration, OffsetDateTime};
 use tracing::debug;
 
 use super::{get_task_id, Pagination, SummarizedTaskView, PAGINATION_DEFAULT_LIMIT};
@@ -25,10 +28,17 @@ use crate::extractors::sequential_extractor::SeqHandler;
 use crate::routes::is_dry_run;
 use crate::Opt;
 
+pub mod analytics;
 pub mod documents;
+pub mod export;
+pub mod facet_distribution;
 pub mod facet_search;
+pub mod field_suggestions;
+pub mod merge;
+pub mod previews;
 pub mod search;
 pub mod settings;
+pub mod split;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(
@@ -45,9 +55,21 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
                     .route(web::delete().to(SeqHandler(delete_index))),
             )
             .service(web::resource("/stats").route(web::get().to(SeqHandler(get_index_stats))))
+            .service(web::scope("/analytics").configure(analytics::configure))
+            .service(
+                web::resource("/export").route(web::post().to(SeqHandler(export::export_index))),
+            )
+            .service(
+                web::resource("/import").route(web::post().to(SeqHandler(export::import_index))),
+            )
+            .service(web::resource("/merge").route(web::post().to(SeqHandler(merge::merge_index))))
+            .service(web::resource("/split").route(web::post().to(SeqHandler(split::split_index))))
+            .service(web::scope("/previews").configure(previews::configure))
             .service(web::scope("/documents").configure(documents::configure))
             .service(web::scope("/search").configure(search::configure))
             .service(web::scope("/facet-search").configure(facet_search::configure))
+            .service(web::scope("/facet-distribution").configure(facet_distribution::configure))
+            .service(web::scope("/field-suggestions").configure(field_suggestions::configure))
             .service(web::scope("/settings").configure(settings::configure)),
     );
 }
@@ -60,7 +82,7 @@ pub struct IndexView {
     pub created_at: OffsetDateTime,
     #[serde(with = "time::serde::rfc3339")]
     pub updated_at: OffsetDateTime,
-    pub primary_key: Option<String>,
+    pub primary_key: Option<PrimaryKeyView>,
 }
 
 impl IndexView {
@@ -72,11 +94,56 @@ impl IndexView {
             uid,
             created_at: index.created_at(&rtxn)?,
             updated_at: index.updated_at(&rtxn)?,
-            primary_key: index.primary_key(&rtxn)?.map(String::from),
+            primary_key: index.primary_key(&rtxn)?.map(PrimaryKeyView::new),
         })
     }
 }
 
+/// The number of succeeded tasks the update frequency of an index is estimated from.
+const UPDATE_CADENCE_TASKS: u32 = 20;
+
+/// How often an index is updated, from its last succeeded tasks, to help choosing the TTL of
+/// the cached search results.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexWithCadenceView {
+    #[serde(flatten)]
+    pub index: IndexView,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub last_update: Option<OffsetDateTime>,
+    /// The average duration between two of the last updates.
+    #[serde(serialize_with = "serialize_duration")]
+    pub update_frequency_estimate: Option<Duration>,
+}
+
+impl IndexWithCadenceView {
+    fn new(
+        index: IndexView,
+        index_scheduler: &IndexScheduler,
+    ) -> Result<IndexWithCadenceView, index_scheduler::Error> {
+        let query = Query {
+            limit: Some(UPDATE_CADENCE_TASKS),
+            statuses: Some(vec![Status::Succeeded]),
+            index_uids: Some(vec![index.uid.clone()]),
+            ..Query::default()
+        };
+        let (tasks, _) =
+            index_scheduler.get_tasks_from_authorized_indexes(query, &AuthFilter::default())?;
+        let finished_at: Vec<_> = tasks.iter().filter_map(|task| task.finished_at).collect();
+
+        let last_update = finished_at.iter().max().copied();
+        let first_update = finished_at.iter().min().copied();
+        let update_frequency_estimate = match (first_update, last_update) {
+            (Some(first), Some(last)) if finished_at.len() > 1 => {
+                Some((last - first) / (finished_at.len() - 1) as u32)
+            }
+            _ => None,
+        };
+
+        Ok(IndexWithCadenceView { index, last_update, update_frequency_estimate })
+    }
+}
+
 #[derive(Deserr, Debug, Clone, Copy)]
 #[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
 pub struct ListIndexes {
@@ -118,7 +185,68 @@ pub struct IndexCreateRequest {
     #[deserr(error = DeserrJsonError<InvalidIndexUid>, missing_field_error = DeserrJsonError::missing_index_uid)]
     uid: IndexUid,
     #[deserr(default, error = DeserrJsonError<InvalidIndexPrimaryKey>)]
-    primary_key: Option<String>,
+    primary_key: Option<PrimaryKeyDeclaration>,
+}
+
+/// A primary key declared either by the name of a field or by the list of the fields composing it.
+///
+/// A composite primary key is stored as the JSON array of its fields, which is also how it's
+/// declared in the `primaryKey` query parameter of the documents routes.
+#[derive(Debug)]
+pub struct PrimaryKeyDeclaration(String);
+
+impl PrimaryKeyDeclaration {
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+
+    fn composite<E: DeserializeError>(
+        fields: Vec<String>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        match fields.as_slice() {
+            [field] if !field.is_empty() => Ok(PrimaryKeyDeclaration(field.clone())),
+            fields if !fields.is_empty() && fields.iter().all(|field| !field.is_empty()) => {
+                Ok(PrimaryKeyDeclaration(composite_primary_key(fields)))
+            }
+            _ => Err(deserr::take_cf_content(E::error::<Infallible>(
+                None,
+                ErrorKind::Unexpected {
+                    msg: "A composite primary key must be a non-empty list of non-empty fields."
+                        .to_string(),
+                },
+                location,
+            ))),
+        }
+    }
+}
+
+impl<E: DeserializeError> Deserr<E> for PrimaryKeyDeclaration {
+    fn deserialize_from_value<V: IntoValue>(
+        value: deserr::Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        match value {
+            deserr::Value::String(primary_key) => {
+                match composite_primary_key_fields(&primary_key) {
+                    Some(fields) => Self::composite(fields, location),
+                    None => Ok(PrimaryKeyDeclaration(primary_key)),
+                }
+            }
+            value @ deserr::Value::Sequence(_) => {
+                let fields = Vec::<String>::deserialize_from_value(value, location)?;
+                Self::composite(fields, location)
+            }
+            value => Err(deserr::take_cf_content(E::error::<V>(
+                None,
+                ErrorKind::IncorrectValueKind {
+                    actual: value,
+                    accepted: &[ValueKind::String, ValueKind::Sequence],
+                },
+                location,
+            ))),
+        }
+    }
 }
 
 pub async fn create_index(
@@ -130,6 +258,7 @@ pub async fn create_index(
 ) -> Result<HttpResponse, ResponseError> {
     debug!(parameters = ?body, "Create index");
     let IndexCreateRequest { primary_key, uid } = body.into_inner();
+    let primary_key = primary_key.map(PrimaryKeyDeclaration::into_inner);
 
     let allow_index_creation = index_scheduler.filters().allow_index_creation(&uid);
     if allow_index_creation {
@@ -175,7 +304,7 @@ fn deny_immutable_fields_index(
 #[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields = deny_immutable_fields_index)]
 pub struct UpdateIndexRequest {
     #[deserr(default, error = DeserrJsonError<InvalidIndexPrimaryKey>)]
-    primary_key: Option<String>,
+    primary_key: Option<PrimaryKeyDeclaration>,
 }
 
 pub async fn get_index(
@@ -186,6 +315,7 @@ pub async fn get_index(
 
     let index = index_scheduler.index(&index_uid)?;
     let index_view = IndexView::new(index_uid.into_inner(), &index)?;
+    let index_view = IndexWithCadenceView::new(index_view, &index_scheduler)?;
 
     debug!(returns = ?index_view, "Get index");
 
@@ -202,17 +332,14 @@ pub async fn update_index(
 ) -> Result<HttpResponse, ResponseError> {
     debug!(parameters = ?body, "Update index");
     let index_uid = IndexUid::try_from(index_uid.into_inner())?;
-    let body = body.into_inner();
+    let primary_key = body.into_inner().primary_key.map(PrimaryKeyDeclaration::into_inner);
     analytics.publish(
         "Index Updated".to_string(),
-        json!({ "primary_key": body.primary_key }),
+        json!({ "primary_key": primary_key }),
         Some(&req),
     );
 
-    let task = KindWithContent::IndexUpdate {
-        index_uid: index_uid.into_inner(),
-        primary_key: body.primary_key,
-    };
+    let task = KindWithContent::IndexUpdate { index_uid: index_uid.into_inner(), primary_key };
 
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
@@ -252,6 +379,9 @@ pub struct IndexStats {
     pub number_of_documents: u64,
     /// Whether the index is currently performing indexation, according to the scheduler.
     pub is_indexing: bool,
+    /// Whether the index exceeds one of the alert thresholds, only present when a threshold is defined.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exceeds_alert_thresholds: Option<bool>,
     /// Association of every field name with the number of times it occurs in the documents.
     pub field_distribution: FieldDistribution,
 }
@@ -261,6 +391,7 @@ impl From<index_scheduler::IndexStats> for IndexStats {
         IndexStats {
             number_of_documents: stats.inner_stats.number_of_documents,
             is_indexing: stats.is_indexing,
+            exceeds_alert_thresholds: stats.exceeds_alert_thresholds,
             field_distribution: stats.inner_stats.field_distribution,
         }
     }
@@ -0,0 +1,111 @@
+use std::time::Duration;
+
+use actix_web::web::Data;
+use actix_web::{web, HttpRequest, HttpResponse};
+use deserr::actix_web::AwebJson;
+use deserr::Deserr;
+use index_scheduler::IndexScheduler;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::{Code, ResponseError};
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::settings::{Settings, Unchecked};
+use meilisearch_types::tasks::KindWithContent;
+use serde_json::json;
+use time::OffsetDateTime;
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::analytics::Analytics;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::{AuthenticationError, GuardedData};
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::routes::indexes::settings::validate_settings;
+use crate::routes::{get_task_id, is_dry_run, SummarizedTaskView};
+use crate::Opt;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("")
+            .route(web::get().to(SeqHandler(get_previews)))
+            .route(web::post().to(SeqHandler(create_preview))),
+    );
+}
+
+#[derive(Debug, Deserr)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct IndexPreviewCreation {
+    #[deserr(default, error = DeserrJsonError<InvalidIndexUid>)]
+    uid: Option<IndexUid>,
+    #[deserr(default)]
+    settings: Settings<Unchecked>,
+    /// How long the preview is kept after its creation is registered, in seconds.
+    #[deserr(error = DeserrJsonError<InvalidIndexPreviewTtl>)]
+    ttl: u64,
+}
+
+/// Registers a task copying the index into a new index on which the given settings are applied.
+/// The copy is deleted once its time to live is elapsed.
+pub async fn create_preview(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::INDEXES_CREATE }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    body: AwebJson<IndexPreviewCreation, DeserrJsonError>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let IndexPreviewCreation { uid, settings, ttl } = body.into_inner();
+    if ttl == 0 {
+        return Err(ResponseError::from_msg(
+            "The `ttl` of a preview must be a positive number of seconds.".to_string(),
+            Code::InvalidIndexPreviewTtl,
+        ));
+    }
+    let settings = validate_settings(settings, &index_scheduler)?;
+
+    let uid = match uid {
+        Some(uid) => uid,
+        None => {
+            let suffix = Uuid::new_v4().simple().to_string();
+            IndexUid::try_from(format!("{index_uid}-preview-{}", &suffix[..8]))?
+        }
+    };
+    if !index_scheduler.filters().is_index_authorized(&uid) {
+        return Err(AuthenticationError::InvalidToken.into());
+    }
+
+    analytics.publish("Index Preview Created".to_string(), json!({ "ttl": ttl }), Some(&req));
+
+    let task = KindWithContent::IndexPreviewCreation {
+        index_uid: uid.into_inner(),
+        source_index_uid: index_uid.into_inner(),
+        settings: Box::new(settings),
+        expires_at: OffsetDateTime::now_utc() + Duration::from_secs(ttl),
+    };
+    let uid = get_task_id(&req, &opt)?;
+    let dry_run = is_dry_run(&req, &opt)?;
+    let task: SummarizedTaskView =
+        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
+            .await??
+            .into();
+
+    debug!(returns = ?task, "Create index preview");
+    Ok(HttpResponse::Accepted().json(task))
+}
+
+pub async fn get_previews(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::INDEXES_GET }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let filters = index_scheduler.filters();
+    let previews: Vec<_> = index_scheduler
+        .get_previews(&index_uid)?
+        .into_iter()
+        .filter(|preview| filters.is_index_authorized(&preview.uid))
+        .collect();
+
+    debug!(returns = ?previews, "Get index previews");
+    Ok(HttpResponse::Ok().json(json!({ "results": previews })))
+}
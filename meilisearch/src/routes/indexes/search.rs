@@ -5,8 +5,9 @@ use index_scheduler::IndexScheduler;
 use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
 use meilisearch_types::error::deserr_codes::*;
-use meilisearch_types::error::ResponseError;
+use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::locales::Locale;
 use meilisearch_types::milli;
 use meilisearch_types::milli::vector::DistributionShift;
 use meilisearch_types::serde_cs::vec::CS;
@@ -17,18 +18,31 @@ use crate::analytics::{Analytics, SearchAggregator};
 use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
 use crate::extractors::sequential_extractor::SeqHandler;
+use crate::field_usage::{FieldUsage, UsedFields};
+use crate::metrics::search_latency_timer;
+use crate::query_log::QueryLog;
+use crate::routes::get_response_version;
 use crate::search::{
-    add_search_rules, perform_search, HybridQuery, MatchingStrategy, SearchQuery, SemanticRatio,
-    DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG,
+    add_search_rules, check_restricted_attributes, perform_candidates_statistics, perform_search,
+    perform_search_explanation, FilterLimits, HybridQuery, MatchingStrategy, SearchQuery,
+    SemanticRatio, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG,
     DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET, DEFAULT_SEMANTIC_RATIO,
 };
+use crate::search_queue::SearchQueue;
+use crate::wasm_middleware::{rewrite_request, Hook, WasmMiddlewares};
+use crate::Opt;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::resource("")
             .route(web::get().to(SeqHandler(search_with_url_query)))
             .route(web::post().to(SeqHandler(search_with_post))),
-    );
+    )
+    .service(
+        web::resource("/candidates")
+            .route(web::post().to(SeqHandler(search_candidates_statistics))),
+    )
+    .service(web::resource("/explain").route(web::post().to(SeqHandler(search_explanation))));
 }
 
 #[derive(Debug, deserr::Deserr)]
@@ -58,6 +72,10 @@ pub struct SearchQueryGet {
     filter: Option<String>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchSort>)]
     sort: Option<String>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchSortFirst>)]
+    sort_first: Option<Param<bool>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchRetrieveVectors>)]
+    retrieve_vectors: Option<Param<bool>>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowMatchesPosition>)]
     show_matches_position: Param<bool>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowRankingScore>)]
@@ -66,6 +84,10 @@ pub struct SearchQueryGet {
     show_ranking_score_details: Param<bool>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchFacets>)]
     facets: Option<CS<String>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchDisjunctiveFacets>)]
+    disjunctive_facets: Param<bool>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchAggregates>)]
+    aggregates: Option<CS<String>>,
     #[deserr( default = DEFAULT_HIGHLIGHT_PRE_TAG(), error = DeserrQueryParamError<InvalidSearchHighlightPreTag>)]
     highlight_pre_tag: String,
     #[deserr( default = DEFAULT_HIGHLIGHT_POST_TAG(), error = DeserrQueryParamError<InvalidSearchHighlightPostTag>)]
@@ -80,6 +102,8 @@ pub struct SearchQueryGet {
     pub hybrid_embedder: Option<String>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchSemanticRatio>)]
     pub hybrid_semantic_ratio: Option<SemanticRatioGet>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchLocales>)]
+    pub locales: Option<CS<Locale>>,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, deserr::Deserr)]
@@ -140,16 +164,25 @@ impl From<SearchQueryGet> for SearchQuery {
             attributes_to_highlight: other.attributes_to_highlight.map(|o| o.into_iter().collect()),
             filter,
             sort: other.sort.map(|attr| fix_sort_query_parameters(&attr)),
+            sort_first: other.sort_first.as_deref().copied(),
+            retrieve_vectors: other.retrieve_vectors.as_deref().copied(),
             show_matches_position: other.show_matches_position.0,
             show_ranking_score: other.show_ranking_score.0,
             show_ranking_score_details: other.show_ranking_score_details.0,
-            facets: other.facets.map(|o| o.into_iter().collect()),
+            facets: other.facets.map(|o| o.into_inner().into()),
+            facet_filters: None,
+            disjunctive_facets: other.disjunctive_facets.0,
+            aggregates: other.aggregates.map(|o| o.into_iter().collect()),
             highlight_pre_tag: other.highlight_pre_tag,
             highlight_post_tag: other.highlight_post_tag,
             crop_marker: other.crop_marker,
             matching_strategy: other.matching_strategy,
             attributes_to_search_on: other.attributes_to_search_on.map(|o| o.into_iter().collect()),
+            locales: other.locales.map(|o| o.into_iter().collect()),
             hybrid,
+            boosts: Vec::new(),
+            group_by: None,
+            nested: None,
         }
     }
 }
@@ -184,28 +217,64 @@ pub async fn search_with_url_query(
     index_uid: web::Path<String>,
     params: AwebQueryParameter<SearchQueryGet, DeserrQueryParamError>,
     req: HttpRequest,
+    opt: web::Data<Opt>,
     analytics: web::Data<dyn Analytics>,
+    field_usage: web::Data<FieldUsage>,
+    query_log: web::Data<QueryLog>,
+    search_queue: web::Data<SearchQueue>,
 ) -> Result<HttpResponse, ResponseError> {
     debug!(parameters = ?params, "Search get");
     let index_uid = IndexUid::try_from(index_uid.into_inner())?;
 
     let mut query: SearchQuery = params.into_inner().into();
 
+    let filter_limits = FilterLimits::from(opt.as_ref());
+    let restricted_attributes = index_scheduler.filters().displayed_attributes(&index_uid);
+    check_restricted_attributes(&query, restricted_attributes.as_deref(), filter_limits)?;
+    // the filters of the tenant tokens are not logged.
+    let logged_search = query_log.start(&index_uid, &query);
+
     // Tenant token search_rules.
     if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
         add_search_rules(&mut query, search_rules);
     }
 
+    let response_version = get_response_version(&req)?;
     let mut aggregate = SearchAggregator::from_query(&query, &req);
 
     let index = index_scheduler.index(&index_uid)?;
     let features = index_scheduler.features();
 
     let distribution = embed(&mut query, index_scheduler.get_ref(), &index).await?;
-
-    let search_result =
-        tokio::task::spawn_blocking(move || perform_search(&index, query, features, distribution))
-            .await?;
+    let permit = search_queue.try_get_search_permit().await?;
+
+    let search_cutoff_ms = opt.search_cutoff_ms;
+    field_usage.record(&index_uid, UsedFields::from_query(&query, filter_limits));
+    let diagnostics = logged_search.as_ref().map_or(false, |search| search.diagnostics());
+    let latency_timer = search_latency_timer(features, &index_uid);
+    // keeps the search spans under the span of the request
+    let span = tracing::Span::current();
+    let search_result = tokio::task::spawn_blocking(move || {
+        // the permit is released once the search is done, even if the request was dropped.
+        let _permit = permit;
+        span.in_scope(|| {
+            perform_search(
+                &index,
+                query,
+                features,
+                filter_limits,
+                distribution,
+                restricted_attributes,
+                search_cutoff_ms,
+                diagnostics,
+            )
+        })
+    })
+    .await?;
+    if let Some(latency_timer) = latency_timer {
+        latency_timer.observe_duration();
+    }
+    query_log.finish(logged_search, &search_result);
     if let Ok(ref search_result) = search_result {
         aggregate.succeed(search_result);
     }
@@ -214,26 +283,91 @@ pub async fn search_with_url_query(
     let search_result = search_result?;
 
     debug!(returns = ?search_result, "Search get");
-    Ok(HttpResponse::Ok().json(search_result))
+    Ok(HttpResponse::Ok().json(response_version.format(search_result)?))
+}
+
+/// The search template applied to a search made with `POST`, e.g. `?template=storefront`.
+#[derive(Debug, deserr::Deserr)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase)]
+pub struct SearchTemplateParam {
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchTemplate>)]
+    template: Option<String>,
+}
+
+/// Returns the search query made of the parameters of the search template merged with the
+/// parameters of the request, the latter taking precedence.
+fn apply_search_template(
+    index: &milli::Index,
+    name: &str,
+    params: Value,
+) -> Result<SearchQuery, ResponseError> {
+    let rtxn = index.read_txn()?;
+    let mut templates = index.search_templates(&rtxn)?;
+    let Some(Value::Object(mut parameters)) = templates.remove(name) else {
+        let msg = if templates.is_empty() {
+            format!(
+                "Search template `{name}` not found. This index has no search templates, add them to the `searchTemplates` index setting."
+            )
+        } else {
+            let names: Vec<_> = templates.into_keys().collect();
+            format!(
+                "Search template `{name}` not found. Available search templates are: `{}`.",
+                names.join(", ")
+            )
+        };
+        return Err(ResponseError::from_msg(msg, Code::InvalidSearchTemplate));
+    };
+
+    match params {
+        Value::Object(params) => parameters.extend(params),
+        // lets the deserialization report the invalid request
+        params => return Ok(deserr::deserialize::<_, _, DeserrJsonError>(params)?),
+    }
+    Ok(deserr::deserialize::<_, _, DeserrJsonError>(Value::Object(parameters))?)
 }
 
 pub async fn search_with_post(
     index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
     index_uid: web::Path<String>,
-    params: AwebJson<SearchQuery, DeserrJsonError>,
+    template: AwebQueryParameter<SearchTemplateParam, DeserrQueryParamError>,
+    params: AwebJson<Value, DeserrJsonError>,
     req: HttpRequest,
+    opt: web::Data<Opt>,
     analytics: web::Data<dyn Analytics>,
+    field_usage: web::Data<FieldUsage>,
+    query_log: web::Data<QueryLog>,
+    search_queue: web::Data<SearchQueue>,
+    wasm_middlewares: web::Data<WasmMiddlewares>,
 ) -> Result<HttpResponse, ResponseError> {
     let index_uid = IndexUid::try_from(index_uid.into_inner())?;
 
-    let mut query = params.into_inner();
+    // the request is only given to the WASM middleware once authenticated and rate limited.
+    let mut params = params.into_inner();
+    if wasm_middlewares.has_hook(&index_uid, Hook::Request) {
+        params = rewrite_request(&wasm_middlewares, &index_uid, req.headers(), params).await?;
+    }
+
+    let mut query = match template.into_inner().template {
+        Some(name) => {
+            let index = index_scheduler.index(&index_uid)?;
+            apply_search_template(&index, &name, params)?
+        }
+        None => deserr::deserialize::<_, _, DeserrJsonError>(params)?,
+    };
     debug!(parameters = ?query, "Search post");
 
+    let filter_limits = FilterLimits::from(opt.as_ref());
+    let restricted_attributes = index_scheduler.filters().displayed_attributes(&index_uid);
+    check_restricted_attributes(&query, restricted_attributes.as_deref(), filter_limits)?;
+    // the filters of the tenant tokens are not logged.
+    let logged_search = query_log.start(&index_uid, &query);
+
     // Tenant token search_rules.
     if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
         add_search_rules(&mut query, search_rules);
     }
 
+    let response_version = get_response_version(&req)?;
     let mut aggregate = SearchAggregator::from_query(&query, &req);
 
     let index = index_scheduler.index(&index_uid)?;
@@ -241,10 +375,35 @@ pub async fn search_with_post(
     let features = index_scheduler.features();
 
     let distribution = embed(&mut query, index_scheduler.get_ref(), &index).await?;
-
-    let search_result =
-        tokio::task::spawn_blocking(move || perform_search(&index, query, features, distribution))
-            .await?;
+    let permit = search_queue.try_get_search_permit().await?;
+
+    let search_cutoff_ms = opt.search_cutoff_ms;
+    field_usage.record(&index_uid, UsedFields::from_query(&query, filter_limits));
+    let diagnostics = logged_search.as_ref().map_or(false, |search| search.diagnostics());
+    let latency_timer = search_latency_timer(features, &index_uid);
+    // keeps the search spans under the span of the request
+    let span = tracing::Span::current();
+    let search_result = tokio::task::spawn_blocking(move || {
+        // the permit is released once the search is done, even if the request was dropped.
+        let _permit = permit;
+        span.in_scope(|| {
+            perform_search(
+                &index,
+                query,
+                features,
+                filter_limits,
+                distribution,
+                restricted_attributes,
+                search_cutoff_ms,
+                diagnostics,
+            )
+        })
+    })
+    .await?;
+    if let Some(latency_timer) = latency_timer {
+        latency_timer.observe_duration();
+    }
+    query_log.finish(logged_search, &search_result);
     if let Ok(ref search_result) = search_result {
         aggregate.succeed(search_result);
     }
@@ -253,7 +412,78 @@ pub async fn search_with_post(
     let search_result = search_result?;
 
     debug!(returns = ?search_result, "Search post");
-    Ok(HttpResponse::Ok().json(search_result))
+    Ok(HttpResponse::Ok().json(response_version.format(search_result)?))
+}
+
+/// Returns the number of documents matched by a search query after each step of the resolution of
+/// its candidates, to understand why a query examines so many documents.
+///
+/// Only the keys allowed to perform all the actions can access this route.
+pub async fn search_candidates_statistics(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::ALL }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: AwebJson<SearchQuery, DeserrJsonError>,
+    opt: web::Data<Opt>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    let mut query = params.into_inner();
+    debug!(parameters = ?query, "Search candidates statistics");
+
+    let filter_limits = FilterLimits::from(opt.as_ref());
+    let restricted_attributes = index_scheduler.filters().displayed_attributes(&index_uid);
+    check_restricted_attributes(&query, restricted_attributes.as_deref(), filter_limits)?;
+
+    // Tenant token search_rules.
+    if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
+        add_search_rules(&mut query, search_rules);
+    }
+
+    let index = index_scheduler.index(&index_uid)?;
+    let features = index_scheduler.features();
+    let statistics = tokio::task::spawn_blocking(move || {
+        perform_candidates_statistics(&index, query, features, filter_limits)
+    })
+    .await??;
+
+    debug!(returns = ?statistics, "Search candidates statistics");
+    Ok(HttpResponse::Ok().json(statistics))
+}
+
+/// Returns how a search query is tokenized, which words its terms are derived into through typos,
+/// prefixes and synonyms, how its filter is parsed and how many candidates each ranking rule
+/// sorted, to debug its relevancy.
+///
+/// Only the keys allowed to perform all the actions can access this route.
+pub async fn search_explanation(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::ALL }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: AwebJson<SearchQuery, DeserrJsonError>,
+    opt: web::Data<Opt>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    let mut query = params.into_inner();
+    debug!(parameters = ?query, "Search explanation");
+
+    let filter_limits = FilterLimits::from(opt.as_ref());
+    let restricted_attributes = index_scheduler.filters().displayed_attributes(&index_uid);
+    check_restricted_attributes(&query, restricted_attributes.as_deref(), filter_limits)?;
+
+    // Tenant token search_rules.
+    if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
+        add_search_rules(&mut query, search_rules);
+    }
+
+    let index = index_scheduler.index(&index_uid)?;
+    let features = index_scheduler.features();
+    let explanation = tokio::task::spawn_blocking(move || {
+        perform_search_explanation(&index, query, features, filter_limits)
+    })
+    .await??;
+
+    debug!(returns = ?explanation, "Search explanation");
+    Ok(HttpResponse::Ok().json(explanation))
 }
 
 pub async fn embed(
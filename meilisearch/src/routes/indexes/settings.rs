@@ -7,7 +7,9 @@ use meilisearch_types::error::ResponseError;
 use meilisearch_types::facet_values_sort::FacetValuesSort;
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::milli::update::Setting;
-use meilisearch_types::settings::{settings, RankingRuleView, Settings, Unchecked};
+use meilisearch_types::settings::{
+    settings, DecompoundingDictionaries, RankingRuleView, Settings, StopWords, Unchecked,
+};
 use meilisearch_types::tasks::KindWithContent;
 use serde_json::json;
 use tracing::debug;
@@ -20,7 +22,7 @@ use crate::Opt;
 
 #[macro_export]
 macro_rules! make_setting_route {
-    ($route:literal, $update_verb:ident, $type:ty, $err_ty:ty, $attr:ident, $camelcase_attr:literal, $analytics_var:ident, $analytics:expr) => {
+    ($route:literal, $update_verb:ident, $type:ty, $err_ty:ty, $attr:ident, $camelcase_attr:literal, $get_action:ident, $update_action:ident, $analytics_var:ident, $analytics:expr) => {
         pub mod $attr {
             use actix_web::web::Data;
             use actix_web::{web, HttpRequest, HttpResponse, Resource};
@@ -40,7 +42,7 @@ macro_rules! make_setting_route {
 
             pub async fn delete(
                 index_scheduler: GuardedData<
-                    ActionPolicy<{ actions::SETTINGS_UPDATE }>,
+                    ActionPolicy<{ actions::$update_action }>,
                     Data<IndexScheduler>,
                 >,
                 index_uid: web::Path<String>,
@@ -73,7 +75,7 @@ macro_rules! make_setting_route {
 
             pub async fn update(
                 index_scheduler: GuardedData<
-                    ActionPolicy<{ actions::SETTINGS_UPDATE }>,
+                    ActionPolicy<{ actions::$update_action }>,
                     Data<IndexScheduler>,
                 >,
                 index_uid: actix_web::web::Path<String>,
@@ -125,7 +127,7 @@ macro_rules! make_setting_route {
 
             pub async fn get(
                 index_scheduler: GuardedData<
-                    ActionPolicy<{ actions::SETTINGS_GET }>,
+                    ActionPolicy<{ actions::$get_action }>,
                     Data<IndexScheduler>,
                 >,
                 index_uid: actix_web::web::Path<String>,
@@ -162,6 +164,8 @@ make_setting_route!(
     >,
     filterable_attributes,
     "filterableAttributes",
+    SETTINGS_FILTERABLE_ATTRIBUTES_GET,
+    SETTINGS_FILTERABLE_ATTRIBUTES_UPDATE,
     analytics,
     |setting: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
         use serde_json::json;
@@ -188,6 +192,8 @@ make_setting_route!(
     >,
     sortable_attributes,
     "sortableAttributes",
+    SETTINGS_SORTABLE_ATTRIBUTES_GET,
+    SETTINGS_SORTABLE_ATTRIBUTES_UPDATE,
     analytics,
     |setting: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
         use serde_json::json;
@@ -214,6 +220,8 @@ make_setting_route!(
     >,
     displayed_attributes,
     "displayedAttributes",
+    SETTINGS_DISPLAYED_ATTRIBUTES_GET,
+    SETTINGS_DISPLAYED_ATTRIBUTES_UPDATE,
     analytics,
     |displayed: &Option<Vec<String>>, req: &HttpRequest| {
         use serde_json::json;
@@ -240,6 +248,8 @@ make_setting_route!(
     >,
     typo_tolerance,
     "typoTolerance",
+    SETTINGS_TYPO_TOLERANCE_GET,
+    SETTINGS_TYPO_TOLERANCE_UPDATE,
     analytics,
     |setting: &Option<meilisearch_types::settings::TypoSettings>, req: &HttpRequest| {
         use serde_json::json;
@@ -255,6 +265,9 @@ make_setting_route!(
                     "disable_on_words": setting
                         .as_ref()
                         .and_then(|s| s.disable_on_words.as_ref().set().map(|m| !m.is_empty())),
+                    "disable_on_numbers": setting
+                        .as_ref()
+                        .and_then(|s| s.disable_on_numbers.as_ref().set().copied()),
                     "min_word_size_for_one_typo": setting
                         .as_ref()
                         .and_then(|s| s.min_word_size_for_typos
@@ -285,6 +298,8 @@ make_setting_route!(
     >,
     searchable_attributes,
     "searchableAttributes",
+    SETTINGS_SEARCHABLE_ATTRIBUTES_GET,
+    SETTINGS_SEARCHABLE_ATTRIBUTES_UPDATE,
     analytics,
     |setting: &Option<Vec<String>>, req: &HttpRequest| {
         use serde_json::json;
@@ -305,21 +320,79 @@ make_setting_route!(
 make_setting_route!(
     "/stop-words",
     put,
-    std::collections::BTreeSet<String>,
+    meilisearch_types::settings::StopWords,
     meilisearch_types::deserr::DeserrJsonError<
         meilisearch_types::error::deserr_codes::InvalidSettingsStopWords,
     >,
     stop_words,
     "stopWords",
+    SETTINGS_STOP_WORDS_GET,
+    SETTINGS_STOP_WORDS_UPDATE,
     analytics,
-    |stop_words: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
+    |stop_words: &Option<meilisearch_types::settings::StopWords>, req: &HttpRequest| {
         use serde_json::json;
 
         analytics.publish(
             "StopWords Updated".to_string(),
             json!({
                 "stop_words": {
-                    "total": stop_words.as_ref().map(|stop_words| stop_words.len()),
+                    "total": stop_words.as_ref().map(StopWords::word_count),
+                    "localized": matches!(stop_words, Some(StopWords::Localized(_))),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/stemming",
+    put,
+    Vec<meilisearch_types::locales::StemmingLocale>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsStemming,
+    >,
+    stemming,
+    "stemming",
+    SETTINGS_STEMMING_GET,
+    SETTINGS_STEMMING_UPDATE,
+    analytics,
+    |setting: &Option<Vec<meilisearch_types::locales::StemmingLocale>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "Stemming Updated".to_string(),
+            json!({
+                "stemming": {
+                    "total": setting.as_ref().map(|locales| locales.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/decompounding",
+    put,
+    meilisearch_types::settings::DecompoundingDictionaries,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsDecompounding,
+    >,
+    decompounding,
+    "decompounding",
+    SETTINGS_DECOMPOUNDING_GET,
+    SETTINGS_DECOMPOUNDING_UPDATE,
+    analytics,
+    |setting: &Option<meilisearch_types::settings::DecompoundingDictionaries>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "Decompounding Updated".to_string(),
+            json!({
+                "decompounding": {
+                    "total": setting.as_ref().map(|dictionaries| dictionaries.word_count()),
+                    "languages": setting.as_ref().map(|dictionaries| dictionaries.0.len()),
                 },
             }),
             Some(req),
@@ -336,6 +409,8 @@ make_setting_route!(
     >,
     non_separator_tokens,
     "nonSeparatorTokens",
+    SETTINGS_NON_SEPARATOR_TOKENS_GET,
+    SETTINGS_NON_SEPARATOR_TOKENS_UPDATE,
     analytics,
     |non_separator_tokens: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
         use serde_json::json;
@@ -361,6 +436,8 @@ make_setting_route!(
     >,
     separator_tokens,
     "separatorTokens",
+    SETTINGS_SEPARATOR_TOKENS_GET,
+    SETTINGS_SEPARATOR_TOKENS_UPDATE,
     analytics,
     |separator_tokens: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
         use serde_json::json;
@@ -386,6 +463,8 @@ make_setting_route!(
     >,
     dictionary,
     "dictionary",
+    SETTINGS_DICTIONARY_GET,
+    SETTINGS_DICTIONARY_UPDATE,
     analytics,
     |dictionary: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
         use serde_json::json;
@@ -411,6 +490,8 @@ make_setting_route!(
     >,
     synonyms,
     "synonyms",
+    SETTINGS_SYNONYMS_GET,
+    SETTINGS_SYNONYMS_UPDATE,
     analytics,
     |synonyms: &Option<std::collections::BTreeMap<String, Vec<String>>>, req: &HttpRequest| {
         use serde_json::json;
@@ -436,6 +517,8 @@ make_setting_route!(
     >,
     distinct_attribute,
     "distinctAttribute",
+    SETTINGS_DISTINCT_ATTRIBUTE_GET,
+    SETTINGS_DISTINCT_ATTRIBUTE_UPDATE,
     analytics,
     |distinct: &Option<String>, req: &HttpRequest| {
         use serde_json::json;
@@ -460,6 +543,8 @@ make_setting_route!(
     >,
     proximity_precision,
     "proximityPrecision",
+    SETTINGS_PROXIMITY_PRECISION_GET,
+    SETTINGS_PROXIMITY_PRECISION_UPDATE,
     analytics,
     |precision: &Option<meilisearch_types::settings::ProximityPrecisionView>, req: &HttpRequest| {
         use serde_json::json;
@@ -476,6 +561,33 @@ make_setting_route!(
     }
 );
 
+make_setting_route!(
+    "/prefix-search",
+    put,
+    meilisearch_types::settings::PrefixSearchView,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsPrefixSearch,
+    >,
+    prefix_search,
+    "prefixSearch",
+    SETTINGS_PREFIX_SEARCH_GET,
+    SETTINGS_PREFIX_SEARCH_UPDATE,
+    analytics,
+    |prefix_search: &Option<meilisearch_types::settings::PrefixSearchView>, req: &HttpRequest| {
+        use serde_json::json;
+        analytics.publish(
+            "PrefixSearch Updated".to_string(),
+            json!({
+                "prefix_search": {
+                    "set": prefix_search.is_some(),
+                    "value": prefix_search.unwrap_or_default(),
+                }
+            }),
+            Some(req),
+        );
+    }
+);
+
 make_setting_route!(
     "/ranking-rules",
     put,
@@ -485,6 +597,8 @@ make_setting_route!(
     >,
     ranking_rules,
     "rankingRules",
+    SETTINGS_RANKING_RULES_GET,
+    SETTINGS_RANKING_RULES_UPDATE,
     analytics,
     |setting: &Option<Vec<meilisearch_types::settings::RankingRuleView>>, req: &HttpRequest| {
         use serde_json::json;
@@ -499,6 +613,7 @@ make_setting_route!(
                     "attribute_position": setting.as_ref().map(|rr| rr.iter().position(|s| matches!(s, meilisearch_types::settings::RankingRuleView::Attribute))),
                     "sort_position": setting.as_ref().map(|rr| rr.iter().position(|s| matches!(s, meilisearch_types::settings::RankingRuleView::Sort))),
                     "exactness_position": setting.as_ref().map(|rr| rr.iter().position(|s| matches!(s, meilisearch_types::settings::RankingRuleView::Exactness))),
+                    "recency_position": setting.as_ref().map(|rr| rr.iter().position(|s| matches!(s, meilisearch_types::settings::RankingRuleView::Recency { .. }))),
                     "values": setting.as_ref().map(|rr| rr.iter().filter(|s| matches!(s, meilisearch_types::settings::RankingRuleView::Asc(_) | meilisearch_types::settings::RankingRuleView::Desc(_)) ).map(|x| x.to_string()).collect::<Vec<_>>().join(", ")),
                 }
             }),
@@ -516,6 +631,8 @@ make_setting_route!(
     >,
     faceting,
     "faceting",
+    SETTINGS_FACETING_GET,
+    SETTINGS_FACETING_UPDATE,
     analytics,
     |setting: &Option<meilisearch_types::settings::FacetingSettings>, req: &HttpRequest| {
         use serde_json::json;
@@ -546,6 +663,8 @@ make_setting_route!(
     >,
     pagination,
     "pagination",
+    SETTINGS_PAGINATION_GET,
+    SETTINGS_PAGINATION_UPDATE,
     analytics,
     |setting: &Option<meilisearch_types::settings::PaginationSettings>, req: &HttpRequest| {
         use serde_json::json;
@@ -562,6 +681,199 @@ make_setting_route!(
     }
 );
 
+make_setting_route!(
+    "/query-plan-hints",
+    patch,
+    meilisearch_types::settings::QueryPlanHintsSettings,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsQueryPlanHints,
+    >,
+    query_plan_hints,
+    "queryPlanHints",
+    SETTINGS_QUERY_PLAN_HINTS_GET,
+    SETTINGS_QUERY_PLAN_HINTS_UPDATE,
+    analytics,
+    |setting: &Option<meilisearch_types::settings::QueryPlanHintsSettings>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "QueryPlanHints Updated".to_string(),
+            json!({
+                "query_plan_hints": {
+                    "filter_resolution": setting.as_ref().and_then(|s| s.filter_resolution.set()),
+                    "proximity_max_candidates": setting.as_ref().and_then(|s| s.proximity_max_candidates.set()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/search-exclusions",
+    patch,
+    meilisearch_types::settings::SearchExclusionsSettings,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsSearchExclusions,
+    >,
+    search_exclusions,
+    "searchExclusions",
+    SETTINGS_SEARCH_EXCLUSIONS_GET,
+    SETTINGS_SEARCH_EXCLUSIONS_UPDATE,
+    analytics,
+    |setting: &Option<meilisearch_types::settings::SearchExclusionsSettings>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "SearchExclusions Updated".to_string(),
+            json!({
+                "search_exclusions": {
+                    "total_document_ids": setting.as_ref().and_then(|s| s.document_ids.as_ref().set()).map(|ids| ids.len()),
+                    "filter": setting.as_ref().map_or(false, |s| s.filter.as_ref().set().is_some()),
+                    "mode": setting.as_ref().and_then(|s| s.mode.set()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/enrichment-webhook",
+    patch,
+    meilisearch_types::settings::EnrichmentWebhookSettings,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsEnrichmentWebhook,
+    >,
+    enrichment_webhook,
+    "enrichmentWebhook",
+    SETTINGS_ENRICHMENT_WEBHOOK_GET,
+    SETTINGS_ENRICHMENT_WEBHOOK_UPDATE,
+    analytics,
+    |setting: &Option<meilisearch_types::settings::EnrichmentWebhookSettings>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "EnrichmentWebhook Updated".to_string(),
+            json!({
+                "enrichment_webhook": {
+                    "set": setting.as_ref().map_or(false, |s| s.url.as_ref().set().is_some()),
+                    "batch_size": setting.as_ref().and_then(|s| s.batch_size.as_ref().set()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/unit-conversions",
+    put,
+    std::collections::BTreeMap<String, meilisearch_types::milli::unit_conversion::UnitConversion>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsUnitConversions,
+    >,
+    unit_conversions,
+    "unitConversions",
+    SETTINGS_UNIT_CONVERSIONS_GET,
+    SETTINGS_UNIT_CONVERSIONS_UPDATE,
+    analytics,
+    |setting: &Option<std::collections::BTreeMap<String, meilisearch_types::milli::unit_conversion::UnitConversion>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "UnitConversions Updated".to_string(),
+            json!({
+                "unit_conversions": {
+                    "total": setting.as_ref().map(|conversions| conversions.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/search-cutoff-ms",
+    put,
+    u64,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsSearchCutoffMs,
+    >,
+    search_cutoff_ms,
+    "searchCutoffMs",
+    SETTINGS_SEARCH_CUTOFF_MS_GET,
+    SETTINGS_SEARCH_CUTOFF_MS_UPDATE,
+    analytics,
+    |setting: &Option<u64>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "SearchCutoffMs Updated".to_string(),
+            json!({
+                "search_cutoff_ms": {
+                    "search_cutoff_ms": setting,
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/search-templates",
+    put,
+    std::collections::BTreeMap<String, meilisearch_types::settings::SearchTemplate>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsSearchTemplates,
+    >,
+    search_templates,
+    "searchTemplates",
+    SETTINGS_SEARCH_TEMPLATES_GET,
+    SETTINGS_SEARCH_TEMPLATES_UPDATE,
+    analytics,
+    |setting: &Option<std::collections::BTreeMap<String, meilisearch_types::settings::SearchTemplate>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "SearchTemplates Updated".to_string(),
+            json!({
+                "search_templates": {
+                    "total": setting.as_ref().map(|templates| templates.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/localized-attributes",
+    put,
+    Vec<meilisearch_types::locales::LocalizedAttributesRuleView>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsLocalizedAttributes,
+    >,
+    localized_attributes,
+    "localizedAttributes",
+    SETTINGS_LOCALIZED_ATTRIBUTES_GET,
+    SETTINGS_LOCALIZED_ATTRIBUTES_UPDATE,
+    analytics,
+    |setting: &Option<Vec<meilisearch_types::locales::LocalizedAttributesRuleView>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "LocalizedAttributes Updated".to_string(),
+            json!({
+                "localized_attributes": {
+                    "total": setting.as_ref().map(|rules| rules.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
 make_setting_route!(
     "/embedders",
     patch,
@@ -571,6 +883,8 @@ make_setting_route!(
     >,
     embedders,
     "embedders",
+    SETTINGS_EMBEDDERS_GET,
+    SETTINGS_EMBEDDERS_UPDATE,
     analytics,
     |setting: &Option<std::collections::BTreeMap<String, Setting<meilisearch_types::milli::vector::settings::EmbeddingSettings>>>, req: &HttpRequest| {
 
@@ -645,7 +959,10 @@ generate_configure!(
     searchable_attributes,
     distinct_attribute,
     proximity_precision,
+    prefix_search,
     stop_words,
+    stemming,
+    decompounding,
     separator_tokens,
     non_separator_tokens,
     dictionary,
@@ -654,6 +971,13 @@ generate_configure!(
     typo_tolerance,
     pagination,
     faceting,
+    query_plan_hints,
+    search_exclusions,
+    enrichment_webhook,
+    unit_conversions,
+    search_cutoff_ms,
+    search_templates,
+    localized_attributes,
     embedders
 );
 
@@ -681,6 +1005,7 @@ pub async fn update_all(
                 "attribute_position": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().position(|s| matches!(s, RankingRuleView::Attribute))),
                 "sort_position": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().position(|s| matches!(s, RankingRuleView::Sort))),
                 "exactness_position": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().position(|s| matches!(s, RankingRuleView::Exactness))),
+                "recency_position": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().position(|s| matches!(s, RankingRuleView::Recency { .. }))),
                 "values": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().filter(|s| !matches!(s, RankingRuleView::Asc(_) | RankingRuleView::Desc(_)) ).map(|x| x.to_string()).collect::<Vec<_>>().join(", ")),
             },
             "searchable_attributes": {
@@ -706,6 +1031,10 @@ pub async fn update_all(
                 "set": new_settings.proximity_precision.as_ref().set().is_some(),
                 "value": new_settings.proximity_precision.as_ref().set().copied().unwrap_or_default()
             },
+            "prefix_search": {
+                "set": new_settings.prefix_search.as_ref().set().is_some(),
+                "value": new_settings.prefix_search.as_ref().set().copied().unwrap_or_default()
+            },
             "typo_tolerance": {
                 "enabled": new_settings.typo_tolerance
                     .as_ref()
@@ -720,6 +1049,10 @@ pub async fn update_all(
                     .as_ref()
                     .set()
                     .and_then(|s| s.disable_on_words.as_ref().set().map(|m| !m.is_empty())),
+                "disable_on_numbers": new_settings.typo_tolerance
+                    .as_ref()
+                    .set()
+                    .and_then(|s| s.disable_on_numbers.as_ref().set().copied()),
                 "min_word_size_for_one_typo": new_settings.typo_tolerance
                     .as_ref()
                     .set()
@@ -759,8 +1092,63 @@ pub async fn update_all(
                     .set()
                     .and_then(|s| s.max_total_hits.as_ref().set()),
             },
+            "query_plan_hints": {
+                "filter_resolution": new_settings.query_plan_hints
+                    .as_ref()
+                    .set()
+                    .and_then(|s| s.filter_resolution.as_ref().set()),
+                "proximity_max_candidates": new_settings.query_plan_hints
+                    .as_ref()
+                    .set()
+                    .and_then(|s| s.proximity_max_candidates.as_ref().set()),
+            },
+            "search_exclusions": {
+                "total_document_ids": new_settings.search_exclusions
+                    .as_ref()
+                    .set()
+                    .and_then(|s| s.document_ids.as_ref().set())
+                    .map(|ids| ids.len()),
+                "filter": new_settings.search_exclusions
+                    .as_ref()
+                    .set()
+                    .map_or(false, |s| s.filter.as_ref().set().is_some()),
+                "mode": new_settings.search_exclusions
+                    .as_ref()
+                    .set()
+                    .and_then(|s| s.mode.as_ref().set()),
+            },
+            "enrichment_webhook": {
+                "set": new_settings.enrichment_webhook
+                    .as_ref()
+                    .set()
+                    .map_or(false, |s| s.url.as_ref().set().is_some()),
+                "batch_size": new_settings.enrichment_webhook
+                    .as_ref()
+                    .set()
+                    .and_then(|s| s.batch_size.as_ref().set()),
+            },
+            "unit_conversions": {
+                "total": new_settings.unit_conversions.as_ref().set().map(|conversions| conversions.len()),
+            },
+            "search_cutoff_ms": {
+                "search_cutoff_ms": new_settings.search_cutoff_ms.as_ref().set(),
+            },
+            "search_templates": {
+                "total": new_settings.search_templates.as_ref().set().map(|templates| templates.len()),
+            },
+            "localized_attributes": {
+                "total": new_settings.localized_attributes.as_ref().set().map(|rules| rules.len()),
+            },
             "stop_words": {
-                "total": new_settings.stop_words.as_ref().set().map(|stop_words| stop_words.len()),
+                "total": new_settings.stop_words.as_ref().set().map(StopWords::word_count),
+                "localized": matches!(new_settings.stop_words, Setting::Set(StopWords::Localized(_))),
+            },
+            "stemming": {
+                "total": new_settings.stemming.as_ref().set().map(|locales| locales.len()),
+            },
+            "decompounding": {
+                "total": new_settings.decompounding.as_ref().set().map(DecompoundingDictionaries::word_count),
+                "languages": new_settings.decompounding.as_ref().set().map(|dictionaries| dictionaries.0.len()),
             },
             "synonyms": {
                 "total": new_settings.synonyms.as_ref().set().map(|synonyms| synonyms.len()),
@@ -831,7 +1219,7 @@ pub async fn delete_all(
     Ok(HttpResponse::Accepted().json(task))
 }
 
-fn validate_settings(
+pub fn validate_settings(
     settings: Settings<Unchecked>,
     index_scheduler: &IndexScheduler,
 ) -> Result<Settings<Unchecked>, ResponseError> {
@@ -0,0 +1,79 @@
+use actix_web::web::Data;
+use actix_web::{web, HttpRequest, HttpResponse};
+use deserr::actix_web::AwebJson;
+use deserr::Deserr;
+use index_scheduler::IndexScheduler;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::{Code, ResponseError};
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::tasks::KindWithContent;
+use serde_json::{json, Value};
+use tracing::debug;
+
+use crate::analytics::Analytics;
+use crate::error::MeilisearchHttpError;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::{AuthenticationError, GuardedData};
+use crate::routes::{get_task_id, is_dry_run, SummarizedTaskView};
+use crate::search::{parse_filter, FilterLimits};
+use crate::Opt;
+
+#[derive(Debug, Deserr)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct IndexSplit {
+    #[deserr(error = DeserrJsonError<InvalidIndexUid>, missing_field_error = DeserrJsonError::missing_index_uid)]
+    uid: IndexUid,
+    #[deserr(error = DeserrJsonError<InvalidIndexSplitFilter>, missing_field_error = DeserrJsonError::missing_index_split_filter)]
+    filter: Value,
+}
+
+/// Registers a task creating a new index from the documents of the index matching the filter,
+/// along with its settings.
+pub async fn split_index(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::INDEXES_CREATE }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    body: AwebJson<IndexSplit, DeserrJsonError>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let IndexSplit { uid, filter } = body.into_inner();
+    if uid == index_uid {
+        return Err(ResponseError::from_msg(
+            format!("Index `{index_uid}` can't be split into itself."),
+            Code::InvalidIndexUid,
+        ));
+    }
+    if !index_scheduler.filters().is_index_authorized(&uid) {
+        return Err(AuthenticationError::InvalidToken.into());
+    }
+
+    // we ensure the filter is well formed and not too complex before enqueuing it
+    || -> Result<_, MeilisearchHttpError> {
+        parse_filter(&filter, FilterLimits::from(opt.as_ref()))?
+            .ok_or(MeilisearchHttpError::EmptyFilter)
+    }()
+    .map_err(|err| match err {
+        MeilisearchHttpError::FilterTooComplex { .. } => ResponseError::from(err),
+        err => ResponseError::from_msg(err.to_string(), Code::InvalidIndexSplitFilter),
+    })?;
+
+    analytics.publish("Index Split".to_string(), json!({}), Some(&req));
+
+    let task = KindWithContent::IndexSplit {
+        index_uid: uid.into_inner(),
+        source_index_uid: index_uid.into_inner(),
+        filter_expr: filter,
+    };
+    let uid = get_task_id(&req, &opt)?;
+    let dry_run = is_dry_run(&req, &opt)?;
+    let task: SummarizedTaskView =
+        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
+            .await??
+            .into();
+
+    debug!(returns = ?task, "Split index");
+    Ok(HttpResponse::Accepted().json(task))
+}
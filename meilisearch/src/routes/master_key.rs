@@ -0,0 +1,90 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use deserr::actix_web::AwebJson;
+use deserr::Deserr;
+use meilisearch_auth::{AuthController, MASTER_KEY_MIN_SIZE};
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::{Code, ResponseError};
+use serde_json::json;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use tracing::debug;
+
+use crate::analytics::Analytics;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::Opt;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::post().to(SeqHandler(rotate_master_key))));
+}
+
+#[derive(Debug, Deserr)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct MasterKeyRotation {
+    #[deserr(error = DeserrJsonError<InvalidNewMasterKey>)]
+    new_master_key: String,
+    /// How long the previous master key and the keys derived from it stay valid, in seconds.
+    #[deserr(default, error = DeserrJsonError<InvalidMasterKeyGracePeriod>)]
+    grace_period: u64,
+}
+
+/// Replaces the master key without restarting Meilisearch.
+///
+/// The API keys are derived from the master key, they're all re-derived from the new one.
+///
+/// The new master key is only kept in memory, the configuration must be updated
+/// before the next restart: Meilisearch refuses to restart with the previous one.
+pub async fn rotate_master_key(
+    auth_controller: GuardedData<CurrentMasterKeyPolicy, web::Data<AuthController>>,
+    body: AwebJson<MasterKeyRotation, DeserrJsonError>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let MasterKeyRotation { new_master_key, grace_period } = body.into_inner();
+    if new_master_key.is_empty()
+        || (opt.env == "production" && new_master_key.len() < MASTER_KEY_MIN_SIZE)
+    {
+        return Err(ResponseError::from_msg(
+            format!(
+                "The new master key must be at least {MASTER_KEY_MIN_SIZE} bytes in a production environment and can't be empty."
+            ),
+            Code::InvalidNewMasterKey,
+        ));
+    }
+    if auth_controller.get_master_key().as_ref() == Some(&new_master_key) {
+        return Err(ResponseError::from_msg(
+            "The new master key must be different from the current one.".to_string(),
+            Code::InvalidNewMasterKey,
+        ));
+    }
+
+    let valid_until = i64::try_from(grace_period)
+        .ok()
+        .and_then(|grace_period| {
+            OffsetDateTime::now_utc().checked_add(time::Duration::seconds(grace_period))
+        })
+        .ok_or_else(|| {
+            ResponseError::from_msg(
+                format!("The grace period of {grace_period} seconds is too long."),
+                Code::InvalidMasterKeyGracePeriod,
+            )
+        })?;
+
+    analytics.publish(
+        "Master Key Rotated".to_string(),
+        json!({ "grace_period": grace_period }),
+        Some(&req),
+    );
+
+    auth_controller.rotate_master_key(new_master_key, valid_until)?;
+    tracing::info!("The master key was rotated, the previous one is valid until {valid_until}.");
+
+    let response = json!({
+        "previousMasterKeyValidUntil": valid_until.format(&Rfc3339).unwrap(),
+    });
+    debug!(returns = ?response, "Rotate master key");
+    Ok(HttpResponse::Ok().json(response))
+}
@@ -35,20 +35,50 @@ pub async fn get_metrics(
     crate::metrics::MEILISEARCH_USED_DB_SIZE_BYTES.set(response.used_database_size as i64);
     crate::metrics::MEILISEARCH_INDEX_COUNT.set(response.indexes.len() as i64);
 
+    // The deleted indexes must not be reported anymore.
+    crate::metrics::MEILISEARCH_INDEX_DOCS_COUNT.reset();
+    crate::metrics::MEILISEARCH_INDEX_DB_SIZE_BYTES.reset();
+    crate::metrics::MEILISEARCH_INDEX_USED_DB_SIZE_BYTES.reset();
+    crate::metrics::MEILISEARCH_INDEX_MAP_SIZE_BYTES.reset();
+
     for (index, value) in response.indexes.iter() {
         crate::metrics::MEILISEARCH_INDEX_DOCS_COUNT
             .with_label_values(&[index])
             .set(value.number_of_documents as i64);
+
+        let stats = index_scheduler.index_stats(index)?.inner_stats;
+        crate::metrics::MEILISEARCH_INDEX_DB_SIZE_BYTES
+            .with_label_values(&[index])
+            .set(stats.database_size as i64);
+        crate::metrics::MEILISEARCH_INDEX_USED_DB_SIZE_BYTES
+            .with_label_values(&[index])
+            .set(stats.used_database_size as i64);
+        crate::metrics::MEILISEARCH_INDEX_MAP_SIZE_BYTES
+            .with_label_values(&[index])
+            .set(stats.map_size as i64);
     }
 
     for (kind, value) in index_scheduler.get_stats()? {
         for (value, count) in value {
+            if kind == "statuses" && value == "enqueued" {
+                crate::metrics::MEILISEARCH_TASK_QUEUE_DEPTH.set(count as i64);
+            }
             crate::metrics::MEILISEARCH_NB_TASKS
                 .with_label_values(&[&kind, &value])
                 .set(count as i64);
         }
     }
 
+    // The counters can only be incremented, so we add what was done since the last scrape.
+    let activity = index_scheduler.indexing_activity();
+    let indexed_documents = &crate::metrics::MEILISEARCH_INDEXED_DOCUMENTS_TOTAL;
+    indexed_documents.inc_by(activity.indexed_documents.saturating_sub(indexed_documents.get()));
+    let processed_batches = &crate::metrics::MEILISEARCH_PROCESSED_BATCHES_TOTAL;
+    processed_batches.inc_by(activity.processed_batches.saturating_sub(processed_batches.get()));
+    let processing_time = &crate::metrics::MEILISEARCH_BATCH_PROCESSING_SECONDS_TOTAL;
+    processing_time
+        .inc_by((activity.processing_time.as_secs_f64() - processing_time.get()).max(0.0));
+
     if let Some(last_update) = response.last_update {
         crate::metrics::MEILISEARCH_LAST_UPDATE.set(last_update.unix_timestamp());
     }
@@ -15,30 +15,44 @@ use tracing::debug;
 use crate::analytics::Analytics;
 use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
+use crate::search::ResponseVersion;
 use crate::Opt;
 
 const PAGINATION_DEFAULT_LIMIT: usize = 20;
 
 mod api_key;
+mod audit_log;
+mod dead_letters;
+mod document_routing;
 mod dump;
 pub mod features;
 pub mod indexes;
 mod logs;
+mod master_key;
 mod metrics;
 mod multi_search;
+mod schedules;
 mod snapshot;
 mod swap_indexes;
 pub mod tasks;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(web::scope("/tasks").configure(tasks::configure))
+        .service(web::scope("/documents").configure(document_routing::configure))
         .service(web::resource("/health").route(web::get().to(get_health)))
+        .service(web::resource("/health/live").route(web::get().to(get_liveness)))
+        .service(web::resource("/health/ready").route(web::get().to(get_readiness)))
         .service(web::scope("/logs").configure(logs::configure))
         .service(web::scope("/keys").configure(api_key::configure))
+        .service(web::scope("/master-key").configure(master_key::configure))
+        .service(web::scope("/audit-log").configure(audit_log::configure))
         .service(web::scope("/dumps").configure(dump::configure))
         .service(web::scope("/snapshots").configure(snapshot::configure))
+        .service(web::scope("/schedules").configure(schedules::configure))
+        .service(web::scope("/dead-letters").configure(dead_letters::configure))
         .service(web::resource("/stats").route(web::get().to(get_stats)))
         .service(web::resource("/version").route(web::get().to(get_version)))
+        .service(web::resource("/recovery-report").route(web::get().to(get_recovery_report)))
         .service(web::scope("/indexes").configure(indexes::configure))
         .service(web::scope("/multi-search").configure(multi_search::configure))
         .service(web::scope("/swap-indexes").configure(swap_indexes::configure))
@@ -77,6 +91,27 @@ pub fn get_task_id(req: &HttpRequest, opt: &Opt) -> Result<Option<TaskId>, Respo
     Ok(task_id)
 }
 
+/// The header clients can send to lock the layout of the search responses to a previous major version.
+pub const RESPONSE_VERSION_HEADER: &str = "Meili-Response-Version";
+
+pub fn get_response_version(req: &HttpRequest) -> Result<ResponseVersion, ResponseError> {
+    let response_version = req
+        .headers()
+        .get(RESPONSE_VERSION_HEADER)
+        .map(|header| {
+            header.to_str().map_err(|e| {
+                ResponseError::from_msg(
+                    format!("{RESPONSE_VERSION_HEADER} is not a valid utf-8 string: {e}"),
+                    Code::InvalidResponseVersion,
+                )
+            })
+        })
+        .transpose()?
+        .map(|s| s.parse().map_err(|e| ResponseError::from_msg(e, Code::InvalidResponseVersion)))
+        .transpose()?;
+    Ok(response_version.unwrap_or_default())
+}
+
 pub fn is_dry_run(req: &HttpRequest, opt: &Opt) -> Result<bool, ResponseError> {
     if !opt.experimental_replication_parameters {
         return Ok(false);
@@ -352,7 +387,7 @@ struct VersionResponse {
     pkg_version: String,
 }
 
-async fn get_version(
+pub(crate) async fn get_version(
     _index_scheduler: GuardedData<ActionPolicy<{ actions::VERSION }>, Data<IndexScheduler>>,
     req: HttpRequest,
     analytics: web::Data<dyn Analytics>,
@@ -375,6 +410,24 @@ async fn get_version(
     })
 }
 
+/// Returns what was done to recover the task queue when Meilisearch was restarted
+/// after an unclean shutdown.
+pub(crate) async fn get_recovery_report(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_GET }>, Data<IndexScheduler>>,
+) -> Result<HttpResponse, ResponseError> {
+    match index_scheduler.recovery_report() {
+        Some(report) => {
+            debug!(returns = ?report, "Get recovery report");
+            Ok(HttpResponse::Ok().json(report))
+        }
+        None => Err(ResponseError::from_msg(
+            "Meilisearch was not recovered from an unclean shutdown when it was last started."
+                .to_string(),
+            Code::RecoveryReportNotFound,
+        )),
+    }
+}
+
 #[derive(Serialize)]
 struct KeysResponse {
     private: Option<String>,
@@ -394,3 +447,32 @@ pub async fn get_health(
 
     Ok(HttpResponse::Ok().json(serde_json::json!({ "status": "available" })))
 }
+
+/// Answers as long as the instance is able to serve HTTP requests.
+pub async fn get_liveness() -> HttpResponse {
+    HttpResponse::Ok().json(serde_json::json!({ "status": "available" }))
+}
+
+/// Answers with a `503 Service Unavailable` while the instance is not ready to serve the
+/// requests, e.g. because it's importing a dump or one of its databases can't be read.
+pub async fn get_readiness(
+    index_scheduler: Data<IndexScheduler>,
+    auth_controller: Data<AuthController>,
+) -> HttpResponse {
+    let readiness = index_scheduler.readiness();
+    let auth_database = auth_controller.health().is_ok();
+
+    let (mut response, status) = if readiness.is_ready() && auth_database {
+        (HttpResponse::Ok(), "available")
+    } else {
+        (HttpResponse::ServiceUnavailable(), "unavailable")
+    };
+    let checks = serde_json::json!({
+        "tasksDatabase": readiness.database,
+        "authDatabase": auth_database,
+        "scheduler": readiness.scheduler,
+        "dumpImportFinished": readiness.dump_import_finished,
+    });
+    debug!(returns = %checks, "Get readiness");
+    response.json(serde_json::json!({ "status": status, "checks": checks }))
+}
@@ -2,21 +2,32 @@ use actix_http::StatusCode;
 use actix_web::web::{self, Data};
 use actix_web::{HttpRequest, HttpResponse};
 use deserr::actix_web::AwebJson;
-use index_scheduler::IndexScheduler;
+use index_scheduler::{IndexScheduler, RoFeatures};
 use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::keys::actions;
 use serde::Serialize;
+use serde_json::value::RawValue;
 use tracing::debug;
 
 use crate::analytics::{Analytics, MultiSearchAggregator};
+use crate::error::MeilisearchHttpError;
 use crate::extractors::authentication::policies::ActionPolicy;
 use crate::extractors::authentication::{AuthenticationError, GuardedData};
 use crate::extractors::sequential_extractor::SeqHandler;
+use crate::field_usage::{FieldUsage, UsedFields};
+use crate::metrics::search_latency_timer;
+use crate::query_log::QueryLog;
+use crate::routes::get_response_version;
 use crate::routes::indexes::search::embed;
 use crate::search::{
-    add_search_rules, perform_search, SearchQueryWithIndex, SearchResultWithIndex,
+    add_search_rules, check_restricted_attributes, perform_search, FilterLimits, SearchQuery,
+    SearchQueryWithIndex, SearchResult, SearchResultWithIndex,
 };
+use crate::search_queue::SearchQueue;
+use crate::Opt;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(web::resource("").route(web::post().to(SeqHandler(multi_search_with_post))));
@@ -24,25 +35,53 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
 
 #[derive(Serialize)]
 struct SearchResults {
-    results: Vec<SearchResultWithIndex>,
+    results: Vec<Box<RawValue>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<MultiSearchStatus>,
+}
+
+/// The aggregate status of a multi-search allowing partial failures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+enum MultiSearchStatus {
+    Succeeded,
+    PartiallyFailed,
+    Failed,
+}
+
+/// A failed query of a multi-search allowing partial failures, embedded in place of its results.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchErrorWithIndex {
+    index_uid: String,
+    error: ResponseError,
 }
 
 #[derive(Debug, deserr::Deserr)]
 #[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
 pub struct SearchQueries {
     queries: Vec<SearchQueryWithIndex>,
+    #[deserr(default, error = DeserrJsonError<InvalidMultiSearchAllowPartialFailures>)]
+    allow_partial_failures: bool,
 }
 
 pub async fn multi_search_with_post(
     index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
     params: AwebJson<SearchQueries, DeserrJsonError>,
     req: HttpRequest,
+    opt: web::Data<Opt>,
     analytics: web::Data<dyn Analytics>,
+    field_usage: web::Data<FieldUsage>,
+    query_log: web::Data<QueryLog>,
+    search_queue: web::Data<SearchQueue>,
 ) -> Result<HttpResponse, ResponseError> {
-    let queries = params.into_inner().queries;
+    let SearchQueries { queries, allow_partial_failures } = params.into_inner();
+    let response_version = get_response_version(&req)?;
 
     let mut multi_aggregate = MultiSearchAggregator::from_queries(&queries, &req);
     let features = index_scheduler.features();
+    let filter_limits = FilterLimits::from(opt.as_ref());
+    let search_cutoff_ms = opt.search_cutoff_ms;
 
     // Explicitly expect a `(ResponseError, usize)` for the error type rather than `ResponseError` only,
     // so that `?` doesn't work if it doesn't use `with_index`, ensuring that it is not forgotten in case of code
@@ -58,43 +97,52 @@ pub async fn multi_search_with_post(
             if !index_scheduler.filters().is_index_authorized(&index_uid) {
                 return Err(AuthenticationError::InvalidToken).with_index(query_index);
             }
+            let restricted_attributes = index_scheduler.filters().displayed_attributes(&index_uid);
+            check_restricted_attributes(&query, restricted_attributes.as_deref(), filter_limits)
+                .with_index(query_index)?;
+            // the filters of the tenant tokens are not logged.
+            let logged_search = query_log.start(&index_uid, &query);
             // Apply search rules from tenant token
             if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid)
             {
                 add_search_rules(&mut query, search_rules);
             }
-
-            let index = index_scheduler
-                .index(&index_uid)
-                .map_err(|err| {
-                    let mut err = ResponseError::from(err);
-                    // Patch the HTTP status code to 400 as it defaults to 404 for `index_not_found`, but
-                    // here the resource not found is not part of the URL.
-                    err.code = StatusCode::BAD_REQUEST;
-                    err
-                })
-                .with_index(query_index)?;
-
-            let distribution = embed(&mut query, index_scheduler.get_ref(), &index)
-                .await
-                .with_index(query_index)?;
-
-            let search_result = tokio::task::spawn_blocking(move || {
-                perform_search(&index, query, features, distribution)
-            })
-            .await
-            .with_index(query_index)?;
-
-            search_results.push(SearchResultWithIndex {
-                index_uid: index_uid.into_inner(),
-                result: search_result.with_index(query_index)?,
-            });
+            let used_fields = UsedFields::from_query(&query, filter_limits);
+
+            let diagnostics = logged_search.as_ref().map_or(false, |search| search.diagnostics());
+            let result = search_one(
+                index_scheduler.get_ref(),
+                &index_uid,
+                query,
+                features,
+                filter_limits,
+                restricted_attributes,
+                search_cutoff_ms,
+                diagnostics,
+                &search_queue,
+            )
+            .await;
+            query_log.finish(logged_search, &result);
+            match result {
+                Ok(result) => {
+                    // the searches on a missing index are not recorded.
+                    field_usage.record(&index_uid, used_fields);
+                    search_results.push(Ok(SearchResultWithIndex {
+                        index_uid: index_uid.into_inner(),
+                        result,
+                    }))
+                }
+                // The failure of a single query doesn't fail the others: its error is returned in place of its results.
+                Err(error) if allow_partial_failures => search_results
+                    .push(Err(SearchErrorWithIndex { index_uid: index_uid.into_inner(), error })),
+                Err(error) => return Err(error).with_index(query_index),
+            }
         }
         Ok(search_results)
     }
     .await;
 
-    if search_results.is_ok() {
+    if search_results.as_ref().is_ok_and(|results| results.iter().all(Result::is_ok)) {
         multi_aggregate.succeed();
     }
     analytics.post_multi_search(multi_aggregate);
@@ -109,7 +157,76 @@ pub async fn multi_search_with_post(
 
     debug!(returns = ?search_results, "Multi-search");
 
-    Ok(HttpResponse::Ok().json(SearchResults { results: search_results }))
+    let status = allow_partial_failures.then(|| {
+        let failed = search_results.iter().filter(|result| result.is_err()).count();
+        match failed {
+            0 => MultiSearchStatus::Succeeded,
+            failed if failed == search_results.len() => MultiSearchStatus::Failed,
+            _ => MultiSearchStatus::PartiallyFailed,
+        }
+    });
+
+    let results = search_results
+        .into_iter()
+        .map(|result| match result {
+            Ok(result) => response_version.format(result),
+            Err(error) => {
+                serde_json::value::to_raw_value(&error).map_err(MeilisearchHttpError::from)
+            }
+        })
+        .collect::<Result<Vec<_>, MeilisearchHttpError>>()?;
+
+    Ok(HttpResponse::Ok().json(SearchResults { results, status }))
+}
+
+/// Performs a single query of a multi-search on its index.
+#[allow(clippy::too_many_arguments)]
+async fn search_one(
+    index_scheduler: &IndexScheduler,
+    index_uid: &IndexUid,
+    mut query: SearchQuery,
+    features: RoFeatures,
+    filter_limits: FilterLimits,
+    restricted_attributes: Option<Vec<String>>,
+    search_cutoff_ms: Option<u64>,
+    diagnostics: bool,
+    search_queue: &SearchQueue,
+) -> Result<SearchResult, ResponseError> {
+    let index = index_scheduler.index(index_uid).map_err(|err| {
+        let mut err = ResponseError::from(err);
+        // Patch the HTTP status code to 400 as it defaults to 404 for `index_not_found`, but
+        // here the resource not found is not part of the URL.
+        err.code = StatusCode::BAD_REQUEST;
+        err
+    })?;
+
+    let distribution = embed(&mut query, index_scheduler, &index).await?;
+    let permit = search_queue.try_get_search_permit().await?;
+
+    let latency_timer = search_latency_timer(features, index_uid);
+    // keeps the search spans under the span of the request
+    let span = tracing::Span::current();
+    let search_result = tokio::task::spawn_blocking(move || {
+        let _permit = permit;
+        span.in_scope(|| {
+            perform_search(
+                &index,
+                query,
+                features,
+                filter_limits,
+                distribution,
+                restricted_attributes,
+                search_cutoff_ms,
+                diagnostics,
+            )
+        })
+    })
+    .await?;
+    if let Some(latency_timer) = latency_timer {
+        latency_timer.observe_duration();
+    }
+
+    Ok(search_result?)
 }
 
 /// Local `Result` extension trait to avoid `map_err` boilerplate.
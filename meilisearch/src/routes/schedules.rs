@@ -0,0 +1,253 @@
+use std::time::Duration;
+
+use actix_web::web::Data;
+use actix_web::{web, HttpRequest, HttpResponse};
+use deserr::actix_web::AwebJson;
+use deserr::Deserr;
+use index_scheduler::{IndexScheduler, Schedule, ScheduleId};
+use meilisearch_auth::AuthController;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::{Code, ParseOffsetDateTimeError, ResponseError};
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::keys::Action;
+use meilisearch_types::tasks::KindWithContent;
+use serde::Serialize;
+use serde_json::{json, Value};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use tracing::debug;
+
+use crate::analytics::Analytics;
+use crate::error::MeilisearchHttpError;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::{AuthenticationError, GuardedData};
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::search::{parse_filter, FilterLimits};
+use crate::Opt;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("")
+            .route(web::get().to(SeqHandler(get_schedules)))
+            .route(web::post().to(SeqHandler(create_schedule))),
+    )
+    .service(
+        web::resource("/{schedule_uid}")
+            .route(web::get().to(SeqHandler(get_schedule)))
+            .route(web::delete().to(SeqHandler(delete_schedule))),
+    );
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserr, Serialize)]
+#[deserr(rename_all = camelCase)]
+#[serde(rename_all = "camelCase")]
+pub enum ScheduleType {
+    SnapshotCreation,
+    /// A deletion of the documents matching a filter.
+    DocumentDeletion,
+}
+
+#[derive(Debug, Deserr)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct ScheduleCreation {
+    #[deserr(rename = "type", error = DeserrJsonError<InvalidScheduleType>)]
+    schedule_type: ScheduleType,
+    #[deserr(default, error = DeserrJsonError<InvalidIndexUid>)]
+    index_uid: Option<IndexUid>,
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentFilter>)]
+    filter: Option<Value>,
+    /// The time between two runs, in seconds.
+    #[deserr(error = DeserrJsonError<InvalidScheduleInterval>)]
+    interval: u64,
+    #[deserr(default, error = DeserrJsonError<InvalidScheduleStartAt>, try_from(Option<String>) = parse_start_at -> ParseOffsetDateTimeError)]
+    start_at: Option<OffsetDateTime>,
+}
+
+fn parse_start_at(
+    string: Option<String>,
+) -> std::result::Result<Option<OffsetDateTime>, ParseOffsetDateTimeError> {
+    let Some(string) = string else { return Ok(None) };
+    OffsetDateTime::parse(&string, &Rfc3339).map(Some).map_err(|_| ParseOffsetDateTimeError(string))
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleView {
+    uid: ScheduleId,
+    #[serde(rename = "type")]
+    schedule_type: ScheduleType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    index_uid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    filter: Option<Value>,
+    interval: u64,
+    #[serde(with = "time::serde::rfc3339")]
+    next_run_at: OffsetDateTime,
+    last_task_uid: Option<u32>,
+    #[serde(with = "time::serde::rfc3339")]
+    created_at: OffsetDateTime,
+}
+
+impl ScheduleView {
+    fn from_schedule(schedule: Schedule) -> Self {
+        let Schedule { uid, kind, interval, next_run_at, last_task_uid, created_at } = schedule;
+        let (schedule_type, index_uid, filter) = match kind {
+            KindWithContent::DocumentDeletionByFilter { index_uid, filter_expr } => {
+                (ScheduleType::DocumentDeletion, Some(index_uid), Some(filter_expr))
+            }
+            _ => (ScheduleType::SnapshotCreation, None, None),
+        };
+        ScheduleView {
+            uid,
+            schedule_type,
+            index_uid,
+            filter,
+            interval: interval.as_secs(),
+            next_run_at,
+            last_task_uid,
+            created_at,
+        }
+    }
+}
+
+pub async fn create_schedule(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SCHEDULES_CREATE }>, Data<IndexScheduler>>,
+    auth_controller: Data<AuthController>,
+    body: AwebJson<ScheduleCreation, DeserrJsonError>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let ScheduleCreation { schedule_type, index_uid, filter, interval, start_at } =
+        body.into_inner();
+
+    let kind = match (schedule_type, index_uid, filter) {
+        (ScheduleType::SnapshotCreation, None, None) => KindWithContent::SnapshotCreation,
+        (ScheduleType::SnapshotCreation, _, _) => {
+            return Err(ResponseError::from_msg(
+                "The `indexUid` and `filter` fields can only be used with the `documentDeletion` schedule type.".to_string(),
+                Code::InvalidScheduleType,
+            ))
+        }
+        (ScheduleType::DocumentDeletion, None, _) => {
+            return Err(ResponseError::from_msg(
+                "The `indexUid` field is required by the `documentDeletion` schedule type."
+                    .to_string(),
+                Code::MissingIndexUid,
+            ))
+        }
+        (ScheduleType::DocumentDeletion, _, None) => {
+            return Err(ResponseError::from_msg(
+                "The `filter` field is required by the `documentDeletion` schedule type."
+                    .to_string(),
+                Code::MissingDocumentFilter,
+            ))
+        }
+        (ScheduleType::DocumentDeletion, Some(index_uid), Some(filter)) => {
+            if !index_scheduler.filters().is_index_authorized(&index_uid) {
+                return Err(AuthenticationError::InvalidToken.into());
+            }
+            // we ensure the filter is well formed and not too complex before registering it
+            || -> Result<_, MeilisearchHttpError> {
+                parse_filter(&filter, FilterLimits::from(opt.as_ref()))?
+                    .ok_or(MeilisearchHttpError::EmptyFilter)
+            }()
+            // and except for a too complex filter, the error code should always be an InvalidDocumentFilter
+            .map_err(|err| match err {
+                MeilisearchHttpError::FilterTooComplex { .. } => ResponseError::from(err),
+                err => ResponseError::from_msg(err.to_string(), Code::InvalidDocumentFilter),
+            })?;
+            KindWithContent::DocumentDeletionByFilter {
+                index_uid: index_uid.into_inner(),
+                filter_expr: filter,
+            }
+        }
+    };
+
+    // the schedule registers its tasks on behalf of the key, which must be allowed to register
+    // them itself.
+    if let Some(key_uid) = index_scheduler.filters().key_uid() {
+        let (action, index) = match &kind {
+            KindWithContent::DocumentDeletionByFilter { index_uid, .. } => {
+                (Action::DocumentsDelete, Some(index_uid.as_str()))
+            }
+            _ => (Action::SnapshotsCreate, None),
+        };
+        if !auth_controller.is_key_authorized(key_uid, action, index)? {
+            return Err(AuthenticationError::InvalidToken.into());
+        }
+    }
+
+    analytics.publish(
+        "Schedule Created".to_string(),
+        json!({ "type": schedule_type, "interval": interval }),
+        Some(&req),
+    );
+
+    let schedule = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_schedule(kind, Duration::from_secs(interval), start_at)
+    })
+    .await??;
+    let schedule = ScheduleView::from_schedule(schedule);
+
+    debug!(returns = ?schedule, "Create schedule");
+    Ok(HttpResponse::Created().json(schedule))
+}
+
+pub async fn get_schedules(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SCHEDULES_GET }>, Data<IndexScheduler>>,
+) -> Result<HttpResponse, ResponseError> {
+    let filters = index_scheduler.filters();
+    let schedules: Vec<_> = index_scheduler
+        .get_schedules()?
+        .into_iter()
+        .filter(|schedule| schedule.kind.indexes().iter().all(|i| filters.is_index_authorized(i)))
+        .map(ScheduleView::from_schedule)
+        .collect();
+
+    debug!(returns = ?schedules, "Get schedules");
+    Ok(HttpResponse::Ok().json(json!({ "results": schedules })))
+}
+
+pub async fn get_schedule(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SCHEDULES_GET }>, Data<IndexScheduler>>,
+    schedule_uid: web::Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let schedule_uid = parse_schedule_uid(schedule_uid.into_inner())?;
+    let schedule = index_scheduler.get_schedule(schedule_uid)?;
+    if !schedule.kind.indexes().iter().all(|i| index_scheduler.filters().is_index_authorized(i)) {
+        return Err(index_scheduler::Error::ScheduleNotFound(schedule_uid).into());
+    }
+    let schedule = ScheduleView::from_schedule(schedule);
+
+    debug!(returns = ?schedule, "Get schedule");
+    Ok(HttpResponse::Ok().json(schedule))
+}
+
+pub async fn delete_schedule(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SCHEDULES_DELETE }>, Data<IndexScheduler>>,
+    schedule_uid: web::Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let schedule_uid = parse_schedule_uid(schedule_uid.into_inner())?;
+    let schedule = index_scheduler.get_schedule(schedule_uid)?;
+    if !schedule.kind.indexes().iter().all(|i| index_scheduler.filters().is_index_authorized(i)) {
+        return Err(index_scheduler::Error::ScheduleNotFound(schedule_uid).into());
+    }
+    let schedule =
+        tokio::task::spawn_blocking(move || index_scheduler.delete_schedule(schedule_uid))
+            .await??;
+    let schedule = ScheduleView::from_schedule(schedule);
+
+    debug!(returns = ?schedule, "Delete schedule");
+    Ok(HttpResponse::Ok().json(schedule))
+}
+
+fn parse_schedule_uid(schedule_uid: String) -> Result<ScheduleId, ResponseError> {
+    schedule_uid.parse().map_err(|_| {
+        ResponseError::from_msg(
+            format!("Schedule uid `{schedule_uid}` is invalid. It should only contain numeric characters."),
+            Code::BadRequest,
+        )
+    })
+}
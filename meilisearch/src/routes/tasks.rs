@@ -6,7 +6,7 @@ use index_scheduler::{IndexScheduler, Query, TaskId};
 use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::DeserrQueryParamError;
 use meilisearch_types::error::deserr_codes::*;
-use meilisearch_types::error::{InvalidTaskDateError, ResponseError};
+use meilisearch_types::error::{Code, InvalidTaskDateError, ResponseError};
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::star_or::{OptionStarOr, OptionStarOrList};
 use meilisearch_types::task_view::TaskView;
@@ -27,6 +27,9 @@ use crate::Opt;
 
 const DEFAULT_LIMIT: u32 = 20;
 
+/// The maximum time a client can wait for a task to be finished, in milliseconds.
+const MAX_TASK_WAIT_MS: u64 = 60_000;
+
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::resource("")
@@ -34,7 +37,8 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
             .route(web::delete().to(SeqHandler(delete_tasks))),
     )
     .service(web::resource("/cancel").route(web::post().to(SeqHandler(cancel_tasks))))
-    .service(web::resource("/{task_id}").route(web::get().to(SeqHandler(get_task))));
+    .service(web::resource("/{task_id}").route(web::get().to(SeqHandler(get_task))))
+    .service(web::resource("/{task_id}/retry").route(web::post().to(SeqHandler(retry_task))));
 }
 #[derive(Debug, Deserr)]
 #[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
@@ -209,6 +213,37 @@ async fn cancel_tasks(
     Ok(HttpResponse::Ok().json(task))
 }
 
+async fn retry_task(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_RETRY }>, Data<IndexScheduler>>,
+    task_uid: web::Path<String>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let task_uid_string = task_uid.into_inner();
+
+    let task_uid: TaskId = match task_uid_string.parse() {
+        Ok(id) => id,
+        Err(_e) => {
+            return Err(index_scheduler::Error::InvalidTaskUids { task_uid: task_uid_string }.into())
+        }
+    };
+
+    analytics.publish("Task Retried".to_string(), json!({}), Some(&req));
+
+    // The task can only be retried if it's visible with the restrictions of the API key.
+    let query = index_scheduler::Query { uids: Some(vec![task_uid]), ..Query::default() };
+    let filters = index_scheduler.filters();
+    let (tasks, _) = index_scheduler.get_tasks_from_authorized_indexes(query, filters)?;
+    if tasks.is_empty() {
+        return Err(index_scheduler::Error::TaskNotFound(task_uid).into());
+    }
+
+    let task = task::spawn_blocking(move || index_scheduler.retry(task_uid)).await??;
+    let task: SummarizedTaskView = task.into();
+
+    Ok(HttpResponse::Accepted().json(task))
+}
+
 async fn delete_tasks(
     index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_DELETE }>, Data<IndexScheduler>>,
     params: AwebQueryParameter<TaskDeletionOrCancelationQuery, DeserrQueryParamError>,
@@ -295,9 +330,17 @@ async fn get_tasks(
     Ok(HttpResponse::Ok().json(tasks))
 }
 
+#[derive(Debug, Deserr)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+pub struct TaskQuery {
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskWaitMs>)]
+    pub wait_ms: Option<Param<u64>>,
+}
+
 async fn get_task(
     index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_GET }>, Data<IndexScheduler>>,
     task_uid: web::Path<String>,
+    params: AwebQueryParameter<TaskQuery, DeserrQueryParamError>,
     req: HttpRequest,
     analytics: web::Data<dyn Analytics>,
 ) -> Result<HttpResponse, ResponseError> {
@@ -309,18 +352,45 @@ async fn get_task(
             return Err(index_scheduler::Error::InvalidTaskUids { task_uid: task_uid_string }.into())
         }
     };
+    let wait_ms = params.into_inner().wait_ms.map_or(0, |wait_ms| wait_ms.0);
+    if wait_ms > MAX_TASK_WAIT_MS {
+        return Err(ResponseError::from_msg(
+            format!(
+                "`waitMs` must be at most {MAX_TASK_WAIT_MS} milliseconds, but found `{wait_ms}`."
+            ),
+            Code::InvalidTaskWaitMs,
+        ));
+    }
 
-    analytics.publish("Tasks Seen".to_string(), json!({ "per_task_uid": true }), Some(&req));
-
-    let query = index_scheduler::Query { uids: Some(vec![task_uid]), ..Query::default() };
-    let filters = index_scheduler.filters();
-    let (tasks, _) = index_scheduler.get_tasks_from_authorized_indexes(query, filters)?;
+    analytics.publish(
+        "Tasks Seen".to_string(),
+        json!({ "per_task_uid": true, "wait": wait_ms != 0 }),
+        Some(&req),
+    );
 
-    if let Some(task) = tasks.first() {
-        let task_view = TaskView::from_task(task);
-        Ok(HttpResponse::Ok().json(task_view))
-    } else {
-        Err(index_scheduler::Error::TaskNotFound(task_uid).into())
+    // When asked to, we hold the request until the task is finished or the time is up,
+    // checking the task again every time a batch is processed.
+    let deadline = tokio::time::Instant::now() + std::time::Duration::from_millis(wait_ms);
+    let mut finished_batches = index_scheduler.subscribe_to_finished_batches();
+    loop {
+        let query = index_scheduler::Query { uids: Some(vec![task_uid]), ..Query::default() };
+        let filters = index_scheduler.filters();
+        let (tasks, _) = index_scheduler.get_tasks_from_authorized_indexes(query, filters)?;
+
+        let Some(task) = tasks.first() else {
+            return Err(index_scheduler::Error::TaskNotFound(task_uid).into());
+        };
+        let finished = matches!(task.status, Status::Succeeded | Status::Failed | Status::Canceled);
+        if finished || tokio::time::Instant::now() >= deadline {
+            let task_view = TaskView::from_task(task);
+            return Ok(HttpResponse::Ok().json(task_view));
+        }
+        // once the time is up, the task is checked one last time.
+        if let Ok(Err(_)) = tokio::time::timeout_at(deadline, finished_batches.changed()).await {
+            // the scheduler is gone, the task can't change anymore.
+            let task_view = TaskView::from_task(task);
+            return Ok(HttpResponse::Ok().json(task_view));
+        }
     }
 }
 
@@ -599,7 +669,7 @@ mod tests {
             let err = deserr_query_params::<TaskDeletionOrCancelationQuery>(params).unwrap_err();
             snapshot!(meili_snap::json_string!(err), @r###"
             {
-              "message": "Invalid value in parameter `types`: `createIndex` is not a valid task type. Available types are `documentAdditionOrUpdate`, `documentDeletion`, `settingsUpdate`, `indexCreation`, `indexDeletion`, `indexUpdate`, `indexSwap`, `taskCancelation`, `taskDeletion`, `dumpCreation`, `snapshotCreation`.",
+              "message": "Invalid value in parameter `types`: `createIndex` is not a valid task type. Available types are `documentAdditionOrUpdate`, `documentDeletion`, `settingsUpdate`, `indexCreation`, `indexDeletion`, `indexUpdate`, `indexSwap`, `taskCancelation`, `taskDeletion`, `dumpCreation`, `snapshotCreation`, `dumpImport`, `indexExport`, `indexPreviewCreation`, `indexMerge`, `indexSplit`.",
               "code": "invalid_task_types",
               "type": "invalid_request",
               "link": "https://docs.meilisearch.com/errors#invalid_task_types"
@@ -1,9 +1,11 @@
 use std::cmp::min;
 use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::convert::Infallible;
+use std::ops::Range;
 use std::str::FromStr;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
-use deserr::Deserr;
+use deserr::{DeserializeError, Deserr, ErrorKind, IntoValue, ValueKind, ValuePointerRef};
 use either::Either;
 use index_scheduler::RoFeatures;
 use indexmap::IndexMap;
@@ -12,21 +14,28 @@ use meilisearch_types::deserr::DeserrJsonError;
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::heed::RoTxn;
 use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::locales::Locale;
+use meilisearch_types::milli::localized_attributes_rules::script_language_allow_list;
 use meilisearch_types::milli::score_details::{self, ScoreDetails, ScoringStrategy};
+use meilisearch_types::milli::stemming::Stemmers;
 use meilisearch_types::milli::vector::DistributionShift;
 use meilisearch_types::milli::{FacetValueHit, OrderBy, SearchForFacetValues};
 use meilisearch_types::settings::DEFAULT_PAGINATION_MAX_TOTAL_HITS;
 use meilisearch_types::{milli, Document};
 use milli::tokenizer::TokenizerBuilder;
 use milli::{
-    AscDesc, FieldId, FieldsIdsMap, Filter, FormatOptions, Index, MatchBounds, MatcherBuilder,
-    SortError, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
+    AscDesc, FacetAggregates, FacetBuckets, FieldId, FieldsIdsMap, Filter, FormatOptions, GroupBy,
+    Index, MatchBounds, MatcherBuilder, Member, NestedFilter, ScoreBoost, SortError,
+    TermsMatchingStrategy, TimeBudget, DEFAULT_VALUES_PER_FACET,
 };
 use regex::Regex;
-use serde::Serialize;
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+use serde_json::value::RawValue;
 use serde_json::{json, Value};
 
 use crate::error::MeilisearchHttpError;
+use crate::Opt;
 
 type MatchesPosition = BTreeMap<String, Vec<MatchBounds>>;
 
@@ -37,6 +46,7 @@ pub const DEFAULT_CROP_MARKER: fn() -> String = || "…".to_string();
 pub const DEFAULT_HIGHLIGHT_PRE_TAG: fn() -> String = || "<em>".to_string();
 pub const DEFAULT_HIGHLIGHT_POST_TAG: fn() -> String = || "</em>".to_string();
 pub const DEFAULT_SEMANTIC_RATIO: fn() -> SemanticRatio = || SemanticRatio(0.5);
+pub const DEFAULT_GROUP_BY_LIMIT: fn() -> usize = || 3;
 
 #[derive(Debug, Clone, Default, PartialEq, Deserr)]
 #[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
@@ -73,8 +83,18 @@ pub struct SearchQuery {
     pub filter: Option<Value>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchSort>)]
     pub sort: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSortFirst>)]
+    pub sort_first: Option<bool>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchRetrieveVectors>)]
+    pub retrieve_vectors: Option<bool>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
-    pub facets: Option<Vec<String>>,
+    pub facets: Option<SearchFacets>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetFilters>)]
+    pub facet_filters: Option<BTreeMap<String, Value>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDisjunctiveFacets>, default)]
+    pub disjunctive_facets: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchAggregates>)]
+    pub aggregates: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
     pub highlight_pre_tag: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPostTag>, default = DEFAULT_HIGHLIGHT_POST_TAG())]
@@ -85,6 +105,14 @@ pub struct SearchQuery {
     pub matching_strategy: MatchingStrategy,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToSearchOn>, default)]
     pub attributes_to_search_on: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchLocales>)]
+    pub locales: Option<Vec<Locale>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchBoosts>)]
+    pub boosts: Vec<SearchBoost>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchGroupBy>)]
+    pub group_by: Option<SearchGroupBy>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchNested>)]
+    pub nested: Option<SearchNested>,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Deserr)]
@@ -101,6 +129,138 @@ pub struct HybridQuery {
 #[deserr(try_from(f32) = TryFrom::try_from -> InvalidSearchSemanticRatio)]
 pub struct SemanticRatio(f32);
 
+/// The attributes whose values are counted, either a list of attributes or a map of the attributes
+/// to the buckets their numeric values are counted in, e.g.
+/// `{ "genre": {}, "price": { "ranges": [[0, 50], [50, 100]] } }`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchFacets {
+    /// All the requested attributes.
+    pub names: Vec<String>,
+    /// The requested attributes whose numeric values are counted in buckets.
+    pub buckets: BTreeMap<String, FacetBuckets>,
+}
+
+impl From<Vec<String>> for SearchFacets {
+    fn from(names: Vec<String>) -> Self {
+        SearchFacets { names, buckets: BTreeMap::new() }
+    }
+}
+
+/// The buckets of an attribute, none of them to count its values one by one.
+#[derive(Debug, Deserr)]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+struct FacetBucketsView {
+    #[deserr(default)]
+    ranges: Option<Vec<Vec<f64>>>,
+    #[deserr(default)]
+    interval: Option<f64>,
+}
+
+impl<E: DeserializeError> Deserr<E> for SearchFacets {
+    fn deserialize_from_value<V: IntoValue>(
+        value: deserr::Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        match value {
+            value @ deserr::Value::Sequence(_) => {
+                Vec::<String>::deserialize_from_value(value, location).map(SearchFacets::from)
+            }
+            value @ deserr::Value::Map(_) => {
+                let views =
+                    BTreeMap::<String, FacetBucketsView>::deserialize_from_value(value, location)?;
+                let mut facets = SearchFacets::default();
+                for (name, FacetBucketsView { ranges, interval }) in views {
+                    let error = |msg: String| {
+                        deserr::take_cf_content(E::error::<Infallible>(
+                            None,
+                            ErrorKind::Unexpected { msg },
+                            location.push_key(&name),
+                        ))
+                    };
+                    let buckets = match (ranges, interval) {
+                        (None, None) => None,
+                        (Some(ranges), None) => {
+                            let mut buckets = Vec::with_capacity(ranges.len());
+                            for range in ranges {
+                                match range[..] {
+                                    [start, end] if start < end => buckets.push(start..end),
+                                    _ => {
+                                        return Err(error(format!(
+                                            "A range must be written like `[start, end]` with a start lower than its end, found `{range:?}`."
+                                        )))
+                                    }
+                                }
+                            }
+                            Some(FacetBuckets::Ranges(buckets))
+                        }
+                        (None, Some(interval)) if interval > 0.0 => {
+                            Some(FacetBuckets::Interval(interval))
+                        }
+                        (None, Some(interval)) => {
+                            return Err(error(format!(
+                                "The interval must be a positive number, found `{interval}`."
+                            )))
+                        }
+                        (Some(_), Some(_)) => {
+                            return Err(error(
+                                "The `ranges` and the `interval` can't be used together."
+                                    .to_string(),
+                            ))
+                        }
+                    };
+                    if let Some(buckets) = buckets {
+                        facets.buckets.insert(name.clone(), buckets);
+                    }
+                    facets.names.push(name);
+                }
+                Ok(facets)
+            }
+            value => Err(deserr::take_cf_content(E::error::<V>(
+                None,
+                ErrorKind::IncorrectValueKind {
+                    actual: value,
+                    accepted: &[ValueKind::Sequence, ValueKind::Map],
+                },
+                location,
+            ))),
+        }
+    }
+}
+
+/// Returns the number of candidates of each bucket keyed by its range, e.g. `0..50`.
+fn bucket_values(buckets: Vec<(Range<f64>, u64)>) -> IndexMap<String, u64> {
+    buckets
+        .into_iter()
+        .map(|(range, count)| (format!("{}..{}", range.start, range.end), count))
+        .collect()
+}
+
+/// A factor multiplying the ranking score of the documents matching the filter.
+#[derive(Debug, Clone, PartialEq, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidSearchBoosts>, rename_all = camelCase, deny_unknown_fields)]
+pub struct SearchBoost {
+    pub filter: Value,
+    pub factor: f64,
+}
+
+/// Collapses the hits by the values of an attribute, each hit comes with the best hits of its group.
+#[derive(Debug, Clone, PartialEq, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidSearchGroupBy>, rename_all = camelCase, deny_unknown_fields)]
+pub struct SearchGroupBy {
+    pub attribute: String,
+    #[deserr(default = DEFAULT_GROUP_BY_LIMIT())]
+    pub limit: usize,
+}
+
+/// Restricts the hits to the documents having an element of an array of objects matching a filter
+/// on the fields of the elements, the facets of the elements only count the matching elements.
+#[derive(Debug, Clone, PartialEq, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidSearchNested>, rename_all = camelCase, deny_unknown_fields)]
+pub struct SearchNested {
+    pub attribute: String,
+    pub filter: Value,
+}
+
 impl Default for SemanticRatio {
     fn default() -> Self {
         DEFAULT_SEMANTIC_RATIO()
@@ -176,8 +336,18 @@ pub struct SearchQueryWithIndex {
     pub filter: Option<Value>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchSort>)]
     pub sort: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSortFirst>)]
+    pub sort_first: Option<bool>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchRetrieveVectors>)]
+    pub retrieve_vectors: Option<bool>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
-    pub facets: Option<Vec<String>>,
+    pub facets: Option<SearchFacets>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetFilters>)]
+    pub facet_filters: Option<BTreeMap<String, Value>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDisjunctiveFacets>, default)]
+    pub disjunctive_facets: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchAggregates>)]
+    pub aggregates: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
     pub highlight_pre_tag: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPostTag>, default = DEFAULT_HIGHLIGHT_POST_TAG())]
@@ -188,6 +358,14 @@ pub struct SearchQueryWithIndex {
     pub matching_strategy: MatchingStrategy,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToSearchOn>, default)]
     pub attributes_to_search_on: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchLocales>)]
+    pub locales: Option<Vec<Locale>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchBoosts>)]
+    pub boosts: Vec<SearchBoost>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchGroupBy>)]
+    pub group_by: Option<SearchGroupBy>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchNested>)]
+    pub nested: Option<SearchNested>,
 }
 
 impl SearchQueryWithIndex {
@@ -209,13 +387,22 @@ impl SearchQueryWithIndex {
             show_matches_position,
             filter,
             sort,
+            sort_first,
+            retrieve_vectors,
             facets,
+            facet_filters,
+            disjunctive_facets,
+            aggregates,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
             matching_strategy,
             attributes_to_search_on,
+            locales,
             hybrid,
+            boosts,
+            group_by,
+            nested,
         } = self;
         (
             index_uid,
@@ -235,13 +422,22 @@ impl SearchQueryWithIndex {
                 show_matches_position,
                 filter,
                 sort,
+                sort_first,
+                retrieve_vectors,
                 facets,
+                facet_filters,
+                disjunctive_facets,
+                aggregates,
                 highlight_pre_tag,
                 highlight_post_tag,
                 crop_marker,
                 matching_strategy,
                 attributes_to_search_on,
+                locales,
                 hybrid,
+                boosts,
+                group_by,
+                nested,
                 // do not use ..Default::default() here,
                 // rather add any missing field from `SearchQuery` to `SearchQueryWithIndex`
             },
@@ -296,7 +492,7 @@ impl From<FacetValuesSort> for OrderBy {
 #[derive(Debug, Clone, Serialize, PartialEq)]
 pub struct SearchHit {
     #[serde(flatten)]
-    pub document: Document,
+    pub document: HitDocument,
     #[serde(rename = "_formatted", skip_serializing_if = "Document::is_empty")]
     pub formatted: Document,
     #[serde(rename = "_matchesPosition", skip_serializing_if = "Option::is_none")]
@@ -307,6 +503,56 @@ pub struct SearchHit {
     pub ranking_score_details: Option<serde_json::Map<String, serde_json::Value>>,
     #[serde(rename = "_semanticScore", skip_serializing_if = "Option::is_none")]
     pub semantic_score: Option<f32>,
+    #[serde(rename = "_group", skip_serializing_if = "Option::is_none")]
+    pub group: Option<SearchHitGroup>,
+}
+
+/// The best hits of the group of a hit, starting with the hit itself.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHitGroup {
+    pub total_hits: u64,
+    pub hits: Vec<SearchHit>,
+}
+
+/// The fields of a hit.
+#[derive(Debug, Clone)]
+pub enum HitDocument {
+    /// The fields parsed from the index, to be selected or formatted.
+    Parsed(Document),
+    /// The fields as they are stored in the index, already serialized in JSON, that are written
+    /// as is in the response.
+    Raw(Vec<(String, Box<RawValue>)>),
+}
+
+impl Serialize for HitDocument {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            HitDocument::Parsed(document) => document.serialize(serializer),
+            HitDocument::Raw(fields) => {
+                let mut map = serializer.serialize_map(Some(fields.len()))?;
+                for (name, value) in fields {
+                    map.serialize_entry(name, value)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+impl PartialEq for HitDocument {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (HitDocument::Parsed(lhs), HitDocument::Parsed(rhs)) => lhs == rhs,
+            (HitDocument::Raw(lhs), HitDocument::Raw(rhs)) => {
+                lhs.len() == rhs.len()
+                    && lhs.iter().zip(rhs).all(|((lname, lvalue), (rname, rvalue))| {
+                        lname == rname && lvalue.get() == rvalue.get()
+                    })
+            }
+            _ => false,
+        }
+    }
 }
 
 #[derive(Serialize, Debug, Clone, PartialEq)]
@@ -323,6 +569,14 @@ pub struct SearchResult {
     pub facet_distribution: Option<BTreeMap<String, IndexMap<String, u64>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub facet_stats: Option<BTreeMap<String, FacetStats>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aggregates: Option<BTreeMap<String, FacetAggregate>>,
+    /// Whether the search cutoff was reached before all the documents were ranked.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub degraded: bool,
+    /// The work done by each ranking rule, only computed when asked and never returned.
+    #[serde(skip)]
+    pub diagnostics: Option<milli::SearchDiagnostics>,
 }
 
 #[derive(Serialize, Debug, Clone, PartialEq)]
@@ -348,6 +602,23 @@ pub struct FacetStats {
     pub max: f64,
 }
 
+/// The aggregation of the numeric values of an attribute among all the candidates.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct FacetAggregate {
+    pub count: u64,
+    pub sum: f64,
+    pub avg: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl From<milli::FacetAggregate> for FacetAggregate {
+    fn from(aggregate: milli::FacetAggregate) -> Self {
+        let milli::FacetAggregate { count, sum, min, max } = aggregate;
+        Self { count, sum, avg: aggregate.avg(), min, max }
+    }
+}
+
 #[derive(Serialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct FacetSearchResult {
@@ -356,6 +627,120 @@ pub struct FacetSearchResult {
     pub processing_time_ms: u128,
 }
 
+/// The number of candidates of a search query after each step of their resolution.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CandidatesStatistics {
+    pub number_of_documents: u64,
+    pub after_words: u64,
+    pub after_typos: u64,
+    pub after_filter: u64,
+    pub processing_time_ms: u128,
+}
+
+/// How a search query is tokenized, derived, filtered and ranked.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchExplanation {
+    pub terms: Vec<ExplainedTerm>,
+    /// The filter, as it's parsed.
+    pub filter: Option<String>,
+    pub ranking_rules: Vec<ExplainedRankingRule>,
+    #[serde(flatten)]
+    pub candidates: CandidatesStatistics,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExplainedTerm {
+    pub original: String,
+    /// The first and last positions of the term in the query.
+    pub positions: [u16; 2],
+    pub is_prefix: bool,
+    pub is_phrase: bool,
+    pub exact: Option<String>,
+    pub prefix_of: Vec<String>,
+    pub one_typo: Vec<String>,
+    pub two_typos: Vec<String>,
+    pub split_words: Option<String>,
+    pub synonyms: Vec<String>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExplainedRankingRule {
+    pub name: String,
+    /// The number of buckets the ranking rule sorted its candidates into.
+    pub buckets: usize,
+    /// The number of candidates in these buckets.
+    pub candidates: u64,
+}
+
+/// The layout of the search responses.
+///
+/// Lets the clients pinned to a previous major version keep receiving the fields they know how to parse.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseVersion {
+    /// The layout of the v0.x search responses, before `nbHits` and `facetsDistribution` were renamed.
+    V0,
+    /// The current layout.
+    #[default]
+    V1,
+}
+
+impl FromStr for ResponseVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "0" | "v0" => Ok(ResponseVersion::V0),
+            "1" | "v1" => Ok(ResponseVersion::V1),
+            _ => Err(format!(
+                "Unsupported response version `{s}`. Supported values are `v0` and `v1`."
+            )),
+        }
+    }
+}
+
+impl ResponseVersion {
+    /// Serializes a search result in the layout of this version.
+    ///
+    /// The current layout is serialized directly, so that the raw hits are never parsed.
+    pub fn format(self, result: impl Serialize) -> Result<Box<RawValue>, MeilisearchHttpError> {
+        if self == ResponseVersion::V1 {
+            return Ok(serde_json::value::to_raw_value(&result)?);
+        }
+
+        let mut result = serde_json::to_value(result)?;
+        match (self, &mut result) {
+            (ResponseVersion::V0, Value::Object(result)) => {
+                let degraded = result.get("degraded").and_then(Value::as_bool).unwrap_or(false);
+                if let Some(estimated_total_hits) = result.remove("estimatedTotalHits") {
+                    // the estimation is exact when the last hits are returned.
+                    let hits = result.get("hits").and_then(Value::as_array).map_or(0, Vec::len);
+                    let offset = result.get("offset").and_then(Value::as_u64).unwrap_or(0);
+                    let limit = result.get("limit").and_then(Value::as_u64).unwrap_or(0);
+                    let exhaustive = !degraded
+                        && (hits as u64) < limit
+                        && estimated_total_hits.as_u64() == Some(offset + hits as u64);
+                    result.insert("nbHits".to_string(), estimated_total_hits);
+                    result.insert("exhaustiveNbHits".to_string(), Value::Bool(exhaustive));
+                } else if let Some(total_hits) = result.get("totalHits").cloned() {
+                    // the hits are counted exhaustively with the page selection.
+                    result.insert("nbHits".to_string(), total_hits);
+                    result.insert("exhaustiveNbHits".to_string(), Value::Bool(!degraded));
+                }
+                if let Some(facet_distribution) = result.remove("facetDistribution") {
+                    result.insert("facetsDistribution".to_string(), facet_distribution);
+                    result.insert("exhaustiveFacetsCount".to_string(), Value::Bool(false));
+                }
+            }
+            (ResponseVersion::V0, _) | (ResponseVersion::V1, _) => (),
+        }
+        Ok(serde_json::value::to_raw_value(&result)?)
+    }
+}
+
 /// Incorporate search rules in search query
 pub fn add_search_rules(query: &mut SearchQuery, rules: IndexSearchRules) {
     query.filter = match (query.filter.take(), rules.filter) {
@@ -376,11 +761,84 @@ pub fn add_search_rules(query: &mut SearchQuery, rules: IndexSearchRules) {
     }
 }
 
+/// Returns whether an API key or a tenant token restricted to these attributes can access the
+/// field, the fields nested in an allowed attribute being allowed too.
+pub fn is_attribute_allowed(restricted_attributes: Option<&[String]>, field: &str) -> bool {
+    restricted_attributes.map_or(true, |attributes| {
+        attributes.iter().any(|attr| attr == "*" || milli::is_faceted_by(field, attr))
+    })
+}
+
+/// Rejects the queries filtering, sorting, faceting, aggregating, grouping or searching on an
+/// attribute the API key or the tenant token can't access, as their results would reveal its values.
+///
+/// Must be called before the search rules of the tenant token are added to the query, as their
+/// filter can use any attribute.
+pub fn check_restricted_attributes(
+    query: &SearchQuery,
+    restricted_attributes: Option<&[String]>,
+    filter_limits: FilterLimits,
+) -> Result<(), MeilisearchHttpError> {
+    let check = |field: &str| {
+        if is_attribute_allowed(restricted_attributes, field) {
+            Ok(())
+        } else {
+            Err(MeilisearchHttpError::ForbiddenAttribute(field.to_string()))
+        }
+    };
+    let check_filter = |filter: &Value| match parse_filter(filter, filter_limits) {
+        Ok(Some(filter)) => filter.fields().into_iter().try_for_each(check),
+        // the invalid filters are rejected by the search itself
+        Ok(None) | Err(_) => Ok(()),
+    };
+    if restricted_attributes.map_or(true, |attrs| attrs.iter().any(|attr| attr == "*")) {
+        return Ok(());
+    }
+
+    if let Some(filter) = &query.filter {
+        check_filter(filter)?;
+    }
+    for (facet, filter) in query.facet_filters.iter().flatten() {
+        check(facet)?;
+        check_filter(filter)?;
+    }
+    for boost in &query.boosts {
+        check_filter(&boost.filter)?;
+    }
+    for sort in query.sort.iter().flatten() {
+        match sort.parse::<AscDesc>().as_ref().map(AscDesc::member) {
+            Ok(Member::Field(field)) => check(field)?,
+            Ok(Member::Geo(_)) => check("_geo")?,
+            // the invalid sorts are rejected by the search itself
+            Ok(Member::Random(_)) | Err(_) => (),
+        }
+    }
+    if let Some(facets) = &query.facets {
+        facets.names.iter().filter(|name| *name != "*").try_for_each(|name| check(name))?;
+        facets.buckets.keys().try_for_each(|name| check(name))?;
+    }
+    query.aggregates.iter().flatten().try_for_each(|field| check(field))?;
+    query
+        .attributes_to_search_on
+        .iter()
+        .flatten()
+        .filter(|field| *field != "*")
+        .try_for_each(|field| check(field))?;
+    if let Some(group_by) = &query.group_by {
+        check(&group_by.attribute)?;
+    }
+    if let Some(nested) = &query.nested {
+        check(&nested.attribute)?;
+    }
+    Ok(())
+}
+
 fn prepare_search<'t>(
     index: &'t Index,
     rtxn: &'t RoTxn,
     query: &'t SearchQuery,
     features: RoFeatures,
+    filter_limits: FilterLimits,
     distribution: Option<DistributionShift>,
 ) -> Result<(milli::Search<'t>, bool, usize, usize), MeilisearchHttpError> {
     let mut search = index.search(rtxn);
@@ -465,7 +923,7 @@ fn prepare_search<'t>(
     search.limit(limit);
 
     if let Some(ref filter) = query.filter {
-        if let Some(facets) = parse_filter(filter)? {
+        if let Some(facets) = parse_filter(filter, filter_limits)? {
             search.filter(facets);
         }
     }
@@ -481,37 +939,180 @@ fn prepare_search<'t>(
         search.sort_criteria(sort);
     }
 
+    if let Some(sort_first) = query.sort_first {
+        if query.sort.as_ref().map_or(true, Vec::is_empty) {
+            return Err(MeilisearchHttpError::SortFirstWithoutSort);
+        }
+        search.sort_first(sort_first);
+    }
+
+    if let Some(ref locales) = query.locales {
+        search.locales(locales.iter().copied().map(Into::into).collect());
+    }
+
+    let mut boosts = Vec::with_capacity(query.boosts.len());
+    for SearchBoost { filter, factor } in &query.boosts {
+        if !factor.is_finite() || *factor <= 0.0 {
+            return Err(MeilisearchHttpError::InvalidSearchBoost(format!(
+                "the factor `{factor}` must be a positive number."
+            )));
+        }
+        let filter = parse_filter(filter, filter_limits)
+            .map_err(|e| MeilisearchHttpError::InvalidSearchBoost(e.to_string()))?;
+        if let Some(filter) = filter {
+            boosts.push(ScoreBoost { filter, factor: *factor });
+        }
+    }
+    search.boosts(boosts);
+
+    if let Some(SearchGroupBy { attribute, limit }) = &query.group_by {
+        // the keyword and semantic hits are merged after being grouped separately
+        if query
+            .hybrid
+            .as_ref()
+            .is_some_and(|hybrid| *hybrid.semantic_ratio != 0.0 && *hybrid.semantic_ratio != 1.0)
+        {
+            return Err(MeilisearchHttpError::GroupByWithHybrid);
+        }
+        search.group_by(GroupBy { field: attribute.clone(), limit: *limit });
+    }
+
+    if let Some(nested) = &query.nested {
+        if let Some(nested_filter) = parse_nested(nested, filter_limits)? {
+            search.nested_filter(nested_filter);
+        }
+    }
+
     Ok((search, is_finite_pagination, max_total_hits, offset))
 }
 
+#[allow(clippy::too_many_arguments)]
+#[tracing::instrument(level = "trace", skip_all, target = "search")]
 pub fn perform_search(
     index: &Index,
-    query: SearchQuery,
+    mut query: SearchQuery,
     features: RoFeatures,
+    filter_limits: FilterLimits,
     distribution: Option<DistributionShift>,
+    restricted_attributes: Option<Vec<String>>,
+    search_cutoff_ms: Option<u64>,
+    diagnostics: bool,
 ) -> Result<SearchResult, MeilisearchHttpError> {
     let before_search = Instant::now();
     let rtxn = index.read_txn()?;
 
-    let (search, is_finite_pagination, max_total_hits, offset) =
-        prepare_search(index, &rtxn, &query, features, distribution)?;
+    // The facet filters restrict the hits like the filter, but the distribution of each facet
+    // ignores its own facet filter so that the other values of the facet can still be selected.
+    let facet_filters = query.facet_filters.take().unwrap_or_default();
+    let requested = |facet: &str| {
+        query.facets.iter().flat_map(|f| &f.names).any(|name| name == "*" || name == facet)
+    };
+    let mut facet_queries = Vec::new();
+    if !facet_filters.is_empty() {
+        for (facet, facet_filter) in &facet_filters {
+            parse_filter(facet_filter, filter_limits).map_err(|e| {
+                MeilisearchHttpError::InvalidFacetFilter(facet.clone(), e.to_string())
+            })?;
+        }
+        for facet in facet_filters.keys().filter(|facet| requested(facet)) {
+            let others = facet_filters.iter().filter(|(name, _)| *name != facet);
+            let filter = combine_filters(query.filter.iter().chain(others.map(|(_, f)| f)));
+            facet_queries.push((facet.clone(), facet_query(&query, filter)));
+        }
+        query.filter = combine_filters(query.filter.iter().chain(facet_filters.values()));
+    }
 
-    let milli::SearchResult { documents_ids, matching_words, candidates, document_scores, .. } =
-        match &query.hybrid {
-            Some(hybrid) => match *hybrid.semantic_ratio {
-                ratio if ratio == 0.0 || ratio == 1.0 => search.execute()?,
-                ratio => search.execute_hybrid(ratio)?,
-            },
-            None => search.execute()?,
-        };
+    // With the disjunctive facets, the distribution of each facet ignores the clauses of the
+    // filter made only of this facet, without having to move them into the facet filters.
+    let filter = match &query.filter {
+        Some(filter) if query.disjunctive_facets => parse_filter(filter, filter_limits)?,
+        _ => None,
+    };
+    let mut disjunctive_filters = Vec::new();
+    if let Some(filter) = &filter {
+        let facets: BTreeSet<_> =
+            filter.fields().into_iter().filter(|f| *f != "_geo" && requested(f)).collect();
+        for facet in facets {
+            disjunctive_filters.push((facet.to_string(), filter.without_field(facet)));
+        }
+        facet_queries.retain(|(facet, _)| disjunctive_filters.iter().all(|(f, _)| f != facet));
+    }
+
+    let nested_filter = match &query.nested {
+        Some(nested) => parse_nested(nested, filter_limits)?,
+        None => None,
+    };
+
+    let (mut search, is_finite_pagination, max_total_hits, offset) =
+        prepare_search(index, &rtxn, &query, features, filter_limits, distribution)?;
+
+    // The cutoff of the index takes precedence over the one of the instance.
+    if let Some(cutoff) = index.search_cutoff(&rtxn)?.or(search_cutoff_ms) {
+        search.time_budget(TimeBudget::new(Duration::from_millis(cutoff)));
+    }
+    search.diagnostics(diagnostics);
+
+    let milli::SearchResult {
+        documents_ids,
+        matching_words,
+        candidates,
+        document_scores,
+        degraded,
+        diagnostics,
+        groups,
+        ..
+    } = match &query.hybrid {
+        Some(hybrid) => match *hybrid.semantic_ratio {
+            ratio if ratio == 0.0 || ratio == 1.0 => search.execute()?,
+            ratio => search.execute_hybrid(ratio)?,
+        },
+        None => search.execute()?,
+    };
+
+    let mut facet_candidates = Vec::with_capacity(facet_queries.len());
+    for (facet, facet_query) in &facet_queries {
+        let (search, _, _, _) =
+            prepare_search(index, &rtxn, facet_query, features, filter_limits, distribution)?;
+        let candidates = search.execute_for_candidates(facet_query.hybrid.is_some())?;
+        facet_candidates.push((facet, candidates));
+    }
+    for (facet, facet_filter) in &disjunctive_filters {
+        let facet_query = facet_query(&query, None);
+        let (mut search, _, _, _) =
+            prepare_search(index, &rtxn, &facet_query, features, filter_limits, distribution)?;
+        if let Some(facet_filter) = facet_filter {
+            search.filter(facet_filter.clone());
+        }
+        let candidates = search.execute_for_candidates(facet_query.hybrid.is_some())?;
+        facet_candidates.push((facet, candidates));
+    }
 
     let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
 
-    let displayed_ids = index
+    let mut displayed_ids = index
         .displayed_fields_ids(&rtxn)?
         .map(|fields| fields.into_iter().collect::<BTreeSet<_>>())
         .unwrap_or_else(|| fields_ids_map.iter().map(|(id, _)| id).collect());
 
+    // The API key or the tenant token can hide some of the displayed attributes, they must never
+    // be returned, neither in the documents nor in the formatted documents.
+    if let Some(restricted_attributes) =
+        restricted_attributes.as_ref().filter(|attrs| !attrs.iter().any(|attr| attr == "*"))
+    {
+        displayed_ids.retain(|id| {
+            fields_ids_map
+                .name(*id)
+                .map_or(false, |name| restricted_attributes.iter().any(|attr| attr == name))
+        });
+    }
+
+    // The vectors provided with the documents are returned unless the query opts out of them.
+    if query.retrieve_vectors == Some(false) {
+        displayed_ids.retain(|id| {
+            fields_ids_map.name(*id).map_or(true, |name| !milli::is_faceted_by(name, "_vectors"))
+        });
+    }
+
     let fids = |attrs: &BTreeSet<String>| {
         let mut ids = BTreeSet::new();
         for attr in attrs {
@@ -520,7 +1121,11 @@ pub fn perform_search(
                 break;
             }
 
-            if let Some(id) = fields_ids_map.id(attr) {
+            if attr.contains('*') {
+                let matching =
+                    fields_ids_map.iter().filter(|(_, name)| milli::match_pattern(attr, name));
+                ids.extend(matching.map(|(id, _)| id));
+            } else if let Some(id) = fields_ids_map.id(attr) {
                 ids.insert(id);
             }
         }
@@ -558,10 +1163,33 @@ pub fn perform_search(
         &displayed_ids,
     );
 
+    // The hits are written as the documents are stored in the index, without being parsed,
+    // when none of their fields must be selected, formatted or computed.
+    let raw_hits = formatted_options.is_empty()
+        && !query.show_matches_position
+        && query.attributes_to_retrieve.as_ref().map_or(true, |attrs| attrs.contains("*"))
+        && !query.sort.iter().flatten().any(|sort| sort.contains("_geoPoint("))
+        && displayed_ids.iter().all(|&fid| {
+            // a nested field can only be selected by parsing its root field.
+            match fields_ids_map.name(fid).and_then(|name| name.split_once('.')) {
+                Some((root, _)) => {
+                    fields_ids_map.id(root).is_some_and(|id| displayed_ids.contains(&id))
+                }
+                None => true,
+            }
+        });
+
     let mut tokenizer_builder = TokenizerBuilder::default();
     tokenizer_builder.create_char_map(true);
 
-    let script_lang_map = index.script_language(&rtxn)?;
+    // The highlighting must segment the documents the same way as the query.
+    let script_lang_map = match &query.locales {
+        Some(locales) => {
+            let locales: Vec<_> = locales.iter().copied().map(Into::into).collect();
+            script_language_allow_list(&locales)
+        }
+        None => index.script_language(&rtxn)?,
+    };
     if !script_lang_map.is_empty() {
         tokenizer_builder.allow_list(&script_lang_map);
     }
@@ -581,36 +1209,40 @@ pub fn perform_search(
     }
 
     let mut formatter_builder = MatcherBuilder::new(matching_words, tokenizer_builder.build());
+    formatter_builder.stemmers(Stemmers::new(&index.stemming(&rtxn)?.unwrap_or_default()));
     formatter_builder.crop_marker(query.crop_marker);
     formatter_builder.highlight_prefix(query.highlight_pre_tag);
     formatter_builder.highlight_suffix(query.highlight_post_tag);
 
-    let mut documents = Vec::new();
-    let documents_iter = index.documents(&rtxn, documents_ids)?;
-
-    for ((_id, obkv), score) in documents_iter.into_iter().zip(document_scores.into_iter()) {
-        // First generate a document with all the displayed fields
-        let displayed_document = make_document(&displayed_ids, &fields_ids_map, obkv)?;
-
-        // select the attributes to retrieve
-        let attributes_to_retrieve = to_retrieve_ids
-            .iter()
-            .map(|&fid| fields_ids_map.name(fid).expect("Missing field name"));
-        let mut document =
-            permissive_json_pointer::select_values(&displayed_document, attributes_to_retrieve);
-
-        let (matches_position, formatted) = format_fields(
-            &displayed_document,
-            &fields_ids_map,
-            &formatter_builder,
-            &formatted_options,
-            query.show_matches_position,
-            &displayed_ids,
-        )?;
-
-        if let Some(sort) = query.sort.as_ref() {
-            insert_geo_distance(sort, &mut document);
-        }
+    let make_hit = |obkv, score: Vec<ScoreDetails>| -> Result<_, MeilisearchHttpError> {
+        let (document, matches_position, formatted) = if raw_hits {
+            let document = make_raw_document(&displayed_ids, &fields_ids_map, obkv)?;
+            (HitDocument::Raw(document), None, Document::new())
+        } else {
+            // First generate a document with all the displayed fields
+            let displayed_document = make_document(&displayed_ids, &fields_ids_map, obkv)?;
+
+            // select the attributes to retrieve
+            let attributes_to_retrieve = to_retrieve_ids
+                .iter()
+                .map(|&fid| fields_ids_map.name(fid).expect("Missing field name"));
+            let mut document =
+                permissive_json_pointer::select_values(&displayed_document, attributes_to_retrieve);
+
+            let (matches_position, formatted) = format_fields(
+                &displayed_document,
+                &fields_ids_map,
+                &formatter_builder,
+                &formatted_options,
+                query.show_matches_position,
+                &displayed_ids,
+            )?;
+
+            if let Some(sort) = query.sort.as_ref() {
+                insert_geo_distance(sort, &mut document);
+            }
+            (HitDocument::Parsed(document), matches_position, formatted)
+        };
 
         let mut semantic_score = None;
         for details in &score {
@@ -629,15 +1261,31 @@ pub fn perform_search(
         let ranking_score_details =
             query.show_ranking_score_details.then(|| ScoreDetails::to_json_map(score.iter()));
 
-        let hit = SearchHit {
+        Ok(SearchHit {
             document,
             formatted,
             matches_position,
             ranking_score_details,
             ranking_score,
             semantic_score,
-        };
-        documents.push(hit);
+            group: None,
+        })
+    };
+
+    let mut documents = Vec::new();
+    let documents_iter = index.documents(&rtxn, documents_ids)?;
+    for ((_id, obkv), score) in documents_iter.into_iter().zip(document_scores.into_iter()) {
+        documents.push(make_hit(obkv, score)?);
+    }
+
+    // Each hit comes with the best hits of its group, the hit itself being the first one.
+    for (hit, group) in documents.iter_mut().zip(groups) {
+        let mut hits = Vec::with_capacity(group.documents_ids.len());
+        let documents_iter = index.documents(&rtxn, group.documents_ids)?;
+        for ((_id, obkv), score) in documents_iter.into_iter().zip(group.document_scores) {
+            hits.push(make_hit(obkv, score)?);
+        }
+        hit.group = Some(SearchHitGroup { total_hits: group.total_hits, hits });
     }
 
     let number_of_hits = min(candidates.len() as usize, max_total_hits);
@@ -658,6 +1306,18 @@ pub fn perform_search(
         HitsInfo::OffsetLimit { limit: query.limit, offset, estimated_total_hits: number_of_hits }
     };
 
+    // The aggregates are computed over all the candidates, not only the returned hits.
+    let aggregates = match &query.aggregates {
+        Some(fields) => {
+            let aggregates = FacetAggregates::new(&rtxn, index)
+                .fields(fields)
+                .candidates(candidates.clone())
+                .execute()?;
+            Some(aggregates.into_iter().map(|(k, aggregate)| (k, aggregate.into())).collect())
+        }
+        None => None,
+    };
+
     let (facet_distribution, facet_stats) = match query.facets {
         Some(ref fields) => {
             let mut facet_distribution = index.facets_distribution(&rtxn);
@@ -674,8 +1334,9 @@ pub fn perform_search(
             let default_sort_facet_values_by =
                 sort_facet_values_by.get("*").copied().unwrap_or_default();
 
-            if fields.iter().all(|f| f != "*") {
-                let fields: Vec<_> = fields
+            let all_fields = fields.names.iter().all(|f| f != "*").then(|| {
+                fields
+                    .names
                     .iter()
                     .map(|n| {
                         (
@@ -686,14 +1347,82 @@ pub fn perform_search(
                                 .unwrap_or(default_sort_facet_values_by),
                         )
                     })
-                    .collect();
-                facet_distribution.facets(fields);
+                    .collect::<Vec<_>>()
+            });
+            if let Some(all_fields) = &all_fields {
+                // the values of the bucketed facets are not counted one by one
+                let fields = all_fields.iter().filter(|(n, _)| !fields.buckets.contains_key(*n));
+                facet_distribution.facets(fields.cloned());
             }
-            let distribution = facet_distribution
+
+            // the values of the elements of the nested attribute are only counted for the
+            // elements matching the nested filter
+            let names: Vec<&str> = match &all_fields {
+                Some(all_fields) => all_fields.iter().map(|(name, _)| name.as_str()).collect(),
+                None => fields_ids_map.iter().map(|(_, name)| name).collect(),
+            };
+            let nested_values = match &nested_filter {
+                Some(nested_filter) => nested_filter.facet_distribution(
+                    &rtxn,
+                    index,
+                    &candidates,
+                    &names,
+                    max_values_by_facet,
+                )?,
+                None => BTreeMap::new(),
+            };
+
+            let mut distribution = facet_distribution
                 .candidates(candidates)
                 .default_order_by(default_sort_facet_values_by)
                 .execute()?;
-            let stats = facet_distribution.compute_stats()?;
+            if let Some(all_fields) = all_fields {
+                facet_distribution.facets(all_fields);
+            }
+            let mut stats = facet_distribution.compute_stats()?;
+            for (facet, buckets) in &fields.buckets {
+                let values = facet_distribution.bucket_distribution(facet, buckets)?;
+                distribution.insert(facet.clone(), bucket_values(values));
+            }
+            distribution.extend(nested_values);
+
+            for (facet, candidates) in facet_candidates {
+                let nested_values = match &nested_filter {
+                    Some(nested_filter) => nested_filter.facet_distribution(
+                        &rtxn,
+                        index,
+                        &candidates,
+                        &[facet.as_str()],
+                        max_values_by_facet,
+                    )?,
+                    None => BTreeMap::new(),
+                };
+                let order_by = sort_facet_values_by
+                    .get(facet)
+                    .copied()
+                    .unwrap_or(default_sort_facet_values_by);
+                let mut facet_distribution = index.facets_distribution(&rtxn);
+                facet_distribution
+                    .max_values_per_facet(max_values_by_facet)
+                    .facets([(facet, order_by)])
+                    .candidates(candidates);
+                if let Some(buckets) = fields.buckets.get(facet) {
+                    let values = facet_distribution.bucket_distribution(facet, buckets)?;
+                    distribution.insert(facet.clone(), bucket_values(values));
+                } else if let Some(values) = facet_distribution.execute()?.remove(facet) {
+                    distribution.insert(facet.clone(), values);
+                }
+                distribution.extend(nested_values);
+                match facet_distribution.compute_stats()?.remove(facet) {
+                    Some(facet_stats) => stats.insert(facet.clone(), facet_stats),
+                    None => stats.remove(facet),
+                };
+            }
+            // all the facets can be requested at once, the ones the API key or the tenant token
+            // can't access are left out.
+            let restricted_attributes = restricted_attributes.as_deref();
+            distribution.retain(|facet, _| is_attribute_allowed(restricted_attributes, facet));
+            stats.retain(|facet, _| is_attribute_allowed(restricted_attributes, facet));
             (Some(distribution), Some(stats))
         }
         None => (None, None),
@@ -711,6 +1440,9 @@ pub fn perform_search(
         processing_time_ms: before_search.elapsed().as_millis(),
         facet_distribution,
         facet_stats,
+        aggregates,
+        degraded,
+        diagnostics,
     };
     Ok(result)
 }
@@ -721,11 +1453,13 @@ pub fn perform_facet_search(
     facet_query: Option<String>,
     facet_name: String,
     features: RoFeatures,
+    filter_limits: FilterLimits,
 ) -> Result<FacetSearchResult, MeilisearchHttpError> {
     let before_search = Instant::now();
     let rtxn = index.read_txn()?;
 
-    let (search, _, _, _) = prepare_search(index, &rtxn, &search_query, features, None)?;
+    let (search, _, _, _) =
+        prepare_search(index, &rtxn, &search_query, features, filter_limits, None)?;
     let mut facet_search =
         SearchForFacetValues::new(facet_name, search, search_query.hybrid.is_some());
     if let Some(facet_query) = &facet_query {
@@ -742,6 +1476,100 @@ pub fn perform_facet_search(
     })
 }
 
+pub fn perform_candidates_statistics(
+    index: &Index,
+    query: SearchQuery,
+    features: RoFeatures,
+    filter_limits: FilterLimits,
+) -> Result<CandidatesStatistics, MeilisearchHttpError> {
+    let before_search = Instant::now();
+    let rtxn = index.read_txn()?;
+
+    let (search, _, _, _) = prepare_search(index, &rtxn, &query, features, filter_limits, None)?;
+    let milli::CandidatesStatistics { documents, after_words, after_typos, after_filter } =
+        search.candidates_statistics()?;
+
+    Ok(CandidatesStatistics {
+        number_of_documents: documents,
+        after_words,
+        after_typos,
+        after_filter,
+        processing_time_ms: before_search.elapsed().as_millis(),
+    })
+}
+
+pub fn perform_search_explanation(
+    index: &Index,
+    query: SearchQuery,
+    features: RoFeatures,
+    filter_limits: FilterLimits,
+) -> Result<SearchExplanation, MeilisearchHttpError> {
+    let before_search = Instant::now();
+    let rtxn = index.read_txn()?;
+
+    let (mut search, _, _, _) =
+        prepare_search(index, &rtxn, &query, features, filter_limits, None)?;
+    let milli::SearchExplanation { terms, filter, candidates } = search.explain()?;
+    let milli::CandidatesStatistics { documents, after_words, after_typos, after_filter } =
+        candidates;
+
+    // The ranking rules are explained by the buckets they sorted the candidates into while
+    // retrieving the requested page of hits.
+    let diagnostics = search.diagnostics(true).execute()?.diagnostics.unwrap_or_default();
+
+    let terms = terms
+        .into_iter()
+        .map(|term| {
+            let milli::ExplainedTerm {
+                original,
+                positions,
+                is_prefix,
+                is_phrase,
+                exact,
+                prefix_of,
+                one_typo,
+                two_typos,
+                split_words,
+                synonyms,
+            } = term;
+            ExplainedTerm {
+                original,
+                positions: [*positions.start(), *positions.end()],
+                is_prefix,
+                is_phrase,
+                exact,
+                prefix_of,
+                one_typo,
+                two_typos,
+                split_words,
+                synonyms,
+            }
+        })
+        .collect();
+    let ranking_rules = diagnostics
+        .ranking_rules
+        .into_iter()
+        .map(|rule| ExplainedRankingRule {
+            name: rule.name,
+            buckets: rule.buckets,
+            candidates: rule.bucket_documents,
+        })
+        .collect();
+
+    Ok(SearchExplanation {
+        terms,
+        filter,
+        ranking_rules,
+        candidates: CandidatesStatistics {
+            number_of_documents: documents,
+            after_words,
+            after_typos,
+            after_filter,
+            processing_time_ms: before_search.elapsed().as_millis(),
+        },
+    })
+}
+
 fn insert_geo_distance(sorts: &[String], document: &mut Document) {
     lazy_static::lazy_static! {
         static ref GEO_REGEX: Regex =
@@ -885,6 +1713,24 @@ fn make_document(
     Ok(document)
 }
 
+/// Returns the displayed fields of the document as they are stored in the index, without
+/// parsing them.
+fn make_raw_document(
+    displayed_attributes: &BTreeSet<FieldId>,
+    field_ids_map: &FieldsIdsMap,
+    obkv: obkv::KvReaderU16,
+) -> Result<Vec<(String, Box<RawValue>)>, MeilisearchHttpError> {
+    let mut document = Vec::new();
+    for (key, value) in obkv.iter() {
+        if displayed_attributes.contains(&key) {
+            let value = serde_json::from_slice(value)?;
+            let key = field_ids_map.name(key).expect("Missing field name").to_string();
+            document.push((key, value));
+        }
+    }
+    Ok(document)
+}
+
 fn format_fields<'a>(
     document: &Document,
     field_ids_map: &FieldsIdsMap,
@@ -1024,15 +1870,88 @@ fn format_value<'a>(
     }
 }
 
-pub(crate) fn parse_filter(facets: &Value) -> Result<Option<Filter>, MeilisearchHttpError> {
-    match facets {
-        Value::String(expr) => {
-            let condition = Filter::from_str(expr)?;
-            Ok(condition)
+/// The maximum size of the filters accepted by the instance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FilterLimits {
+    /// The maximum number of nested `OR` and `AND` levels.
+    pub max_depth: usize,
+    /// The maximum number of conditions a filter can be made of.
+    pub max_complexity: usize,
+}
+
+impl FilterLimits {
+    fn check(&self, filter: &Filter) -> Result<(), MeilisearchHttpError> {
+        let depth = filter.depth();
+        let complexity = filter.complexity();
+        if depth > self.max_depth || complexity > self.max_complexity {
+            return Err(MeilisearchHttpError::FilterTooComplex {
+                depth,
+                complexity,
+                max_depth: self.max_depth,
+                max_complexity: self.max_complexity,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl From<&Opt> for FilterLimits {
+    fn from(opt: &Opt) -> Self {
+        FilterLimits {
+            max_depth: opt.experimental_max_filter_depth,
+            max_complexity: opt.experimental_max_filter_complexity,
+        }
+    }
+}
+
+pub(crate) fn parse_filter(
+    facets: &Value,
+    limits: FilterLimits,
+) -> Result<Option<Filter>, MeilisearchHttpError> {
+    let filter = match facets {
+        Value::String(expr) => Filter::from_str(expr)?,
+        Value::Array(arr) => parse_filter_array(arr)?,
+        v => return Err(MeilisearchHttpError::InvalidExpression(&["String", "Array"], v.clone())),
+    };
+    if let Some(filter) = &filter {
+        limits.check(filter)?;
+    }
+    Ok(filter)
+}
+
+/// Combines the filters into a single one only matching the documents matching all of them.
+fn parse_nested<'a>(
+    nested: &'a SearchNested,
+    filter_limits: FilterLimits,
+) -> Result<Option<NestedFilter<'a>>, MeilisearchHttpError> {
+    let filter = parse_filter(&nested.filter, filter_limits)
+        .map_err(|e| MeilisearchHttpError::InvalidSearchNested(e.to_string()))?;
+    Ok(filter.map(|filter| NestedFilter { attribute: nested.attribute.clone(), filter }))
+}
+
+/// Returns the query computing the candidates of a facet distribution with the given filter.
+fn facet_query(query: &SearchQuery, filter: Option<Value>) -> SearchQuery {
+    SearchQuery {
+        filter,
+        sort: None,
+        sort_first: None,
+        facets: None,
+        aggregates: None,
+        group_by: None,
+        limit: 0,
+        ..query.clone()
+    }
+}
+
+fn combine_filters<'a>(filters: impl IntoIterator<Item = &'a Value>) -> Option<Value> {
+    let mut ands = Vec::new();
+    for filter in filters {
+        match filter {
+            Value::Array(filters) => ands.extend(filters.iter().cloned()),
+            filter => ands.push(filter.clone()),
         }
-        Value::Array(arr) => parse_filter_array(arr),
-        v => Err(MeilisearchHttpError::InvalidExpression(&["String", "Array"], v.clone())),
     }
+    (!ands.is_empty()).then_some(Value::Array(ands))
 }
 
 fn parse_filter_array(arr: &[Value]) -> Result<Option<Filter>, MeilisearchHttpError> {
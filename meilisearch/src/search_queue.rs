@@ -0,0 +1,81 @@
+/*!
+The queue limiting the number of searches executed at the same time.
+
+Every search must get a permit from the queue before being executed. When all the permits are
+taken, the search waits in the queue for one to be released, up to the timeout of the queue.
+Once the queue is full, the new searches are rejected right away with a
+`too_many_search_requests` error, so that a traffic spike is answered with errors the clients
+can retry rather than with a latency growing for everyone.
+*/
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::error::MeilisearchHttpError;
+use crate::option::Opt;
+
+pub struct SearchQueue {
+    permits: Arc<Semaphore>,
+    /// The number of searches waiting for a permit.
+    waiting: AtomicUsize,
+    capacity: usize,
+    timeout: Duration,
+}
+
+/// The right to execute a search, given back to the queue when dropped.
+#[derive(Debug)]
+pub struct SearchPermit {
+    _permit: OwnedSemaphorePermit,
+}
+
+impl SearchQueue {
+    /// Creates a queue executing up to `max_concurrent_searches` searches at the same time, with
+    /// up to `capacity` searches waiting at most `timeout` for their turn.
+    pub fn new(max_concurrent_searches: usize, capacity: usize, timeout: Duration) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(max_concurrent_searches.max(1))),
+            waiting: AtomicUsize::new(0),
+            capacity,
+            timeout,
+        }
+    }
+
+    pub fn from_opt(opt: &Opt) -> Self {
+        Self::new(
+            opt.max_concurrent_searches,
+            opt.search_queue_size,
+            Duration::from_millis(opt.search_queue_timeout_ms),
+        )
+    }
+
+    /// Waits for the turn of the search, fails if the queue is full or the search waited too long.
+    pub async fn try_get_search_permit(&self) -> Result<SearchPermit, MeilisearchHttpError> {
+        if let Ok(permit) = self.permits.clone().try_acquire_owned() {
+            return Ok(SearchPermit { _permit: permit });
+        }
+
+        let waiting = self.waiting.fetch_add(1, Ordering::Relaxed);
+        // the search leaves the queue whether it gets its permit, times out or is dropped.
+        let _waiting = WaitingGuard(&self.waiting);
+        if waiting >= self.capacity {
+            return Err(MeilisearchHttpError::TooManySearchRequests(self.capacity));
+        }
+
+        match tokio::time::timeout(self.timeout, self.permits.clone().acquire_owned()).await {
+            // the semaphore is never closed.
+            Ok(permit) => Ok(SearchPermit { _permit: permit.unwrap() }),
+            Err(_) => Err(MeilisearchHttpError::SearchQueueTimeout(self.timeout.as_millis())),
+        }
+    }
+}
+
+struct WaitingGuard<'a>(&'a AtomicUsize);
+
+impl Drop for WaitingGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,171 @@
+/*!
+The WASM modules rewriting the search requests and responses of the indexes.
+
+The module of an index is loaded from the `--experimental-wasm-middleware-dir` directory and named
+after the index uid, for instance `movies.wasm`, or `movies.wat` for the text format. It must export:
+- its `memory`;
+- an `alloc(len: i32) -> i32` function returning where to write an input of `len` bytes;
+- a `rewrite_request(ptr: i32, len: i32) -> i64` function, called once the request is authenticated, a `rewrite_response(ptr: i32, len: i32) -> i64`
+  function, or both.
+
+The rewrite functions receive a JSON object made of the `indexUid`, the `headers` of the request,
+except the `Authorization` one, and the JSON `body` of the request or the response. They return
+where the new body is in the memory of the module, its offset in the upper 32 bits and its length
+in the lower 32 bits.
+
+Every rewrite runs in a new instance of the module without any import, it can't consume more than
+the fuel and the memory given with `--experimental-wasm-middleware-fuel` and
+`--experimental-wasm-middleware-max-memory`.
+*/
+
+use std::collections::HashMap;
+
+use actix_web::http::header::{HeaderMap, AUTHORIZATION};
+use actix_web::web::Data;
+use anyhow::Context;
+use serde_json::Value;
+use wasmtime::{Config, Engine, Instance, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+use crate::error::MeilisearchHttpError;
+use crate::option::Opt;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Hook {
+    Request,
+    Response,
+}
+
+impl Hook {
+    fn export_name(self) -> &'static str {
+        match self {
+            Hook::Request => "rewrite_request",
+            Hook::Response => "rewrite_response",
+        }
+    }
+}
+
+pub struct WasmMiddlewares {
+    engine: Engine,
+    /// The modules by index uid.
+    modules: HashMap<String, Module>,
+    fuel: u64,
+    max_memory: usize,
+}
+
+impl WasmMiddlewares {
+    pub fn from_opt(opt: &Opt) -> anyhow::Result<Self> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config)?;
+
+        let mut modules = HashMap::new();
+        if let Some(dir) = &opt.experimental_wasm_middleware_dir {
+            let entries = std::fs::read_dir(dir).with_context(|| {
+                format!("Could not read the WASM middlewares directory `{}`", dir.display())
+            })?;
+            for entry in entries {
+                let path = entry?.path();
+                if !matches!(path.extension().and_then(|ext| ext.to_str()), Some("wasm" | "wat")) {
+                    continue;
+                }
+                let Some(index_uid) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                    continue;
+                };
+                let module = Module::from_file(&engine, &path).with_context(|| {
+                    format!("Could not load the WASM middleware `{}`", path.display())
+                })?;
+                modules.insert(index_uid.to_string(), module);
+            }
+        }
+
+        Ok(Self {
+            engine,
+            modules,
+            fuel: opt.experimental_wasm_middleware_fuel,
+            max_memory: opt.experimental_wasm_middleware_max_memory.get_bytes() as usize,
+        })
+    }
+
+    /// Whether the module of the index rewrites the requests or the responses.
+    pub fn has_hook(&self, index_uid: &str, hook: Hook) -> bool {
+        self.modules
+            .get(index_uid)
+            .map_or(false, |module| module.get_export(hook.export_name()).is_some())
+    }
+
+    /// Returns the body rewritten by the module of the index.
+    ///
+    /// The module must have the hook, see [`Self::has_hook`].
+    pub fn rewrite(
+        &self,
+        index_uid: &str,
+        hook: Hook,
+        headers: &HeaderMap,
+        body: Value,
+    ) -> Result<Vec<u8>, MeilisearchHttpError> {
+        let headers: serde_json::Map<String, Value> = headers
+            .iter()
+            .filter(|(name, _)| *name != AUTHORIZATION)
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.into())))
+            .collect();
+        let input = serde_json::json!({ "indexUid": index_uid, "headers": headers, "body": body });
+        let input = serde_json::to_vec(&input)?;
+
+        self.run(index_uid, hook, &input).map_err(|error| {
+            MeilisearchHttpError::WasmMiddleware(index_uid.to_string(), format!("{error:#}"))
+        })
+    }
+
+    fn run(&self, index_uid: &str, hook: Hook, input: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let module = self.modules.get(index_uid).context("missing module")?;
+
+        let limits = StoreLimitsBuilder::new().memory_size(self.max_memory).build();
+        let mut store = Store::new(&self.engine, limits);
+        store.limiter(|limits: &mut StoreLimits| limits);
+        store.set_fuel(self.fuel)?;
+
+        let instance = Instance::new(&mut store, module, &[])?;
+        let memory =
+            instance.get_memory(&mut store, "memory").context("missing `memory` export")?;
+        let alloc = instance.get_typed_func::<i32, i32>(&mut store, "alloc")?;
+        let rewrite = instance.get_typed_func::<(i32, i32), i64>(&mut store, hook.export_name())?;
+
+        let len = i32::try_from(input.len())?;
+        let ptr = alloc.call(&mut store, len)?;
+        memory.write(&mut store, ptr as u32 as usize, input)?;
+
+        let output = rewrite.call(&mut store, (ptr, len))? as u64;
+        let (output_ptr, output_len) = ((output >> 32) as usize, (output & 0xffff_ffff) as usize);
+        // the length is checked before allocating, the module could return any length
+        let end = output_ptr.checked_add(output_len);
+        anyhow::ensure!(
+            output_len <= self.max_memory && end.map_or(false, |end| end <= memory.data_size(&store)),
+            "the rewritten body of {output_len} bytes at {output_ptr} is out of the memory of the module"
+        );
+        let mut output = vec![0; output_len];
+        memory.read(&store, output_ptr, &mut output)?;
+        Ok(output)
+    }
+}
+
+/// Returns the search request rewritten by the module of the index, outside of the HTTP workers as
+/// the module can use a lot of CPU.
+///
+/// The module must have the request hook, see [`WasmMiddlewares::has_hook`].
+pub async fn rewrite_request(
+    wasm: &Data<WasmMiddlewares>,
+    index_uid: &str,
+    headers: &HeaderMap,
+    body: Value,
+) -> Result<Value, MeilisearchHttpError> {
+    let (wasm, uid, headers) = (wasm.clone(), index_uid.to_string(), headers.clone());
+    let output =
+        tokio::task::spawn_blocking(move || wasm.rewrite(&uid, Hook::Request, &headers, body))
+            .await??;
+    serde_json::from_slice(&output).map_err(|error| {
+        MeilisearchHttpError::WasmMiddleware(
+            index_uid.to_string(),
+            format!("the rewritten request is not valid JSON: {error}"),
+        )
+    })
+}
@@ -0,0 +1,123 @@
+use std::net::SocketAddr;
+
+use actix_web::test;
+use meilisearch::Opt;
+use tempfile::TempDir;
+
+use crate::common::{default_settings, Server};
+use crate::json;
+
+fn request_from(peer: &str) -> test::TestRequest {
+    test::TestRequest::get().uri("/indexes").peer_addr(SocketAddr::new(peer.parse().unwrap(), 4242))
+}
+
+#[actix_rt::test]
+async fn keys_are_refused_outside_of_their_allowed_ips() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let (response, code) = server
+        .add_api_key(json!({
+            "actions": ["indexes.get"],
+            "indexes": ["*"],
+            "expiresAt": null,
+            "allowedIps": ["10.0.0.0/8", "2001:db8::1"],
+        }))
+        .await;
+    assert_eq!(201, code, "{response}");
+    assert_eq!(response["allowedIps"], json!(["10.0.0.0/8", "2001:db8::1/128"]));
+    let key = response["key"].as_str().unwrap().to_string();
+    let uid = response["uid"].as_str().unwrap().to_string();
+
+    server.use_api_key(&key);
+    for ip in ["10.1.2.3", "2001:db8::1"] {
+        let (response, code) = server.service.request(request_from(ip)).await;
+        assert_eq!(200, code, "{response}");
+    }
+
+    let (response, code) = server.service.request(request_from("192.168.1.1")).await;
+    assert_eq!(403, code, "{response}");
+    assert_eq!(response["code"], "forbidden_ip_address");
+
+    // the forwarded address is ignored when the peer isn't a trusted proxy
+    let req = request_from("192.168.1.1").insert_header(("X-Forwarded-For", "10.1.2.3"));
+    let (response, code) = server.service.request(req).await;
+    assert_eq!(403, code, "{response}");
+    assert_eq!(response["code"], "forbidden_ip_address");
+
+    // the address of the client is unknown
+    let (response, code) = server.list_indexes(None, None).await;
+    assert_eq!(403, code, "{response}");
+    assert_eq!(response["code"], "forbidden_ip_address");
+
+    // removing the restriction makes the key usable from anywhere
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server.patch_api_key(&uid, json!({ "allowedIps": null })).await;
+    assert_eq!(200, code, "{response}");
+    assert!(response.get("allowedIps").is_none(), "{response}");
+
+    server.use_api_key(&key);
+    let (response, code) = server.list_indexes(None, None).await;
+    assert_eq!(200, code, "{response}");
+}
+
+#[actix_rt::test]
+async fn only_the_trusted_proxies_can_forward_the_client_address() {
+    let dir = TempDir::new().unwrap();
+    let options = Opt {
+        experimental_trusted_proxies: "172.16.0.0/12".parse().unwrap(),
+        ..default_settings(dir.path())
+    };
+    let mut server = Server::new_auth_with_options(options, dir).await;
+    server.use_api_key("MASTER_KEY");
+
+    let (response, code) = server
+        .add_api_key(json!({
+            "actions": ["indexes.get"],
+            "indexes": ["*"],
+            "expiresAt": null,
+            "allowedIps": ["10.0.0.0/8"],
+        }))
+        .await;
+    assert_eq!(201, code, "{response}");
+    let key = response["key"].as_str().unwrap().to_string();
+    server.use_api_key(&key);
+
+    let req = request_from("172.16.0.1").insert_header(("X-Forwarded-For", "10.1.2.3"));
+    let (response, code) = server.service.request(req).await;
+    assert_eq!(200, code, "{response}");
+
+    let req = request_from("172.16.0.1").insert_header(("Forwarded", "for=10.1.2.3;proto=https"));
+    let (response, code) = server.service.request(req).await;
+    assert_eq!(200, code, "{response}");
+
+    // the client prepended an allowed address to the one the proxy appended
+    let req =
+        request_from("172.16.0.1").insert_header(("X-Forwarded-For", "10.1.2.3, 192.168.1.1"));
+    let (response, code) = server.service.request(req).await;
+    assert_eq!(403, code, "{response}");
+    assert_eq!(response["code"], "forbidden_ip_address");
+
+    // a spoofed header sent by an untrusted peer
+    let req = request_from("192.168.1.1").insert_header(("X-Forwarded-For", "10.1.2.3"));
+    let (response, code) = server.service.request(req).await;
+    assert_eq!(403, code, "{response}");
+    assert_eq!(response["code"], "forbidden_ip_address");
+}
+
+#[actix_rt::test]
+async fn invalid_allowed_ips() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let (response, code) = server
+        .add_api_key(json!({
+            "actions": ["search"],
+            "indexes": ["*"],
+            "expiresAt": null,
+            "allowedIps": ["10.0.0.0/33"],
+        }))
+        .await;
+    assert_eq!(400, code, "{response}");
+    assert_eq!(response["code"], "invalid_api_key_allowed_ips");
+}
@@ -421,7 +421,7 @@ async fn error_add_api_key_invalid_parameters_actions() {
     meili_snap::snapshot!(code, @"400 Bad Request");
     meili_snap::snapshot!(meili_snap::json_string!(response, { ".createdAt" => "[ignored]", ".updatedAt" => "[ignored]" }), @r###"
     {
-      "message": "Unknown value `doc.add` at `.actions[0]`: expected one of `*`, `search`, `documents.*`, `documents.add`, `documents.get`, `documents.delete`, `indexes.*`, `indexes.create`, `indexes.get`, `indexes.update`, `indexes.delete`, `indexes.swap`, `tasks.*`, `tasks.cancel`, `tasks.delete`, `tasks.get`, `settings.*`, `settings.get`, `settings.update`, `stats.*`, `stats.get`, `metrics.*`, `metrics.get`, `dumps.*`, `dumps.create`, `snapshots.*`, `snapshots.create`, `version`, `keys.create`, `keys.get`, `keys.update`, `keys.delete`, `experimental.get`, `experimental.update`",
+      "message": "Unknown value `doc.add` at `.actions[0]`: expected one of `*`, `search`, `documents.*`, `documents.add`, `documents.get`, `documents.delete`, `indexes.*`, `indexes.create`, `indexes.get`, `indexes.update`, `indexes.delete`, `indexes.swap`, `tasks.*`, `tasks.cancel`, `tasks.delete`, `tasks.get`, `settings.*`, `settings.get`, `settings.update`, `stats.*`, `stats.get`, `metrics.*`, `metrics.get`, `dumps.*`, `dumps.create`, `snapshots.*`, `snapshots.create`, `version`, `keys.create`, `keys.get`, `keys.update`, `keys.delete`, `experimental.get`, `experimental.update`, `schedules.*`, `schedules.create`, `schedules.get`, `schedules.delete`, `settings.filterableAttributes.get`, `settings.filterableAttributes.update`, `settings.sortableAttributes.get`, `settings.sortableAttributes.update`, `settings.displayedAttributes.get`, `settings.displayedAttributes.update`, `settings.typoTolerance.get`, `settings.typoTolerance.update`, `settings.searchableAttributes.get`, `settings.searchableAttributes.update`, `settings.stopWords.get`, `settings.stopWords.update`, `settings.nonSeparatorTokens.get`, `settings.nonSeparatorTokens.update`, `settings.separatorTokens.get`, `settings.separatorTokens.update`, `settings.dictionary.get`, `settings.dictionary.update`, `settings.synonyms.get`, `settings.synonyms.update`, `settings.distinctAttribute.get`, `settings.distinctAttribute.update`, `settings.proximityPrecision.get`, `settings.proximityPrecision.update`, `settings.rankingRules.get`, `settings.rankingRules.update`, `settings.faceting.get`, `settings.faceting.update`, `settings.pagination.get`, `settings.pagination.update`, `settings.queryPlanHints.get`, `settings.queryPlanHints.update`, `settings.searchExclusions.get`, `settings.searchExclusions.update`, `settings.embedders.get`, `settings.embedders.update`, `settings.enrichmentWebhook.get`, `settings.enrichmentWebhook.update`, `settings.unitConversions.get`, `settings.unitConversions.update`, `settings.searchCutoffMs.get`, `settings.searchCutoffMs.update`, `tasks.retry`, `settings.localizedAttributes.get`, `settings.localizedAttributes.update`, `keys.manage`, `settings.stemming.get`, `settings.stemming.update`, `settings.decompounding.get`, `settings.decompounding.update`, `settings.prefixSearch.get`, `settings.prefixSearch.update`, `settings.searchTemplates.get`, `settings.searchTemplates.update`",
       "code": "invalid_api_key_actions",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_api_key_actions"
@@ -637,7 +637,13 @@ async fn get_api_key() {
       ],
       "expiresAt": "2050-11-13T00:00:00Z",
       "createdAt": "[ignored]",
-      "updatedAt": "[ignored]"
+      "updatedAt": "[ignored]",
+      "usage": {
+        "requests": 0,
+        "searches": 0,
+        "documentWrites": 0,
+        "lastUsedAt": null
+      }
     }
     "###);
     meili_snap::snapshot!(code, @"200 OK");
@@ -669,7 +675,13 @@ async fn get_api_key() {
       ],
       "expiresAt": "2050-11-13T00:00:00Z",
       "createdAt": "[ignored]",
-      "updatedAt": "[ignored]"
+      "updatedAt": "[ignored]",
+      "usage": {
+        "requests": 0,
+        "searches": 0,
+        "documentWrites": 0,
+        "lastUsedAt": null
+      }
     }
     "###);
     meili_snap::snapshot!(code, @"200 OK");
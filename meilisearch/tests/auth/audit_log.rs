@@ -0,0 +1,66 @@
+use meilisearch::Opt;
+use tempfile::TempDir;
+
+use crate::common::{default_settings, Server};
+use crate::json;
+
+#[actix_rt::test]
+async fn write_requests_are_recorded_in_the_audit_log() {
+    let dir = TempDir::new().unwrap();
+    let options = Opt { audit_log: true, ..default_settings(dir.path()) };
+    let mut server = Server::new_auth_with_options(options, dir).await;
+    server.use_api_key("MASTER_KEY");
+
+    let (response, code) =
+        server.add_api_key(json!({ "actions": ["*"], "indexes": ["*"], "expiresAt": null })).await;
+    assert_eq!(201, code, "{response}");
+    let key = response["key"].as_str().unwrap().to_string();
+    let uid = response["uid"].as_str().unwrap().to_string();
+
+    server.use_api_key(&key);
+    let (response, code) = server.index("products").add_documents(json!([{ "id": 1 }]), None).await;
+    assert_eq!(202, code, "{response}");
+    // the read requests are not recorded
+    let (response, code) = server.list_indexes(None, None).await;
+    assert_eq!(200, code, "{response}");
+
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server.service.get(format!("/audit-log?keyUid={uid}")).await;
+    assert_eq!(200, code, "{response}");
+    assert_eq!(response["total"], 1, "{response}");
+    let entry = &response["results"][0];
+    assert_eq!(entry["keyUid"], json!(uid), "{response}");
+    assert_eq!(entry["method"], "POST", "{response}");
+    assert_eq!(entry["path"], "/indexes/products/documents", "{response}");
+    assert_eq!(entry["indexUid"], "products", "{response}");
+    assert_eq!(entry["status"], 202, "{response}");
+    assert!(entry["at"].is_string(), "{response}");
+
+    // the key creation was made with the master key
+    let (response, code) = server.service.get("/audit-log").await;
+    assert_eq!(200, code, "{response}");
+    assert_eq!(response["total"], 2, "{response}");
+    assert_eq!(response["results"][1]["keyUid"], json!(null), "{response}");
+    assert_eq!(response["results"][1]["path"], "/keys", "{response}");
+
+    let (response, code) = server.service.get("/audit-log?offset=1&limit=1").await;
+    assert_eq!(200, code, "{response}");
+    assert_eq!(response["total"], 2, "{response}");
+    assert_eq!(response["results"].as_array().unwrap().len(), 1, "{response}");
+    assert_eq!(response["results"][0]["path"], "/keys", "{response}");
+
+    let (response, code) = server.service.get("/audit-log?offset=2").await;
+    assert_eq!(200, code, "{response}");
+    assert_eq!(response["total"], 2, "{response}");
+    assert_eq!(response["results"], json!([]), "{response}");
+}
+
+#[actix_rt::test]
+async fn audit_log_is_disabled_by_default() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let (response, code) = server.service.get("/audit-log").await;
+    assert_eq!(400, code, "{response}");
+    assert_eq!(response["code"], "audit_log_disabled", "{response}");
+}
@@ -16,6 +16,8 @@ pub static AUTHORIZATIONS: Lazy<HashMap<(&'static str, &'static str), HashSet<&'
             ("POST",    "/multi-search") =>                                    hashset!{"search", "*"},
             ("POST",    "/indexes/products/search") =>                         hashset!{"search", "*"},
             ("GET",     "/indexes/products/search") =>                         hashset!{"search", "*"},
+            ("POST",    "/indexes/products/search/candidates") =>              hashset!{"*"},
+            ("POST",    "/indexes/products/search/explain") =>                 hashset!{"*"},
             ("POST",    "/indexes/products/documents") =>                      hashset!{"documents.add", "documents.*", "*"},
             ("GET",     "/indexes/products/documents") =>                      hashset!{"documents.get", "documents.*", "*"},
             ("POST",    "/indexes/products/documents/fetch") =>                hashset!{"documents.get", "documents.*", "*"},
@@ -27,6 +29,7 @@ pub static AUTHORIZATIONS: Lazy<HashMap<(&'static str, &'static str), HashSet<&'
             ("DELETE",  "/tasks") =>                                           hashset!{"tasks.delete", "tasks.*", "*"},
             ("GET",     "/tasks?indexUid=products") =>                         hashset!{"tasks.get", "tasks.*", "*"},
             ("GET",     "/tasks/0") =>                                         hashset!{"tasks.get", "tasks.*", "*"},
+            ("POST",    "/tasks/0/retry") =>                                   hashset!{"tasks.retry", "tasks.*", "*"},
             ("PATCH",   "/indexes/products/") =>                               hashset!{"indexes.update", "indexes.*", "*"},
             ("GET",     "/indexes/products/") =>                               hashset!{"indexes.get", "indexes.*", "*"},
             ("DELETE",  "/indexes/products/") =>                               hashset!{"indexes.delete", "indexes.*", "*"},
@@ -34,40 +37,62 @@ pub static AUTHORIZATIONS: Lazy<HashMap<(&'static str, &'static str), HashSet<&'
             ("GET",     "/indexes") =>                                         hashset!{"indexes.get", "indexes.*", "*"},
             ("POST",    "/swap-indexes") =>                                    hashset!{"indexes.swap", "indexes.*", "*"},
             ("GET",     "/indexes/products/settings") =>                       hashset!{"settings.get", "settings.*", "*"},
-            ("GET",     "/indexes/products/settings/displayed-attributes") =>  hashset!{"settings.get", "settings.*", "*"},
-            ("GET",     "/indexes/products/settings/distinct-attribute") =>    hashset!{"settings.get", "settings.*", "*"},
-            ("GET",     "/indexes/products/settings/filterable-attributes") => hashset!{"settings.get", "settings.*", "*"},
-            ("GET",     "/indexes/products/settings/ranking-rules") =>         hashset!{"settings.get", "settings.*", "*"},
-            ("GET",     "/indexes/products/settings/searchable-attributes") => hashset!{"settings.get", "settings.*", "*"},
-            ("GET",     "/indexes/products/settings/sortable-attributes") =>   hashset!{"settings.get", "settings.*", "*"},
-            ("GET",     "/indexes/products/settings/stop-words") =>            hashset!{"settings.get", "settings.*", "*"},
-            ("GET",     "/indexes/products/settings/synonyms") =>              hashset!{"settings.get", "settings.*", "*"},
+            ("GET",     "/indexes/products/field-suggestions") =>              hashset!{"settings.get", "settings.*", "*"},
+            ("GET",     "/indexes/products/settings/displayed-attributes") =>  hashset!{"settings.displayedAttributes.get", "settings.get", "settings.*", "*"},
+            ("GET",     "/indexes/products/settings/distinct-attribute") =>    hashset!{"settings.distinctAttribute.get", "settings.get", "settings.*", "*"},
+            ("GET",     "/indexes/products/settings/filterable-attributes") => hashset!{"settings.filterableAttributes.get", "settings.get", "settings.*", "*"},
+            ("GET",     "/indexes/products/settings/ranking-rules") =>         hashset!{"settings.rankingRules.get", "settings.get", "settings.*", "*"},
+            ("GET",     "/indexes/products/settings/searchable-attributes") => hashset!{"settings.searchableAttributes.get", "settings.get", "settings.*", "*"},
+            ("GET",     "/indexes/products/settings/sortable-attributes") =>   hashset!{"settings.sortableAttributes.get", "settings.get", "settings.*", "*"},
+            ("GET",     "/indexes/products/settings/stop-words") =>            hashset!{"settings.stopWords.get", "settings.get", "settings.*", "*"},
+            ("GET",     "/indexes/products/settings/synonyms") =>              hashset!{"settings.synonyms.get", "settings.get", "settings.*", "*"},
             ("DELETE",  "/indexes/products/settings") =>                       hashset!{"settings.update", "settings.*", "*"},
             ("PATCH",   "/indexes/products/settings") =>                       hashset!{"settings.update", "settings.*", "*"},
-            ("PATCH",   "/indexes/products/settings/typo-tolerance") =>        hashset!{"settings.update", "settings.*", "*"},
-            ("PUT",     "/indexes/products/settings/displayed-attributes") =>  hashset!{"settings.update", "settings.*", "*"},
-            ("PUT",     "/indexes/products/settings/distinct-attribute") =>    hashset!{"settings.update", "settings.*", "*"},
-            ("PUT",     "/indexes/products/settings/filterable-attributes") => hashset!{"settings.update", "settings.*", "*"},
-            ("PUT",     "/indexes/products/settings/ranking-rules") =>         hashset!{"settings.update", "settings.*", "*"},
-            ("PUT",     "/indexes/products/settings/searchable-attributes") => hashset!{"settings.update", "settings.*", "*"},
-            ("PUT",     "/indexes/products/settings/sortable-attributes") =>   hashset!{"settings.update", "settings.*", "*"},
-            ("PUT",     "/indexes/products/settings/stop-words") =>            hashset!{"settings.update", "settings.*", "*"},
-            ("PUT",     "/indexes/products/settings/synonyms") =>              hashset!{"settings.update", "settings.*", "*"},
+            ("PATCH",   "/indexes/products/settings/typo-tolerance") =>        hashset!{"settings.typoTolerance.update", "settings.update", "settings.*", "*"},
+            ("PUT",     "/indexes/products/settings/displayed-attributes") =>  hashset!{"settings.displayedAttributes.update", "settings.update", "settings.*", "*"},
+            ("PUT",     "/indexes/products/settings/distinct-attribute") =>    hashset!{"settings.distinctAttribute.update", "settings.update", "settings.*", "*"},
+            ("PUT",     "/indexes/products/settings/filterable-attributes") => hashset!{"settings.filterableAttributes.update", "settings.update", "settings.*", "*"},
+            ("PUT",     "/indexes/products/settings/ranking-rules") =>         hashset!{"settings.rankingRules.update", "settings.update", "settings.*", "*"},
+            ("PUT",     "/indexes/products/settings/searchable-attributes") => hashset!{"settings.searchableAttributes.update", "settings.update", "settings.*", "*"},
+            ("PUT",     "/indexes/products/settings/sortable-attributes") =>   hashset!{"settings.sortableAttributes.update", "settings.update", "settings.*", "*"},
+            ("PUT",     "/indexes/products/settings/stop-words") =>            hashset!{"settings.stopWords.update", "settings.update", "settings.*", "*"},
+            ("PUT",     "/indexes/products/settings/synonyms") =>              hashset!{"settings.synonyms.update", "settings.update", "settings.*", "*"},
             ("GET",     "/indexes/products/stats") =>                          hashset!{"stats.get", "stats.*", "*"},
+            ("GET",     "/indexes/products/analytics") =>                      hashset!{"stats.get", "stats.*", "*"},
+            ("POST",    "/indexes/products/analytics/clicks") =>               hashset!{"search", "*"},
+            ("POST",    "/indexes/products/export") =>                         hashset!{"dumps.create", "dumps.*", "*"},
+            ("POST",    "/indexes/products/import") =>                         hashset!{"*"},
+            ("POST",    "/indexes/products/merge") =>                          hashset!{"documents.add", "documents.*", "*"},
+            ("POST",    "/indexes/products/previews") =>                       hashset!{"indexes.create", "indexes.*", "*"},
+            ("GET",     "/indexes/products/previews") =>                       hashset!{"indexes.get", "indexes.*", "*"},
+            ("POST",    "/indexes/products/split") =>                          hashset!{"indexes.create", "indexes.*", "*"},
             ("GET",     "/stats") =>                                           hashset!{"stats.get", "stats.*", "*"},
             ("POST",    "/dumps") =>                                           hashset!{"dumps.create", "dumps.*", "*"},
+            ("POST",    "/dumps/import") =>                                    hashset!{"*"},
             ("POST",    "/snapshots") =>                                       hashset!{"snapshots.create", "snapshots.*", "*"},
+            ("GET",     "/dead-letters") =>                                    hashset!{"tasks.get", "tasks.*", "*"},
+            ("GET",     "/dead-letters/0") =>                                  hashset!{"tasks.get", "tasks.*", "*"},
+            ("DELETE",  "/dead-letters/0") =>                                  hashset!{"tasks.delete", "tasks.*", "*"},
+            ("POST",    "/dead-letters/0/import") =>                           hashset!{"tasks.retry", "tasks.*", "*"},
+            ("GET",     "/schedules") =>                                       hashset!{"schedules.get", "schedules.*", "*"},
+            ("POST",    "/schedules") =>                                       hashset!{"schedules.create", "schedules.*", "*"},
+            ("GET",     "/schedules/0") =>                                     hashset!{"schedules.get", "schedules.*", "*"},
+            ("DELETE",  "/schedules/0") =>                                     hashset!{"schedules.delete", "schedules.*", "*"},
             ("GET",     "/version") =>                                         hashset!{"version", "*"},
+            ("GET",     "/recovery-report") =>                                 hashset!{"tasks.get", "tasks.*", "*"},
             ("GET",     "/metrics") =>                                         hashset!{"metrics.get", "metrics.*", "*"},
             ("POST",    "/logs/stream") =>                                     hashset!{"metrics.get", "metrics.*", "*"},
             ("DELETE",  "/logs/stream") =>                                     hashset!{"metrics.get", "metrics.*", "*"},
-            ("PATCH",   "/keys/mykey/") =>                                     hashset!{"keys.update", "*"},
-            ("GET",     "/keys/mykey/") =>                                     hashset!{"keys.get", "*"},
-            ("DELETE",  "/keys/mykey/") =>                                     hashset!{"keys.delete", "*"},
-            ("POST",    "/keys") =>                                            hashset!{"keys.create", "*"},
-            ("GET",     "/keys") =>                                            hashset!{"keys.get", "*"},
+            ("PATCH",   "/keys/mykey/") =>                                     hashset!{"keys.update", "keys.manage", "*"},
+            ("GET",     "/keys/mykey/") =>                                     hashset!{"keys.get", "keys.manage", "*"},
+            ("GET",     "/keys/mykey/stats") =>                                hashset!{"keys.get", "keys.manage", "*"},
+            ("DELETE",  "/keys/mykey/") =>                                     hashset!{"keys.delete", "keys.manage", "*"},
+            ("POST",    "/keys") =>                                            hashset!{"keys.create", "keys.manage", "*"},
+            ("GET",     "/keys") =>                                            hashset!{"keys.get", "keys.manage", "*"},
             ("GET",     "/experimental-features") =>                           hashset!{"experimental.get", "*"},
             ("PATCH",   "/experimental-features") =>                           hashset!{"experimental.update", "*"},
+            ("GET",     "/experimental-features/autobatching") =>              hashset!{"experimental.get", "*"},
+            ("PATCH",   "/experimental-features/autobatching") =>              hashset!{"experimental.update", "*"},
         };
 
         authorizations
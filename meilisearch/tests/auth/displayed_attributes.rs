@@ -0,0 +1,257 @@
+use actix_web::http::header::ACCEPT;
+use actix_web::test;
+use maplit::hashmap;
+use time::{Duration, OffsetDateTime};
+
+use super::tenant_token::generate_tenant_token;
+use crate::common::{GetAllDocumentsOptions, Server};
+use crate::json;
+
+fn field_names(document: &serde_json::Value) -> Vec<&str> {
+    let mut names: Vec<_> = document.as_object().unwrap().keys().map(String::as_str).collect();
+    names.sort_unstable();
+    names
+}
+
+async fn server_with_products() -> Server {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+    let index = server.index("products");
+    index
+        .add_documents(
+            json!([{ "id": 1, "name": "shirt", "price": 20, "cost": 8, "email": "shirt@supplier.com" }]),
+            None,
+        )
+        .await;
+    index.wait_task(0).await;
+    server
+}
+
+#[actix_rt::test]
+async fn keys_only_retrieve_their_displayed_attributes() {
+    let mut server = server_with_products().await;
+
+    let (response, code) = server
+        .add_api_key(json!({
+            "actions": ["search", "documents.get"],
+            "indexes": ["products"],
+            "expiresAt": null,
+            "displayedAttributes": ["id", "name", "price"],
+        }))
+        .await;
+    assert_eq!(201, code, "{response}");
+    assert_eq!(response["displayedAttributes"], json!(["id", "name", "price"]));
+    let key = response["key"].as_str().unwrap().to_string();
+    let uid = response["uid"].as_str().unwrap().to_string();
+
+    server.use_api_key(&key);
+    let index = server.index("products");
+    let (response, code) = index
+        .search_post(json!({
+            "q": "shirt",
+            "attributesToRetrieve": ["*"],
+            "attributesToHighlight": ["*"],
+            "showMatchesPosition": true,
+        }))
+        .await;
+    assert_eq!(200, code, "{response}");
+    let hit = &response["hits"][0];
+    assert_eq!(field_names(hit), ["_formatted", "_matchesPosition", "id", "name", "price"]);
+    assert_eq!(field_names(&hit["_formatted"]), ["id", "name", "price"]);
+
+    let (response, code) = index.get_document(1, None).await;
+    assert_eq!(200, code, "{response}");
+    assert_eq!(field_names(&response), ["id", "name", "price"]);
+
+    let (response, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(200, code, "{response}");
+    assert_eq!(field_names(&response["results"][0]), ["id", "name", "price"]);
+    drop(index);
+
+    // removing the restriction gives access to all the displayed attributes again
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server.patch_api_key(&uid, json!({ "displayedAttributes": null })).await;
+    assert_eq!(200, code, "{response}");
+    assert!(response.get("displayedAttributes").is_none(), "{response}");
+
+    server.use_api_key(&key);
+    let (response, code) = server.index("products").get_document(1, None).await;
+    assert_eq!(200, code, "{response}");
+    assert_eq!(field_names(&response), ["cost", "email", "id", "name", "price"]);
+}
+
+#[actix_rt::test]
+async fn tenant_tokens_restrict_the_displayed_attributes_of_their_key() {
+    let mut server = server_with_products().await;
+
+    let (response, code) = server
+        .add_api_key(json!({
+            "actions": ["search"],
+            "indexes": ["products"],
+            "expiresAt": null,
+            "displayedAttributes": ["id", "name", "price"],
+        }))
+        .await;
+    assert_eq!(201, code, "{response}");
+    let key = response["key"].as_str().unwrap().to_string();
+    let uid = response["uid"].as_str().unwrap().to_string();
+
+    // the token can't give access to the attributes hidden by its key
+    let tenant_token = hashmap! {
+        "searchRules" => json!({ "products": { "displayedAttributes": ["name", "cost"] } }),
+        "exp" => json!((OffsetDateTime::now_utc() + Duration::hours(1)).unix_timestamp()),
+    };
+    server.use_api_key(generate_tenant_token(&uid, &key, tenant_token));
+    let (response, code) = server
+        .index("products")
+        .search_post(json!({ "attributesToRetrieve": ["*"], "attributesToCrop": ["*"] }))
+        .await;
+    assert_eq!(200, code, "{response}");
+    let hit = &response["hits"][0];
+    assert_eq!(field_names(hit), ["_formatted", "name"]);
+    assert_eq!(field_names(&hit["_formatted"]), ["name"]);
+}
+
+/// Creates a key restricted to the `id`, `name` and `price` attributes of the products, which can
+/// be filtered and sorted on, like the hidden `cost` attribute.
+async fn restricted_key(server: &mut Server, actions: serde_json::Value) -> String {
+    let index = server.index("products");
+    let (response, _) = index
+        .update_settings(json!({
+            "filterableAttributes": ["name", "price", "cost"],
+            "sortableAttributes": ["price", "cost"],
+        }))
+        .await;
+    index.wait_task(response.uid()).await;
+
+    let (response, code) = server
+        .add_api_key(json!({
+            "actions": actions,
+            "indexes": ["products"],
+            "expiresAt": null,
+            "displayedAttributes": ["id", "name", "price"],
+        }))
+        .await;
+    assert_eq!(201, code, "{response}");
+    response["key"].as_str().unwrap().to_string()
+}
+
+#[actix_rt::test]
+async fn keys_cannot_use_their_hidden_attributes_to_search() {
+    let mut server = server_with_products().await;
+    let key = restricted_key(&mut server, json!(["search", "documents.get"])).await;
+    server.use_api_key(&key);
+    let index = server.index("products");
+
+    for query in [
+        json!({ "filter": "cost = 8" }),
+        json!({ "facetFilters": { "price": "cost = 8" } }),
+        json!({ "sort": ["cost:asc"] }),
+        json!({ "facets": ["cost"] }),
+        json!({ "facets": { "cost": { "ranges": [[0, 10]] } } }),
+        json!({ "aggregates": ["cost"] }),
+        json!({ "attributesToSearchOn": ["email"] }),
+    ] {
+        let (response, code) = index.search_post(query.clone()).await;
+        assert_eq!(403, code, "{query}: {response}");
+        assert_eq!(response["code"], "forbidden_attribute", "{query}: {response}");
+    }
+
+    // all the facets can be requested, the hidden ones are left out
+    let (response, code) = index.search_post(json!({ "facets": ["*"] })).await;
+    assert_eq!(200, code, "{response}");
+    assert_eq!(field_names(&response["facetDistribution"]), ["name", "price"]);
+    assert_eq!(field_names(&response["facetStats"]), ["price"]);
+
+    let (response, code) = index.facet_search(json!({ "facetName": "cost" })).await;
+    assert_eq!(403, code, "{response}");
+    assert_eq!(response["code"], "forbidden_attribute");
+
+    let (response, code) = index.get_document_by_filter(json!({ "filter": "cost = 8" })).await;
+    assert_eq!(403, code, "{response}");
+    assert_eq!(response["code"], "forbidden_attribute");
+    let (response, code) = index.get_document_by_filter(json!({ "sort": ["cost:desc"] })).await;
+    assert_eq!(403, code, "{response}");
+    assert_eq!(response["code"], "forbidden_attribute");
+}
+
+#[actix_rt::test]
+async fn facet_distribution_leaves_out_the_hidden_attributes() {
+    let mut server = server_with_products().await;
+    let key = restricted_key(&mut server, json!(["search"])).await;
+    server.use_api_key(&key);
+
+    let (response, code) =
+        server.service.get("/indexes/products/facet-distribution?facets=cost").await;
+    assert_eq!(403, code, "{response}");
+    assert_eq!(response["code"], "forbidden_attribute");
+
+    let (response, code) = server.service.get("/indexes/products/facet-distribution").await;
+    assert_eq!(200, code, "{response}");
+    assert_eq!(field_names(&response["facetDistribution"]), ["name", "price"]);
+    assert_eq!(field_names(&response["facetStats"]), ["price"]);
+
+    let app = server.init_web_app().await;
+    let req = test::TestRequest::get()
+        .uri("/indexes/products/facet-distribution")
+        .insert_header((ACCEPT, "text/csv"))
+        .insert_header(("Authorization", format!("Bearer {key}")))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(200, res.status().as_u16());
+    let body = String::from_utf8(test::read_body(res).await.to_vec()).unwrap();
+    assert!(!body.contains("cost"), "{body}");
+    assert!(body.contains("price"), "{body}");
+}
+
+#[actix_rt::test]
+async fn keys_cannot_explain_or_export_their_hidden_attributes() {
+    let mut server = server_with_products().await;
+    let key = restricted_key(&mut server, json!(["*"])).await;
+    server.use_api_key(&key);
+    let index = server.index("products");
+
+    let (response, code) = index.search_explain(json!({ "filter": "cost = 8" })).await;
+    assert_eq!(403, code, "{response}");
+    assert_eq!(response["code"], "forbidden_attribute");
+
+    let (response, code) = index.export().await;
+    assert_eq!(403, code, "{response}");
+    assert_eq!(response["code"], "forbidden_attribute");
+}
+
+#[actix_rt::test]
+async fn tenant_tokens_can_filter_on_hidden_attributes() {
+    let mut server = server_with_products().await;
+    let key = restricted_key(&mut server, json!(["search"])).await;
+    server.use_api_key("MASTER_KEY");
+    let (response, _) = server.get_api_key(&key).await;
+    let uid = response["uid"].as_str().unwrap().to_string();
+
+    // the filter of the search rules is set by the application, not by the user
+    let tenant_token = hashmap! {
+        "searchRules" => json!({ "products": { "filter": "cost = 8" } }),
+        "exp" => json!((OffsetDateTime::now_utc() + Duration::hours(1)).unix_timestamp()),
+    };
+    server.use_api_key(generate_tenant_token(&uid, &key, tenant_token));
+    let (response, code) = server.index("products").search_post(json!({})).await;
+    assert_eq!(200, code, "{response}");
+    assert_eq!(response["hits"].as_array().unwrap().len(), 1, "{response}");
+    assert_eq!(field_names(&response["hits"][0]), ["id", "name", "price"]);
+}
+
+#[actix_rt::test]
+async fn invalid_displayed_attributes() {
+    let server = server_with_products().await;
+
+    let (response, code) = server
+        .add_api_key(json!({
+            "actions": ["search"],
+            "indexes": ["*"],
+            "expiresAt": null,
+            "displayedAttributes": "name",
+        }))
+        .await;
+    assert_eq!(400, code, "{response}");
+    assert_eq!(response["code"], "invalid_api_key_displayed_attributes");
+}
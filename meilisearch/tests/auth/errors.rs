@@ -90,7 +90,7 @@ async fn create_api_key_bad_actions() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Unknown value `doggo` at `.actions[0]`: expected one of `*`, `search`, `documents.*`, `documents.add`, `documents.get`, `documents.delete`, `indexes.*`, `indexes.create`, `indexes.get`, `indexes.update`, `indexes.delete`, `indexes.swap`, `tasks.*`, `tasks.cancel`, `tasks.delete`, `tasks.get`, `settings.*`, `settings.get`, `settings.update`, `stats.*`, `stats.get`, `metrics.*`, `metrics.get`, `dumps.*`, `dumps.create`, `snapshots.*`, `snapshots.create`, `version`, `keys.create`, `keys.get`, `keys.update`, `keys.delete`, `experimental.get`, `experimental.update`",
+      "message": "Unknown value `doggo` at `.actions[0]`: expected one of `*`, `search`, `documents.*`, `documents.add`, `documents.get`, `documents.delete`, `indexes.*`, `indexes.create`, `indexes.get`, `indexes.update`, `indexes.delete`, `indexes.swap`, `tasks.*`, `tasks.cancel`, `tasks.delete`, `tasks.get`, `settings.*`, `settings.get`, `settings.update`, `stats.*`, `stats.get`, `metrics.*`, `metrics.get`, `dumps.*`, `dumps.create`, `snapshots.*`, `snapshots.create`, `version`, `keys.create`, `keys.get`, `keys.update`, `keys.delete`, `experimental.get`, `experimental.update`, `schedules.*`, `schedules.create`, `schedules.get`, `schedules.delete`, `settings.filterableAttributes.get`, `settings.filterableAttributes.update`, `settings.sortableAttributes.get`, `settings.sortableAttributes.update`, `settings.displayedAttributes.get`, `settings.displayedAttributes.update`, `settings.typoTolerance.get`, `settings.typoTolerance.update`, `settings.searchableAttributes.get`, `settings.searchableAttributes.update`, `settings.stopWords.get`, `settings.stopWords.update`, `settings.nonSeparatorTokens.get`, `settings.nonSeparatorTokens.update`, `settings.separatorTokens.get`, `settings.separatorTokens.update`, `settings.dictionary.get`, `settings.dictionary.update`, `settings.synonyms.get`, `settings.synonyms.update`, `settings.distinctAttribute.get`, `settings.distinctAttribute.update`, `settings.proximityPrecision.get`, `settings.proximityPrecision.update`, `settings.rankingRules.get`, `settings.rankingRules.update`, `settings.faceting.get`, `settings.faceting.update`, `settings.pagination.get`, `settings.pagination.update`, `settings.queryPlanHints.get`, `settings.queryPlanHints.update`, `settings.searchExclusions.get`, `settings.searchExclusions.update`, `settings.embedders.get`, `settings.embedders.update`, `settings.enrichmentWebhook.get`, `settings.enrichmentWebhook.update`, `settings.unitConversions.get`, `settings.unitConversions.update`, `settings.searchCutoffMs.get`, `settings.searchCutoffMs.update`, `tasks.retry`, `settings.localizedAttributes.get`, `settings.localizedAttributes.update`, `keys.manage`, `settings.stemming.get`, `settings.stemming.update`, `settings.decompounding.get`, `settings.decompounding.update`, `settings.prefixSearch.get`, `settings.prefixSearch.update`, `settings.searchTemplates.get`, `settings.searchTemplates.update`",
       "code": "invalid_api_key_actions",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_api_key_actions"
@@ -138,7 +138,7 @@ async fn create_api_key_bad_expires_at() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Unknown field `expires_at`: did you mean `expiresAt`? expected one of `description`, `name`, `uid`, `actions`, `indexes`, `expiresAt`",
+      "message": "Unknown field `expires_at`: did you mean `expiresAt`? expected one of `description`, `name`, `uid`, `actions`, `indexes`, `expiresAt`, `rateLimits`, `allowedIps`, `displayedAttributes`",
       "code": "bad_request",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#bad_request"
@@ -150,7 +150,7 @@ async fn create_api_key_bad_expires_at() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Unknown field `expires_at`: did you mean `expiresAt`? expected one of `description`, `name`, `uid`, `actions`, `indexes`, `expiresAt`",
+      "message": "Unknown field `expires_at`: did you mean `expiresAt`? expected one of `description`, `name`, `uid`, `actions`, `indexes`, `expiresAt`, `rateLimits`, `allowedIps`, `displayedAttributes`",
       "code": "bad_request",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#bad_request"
@@ -225,7 +225,7 @@ async fn create_api_key_unexpected_field() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Unknown field `doggo`: expected one of `description`, `name`, `uid`, `actions`, `indexes`, `expiresAt`",
+      "message": "Unknown field `doggo`: expected one of `description`, `name`, `uid`, `actions`, `indexes`, `expiresAt`, `rateLimits`, `allowedIps`, `displayedAttributes`",
       "code": "bad_request",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#bad_request"
@@ -327,7 +327,7 @@ async fn patch_api_keys_immutable_uid() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Immutable field `uid`: expected one of `description`, `name`",
+      "message": "Immutable field `uid`: expected one of `description`, `name`, `rateLimits`, `allowedIps`, `displayedAttributes`",
       "code": "immutable_api_key_uid",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#immutable_api_key_uid"
@@ -344,7 +344,7 @@ async fn patch_api_keys_immutable_actions() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Immutable field `actions`: expected one of `description`, `name`",
+      "message": "Immutable field `actions`: expected one of `description`, `name`, `rateLimits`, `allowedIps`, `displayedAttributes`",
       "code": "immutable_api_key_actions",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#immutable_api_key_actions"
@@ -361,7 +361,7 @@ async fn patch_api_keys_immutable_indexes() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Immutable field `indexes`: expected one of `description`, `name`",
+      "message": "Immutable field `indexes`: expected one of `description`, `name`, `rateLimits`, `allowedIps`, `displayedAttributes`",
       "code": "immutable_api_key_indexes",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#immutable_api_key_indexes"
@@ -378,7 +378,7 @@ async fn patch_api_keys_immutable_expires_at() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Immutable field `expiresAt`: expected one of `description`, `name`",
+      "message": "Immutable field `expiresAt`: expected one of `description`, `name`, `rateLimits`, `allowedIps`, `displayedAttributes`",
       "code": "immutable_api_key_expires_at",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#immutable_api_key_expires_at"
@@ -395,7 +395,7 @@ async fn patch_api_keys_immutable_created_at() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Immutable field `createdAt`: expected one of `description`, `name`",
+      "message": "Immutable field `createdAt`: expected one of `description`, `name`, `rateLimits`, `allowedIps`, `displayedAttributes`",
       "code": "immutable_api_key_created_at",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#immutable_api_key_created_at"
@@ -412,7 +412,7 @@ async fn patch_api_keys_immutable_updated_at() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Immutable field `updatedAt`: expected one of `description`, `name`",
+      "message": "Immutable field `updatedAt`: expected one of `description`, `name`, `rateLimits`, `allowedIps`, `displayedAttributes`",
       "code": "immutable_api_key_updated_at",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#immutable_api_key_updated_at"
@@ -429,7 +429,7 @@ async fn patch_api_keys_unknown_field() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Unknown field `doggo`: expected one of `description`, `name`",
+      "message": "Unknown field `doggo`: expected one of `description`, `name`, `rateLimits`, `allowedIps`, `displayedAttributes`",
       "code": "bad_request",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#bad_request"
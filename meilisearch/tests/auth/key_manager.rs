@@ -0,0 +1,196 @@
+use crate::common::Server;
+use crate::json;
+
+async fn add_key_manager(server: &mut Server) -> String {
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server
+        .add_api_key(json!({
+            "actions": ["keys.manage", "search", "documents.get"],
+            "indexes": ["products_*"],
+            "expiresAt": "2050-11-13T00:00:00Z",
+        }))
+        .await;
+    assert_eq!(201, code, "{response}");
+    response["key"].as_str().unwrap().to_string()
+}
+
+#[actix_rt::test]
+async fn key_manager_creates_and_deletes_narrower_keys() {
+    let mut server = Server::new_auth().await;
+    let manager = add_key_manager(&mut server).await;
+    server.use_api_key(&manager);
+
+    let (response, code) = server
+        .add_api_key(json!({
+            "actions": ["search"],
+            "indexes": ["products_fr"],
+            "expiresAt": "2049-01-01T00:00:00Z",
+        }))
+        .await;
+    assert_eq!(201, code, "{response}");
+    let uid = response["uid"].as_str().unwrap().to_string();
+
+    // only the keys granting no more than the manager are listed, the manager included
+    let (response, code) = server.list_api_keys("").await;
+    assert_eq!(200, code, "{response}");
+    let results = response["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2, "{response}");
+    assert!(results.iter().any(|key| key["uid"] == json!(uid)), "{response}");
+
+    let (response, code) = server.delete_api_key(&uid).await;
+    assert_eq!(204, code, "{response}");
+}
+
+#[actix_rt::test]
+async fn key_manager_cannot_create_broader_keys() {
+    let mut server = Server::new_auth().await;
+    let manager = add_key_manager(&mut server).await;
+    server.use_api_key(&manager);
+
+    let broader_keys = [
+        // another action
+        json!({ "actions": ["documents.add"], "indexes": ["products_fr"], "expiresAt": "2049-01-01T00:00:00Z" }),
+        // all the actions
+        json!({ "actions": ["*"], "indexes": ["products_fr"], "expiresAt": "2049-01-01T00:00:00Z" }),
+        // another index
+        json!({ "actions": ["search"], "indexes": ["movies"], "expiresAt": "2049-01-01T00:00:00Z" }),
+        // a broader pattern
+        json!({ "actions": ["search"], "indexes": ["products*"], "expiresAt": "2049-01-01T00:00:00Z" }),
+        // all the indexes
+        json!({ "actions": ["search"], "indexes": ["*"], "expiresAt": "2049-01-01T00:00:00Z" }),
+        // a later expiration date
+        json!({ "actions": ["search"], "indexes": ["products_fr"], "expiresAt": "2051-01-01T00:00:00Z" }),
+        // no expiration date
+        json!({ "actions": ["search"], "indexes": ["products_fr"], "expiresAt": null }),
+    ];
+    for content in broader_keys {
+        let (response, code) = server.add_api_key(content.clone()).await;
+        assert_eq!(403, code, "{content} {response}");
+        assert_eq!(response["code"], "api_key_exceeds_permissions", "{content} {response}");
+    }
+}
+
+#[actix_rt::test]
+async fn key_manager_cannot_escalate_through_the_keys_actions() {
+    let mut server = Server::new_auth().await;
+    let manager = add_key_manager(&mut server).await;
+    server.use_api_key(&manager);
+
+    // the keys actions aren't restricted, a key holding them could create any key
+    for action in ["keys.create", "keys.get", "keys.update", "keys.delete"] {
+        let content = json!({ "actions": [action], "indexes": ["products_fr"], "expiresAt": "2049-01-01T00:00:00Z" });
+        let (response, code) = server.add_api_key(content.clone()).await;
+        assert_eq!(403, code, "{content} {response}");
+        assert_eq!(response["code"], "api_key_exceeds_permissions", "{content} {response}");
+    }
+
+    let (response, code) =
+        server.add_api_key(json!({ "actions": ["*"], "indexes": ["*"], "expiresAt": null })).await;
+    assert_eq!(403, code, "{response}");
+    assert_eq!(response["code"], "api_key_exceeds_permissions", "{response}");
+
+    // a narrower key manager stays restricted to its own permissions
+    let (response, code) = server
+        .add_api_key(json!({
+            "actions": ["keys.manage", "search"],
+            "indexes": ["products_fr"],
+            "expiresAt": "2049-01-01T00:00:00Z",
+        }))
+        .await;
+    assert_eq!(201, code, "{response}");
+    server.use_api_key(response["key"].as_str().unwrap());
+
+    let (response, code) = server
+        .add_api_key(json!({ "actions": ["keys.create"], "indexes": ["products_fr"], "expiresAt": "2049-01-01T00:00:00Z" }))
+        .await;
+    assert_eq!(403, code, "{response}");
+    assert_eq!(response["code"], "api_key_exceeds_permissions", "{response}");
+}
+
+#[actix_rt::test]
+async fn key_manager_cannot_see_broader_keys() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server.list_api_keys("").await;
+    assert_eq!(200, code, "{response}");
+    let admin_uid = response["results"][1]["uid"].as_str().unwrap().to_string();
+
+    let manager = add_key_manager(&mut server).await;
+    server.use_api_key(&manager);
+
+    let (response, code) = server.get_api_key(&admin_uid).await;
+    assert_eq!(404, code, "{response}");
+    assert_eq!(response["code"], "api_key_not_found", "{response}");
+
+    let (response, code) = server.delete_api_key(&admin_uid).await;
+    assert_eq!(404, code, "{response}");
+    assert_eq!(response["code"], "api_key_not_found", "{response}");
+}
+
+#[actix_rt::test]
+async fn key_manager_updates_narrower_keys() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server.list_api_keys("").await;
+    assert_eq!(200, code, "{response}");
+    let admin_uid = response["results"][1]["uid"].as_str().unwrap().to_string();
+
+    let manager = add_key_manager(&mut server).await;
+    server.use_api_key(&manager);
+
+    let (response, code) = server
+        .add_api_key(json!({
+            "actions": ["search"],
+            "indexes": ["products_fr"],
+            "expiresAt": "2049-01-01T00:00:00Z",
+        }))
+        .await;
+    assert_eq!(201, code, "{response}");
+    let uid = response["uid"].as_str().unwrap().to_string();
+
+    let (response, code) = server.patch_api_key(&uid, json!({ "name": "storefront" })).await;
+    assert_eq!(200, code, "{response}");
+    assert_eq!(response["name"], "storefront", "{response}");
+
+    let (response, code) = server.patch_api_key(&admin_uid, json!({ "name": "mine" })).await;
+    assert_eq!(404, code, "{response}");
+    assert_eq!(response["code"], "api_key_not_found", "{response}");
+}
+
+#[actix_rt::test]
+async fn keys_actions_still_manage_all_the_keys() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server.list_api_keys("").await;
+    assert_eq!(200, code, "{response}");
+    let admin_uid = response["results"][1]["uid"].as_str().unwrap().to_string();
+
+    // the keys actions aren't restricted to what the key grants, unlike `keys.manage`
+    let (response, code) = server
+        .add_api_key(json!({
+            "actions": ["keys.create", "keys.get", "keys.update", "keys.delete"],
+            "indexes": ["products_*"],
+            "expiresAt": "2050-11-13T00:00:00Z",
+        }))
+        .await;
+    assert_eq!(201, code, "{response}");
+    server.use_api_key(response["key"].as_str().unwrap());
+
+    let (response, code) =
+        server.add_api_key(json!({ "actions": ["*"], "indexes": ["*"], "expiresAt": null })).await;
+    assert_eq!(201, code, "{response}");
+    let uid = response["uid"].as_str().unwrap().to_string();
+
+    let (response, code) = server.list_api_keys("").await;
+    assert_eq!(200, code, "{response}");
+    assert_eq!(response["results"].as_array().unwrap().len(), 4, "{response}");
+
+    let (response, code) = server.get_api_key(&admin_uid).await;
+    assert_eq!(200, code, "{response}");
+
+    let (response, code) = server.patch_api_key(&admin_uid, json!({ "name": "admin" })).await;
+    assert_eq!(200, code, "{response}");
+
+    let (response, code) = server.delete_api_key(&uid).await;
+    assert_eq!(204, code, "{response}");
+}
@@ -0,0 +1,59 @@
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn requests_are_counted_in_the_key_usage() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let (response, code) =
+        server.add_api_key(json!({ "actions": ["*"], "indexes": ["*"], "expiresAt": null })).await;
+    assert_eq!(201, code, "{response}");
+    let key = response["key"].as_str().unwrap().to_string();
+    let uid = response["uid"].as_str().unwrap().to_string();
+
+    let (response, code) = server.service.get(format!("/keys/{uid}/stats")).await;
+    assert_eq!(200, code, "{response}");
+    assert_eq!(
+        response,
+        json!({ "requests": 0, "searches": 0, "documentWrites": 0, "lastUsedAt": null })
+    );
+
+    server.use_api_key(&key);
+    let (response, code) = server.index("products").add_documents(json!([{ "id": 1 }]), None).await;
+    assert_eq!(202, code, "{response}");
+    let (response, code) = server.multi_search(json!({ "queries": [] })).await;
+    assert_eq!(200, code, "{response}");
+    let (response, code) = server.list_indexes(None, None).await;
+    assert_eq!(200, code, "{response}");
+
+    // the requests made with the master key are not counted
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server.service.get(format!("/keys/{uid}/stats")).await;
+    assert_eq!(200, code, "{response}");
+    assert_eq!(response["requests"], 3, "{response}");
+    assert_eq!(response["searches"], 1, "{response}");
+    assert_eq!(response["documentWrites"], 1, "{response}");
+    assert!(response["lastUsedAt"].is_string(), "{response}");
+
+    // the usage is also returned with the key
+    let (response, code) = server.get_api_key(&uid).await;
+    assert_eq!(200, code, "{response}");
+    assert_eq!(response["usage"]["requests"], 3, "{response}");
+
+    // but not when listing the keys
+    let (response, code) = server.list_api_keys("").await;
+    assert_eq!(200, code, "{response}");
+    assert!(response["results"][0].get("usage").is_none(), "{response}");
+}
+
+#[actix_rt::test]
+async fn stats_of_an_unknown_key() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let (response, code) =
+        server.service.get("/keys/4bc0887a-0e41-4f3b-935d-0c451dcee9c8/stats").await;
+    assert_eq!(404, code, "{response}");
+    assert_eq!(response["code"], "api_key_not_found");
+}
@@ -0,0 +1,148 @@
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn rotate_master_key_with_a_grace_period() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let (response, code) = server
+        .add_api_key(json!({ "actions": ["indexes.get"], "indexes": ["*"], "expiresAt": null }))
+        .await;
+    assert_eq!(201, code, "{response}");
+    let old_key = response["key"].as_str().unwrap().to_string();
+    let uid = response["uid"].as_str().unwrap().to_string();
+
+    let (response, code) = server
+        .service
+        .post("/master-key", json!({ "newMasterKey": "NEW_MASTER_KEY", "gracePeriod": 3600 }))
+        .await;
+    assert_eq!(200, code, "{response}");
+    assert!(response["previousMasterKeyValidUntil"].is_string(), "{response}");
+
+    // both master keys are accepted during the grace period
+    for master_key in ["MASTER_KEY", "NEW_MASTER_KEY"] {
+        server.use_api_key(master_key);
+        let (response, code) = server.list_api_keys("").await;
+        assert_eq!(200, code, "{response}");
+    }
+
+    // the keys are now derived from the new master key
+    let (response, code) = server.get_api_key(&uid).await;
+    assert_eq!(200, code, "{response}");
+    let new_key = response["key"].as_str().unwrap().to_string();
+    assert_ne!(old_key, new_key);
+
+    for key in [old_key, new_key] {
+        server.use_api_key(key);
+        let (response, code) = server.list_indexes(None, None).await;
+        assert_eq!(200, code, "{response}");
+    }
+}
+
+#[actix_rt::test]
+async fn rotate_master_key_without_grace_period() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let (response, code) =
+        server.service.post("/master-key", json!({ "newMasterKey": "NEW_MASTER_KEY" })).await;
+    assert_eq!(200, code, "{response}");
+
+    let (response, code) = server.list_api_keys("").await;
+    assert_eq!(403, code, "{response}");
+    assert_eq!(response["code"], "invalid_api_key");
+
+    server.use_api_key("NEW_MASTER_KEY");
+    let (response, code) = server.list_api_keys("").await;
+    assert_eq!(200, code, "{response}");
+}
+
+#[actix_rt::test]
+async fn only_the_master_key_can_rotate_the_master_key() {
+    let mut server = Server::new_auth().await;
+    server.use_admin_key("MASTER_KEY").await;
+
+    let (response, code) =
+        server.service.post("/master-key", json!({ "newMasterKey": "NEW_MASTER_KEY" })).await;
+    assert_eq!(403, code, "{response}");
+    assert_eq!(response["code"], "invalid_api_key");
+}
+
+#[actix_rt::test]
+async fn invalid_new_master_key() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    for new_master_key in ["", "MASTER_KEY"] {
+        let (response, code) =
+            server.service.post("/master-key", json!({ "newMasterKey": new_master_key })).await;
+        assert_eq!(400, code, "{response}");
+        assert_eq!(response["code"], "invalid_new_master_key");
+    }
+
+    let (response, code) = server
+        .service
+        .post("/master-key", json!({ "newMasterKey": "NEW_MASTER_KEY", "gracePeriod": -1 }))
+        .await;
+    assert_eq!(400, code, "{response}");
+    assert_eq!(response["code"], "invalid_master_key_grace_period");
+}
+
+#[actix_rt::test]
+async fn grace_period_too_long() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let (response, code) = server
+        .service
+        .post("/master-key", json!({ "newMasterKey": "NEW_MASTER_KEY", "gracePeriod": u64::MAX }))
+        .await;
+    assert_eq!(400, code, "{response}");
+    assert_eq!(response["code"], "invalid_master_key_grace_period");
+
+    // the master key wasn't rotated
+    let (response, code) = server.list_api_keys("").await;
+    assert_eq!(200, code, "{response}");
+}
+
+#[actix_rt::test]
+async fn only_the_current_master_key_can_rotate_the_master_key() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let (response, code) = server
+        .service
+        .post("/master-key", json!({ "newMasterKey": "NEW_MASTER_KEY", "gracePeriod": 3600 }))
+        .await;
+    assert_eq!(200, code, "{response}");
+
+    // the previous master key is still valid but can't take the new one back
+    let (response, code) =
+        server.service.post("/master-key", json!({ "newMasterKey": "STOLEN_MASTER_KEY" })).await;
+    assert_eq!(403, code, "{response}");
+    assert_eq!(response["code"], "invalid_api_key");
+
+    server.use_api_key("NEW_MASTER_KEY");
+    let (response, code) =
+        server.service.post("/master-key", json!({ "newMasterKey": "MASTER_KEY" })).await;
+    assert_eq!(200, code, "{response}");
+}
+
+#[actix_rt::test]
+async fn restart_with_a_retired_master_key() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let (response, code) =
+        server.service.post("/master-key", json!({ "newMasterKey": "NEW_MASTER_KEY" })).await;
+    assert_eq!(200, code, "{response}");
+
+    let db_path = &server.service.options.db_path;
+    let Err(error) = meilisearch_auth::AuthController::new(db_path, &Some("MASTER_KEY".into()))
+    else {
+        panic!("Meilisearch restarted with the retired master key");
+    };
+    assert!(error.to_string().starts_with("The master key was replaced through a rotation"));
+    assert!(meilisearch_auth::AuthController::new(db_path, &Some("NEW_MASTER_KEY".into())).is_ok());
+}
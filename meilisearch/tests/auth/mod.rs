@@ -1,7 +1,14 @@
+mod allowed_ips;
 mod api_keys;
+mod audit_log;
 mod authorization;
+mod displayed_attributes;
 mod errors;
+mod key_manager;
+mod key_usage;
+mod master_key;
 mod payload;
+mod rate_limits;
 mod tenant_token;
 
 mod tenant_token_multi_search;
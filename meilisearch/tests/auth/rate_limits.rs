@@ -0,0 +1,128 @@
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn writes_above_the_rate_limit_are_refused() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let (response, code) = server
+        .add_api_key(json!({
+            "actions": ["indexes.create", "search"],
+            "indexes": ["*"],
+            "expiresAt": null,
+            "rateLimits": { "writesPerMinute": 2 },
+        }))
+        .await;
+    assert_eq!(201, code, "{response}");
+    assert_eq!(response["rateLimits"], json!({ "searchPerSecond": null, "writesPerMinute": 2 }));
+    let key = response["key"].as_str().unwrap().to_string();
+    let uid = response["uid"].as_str().unwrap().to_string();
+
+    server.use_api_key(&key);
+    for uid in ["products_1", "products_2"] {
+        let (response, code) = server.index(uid).create(None).await;
+        assert_eq!(202, code, "{response}");
+    }
+    let (response, code) = server.index("products_3").create(None).await;
+    assert_eq!(429, code, "{response}");
+    assert_eq!(response["code"], "rate_limit_exceeded");
+
+    // The searches are not limited.
+    let (response, code) = server.multi_search(json!({ "queries": [] })).await;
+    assert_eq!(200, code, "{response}");
+
+    // Removing the limits makes the key usable again right away.
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server.patch_api_key(&uid, json!({ "rateLimits": null })).await;
+    assert_eq!(200, code, "{response}");
+    assert!(response.get("rateLimits").is_none(), "{response}");
+
+    server.use_api_key(&key);
+    let (response, code) = server.index("products_3").create(None).await;
+    assert_eq!(202, code, "{response}");
+}
+
+#[actix_rt::test]
+async fn documents_indexed_above_the_rate_limit_are_refused() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let (response, code) = server
+        .add_api_key(json!({
+            "actions": ["documents.add", "indexes.create", "tasks.get"],
+            "indexes": ["*"],
+            "expiresAt": null,
+            "rateLimits": { "indexedDocumentsPerSecond": 1 },
+        }))
+        .await;
+    assert_eq!(201, code, "{response}");
+    assert_eq!(response["rateLimits"]["indexedDocumentsPerSecond"], json!(1));
+    let key = response["key"].as_str().unwrap().to_string();
+    let uid = response["uid"].as_str().unwrap().to_string();
+
+    let documents: Vec<_> = (0..100).map(|id| json!({ "id": id })).collect();
+    server.use_api_key(&key);
+    let index = server.index("products");
+    let (response, code) = index.add_documents(json!(documents), None).await;
+    assert_eq!(202, code, "{response}");
+    // The first documents are indexed right away, and put the key in debt for about 100 seconds.
+    let response = index.wait_task(response["taskUid"].as_u64().unwrap()).await;
+    assert_eq!(response["status"], "succeeded", "{response}");
+
+    let (response, code) = index.add_documents(json!([{ "id": 100 }]), None).await;
+    assert_eq!(429, code, "{response}");
+    assert_eq!(response["code"], "rate_limit_exceeded");
+    drop(index);
+
+    // Removing the limits makes the key usable again right away.
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server.patch_api_key(&uid, json!({ "rateLimits": null })).await;
+    assert_eq!(200, code, "{response}");
+
+    server.use_api_key(&key);
+    let (response, code) =
+        server.index("products").add_documents(json!([{ "id": 100 }]), None).await;
+    assert_eq!(202, code, "{response}");
+}
+
+#[actix_rt::test]
+async fn invalid_rate_limits() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let (response, code) = server
+        .add_api_key(json!({
+            "actions": ["search"],
+            "indexes": ["*"],
+            "expiresAt": null,
+            "rateLimits": { "searchPerSecond": -1 },
+        }))
+        .await;
+    assert_eq!(400, code, "{response}");
+    assert_eq!(response["code"], "invalid_api_key_rate_limits");
+
+    // A throughput of zero would never let the documents be indexed.
+    for limit in ["indexedDocumentsPerSecond", "indexedBytesPerSecond"] {
+        let (response, code) = server
+            .add_api_key(json!({
+                "actions": ["documents.add"],
+                "indexes": ["*"],
+                "expiresAt": null,
+                "rateLimits": { limit: 0 },
+            }))
+            .await;
+        assert_eq!(400, code, "{response}");
+        assert_eq!(response["code"], "invalid_api_key_rate_limits");
+    }
+
+    let (response, code) = server
+        .add_api_key(json!({ "actions": ["documents.add"], "indexes": ["*"], "expiresAt": null }))
+        .await;
+    assert_eq!(201, code, "{response}");
+    let uid = response["uid"].as_str().unwrap().to_string();
+    let (response, code) =
+        server.patch_api_key(&uid, json!({ "rateLimits": { "indexedBytesPerSecond": 0 } })).await;
+    assert_eq!(400, code, "{response}");
+    assert_eq!(response["code"], "invalid_api_key_rate_limits");
+}
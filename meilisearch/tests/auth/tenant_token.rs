@@ -9,7 +9,7 @@ use super::authorization::{ALL_ACTIONS, AUTHORIZATIONS};
 use crate::common::{Server, Value};
 use crate::json;
 
-fn generate_tenant_token(
+pub(super) fn generate_tenant_token(
     parent_uid: impl AsRef<str>,
     parent_key: impl AsRef<str>,
     mut body: HashMap<&str, Value>,
@@ -338,6 +338,21 @@ impl Index<'_> {
         self.service.get(url).await
     }
 
+    pub async fn field_suggestions(&self) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/field-suggestions", urlencode(self.uid.as_ref()));
+        self.service.get(url).await
+    }
+
+    pub async fn export(&self) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/export", urlencode(self.uid.as_ref()));
+        self.service.post(url, json!(null)).await
+    }
+
+    pub async fn import(&self, body: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/import", urlencode(self.uid.as_ref()));
+        self.service.post(url, body).await
+    }
+
     /// Performs both GET and POST search queries
     pub async fn search(
         &self,
@@ -370,11 +385,30 @@ impl Index<'_> {
         self.service.post_encoded(url, query, self.encoder).await
     }
 
+    pub async fn search_with_template(&self, template: &str, query: Value) -> (Value, StatusCode) {
+        let url = format!(
+            "/indexes/{}/search?template={}",
+            urlencode(self.uid.as_ref()),
+            urlencode(template)
+        );
+        self.service.post_encoded(url, query, self.encoder).await
+    }
+
     pub async fn search_get(&self, query: &str) -> (Value, StatusCode) {
         let url = format!("/indexes/{}/search?{}", urlencode(self.uid.as_ref()), query);
         self.service.get(url).await
     }
 
+    pub async fn search_candidates(&self, query: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/search/candidates", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, query, self.encoder).await
+    }
+
+    pub async fn search_explain(&self, query: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/search/explain", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, query, self.encoder).await
+    }
+
     pub async fn facet_search(&self, query: Value) -> (Value, StatusCode) {
         let url = format!("/indexes/{}/facet-search", urlencode(self.uid.as_ref()));
         self.service.post_encoded(url, query, self.encoder).await
@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 
 use std::path::Path;
+use std::sync::Arc;
 use std::time::Duration;
 
 use actix_http::body::MessageBody;
@@ -9,7 +10,13 @@ use actix_web::http::StatusCode;
 use byte_unit::{Byte, ByteUnit};
 use clap::Parser;
 use meilisearch::option::{IndexerOpts, MaxMemory, Opt};
-use meilisearch::{analytics, create_app, setup_meilisearch, SubscriberForSecondLayer};
+use meilisearch::query_log::QueryLog;
+use meilisearch::search_queue::SearchQueue;
+use meilisearch::wasm_middleware::WasmMiddlewares;
+use meilisearch::{
+    analytics, create_app, setup_meilisearch, LogRouteHandle, LogStderrHandle,
+    SubscriberForSecondLayer,
+};
 use once_cell::sync::Lazy;
 use tempfile::TempDir;
 use tokio::time::sleep;
@@ -43,7 +50,19 @@ impl Server {
         let options = default_settings(dir.path());
 
         let (index_scheduler, auth) = setup_meilisearch(&options).unwrap();
-        let service = Service { index_scheduler, auth, options, api_key: None };
+        let query_log = Arc::new(QueryLog::from_opt(&options).unwrap());
+        let search_queue = Arc::new(SearchQueue::from_opt(&options));
+        let wasm_middlewares = Arc::new(WasmMiddlewares::from_opt(&options).unwrap());
+        let service = Service {
+            index_scheduler,
+            auth,
+            options,
+            field_usage: Default::default(),
+            query_log,
+            search_queue,
+            wasm_middlewares,
+            api_key: None,
+        };
 
         Server { service, _dir: Some(dir) }
     }
@@ -58,7 +77,19 @@ impl Server {
         options.master_key = Some("MASTER_KEY".to_string());
 
         let (index_scheduler, auth) = setup_meilisearch(&options).unwrap();
-        let service = Service { index_scheduler, auth, options, api_key: None };
+        let query_log = Arc::new(QueryLog::from_opt(&options).unwrap());
+        let search_queue = Arc::new(SearchQueue::from_opt(&options));
+        let wasm_middlewares = Arc::new(WasmMiddlewares::from_opt(&options).unwrap());
+        let service = Service {
+            index_scheduler,
+            auth,
+            options,
+            field_usage: Default::default(),
+            query_log,
+            search_queue,
+            wasm_middlewares,
+            api_key: None,
+        };
 
         Server { service, _dir: Some(dir) }
     }
@@ -71,7 +102,19 @@ impl Server {
 
     pub async fn new_with_options(options: Opt) -> Result<Self, anyhow::Error> {
         let (index_scheduler, auth) = setup_meilisearch(&options)?;
-        let service = Service { index_scheduler, auth, options, api_key: None };
+        let query_log = Arc::new(QueryLog::from_opt(&options)?);
+        let search_queue = Arc::new(SearchQueue::from_opt(&options));
+        let wasm_middlewares = Arc::new(WasmMiddlewares::from_opt(&options)?);
+        let service = Service {
+            index_scheduler,
+            auth,
+            options,
+            field_usage: Default::default(),
+            query_log,
+            search_queue,
+            wasm_middlewares,
+            api_key: None,
+        };
 
         Ok(Server { service, _dir: None })
     }
@@ -83,30 +126,54 @@ impl Server {
         Response = ServiceResponse<impl MessageBody>,
         Error = actix_web::Error,
     > {
-        let (_route_layer, route_layer_handle) =
-            tracing_subscriber::reload::Layer::new(None.with_filter(
-                tracing_subscriber::filter::Targets::new().with_target("", LevelFilter::OFF),
-            ));
-        let (_stderr_layer, stderr_layer_handle) = tracing_subscriber::reload::Layer::new(
-            (Box::new(
-                tracing_subscriber::fmt::layer()
-                    .with_span_events(tracing_subscriber::fmt::format::FmtSpan::CLOSE),
-            )
-                as Box<dyn tracing_subscriber::Layer<SubscriberForSecondLayer> + Send + Sync>)
-                .with_filter(tracing_subscriber::filter::Targets::new()),
-        );
-
         actix_web::test::init_service(create_app(
             self.service.index_scheduler.clone().into(),
             self.service.auth.clone().into(),
             self.service.options.clone(),
-            (route_layer_handle, stderr_layer_handle),
+            log_handles(),
             analytics::MockAnalytics::new(&self.service.options),
+            self.service.field_usage.clone().into(),
+            self.service.query_log.clone().into(),
+            self.service.search_queue.clone().into(),
+            self.service.wasm_middlewares.clone().into(),
             true,
         ))
         .await
     }
 
+    /// Serves the instance over HTTP on a random port and returns its URL.
+    pub fn listen(&self) -> String {
+        let index_scheduler = self.service.index_scheduler.clone();
+        let auth = self.service.auth.clone();
+        let options = self.service.options.clone();
+        let field_usage = self.service.field_usage.clone();
+        let query_log = self.service.query_log.clone();
+        let search_queue = self.service.search_queue.clone();
+        let wasm_middlewares = self.service.wasm_middlewares.clone();
+
+        // By listening on the port 0, the system will give us any available port.
+        let server = actix_web::HttpServer::new(move || {
+            create_app(
+                index_scheduler.clone().into(),
+                auth.clone().into(),
+                options.clone(),
+                log_handles(),
+                analytics::MockAnalytics::new(&options),
+                field_usage.clone().into(),
+                query_log.clone().into(),
+                search_queue.clone().into(),
+                wasm_middlewares.clone().into(),
+                false,
+            )
+        })
+        .workers(1)
+        .bind(("127.0.0.1", 0))
+        .unwrap();
+        let (ip, scheme) = server.addrs_with_scheme()[0];
+        tokio::spawn(server.run());
+        format!("{scheme}://{ip}")
+    }
+
     /// Returns a view to an index. There is no guarantee that the index exists.
     pub fn index(&self, uid: impl AsRef<str>) -> Index<'_> {
         self.index_with_encoder(uid, Encoder::Plain)
@@ -173,6 +240,14 @@ impl Server {
         self.service.post("/dumps", json!(null)).await
     }
 
+    pub async fn create_dump_with_compression(&self, compression: &str) -> (Value, StatusCode) {
+        self.service.post(format!("/dumps?compression={compression}"), json!(null)).await
+    }
+
+    pub async fn import_dump(&self, value: Value) -> (Value, StatusCode) {
+        self.service.post("/dumps/import", value).await
+    }
+
     pub async fn create_snapshot(&self) -> (Value, StatusCode) {
         self.service.post("/snapshots", json!(null)).await
     }
@@ -185,6 +260,10 @@ impl Server {
         self.service.post(format!("/tasks/cancel?{}", value), json!(null)).await
     }
 
+    pub async fn retry_task(&self, task_uid: u64) -> (Value, StatusCode) {
+        self.service.post(format!("/tasks/{}/retry", task_uid), json!(null)).await
+    }
+
     pub async fn delete_tasks(&self, value: &str) -> (Value, StatusCode) {
         self.service.delete(format!("/tasks?{}", value)).await
     }
@@ -219,11 +298,66 @@ impl Server {
         self.service.patch("/experimental-features", value).await
     }
 
+    pub async fn get_autobatching(&self) -> (Value, StatusCode) {
+        self.service.get("/experimental-features/autobatching").await
+    }
+
+    pub async fn set_autobatching(&self, value: Value) -> (Value, StatusCode) {
+        self.service.patch("/experimental-features/autobatching", value).await
+    }
+
+    pub async fn create_schedule(&self, value: Value) -> (Value, StatusCode) {
+        self.service.post("/schedules", value).await
+    }
+
+    pub async fn get_schedules(&self) -> (Value, StatusCode) {
+        self.service.get("/schedules").await
+    }
+
+    pub async fn get_schedule(&self, uid: u32) -> (Value, StatusCode) {
+        self.service.get(format!("/schedules/{}", uid)).await
+    }
+
+    pub async fn delete_schedule(&self, uid: u32) -> (Value, StatusCode) {
+        self.service.delete(format!("/schedules/{}", uid)).await
+    }
+
+    pub async fn get_dead_letters(&self) -> (Value, StatusCode) {
+        self.service.get("/dead-letters").await
+    }
+
+    pub async fn get_dead_letter(&self, task_uid: u64) -> (Value, StatusCode) {
+        self.service.get(format!("/dead-letters/{}", task_uid)).await
+    }
+
+    pub async fn delete_dead_letter(&self, task_uid: u64) -> (Value, StatusCode) {
+        self.service.delete(format!("/dead-letters/{}", task_uid)).await
+    }
+
+    pub async fn import_dead_letter(&self, task_uid: u64) -> (Value, StatusCode) {
+        self.service.post(format!("/dead-letters/{}/import", task_uid), json!(null)).await
+    }
+
     pub async fn get_metrics(&self) -> (Value, StatusCode) {
         self.service.get("/metrics").await
     }
 }
 
+fn log_handles() -> (LogRouteHandle, LogStderrHandle) {
+    let (_route_layer, route_layer_handle) =
+        tracing_subscriber::reload::Layer::new(None.with_filter(
+            tracing_subscriber::filter::Targets::new().with_target("", LevelFilter::OFF),
+        ));
+    let (_stderr_layer, stderr_layer_handle) = tracing_subscriber::reload::Layer::new(
+        (Box::new(
+            tracing_subscriber::fmt::layer()
+                .with_span_events(tracing_subscriber::fmt::format::FmtSpan::CLOSE),
+        ) as Box<dyn tracing_subscriber::Layer<SubscriberForSecondLayer> + Send + Sync>)
+            .with_filter(tracing_subscriber::filter::Targets::new()),
+    );
+    (route_layer_handle, stderr_layer_handle)
+}
+
 pub fn default_settings(dir: impl AsRef<Path>) -> Opt {
     Opt {
         db_path: dir.as_ref().join("db"),
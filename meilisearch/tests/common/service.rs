@@ -5,6 +5,10 @@ use actix_web::http::StatusCode;
 use actix_web::test;
 use actix_web::test::TestRequest;
 use index_scheduler::IndexScheduler;
+use meilisearch::field_usage::FieldUsage;
+use meilisearch::query_log::QueryLog;
+use meilisearch::search_queue::SearchQueue;
+use meilisearch::wasm_middleware::WasmMiddlewares;
 use meilisearch::{analytics, create_app, Opt, SubscriberForSecondLayer};
 use meilisearch_auth::AuthController;
 use tracing::level_filters::LevelFilter;
@@ -17,6 +21,10 @@ pub struct Service {
     pub index_scheduler: Arc<IndexScheduler>,
     pub auth: Arc<AuthController>,
     pub options: Opt,
+    pub field_usage: Arc<FieldUsage>,
+    pub query_log: Arc<QueryLog>,
+    pub search_queue: Arc<SearchQueue>,
+    pub wasm_middlewares: Arc<WasmMiddlewares>,
     pub api_key: Option<String>,
 }
 
@@ -126,6 +134,10 @@ impl Service {
             self.options.clone(),
             (route_layer_handle, stderr_layer_handle),
             analytics::MockAnalytics::new(&self.options),
+            self.field_usage.clone().into(),
+            self.query_log.clone().into(),
+            self.search_queue.clone().into(),
+            self.wasm_middlewares.clone().into(),
             true,
         ))
         .await;
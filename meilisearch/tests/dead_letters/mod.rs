@@ -0,0 +1,120 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn import_the_payload_of_a_failed_document_addition() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let (task, _) = index.create(None).await;
+    index.wait_task(task.uid()).await;
+
+    // the primary key can't be inferred
+    let (task, _) = index.add_documents(json!([{ "name": "kefir" }]), None).await;
+    let failed = index.wait_task(task.uid()).await;
+    assert_eq!(failed["status"], "failed", "{failed}");
+
+    let (response, code) = server.get_dead_letters().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".results[].enqueuedAt" => "[date]", ".results[].startedAt" => "[date]", ".results[].finishedAt" => "[date]" }), @r###"
+    {
+      "results": [
+        {
+          "taskUid": 1,
+          "indexUid": "test",
+          "primaryKey": null,
+          "documentsCount": 1,
+          "error": {
+            "message": "The primary key inference failed as the engine did not find any field ending with `id` in its name. Please specify the primary key manually using the `primaryKey` query parameter.",
+            "code": "index_primary_key_no_candidate_found",
+            "type": "invalid_request",
+            "link": "https://docs.meilisearch.com/errors#index_primary_key_no_candidate_found"
+          },
+          "enqueuedAt": "[date]",
+          "startedAt": "[date]",
+          "finishedAt": "[date]"
+        }
+      ]
+    }
+    "###);
+
+    let (task, _) = index.update(Some("name")).await;
+    index.wait_task(task.uid()).await;
+
+    let (response, code) = server.import_dead_letter(1).await;
+    snapshot!(code, @"202 Accepted");
+    let task = index.wait_task(response.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+    snapshot!(task["retriedFrom"], @"1");
+    let (response, _) = index.get_all_documents_raw("").await;
+    snapshot!(json_string!(response["results"]), @r###"
+    [
+      {
+        "name": "kefir"
+      }
+    ]
+    "###);
+
+    let (response, code) = server.get_dead_letters().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "results": []
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn delete_a_dead_letter() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let (task, _) = index.add_documents(json!([{ "name": "kefir" }]), None).await;
+    index.wait_task(task.uid()).await;
+
+    let (response, code) = server.delete_dead_letter(0).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["taskUid"], @"0");
+
+    let (response, code) = server.get_dead_letter(0).await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Dead letter of task `0` not found.",
+      "code": "dead_letter_not_found",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#dead_letter_not_found"
+    }
+    "###);
+
+    // the payload is gone, the failed task can't be retried anymore
+    let (response, code) = server.retry_task(0).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""task_not_retryable""###);
+}
+
+#[actix_rt::test]
+async fn delete_a_dead_letter_along_with_its_task() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let (task, _) = index.add_documents(json!([{ "name": "kefir" }]), None).await;
+    index.wait_task(task.uid()).await;
+
+    let (response, code) = server.get_dead_letter(0).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["taskUid"], @"0");
+
+    let (task, _) = server.delete_tasks("uids=0").await;
+    server.wait_task(task.uid()).await;
+
+    let (response, code) = server.get_dead_letter(0).await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(response["code"], @r###""dead_letter_not_found""###);
+    let (response, code) = server.get_dead_letters().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "results": []
+    }
+    "###);
+}
@@ -280,6 +280,101 @@ async fn add_csv_document() {
     "###);
 }
 
+#[actix_rt::test]
+async fn add_multipart_documents_with_mixed_formats() {
+    let server = Server::new().await;
+    let index = server.index("pets");
+
+    let payload = "--boundary\r
+Content-Disposition: form-data; name=\"csv\"; filename=\"pets.csv\"\r
+Content-Type: text/csv\r
+\r
+#id,name,race
+0,jean,bernese mountain
+1,jorts,orange cat\r
+--boundary\r
+Content-Disposition: form-data; name=\"ndjson\"; filename=\"pets.ndjson\"\r
+\r
+{ \"#id\": \"2\", \"name\": \"lilou\", \"race\": \"pug\" }\r
+--boundary\r
+Content-Disposition: form-data; name=\"comment\"\r
+\r
+this field is not a file and is ignored\r
+--boundary--\r
+";
+
+    let (response, code) = index
+        .raw_update_documents(payload, Some("multipart/form-data; boundary=boundary"), "")
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response["taskUid"].as_u64().unwrap()).await;
+    snapshot!(json_string!(response, { ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]", ".duration" => "[duration]" }), @r###"
+    {
+      "uid": 0,
+      "indexUid": "pets",
+      "status": "succeeded",
+      "type": "documentAdditionOrUpdate",
+      "canceledBy": null,
+      "details": {
+        "receivedDocuments": 3,
+        "indexedDocuments": 3
+      },
+      "error": null,
+      "duration": "[duration]",
+      "enqueuedAt": "[date]",
+      "startedAt": "[date]",
+      "finishedAt": "[date]"
+    }
+    "###);
+
+    let (documents, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(documents), @r###"
+    {
+      "results": [
+        {
+          "#id": "0",
+          "name": "jean",
+          "race": "bernese mountain"
+        },
+        {
+          "#id": "1",
+          "name": "jorts",
+          "race": "orange cat"
+        },
+        {
+          "#id": "2",
+          "name": "lilou",
+          "race": "pug"
+        }
+      ],
+      "offset": 0,
+      "limit": 20,
+      "total": 3
+    }
+    "###);
+
+    // a form without any file is rejected
+    let payload = "--boundary\r
+Content-Disposition: form-data; name=\"comment\"\r
+\r
+no files here\r
+--boundary--\r
+";
+    let (response, code) = index
+        .raw_update_documents(payload, Some("multipart/form-data; boundary=boundary"), "")
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "A multipart/form-data payload must contain at least one file.",
+      "code": "missing_payload",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#missing_payload"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn add_csv_document_with_types() {
     let server = Server::new().await;
@@ -419,6 +514,84 @@ async fn add_csv_document_with_custom_delimiter() {
     "###);
 }
 
+#[actix_rt::test]
+async fn add_documents_with_field_mapping() {
+    let server = Server::new().await;
+    let index = server.index("books");
+
+    let documents = r#"[
+        {
+            "id": 1,
+            "title": "Carol",
+            "internal": "to drop",
+            "meta": { "author": "Patricia Highsmith", "year": 1952 }
+        }
+    ]"#;
+    let (response, code) = index
+        .raw_add_documents(
+            documents,
+            vec![("Content-Type", "application/json")],
+            "?fieldMapping=title:name,internal:,meta.author:author",
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response["taskUid"].as_u64().unwrap()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+
+    let (documents, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(documents["results"]), @r###"
+    [
+      {
+        "id": 1,
+        "meta": {
+          "year": 1952
+        },
+        "name": "Carol",
+        "author": "Patricia Highsmith"
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn add_csv_document_with_field_mapping() {
+    let server = Server::new().await;
+    let index = server.index("pets");
+
+    let document = "#id,name,internal,price:number
+0,jean,to drop,10
+1,jorts,to drop,12";
+
+    let (response, code) = index
+        .raw_update_documents(
+            document,
+            Some("text/csv"),
+            "?fieldMapping=name:nickname,internal:,price:cost",
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response["taskUid"].as_u64().unwrap()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+
+    let (documents, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(documents["results"]), @r###"
+    [
+      {
+        "#id": "0",
+        "nickname": "jean",
+        "cost": 10
+      },
+      {
+        "#id": "1",
+        "nickname": "jorts",
+        "cost": 12
+      }
+    ]
+    "###);
+}
+
 #[actix_rt::test]
 async fn add_csv_document_with_types_error() {
     let server = Server::new().await;
@@ -480,7 +653,7 @@ async fn error_add_documents_test_bad_content_types() {
     snapshot!(json_string!(response),
         @r###"
     {
-      "message": "The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `multipart/form-data`",
       "code": "invalid_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_content_type"
@@ -501,7 +674,7 @@ async fn error_add_documents_test_bad_content_types() {
     snapshot!(json_string!(response),
         @r###"
     {
-      "message": "The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `multipart/form-data`",
       "code": "invalid_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_content_type"
@@ -535,7 +708,7 @@ async fn error_add_documents_test_no_content_type() {
     snapshot!(json_string!(response),
         @r###"
     {
-      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `multipart/form-data`",
       "code": "missing_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#missing_content_type"
@@ -555,7 +728,7 @@ async fn error_add_documents_test_no_content_type() {
     snapshot!(json_string!(response),
         @r###"
     {
-      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `multipart/form-data`",
       "code": "missing_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#missing_content_type"
@@ -1182,7 +1355,7 @@ async fn error_add_documents_bad_document_id() {
         "indexedDocuments": 0
       },
       "error": {
-        "message": "Document identifier `\"foo & bar\"` is invalid. A document identifier can be of type integer or string, only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and underscores (_).",
+        "message": "Document identifier `\"foo & bar\"` is invalid. A document identifier can be of type integer or string, only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-), underscores (_) and colons (:).",
         "code": "invalid_document_id",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_document_id"
@@ -1195,6 +1368,61 @@ async fn error_add_documents_bad_document_id() {
     "###);
 }
 
+#[actix_rt::test]
+async fn error_add_documents_float_document_id() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(Some("docid")).await;
+    let documents = json!([
+        {
+            "docid": 1e5,
+            "content": "foobar"
+        }
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+    let (response, code) = index.get_task(1).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "Document identifier `100000.0` is invalid. A document identifier can't be a floating-point number, use an integer or a string instead.",
+      "code": "invalid_document_id",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_id"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn add_documents_integer_document_ids_round_trip() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let documents = json!([
+        { "id": 18446744073709551615u64, "content": "largest" },
+        { "id": -7, "content": "negative" },
+        { "id": "007", "content": "leading zeros" },
+    ]);
+    let (task, _) = index.add_documents(documents, Some("id")).await;
+    let response = index.wait_task(task.uid()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+
+    let (document, code) = index.get_document(18446744073709551615u64, None).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(document), @r###"
+    {
+      "id": 18446744073709551615,
+      "content": "largest"
+    }
+    "###);
+
+    // a string id is never coerced into an integer
+    let (_, code) = index.get_document(7, None).await;
+    snapshot!(code, @"404 Not Found");
+    let (document, code) = server.service.get("/indexes/test/documents/007").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(document["id"], @r###""007""###);
+}
+
 #[actix_rt::test]
 async fn error_add_documents_missing_document_id() {
     let server = Server::new().await;
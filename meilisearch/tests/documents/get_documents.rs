@@ -1,5 +1,5 @@
 use actix_web::test;
-use http::header::ACCEPT_ENCODING;
+use http::header::{ACCEPT, ACCEPT_ENCODING, CONTENT_ENCODING};
 use meili_snap::*;
 use urlencoding::encode as urlencode;
 
@@ -169,6 +169,76 @@ async fn get_all_documents_no_options_with_response_compression() {
     assert_eq!(arr.len(), 20);
 }
 
+#[actix_rt::test]
+async fn small_responses_are_not_compressed() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.add_documents(json!([{ "id": 1, "title": "Carol" }]), None).await;
+    index.wait_task(0).await;
+
+    let app = server.init_web_app().await;
+    let req = test::TestRequest::get()
+        .uri("/indexes/test/documents")
+        .insert_header((ACCEPT_ENCODING, "gzip, br"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    snapshot!(res.status(), @"200 OK");
+    snapshot!(res.headers().get(CONTENT_ENCODING).unwrap().to_str().unwrap(), @"identity");
+    let response: Value = serde_json::from_slice(&test::read_body(res).await).unwrap();
+    snapshot!(response["results"], @r###"[{"id":1,"title":"Carol"}]"###);
+}
+
+#[actix_rt::test]
+async fn get_all_documents_as_csv() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index
+        .add_documents(
+            json!([
+                { "id": 1, "title": "Carol", "author": { "name": "Patricia" }, "tags": ["a", "b"] },
+                { "id": 2, "title": "Dune, Messiah", "author": { "name": "Frank" } },
+            ]),
+            None,
+        )
+        .await;
+    index.wait_task(0).await;
+
+    let app = server.init_web_app().await;
+    let req = test::TestRequest::get()
+        .uri("/indexes/test/documents?csvNestedSeparator=__&csvArraySeparator=%3B")
+        .insert_header((ACCEPT, "text/csv"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    snapshot!(res.status(), @"200 OK");
+    snapshot!(res.headers().get("content-type").unwrap().to_str().unwrap(), @"text/csv; charset=utf-8");
+    let body = String::from_utf8(test::read_body(res).await.to_vec()).unwrap();
+    snapshot!(body, @r###"
+    id,title,author__name,tags
+    1,Carol,Patricia,a;b
+    2,"Dune, Messiah",Frank,
+    "###);
+
+    let (response, code) = index.get_all_documents_raw("?csvNestedSeparator=").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["results"].as_array().unwrap().len(), @"2");
+
+    let req = test::TestRequest::get()
+        .uri("/indexes/test/documents?csvArraySeparator=")
+        .insert_header((ACCEPT, "text/csv"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    snapshot!(res.status(), @"400 Bad Request");
+    let response: Value = test::read_body_json(res).await;
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The `csvArraySeparator` parameter must not be empty.",
+      "code": "invalid_document_csv_separator",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_csv_separator"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn test_get_all_documents_limit() {
     let server = Server::new().await;
@@ -540,3 +610,76 @@ async fn get_document_by_filter() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn get_documents_sorted() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+    index
+        .update_settings(
+            json!({ "filterableAttributes": ["color"], "sortableAttributes": ["date"] }),
+        )
+        .await;
+    index
+        .add_documents(
+            json!([
+                { "id": 0, "color": "red", "date": 2 },
+                { "id": 1, "color": "blue", "date": 3 },
+                { "id": 2, "color": "blue" },
+                { "id": 3, "color": "blue", "date": 1 },
+            ]),
+            Some("id"),
+        )
+        .await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.get_document_by_filter(json!({ "sort": ["date:desc"] })).await;
+    let (response2, code2) = index.get_all_documents_raw("?sort=date:desc").await;
+    snapshot!(code, @"200 OK");
+    let ids: Vec<_> = response["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|doc| doc["id"].as_u64().unwrap())
+        .collect();
+    snapshot!(format!("{ids:?}"), @"[1, 0, 3, 2]");
+    snapshot!(response["total"], @"4");
+    assert_eq!(code, code2);
+    assert_eq!(response, response2);
+
+    let (response, code) = index
+        .get_document_by_filter(
+            json!({ "offset": 1, "limit": 1, "filter": "color = blue", "sort": ["date:asc"] }),
+        )
+        .await;
+    let (response2, code2) =
+        index.get_all_documents_raw("?filter=color=blue&sort=date:asc&offset=1&limit=1").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "results": [
+        {
+          "id": 1,
+          "color": "blue",
+          "date": 3
+        }
+      ],
+      "offset": 1,
+      "limit": 1,
+      "total": 3
+    }
+    "###);
+    assert_eq!(code, code2);
+    assert_eq!(response, response2);
+
+    let (response, code) = index.get_all_documents_raw("?sort=color:asc").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Attribute `color` is not sortable. Available sortable attributes are: `date`.",
+      "code": "invalid_document_sort",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_sort"
+    }
+    "###);
+}
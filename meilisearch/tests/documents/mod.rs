@@ -2,4 +2,5 @@ mod add_documents;
 mod delete_documents;
 mod errors;
 mod get_documents;
+mod route_documents;
 mod update_documents;
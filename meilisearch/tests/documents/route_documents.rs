@@ -0,0 +1,150 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::Server;
+
+const NDJSON: (&str, &str) = ("content-type", "application/x-ndjson");
+
+#[actix_rt::test]
+async fn route_documents_by_field_value() {
+    let server = Server::new().await;
+    let documents = r#"{ "id": 1, "type": "books", "title": "Dune" }
+{ "id": 2, "type": "movies", "title": "Dune" }
+{ "id": 3, "type": "books", "title": "Hyperion" }"#;
+
+    let (response, code) =
+        server.service.post_str("/documents?routingField=type", documents, vec![NDJSON]).await;
+    snapshot!(code, @"202 Accepted");
+    snapshot!(json_string!(response, { "[].enqueuedAt" => "[date]" }), @r###"
+    [
+      {
+        "taskUid": 0,
+        "indexUid": "books",
+        "status": "enqueued",
+        "type": "documentAdditionOrUpdate",
+        "enqueuedAt": "[date]"
+      },
+      {
+        "taskUid": 1,
+        "indexUid": "movies",
+        "status": "enqueued",
+        "type": "documentAdditionOrUpdate",
+        "enqueuedAt": "[date]"
+      }
+    ]
+    "###);
+    server.wait_task(1).await;
+
+    let (response, _) = server.index("books").get_all_documents_raw("?fields=id").await;
+    snapshot!(json_string!(response["results"]), @r###"
+    [
+      {
+        "id": 1
+      },
+      {
+        "id": 3
+      }
+    ]
+    "###);
+    let (response, _) = server.index("movies").get_all_documents_raw("?fields=id").await;
+    snapshot!(json_string!(response["results"]), @r###"
+    [
+      {
+        "id": 2
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn route_documents_with_routes_and_default_index() {
+    let server = Server::new().await;
+    let documents = r#"{ "id": 1, "type": "book" }
+{ "id": 2, "type": "song" }
+{ "id": 3 }"#;
+
+    let (response, code) = server
+        .service
+        .post_str(
+            "/documents?routingField=type&routes=book:books&defaultIndex=misc",
+            documents,
+            vec![NDJSON],
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let indexes: Vec<_> =
+        response.as_array().unwrap().iter().map(|task| &task["indexUid"]).collect();
+    snapshot!(json_string!(indexes), @r###"
+    [
+      "books",
+      "misc"
+    ]
+    "###);
+    server.wait_task(1).await;
+
+    let (response, _) = server.index("misc").get_all_documents_raw("?fields=id").await;
+    snapshot!(json_string!(response["results"]), @r###"
+    [
+      {
+        "id": 2
+      },
+      {
+        "id": 3
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn route_documents_errors() {
+    let server = Server::new().await;
+
+    let (response, code) = server
+        .service
+        .post_str("/documents?routingField=type", r#"{ "id": 1 }"#, vec![NDJSON])
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The document at line 1 has no `type` field to route it and no `defaultIndex` was given.",
+      "code": "invalid_document_routing_field",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_routing_field"
+    }
+    "###);
+
+    let (response, code) = server
+        .service
+        .post_str("/documents?routingField=type", r#"{ "id": 1, "type": "b@d" }"#, vec![NDJSON])
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_document_routing_field""###);
+
+    let (response, code) = server
+        .service
+        .post_str("/documents?routingField=type&routes=books", r#"{ "id": 1 }"#, vec![NDJSON])
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid route `books`, a route is written `value:indexUid`.",
+      "code": "invalid_document_routes",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_routes"
+    }
+    "###);
+
+    let (response, code) = server
+        .service
+        .post_str(
+            "/documents?routingField=type",
+            r#"[{ "id": 1, "type": "books" }]"#,
+            vec![("content-type", "application/json")],
+        )
+        .await;
+    snapshot!(code, @"415 Unsupported Media Type");
+    snapshot!(response["code"], @r###""invalid_content_type""###);
+
+    // nothing was enqueued
+    let (response, _) = server.tasks().await;
+    snapshot!(response["total"], @"0");
+}
@@ -67,7 +67,8 @@ async fn import_dump_v1_movie_raw() {
           "twoTypos": 9
         },
         "disableOnWords": [],
-        "disableOnAttributes": []
+        "disableOnAttributes": [],
+        "disableOnNumbers": false
       },
       "faceting": {
         "maxValuesPerFacet": 100,
@@ -77,6 +78,10 @@ async fn import_dump_v1_movie_raw() {
       },
       "pagination": {
         "maxTotalHits": 1000
+      },
+      "queryPlanHints": {
+        "filterResolution": "beforeWords",
+        "proximityMaxCandidates": null
       }
     }
     "###
@@ -228,7 +233,8 @@ async fn import_dump_v1_movie_with_settings() {
           "twoTypos": 9
         },
         "disableOnWords": [],
-        "disableOnAttributes": []
+        "disableOnAttributes": [],
+        "disableOnNumbers": false
       },
       "faceting": {
         "maxValuesPerFacet": 100,
@@ -238,6 +244,10 @@ async fn import_dump_v1_movie_with_settings() {
       },
       "pagination": {
         "maxTotalHits": 1000
+      },
+      "queryPlanHints": {
+        "filterResolution": "beforeWords",
+        "proximityMaxCandidates": null
       }
     }
     "###
@@ -375,7 +385,8 @@ async fn import_dump_v1_rubygems_with_settings() {
           "twoTypos": 9
         },
         "disableOnWords": [],
-        "disableOnAttributes": []
+        "disableOnAttributes": [],
+        "disableOnNumbers": false
       },
       "faceting": {
         "maxValuesPerFacet": 100,
@@ -385,6 +396,10 @@ async fn import_dump_v1_rubygems_with_settings() {
       },
       "pagination": {
         "maxTotalHits": 1000
+      },
+      "queryPlanHints": {
+        "filterResolution": "beforeWords",
+        "proximityMaxCandidates": null
       }
     }
     "###
@@ -508,7 +523,8 @@ async fn import_dump_v2_movie_raw() {
           "twoTypos": 9
         },
         "disableOnWords": [],
-        "disableOnAttributes": []
+        "disableOnAttributes": [],
+        "disableOnNumbers": false
       },
       "faceting": {
         "maxValuesPerFacet": 100,
@@ -518,6 +534,10 @@ async fn import_dump_v2_movie_raw() {
       },
       "pagination": {
         "maxTotalHits": 1000
+      },
+      "queryPlanHints": {
+        "filterResolution": "beforeWords",
+        "proximityMaxCandidates": null
       }
     }
     "###
@@ -653,7 +673,8 @@ async fn import_dump_v2_movie_with_settings() {
           "twoTypos": 9
         },
         "disableOnWords": [],
-        "disableOnAttributes": []
+        "disableOnAttributes": [],
+        "disableOnNumbers": false
       },
       "faceting": {
         "maxValuesPerFacet": 100,
@@ -663,6 +684,10 @@ async fn import_dump_v2_movie_with_settings() {
       },
       "pagination": {
         "maxTotalHits": 1000
+      },
+      "queryPlanHints": {
+        "filterResolution": "beforeWords",
+        "proximityMaxCandidates": null
       }
     }
     "###
@@ -797,7 +822,8 @@ async fn import_dump_v2_rubygems_with_settings() {
           "twoTypos": 9
         },
         "disableOnWords": [],
-        "disableOnAttributes": []
+        "disableOnAttributes": [],
+        "disableOnNumbers": false
       },
       "faceting": {
         "maxValuesPerFacet": 100,
@@ -807,6 +833,10 @@ async fn import_dump_v2_rubygems_with_settings() {
       },
       "pagination": {
         "maxTotalHits": 1000
+      },
+      "queryPlanHints": {
+        "filterResolution": "beforeWords",
+        "proximityMaxCandidates": null
       }
     }
     "###
@@ -930,7 +960,8 @@ async fn import_dump_v3_movie_raw() {
           "twoTypos": 9
         },
         "disableOnWords": [],
-        "disableOnAttributes": []
+        "disableOnAttributes": [],
+        "disableOnNumbers": false
       },
       "faceting": {
         "maxValuesPerFacet": 100,
@@ -940,6 +971,10 @@ async fn import_dump_v3_movie_raw() {
       },
       "pagination": {
         "maxTotalHits": 1000
+      },
+      "queryPlanHints": {
+        "filterResolution": "beforeWords",
+        "proximityMaxCandidates": null
       }
     }
     "###
@@ -1075,7 +1110,8 @@ async fn import_dump_v3_movie_with_settings() {
           "twoTypos": 9
         },
         "disableOnWords": [],
-        "disableOnAttributes": []
+        "disableOnAttributes": [],
+        "disableOnNumbers": false
       },
       "faceting": {
         "maxValuesPerFacet": 100,
@@ -1085,6 +1121,10 @@ async fn import_dump_v3_movie_with_settings() {
       },
       "pagination": {
         "maxTotalHits": 1000
+      },
+      "queryPlanHints": {
+        "filterResolution": "beforeWords",
+        "proximityMaxCandidates": null
       }
     }
     "###
@@ -1219,7 +1259,8 @@ async fn import_dump_v3_rubygems_with_settings() {
           "twoTypos": 9
         },
         "disableOnWords": [],
-        "disableOnAttributes": []
+        "disableOnAttributes": [],
+        "disableOnNumbers": false
       },
       "faceting": {
         "maxValuesPerFacet": 100,
@@ -1229,6 +1270,10 @@ async fn import_dump_v3_rubygems_with_settings() {
       },
       "pagination": {
         "maxTotalHits": 1000
+      },
+      "queryPlanHints": {
+        "filterResolution": "beforeWords",
+        "proximityMaxCandidates": null
       }
     }
     "###
@@ -1352,7 +1397,8 @@ async fn import_dump_v4_movie_raw() {
           "twoTypos": 9
         },
         "disableOnWords": [],
-        "disableOnAttributes": []
+        "disableOnAttributes": [],
+        "disableOnNumbers": false
       },
       "faceting": {
         "maxValuesPerFacet": 100,
@@ -1362,6 +1408,10 @@ async fn import_dump_v4_movie_raw() {
       },
       "pagination": {
         "maxTotalHits": 1000
+      },
+      "queryPlanHints": {
+        "filterResolution": "beforeWords",
+        "proximityMaxCandidates": null
       }
     }
     "###
@@ -1497,7 +1547,8 @@ async fn import_dump_v4_movie_with_settings() {
           "twoTypos": 9
         },
         "disableOnWords": [],
-        "disableOnAttributes": []
+        "disableOnAttributes": [],
+        "disableOnNumbers": false
       },
       "faceting": {
         "maxValuesPerFacet": 100,
@@ -1507,6 +1558,10 @@ async fn import_dump_v4_movie_with_settings() {
       },
       "pagination": {
         "maxTotalHits": 1000
+      },
+      "queryPlanHints": {
+        "filterResolution": "beforeWords",
+        "proximityMaxCandidates": null
       }
     }
     "###
@@ -1641,7 +1696,8 @@ async fn import_dump_v4_rubygems_with_settings() {
           "twoTypos": 9
         },
         "disableOnWords": [],
-        "disableOnAttributes": []
+        "disableOnAttributes": [],
+        "disableOnNumbers": false
       },
       "faceting": {
         "maxValuesPerFacet": 100,
@@ -1651,6 +1707,10 @@ async fn import_dump_v4_rubygems_with_settings() {
       },
       "pagination": {
         "maxTotalHits": 1000
+      },
+      "queryPlanHints": {
+        "filterResolution": "beforeWords",
+        "proximityMaxCandidates": null
       }
     }
     "###
@@ -1885,7 +1945,8 @@ async fn import_dump_v6_containing_experimental_features() {
           "twoTypos": 9
         },
         "disableOnWords": [],
-        "disableOnAttributes": []
+        "disableOnAttributes": [],
+        "disableOnNumbers": false
       },
       "faceting": {
         "maxValuesPerFacet": 100,
@@ -1895,6 +1956,10 @@ async fn import_dump_v6_containing_experimental_features() {
       },
       "pagination": {
         "maxTotalHits": 1000
+      },
+      "queryPlanHints": {
+        "filterResolution": "beforeWords",
+        "proximityMaxCandidates": null
       }
     }
     "###);
@@ -1926,3 +1991,202 @@ async fn import_dump_v6_containing_experimental_features() {
         })
         .await;
 }
+
+#[actix_rt::test]
+async fn import_dump_at_runtime() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let (task, _) = index.add_documents(json!([{ "id": 1, "title": "Carol" }]), None).await;
+    index.wait_task(task.uid()).await;
+
+    let (task, code) = server.create_dump().await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(task.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+    let dump_uid = task["details"]["dumpUid"].as_str().unwrap().to_string();
+
+    let (task, _) = index.delete().await;
+    index.wait_task(task.uid()).await;
+
+    let (task, code) = server.import_dump(json!({ "dumpUid": dump_uid })).await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(task.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+    snapshot!(task["type"], @r###""dumpImport""###);
+
+    let (documents, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(documents["results"]), @r###"
+    [
+      {
+        "id": 1,
+        "title": "Carol"
+      }
+    ]
+    "###);
+
+    // the index now exists, importing the same dump again must fail without altering it
+    let (task, _) = server.import_dump(json!({ "dumpUid": dump_uid })).await;
+    let task = server.wait_task(task.uid()).await;
+    snapshot!(task["status"], @r###""failed""###);
+    snapshot!(task["error"]["code"], @r###""index_already_exists""###);
+}
+
+#[actix_rt::test]
+async fn import_dumps_of_every_compression() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let (task, _) = index.add_documents(json!([{ "id": 1, "title": "Carol" }]), None).await;
+    index.wait_task(task.uid()).await;
+
+    for compression in ["gzip:1", "zstd", "zstd:19", "none"] {
+        let (task, code) = server.create_dump_with_compression(compression).await;
+        snapshot!(code, @"202 Accepted");
+        let task = server.wait_task(task.uid()).await;
+        assert_eq!(task["status"], "succeeded", "{compression}: {task}");
+        let dump_uid = task["details"]["dumpUid"].as_str().unwrap().to_string();
+
+        let (task, _) = index.delete().await;
+        index.wait_task(task.uid()).await;
+        let (task, _) = server.import_dump(json!({ "dumpUid": dump_uid })).await;
+        let task = server.wait_task(task.uid()).await;
+        assert_eq!(task["status"], "succeeded", "{compression}: {task}");
+
+        let (documents, _) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+        assert_eq!(documents["results"], json!([{ "id": 1, "title": "Carol" }]), "{compression}");
+    }
+}
+
+#[actix_rt::test]
+async fn create_dump_bad_compression() {
+    let server = Server::new().await;
+
+    let (response, code) = server.create_dump_with_compression("brotli").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `compression`: Invalid dump compression `brotli`. Expected `gzip`, `zstd` or `none`, optionally followed by a level, e.g. `gzip:9` with a level between 0 and 9 or `zstd:3` with a level between 1 and 22.",
+      "code": "invalid_dump_compression",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_dump_compression"
+    }
+    "###);
+
+    let (response, code) = server.create_dump_with_compression("zstd:23").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_dump_compression""###);
+    let (response, code) = server.create_dump_with_compression("none:1").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_dump_compression""###);
+}
+
+#[actix_rt::test]
+async fn import_dump_at_runtime_errors() {
+    let server = Server::new().await;
+
+    let (response, code) = server.import_dump(json!({})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `dumpUid`",
+      "code": "missing_dump_uid",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#missing_dump_uid"
+    }
+    "###);
+
+    let (response, code) = server.import_dump(json!({ "dumpUid": "../data.ms" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Dump uid `../data.ms` is invalid. It should only contain alphanumeric characters, hyphens (-) and underscores (_).",
+      "code": "invalid_dump_uid",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_dump_uid"
+    }
+    "###);
+
+    let (response, code) = server.import_dump(json!({ "dumpUid": "20240101-000000000" })).await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Dump `20240101-000000000` not found.",
+      "code": "dump_not_found",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#dump_not_found"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn export_and_import_an_index() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let (task, _) = index.add_documents(json!([{ "id": 1, "title": "Carol" }]), None).await;
+    index.wait_task(task.uid()).await;
+    let (task, _) = index.update_settings(json!({ "filterableAttributes": ["title"] })).await;
+    index.wait_task(task.uid()).await;
+    let (task, _) = server.index("other").create(None).await;
+    server.wait_task(task.uid()).await;
+
+    let (task, code) = index.export().await;
+    snapshot!(code, @"202 Accepted");
+    let task = index.wait_task(task.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+    snapshot!(task["type"], @r###""indexExport""###);
+    snapshot!(task["indexUid"], @r###""test""###);
+    let dump_uid = task["details"]["dumpUid"].as_str().unwrap().to_string();
+
+    // the exported index can be imported under another uid
+    let copy = server.index("copy");
+    let (task, code) = copy.import(json!({ "dumpUid": dump_uid })).await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(task.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+
+    let (documents, _) = copy.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(json_string!(documents["results"]), @r###"
+    [
+      {
+        "id": 1,
+        "title": "Carol"
+      }
+    ]
+    "###);
+    let (settings, _) = copy.settings().await;
+    snapshot!(settings["filterableAttributes"], @r###"["title"]"###);
+
+    // only the exported index is in the dump
+    let (task, _) = index.delete().await;
+    server.wait_task(task.uid()).await;
+    let (task, _) = server.index("other").delete().await;
+    server.wait_task(task.uid()).await;
+    let (task, _) = server.import_dump(json!({ "dumpUid": dump_uid })).await;
+    let task = server.wait_task(task.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+    let (_, code) = index.get().await;
+    snapshot!(code, @"200 OK");
+    let (_, code) = server.index("other").get().await;
+    snapshot!(code, @"404 Not Found");
+}
+
+#[actix_rt::test]
+async fn import_a_dump_of_multiple_indexes_into_an_index() {
+    let server = Server::new().await;
+    let (task, _) = server.index("a").create(None).await;
+    server.wait_task(task.uid()).await;
+    let (task, _) = server.index("b").create(None).await;
+    server.wait_task(task.uid()).await;
+    let (task, _) = server.create_dump().await;
+    let task = server.wait_task(task.uid()).await;
+    let dump_uid = task["details"]["dumpUid"].as_str().unwrap().to_string();
+
+    let (task, _) = server.index("c").import(json!({ "dumpUid": dump_uid })).await;
+    let task = server.wait_task(task.uid()).await;
+    snapshot!(task["status"], @r###""failed""###);
+    snapshot!(task["error"]["code"], @r###""invalid_dump_uid""###);
+    let message = task["error"]["message"].as_str().unwrap().replace(&dump_uid, "[dumpUid]");
+    snapshot!(message, @"Dump `[dumpUid]` contains 2 indexes. Only a dump of a single index can be imported into an index.");
+    let (_, code) = server.index("c").get().await;
+    snapshot!(code, @"404 Not Found");
+}
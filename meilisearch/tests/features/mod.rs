@@ -1,3 +1,4 @@
+use actix_web::test;
 use meilisearch::Opt;
 use tempfile::TempDir;
 
@@ -136,6 +137,37 @@ async fn experimental_feature_metrics() {
     meili_snap::snapshot!(response, @"null");
 }
 
+#[actix_rt::test]
+async fn metrics_report_the_searches_the_indexing_and_the_indexes_size() {
+    let dir = TempDir::new().unwrap();
+    let enable_metrics = Opt { experimental_enable_metrics: true, ..default_settings(dir.path()) };
+    let server = Server::new_with_options(enable_metrics).await.unwrap();
+    let index = server.index("metrics");
+    index.add_documents(json!([{ "id": 1 }, { "id": 2 }]), None).await;
+    index.wait_task(0).await;
+    index.search(json!({ "q": "" }), |response, code| assert_eq!(code, 200, "{}", response)).await;
+
+    let app = server.init_web_app().await;
+    let req = test::TestRequest::get().uri("/metrics").to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 200);
+    let metrics = String::from_utf8(test::read_body(res).await.to_vec()).unwrap();
+
+    for expected in [
+        r#"meilisearch_search_latency_seconds_count{index="metrics"}"#,
+        r#"meilisearch_http_responses_total{method="POST",path="/indexes/metrics/search",status="200"}"#,
+        r#"meilisearch_index_db_size_bytes{index="metrics"}"#,
+        r#"meilisearch_index_used_db_size_bytes{index="metrics"}"#,
+        r#"meilisearch_index_map_size_bytes{index="metrics"}"#,
+        "meilisearch_task_queue_depth ",
+        "meilisearch_indexed_documents_total ",
+        "meilisearch_processed_batches_total ",
+        "meilisearch_batch_processing_seconds_total ",
+    ] {
+        assert!(metrics.contains(expected), "`{expected}` is missing from:\n{metrics}");
+    }
+}
+
 #[actix_rt::test]
 async fn errors() {
     let server = Server::new().await;
@@ -179,3 +211,92 @@ async fn errors() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn autobatching_limits() {
+    let server = Server::new().await;
+
+    let (response, code) = server.get_autobatching().await;
+
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
+    {
+      "enabled": true,
+      "maxBatchedTasks": 18446744073709551615,
+      "maxBatchedPayloadSize": null
+    }
+    "###);
+
+    let (response, code) = server
+        .set_autobatching(json!({"maxBatchedTasks": 10, "maxBatchedPayloadSize": 1024}))
+        .await;
+
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
+    {
+      "enabled": true,
+      "maxBatchedTasks": 10,
+      "maxBatchedPayloadSize": 1024
+    }
+    "###);
+
+    // sending null resets the payload size limit
+    let (response, code) =
+        server.set_autobatching(json!({"enabled": false, "maxBatchedPayloadSize": null})).await;
+
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
+    {
+      "enabled": false,
+      "maxBatchedTasks": 10,
+      "maxBatchedPayloadSize": null
+    }
+    "###);
+
+    let (response, code) = server.get_autobatching().await;
+
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
+    {
+      "enabled": false,
+      "maxBatchedTasks": 10,
+      "maxBatchedPayloadSize": null
+    }
+    "###);
+
+    // a limit of zero would prevent any batch from being built
+    let (response, code) = server.set_autobatching(json!({"maxBatchedTasks": 0})).await;
+
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
+    {
+      "message": "Invalid value: `maxBatchedTasks` must be greater than 0.",
+      "code": "invalid_autobatching_limits",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_autobatching_limits"
+    }
+    "###);
+
+    let (response, code) = server.set_autobatching(json!({"maxBatchedPayloadSize": 0})).await;
+
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
+    {
+      "message": "Invalid value: `maxBatchedPayloadSize` must be greater than 0.",
+      "code": "invalid_autobatching_limits",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_autobatching_limits"
+    }
+    "###);
+
+    let (response, code) = server.get_autobatching().await;
+
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
+    {
+      "enabled": false,
+      "maxBatchedTasks": 10,
+      "maxBatchedPayloadSize": null
+    }
+    "###);
+}
@@ -1,5 +1,6 @@
 use actix_web::http::header::ContentType;
 use actix_web::test;
+use byte_unit::Byte;
 use http::header::ACCEPT_ENCODING;
 use meili_snap::{json_string, snapshot};
 use meilisearch::Opt;
@@ -44,7 +45,11 @@ async fn create_index_with_gzip_encoded_request() {
 
 #[actix_rt::test]
 async fn create_index_with_gzip_encoded_request_and_receiving_brotli_encoded_response() {
-    let server = Server::new().await;
+    let temp = tempfile::tempdir().unwrap();
+    // the response is smaller than the default minimum size of the compressed responses.
+    let options =
+        Opt { http_compression_min_size: Byte::from_bytes(0), ..default_settings(temp.path()) };
+    let server = Server::new_with_options(options).await.unwrap();
     let app = server.init_web_app().await;
 
     let body = serde_json::to_string(&json!({
@@ -201,6 +206,74 @@ async fn error_create_with_invalid_index_uid() {
     "###);
 }
 
+#[actix_rt::test]
+async fn create_index_with_composite_primary_key() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (task, code) =
+        server.create_index(json!({ "uid": "test", "primaryKey": ["tenant_id", "sku"] })).await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(task.uid()).await;
+    snapshot!(json_string!(response["details"]), @r###"
+    {
+      "primaryKey": [
+        "tenant_id",
+        "sku"
+      ]
+    }
+    "###);
+    let (response, _) = index.get().await;
+    snapshot!(json_string!(response["primaryKey"]), @r###"
+    [
+      "tenant_id",
+      "sku"
+    ]
+    "###);
+
+    let documents = json!([
+        { "tenant_id": "acme", "sku": 1, "title": "Anvil" },
+        { "tenant_id": "initech", "sku": 1, "title": "Stapler" },
+    ]);
+    let (task, _) = index.add_documents(documents, None).await;
+    let response = index.wait_task(task.uid()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+
+    let (document, code) = server.service.get("/indexes/test/documents/initech:1").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(document), @r###"
+    {
+      "tenant_id": "initech",
+      "sku": 1,
+      "title": "Stapler"
+    }
+    "###);
+
+    // the values are joined by colons, so they can't contain any
+    let (task, _) = index.add_documents(json!({ "tenant_id": "a:b", "sku": 2 }), None).await;
+    let response = index.wait_task(task.uid()).await;
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "Document identifier `\"a:b\"` is invalid. The values of the fields of a composite primary key can't contain colons (:).",
+      "code": "invalid_document_id",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_id"
+    }
+    "###);
+
+    let (response, code) =
+        server.create_index(json!({ "uid": "other", "primaryKey": ["tenant_id", ""] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.primaryKey`: A composite primary key must be a non-empty list of non-empty fields.",
+      "code": "invalid_index_primary_key",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_index_primary_key"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn send_task_id() {
     let temp = tempfile::tempdir().unwrap();
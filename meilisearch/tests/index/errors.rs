@@ -99,12 +99,12 @@ async fn create_index_bad_primary_key() {
     let server = Server::new().await;
 
     let (response, code) = server
-        .create_index(json!({ "uid": "doggo", "primaryKey": ["the", "best", "doggo"] }))
+        .create_index(json!({ "uid": "doggo", "primaryKey": 42 }))
         .await;
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Invalid value type at `.primaryKey`: expected a string, but found an array: `[\"the\",\"best\",\"doggo\"]`",
+      "message": "Invalid value type at `.primaryKey`: expected a string or an array, but found a positive integer: `42`",
       "code": "invalid_index_primary_key",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_index_primary_key"
@@ -150,11 +150,11 @@ async fn update_index_bad_primary_key() {
     let server = Server::new().await;
     let index = server.index("doggo");
 
-    let (response, code) = index.update_raw(json!({ "primaryKey": ["doggo"] })).await;
+    let (response, code) = index.update_raw(json!({ "primaryKey": 42 })).await;
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Invalid value type at `.primaryKey`: expected a string, but found an array: `[\"doggo\"]`",
+      "message": "Invalid value type at `.primaryKey`: expected a string or an array, but found a positive integer: `42`",
       "code": "invalid_index_primary_key",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_index_primary_key"
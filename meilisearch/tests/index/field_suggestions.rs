@@ -0,0 +1,66 @@
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn field_suggestions() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (_, code) = index
+        .update_settings(json!({
+            "filterableAttributes": ["genre", "color", "missing"],
+            "sortableAttributes": ["price"],
+        }))
+        .await;
+    assert_eq!(code, 202);
+    index.wait_task(0).await;
+
+    let documents = json!([
+        { "id": 1, "genre": "horror", "color": "red", "price": 10 },
+        { "id": 2, "genre": "comedy", "color": "blue", "price": 20 },
+    ]);
+    let (_, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202);
+    index.wait_task(1).await;
+
+    // without any search, only the fields that are in no document are unused
+    let (response, code) = index.field_suggestions().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(
+        response,
+        json!({
+            "analyzedSearches": 0,
+            "filterableAttributes": { "add": [], "unused": ["missing"] },
+            "sortableAttributes": { "add": [], "unused": [] },
+            "searchableAttributes": { "add": [], "unused": [] },
+        })
+    );
+
+    let (response, code) =
+        index.search_post(json!({ "filter": "genre = horror", "sort": ["price:asc"] })).await;
+    assert_eq!(code, 200, "{}", response);
+    // the facets of a search must be filterable
+    let (response, code) = index.search_post(json!({ "facets": ["id"] })).await;
+    assert_eq!(code, 400, "{}", response);
+
+    let (response, code) = index.field_suggestions().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(
+        response,
+        json!({
+            "analyzedSearches": 2,
+            "filterableAttributes": { "add": ["id"], "unused": ["color", "missing"] },
+            "sortableAttributes": { "add": [], "unused": [] },
+            "searchableAttributes": { "add": [], "unused": [] },
+        })
+    );
+}
+
+#[actix_rt::test]
+async fn field_suggestions_unexisting_index() {
+    let server = Server::new().await;
+    let (response, code) = server.index("test").field_suggestions().await;
+
+    assert_eq!(code, 404, "{}", response);
+    assert_eq!(response["code"], "index_not_found");
+}
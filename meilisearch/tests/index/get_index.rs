@@ -21,7 +21,31 @@ async fn create_and_get_index() {
     assert!(response.get("updatedAt").is_some());
     assert_eq!(response["createdAt"], response["updatedAt"]);
     assert_eq!(response["primaryKey"], Value::Null);
-    assert_eq!(response.as_object().unwrap().len(), 4);
+    assert!(response["lastUpdate"].is_string());
+    // a single update isn't enough to estimate the frequency of the updates
+    assert_eq!(response["updateFrequencyEstimate"], Value::Null);
+    assert_eq!(response.as_object().unwrap().len(), 6);
+}
+
+#[actix_rt::test]
+async fn get_index_update_cadence() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+    index.add_documents(json!([{ "id": 1 }]), None).await;
+    index.add_documents(json!([{ "id": 2 }]), None).await;
+    // the failed tasks don't update the index
+    index.add_documents(json!([{ "id": "b@d" }]), None).await;
+    let task = index.wait_task(3).await;
+    assert_eq!(task["status"], "failed");
+
+    let (response, code) = index.get().await;
+    assert_eq!(code, 200);
+
+    let (task, _) = index.get_task(2).await;
+    assert_eq!(response["lastUpdate"], task["finishedAt"]);
+    let frequency = response["updateFrequencyEstimate"].as_str().unwrap();
+    assert!(frequency.starts_with("PT"), "{frequency}");
 }
 
 #[actix_rt::test]
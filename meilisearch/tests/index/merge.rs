@@ -0,0 +1,146 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::{GetAllDocumentsOptions, Server};
+use crate::json;
+
+async fn create_indexes(server: &Server) {
+    let index = server.index("movies");
+    index
+        .add_documents(
+            json!([
+                { "id": 1, "title": "Carol", "genre": "drama" },
+                { "id": 2, "title": "Dune", "genre": "scifi" },
+            ]),
+            None,
+        )
+        .await;
+    index.wait_task(0).await;
+
+    let staging = server.index("staging");
+    staging
+        .add_documents(
+            json!([
+                { "id": 2, "title": "Dune: Part One", "year": 2021 },
+                { "id": 3, "title": "Arrival", "genre": "scifi" },
+            ]),
+            None,
+        )
+        .await;
+    staging.wait_task(1).await;
+}
+
+async fn merged_documents(server: &Server, on_conflict: &str) -> crate::common::Value {
+    let (response, code) = server
+        .service
+        .post("/indexes/movies/merge", json!({ "source": "staging", "onConflict": on_conflict }))
+        .await;
+    assert_eq!(code, 202, "{response}");
+    let response = server.wait_task(response.uid()).await;
+    assert_eq!(response["status"], "succeeded", "{response}");
+    assert_eq!(response["type"], "indexMerge");
+    assert_eq!(response["details"]["sourceIndexUid"], "staging");
+    assert_eq!(response["details"]["onConflict"], on_conflict);
+
+    let (documents, code) =
+        server.index("movies").get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(code, 200, "{documents}");
+    documents["results"].clone().into()
+}
+
+#[actix_rt::test]
+async fn merge_replacing_the_conflicting_documents() {
+    let server = Server::new().await;
+    create_indexes(&server).await;
+
+    snapshot!(json_string!(merged_documents(&server, "replace").await), @r###"
+    [
+      {
+        "id": 1,
+        "title": "Carol",
+        "genre": "drama"
+      },
+      {
+        "id": 2,
+        "title": "Dune: Part One",
+        "year": 2021
+      },
+      {
+        "id": 3,
+        "title": "Arrival",
+        "genre": "scifi"
+      }
+    ]
+    "###);
+
+    // The source is left untouched.
+    let (stats, _) = server.index("staging").stats().await;
+    assert_eq!(stats["numberOfDocuments"], 2, "{stats}");
+}
+
+#[actix_rt::test]
+async fn merge_updating_the_conflicting_documents() {
+    let server = Server::new().await;
+    create_indexes(&server).await;
+
+    let documents = merged_documents(&server, "update").await;
+    assert_eq!(
+        documents[1],
+        json!({ "id": 2, "title": "Dune: Part One", "genre": "scifi", "year": 2021 })
+    );
+    assert_eq!(documents.as_array().unwrap().len(), 3, "{documents}");
+}
+
+#[actix_rt::test]
+async fn merge_skipping_the_conflicting_documents() {
+    let server = Server::new().await;
+    create_indexes(&server).await;
+
+    let documents = merged_documents(&server, "skip").await;
+    assert_eq!(documents[1], json!({ "id": 2, "title": "Dune", "genre": "scifi" }));
+    assert_eq!(documents[2]["id"], 3, "{documents}");
+    let task = server.get_task(2).await.0;
+    assert_eq!(task["details"]["mergedDocuments"], 1, "{task}");
+}
+
+#[actix_rt::test]
+async fn merge_errors() {
+    let server = Server::new().await;
+    create_indexes(&server).await;
+
+    let (response, code) = server.service.post("/indexes/movies/merge", json!({})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `source`",
+      "code": "missing_index_merge_source",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#missing_index_merge_source"
+    }
+    "###);
+
+    let (response, code) =
+        server.service.post("/indexes/movies/merge", json!({ "source": "movies" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Index `movies` can't be merged into itself.",
+      "code": "invalid_index_merge_source",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_index_merge_source"
+    }
+    "###);
+
+    let (response, code) = server
+        .service
+        .post("/indexes/movies/merge", json!({ "source": "staging", "onConflict": "keep" }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    assert_eq!(response["code"], "invalid_index_merge_on_conflict", "{response}");
+
+    let (response, code) =
+        server.service.post("/indexes/movies/merge", json!({ "source": "unknown" })).await;
+    assert_eq!(code, 202, "{response}");
+    let response = server.wait_task(response.uid()).await;
+    assert_eq!(response["status"], "failed", "{response}");
+    assert_eq!(response["error"]["code"], "index_not_found", "{response}");
+}
@@ -1,6 +1,10 @@
 mod create_index;
 mod delete_index;
 mod errors;
+mod field_suggestions;
 mod get_index;
+mod merge;
+mod previews;
+mod split;
 mod stats;
 mod update_index;
@@ -0,0 +1,103 @@
+use std::time::Duration;
+
+use tokio::time::sleep;
+
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn create_preview_with_different_settings() {
+    let server = Server::new().await;
+    let index = server.index("movies");
+    index
+        .add_documents(
+            json!([
+                { "id": 1, "title": "Carol", "genre": "drama" },
+                { "id": 2, "title": "Dune", "genre": "scifi" },
+            ]),
+            None,
+        )
+        .await;
+    index.wait_task(0).await;
+
+    let (response, code) = server
+        .service
+        .post(
+            "/indexes/movies/previews",
+            json!({ "uid": "movies-preview", "settings": { "searchableAttributes": ["genre"] }, "ttl": 3600 }),
+        )
+        .await;
+    assert_eq!(code, 202, "{response}");
+    let response = server.wait_task(response.uid()).await;
+    assert_eq!(response["status"], "succeeded", "{response}");
+    assert_eq!(response["type"], "indexPreviewCreation");
+    assert_eq!(response["indexUid"], "movies-preview");
+    assert_eq!(response["details"]["sourceIndexUid"], "movies");
+
+    // The preview holds the documents of its source with the new settings.
+    let preview = server.index("movies-preview");
+    preview
+        .search(json!({ "q": "drama" }), |response, code| {
+            assert_eq!(code, 200, "{response}");
+            assert_eq!(response["hits"][0]["id"], 1, "{response}");
+        })
+        .await;
+    preview
+        .search(json!({ "q": "dune" }), |response, code| {
+            assert_eq!(code, 200, "{response}");
+            assert_eq!(response["hits"], json!([]), "{response}");
+        })
+        .await;
+
+    // The source is left untouched.
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["searchableAttributes"], json!(["*"]));
+
+    let (response, code) = server.service.get("/indexes/movies/previews").await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["results"][0]["uid"], "movies-preview", "{response}");
+    assert_eq!(response["results"][0]["sourceIndexUid"], "movies", "{response}");
+}
+
+#[actix_rt::test]
+async fn expired_preview_is_deleted() {
+    let server = Server::new().await;
+    let index = server.index("movies");
+    index.create(None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = server
+        .service
+        .post("/indexes/movies/previews", json!({ "uid": "movies-preview", "ttl": 1 }))
+        .await;
+    assert_eq!(code, 202, "{response}");
+    let response = server.wait_task(response.uid()).await;
+    assert_eq!(response["status"], "succeeded", "{response}");
+
+    let preview = server.index("movies-preview");
+    for _ in 0..20 {
+        sleep(Duration::from_millis(500)).await;
+        let (_, code) = preview.get().await;
+        if code == 404 {
+            let (response, _) = server.service.get("/indexes/movies/previews").await;
+            assert_eq!(response["results"], json!([]), "{response}");
+            return;
+        }
+    }
+    panic!("The expired preview was not deleted");
+}
+
+#[actix_rt::test]
+async fn create_preview_bad_ttl() {
+    let server = Server::new().await;
+
+    let (response, code) =
+        server.service.post("/indexes/movies/previews", json!({ "ttl": 0 })).await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], "invalid_index_preview_ttl");
+
+    let (response, code) = server.service.post("/indexes/movies/previews", json!({})).await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], "invalid_index_preview_ttl");
+}
@@ -0,0 +1,138 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::{GetAllDocumentsOptions, Server};
+use crate::json;
+
+async fn create_index(server: &Server) {
+    let index = server.index("movies");
+    index
+        .add_documents(
+            json!([
+                { "id": 1, "title": "Carol", "genre": "drama" },
+                { "id": 2, "title": "Dune", "genre": "scifi" },
+                { "id": 3, "title": "Arrival", "genre": "scifi" },
+            ]),
+            None,
+        )
+        .await;
+    index.wait_task(0).await;
+    let (response, code) = index
+        .update_settings(json!({ "filterableAttributes": ["genre"], "stopWords": ["the"] }))
+        .await;
+    assert_eq!(code, 202, "{response}");
+    index.wait_task(1).await;
+}
+
+#[actix_rt::test]
+async fn split_the_matching_documents_into_a_new_index() {
+    let server = Server::new().await;
+    create_index(&server).await;
+
+    let (response, code) = server
+        .service
+        .post("/indexes/movies/split", json!({ "uid": "scifi", "filter": "genre = scifi" }))
+        .await;
+    assert_eq!(code, 202, "{response}");
+    let response = server.wait_task(response.uid()).await;
+    assert_eq!(response["status"], "succeeded", "{response}");
+    assert_eq!(response["type"], "indexSplit");
+    assert_eq!(response["indexUid"], "scifi");
+    assert_eq!(response["details"]["sourceIndexUid"], "movies");
+    assert_eq!(response["details"]["copiedDocuments"], 2, "{response}");
+
+    let scifi = server.index("scifi");
+    let (documents, code) = scifi.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(code, 200, "{documents}");
+    snapshot!(json_string!(documents["results"]), @r###"
+    [
+      {
+        "id": 2,
+        "title": "Dune",
+        "genre": "scifi"
+      },
+      {
+        "id": 3,
+        "title": "Arrival",
+        "genre": "scifi"
+      }
+    ]
+    "###);
+
+    // The settings are copied along with the documents.
+    let (settings, _) = scifi.settings().await;
+    assert_eq!(settings["filterableAttributes"], json!(["genre"]), "{settings}");
+    assert_eq!(settings["stopWords"], json!(["the"]), "{settings}");
+    let (stats, _) = scifi.stats().await;
+    assert_eq!(stats["numberOfDocuments"], 2, "{stats}");
+
+    // The source is left untouched.
+    let (stats, _) = server.index("movies").stats().await;
+    assert_eq!(stats["numberOfDocuments"], 3, "{stats}");
+}
+
+#[actix_rt::test]
+async fn split_errors() {
+    let server = Server::new().await;
+    create_index(&server).await;
+
+    let (response, code) =
+        server.service.post("/indexes/movies/split", json!({ "uid": "scifi" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `filter`",
+      "code": "missing_index_split_filter",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#missing_index_split_filter"
+    }
+    "###);
+
+    let (response, code) = server
+        .service
+        .post("/indexes/movies/split", json!({ "uid": "movies", "filter": "genre = scifi" }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Index `movies` can't be split into itself.",
+      "code": "invalid_index_uid",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_index_uid"
+    }
+    "###);
+
+    let (response, code) = server
+        .service
+        .post("/indexes/movies/split", json!({ "uid": "scifi", "filter": "genre =" }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    assert_eq!(response["code"], "invalid_index_split_filter", "{response}");
+
+    // The filter is only checked against the filterable attributes when the task is processed,
+    // and the new index isn't created when it fails.
+    let (response, code) = server
+        .service
+        .post("/indexes/movies/split", json!({ "uid": "scifi", "filter": "title = Dune" }))
+        .await;
+    assert_eq!(code, 202, "{response}");
+    let response = server.wait_task(response.uid()).await;
+    assert_eq!(response["status"], "failed", "{response}");
+    assert_eq!(response["error"]["code"], "invalid_index_split_filter", "{response}");
+    let (response, code) = server.index("scifi").get().await;
+    assert_eq!(code, 404, "{response}");
+
+    let (response, code) = server
+        .service
+        .post("/indexes/movies/split", json!({ "uid": "movies-copy", "filter": "genre = scifi" }))
+        .await;
+    assert_eq!(code, 202, "{response}");
+    server.wait_task(response.uid()).await;
+    let (response, code) = server
+        .service
+        .post("/indexes/movies/split", json!({ "uid": "movies-copy", "filter": "genre = drama" }))
+        .await;
+    assert_eq!(code, 202, "{response}");
+    let response = server.wait_task(response.uid()).await;
+    assert_eq!(response["status"], "failed", "{response}");
+    assert_eq!(response["error"]["code"], "index_already_exists", "{response}");
+}
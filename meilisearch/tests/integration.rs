@@ -1,11 +1,14 @@
 mod auth;
 mod common;
 mod dashboard;
+mod dead_letters;
 mod documents;
 mod dumps;
 mod features;
 mod index;
 mod logs;
+mod proxy;
+mod schedules;
 mod search;
 mod settings;
 mod snapshot;
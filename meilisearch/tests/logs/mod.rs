@@ -1,4 +1,5 @@
 mod error;
+mod request_id;
 
 use std::rc::Rc;
 use std::str::FromStr;
@@ -47,6 +48,10 @@ async fn basic_test_log_stream_route() {
         server.service.options.clone(),
         (route_layer_handle, stderr_layer_handle),
         analytics::MockAnalytics::new(&server.service.options),
+        server.service.field_usage.clone().into(),
+        server.service.query_log.clone().into(),
+        server.service.search_queue.clone().into(),
+        server.service.wasm_middlewares.clone().into(),
         true,
     ))
     .await;
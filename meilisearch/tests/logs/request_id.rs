@@ -0,0 +1,46 @@
+use actix_web::test;
+use meili_snap::*;
+
+use crate::common::Server;
+
+#[actix_rt::test]
+async fn request_id_is_sent_back() {
+    let server = Server::new().await;
+    let app = server.init_web_app().await;
+
+    let req = test::TestRequest::get()
+        .uri("/indexes/doggo")
+        .insert_header(("X-Request-Id", "my-request-42"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 404);
+    assert_eq!(res.headers().get("x-request-id").unwrap(), "my-request-42");
+    let response: serde_json::Value = test::read_body_json(res).await;
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Index `doggo` not found.",
+      "code": "index_not_found",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#index_not_found",
+      "requestId": "my-request-42"
+    }
+    "###);
+
+    // a generated id is only sent back in the header
+    let req = test::TestRequest::get().uri("/indexes/doggo").to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 404);
+    assert!(res.headers().contains_key("x-request-id"));
+    let response: serde_json::Value = test::read_body_json(res).await;
+    assert!(response.get("requestId").is_none(), "{}", response);
+
+    // an invalid id is replaced by a generated one
+    let req = test::TestRequest::get()
+        .uri("/indexes/doggo")
+        .insert_header(("X-Request-Id", "not a valid id"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_ne!(res.headers().get("x-request-id").unwrap(), "not a valid id");
+    let response: serde_json::Value = test::read_body_json(res).await;
+    assert!(response.get("requestId").is_none(), "{}", response);
+}
@@ -0,0 +1,161 @@
+use meilisearch::Opt;
+use tempfile::TempDir;
+
+use crate::common::{default_settings, Server};
+use crate::json;
+
+/// Spawns two backends, one holding the `movies*` indexes and one holding everything else, and a
+/// proxy routing the requests between them.
+async fn proxy_and_backends() -> (TempDir, Server, Server, Server) {
+    let movies = Server::new().await;
+    let others = Server::new().await;
+    let (dir, proxy) = proxy_of(&movies, &others).await;
+    (dir, proxy, movies, others)
+}
+
+/// Spawns a proxy routing the `movies*` indexes to the first backend and everything else to the
+/// second one.
+async fn proxy_of(movies: &Server, others: &Server) -> (TempDir, Server) {
+    let (movies_url, others_url) = (movies.listen(), others.listen());
+
+    let dir = TempDir::new().unwrap();
+    let options = Opt {
+        experimental_proxy_backends: format!("movies*={movies_url},*={others_url}")
+            .parse()
+            .unwrap(),
+        ..default_settings(dir.path())
+    };
+    let proxy = Server::new_with_options(options).await.unwrap();
+
+    (dir, proxy)
+}
+
+#[actix_rt::test]
+async fn proxy_routes_the_indexes_to_their_backend() {
+    let (_dir, proxy, movies, others) = proxy_and_backends().await;
+
+    let (task, code) = proxy.create_index(json!({ "uid": "movies", "primaryKey": "id" })).await;
+    assert_eq!(code, 202, "{task}");
+    movies.wait_task(task.uid()).await;
+    let (task, code) = proxy.create_index(json!({ "uid": "books", "primaryKey": "id" })).await;
+    assert_eq!(code, 202, "{task}");
+    others.wait_task(task.uid()).await;
+
+    let (task, code) = proxy
+        .service
+        .post("/indexes/movies/documents", json!([{ "id": 1, "title": "Carol" }]))
+        .await;
+    assert_eq!(code, 202, "{task}");
+    movies.wait_task(task.uid()).await;
+    let (task, code) = proxy
+        .service
+        .post(
+            "/indexes/books/documents",
+            json!([{ "id": 1, "title": "Dune" }, { "id": 2, "title": "Emma" }]),
+        )
+        .await;
+    assert_eq!(code, 202, "{task}");
+    others.wait_task(task.uid()).await;
+
+    // Each backend only holds its own indexes.
+    let (_, code) = movies.index("movies").get().await;
+    assert_eq!(code, 200);
+    let (_, code) = movies.index("books").get().await;
+    assert_eq!(code, 404);
+    let (_, code) = others.index("books").get().await;
+    assert_eq!(code, 200);
+
+    let (response, code) = proxy.service.get("/indexes/books/documents/2").await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["title"], "Emma");
+
+    let (response, code) = proxy.service.get("/indexes").await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["total"], 2);
+    assert_eq!(response["results"][0]["uid"], "books");
+    assert_eq!(response["results"][1]["uid"], "movies");
+
+    let (response, code) = proxy.stats().await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["indexes"]["movies"]["numberOfDocuments"], 1);
+    assert_eq!(response["indexes"]["books"]["numberOfDocuments"], 2);
+
+    let (response, code) = proxy.tasks().await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["total"], 4);
+    assert_eq!(response["results"].as_array().unwrap().len(), 4);
+
+    // Both backends have a task 0, the proxy can't tell which one is requested.
+    let (response, code) = proxy.get_task(0).await;
+    assert_eq!(code, 400, "{response}");
+    let (response, code) = proxy.service.get("/tasks/0?indexUid=books").await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["indexUid"], "books");
+
+    let (response, code) = proxy
+        .multi_search(json!({"queries": [
+            { "indexUid": "books", "q": "emma" },
+            { "indexUid": "movies", "q": "carol" },
+        ]}))
+        .await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["results"][0]["indexUid"], "books");
+    assert_eq!(response["results"][0]["hits"][0]["id"], 2);
+    assert_eq!(response["results"][1]["indexUid"], "movies");
+    assert_eq!(response["results"][1]["hits"][0]["id"], 1);
+}
+
+#[actix_rt::test]
+async fn proxy_rejects_the_instance_wide_routes() {
+    let (_dir, proxy, _movies, _others) = proxy_and_backends().await;
+
+    let (response, code) = proxy.create_dump().await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], "not_available_in_proxy_mode");
+
+    let (response, code) = proxy.service.get("/health").await;
+    assert_eq!(code, 200, "{response}");
+}
+
+#[actix_rt::test]
+async fn proxy_merges_the_partial_failures_of_the_backends() {
+    let (_dir, proxy, _movies, others) = proxy_and_backends().await;
+
+    let (task, code) = proxy.create_index(json!({ "uid": "books", "primaryKey": "id" })).await;
+    assert_eq!(code, 202, "{task}");
+    others.wait_task(task.uid()).await;
+
+    let (response, code) = proxy
+        .multi_search(json!({
+            "queries": [
+                { "indexUid": "books", "q": "emma" },
+                { "indexUid": "movies", "q": "carol" },
+            ],
+            "allowPartialFailures": true,
+        }))
+        .await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["status"], "partiallyFailed", "{response}");
+    assert_eq!(response["results"][0]["indexUid"], "books", "{response}");
+    assert_eq!(response["results"][1]["error"]["code"], "index_not_found", "{response}");
+}
+
+#[actix_rt::test]
+async fn proxy_removes_the_key_created_on_some_backends_only() {
+    let mut movies = Server::new_auth().await;
+    // a backend without master key can't create keys
+    let others = Server::new().await;
+    let (_dir, mut proxy) = proxy_of(&movies, &others).await;
+
+    proxy.use_api_key("MASTER_KEY");
+    let (response, code) = proxy
+        .add_api_key(json!({ "actions": ["search"], "indexes": ["*"], "expiresAt": null }))
+        .await;
+    assert_ne!(code, 201, "{response}");
+
+    movies.use_api_key("MASTER_KEY");
+    let (response, code) = movies.list_api_keys("").await;
+    assert_eq!(code, 200, "{response}");
+    // only the default keys are left
+    assert_eq!(response["total"], 2, "{response}");
+}
@@ -0,0 +1,175 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn create_and_delete_schedule() {
+    let server = Server::new().await;
+
+    let (response, code) = server
+        .create_schedule(json!({ "type": "snapshotCreation", "interval": 86400, "startAt": "2100-01-01T02:00:00Z" }))
+        .await;
+    snapshot!(code, @"201 Created");
+    snapshot!(json_string!(response, { ".createdAt" => "[date]" }), @r###"
+    {
+      "uid": 0,
+      "type": "snapshotCreation",
+      "interval": 86400,
+      "nextRunAt": "2100-01-01T02:00:00Z",
+      "lastTaskUid": null,
+      "createdAt": "[date]"
+    }
+    "###);
+
+    let (response, code) = server.get_schedules().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".results[].createdAt" => "[date]" }), @r###"
+    {
+      "results": [
+        {
+          "uid": 0,
+          "type": "snapshotCreation",
+          "interval": 86400,
+          "nextRunAt": "2100-01-01T02:00:00Z",
+          "lastTaskUid": null,
+          "createdAt": "[date]"
+        }
+      ]
+    }
+    "###);
+
+    let (_response, code) = server.delete_schedule(0).await;
+    snapshot!(code, @"200 OK");
+
+    let (response, code) = server.get_schedule(0).await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Schedule `0` not found.",
+      "code": "schedule_not_found",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#schedule_not_found"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn due_schedule_enqueues_a_task() {
+    let server = Server::new().await;
+
+    let (response, code) = server
+        .create_schedule(json!({
+            "type": "documentDeletion",
+            "indexUid": "doggos",
+            "filter": "age > 12",
+            "interval": 3600,
+            "startAt": "2000-01-01T00:00:00Z",
+        }))
+        .await;
+    snapshot!(code, @"201 Created");
+    snapshot!(response["filter"], @r###""age > 12""###);
+
+    // the schedule is already due, its task is enqueued right away.
+    let response = server.wait_task(0).await;
+    snapshot!(response["type"], @r###""documentDeletion""###);
+    snapshot!(response["status"], @r###""failed""###);
+
+    let (response, code) = server.get_schedule(0).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["lastTaskUid"], @"0");
+}
+
+#[actix_rt::test]
+async fn create_schedule_bad_request() {
+    let server = Server::new().await;
+
+    let (response, code) =
+        server.create_schedule(json!({ "type": "dumpCreation", "interval": 60 })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `dumpCreation` at `.type`: expected one of `snapshotCreation`, `documentDeletion`",
+      "code": "invalid_schedule_type",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_schedule_type"
+    }
+    "###);
+
+    let (response, code) = server
+        .create_schedule(
+            json!({ "type": "documentDeletion", "filter": "age > 12", "interval": 60 }),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The `indexUid` field is required by the `documentDeletion` schedule type.",
+      "code": "missing_index_uid",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#missing_index_uid"
+    }
+    "###);
+
+    let (response, code) =
+        server.create_schedule(json!({ "type": "snapshotCreation", "interval": 0 })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The interval of a schedule must be at least one second.",
+      "code": "invalid_schedule_interval",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_schedule_interval"
+    }
+    "###);
+
+    let (response, code) = server.get_schedule(0).await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(response["code"], @r###""schedule_not_found""###);
+}
+
+#[actix_rt::test]
+async fn schedules_require_the_actions_of_their_tasks() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let (response, code) = server
+        .add_api_key(json!({
+            "actions": ["schedules.create", "documents.delete"],
+            "indexes": ["products"],
+            "expiresAt": null,
+        }))
+        .await;
+    snapshot!(code, @"201 Created");
+    let key = response["key"].as_str().unwrap().to_string();
+    server.use_api_key(&key);
+
+    // the key can't create the snapshots itself
+    let (response, code) =
+        server.create_schedule(json!({ "type": "snapshotCreation", "interval": 60 })).await;
+    snapshot!(code, @"403 Forbidden");
+    snapshot!(response["code"], @r###""invalid_api_key""###);
+
+    // nor delete the documents of the other indexes
+    let (response, code) = server
+        .create_schedule(json!({
+            "type": "documentDeletion",
+            "indexUid": "movies",
+            "filter": "age > 12",
+            "interval": 60,
+        }))
+        .await;
+    snapshot!(code, @"403 Forbidden");
+    snapshot!(response["code"], @r###""invalid_api_key""###);
+
+    let (response, code) = server
+        .create_schedule(json!({
+            "type": "documentDeletion",
+            "indexUid": "products",
+            "filter": "age > 12",
+            "interval": 60,
+        }))
+        .await;
+    snapshot!(code, @"201 Created");
+    snapshot!(response["indexUid"], @r###""products""###);
+}
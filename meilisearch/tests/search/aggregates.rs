@@ -0,0 +1,90 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::{Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        { "id": 1, "category": "shirt", "price": 20, "stock": 3 },
+        { "id": 2, "category": "shirt", "price": 30, "stock": [1, 4] },
+        { "id": 3, "category": "shoes", "price": 55.5 },
+        { "id": 4, "category": "hat", "price": "unknown", "stock": 10 },
+    ])
+});
+
+async fn create_index(server: &Server) {
+    let index = server.index("test");
+    index.update_settings(json!({ "filterableAttributes": ["category", "price", "stock"] })).await;
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(1).await;
+}
+
+#[actix_rt::test]
+async fn aggregates_of_all_the_candidates() {
+    let server = Server::new().await;
+    create_index(&server).await;
+    let index = server.index("test");
+
+    // the aggregates ignore the pagination and the non-numeric values
+    let (response, code) =
+        index.search_post(json!({ "aggregates": ["price", "stock"], "limit": 1 })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["aggregates"]), @r###"
+    {
+      "price": {
+        "count": 3,
+        "sum": 105.5,
+        "avg": 35.166666666666664,
+        "min": 20.0,
+        "max": 55.5
+      },
+      "stock": {
+        "count": 4,
+        "sum": 18.0,
+        "avg": 4.5,
+        "min": 1.0,
+        "max": 10.0
+      }
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({ "aggregates": ["price", "stock"], "filter": "category = shoes" }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["aggregates"]), @r###"
+    {
+      "price": {
+        "count": 1,
+        "sum": 55.5,
+        "avg": 55.5,
+        "min": 55.5,
+        "max": 55.5
+      }
+    }
+    "###);
+
+    let (response, code) = index.search_get("aggregates=stock&filter=category%20%3D%20shirt").await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(response["aggregates"]["stock"]["sum"], json!(8.0), "{response}");
+
+    let (response, code) = index.search_post(json!({})).await;
+    snapshot!(code, @"200 OK");
+    assert!(response.get("aggregates").is_none(), "{response}");
+}
+
+#[actix_rt::test]
+async fn aggregates_errors() {
+    let server = Server::new().await;
+    create_index(&server).await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({ "aggregates": ["id"] })).await;
+    snapshot!(code, @"400 Bad Request");
+    assert_eq!(response["code"], "invalid_search_aggregates", "{response}");
+
+    let (response, code) = index.search_post(json!({ "aggregates": "price" })).await;
+    snapshot!(code, @"400 Bad Request");
+    assert_eq!(response["code"], "invalid_search_aggregates", "{response}");
+}
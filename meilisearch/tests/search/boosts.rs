@@ -0,0 +1,84 @@
+use meili_snap::snapshot;
+use once_cell::sync::Lazy;
+
+use crate::common::{Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        { "id": 1, "text": "the quick brown fox", "sponsored": false },
+        { "id": 2, "text": "the quick brown", "sponsored": false },
+        { "id": 3, "text": "the quick", "sponsored": true },
+    ])
+});
+
+async fn create_index(server: &Server) {
+    let index = server.index("test");
+    index
+        .update_settings(json!({
+            "filterableAttributes": ["sponsored"],
+            "rankingRules": ["words"],
+        }))
+        .await;
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(1).await;
+}
+
+fn ids(response: &Value) -> Vec<serde_json::Value> {
+    response["hits"].as_array().unwrap().iter().map(|hit| hit["id"].clone()).collect()
+}
+
+#[actix_rt::test]
+async fn boosts_multiply_the_ranking_score() {
+    let server = Server::new().await;
+    create_index(&server).await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({ "q": "quick brown fox" })).await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(ids(&response), vec![json!(1), json!(2), json!(3)], "{response}");
+
+    let (response, code) = index
+        .search_post(json!({
+            "q": "quick brown fox",
+            "boosts": [{ "filter": "sponsored = true", "factor": 2.5 }],
+            "showRankingScore": true,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(ids(&response), vec![json!(1), json!(3), json!(2)], "{response}");
+    let score = response["hits"][1]["_rankingScore"].as_f64().unwrap();
+    assert!((score - 2.5 / 3.0).abs() < f64::EPSILON, "{response}");
+
+    let (response, code) = index
+        .search_post(json!({
+            "q": "quick brown fox",
+            "boosts": [{ "filter": "sponsored = true", "factor": 2.5 }],
+            "offset": 1,
+            "limit": 1,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(ids(&response), vec![json!(3)], "{response}");
+}
+
+#[actix_rt::test]
+async fn boosts_errors() {
+    let server = Server::new().await;
+    create_index(&server).await;
+    let index = server.index("test");
+
+    let invalid_boosts = [
+        json!({ "filter": "sponsored = true", "factor": 0 }),
+        json!({ "filter": "sponsored = true", "factor": -1.5 }),
+        json!({ "filter": "sponsored =", "factor": 1.5 }),
+        json!({ "filter": "sponsored = true" }),
+        json!({ "factor": 1.5 }),
+    ];
+    for boost in invalid_boosts {
+        let (response, code) =
+            index.search_post(json!({ "q": "quick", "boosts": [boost.clone()] })).await;
+        assert_eq!(code, 400, "{boost} {response}");
+        assert_eq!(response["code"], "invalid_search_boosts", "{boost} {response}");
+    }
+}
@@ -0,0 +1,143 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::{Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        { "id": 1, "color": "red", "size": "S" },
+        { "id": 2, "color": "red", "size": "M" },
+        { "id": 3, "color": "blue", "size": "M" },
+        { "id": 4, "color": "green", "size": "L" },
+    ])
+});
+
+async fn create_index(server: &Server) {
+    let index = server.index("test");
+    index.update_settings(json!({ "filterableAttributes": ["color", "size"] })).await;
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(1).await;
+}
+
+fn ids(response: &Value) -> Vec<serde_json::Value> {
+    response["hits"].as_array().unwrap().iter().map(|hit| hit["id"].clone()).collect()
+}
+
+#[actix_rt::test]
+async fn disjunctive_facets_ignore_the_clauses_of_their_facet() {
+    let server = Server::new().await;
+    create_index(&server).await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .search_post(json!({
+            "facets": ["color", "size"],
+            "filter": "color = red AND size = M",
+            "disjunctiveFacets": true,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(ids(&response), vec![json!(2)], "{response}");
+    snapshot!(json_string!(response["facetDistribution"]), @r###"
+    {
+      "color": {
+        "blue": 1,
+        "red": 1
+      },
+      "size": {
+        "M": 1,
+        "S": 1
+      }
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "facets": ["color", "size"],
+            "filter": [["color = red", "color = blue"], "size = M"],
+            "disjunctiveFacets": true,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(ids(&response), vec![json!(2), json!(3)], "{response}");
+    snapshot!(json_string!(response["facetDistribution"]), @r###"
+    {
+      "color": {
+        "blue": 1,
+        "red": 1
+      },
+      "size": {
+        "M": 2,
+        "S": 1
+      }
+    }
+    "###);
+
+    // the clauses mixing several facets are kept
+    let (response, code) = index
+        .search_post(json!({
+            "facets": ["color", "size"],
+            "filter": "color = red OR size = L",
+            "disjunctiveFacets": true,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetDistribution"]), @r###"
+    {
+      "color": {
+        "green": 1,
+        "red": 2
+      },
+      "size": {
+        "L": 1,
+        "M": 1,
+        "S": 1
+      }
+    }
+    "###);
+
+    let (response, code) =
+        index.search_get("facets=color,size&filter=color%20%3D%20red&disjunctiveFacets=true").await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(ids(&response), vec![json!(1), json!(2)], "{response}");
+    snapshot!(json_string!(response["facetDistribution"]), @r###"
+    {
+      "color": {
+        "blue": 1,
+        "green": 1,
+        "red": 2
+      },
+      "size": {
+        "M": 1,
+        "S": 1
+      }
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn disjunctive_facets_are_disabled_by_default() {
+    let server = Server::new().await;
+    create_index(&server).await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .search_post(json!({ "facets": ["color", "size"], "filter": "color = red AND size = M" }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetDistribution"]), @r###"
+    {
+      "color": {
+        "red": 1
+      },
+      "size": {
+        "M": 1
+      }
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({ "disjunctiveFacets": "yes" })).await;
+    snapshot!(code, @"400 Bad Request");
+    assert_eq!(response["code"], "invalid_search_disjunctive_facets", "{response}");
+}
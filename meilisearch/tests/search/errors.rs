@@ -1,6 +1,9 @@
 use meili_snap::*;
 
+use meilisearch::Opt;
+
 use super::DOCUMENTS;
+use crate::common::server::default_settings;
 use crate::common::Server;
 use crate::json;
 
@@ -257,6 +260,61 @@ async fn search_bad_filter() {
     // Can't make the `filter` fail with a get search since it'll accept anything as a strings.
 }
 
+#[actix_rt::test]
+async fn search_filter_too_complex() {
+    let temp = tempfile::tempdir().unwrap();
+    let options = Opt {
+        experimental_max_filter_depth: 2,
+        experimental_max_filter_complexity: 4,
+        ..default_settings(temp.path())
+    };
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("test");
+    index.create(None).await;
+    server.wait_task(0).await;
+
+    let (response, code) =
+        index.search_post(json!({ "filter": "a = 1 OR a = 2 OR a = 3 OR a = 4 OR a = 5" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The filter is too complex: it has a depth of 1 and is made of 5 conditions. The maximum depth is 2 and the maximum number of conditions is 4.",
+      "code": "filter_too_complex",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#filter_too_complex"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({ "filter": "a = 1 OR (a = 2 AND (a = 3 OR a = 4))" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["message"], @r###""The filter is too complex: it has a depth of 3 and is made of 4 conditions. The maximum depth is 2 and the maximum number of conditions is 4.""###);
+
+    let (response, code) = index
+        .delete_document_by_filter(
+            json!({ "filter": [["a = 1", "a = 2"], ["a = 3", "a = 4", "a = 5"]] }),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The filter is too complex: it has a depth of 2 and is made of 5 conditions. The maximum depth is 2 and the maximum number of conditions is 4.",
+      "code": "filter_too_complex",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#filter_too_complex"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({ "filter": "a = 1 OR a IN [2, 3, 4]" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""filter_too_complex""###);
+
+    // within the limits, the filter is evaluated and fails because `a` is not filterable.
+    let (response, code) = index.search_post(json!({ "filter": "a IN [1, 2, 3, 4]" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_search_filter""###);
+}
+
 #[actix_rt::test]
 async fn search_bad_sort() {
     let server = Server::new().await;
@@ -547,7 +605,7 @@ async fn filter_invalid_syntax_object() {
     index.wait_task(1).await;
 
     let expected_response = json!({
-        "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `title & Glass`.\n1:14 title & Glass",
+        "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `~=`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `title & Glass`.\n1:14 title & Glass",
         "code": "invalid_search_filter",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -572,7 +630,7 @@ async fn filter_invalid_syntax_array() {
     index.wait_task(1).await;
 
     let expected_response = json!({
-        "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `title & Glass`.\n1:14 title & Glass",
+        "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `~=`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `title & Glass`.\n1:14 title & Glass",
         "code": "invalid_search_filter",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -912,7 +970,7 @@ async fn sort_invalid_syntax() {
     index.wait_task(1).await;
 
     let expected_response = json!({
-        "message": "Invalid syntax for the sort parameter: expected expression ending by `:asc` or `:desc`, found `title`.",
+        "message": "Invalid syntax for the sort parameter: expected expression ending by `:asc` or `:desc`, optionally followed by `:nulls_first` or `:nulls_last`, found `title`.",
         "code": "invalid_search_sort",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_search_sort"
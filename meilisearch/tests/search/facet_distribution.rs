@@ -0,0 +1,71 @@
+use actix_web::test;
+use http::header::ACCEPT;
+use meili_snap::{json_string, snapshot};
+
+use crate::common::Server;
+use crate::json;
+
+async fn create_index(server: &Server) {
+    let index = server.index("test");
+    index.update_settings(json!({ "filterableAttributes": ["color", "size"] })).await;
+    index
+        .add_documents(
+            json!([
+                { "id": 1, "color": "red", "size": "S" },
+                { "id": 2, "color": "red", "size": "M" },
+                { "id": 3, "color": "blue", "size": "M" },
+            ]),
+            None,
+        )
+        .await;
+    index.wait_task(1).await;
+}
+
+#[actix_rt::test]
+async fn facet_distribution_of_the_filtered_documents() {
+    let server = Server::new().await;
+    create_index(&server).await;
+
+    let (response, code) =
+        server.service.get("/indexes/test/facet-distribution?filter=size%20%3D%20M").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "facetDistribution": {
+        "color": {
+          "blue": 1,
+          "red": 1
+        },
+        "size": {
+          "M": 2
+        }
+      },
+      "facetStats": {}
+    }
+    "###);
+
+    let (response, code) =
+        server.service.get("/indexes/test/facet-distribution?facets=title").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_search_facets""###);
+}
+
+#[actix_rt::test]
+async fn facet_distribution_as_csv() {
+    let server = Server::new().await;
+    create_index(&server).await;
+
+    let app = server.init_web_app().await;
+    let req = test::TestRequest::get()
+        .uri("/indexes/test/facet-distribution?facets=color")
+        .insert_header((ACCEPT, "text/csv"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    snapshot!(res.status(), @"200 OK");
+    let body = String::from_utf8(test::read_body(res).await.to_vec()).unwrap();
+    snapshot!(body, @r###"
+    facet,value,count
+    color,blue,1
+    color,red,2
+    "###);
+}
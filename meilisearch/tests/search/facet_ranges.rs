@@ -0,0 +1,122 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::{Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        { "id": 1, "genre": "fiction", "price": 12 },
+        { "id": 2, "genre": "fiction", "price": 49.9 },
+        { "id": 3, "genre": "poetry", "price": 50 },
+        { "id": 4, "genre": "poetry", "price": [75, 120] },
+        { "id": 5, "genre": "essay" },
+    ])
+});
+
+async fn create_index(server: &Server) {
+    let index = server.index("test");
+    index.update_settings(json!({ "filterableAttributes": ["genre", "price"] })).await;
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(1).await;
+}
+
+#[actix_rt::test]
+async fn facet_ranges() {
+    let server = Server::new().await;
+    create_index(&server).await;
+    let index = server.index("test");
+
+    // the ranges include their start and exclude their end
+    let (response, code) = index
+        .search_post(json!({
+            "facets": { "genre": {}, "price": { "ranges": [[0, 50], [50, 100]] } },
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetDistribution"]), @r###"
+    {
+      "genre": {
+        "essay": 1,
+        "fiction": 2,
+        "poetry": 2
+      },
+      "price": {
+        "0..50": 2,
+        "50..100": 2
+      }
+    }
+    "###);
+    snapshot!(json_string!(response["facetStats"]), @r###"
+    {
+      "price": {
+        "min": 12.0,
+        "max": 120.0
+      }
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "facets": { "price": { "ranges": [[0, 50]] } },
+            "filter": "genre = poetry",
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetDistribution"]), @r###"
+    {
+      "price": {
+        "0..50": 0
+      }
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn facet_interval_buckets() {
+    let server = Server::new().await;
+    create_index(&server).await;
+    let index = server.index("test");
+
+    let (response, code) =
+        index.search_post(json!({ "facets": { "price": { "interval": 50 } } })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetDistribution"]), @r###"
+    {
+      "price": {
+        "0..50": 2,
+        "50..100": 2,
+        "100..150": 1
+      }
+    }
+    "###);
+
+    // the list of facets keeps working as before
+    let (response, code) = index.search_post(json!({ "facets": ["genre"] })).await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(response["facetDistribution"]["genre"]["fiction"], json!(2), "{response}");
+}
+
+#[actix_rt::test]
+async fn facet_buckets_errors() {
+    let server = Server::new().await;
+    create_index(&server).await;
+    let index = server.index("test");
+
+    for buckets in [
+        json!({ "ranges": [[50, 0]] }),
+        json!({ "ranges": [[0, 50, 100]] }),
+        json!({ "interval": 0 }),
+        json!({ "ranges": [[0, 50]], "interval": 10 }),
+    ] {
+        let (response, code) =
+            index.search_post(json!({ "facets": { "price": buckets.clone() } })).await;
+        assert_eq!(code, 400, "{buckets}: {response}");
+        assert_eq!(response["code"], "invalid_search_facets", "{buckets}: {response}");
+    }
+
+    let (response, code) =
+        index.search_post(json!({ "facets": { "id": { "interval": 10 } } })).await;
+    snapshot!(code, @"400 Bad Request");
+    assert_eq!(response["code"], "invalid_search_facets", "{response}");
+}
@@ -0,0 +1,111 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::{Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        { "id": 1, "name": "red shirt", "product_family": "shirt" },
+        { "id": 2, "name": "blue shirt", "product_family": "shirt" },
+        { "id": 3, "name": "red shoes", "product_family": "shoes" },
+        { "id": 4, "name": "green shirt", "product_family": "shirt" },
+        { "id": 5, "name": "red hat" },
+    ])
+});
+
+async fn create_index(server: &Server) {
+    let index = server.index("test");
+    index.update_settings(json!({ "filterableAttributes": ["product_family"] })).await;
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(1).await;
+}
+
+fn ids(hits: &Value) -> Vec<serde_json::Value> {
+    hits.as_array().unwrap().iter().map(|hit| hit["id"].clone()).collect()
+}
+
+#[actix_rt::test]
+async fn group_by_attribute() {
+    let server = Server::new().await;
+    create_index(&server).await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .search_post(json!({ "groupBy": { "attribute": "product_family", "limit": 2 } }))
+        .await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(ids(&response["hits"]), vec![json!(1), json!(3), json!(5)], "{response}");
+    assert_eq!(response["estimatedTotalHits"], json!(3), "{response}");
+
+    let groups: Vec<_> = response["hits"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|hit| (hit["_group"]["totalHits"].clone(), ids(&hit["_group"]["hits"])))
+        .collect();
+    assert_eq!(
+        groups,
+        vec![
+            (json!(3), vec![json!(1), json!(2)]),
+            (json!(1), vec![json!(3)]),
+            (json!(1), vec![json!(5)]),
+        ],
+        "{response}"
+    );
+
+    // the hits of a group are formatted like the other hits
+    let (response, code) = index
+        .search_post(json!({
+            "q": "red",
+            "groupBy": { "attribute": "product_family", "limit": 1 },
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"][0]), @r###"
+    {
+      "id": 1,
+      "_group": {
+        "totalHits": 1,
+        "hits": [
+          {
+            "id": 1
+          }
+        ]
+      }
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn group_by_default_limit() {
+    let server = Server::new().await;
+    create_index(&server).await;
+    let index = server.index("test");
+
+    let (response, code) =
+        index.search_post(json!({ "groupBy": { "attribute": "product_family" } })).await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(ids(&response["hits"][0]["_group"]["hits"]), vec![json!(1), json!(2), json!(4)]);
+}
+
+#[actix_rt::test]
+async fn group_by_errors() {
+    let server = Server::new().await;
+    create_index(&server).await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({ "groupBy": { "attribute": "name" } })).await;
+    snapshot!(code, @"400 Bad Request");
+    assert_eq!(response["code"], "invalid_search_group_by", "{response}");
+
+    let (response, code) = index.search_post(json!({ "groupBy": "product_family" })).await;
+    snapshot!(code, @"400 Bad Request");
+    assert_eq!(response["code"], "invalid_search_group_by", "{response}");
+
+    let (response, code) =
+        index.search_post(json!({ "groupBy": { "attribute": "product_family", "size": 2 } })).await;
+    snapshot!(code, @"400 Bad Request");
+    assert_eq!(response["code"], "invalid_search_group_by", "{response}");
+}
@@ -219,3 +219,35 @@ async fn single_document() {
     snapshot!(code, @"200 OK");
     snapshot!(response["hits"][0], @r###"{"title":"Shazam!","desc":"a Captain Marvel ersatz","id":"1","_vectors":{"default":[1.0,3.0]},"_rankingScore":1.0,"_semanticScore":1.0}"###);
 }
+
+#[actix_rt::test]
+async fn retrieve_vectors() {
+    let server = Server::new().await;
+    let index = index_with_documents(&server, &SINGLE_DOCUMENT).await;
+
+    let (response, code) = index
+        .search_post(json!({
+            "vector": [1.0, 3.0],
+            "hybrid": {"semanticRatio": 1.0},
+            "retrieveVectors": true,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"][0], @r###"{"title":"Shazam!","desc":"a Captain Marvel ersatz","id":"1","_vectors":{"default":[1.0,3.0]},"_semanticScore":1.0}"###);
+
+    let (response, code) = index
+        .search_post(
+            json!({"q": "Captain", "retrieveVectors": false, "attributesToHighlight": ["*"]}),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"][0], @r###"{"title":"Shazam!","desc":"a Captain Marvel ersatz","id":"1","_formatted":{"title":"Shazam!","desc":"a <em>Captain</em> Marvel ersatz","id":"1"}}"###);
+
+    let (response, code) = index.search_get("?q=Captain&retrieveVectors=false").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"][0], @r###"{"title":"Shazam!","desc":"a Captain Marvel ersatz","id":"1"}"###);
+
+    let (response, code) = index.search_get("?retrieveVectors=yes").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_search_retrieve_vectors""###);
+}
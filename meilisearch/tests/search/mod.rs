@@ -1,15 +1,27 @@
 // This modules contains all the test concerning search. Each particular feature of the search
 // should be tested in its own module to isolate tests and keep the tests readable.
 
+mod aggregates;
+mod boosts;
+mod disjunctive_facets;
 mod distinct;
 mod errors;
+mod facet_distribution;
+mod facet_filters;
+mod facet_ranges;
 mod facet_search;
 mod formatted;
 mod geo;
+mod group_by;
 mod hybrid;
 mod multi;
+mod nested;
 mod pagination;
+mod query_log;
 mod restrict_searchable;
+mod search_queue;
+mod sort;
+mod wasm_middleware;
 
 use once_cell::sync::Lazy;
 
@@ -163,6 +175,83 @@ async fn simple_search() {
         .await;
 }
 
+#[actix_rt::test]
+async fn search_candidates_statistics() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.update_settings(json!({"filterableAttributes": ["id"]})).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.search_candidates(json!({})).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["numberOfDocuments"], 5);
+    assert_eq!(response["afterWords"], 5);
+    assert_eq!(response["afterTypos"], 5);
+    assert_eq!(response["afterFilter"], 5);
+
+    let (response, code) =
+        index.search_candidates(json!({"q": "captan marvel", "filter": "id = 287947"})).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["numberOfDocuments"], 5);
+    assert_eq!(response["afterWords"], 0);
+    assert_eq!(response["afterTypos"], 1);
+    assert_eq!(response["afterFilter"], 0);
+
+    let (response, code) =
+        index.search_candidates(json!({"q": "captan marvel", "filter": "id = 299537"})).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["afterTypos"], 1);
+    assert_eq!(response["afterFilter"], 1);
+}
+
+#[actix_rt::test]
+async fn search_explanation() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index
+        .update_settings(json!({
+            "filterableAttributes": ["id"],
+            "synonyms": { "dragon": ["wyvern"] },
+        }))
+        .await;
+    index.wait_task(1).await;
+
+    let (response, code) =
+        index.search_explain(json!({"q": "captan marvel", "filter": "id = 299537"})).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["terms"].as_array().unwrap().len(), 2, "{}", response);
+    assert_eq!(response["terms"][0]["original"], "captan");
+    assert_eq!(response["terms"][0]["positions"], json!([0, 0]));
+    assert_eq!(response["terms"][0]["isPrefix"], false);
+    assert_eq!(response["terms"][0]["oneTypo"], json!(["captain"]));
+    assert_eq!(response["terms"][1]["original"], "marvel");
+    assert_eq!(response["terms"][1]["isPrefix"], true);
+    assert_eq!(response["terms"][1]["exact"], "marvel");
+    assert_eq!(response["filter"], "{id} = {299537}");
+    assert_eq!(response["numberOfDocuments"], 5);
+    assert_eq!(response["afterWords"], 0);
+    assert_eq!(response["afterTypos"], 1);
+    assert_eq!(response["afterFilter"], 1);
+    assert_eq!(response["rankingRules"][0]["name"], "words");
+    assert_eq!(response["rankingRules"][0]["candidates"], 1);
+
+    let (response, code) = index.search_explain(json!({"q": "dragon"})).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["terms"][0]["synonyms"], json!(["wyvern"]));
+    assert_eq!(response["filter"], json!(null));
+
+    let (response, code) = index.search_explain(json!({})).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["terms"], json!([]));
+    assert_eq!(response["afterFilter"], 5);
+}
+
 #[actix_rt::test]
 async fn phrase_search_with_stop_word() {
     // related to https://github.com/meilisearch/meilisearch/issues/3521
@@ -480,6 +569,77 @@ async fn search_with_multiple_sort() {
     assert_eq!(response["hits"].as_array().unwrap().len(), 5);
 }
 
+#[actix_rt::test]
+async fn search_with_sort_first() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 1, "title": "dragon", "price": 10 },
+        { "id": 2, "title": "dragan", "price": 30 },
+        { "id": 3, "title": "dragon", "price": 20 },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+    let (response, code) = index
+        .update_settings(json!({
+            "sortableAttributes": ["price"],
+            "rankingRules": ["sort", "words", "typo", "proximity", "attribute", "exactness"],
+        }))
+        .await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(1).await;
+
+    fn ids(response: &Value) -> Vec<serde_json::Value> {
+        response["hits"].as_array().unwrap().iter().map(|hit| hit["id"].clone()).collect()
+    }
+
+    // the `sort` ranking rule is the first one of the index.
+    index
+        .search(json!({ "q": "dragon", "sort": ["price:desc"] }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(ids(&response), vec![json!(2), json!(3), json!(1)], "{}", response);
+        })
+        .await;
+
+    // the sort only orders the documents that are equally relevant, the one with a typo comes last.
+    index
+        .search(
+            json!({ "q": "dragon", "sort": ["price:desc"], "sortFirst": false }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                assert_eq!(ids(&response), vec![json!(3), json!(1), json!(2)], "{}", response);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({ "q": "dragon", "sort": ["price:desc"], "sortFirst": true }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                assert_eq!(ids(&response), vec![json!(2), json!(3), json!(1)], "{}", response);
+            },
+        )
+        .await;
+
+    let (response, code) = index.search_post(json!({ "q": "dragon", "sortFirst": true })).await;
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
+    {
+      "message": "The `sortFirst` parameter can only be used along with `sort`.",
+      "code": "invalid_search_sort_first",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_sort_first"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({ "sort": ["price:desc"], "sortFirst": "doggo" })).await;
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    assert_eq!(response["code"], "invalid_search_sort_first", "{}", response);
+}
+
 #[actix_rt::test]
 async fn search_facet_distribution() {
     let server = Server::new().await;
@@ -594,6 +754,83 @@ async fn displayed_attributes() {
     assert!(response["hits"][0].get("title").is_some());
 }
 
+#[actix_rt::test]
+async fn hits_written_as_stored_are_the_same_as_the_parsed_ones() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = NESTED_DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    // the documents are written as they are stored when all their fields are retrieved as is.
+    let (raw, code) = index.search_post(json!({ "attributesToRetrieve": ["*"] })).await;
+    assert_eq!(code, 200, "{}", raw);
+    let (parsed, code) = index
+        .search_post(json!({
+            "attributesToRetrieve": ["id", "father", "mother", "doggos", "cattos", "_vectors"]
+        }))
+        .await;
+    assert_eq!(code, 200, "{}", parsed);
+    assert_eq!(raw["hits"], parsed["hits"]);
+    assert_eq!(raw["hits"].as_array().unwrap().len(), 4, "{}", raw);
+
+    // a nested displayed attribute only selects a part of its root field.
+    index.update_settings(json!({ "displayedAttributes": ["id", "doggos.name"] })).await;
+    index.wait_task(1).await;
+    let (response, code) = index.search_post(json!({ "q": "bobby" })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(
+        response["hits"][0],
+        json!({ "id": 852, "doggos": [{ "name": "bobby" }, { "name": "buddy" }] })
+    );
+}
+
+#[actix_rt::test]
+async fn wildcard_patterns_select_the_attributes() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([{
+        "id": 1,
+        "title_en": "Hello",
+        "title_fr": "Bonjour",
+        "address": { "city": "Paris", "country": "France", "zip": "75001" },
+    }]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let (response, code) =
+        index.search_post(json!({ "attributesToRetrieve": ["id", "*_en", "address.c*"] })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(
+        response["hits"][0],
+        json!({ "id": 1, "title_en": "Hello", "address": { "city": "Paris", "country": "France" } })
+    );
+
+    index.update_settings(json!({ "displayedAttributes": ["id", "*_fr", "address.*"] })).await;
+    index.wait_task(1).await;
+    let (response, code) = index.search_post(json!({})).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(
+        response["hits"][0],
+        json!({
+            "id": 1,
+            "title_fr": "Bonjour",
+            "address": { "city": "Paris", "country": "France", "zip": "75001" },
+        })
+    );
+
+    // the attributes to retrieve are restricted to the displayed ones.
+    let (response, code) =
+        index.search_post(json!({ "attributesToRetrieve": ["title_*", "*.zip"] })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(
+        response["hits"][0],
+        json!({ "title_fr": "Bonjour", "address": { "zip": "75001" } })
+    );
+}
+
 #[actix_rt::test]
 async fn placeholder_search_is_hard_limited() {
     let server = Server::new().await;
@@ -1262,3 +1499,89 @@ async fn simple_search_with_strange_synonyms() {
         })
         .await;
 }
+
+#[actix_rt::test]
+async fn search_with_response_version() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({"filterableAttributes": ["title"]})).await;
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = server
+        .service
+        .request(
+            actix_web::test::TestRequest::post()
+                .uri("/indexes/test/search")
+                .insert_header(("Meili-Response-Version", "v0"))
+                .set_json(json!({"q": "glass", "facets": ["title"]})),
+        )
+        .await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(response.get("estimatedTotalHits").is_none(), @"true");
+    meili_snap::snapshot!(response.get("facetDistribution").is_none(), @"true");
+    meili_snap::snapshot!(response["nbHits"], @"1");
+    // every hit is returned, so they're all counted
+    meili_snap::snapshot!(response["exhaustiveNbHits"], @"true");
+    meili_snap::snapshot!(response["exhaustiveFacetsCount"], @"false");
+
+    let (response, code) = server
+        .service
+        .request(
+            actix_web::test::TestRequest::post()
+                .uri("/indexes/test/search")
+                .insert_header(("Meili-Response-Version", "v0"))
+                .set_json(json!({"limit": 1})),
+        )
+        .await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(response["nbHits"], @"5");
+    meili_snap::snapshot!(response["exhaustiveNbHits"], @"false");
+
+    let (response, code) = server
+        .service
+        .request(
+            actix_web::test::TestRequest::post()
+                .uri("/indexes/test/search")
+                .insert_header(("Meili-Response-Version", "v0"))
+                .set_json(json!({"hitsPerPage": 1})),
+        )
+        .await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(response["nbHits"], @"5");
+    meili_snap::snapshot!(response["exhaustiveNbHits"], @"true");
+
+    let (response, code) = server
+        .service
+        .request(
+            actix_web::test::TestRequest::post()
+                .uri("/indexes/test/search")
+                .insert_header(("Meili-Response-Version", "v1"))
+                .set_json(json!({"q": "glass"})),
+        )
+        .await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(response["estimatedTotalHits"], @"1");
+    meili_snap::snapshot!(response.get("nbHits").is_none(), @"true");
+
+    let (response, code) = server
+        .service
+        .request(
+            actix_web::test::TestRequest::post()
+                .uri("/indexes/test/search")
+                .insert_header(("Meili-Response-Version", "v42"))
+                .set_json(json!({"q": "glass"})),
+        )
+        .await;
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
+    {
+      "message": "Unsupported response version `v42`. Supported values are `v0` and `v1`.",
+      "code": "invalid_response_version",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_response_version"
+    }
+    "###);
+}
@@ -376,3 +376,81 @@ async fn search_multiple_query_errors() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn search_query_errors_with_partial_failures() {
+    let server = Server::new().await;
+
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = server
+        .multi_search(json!({"allowPartialFailures": true, "queries": [
+        {"indexUid" : "test", "q": "glass", "facets": ["title"]},
+        {"indexUid": "test", "q": "glass", "attributesToRetrieve": ["id"]},
+        {"indexUid": "missing", "q": "pésti"},
+        ]}))
+        .await;
+    snapshot!(code, @"200 OK");
+    insta::assert_json_snapshot!(response, { ".results[].processingTimeMs" => "[time]" }, @r###"
+    {
+      "results": [
+        {
+          "indexUid": "test",
+          "error": {
+            "message": "Invalid facet distribution, this index does not have configured filterable attributes.",
+            "code": "invalid_search_facets",
+            "type": "invalid_request",
+            "link": "https://docs.meilisearch.com/errors#invalid_search_facets"
+          }
+        },
+        {
+          "indexUid": "test",
+          "hits": [
+            {
+              "id": "450465"
+            }
+          ],
+          "query": "glass",
+          "processingTimeMs": "[time]",
+          "limit": 20,
+          "offset": 0,
+          "estimatedTotalHits": 1
+        },
+        {
+          "indexUid": "missing",
+          "error": {
+            "message": "Index `missing` not found.",
+            "code": "index_not_found",
+            "type": "invalid_request",
+            "link": "https://docs.meilisearch.com/errors#index_not_found"
+          }
+        }
+      ],
+      "status": "partiallyFailed"
+    }
+    "###);
+
+    let (response, code) = server
+        .multi_search(json!({"allowPartialFailures": true, "queries": [
+        {"indexUid": "missing", "q": "pésti"},
+        ]}))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["status"], @r###""failed""###);
+
+    let (response, code) =
+        server.multi_search(json!({"allowPartialFailures": "yes", "queries": []})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.allowPartialFailures`: expected a boolean, but found a string: `\"yes\"`",
+      "code": "invalid_multi_search_allow_partial_failures",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_multi_search_allow_partial_failures"
+    }
+    "###);
+}
@@ -0,0 +1,130 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::{Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        { "id": 1, "name": "shirt", "variants": [
+            { "color": "red", "size": "S" },
+            { "color": "blue", "size": "M" },
+        ] },
+        { "id": 2, "name": "hat", "variants": [
+            { "color": "red", "size": "M" },
+            { "color": "green", "size": "L" },
+        ] },
+        { "id": 3, "name": "socks", "variants": [
+            { "color": "blue", "size": "S" },
+        ] },
+    ])
+});
+
+async fn create_index(server: &Server) {
+    let index = server.index("test");
+    index.update_settings(json!({ "filterableAttributes": ["name", "variants"] })).await;
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(1).await;
+}
+
+fn ids(response: &Value) -> Vec<serde_json::Value> {
+    response["hits"].as_array().unwrap().iter().map(|hit| hit["id"].clone()).collect()
+}
+
+#[actix_rt::test]
+async fn nested_filter_keeps_the_pairing_of_the_elements() {
+    let server = Server::new().await;
+    create_index(&server).await;
+    let index = server.index("test");
+
+    // the flattened fields lose the pairing of the elements
+    let (response, code) =
+        index.search_post(json!({ "filter": "variants.color = red AND variants.size = M" })).await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(ids(&response), vec![json!(1), json!(2)], "{response}");
+
+    let (response, code) = index
+        .search_post(json!({
+            "nested": { "attribute": "variants", "filter": "color = red AND size = M" },
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(ids(&response), vec![json!(2)], "{response}");
+    assert_eq!(response["estimatedTotalHits"], json!(1), "{response}");
+
+    let (response, code) = index
+        .search_post(json!({
+            "filter": "name != hat",
+            "nested": { "attribute": "variants", "filter": ["color = blue"] },
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(ids(&response), vec![json!(1), json!(3)], "{response}");
+}
+
+#[actix_rt::test]
+async fn nested_facets_only_count_the_matching_elements() {
+    let server = Server::new().await;
+    create_index(&server).await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .search_post(json!({
+            "facets": ["name", "variants.color"],
+            "nested": { "attribute": "variants", "filter": "size = M" },
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(ids(&response), vec![json!(1), json!(2)], "{response}");
+    snapshot!(json_string!(response["facetDistribution"]), @r###"
+    {
+      "name": {
+        "hat": 1,
+        "shirt": 1
+      },
+      "variants.color": {
+        "blue": 1,
+        "red": 1
+      }
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "facets": ["variants.size"],
+            "nested": { "attribute": "variants", "filter": "color = red" },
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetDistribution"]), @r###"
+    {
+      "variants.size": {
+        "M": 1,
+        "S": 1
+      }
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn nested_errors() {
+    let server = Server::new().await;
+    create_index(&server).await;
+    let index = server.index("test");
+
+    let (response, code) =
+        index.search_post(json!({ "nested": { "attribute": "id", "filter": "size = M" } })).await;
+    snapshot!(code, @"400 Bad Request");
+    assert_eq!(response["code"], "invalid_search_nested", "{response}");
+
+    let (response, code) = index
+        .search_post(json!({ "nested": { "attribute": "variants", "filter": "size =" } }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    assert_eq!(response["code"], "invalid_search_nested", "{response}");
+
+    let (response, code) =
+        index.search_post(json!({ "nested": { "attribute": "variants" } })).await;
+    snapshot!(code, @"400 Bad Request");
+    assert_eq!(response["code"], "invalid_search_nested", "{response}");
+}
@@ -0,0 +1,215 @@
+use std::path::Path;
+use std::time::Duration;
+
+use meilisearch::Opt;
+
+use crate::common::server::default_settings;
+use crate::common::{Server, Value};
+use crate::json;
+
+/// Returns the query log once it holds at least the given number of lines, as it's written in the
+/// background.
+async fn read_query_log(db_path: &Path, lines: usize) -> String {
+    let path = db_path.join("query-log/queries.ndjson");
+    for _ in 0..100 {
+        let log = std::fs::read_to_string(&path).unwrap();
+        if log.lines().count() >= lines {
+            return log;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("the query log doesn't hold {lines} lines");
+}
+
+#[actix_rt::test]
+async fn searches_are_recorded_anonymized_in_the_query_log() {
+    let temp = tempfile::tempdir().unwrap();
+    let options = Opt { query_log: true, ..default_settings(temp.path()) };
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("test");
+    index
+        .add_documents(json!([{ "id": 1, "title": "Shazam" }, { "id": 2, "title": "Ariel" }]), None)
+        .await;
+    index.wait_task(0).await;
+
+    let (response, code) =
+        index.search_post(json!({ "q": "shazam order 1234 jane@doe.com", "limit": 5 })).await;
+    assert_eq!(code, 200, "{response}");
+    let (response, code) = server
+        .multi_search(
+            json!({ "queries": [{ "indexUid": "test", "q": "ariel", "sort": ["id:asc"] }] }),
+        )
+        .await;
+    assert_eq!(code, 400, "{response}");
+    let (response, code) =
+        server.multi_search(json!({ "queries": [{ "indexUid": "test", "q": "ariel" }] })).await;
+    assert_eq!(code, 200, "{response}");
+
+    let log = read_query_log(&temp.path().join("db"), 2).await;
+    // The failed searches are not recorded.
+    let searches: Vec<Value> =
+        log.lines().map(|line| Value(serde_json::from_str(line).unwrap())).collect();
+    assert_eq!(searches.len(), 2, "{log}");
+
+    assert_eq!(searches[0]["indexUid"], json!("test"));
+    assert_eq!(searches[0]["q"], json!("shazam order *** ***"));
+    assert_eq!(searches[0]["limit"], json!(5));
+    assert_eq!(searches[0]["hits"], json!(1));
+    assert_eq!(searches[0]["totalHits"], json!(1));
+    assert!(searches[0]["processingTimeMs"].is_u64(), "{log}");
+
+    assert_eq!(searches[1]["q"], json!("ariel"));
+    assert_eq!(searches[1]["hits"], json!(1));
+    assert_eq!(searches[1]["totalHits"], json!(1));
+}
+
+#[actix_rt::test]
+async fn sampled_searches_record_the_work_of_the_ranking_rules() {
+    let temp = tempfile::tempdir().unwrap();
+    let options = Opt {
+        query_log: true,
+        query_log_diagnostics_sample_rate: 1.0,
+        ..default_settings(temp.path())
+    };
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("test");
+    let documents = json!([
+        { "id": 1, "title": "Shazam" },
+        { "id": 2, "title": "Shazam returns" },
+        { "id": 3, "title": "Ariel" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.search_post(json!({ "q": "shazam" })).await;
+    assert_eq!(code, 200, "{response}");
+    // the diagnostics are only written in the query log
+    assert!(response.get("rankingRules").is_none(), "{response}");
+
+    let log = read_query_log(&temp.path().join("db"), 1).await;
+    let search = Value(serde_json::from_str(log.lines().next().unwrap()).unwrap());
+    let ranking_rules = search["rankingRules"].as_array().unwrap();
+    assert_eq!(ranking_rules[0]["name"], json!("words"), "{log}");
+    assert_eq!(ranking_rules[0]["buckets"], json!(1), "{log}");
+    assert_eq!(ranking_rules[0]["bucketDocuments"], json!(2), "{log}");
+    assert!(ranking_rules[0]["durationMs"].is_f64(), "{log}");
+}
+
+#[actix_rt::test]
+async fn search_analytics_aggregate_the_query_log() {
+    let temp = tempfile::tempdir().unwrap();
+    let options = Opt { query_log: true, ..default_settings(temp.path()) };
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("test");
+    index
+        .add_documents(json!([{ "id": 1, "title": "Shazam" }, { "id": 2, "title": "Ariel" }]), None)
+        .await;
+    index.wait_task(0).await;
+
+    for q in ["shazam", "Shazam", "ariel", "batman", "batman", "batman", ""] {
+        let (response, code) = index.search_post(json!({ "q": q })).await;
+        assert_eq!(code, 200, "{response}");
+    }
+    let (response, code) = server
+        .service
+        .post("/indexes/test/analytics/clicks", json!({ "q": "ariel", "documentId": 2 }))
+        .await;
+    assert_eq!(code, 204, "{response}");
+
+    let (response, code) = server.service.get("/indexes/test/analytics").await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["searches"], json!(7), "{response}");
+    assert_eq!(response["zeroResultSearches"], json!(3), "{response}");
+    assert_eq!(response["clicks"], json!(1), "{response}");
+    assert_eq!(
+        response["topQueries"],
+        json!([
+            { "q": "batman", "searches": 3, "zeroResultSearches": 3, "clicks": 0 },
+            { "q": "shazam", "searches": 2, "zeroResultSearches": 0, "clicks": 0 },
+            { "q": "ariel", "searches": 1, "zeroResultSearches": 0, "clicks": 1 },
+        ]),
+        "{response}"
+    );
+    assert_eq!(response["zeroResultQueries"][0]["q"], json!("batman"), "{response}");
+    assert_eq!(response["zeroResultQueries"].as_array().unwrap().len(), 1, "{response}");
+    assert_eq!(response["clicklessQueries"][0]["q"], json!("shazam"), "{response}");
+    assert_eq!(response["clicklessQueries"].as_array().unwrap().len(), 1, "{response}");
+
+    let (response, code) = server.service.get("/indexes/test/analytics?limit=1").await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["topQueries"][0]["q"], json!("batman"), "{response}");
+    assert_eq!(response["topQueries"].as_array().unwrap().len(), 1, "{response}");
+
+    // the searches made in the time window
+    let (response, code) = server.service.get("/indexes/test/analytics?to=2000-01-01").await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["searches"], json!(0), "{response}");
+    assert_eq!(response["topQueries"], json!([]), "{response}");
+    let (response, code) = server.service.get("/indexes/test/analytics?from=2000-01-01").await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["searches"], json!(7), "{response}");
+
+    let (response, code) = server.service.get("/indexes/test/analytics?from=yesterday").await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("invalid_analytics_from"), "{response}");
+    let (response, code) = server.service.get("/indexes/unknown/analytics").await;
+    assert_eq!(code, 404, "{response}");
+}
+
+#[actix_rt::test]
+async fn search_analytics_require_the_query_log() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = server.service.get("/indexes/test/analytics").await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("query_log_disabled"), "{response}");
+    let (response, code) = server
+        .service
+        .post("/indexes/test/analytics/clicks", json!({ "q": "ariel", "documentId": 2 }))
+        .await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], json!("query_log_disabled"), "{response}");
+}
+
+#[actix_rt::test]
+async fn filters_are_logged_anonymized_without_the_tenant_token_rules() {
+    use jsonwebtoken::{encode, EncodingKey, Header};
+
+    let temp = tempfile::tempdir().unwrap();
+    let db_path = temp.path().join("db");
+    let options = Opt { query_log: true, ..default_settings(temp.path()) };
+    let mut server = Server::new_auth_with_options(options, temp).await;
+    server.use_api_key("MASTER_KEY");
+    let index = server.index("test");
+    index.update_settings(json!({ "filterableAttributes": ["id", "owner", "title"] })).await;
+    index
+        .add_documents(json!([{ "id": 1, "title": "Shazam", "owner": "jane@doe.com" }]), None)
+        .await;
+    index.wait_task(1).await;
+
+    let (response, code) = server
+        .add_api_key(json!({ "actions": ["search"], "indexes": ["test"], "expiresAt": null }))
+        .await;
+    assert_eq!(code, 201, "{response}");
+    let claims = json!({
+        "apiKeyUid": response["uid"],
+        "searchRules": { "test": { "filter": "owner = 'jane@doe.com'" } },
+    });
+    let key = response["key"].as_str().unwrap();
+    let token = encode(&Header::default(), &claims, &EncodingKey::from_secret(key.as_bytes()));
+    server.use_api_key(token.unwrap());
+
+    let (response, code) = server
+        .index("test")
+        .search_post(json!({ "filter": ["id = 1", ["title = Shazam", "id = 2"]] }))
+        .await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["hits"].as_array().unwrap().len(), 1, "{response}");
+
+    let log = read_query_log(&db_path, 1).await;
+    let search = Value(serde_json::from_str(log.lines().next().unwrap()).unwrap());
+    assert_eq!(search["filter"], json!(["id = ***", ["title = Shazam", "id = ***"]]), "{log}");
+}
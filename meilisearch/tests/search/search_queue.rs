@@ -0,0 +1,70 @@
+use meili_snap::{json_string, snapshot};
+use meilisearch::Opt;
+
+use crate::common::server::default_settings;
+use crate::common::Server;
+use crate::json;
+
+async fn server_with_queue(search_queue_size: usize) -> Server {
+    let temp = tempfile::tempdir().unwrap();
+    let options = Opt {
+        max_concurrent_searches: 1,
+        search_queue_size,
+        search_queue_timeout_ms: 10,
+        ..default_settings(temp.path())
+    };
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("test");
+    index.add_documents(json!([{ "id": 1, "title": "Shazam" }]), None).await;
+    index.wait_task(0).await;
+    server
+}
+
+#[actix_rt::test]
+async fn searches_are_rejected_when_the_search_queue_is_full() {
+    let server = server_with_queue(0).await;
+    let index = server.index("test");
+
+    // Takes the only permit as if a search was being executed.
+    let permit = server.service.search_queue.try_get_search_permit().await.unwrap();
+    let (response, code) = index.search_post(json!({ "q": "shazam" })).await;
+    snapshot!(code, @"503 Service Unavailable");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Too many search requests are waiting to be processed, the search queue holds at most 0 of them. Try again later.",
+      "code": "too_many_search_requests",
+      "type": "system",
+      "link": "https://docs.meilisearch.com/errors#too_many_search_requests"
+    }
+    "###);
+    let (response, code) =
+        server.multi_search(json!({ "queries": [{ "indexUid": "test", "q": "shazam" }] })).await;
+    assert_eq!(code, 503, "{response}");
+
+    drop(permit);
+    let (response, code) = index.search_post(json!({ "q": "shazam" })).await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["hits"].as_array().unwrap().len(), 1, "{response}");
+}
+
+#[actix_rt::test]
+async fn searches_waiting_too_long_in_the_search_queue_are_rejected() {
+    let server = server_with_queue(10).await;
+    let index = server.index("test");
+
+    let permit = server.service.search_queue.try_get_search_permit().await.unwrap();
+    let (response, code) = index.search_get("q=shazam").await;
+    snapshot!(code, @"503 Service Unavailable");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The search request waited more than 10ms in the search queue. Try again later.",
+      "code": "too_many_search_requests",
+      "type": "system",
+      "link": "https://docs.meilisearch.com/errors#too_many_search_requests"
+    }
+    "###);
+
+    drop(permit);
+    let (response, code) = index.search_get("q=shazam").await;
+    assert_eq!(code, 200, "{response}");
+}
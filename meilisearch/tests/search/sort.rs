@@ -0,0 +1,88 @@
+use meili_snap::snapshot;
+use once_cell::sync::Lazy;
+
+use crate::common::{Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        { "id": 1, "price": 20 },
+        { "id": 2 },
+        { "id": 3, "price": 10 },
+        { "id": 4, "price": null },
+    ])
+});
+
+async fn create_index(server: &Server) {
+    let index = server.index("test");
+    index.update_settings(json!({ "sortableAttributes": ["price"] })).await;
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(1).await;
+}
+
+fn ids(response: &Value) -> Vec<serde_json::Value> {
+    response["hits"].as_array().unwrap().iter().map(|hit| hit["id"].clone()).collect()
+}
+
+#[actix_rt::test]
+async fn sort_missing_values_placement() {
+    let server = Server::new().await;
+    create_index(&server).await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({ "sort": ["price:asc"] })).await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(ids(&response), vec![json!(3), json!(1), json!(2), json!(4)], "{response}");
+
+    let (response, code) = index.search_post(json!({ "sort": ["price:asc:nulls_last"] })).await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(ids(&response), vec![json!(3), json!(1), json!(2), json!(4)], "{response}");
+
+    let (response, code) = index.search_post(json!({ "sort": ["price:asc:nulls_first"] })).await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(ids(&response), vec![json!(2), json!(4), json!(3), json!(1)], "{response}");
+
+    let (response, code) = index.search_post(json!({ "sort": ["price:desc:nulls_first"] })).await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(ids(&response), vec![json!(2), json!(4), json!(1), json!(3)], "{response}");
+}
+
+#[actix_rt::test]
+async fn sort_missing_values_placement_errors() {
+    let server = Server::new().await;
+    create_index(&server).await;
+    let index = server.index("test");
+
+    for sort in ["price:nulls_first", "price:asc:nulls", "_geoPoint(0, 0):asc:nulls_first"] {
+        let (response, code) = index.search_post(json!({ "sort": [sort] })).await;
+        assert_eq!(code, 400, "{sort} {response}");
+        assert_eq!(response["code"], "invalid_search_sort", "{sort} {response}");
+    }
+}
+
+#[actix_rt::test]
+async fn random_sort() {
+    let server = Server::new().await;
+    create_index(&server).await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({ "sort": ["_random(42)"] })).await;
+    snapshot!(code, @"200 OK");
+    let mut documents_ids = ids(&response);
+    let (response, code) = index.search_post(json!({ "sort": ["_random(42)"] })).await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(ids(&response), documents_ids, "{response}");
+
+    let (response, code) =
+        index.search_post(json!({ "sort": ["_random(42)"], "offset": 1, "limit": 2 })).await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(ids(&response), documents_ids[1..3], "{response}");
+
+    documents_ids.sort_by_key(|id| id.as_u64());
+    assert_eq!(documents_ids, vec![json!(1), json!(2), json!(3), json!(4)]);
+
+    let (response, code) = index.search_post(json!({ "sort": ["_random(seed)"] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["message"], @r###""Invalid syntax for the random sort: expected expression written like `_random(seed)` with a positive integer seed, found `_random(seed)`.""###);
+    snapshot!(response["code"], @r###""invalid_search_sort""###);
+}
@@ -0,0 +1,128 @@
+use meili_snap::{json_string, snapshot};
+use meilisearch::Opt;
+
+use crate::common::server::default_settings;
+use crate::common::Server;
+use crate::json;
+
+/// Returns a module ignoring its input and rewriting the body with `output` for the `hook` export.
+fn constant_module(hook: &str, output: &str) -> String {
+    format!(
+        r#"(module
+            (memory (export "memory") 1)
+            (data (i32.const 0) "{output}")
+            (func (export "alloc") (param i32) (result i32) i32.const 1024)
+            (func (export "{hook}") (param i32 i32) (result i64) i64.const {len})
+        )"#,
+        output = output.replace('"', "\\\""),
+        len = output.len(),
+    )
+}
+
+async fn server_with_middlewares(modules: &[(&str, String)]) -> (Server, tempfile::TempDir) {
+    let wasm_dir = tempfile::tempdir().unwrap();
+    for (index_uid, module) in modules {
+        std::fs::write(wasm_dir.path().join(format!("{index_uid}.wat")), module).unwrap();
+    }
+    let temp = tempfile::tempdir().unwrap();
+    let options = Opt {
+        experimental_wasm_middleware_dir: Some(wasm_dir.path().to_path_buf()),
+        experimental_wasm_middleware_fuel: 10_000,
+        ..default_settings(temp.path())
+    };
+    let server = Server::new_with_options(options).await.unwrap();
+    for (task, (index_uid, _)) in modules.iter().enumerate() {
+        let index = server.index(index_uid);
+        index.add_documents(json!([{ "id": 1, "title": "Shazam" }]), None).await;
+        index.wait_task(task as u64).await;
+    }
+    (server, temp)
+}
+
+#[actix_rt::test]
+async fn rewrite_search_request_and_response() {
+    let (server, _temp) = server_with_middlewares(&[
+        ("request", constant_module("rewrite_request", r#"{"q":"unknown"}"#)),
+        ("response", constant_module("rewrite_response", r#"{"hits":[],"rewritten":true}"#)),
+    ])
+    .await;
+
+    let (response, code) = server.index("request").search_post(json!({ "q": "shazam" })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @"[]");
+    snapshot!(json_string!(response["query"]), @r###""unknown""###);
+
+    let (response, code) = server.index("response").search_post(json!({ "q": "shazam" })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "hits": [],
+      "rewritten": true
+    }
+    "###);
+
+    // the errors are not rewritten
+    let (response, code) = server.index("response").search_post(json!({ "q": 42 })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_search_q""###);
+}
+
+#[actix_rt::test]
+async fn wasm_middleware_running_out_of_fuel() {
+    let looping = r#"(module
+        (memory (export "memory") 1)
+        (func (export "alloc") (param i32) (result i32) i32.const 0)
+        (func (export "rewrite_request") (param i32 i32) (result i64) (loop br 0) i64.const 0)
+    )"#;
+    let (server, _temp) = server_with_middlewares(&[("test", looping.to_string())]).await;
+
+    let (response, code) = server.index("test").search_post(json!({ "q": "shazam" })).await;
+    snapshot!(code, @"500 Internal Server Error");
+    snapshot!(response["code"], @r###""wasm_middleware_failed""###);
+}
+
+#[actix_rt::test]
+async fn wasm_middleware_returning_a_body_out_of_its_memory() {
+    let out_of_memory = r#"(module
+        (memory (export "memory") 1)
+        (func (export "alloc") (param i32) (result i32) i32.const 0)
+        (func (export "rewrite_request") (param i32 i32) (result i64) i64.const 0xffffffff)
+    )"#;
+    let (server, _temp) = server_with_middlewares(&[("test", out_of_memory.to_string())]).await;
+
+    let (response, code) = server.index("test").search_post(json!({ "q": "shazam" })).await;
+    snapshot!(code, @"500 Internal Server Error");
+    snapshot!(response["code"], @r###""wasm_middleware_failed""###);
+}
+
+#[actix_rt::test]
+async fn wasm_middleware_only_runs_on_authenticated_requests() {
+    let looping = r#"(module
+        (memory (export "memory") 1)
+        (func (export "alloc") (param i32) (result i32) i32.const 0)
+        (func (export "rewrite_request") (param i32 i32) (result i64) (loop br 0) i64.const 0)
+    )"#;
+    let wasm_dir = tempfile::tempdir().unwrap();
+    std::fs::write(wasm_dir.path().join("test.wat"), looping).unwrap();
+    let temp = tempfile::tempdir().unwrap();
+    let options = Opt {
+        experimental_wasm_middleware_dir: Some(wasm_dir.path().to_path_buf()),
+        experimental_wasm_middleware_fuel: 10_000,
+        ..default_settings(temp.path())
+    };
+    let mut server = Server::new_auth_with_options(options, temp).await;
+
+    let (response, code) = server.index("test").search_post(json!({ "q": "shazam" })).await;
+    snapshot!(code, @"401 Unauthorized");
+    snapshot!(response["code"], @r###""missing_authorization_header""###);
+
+    server.use_api_key("WRONG_KEY");
+    let (response, code) = server.index("test").search_post(json!({ "q": "shazam" })).await;
+    snapshot!(code, @"403 Forbidden");
+    snapshot!(response["code"], @r###""invalid_api_key""###);
+
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server.index("test").search_post(json!({ "q": "shazam" })).await;
+    snapshot!(code, @"500 Internal Server Error");
+    snapshot!(response["code"], @r###""wasm_middleware_failed""###);
+}
@@ -0,0 +1,69 @@
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn decompounding_is_only_displayed_when_set() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert!(response.get("decompounding").is_none(), "{}", response);
+
+    let dictionaries = json!({ "deu": ["haftpflicht", "versicherung"] });
+    let (response, code) = index.update_settings(json!({ "decompounding": dictionaries })).await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(1).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["decompounding"], dictionaries, "{}", response);
+
+    let (response, code) = index.update_settings(json!({ "decompounding": null })).await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(2).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert!(response.get("decompounding").is_none(), "{}", response);
+}
+
+#[actix_rt::test]
+async fn decompounding_must_use_known_locales() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) =
+        index.update_settings(json!({ "decompounding": { "german": ["versicherung"] } })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_settings_decompounding", "{}", response);
+}
+
+#[actix_rt::test]
+async fn search_matches_the_parts_of_the_compound_words() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let (response, code) = index
+        .update_settings(json!({
+            "localizedAttributes": [{ "attributePatterns": ["*"], "locales": ["deu"] }],
+            "decompounding": { "deu": ["haftpflicht", "versicherung"] },
+        }))
+        .await;
+    assert_eq!(code, 202, "{}", response);
+    index
+        .add_documents(
+            json!([
+                { "id": 1, "title": "Die Haftpflichtversicherung" },
+                { "id": 2, "title": "Die Hausratversicherung" },
+            ]),
+            None,
+        )
+        .await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.search_post(json!({ "q": "Versicherung" })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["hits"], json!([{ "id": 1, "title": "Die Haftpflichtversicherung" }]));
+}
@@ -0,0 +1,198 @@
+use actix_web::web::Json;
+use actix_web::{post, App, HttpServer};
+use serde_json::Value;
+
+use crate::common::Server;
+use crate::json;
+
+/// Tags every document with the length of its name.
+#[post("/enrich")]
+async fn enrich(documents: Json<Vec<Value>>) -> Json<Vec<Value>> {
+    Json(
+        documents
+            .iter()
+            .map(|document| {
+                let length = document["name"].as_str().map_or(0, |name| name.len());
+                serde_json::json!({ "nameLength": length })
+            })
+            .collect(),
+    )
+}
+
+/// Always forgets the last document.
+#[post("/truncate")]
+async fn truncate(mut documents: Json<Vec<Value>>) -> Json<Vec<Value>> {
+    documents.pop();
+    Json(documents.iter().map(|_| serde_json::json!({})).collect())
+}
+
+/// Gives the same id to every document.
+#[post("/overwrite-id")]
+async fn overwrite_id(documents: Json<Vec<Value>>) -> Json<Vec<Value>> {
+    Json(documents.iter().map(|_| serde_json::json!({ "id": 42 })).collect())
+}
+
+/// Spawns the webhook server and returns its URL.
+fn create_webhook_server() -> String {
+    let server =
+        HttpServer::new(|| App::new().service(enrich).service(truncate).service(overwrite_id))
+            .bind(("127.0.0.1", 0))
+            .unwrap();
+    let (ip, scheme) = server.addrs_with_scheme()[0];
+    tokio::spawn(server.run());
+    format!("{scheme}://{ip}")
+}
+
+#[actix_rt::test]
+async fn enrichment_webhook_is_only_displayed_when_set() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert!(response.get("enrichmentWebhook").is_none(), "{}", response);
+
+    let (response, code) = index
+        .update_settings(json!({ "enrichmentWebhook": { "url": "http://localhost:3000/enrich" } }))
+        .await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(1).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(
+        response["enrichmentWebhook"],
+        json!({ "url": "http://localhost:3000/enrich", "batchSize": 100 })
+    );
+
+    let (response, code) = index.update_settings(json!({ "enrichmentWebhook": null })).await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(2).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert!(response.get("enrichmentWebhook").is_none(), "{}", response);
+}
+
+#[actix_rt::test]
+async fn invalid_enrichment_webhook() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+    index.wait_task(0).await;
+
+    let (response, code) =
+        index.update_settings(json!({ "enrichmentWebhook": { "url": "ftp://localhost" } })).await;
+    assert_eq!(code, 202, "{}", response);
+    let response = index.wait_task(1).await;
+    assert_eq!(response["status"], "failed", "{}", response);
+    assert_eq!(response["error"]["code"], "invalid_settings_enrichment_webhook", "{}", response);
+
+    // the batch size can't be configured without an URL
+    let (response, code) =
+        index.update_settings(json!({ "enrichmentWebhook": { "batchSize": 10 } })).await;
+    assert_eq!(code, 202, "{}", response);
+    let response = index.wait_task(2).await;
+    assert_eq!(response["status"], "failed", "{}", response);
+    assert_eq!(response["error"]["code"], "invalid_settings_enrichment_webhook", "{}", response);
+
+    let (response, code) = index
+        .update_settings(
+            json!({ "enrichmentWebhook": { "url": "http://localhost", "batchSize": 0 } }),
+        )
+        .await;
+    assert_eq!(code, 202, "{}", response);
+    let response = index.wait_task(3).await;
+    assert_eq!(response["status"], "failed", "{}", response);
+    assert_eq!(response["error"]["code"], "invalid_settings_enrichment_webhook", "{}", response);
+
+    let (response, code) =
+        index.update_settings(json!({ "enrichmentWebhook": { "url": true } })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_settings_enrichment_webhook", "{}", response);
+}
+
+#[actix_rt::test]
+async fn documents_are_enriched_before_being_indexed() {
+    let url = create_webhook_server();
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .update_settings(
+            json!({ "enrichmentWebhook": { "url": format!("{url}/enrich"), "batchSize": 2 } }),
+        )
+        .await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(0).await;
+
+    let documents = json!([
+        { "id": 1, "name": "kefir" },
+        { "id": 2, "name": "intel" },
+        { "id": 3, "name": "patou" },
+        { "id": 4, "name": "bouvier" },
+        { "id": 5, "name": "doggo" },
+    ]);
+    index.add_documents(documents, None).await;
+    let response = index.wait_task(1).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+    assert_eq!(response["details"]["indexedDocuments"], 5, "{}", response);
+
+    let (response, code) = index.get_document(4, None).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response, json!({ "id": 4, "name": "bouvier", "nameLength": 7 }));
+}
+
+#[actix_rt::test]
+async fn failing_enrichment_webhook_fails_the_task() {
+    let url = create_webhook_server();
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .update_settings(json!({ "enrichmentWebhook": { "url": format!("{url}/truncate") } }))
+        .await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(0).await;
+
+    index
+        .add_documents(json!([{ "id": 1, "name": "kefir" }, { "id": 2, "name": "intel" }]), None)
+        .await;
+    let response = index.wait_task(1).await;
+    assert_eq!(response["status"], "failed", "{}", response);
+    assert_eq!(response["details"]["indexedDocuments"], 0, "{}", response);
+    assert_eq!(response["error"]["code"], "enrichment_webhook_failed", "{}", response);
+
+    let (response, code) = index.get_all_documents(Default::default()).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["total"], 0, "{}", response);
+}
+
+#[actix_rt::test]
+async fn enrichment_webhook_cant_change_the_primary_key() {
+    let url = create_webhook_server();
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .update_settings(json!({ "enrichmentWebhook": { "url": format!("{url}/overwrite-id") } }))
+        .await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(0).await;
+
+    index
+        .add_documents(
+            json!([{ "id": 1, "name": "kefir" }, { "id": 2, "name": "intel" }]),
+            Some("id"),
+        )
+        .await;
+    let response = index.wait_task(1).await;
+    assert_eq!(response["status"], "failed", "{}", response);
+    assert_eq!(response["error"]["code"], "enrichment_webhook_failed", "{}", response);
+
+    let (response, code) = index.get_all_documents(Default::default()).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["total"], 0, "{}", response);
+}
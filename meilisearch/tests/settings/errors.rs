@@ -152,7 +152,7 @@ async fn settings_bad_stop_words() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Invalid value type at `.stopWords`: expected an array, but found a string: `\"doggo\"`",
+      "message": "Invalid value type at `.stopWords`: expected an array or an object, but found a string: `\"doggo\"`",
       "code": "invalid_settings_stop_words",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_settings_stop_words"
@@ -163,7 +163,19 @@ async fn settings_bad_stop_words() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Invalid value type: expected an array, but found a string: `\"doggo\"`",
+      "message": "Invalid value type: expected an array or an object, but found a string: `\"doggo\"`",
+      "code": "invalid_settings_stop_words",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_stop_words"
+    }
+    "###);
+
+    let (response, code) =
+        index.update_settings(json!({ "stopWords": { "english": ["the"] } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.stopWords.english`: Unsupported locale `english`, expected an ISO 639-3 language code such as `eng`, `jpn` or `cmn`.",
       "code": "invalid_settings_stop_words",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_settings_stop_words"
@@ -274,7 +286,7 @@ async fn settings_bad_typo_tolerance() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Unknown field `typoTolerance`: expected one of `enabled`, `minWordSizeForTypos`, `disableOnWords`, `disableOnAttributes`",
+      "message": "Unknown field `typoTolerance`: expected one of `enabled`, `minWordSizeForTypos`, `disableOnWords`, `disableOnAttributes`, `disableOnNumbers`",
       "code": "invalid_settings_typo_tolerance",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_settings_typo_tolerance"
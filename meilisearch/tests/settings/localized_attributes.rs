@@ -0,0 +1,75 @@
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn localized_attributes_are_only_displayed_when_set() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert!(response.get("localizedAttributes").is_none(), "{}", response);
+
+    let rules = json!([{ "attributePatterns": ["*_ja"], "locales": ["jpn"] }]);
+    let (response, code) = index.update_settings(json!({ "localizedAttributes": rules })).await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(1).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["localizedAttributes"], rules, "{}", response);
+
+    let (response, code) = index.update_settings(json!({ "localizedAttributes": null })).await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(2).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert!(response.get("localizedAttributes").is_none(), "{}", response);
+}
+
+#[actix_rt::test]
+async fn localized_attributes_must_use_known_locales() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .update_settings(json!({
+            "localizedAttributes": [{ "attributePatterns": ["title"], "locales": ["japanese"] }]
+        }))
+        .await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_settings_localized_attributes", "{}", response);
+}
+
+#[actix_rt::test]
+async fn search_with_locales() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let (response, code) = index
+        .update_settings(json!({
+            "localizedAttributes": [{ "attributePatterns": ["*_ja"], "locales": ["jpn"] }]
+        }))
+        .await;
+    assert_eq!(code, 202, "{}", response);
+    index
+        .add_documents(
+            json!([
+                { "id": 1, "name_ja": "東京" },
+                { "id": 2, "name_ja": "大阪" },
+            ]),
+            None,
+        )
+        .await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.search_post(json!({ "q": "東京", "locales": ["jpn"] })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["hits"], json!([{ "id": 1, "name_ja": "東京" }]), "{}", response);
+
+    let (response, code) = index.search_post(json!({ "q": "東京", "locales": ["japanese"] })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_search_locales", "{}", response);
+}
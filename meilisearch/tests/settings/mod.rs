@@ -1,5 +1,18 @@
+mod decompounding;
 mod distinct;
+mod enrichment_webhook;
 mod errors;
 mod get_settings;
+mod localized_attributes;
+mod prefix_search;
 mod proximity_settings;
+mod query_plan_hints;
+mod recency;
+mod search_cutoff_ms;
+mod search_exclusions;
+mod search_templates;
+mod stemming;
+mod stop_words;
 mod tokenizer_customization;
+mod typo_tolerance;
+mod unit_conversions;
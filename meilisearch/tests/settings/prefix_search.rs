@@ -0,0 +1,67 @@
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn prefix_search_is_only_displayed_when_set() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert!(response.get("prefixSearch").is_none(), "{}", response);
+
+    let (response, code) = index.update_settings(json!({ "prefixSearch": "disabled" })).await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(1).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["prefixSearch"], json!("disabled"), "{}", response);
+
+    let (response, code) = index.update_settings(json!({ "prefixSearch": null })).await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(2).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert!(response.get("prefixSearch").is_none(), "{}", response);
+}
+
+#[actix_rt::test]
+async fn prefix_search_must_be_a_known_behavior() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.update_settings(json!({ "prefixSearch": "searchTime" })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_settings_prefix_search", "{}", response);
+}
+
+#[actix_rt::test]
+async fn search_without_prefix_search() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let (response, code) = index
+        .update_settings(json!({
+            "prefixSearch": "disabled",
+            "typoTolerance": { "enabled": false },
+        }))
+        .await;
+    assert_eq!(code, 202, "{}", response);
+    index
+        .add_documents(
+            json!([
+                { "id": 1, "isbn": "9780134685991" },
+                { "id": 2, "isbn": "9780134685" },
+            ]),
+            None,
+        )
+        .await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.search_post(json!({ "q": "9780134685" })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["hits"], json!([{ "id": 2, "isbn": "9780134685" }]), "{}", response);
+}
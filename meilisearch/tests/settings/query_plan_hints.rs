@@ -0,0 +1,130 @@
+use once_cell::sync::Lazy;
+
+use crate::common::Server;
+use crate::json;
+
+static DOCUMENTS: Lazy<crate::common::Value> = Lazy::new(|| {
+    json!([
+        {
+            "id": 1,
+            "a": "Soup of the day",
+            "b": "many the fish",
+        },
+        {
+            "id": 2,
+            "a": "Soup of day",
+            "b": "many the lazy fish",
+        },
+        {
+            "id": 3,
+            "a": "the Soup of day",
+            "b": "many the fish",
+        },
+    ])
+});
+
+#[actix_rt::test]
+async fn query_plan_hints_are_always_displayed() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(
+        response["queryPlanHints"],
+        json!({ "filterResolution": "beforeWords", "proximityMaxCandidates": null })
+    );
+
+    let (response, code) = index
+        .update_settings(json!({
+            "queryPlanHints": { "filterResolution": "afterWords", "proximityMaxCandidates": 2 },
+        }))
+        .await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(1).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(
+        response["queryPlanHints"],
+        json!({ "filterResolution": "afterWords", "proximityMaxCandidates": 2 })
+    );
+
+    let (response, code) =
+        index.update_settings(json!({ "queryPlanHints": { "filterResolution": null } })).await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(2).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(
+        response["queryPlanHints"],
+        json!({ "filterResolution": "beforeWords", "proximityMaxCandidates": 2 })
+    );
+
+    let (response, code) = index.update_settings(json!({ "queryPlanHints": null })).await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(3).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(
+        response["queryPlanHints"],
+        json!({ "filterResolution": "beforeWords", "proximityMaxCandidates": null })
+    );
+}
+
+#[actix_rt::test]
+async fn search_with_query_plan_hints() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index
+        .update_settings(json!({
+            "filterableAttributes": ["id"],
+            "rankingRules": ["words", "typo", "proximity"],
+            "queryPlanHints": { "filterResolution": "afterWords", "proximityMaxCandidates": 2 },
+        }))
+        .await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(1).await;
+
+    // the filter is still applied when it is evaluated after the words
+    index
+        .search(json!({"q": "soup", "filter": "id > 1"}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["estimatedTotalHits"], 2, "{}", response);
+        })
+        .await;
+
+    index
+        .search(json!({"q": "lazy", "filter": "id > 2"}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["estimatedTotalHits"], 0, "{}", response);
+        })
+        .await;
+
+    // three documents match the query, more than the two allowed to be ranked by proximity
+    index
+        .search(json!({"q": "many the fish", "showRankingScoreDetails": true}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let details = &response["hits"][0]["_rankingScoreDetails"];
+            assert!(details.get("proximity").is_none(), "{}", response);
+            assert!(details.get("typo").is_some(), "{}", response);
+        })
+        .await;
+
+    // a single document matches the query and is ranked by proximity
+    index
+        .search(json!({"q": "lazy fish", "showRankingScoreDetails": true}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let details = &response["hits"][0]["_rankingScoreDetails"];
+            assert!(details.get("proximity").is_some(), "{}", response);
+        })
+        .await;
+}
@@ -0,0 +1,88 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::common::Server;
+use crate::json;
+
+const DAY: u64 = 86_400;
+
+#[actix_rt::test]
+async fn recency_ranking_rule_is_normalized() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .update_settings(json!({
+            "rankingRules": ["words", "recency( published_at ,half_life = 720h)"]
+        }))
+        .await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(0).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(
+        response["rankingRules"],
+        json!(["words", "recency(published_at, half_life=30d)"]),
+        "{}",
+        response
+    );
+}
+
+#[actix_rt::test]
+async fn recency_ranking_rule_must_be_valid() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let invalid_rules = [
+        "recency(published_at)",
+        "recency(published_at, half_life=30)",
+        "recency(published_at, half_life=-1d)",
+        "recency(published_at, half_life=1w)",
+        "recency(, half_life=30d)",
+        "recency(_geo, half_life=30d)",
+    ];
+    for rule in invalid_rules {
+        let (response, code) = index.update_settings(json!({ "rankingRules": [rule] })).await;
+        assert_eq!(code, 400, "{rule} {response}");
+        assert_eq!(response["code"], "invalid_settings_ranking_rules", "{rule} {response}");
+    }
+}
+
+#[actix_rt::test]
+async fn search_with_recency_ranking_rule() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let (response, code) = index
+        .update_settings(json!({
+            "rankingRules": ["recency(published_at, half_life=30d)", "words"]
+        }))
+        .await;
+    assert_eq!(code, 202, "{}", response);
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    index
+        .add_documents(
+            json!([
+                { "id": 1, "text": "the quick brown fox", "published_at": now - 90 * DAY },
+                { "id": 2, "text": "the quick", "published_at": now - DAY },
+                { "id": 3, "text": "the quick brown fox", "published_at": now - 2 * DAY },
+                { "id": 4, "text": "the quick brown fox" },
+            ]),
+            None,
+        )
+        .await;
+    index.wait_task(1).await;
+
+    let (response, code) =
+        index.search_post(json!({ "q": "quick brown fox", "showRankingScoreDetails": true })).await;
+    assert_eq!(code, 200, "{}", response);
+    let ids: Vec<_> =
+        response["hits"].as_array().unwrap().iter().map(|hit| hit["id"].clone()).collect();
+    assert_eq!(ids, vec![json!(3), json!(2), json!(1), json!(4)], "{}", response);
+    assert_eq!(
+        response["hits"][0]["_rankingScoreDetails"]["recency(published_at)"],
+        json!({ "order": 0, "score": 1.0 }),
+        "{}",
+        response
+    );
+}
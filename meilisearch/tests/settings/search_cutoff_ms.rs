@@ -0,0 +1,71 @@
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn search_cutoff_ms_is_only_displayed_when_set() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert!(response.get("searchCutoffMs").is_none(), "{}", response);
+
+    let (response, code) = index.update_settings(json!({ "searchCutoffMs": 150 })).await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(1).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["searchCutoffMs"], json!(150));
+
+    let (response, code) = index.update_settings(json!({ "searchCutoffMs": null })).await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(2).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert!(response.get("searchCutoffMs").is_none(), "{}", response);
+}
+
+#[actix_rt::test]
+async fn search_cutoff_ms_must_be_a_positive_integer() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.update_settings(json!({ "searchCutoffMs": -1 })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_settings_search_cutoff_ms", "{}", response);
+}
+
+#[actix_rt::test]
+async fn exceeded_search_cutoff_degrades_the_search() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index
+        .add_documents(
+            json!([
+                { "id": 1, "text": "hello" },
+                { "id": 2, "text": "hello world" },
+                { "id": 3, "text": "world" },
+            ]),
+            None,
+        )
+        .await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.search_post(json!({ "q": "hello world" })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert!(response.get("degraded").is_none(), "{}", response);
+
+    let (response, code) = index.update_settings(json!({ "searchCutoffMs": 0 })).await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(1).await;
+
+    // The documents are still returned, but they are not ranked.
+    let (response, code) = index.search_post(json!({ "q": "hello world" })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["degraded"], json!(true), "{}", response);
+    assert_eq!(response["hits"].as_array().unwrap().len(), 2, "{}", response);
+}
@@ -0,0 +1,154 @@
+use once_cell::sync::Lazy;
+
+use crate::common::Server;
+use crate::json;
+
+static DOCUMENTS: Lazy<crate::common::Value> = Lazy::new(|| {
+    json!([
+        { "id": 1, "name": "red shirt", "discontinued": false },
+        { "id": 2, "name": "red shirt with pockets", "discontinued": true },
+        { "id": 3, "name": "red hat", "discontinued": false },
+        { "id": 4, "name": "blue shirt", "discontinued": false },
+    ])
+});
+
+#[actix_rt::test]
+async fn search_exclusions_are_only_displayed_when_set() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert!(response.get("searchExclusions").is_none(), "{}", response);
+
+    let (response, code) =
+        index.update_settings(json!({ "searchExclusions": { "documentIds": ["1", "2"] } })).await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(1).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(
+        response["searchExclusions"],
+        json!({ "documentIds": ["1", "2"], "filter": null, "mode": "exclude" })
+    );
+
+    let (response, code) = index.update_settings(json!({ "searchExclusions": null })).await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(2).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert!(response.get("searchExclusions").is_none(), "{}", response);
+}
+
+#[actix_rt::test]
+async fn search_exclusions_filter_must_be_filterable() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index
+        .update_settings(json!({ "searchExclusions": { "filter": "discontinued = true" } }))
+        .await;
+    assert_eq!(code, 202, "{}", response);
+    let response = index.wait_task(1).await;
+    assert_eq!(response["status"], "failed", "{}", response);
+
+    let (response, code) = index
+        .update_settings(json!({
+            "filterableAttributes": ["discontinued"],
+            "searchExclusions": { "filter": "discontinued = true" },
+        }))
+        .await;
+    assert_eq!(code, 202, "{}", response);
+    let response = index.wait_task(2).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+}
+
+#[actix_rt::test]
+async fn search_with_excluded_documents() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index
+        .update_settings(json!({
+            "filterableAttributes": ["discontinued"],
+            "searchExclusions": { "documentIds": ["3"], "filter": "discontinued = true" },
+        }))
+        .await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(1).await;
+
+    index
+        .search(json!({"q": "red"}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["estimatedTotalHits"], 1, "{}", response);
+            assert_eq!(response["hits"][0]["id"], 1, "{}", response);
+        })
+        .await;
+
+    // the documents added afterward are excluded too
+    index
+        .add_documents(json!([{ "id": 5, "name": "red socks", "discontinued": true }]), None)
+        .await;
+    index.wait_task(2).await;
+
+    index
+        .search(json!({"q": "red", "page": 1}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["totalHits"], 1, "{}", response);
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_with_deboosted_documents() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index
+        .update_settings(json!({
+            "searchExclusions": { "documentIds": ["1"], "mode": "deboost" },
+        }))
+        .await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(1).await;
+
+    // the best match is returned after all the other documents
+    index
+        .search(json!({"q": "red shirt"}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["estimatedTotalHits"], 3, "{}", response);
+            assert_eq!(response["hits"].as_array().unwrap().len(), 3, "{}", response);
+            assert_eq!(response["hits"][2]["id"], 1, "{}", response);
+        })
+        .await;
+
+    // the pagination goes through both sets of documents
+    index
+        .search(json!({"q": "red shirt", "offset": 1, "limit": 1}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["hits"].as_array().unwrap().len(), 1, "{}", response);
+            assert_ne!(response["hits"][0]["id"], 1, "{}", response);
+        })
+        .await;
+    index
+        .search(json!({"q": "red shirt", "offset": 2, "limit": 5}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(
+                response["hits"],
+                json!([{ "id": 1, "name": "red shirt", "discontinued": false }]),
+                "{}",
+                response
+            );
+        })
+        .await;
+}
@@ -0,0 +1,131 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::Server;
+use crate::json;
+
+static DOCUMENTS: Lazy<crate::common::Value> = Lazy::new(|| {
+    json!([
+        { "id": 1, "name": "shirt blue", "price": 30, "inStock": true },
+        { "id": 2, "name": "shirt red", "price": 20, "inStock": false },
+        { "id": 3, "name": "shirt green", "price": 10, "inStock": true },
+        { "id": 4, "name": "shirt pocket", "price": 5, "inStock": true },
+    ])
+});
+
+static TEMPLATES: Lazy<crate::common::Value> = Lazy::new(|| {
+    json!({
+        "storefront": {
+            "filter": "inStock = true",
+            "sort": ["price:asc"],
+            "facets": ["inStock"],
+            "limit": 2,
+            "attributesToHighlight": ["name"],
+            "highlightPreTag": "<b>",
+            "highlightPostTag": "</b>",
+        },
+    })
+});
+
+async fn add_documents_with_templates(server: &Server) {
+    let index = server.index("test");
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index
+        .update_settings(json!({
+            "filterableAttributes": ["inStock"],
+            "sortableAttributes": ["price"],
+            "searchTemplates": TEMPLATES.clone(),
+        }))
+        .await;
+    assert_eq!(code, 202, "{}", response);
+    let response = index.wait_task(1).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+}
+
+#[actix_rt::test]
+async fn search_templates_are_only_displayed_when_set() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert!(response.get("searchTemplates").is_none(), "{}", response);
+
+    let (response, code) =
+        index.update_settings(json!({ "searchTemplates": TEMPLATES.clone() })).await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(1).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["searchTemplates"], TEMPLATES.clone());
+
+    let (response, code) = index.update_settings(json!({ "searchTemplates": null })).await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(2).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert!(response.get("searchTemplates").is_none(), "{}", response);
+}
+
+#[actix_rt::test]
+async fn search_templates_only_accept_the_template_parameters() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .update_settings(json!({ "searchTemplates": { "storefront": { "q": "shirt" } } }))
+        .await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_settings_search_templates", "{}", response);
+}
+
+#[actix_rt::test]
+async fn search_with_a_template() {
+    let server = Server::new().await;
+    add_documents_with_templates(&server).await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_with_template("storefront", json!({ "q": "shirt" })).await;
+    assert_eq!(code, 200, "{}", response);
+    let ids: Vec<_> =
+        response["hits"].as_array().unwrap().iter().map(|h| h["id"].clone()).collect();
+    assert_eq!(ids, vec![json!(4), json!(3)], "{}", response);
+    assert_eq!(response["hits"][0]["_formatted"]["name"], "<b>shirt</b> pocket", "{}", response);
+    assert_eq!(response["facetDistribution"]["inStock"]["true"], 3, "{}", response);
+
+    // the parameters of the request take precedence over the ones of the template
+    let (response, code) = index
+        .search_with_template(
+            "storefront",
+            json!({ "q": "shirt", "limit": 3, "sort": ["price:desc"] }),
+        )
+        .await;
+    assert_eq!(code, 200, "{}", response);
+    let ids: Vec<_> =
+        response["hits"].as_array().unwrap().iter().map(|h| h["id"].clone()).collect();
+    assert_eq!(ids, vec![json!(1), json!(3), json!(4)], "{}", response);
+}
+
+#[actix_rt::test]
+async fn search_with_an_unknown_template() {
+    let server = Server::new().await;
+    add_documents_with_templates(&server).await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_with_template("backoffice", json!({ "q": "shirt" })).await;
+    assert_eq!(code, 400, "{}", response);
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Search template `backoffice` not found. Available search templates are: `storefront`.",
+      "code": "invalid_search_template",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_template"
+    }
+    "###);
+}
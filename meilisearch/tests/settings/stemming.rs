@@ -0,0 +1,78 @@
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn stemming_is_only_displayed_when_set() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert!(response.get("stemming").is_none(), "{}", response);
+
+    let (response, code) = index.update_settings(json!({ "stemming": ["fra", "eng"] })).await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(1).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["stemming"], json!(["eng", "fra"]), "{}", response);
+
+    let (response, code) = index.update_settings(json!({ "stemming": null })).await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(2).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert!(response.get("stemming").is_none(), "{}", response);
+}
+
+#[actix_rt::test]
+async fn stemming_must_use_languages_with_a_stemmer() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    for locale in ["english", "jpn"] {
+        let (response, code) = index.update_settings(json!({ "stemming": [locale] })).await;
+        assert_eq!(code, 400, "{}", response);
+        assert_eq!(response["code"], "invalid_settings_stemming", "{}", response);
+    }
+}
+
+#[actix_rt::test]
+async fn search_matches_the_other_forms_of_the_words() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let (response, code) = index
+        .update_settings(json!({
+            "localizedAttributes": [{ "attributePatterns": ["*"], "locales": ["eng"] }],
+            "stemming": ["eng"],
+        }))
+        .await;
+    assert_eq!(code, 202, "{}", response);
+    index
+        .add_documents(
+            json!([
+                { "id": 1, "title": "The cat runs" },
+                { "id": 2, "title": "The rune stone" },
+            ]),
+            None,
+        )
+        .await;
+    index.wait_task(1).await;
+
+    let (response, code) = index
+        .search_post(json!({
+            "q": "running ",
+            "locales": ["eng"],
+            "attributesToHighlight": ["title"],
+        }))
+        .await;
+    assert_eq!(code, 200, "{}", response);
+    let hits = response["hits"].as_array().unwrap();
+    assert_eq!(hits.len(), 1, "{}", response);
+    assert_eq!(hits[0]["id"], json!(1), "{}", response);
+    assert_eq!(hits[0]["_formatted"]["title"], json!("The cat <em>runs</em>"), "{}", response);
+}
@@ -0,0 +1,61 @@
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn set_localized_stop_words() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let stop_words = json!({ "deu": ["die", "der"], "fra": ["le", "la"] });
+    let (response, code) = index.update_settings(json!({ "stopWords": stop_words })).await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(0).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["stopWords"], stop_words, "{}", response);
+
+    // a global list replaces the localized stop words
+    let (response, code) = index.update_settings_stop_words(json!(["the"])).await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(1).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["stopWords"], json!(["the"]), "{}", response);
+}
+
+#[actix_rt::test]
+async fn localized_stop_words_only_apply_to_their_language() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let (response, code) = index
+        .update_settings(json!({
+            "localizedAttributes": [
+                { "attributePatterns": ["*_de"], "locales": ["deu"] },
+                { "attributePatterns": ["*_en"], "locales": ["eng"] },
+            ],
+            "stopWords": { "deu": ["die"] },
+        }))
+        .await;
+    assert_eq!(code, 202, "{}", response);
+    index
+        .add_documents(
+            json!([
+                { "id": 1, "title_en": "Die Hard" },
+                { "id": 2, "title_de": "Die Brücke" },
+            ]),
+            None,
+        )
+        .await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.search_post(json!({ "q": "die ", "locales": ["eng"] })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["hits"], json!([{ "id": 1, "title_en": "Die Hard" }]), "{}", response);
+
+    // `die` is ignored in German, the query is handled as a placeholder search
+    let (response, code) = index.search_post(json!({ "q": "die ", "locales": ["deu"] })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["hits"].as_array().unwrap().len(), 2, "{}", response);
+}
@@ -0,0 +1,50 @@
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn disable_on_numbers_must_be_a_boolean() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) =
+        index.update_settings_typo_tolerance(json!({ "disableOnNumbers": "yes" })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(
+        response["code"], "invalid_settings_typo_tolerance_disable_on_numbers",
+        "{}",
+        response
+    );
+}
+
+#[actix_rt::test]
+async fn search_without_typos_on_numbers() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index
+        .add_documents(
+            json!([
+                { "id": 1, "reference": "A1023" },
+                { "id": 2, "reference": "A1024" },
+            ]),
+            None,
+        )
+        .await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.search_post(json!({ "q": "A1023" })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["hits"].as_array().unwrap().len(), 2, "{}", response);
+
+    let (response, code) =
+        index.update_settings_typo_tolerance(json!({ "disableOnNumbers": true })).await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(1).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["typoTolerance"]["disableOnNumbers"], json!(true), "{}", response);
+
+    let (response, code) = index.search_post(json!({ "q": "A1023" })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["hits"], json!([{ "id": 1, "reference": "A1023" }]), "{}", response);
+}
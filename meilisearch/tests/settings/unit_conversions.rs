@@ -0,0 +1,175 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::Server;
+use crate::json;
+
+static DOCUMENTS: Lazy<crate::common::Value> = Lazy::new(|| {
+    json!([
+        { "id": 1, "name": "shirt", "price": 90, "currency": "USD" },
+        { "id": 2, "name": "hat", "price": 100, "currency": "EUR" },
+        { "id": 3, "name": "socks", "price": 5000, "currency": "JPY" },
+        { "id": 4, "name": "scarf", "price": 40, "currency": "GBP" },
+        { "id": 5, "name": "gloves", "price": 10 },
+    ])
+});
+
+static CONVERSIONS: Lazy<crate::common::Value> = Lazy::new(|| {
+    json!({
+        "price": {
+            "unitAttribute": "currency",
+            "rates": { "usd": 1.0, "eur": 1.1, "jpy": 0.01 },
+        },
+    })
+});
+
+async fn add_documents_with_conversions(server: &Server) {
+    let index = server.index("test");
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index
+        .update_settings(json!({
+            "filterableAttributes": ["price", "currency"],
+            "sortableAttributes": ["id", "price", "currency"],
+            "unitConversions": CONVERSIONS.clone(),
+        }))
+        .await;
+    assert_eq!(code, 202, "{}", response);
+    let response = index.wait_task(1).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+}
+
+#[actix_rt::test]
+async fn unit_conversions_are_only_displayed_when_set() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert!(response.get("unitConversions").is_none(), "{}", response);
+
+    let (response, code) =
+        index.update_settings(json!({ "unitConversions": CONVERSIONS.clone() })).await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(1).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["unitConversions"], CONVERSIONS.clone());
+
+    let (response, code) = index.update_settings(json!({ "unitConversions": null })).await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(2).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert!(response.get("unitConversions").is_none(), "{}", response);
+}
+
+#[actix_rt::test]
+async fn unit_conversion_rates_must_be_positive() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index
+        .update_settings(json!({
+            "unitConversions": { "price": { "unitAttribute": "currency", "rates": { "usd": 0 } } },
+        }))
+        .await;
+    assert_eq!(code, 202, "{}", response);
+    let response = index.wait_task(1).await;
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "The rate of the `usd` unit in the conversion of `price` must be a positive number, but found `0`.",
+      "code": "invalid_settings_unit_conversions",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_unit_conversions"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({
+            "unitConversions": { "price": { "unitAttribute": "currency", "rates": {} } },
+        }))
+        .await;
+    assert_eq!(code, 202, "{}", response);
+    let response = index.wait_task(2).await;
+    assert_eq!(response["error"]["code"], "invalid_settings_unit_conversions", "{}", response);
+}
+
+#[actix_rt::test]
+async fn filter_on_a_converted_field() {
+    let server = Server::new().await;
+    add_documents_with_conversions(&server).await;
+    let index = server.index("test");
+
+    // 90 USD, 110 USD and 50 USD, the documents without a known unit never match
+    index
+        .search(json!({ "filter": "price:usd < 100", "sort": ["id:asc"] }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let ids: Vec<_> =
+                response["hits"].as_array().unwrap().iter().map(|h| h["id"].clone()).collect();
+            assert_eq!(ids, vec![json!(1), json!(3)], "{}", response);
+        })
+        .await;
+
+    index
+        .search(json!({ "filter": "price:EUR 50 TO 100", "sort": ["id:asc"] }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let ids: Vec<_> =
+                response["hits"].as_array().unwrap().iter().map(|h| h["id"].clone()).collect();
+            assert_eq!(ids, vec![json!(1), json!(2)], "{}", response);
+        })
+        .await;
+
+    let (response, code) = index.search_post(json!({ "filter": "price:gbp < 100" })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_search_filter", "{}", response);
+}
+
+#[actix_rt::test]
+async fn sort_on_a_converted_field() {
+    let server = Server::new().await;
+    add_documents_with_conversions(&server).await;
+    let index = server.index("test");
+
+    // 90 USD, 110 USD and 50 USD, then the documents without a known unit
+    index
+        .search(json!({ "sort": ["price:usd:asc"] }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let ids: Vec<_> =
+                response["hits"].as_array().unwrap().iter().map(|h| h["id"].clone()).collect();
+            assert_eq!(ids[..3], [json!(3), json!(1), json!(2)], "{}", response);
+        })
+        .await;
+
+    index
+        .search(
+            json!({ "sort": ["price:usd:desc"], "showRankingScoreDetails": true }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                let ids: Vec<_> =
+                    response["hits"].as_array().unwrap().iter().map(|h| h["id"].clone()).collect();
+                assert_eq!(ids[..3], [json!(2), json!(1), json!(3)], "{}", response);
+                let details = &response["hits"][0]["_rankingScoreDetails"]["price:usd:desc"];
+                assert_eq!(details["value"].as_f64().unwrap().round(), 110.0, "{}", response);
+            },
+        )
+        .await;
+
+    let (response, code) = index.search_post(json!({ "sort": ["price:gbp:asc"] })).await;
+    assert_eq!(code, 400, "{}", response);
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unit `gbp` can't be used to sort on `price`. Available units are: `eur, jpy, usd`.",
+      "code": "invalid_search_sort",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_sort"
+    }
+    "###);
+}
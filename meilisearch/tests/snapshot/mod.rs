@@ -4,6 +4,7 @@ use actix_rt::time::sleep;
 use meili_snap::{json_string, snapshot};
 use meilisearch::option::ScheduleSnapshot;
 use meilisearch::Opt;
+use meilisearch_types::snapshot::read_manifest;
 
 use crate::common::server::default_settings;
 use crate::common::{GetAllDocumentsOptions, Server};
@@ -184,3 +185,87 @@ async fn perform_on_demand_snapshot() {
         server.index("doggo").settings(),
     );
 }
+
+#[actix_rt::test]
+async fn snapshot_retention_deletes_the_oldest_snapshots() {
+    let temp = tempfile::tempdir().unwrap();
+    let snapshot_dir = tempfile::tempdir().unwrap();
+
+    let options = Opt {
+        snapshot_dir: snapshot_dir.path().to_owned(),
+        snapshot_retention: Some(2.try_into().unwrap()),
+        ..default_settings(temp.path())
+    };
+    let server = Server::new_with_options(options).await.unwrap();
+
+    for _ in 0..3 {
+        let (task, code) = server.create_snapshot().await;
+        snapshot!(code, @"202 Accepted");
+        let task = server.wait_task(task.uid()).await;
+        snapshot!(task["status"], @r###""succeeded""###);
+    }
+
+    let snapshots: Vec<String> = std::fs::read_dir(&snapshot_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_str().unwrap().to_string())
+        .collect();
+    assert_eq!(snapshots.len(), 2, "{snapshots:?}");
+    assert!(
+        snapshots.iter().all(|name| name.starts_with("db-") && name.ends_with(".snapshot")),
+        "{snapshots:?}"
+    );
+}
+
+#[actix_rt::test]
+async fn incremental_snapshot_only_contains_the_updated_indexes() {
+    let temp = tempfile::tempdir().unwrap();
+    let snapshot_dir = tempfile::tempdir().unwrap();
+
+    let options = Opt {
+        snapshot_dir: snapshot_dir.path().to_owned(),
+        snapshot_incremental: true,
+        ..default_settings(temp.path())
+    };
+    let server = Server::new_with_options(options).await.unwrap();
+
+    let (task, _) =
+        server.index("cats").add_documents(json!([{ "id": 1, "name": "jorts" }]), None).await;
+    server.wait_task(task.uid()).await;
+    let (task, _) =
+        server.index("dogs").add_documents(json!([{ "id": 1, "name": "jean" }]), None).await;
+    server.wait_task(task.uid()).await;
+    let (task, _) = server.create_snapshot().await;
+    server.wait_task(task.uid()).await;
+
+    let (task, _) =
+        server.index("dogs").add_documents(json!([{ "id": 2, "name": "bob" }]), None).await;
+    server.wait_task(task.uid()).await;
+    let (task, _) = server.create_snapshot().await;
+    let task = server.wait_task(task.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+
+    let mut snapshots: Vec<String> = std::fs::read_dir(&snapshot_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_str().unwrap().to_string())
+        .collect();
+    snapshots.sort_unstable();
+    assert_eq!(snapshots.len(), 2, "{snapshots:?}");
+
+    // the unchanged cats index is referenced from the first snapshot
+    let manifest = read_manifest(snapshot_dir.path().join(&snapshots[1])).unwrap().unwrap();
+    assert_eq!(manifest.base.as_ref(), Some(&snapshots[0]));
+    let mut sources: Vec<_> = manifest.indexes.into_values().collect();
+    sources.sort_unstable();
+    assert_eq!(sources, snapshots);
+
+    let temp = tempfile::tempdir().unwrap();
+    let snapshot_path = snapshot_dir.path().join(&snapshots[1]);
+    let options = Opt { import_snapshot: Some(snapshot_path), ..default_settings(temp.path()) };
+    let snapshot_server = Server::new_with_options(options).await.unwrap();
+
+    verify_snapshot!(server, snapshot_server, |server| =>
+        server.list_indexes(None, None),
+        server.index("cats").get_all_documents(GetAllDocumentsOptions::default()),
+        server.index("dogs").get_all_documents(GetAllDocumentsOptions::default()),
+    );
+}
@@ -1,7 +1,9 @@
+use meili_snap::{json_string, snapshot};
+use meilisearch::Opt;
 use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 
-use crate::common::Server;
+use crate::common::{default_settings, Server};
 use crate::json;
 
 #[actix_rt::test]
@@ -24,6 +26,29 @@ async fn test_healthyness() {
     assert_eq!(response["status"], "available");
 }
 
+#[actix_rt::test]
+async fn liveness_and_readiness() {
+    let server = Server::new().await;
+
+    let (response, status_code) = server.service.get("/health/live").await;
+    assert_eq!(status_code, 200);
+    assert_eq!(response["status"], "available");
+
+    let (response, status_code) = server.service.get("/health/ready").await;
+    assert_eq!(status_code, 200);
+    snapshot!(json_string!(response), @r###"
+    {
+      "status": "available",
+      "checks": {
+        "tasksDatabase": true,
+        "authDatabase": true,
+        "scheduler": true,
+        "dumpImportFinished": true
+      }
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn stats() {
     let server = Server::new().await;
@@ -74,3 +99,34 @@ async fn stats() {
     assert_eq!(response["indexes"]["test"]["fieldDistribution"]["name"], 1);
     assert_eq!(response["indexes"]["test"]["fieldDistribution"]["age"], 1);
 }
+
+#[actix_rt::test]
+async fn stats_flag_the_indexes_exceeding_the_alert_thresholds() {
+    let temp = tempfile::tempdir().unwrap();
+    let options = Opt { index_alert_documents_threshold: Some(1), ..default_settings(temp.path()) };
+    let server = Server::new_with_options(options).await.unwrap();
+
+    let small = server.index("small");
+    let (task, _) = small.add_documents(json!([{ "id": 1 }]), None).await;
+    small.wait_task(task.uid()).await;
+    let large = server.index("large");
+    let (task, _) = large.add_documents(json!([{ "id": 1 }, { "id": 2 }]), None).await;
+    large.wait_task(task.uid()).await;
+
+    let (response, code) = server.stats().await;
+    assert_eq!(code, 200);
+    assert_eq!(response["indexes"]["small"]["exceedsAlertThresholds"], false);
+    assert_eq!(response["indexes"]["large"]["exceedsAlertThresholds"], true);
+
+    let (response, code) = large.stats().await;
+    assert_eq!(code, 200);
+    assert_eq!(response["exceedsAlertThresholds"], true);
+
+    // the flag is only present when a threshold is defined
+    let server = Server::new().await;
+    let index = server.index("test");
+    let (task, _) = index.add_documents(json!([{ "id": 1 }, { "id": 2 }]), None).await;
+    index.wait_task(task.uid()).await;
+    let (response, _) = index.stats().await;
+    assert!(response.get("exceedsAlertThresholds").is_none());
+}
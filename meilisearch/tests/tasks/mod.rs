@@ -2,10 +2,11 @@ mod errors;
 mod webhook;
 
 use meili_snap::insta::assert_json_snapshot;
+use meilisearch::Opt;
 use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 
-use crate::common::Server;
+use crate::common::{default_settings, Server};
 use crate::json;
 
 #[actix_rt::test]
@@ -47,6 +48,39 @@ async fn get_task_status() {
     // TODO check response format, as per #48
 }
 
+#[actix_rt::test]
+async fn get_task_waiting_for_it_to_finish() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let (response, code) = index
+        .add_documents(serde_json::from_str(include_str!("../assets/test_set.json")).unwrap(), None)
+        .await;
+    assert_eq!(code, 202, "{response}");
+
+    let (response, code) = server.service.get("/tasks/0?waitMs=30000").await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["status"], "succeeded", "{response}");
+
+    // an unknown task is reported right away.
+    let (response, code) = server.service.get("/tasks/1?waitMs=10").await;
+    assert_eq!(code, 404, "{response}");
+
+    let (response, code) = server.service.get("/tasks/0?waitMs=120000").await;
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
+    {
+      "message": "`waitMs` must be at most 60000 milliseconds, but found `120000`.",
+      "code": "invalid_task_wait_ms",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_task_wait_ms"
+    }
+    "###);
+
+    let (response, code) = server.service.get("/tasks/0?waitMs=soon").await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(response["code"], "invalid_task_wait_ms", "{response}");
+}
+
 #[actix_rt::test]
 async fn list_tasks() {
     let server = Server::new().await;
@@ -1098,3 +1132,92 @@ async fn test_summarized_dump_creation() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn no_recovery_report_after_a_clean_start() {
+    let server = Server::new().await;
+    let (response, code) = server.service.get("/recovery-report").await;
+    assert_eq!(code, 404, "{response}");
+    assert_eq!(response["code"], "recovery_report_not_found", "{response}");
+}
+
+#[actix_rt::test]
+async fn succeeded_tasks_carry_the_warnings_of_their_index() {
+    let temp = tempfile::tempdir().unwrap();
+    let options = Opt { index_alert_documents_threshold: Some(1), ..default_settings(temp.path()) };
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("test");
+
+    let (task, _) = index.add_documents(json!([{ "id": 1 }]), None).await;
+    index.wait_task(task.uid()).await;
+    let (task, _) = index.get_task(task.uid()).await;
+    assert!(task["details"].get("warnings").is_none(), "{task}");
+
+    let (task, _) = index.add_documents(json!([{ "id": 2 }]), None).await;
+    index.wait_task(task.uid()).await;
+    let (task, _) = index.get_task(task.uid()).await;
+    assert_json_snapshot!(task["details"],
+        { ".warnings[].databaseSize" => "[size]" },
+        @r###"
+    {
+      "receivedDocuments": 1,
+      "indexedDocuments": 1,
+      "warnings": [
+        {
+          "type": "indexAlertThresholdExceeded",
+          "numberOfDocuments": 2,
+          "databaseSize": "[size]",
+          "maxNumberOfDocuments": 1,
+          "maxDatabaseSize": null
+        }
+      ]
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn retry_a_failed_task() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let (task, _) = index.create(None).await;
+    let succeeded = index.wait_task(task.uid()).await;
+
+    // the primary key can't be inferred
+    let (task, _) = index.add_documents(json!([{ "name": "kefir" }]), None).await;
+    let failed = index.wait_task(task.uid()).await;
+    assert_eq!(failed["status"], "failed", "{failed}");
+
+    let (task, code) = server.retry_task(succeeded.uid()).await;
+    assert_eq!(code, 400, "{task}");
+    assert_eq!(task["code"], "task_not_retryable", "{task}");
+    let (task, code) = server.retry_task(1000).await;
+    assert_eq!(code, 404, "{task}");
+
+    let (task, _) = index.update(Some("name")).await;
+    index.wait_task(task.uid()).await;
+
+    let (task, code) = server.retry_task(failed.uid()).await;
+    assert_eq!(code, 202, "{task}");
+    let task = index.wait_task(task.uid()).await;
+    assert_json_snapshot!(task,
+        { ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
+        @r###"
+    {
+      "uid": 3,
+      "indexUid": "test",
+      "status": "succeeded",
+      "type": "documentAdditionOrUpdate",
+      "canceledBy": null,
+      "retriedFrom": 1,
+      "details": {
+        "receivedDocuments": 1,
+        "indexedDocuments": 1
+      },
+      "error": null,
+      "duration": "[duration]",
+      "enqueuedAt": "[date]",
+      "startedAt": "[date]",
+      "finishedAt": "[date]"
+    }
+    "###);
+}
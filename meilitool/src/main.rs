@@ -188,7 +188,14 @@ fn export_a_dump(
         }
     };
 
-    let dump = DumpWriter::new(instance_uid).context("While creating a new dump")?;
+    let dump_uid = started_at.format(format_description!(
+        "[year repr:full][month repr:numerical][day padding:zero]-[hour padding:zero][minute padding:zero][second padding:zero][subsecond digits:3]"
+    )).unwrap();
+
+    let path = dump_dir.join(format!("{}.dump", dump_uid));
+    let file = File::create(&path)?;
+    let mut dump =
+        DumpWriter::new(instance_uid, BufWriter::new(file)).context("While creating a new dump")?;
     let file_store =
         FileStore::new(db_path.join("update_files")).context("While opening the FileStore")?;
 
@@ -300,13 +307,7 @@ fn export_a_dump(
     // We will not dump experimental feature settings
     eprintln!("The tool is not dumping experimental features, please set them by hand afterward");
 
-    let dump_uid = started_at.format(format_description!(
-        "[year repr:full][month repr:numerical][day padding:zero]-[hour padding:zero][minute padding:zero][second padding:zero][subsecond digits:3]"
-    )).unwrap();
-
-    let path = dump_dir.join(format!("{}.dump", dump_uid));
-    let file = File::create(&path)?;
-    dump.persist_to(BufWriter::new(file))?;
+    dump.finish()?;
 
     eprintln!("Dump exported at path {:?}", path.display());
 
@@ -58,6 +58,7 @@ fn main() -> Result<(), Box<dyn Error>> {
                 milli::score_details::ScoringStrategy::Skip,
                 false,
                 universe,
+                None,
                 &None,
                 GeoSortStrategy::default(),
                 0,
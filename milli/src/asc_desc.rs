@@ -41,6 +41,9 @@ impl From<AscDescError> for CriterionError {
             AscDescError::ReservedKeyword { name } if name.starts_with("_geoRadius") => {
                 CriterionError::ReservedNameForFilter { name: "_geoRadius".to_string() }
             }
+            AscDescError::ReservedKeyword { name } if name.starts_with("_random") => {
+                CriterionError::ReservedNameForSort { name: "_random".to_string() }
+            }
             AscDescError::ReservedKeyword { name } if name.starts_with("_geoBoundingBox") => {
                 CriterionError::ReservedNameForFilter { name: "_geoBoundingBox".to_string() }
             }
@@ -53,12 +56,22 @@ impl From<AscDescError> for CriterionError {
 pub enum Member {
     Field(String),
     Geo([f64; 2]),
+    /// A pseudo-random order of the documents, the same seed always gives the same order.
+    Random(u64),
 }
 
 impl FromStr for Member {
     type Err = AscDescError;
 
     fn from_str(text: &str) -> Result<Member, Self::Err> {
+        if let Some(seed) = text.strip_prefix("_random(").and_then(|text| text.strip_suffix(')')) {
+            return seed
+                .trim()
+                .parse()
+                .map(Member::Random)
+                .map_err(|_| AscDescError::ReservedKeyword { name: text.to_string() });
+        }
+
         match text.strip_prefix("_geoPoint(").and_then(|text| text.strip_suffix(')')) {
             Some(point) => {
                 let (lat, lng) = point
@@ -83,6 +96,7 @@ impl FromStr for Member {
                     || text.starts_with("_geoBoundingBox(")
                     || text.starts_with("_geo(")
                     || text.starts_with("_geoDistance(")
+                    || text.starts_with("_random(")
                 {
                     return Err(AscDescError::ReservedKeyword { name: text.to_string() })?;
                 }
@@ -97,6 +111,7 @@ impl fmt::Display for Member {
         match self {
             Member::Field(name) => f.write_str(name),
             Member::Geo([lat, lng]) => write!(f, "_geoPoint({}, {})", lat, lng),
+            Member::Random(seed) => write!(f, "_random({})", seed),
         }
     }
 }
@@ -105,22 +120,26 @@ impl Member {
     pub fn field(&self) -> Option<&str> {
         match self {
             Member::Field(field) => Some(field),
-            Member::Geo(_) => None,
+            Member::Geo(_) | Member::Random(_) => None,
         }
     }
 
     pub fn geo_point(&self) -> Option<&[f64; 2]> {
         match self {
             Member::Geo(point) => Some(point),
-            Member::Field(_) => None,
+            Member::Field(_) | Member::Random(_) => None,
         }
     }
 }
 
+/// The documents that don't contain the sorted field are ranked after the others, unless the
+/// criterion is suffixed by `:nulls_first`.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum AscDesc {
     Asc(Member),
     Desc(Member),
+    AscNullsFirst(Member),
+    DescNullsFirst(Member),
 }
 
 impl AscDesc {
@@ -128,22 +147,54 @@ impl AscDesc {
         match self {
             AscDesc::Asc(member) => member,
             AscDesc::Desc(member) => member,
+            AscDesc::AscNullsFirst(member) => member,
+            AscDesc::DescNullsFirst(member) => member,
         }
     }
 
     pub fn field(&self) -> Option<&str> {
         self.member().field()
     }
+
+    pub fn is_ascending(&self) -> bool {
+        matches!(self, AscDesc::Asc(_) | AscDesc::AscNullsFirst(_))
+    }
+
+    /// Whether the documents without a value come before the ones with a value.
+    pub fn nulls_first(&self) -> bool {
+        matches!(self, AscDesc::AscNullsFirst(_) | AscDesc::DescNullsFirst(_))
+    }
 }
 
 impl FromStr for AscDesc {
     type Err = AscDescError;
 
     fn from_str(text: &str) -> Result<AscDesc, Self::Err> {
-        match text.rsplit_once(':') {
-            Some((left, "asc")) => Ok(AscDesc::Asc(left.parse()?)),
-            Some((left, "desc")) => Ok(AscDesc::Desc(left.parse()?)),
-            _ => Err(AscDescError::InvalidSyntax { name: text.to_string() }),
+        // the random order doesn't need a direction
+        if text.starts_with("_random(") && text.ends_with(')') {
+            return Ok(AscDesc::Asc(text.parse()?));
+        }
+
+        let (text_asc_desc, nulls_first) = match text.rsplit_once(':') {
+            Some((left, "nulls_first")) => (left, true),
+            Some((left, "nulls_last")) => (left, false),
+            _ => (text, false),
+        };
+        let (member, ascending): (Member, _) = match text_asc_desc.rsplit_once(':') {
+            Some((left, "asc")) => (left.parse()?, true),
+            Some((left, "desc")) => (left.parse()?, false),
+            _ => return Err(AscDescError::InvalidSyntax { name: text.to_string() }),
+        };
+        match (member, ascending, nulls_first) {
+            // every document is at some distance of a geo point and has a random position,
+            // only the fields can be missing
+            (Member::Geo(_) | Member::Random(_), _, true) => {
+                Err(AscDescError::InvalidSyntax { name: text.to_string() })
+            }
+            (member, true, false) => Ok(AscDesc::Asc(member)),
+            (member, false, false) => Ok(AscDesc::Desc(member)),
+            (member, true, true) => Ok(AscDesc::AscNullsFirst(member)),
+            (member, false, true) => Ok(AscDesc::DescNullsFirst(member)),
         }
     }
 }
@@ -155,7 +206,13 @@ pub enum SortError {
     #[error("Invalid syntax for the geo parameter: expected expression formated like \
                     `_geoPoint(latitude, longitude)` and ending by `:asc` or `:desc`, found `{name}`.")]
     BadGeoPointUsage { name: String },
-    #[error("Invalid syntax for the sort parameter: expected expression ending by `:asc` or `:desc`, found `{name}`.")]
+    #[error(
+        "Invalid syntax for the random sort: expected expression written like \
+`_random(seed)` with a positive integer seed, found `{name}`."
+    )]
+    BadRandomUsage { name: String },
+    #[error("Invalid syntax for the sort parameter: expected expression ending by `:asc` or \
+                    `:desc`, optionally followed by `:nulls_first` or `:nulls_last`, found `{name}`.")]
     InvalidName { name: String },
     #[error("`{name}` is a reserved keyword and thus can't be used as a sort expression.")]
     ReservedName { name: String },
@@ -175,6 +232,9 @@ impl From<AscDescError> for SortError {
             AscDescError::ReservedKeyword { name } if name.starts_with("_geoPoint") => {
                 SortError::BadGeoPointUsage { name }
             }
+            AscDescError::ReservedKeyword { name } if name.starts_with("_random") => {
+                SortError::BadRandomUsage { name }
+            }
             AscDescError::ReservedKeyword { name } if &name == "_geo" => {
                 SortError::ReservedNameForSettings { name }
             }
@@ -222,6 +282,15 @@ mod tests {
             ("_geoPoint(42.0002, 59.895):desc", Desc(Geo([42.0002, 59.895]))),
             ("_geoPoint(42., 59.):desc", Desc(Geo([42., 59.]))),
             ("truc(12, 13):desc", Desc(Field(S("truc(12, 13)")))),
+            ("_random(42)", Asc(Random(42))),
+            ("_random( 0 )", Asc(Random(0))),
+            ("_random(42):desc", Desc(Random(42))),
+            ("_random:asc", Asc(Field(S("_random")))),
+            ("truc:asc:nulls_last", Asc(Field(S("truc")))),
+            ("truc:asc:nulls_first", AscNullsFirst(Field(S("truc")))),
+            ("truc:desc:nulls_first", DescNullsFirst(Field(S("truc")))),
+            ("a:b:desc:nulls_first", DescNullsFirst(Field(S("a:b")))),
+            ("_geoPoint(42, 59):asc:nulls_last", Asc(Geo([42., 59.]))),
         ];
 
         for (req, expected) in valid_req {
@@ -241,6 +310,17 @@ mod tests {
             ("truc:deesc", InvalidSyntax { name: S("truc:deesc") }),
             ("truc:asc:deesc", InvalidSyntax { name: S("truc:asc:deesc") }),
             ("42desc", InvalidSyntax { name: S("42desc") }),
+            ("truc:nulls_first", InvalidSyntax { name: S("truc:nulls_first") }),
+            ("_random(-1)", ReservedKeyword { name: S("_random(-1)") }),
+            ("_random(seed)", ReservedKeyword { name: S("_random(seed)") }),
+            (
+                "_random(42):asc:nulls_first",
+                InvalidSyntax { name: S("_random(42):asc:nulls_first") },
+            ),
+            (
+                "_geoPoint(42, 59):asc:nulls_first",
+                InvalidSyntax { name: S("_geoPoint(42, 59):asc:nulls_first") },
+            ),
             ("_geoPoint:asc", ReservedKeyword { name: S("_geoPoint") }),
             ("_geoDistance:asc", ReservedKeyword { name: S("_geoDistance") }),
             ("_geoPoint(42.12 , 59.598)", InvalidSyntax { name: S("_geoPoint(42.12 , 59.598)") }),
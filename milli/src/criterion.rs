@@ -4,6 +4,7 @@ use std::str::FromStr;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::error::is_reserved_keyword;
 use crate::{AscDesc, Member};
 
 #[derive(Error, Debug)]
@@ -22,6 +23,15 @@ pub enum CriterionError {
 `{name}` can only be used for filtering at search time"
     )]
     ReservedNameForFilter { name: String },
+    #[error(
+        "`{name}` ranking rule is invalid. The recency ranking rule must be written \
+`recency(<field>, half_life=<duration>)` with a duration made of a positive integer followed by \
+`d`, `h`, `m` or `s`, e.g. `recency(published_at, half_life=30d)`."
+    )]
+    InvalidRecency { name: String },
+    #[cfg(feature = "plugins")]
+    #[error("`{name}` ranking rule plugin is not registered.")]
+    UnknownPlugin { name: String },
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -45,6 +55,13 @@ pub enum Criterion {
     Asc(String),
     /// Sorted by the decreasing value of the field specified.
     Desc(String),
+    /// Sorted by the age of the Unix timestamp, in seconds, of the field specified. The score of
+    /// a document is halved every `half_life` seconds, the documents of similar ages are ranked
+    /// in the same bucket.
+    Recency { field: String, half_life: u64 },
+    /// Sorted by the ranking rule plugin registered under the name specified.
+    #[cfg(feature = "plugins")]
+    Plugin(String),
 }
 
 impl Criterion {
@@ -52,6 +69,7 @@ impl Criterion {
     pub fn field_name(&self) -> Option<&str> {
         match self {
             Criterion::Asc(name) | Criterion::Desc(name) => Some(name),
+            Criterion::Recency { field, .. } => Some(field),
             _otherwise => None,
         }
     }
@@ -68,17 +86,75 @@ impl FromStr for Criterion {
             "attribute" => Ok(Criterion::Attribute),
             "sort" => Ok(Criterion::Sort),
             "exactness" => Ok(Criterion::Exactness),
+            text if text.starts_with("recency(") => parse_recency(text),
+            #[cfg(feature = "plugins")]
+            text if text.starts_with("plugin:") => {
+                Ok(Criterion::Plugin(text["plugin:".len()..].to_string()))
+            }
             text => match AscDesc::from_str(text)? {
                 AscDesc::Asc(Member::Field(field)) => Ok(Criterion::Asc(field)),
                 AscDesc::Desc(Member::Field(field)) => Ok(Criterion::Desc(field)),
                 AscDesc::Asc(Member::Geo(_)) | AscDesc::Desc(Member::Geo(_)) => {
                     Err(CriterionError::ReservedNameForSort { name: "_geoPoint".to_string() })?
                 }
+                AscDesc::Asc(Member::Random(_)) | AscDesc::Desc(Member::Random(_)) => {
+                    Err(CriterionError::ReservedNameForSort { name: "_random".to_string() })?
+                }
+                // the placement of the missing values is only chosen at search time
+                AscDesc::AscNullsFirst(_) | AscDesc::DescNullsFirst(_) => {
+                    Err(CriterionError::InvalidName { name: text.to_string() })
+                }
             },
         }
     }
 }
 
+/// Parses a `recency(<field>, half_life=<duration>)` ranking rule.
+fn parse_recency(text: &str) -> Result<Criterion, CriterionError> {
+    let invalid = || CriterionError::InvalidRecency { name: text.to_string() };
+    let arguments = text
+        .strip_prefix("recency(")
+        .and_then(|text| text.strip_suffix(')'))
+        .ok_or_else(invalid)?;
+    let (field, half_life) = arguments.rsplit_once(',').ok_or_else(invalid)?;
+    let half_life = half_life
+        .trim()
+        .strip_prefix("half_life")
+        .and_then(|half_life| half_life.trim_start().strip_prefix('='))
+        .and_then(|duration| parse_duration(duration.trim()))
+        .ok_or_else(invalid)?;
+
+    let field = field.trim();
+    if field.is_empty() {
+        return Err(invalid());
+    }
+    if is_reserved_keyword(field) {
+        return Err(CriterionError::ReservedName { name: field.to_string() });
+    }
+    Ok(Criterion::Recency { field: field.to_string(), half_life })
+}
+
+/// The units of the durations of the recency ranking rule, along with their number of seconds.
+const DURATION_UNITS: [(char, u64); 4] = [('d', 86_400), ('h', 3_600), ('m', 60), ('s', 1)];
+
+/// Parses a duration like `30d` into its number of seconds.
+fn parse_duration(text: &str) -> Option<u64> {
+    let unit = text.chars().last()?;
+    let (_, seconds) = DURATION_UNITS.iter().find(|(u, _)| *u == unit)?;
+    let count: u64 = text[..text.len() - unit.len_utf8()].parse().ok()?;
+    count.checked_mul(*seconds).filter(|&duration| duration > 0)
+}
+
+/// Formats the number of seconds in the largest unit dividing it.
+fn format_duration(duration: u64) -> String {
+    let (unit, seconds) = DURATION_UNITS
+        .iter()
+        .find(|(_, seconds)| duration % seconds == 0)
+        .copied()
+        .unwrap_or(('s', 1));
+    format!("{}{unit}", duration / seconds)
+}
+
 pub fn default_criteria() -> Vec<Criterion> {
     vec![
         Criterion::Words,
@@ -103,6 +179,11 @@ impl fmt::Display for Criterion {
             Exactness => f.write_str("exactness"),
             Asc(attr) => write!(f, "{}:asc", attr),
             Desc(attr) => write!(f, "{}:desc", attr),
+            Recency { field, half_life } => {
+                write!(f, "recency({field}, half_life={})", format_duration(*half_life))
+            }
+            #[cfg(feature = "plugins")]
+            Plugin(name) => write!(f, "plugin:{}", name),
         }
     }
 }
@@ -129,6 +210,14 @@ mod tests {
             ("truc:machin:desc", Criterion::Desc(S("truc:machin"))),
             ("hello-world!:desc", Criterion::Desc(S("hello-world!"))),
             ("it's spacy over there:asc", Criterion::Asc(S("it's spacy over there"))),
+            (
+                "recency(published_at, half_life=30d)",
+                Criterion::Recency { field: S("published_at"), half_life: 30 * 86_400 },
+            ),
+            (
+                "recency(a, b,half_life = 90m)",
+                Criterion::Recency { field: S("a, b"), half_life: 90 * 60 },
+            ),
         ];
 
         for (input, expected) in valid_criteria {
@@ -153,10 +242,28 @@ mod tests {
             ("price:aasc", InvalidName { name: S("price:aasc") }),
             ("price:asc and desc", InvalidName { name: S("price:asc and desc") }),
             ("price:asc:truc", InvalidName { name: S("price:asc:truc") }),
+            ("price:asc:nulls_first", InvalidName { name: S("price:asc:nulls_first") }),
+            ("recency(published_at)", InvalidRecency { name: S("recency(published_at)") }),
+            (
+                "recency(published_at, half_life=0d)",
+                InvalidRecency { name: S("recency(published_at, half_life=0d)") },
+            ),
+            (
+                "recency(published_at, half_life=30y)",
+                InvalidRecency { name: S("recency(published_at, half_life=30y)") },
+            ),
+            (
+                "recency(published_at, decay=30d)",
+                InvalidRecency { name: S("recency(published_at, decay=30d)") },
+            ),
+            ("recency(, half_life=30d)", InvalidRecency { name: S("recency(, half_life=30d)") }),
+            ("recency(_geo, half_life=30d)", ReservedName { name: S("_geo") }),
             ("_geo:asc", ReservedName { name: S("_geo") }),
             ("_geoDistance:asc", ReservedName { name: S("_geoDistance") }),
             ("_geoPoint:asc", ReservedNameForSort { name: S("_geoPoint") }),
             ("_geoPoint(42, 75):asc", ReservedNameForSort { name: S("_geoPoint") }),
+            ("_random(42)", ReservedNameForSort { name: S("_random") }),
+            ("_random(seed)", ReservedNameForSort { name: S("_random") }),
             ("_geoRadius:asc", ReservedNameForFilter { name: S("_geoRadius") }),
             ("_geoRadius(42, 75, 59):asc", ReservedNameForFilter { name: S("_geoRadius") }),
             ("_geoBoundingBox:asc", ReservedNameForFilter { name: S("_geoBoundingBox") }),
@@ -0,0 +1,91 @@
+//! The splitting of the compound words into the words they are made of.
+//!
+//! The agglutinative languages, e.g. German or Dutch, glue words together to form new ones:
+//! `Haftpflichtversicherung` is made of `Haftpflicht` and `Versicherung`. No rule tells where a
+//! compound word can be split, the words it can be made of are given by a dictionary for each
+//! language.
+//!
+//! The parts of a compound word are indexed right after it, so that a query for `Versicherung`
+//! matches the documents containing `Haftpflichtversicherung`. A compound word of the query is
+//! also split, its parts then match the documents containing them next to each other.
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use charabia::{Language, Token, TokenKind};
+
+/// The letters linking the parts of a compound word, e.g. the `s` of `Versicherungsschutz`.
+const LINKING_MORPHEMES: &[&str] = &["s", "es", "n", "en", "e", "er"];
+
+/// The words the compound words of each language are made of, the words are expected to be
+/// normalized.
+#[derive(Debug, Clone, Default)]
+pub struct Decompounder {
+    language_to_dictionary: HashMap<Language, BTreeSet<String>>,
+}
+
+impl Decompounder {
+    /// Builds the decompounder from the dictionaries keyed by the ISO 639-3 code of their language.
+    pub fn new(dictionaries: &BTreeMap<String, BTreeSet<String>>) -> Self {
+        let language_to_dictionary = dictionaries
+            .iter()
+            .filter(|(_, words)| !words.is_empty())
+            .map(|(name, words)| (Language::from_name(name), words.clone()))
+            .collect();
+        Self { language_to_dictionary }
+    }
+
+    /// Splits the word into the words of the dictionary of the language.
+    ///
+    /// Returns `None` when the word isn't made of at least two words of the dictionary.
+    pub fn split(&self, language: Language, word: &str) -> Option<Vec<&str>> {
+        let dictionary = self.language_to_dictionary.get(&language)?;
+        split_with(dictionary, word).filter(|parts| parts.len() > 1)
+    }
+
+    /// Splits the word into the words of the dictionary of any language.
+    ///
+    /// The language of a query is often not detected, a short query doesn't say much about it.
+    pub fn split_any(&self, word: &str) -> Option<Vec<&str>> {
+        self.language_to_dictionary
+            .values()
+            .find_map(|dictionary| split_with(dictionary, word).filter(|parts| parts.len() > 1))
+    }
+
+    /// Returns the token followed by the parts of the compound word it is, if any.
+    pub fn decompound<'o>(&self, token: Token<'o>) -> Vec<Token<'o>> {
+        let parts = match token.language {
+            Some(language) if token.is_word() => self.split(language, token.lemma()),
+            _ => None,
+        };
+
+        let mut tokens = Vec::new();
+        for part in parts.into_iter().flatten() {
+            let lemma = Cow::Owned(part.to_string());
+            tokens.push(Token { kind: TokenKind::Word, lemma, ..token.clone() });
+        }
+        tokens.insert(0, token);
+        tokens
+    }
+}
+
+/// Splits the word into the fewest words of the dictionary, the longest words being tried first.
+fn split_with<'d>(dictionary: &'d BTreeSet<String>, word: &str) -> Option<Vec<&'d str>> {
+    let ends = (1..=word.len()).rev().filter(|&end| word.is_char_boundary(end));
+    for end in ends {
+        let Some(part) = dictionary.get(&word[..end]) else { continue };
+        let rest = &word[end..];
+        if rest.is_empty() {
+            return Some(vec![part.as_str()]);
+        }
+
+        let linked = LINKING_MORPHEMES.iter().filter_map(|morpheme| rest.strip_prefix(morpheme));
+        for rest in std::iter::once(rest).chain(linked) {
+            if let Some(mut parts) = split_with(dictionary, rest) {
+                parts.insert(0, part.as_str());
+                return Some(parts);
+            }
+        }
+    }
+    None
+}
@@ -88,14 +88,29 @@ impl<W: Write> DocumentsBatchBuilder<W> {
     }
 
     /// Appends a new CSV file into the batch and updates the `DocumentsBatchIndex` accordingly.
-    pub fn append_csv<R: io::Read>(&mut self, mut reader: csv::Reader<R>) -> Result<(), Error> {
+    pub fn append_csv<R: io::Read>(&mut self, reader: csv::Reader<R>) -> Result<(), Error> {
+        self.append_csv_with_columns(reader, |_, name| Some(name.to_string()))
+    }
+
+    /// Appends a new CSV file into the batch, the name of each column is given by `map_column`
+    /// from its position and the field name of its header, the column is ignored on `None`.
+    pub fn append_csv_with_columns<R, F>(
+        &mut self,
+        mut reader: csv::Reader<R>,
+        mut map_column: F,
+    ) -> Result<(), Error>
+    where
+        R: io::Read,
+        F: FnMut(usize, &str) -> Option<String>,
+    {
         // Make sure that we insert the fields ids in order as the obkv writer has this requirement.
         let mut typed_fields_ids: Vec<_> = reader
             .headers()?
             .into_iter()
             .map(parse_csv_header)
-            .map(|(k, t)| (self.fields_index.insert(k), t))
             .enumerate()
+            .filter_map(|(i, (k, t))| map_column(i, k).map(|k| (i, (k, t))))
+            .map(|(i, (k, t))| (i, (self.fields_index.insert(&k), t)))
             .collect();
         // Make sure that we insert the fields ids in order as the obkv writer has this requirement.
         typed_fields_ids.sort_unstable_by_key(|(_, (fid, _))| *fid);
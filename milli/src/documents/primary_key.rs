@@ -3,11 +3,17 @@ use std::result::Result as StdResult;
 
 use serde_json::Value;
 
-use crate::{FieldId, InternalError, Object, Result, UserError};
+use crate::{ExternalDocumentId, FieldId, InternalError, Object, Result, UserError};
 
 /// The symbol used to define levels in a nested primary key.
 const PRIMARY_KEY_SPLIT_SYMBOL: char = '.';
 
+/// The symbol joining the values of the fields of a composite primary key into a document id.
+///
+/// The values of the fields can't contain it, so two different sets of values are never joined
+/// into the same document id.
+pub const COMPOSITE_DOCUMENT_ID_SEPARATOR: char = ':';
+
 /// The default primary that is used when not specified.
 pub const DEFAULT_PRIMARY_KEY: &str = "id";
 
@@ -19,12 +25,37 @@ pub trait FieldIdMapper {
     fn id(&self, name: &str) -> Option<FieldId>;
 }
 
+/// Returns the fields of a composite primary key, stored as the JSON array of its fields,
+/// or `None` if the primary key is a single flat or nested field.
+pub fn composite_primary_key_fields(primary_key: &str) -> Option<Vec<String>> {
+    if primary_key.starts_with('[') {
+        serde_json::from_str(primary_key).ok()
+    } else {
+        None
+    }
+}
+
+/// Returns how the composite primary key made of these fields is stored.
+pub fn composite_primary_key(fields: &[String]) -> String {
+    serde_json::to_string(fields).unwrap()
+}
+
 /// A type that represent the type of primary key that has been set
-/// for this index, a classic flat one or a nested one.
-#[derive(Debug, Clone, Copy)]
+/// for this index, a classic flat one, a nested one or a composite one.
+#[derive(Debug, Clone)]
 pub enum PrimaryKey<'a> {
-    Flat { name: &'a str, field_id: FieldId },
-    Nested { name: &'a str },
+    Flat {
+        name: &'a str,
+        field_id: FieldId,
+    },
+    Nested {
+        name: &'a str,
+    },
+    /// Multiple flat or nested fields, see [`composite_primary_key_fields`].
+    Composite {
+        name: &'a str,
+        fields: Vec<String>,
+    },
 }
 
 pub enum DocumentIdExtractionError {
@@ -35,6 +66,14 @@ pub enum DocumentIdExtractionError {
 
 impl<'a> PrimaryKey<'a> {
     pub fn new(path: &'a str, fields: &impl FieldIdMapper) -> Option<Self> {
+        match composite_primary_key_fields(path) {
+            Some(components) => Some(Self::Composite { name: path, fields: components }),
+            None => Self::single(path, fields),
+        }
+    }
+
+    /// A flat or a nested primary key.
+    fn single(path: &'a str, fields: &impl FieldIdMapper) -> Option<Self> {
         Some(if path.contains(PRIMARY_KEY_SPLIT_SYMBOL) {
             Self::Nested { name: path }
         } else {
@@ -47,9 +86,14 @@ impl<'a> PrimaryKey<'a> {
         match self {
             PrimaryKey::Flat { name, .. } => name,
             PrimaryKey::Nested { name } => name,
+            PrimaryKey::Composite { name, .. } => name,
         }
     }
 
+    pub fn is_composite(&self) -> bool {
+        matches!(self, PrimaryKey::Composite { .. })
+    }
+
     pub fn document_id(
         &self,
         document: &obkv::KvReader<FieldId>,
@@ -97,6 +141,33 @@ impl<'a> PrimaryKey<'a> {
                     None => Ok(Err(DocumentIdExtractionError::MissingDocumentId)),
                 }
             }
+            PrimaryKey::Composite { fields: components, .. } => {
+                let mut document_id = String::new();
+                for component in components {
+                    let Some(component) = PrimaryKey::single(component, fields) else {
+                        return Ok(Err(DocumentIdExtractionError::MissingDocumentId));
+                    };
+                    match component.document_id(document, fields)? {
+                        Ok(component_id)
+                            if component_id.contains(COMPOSITE_DOCUMENT_ID_SEPARATOR) =>
+                        {
+                            return Ok(Err(DocumentIdExtractionError::InvalidDocumentId(
+                                UserError::InvalidCompositeDocumentId {
+                                    document_id: Value::String(component_id),
+                                },
+                            )));
+                        }
+                        Ok(component_id) => {
+                            if !document_id.is_empty() {
+                                document_id.push(COMPOSITE_DOCUMENT_ID_SEPARATOR);
+                            }
+                            document_id.push_str(&component_id);
+                        }
+                        Err(error) => return Ok(Err(error)),
+                    }
+                }
+                Ok(Ok(document_id))
+            }
         }
     }
 
@@ -147,26 +218,7 @@ fn starts_with(selector: &str, key: &str) -> bool {
     })
 }
 
-// FIXME: move to a DocumentId struct
-
-fn validate_document_id(document_id: &str) -> Option<&str> {
-    if !document_id.is_empty()
-        && document_id.chars().all(|c| matches!(c, 'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_'))
-    {
-        Some(document_id)
-    } else {
-        None
-    }
-}
-
+/// Validates the document id and returns the key it is stored under in the external documents ids.
 pub fn validate_document_id_value(document_id: Value) -> Result<StdResult<String, UserError>> {
-    match document_id {
-        Value::String(string) => match validate_document_id(&string) {
-            Some(s) if s.len() == string.len() => Ok(Ok(string)),
-            Some(s) => Ok(Ok(s.to_string())),
-            None => Ok(Err(UserError::InvalidDocumentId { document_id: Value::String(string) })),
-        },
-        Value::Number(number) if number.is_i64() => Ok(Ok(number.to_string())),
-        content => Ok(Err(UserError::InvalidDocumentId { document_id: content })),
-    }
+    Ok(ExternalDocumentId::try_from(document_id).map(|id| id.to_string()))
 }
@@ -102,9 +102,19 @@ pub enum UserError {
     #[error(
         "Document identifier `{}` is invalid. \
 A document identifier can be of type integer or string, \
-only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and underscores (_).", .document_id.to_string()
+only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-), underscores (_) and colons (:).", .document_id.to_string()
     )]
     InvalidDocumentId { document_id: Value },
+    #[error(
+        "Document identifier `{}` is invalid. \
+The values of the fields of a composite primary key can't contain colons (:).", .document_id.to_string()
+    )]
+    InvalidCompositeDocumentId { document_id: Value },
+    #[error(
+        "Document identifier `{}` is invalid. \
+A document identifier can't be a floating-point number, use an integer or a string instead.", .document_id.to_string()
+    )]
+    InvalidFloatDocumentId { document_id: Value },
     #[error("Invalid facet distribution, {}", format_invalid_filter_distribution(.invalid_facets_name, .valid_facets_name))]
     InvalidFacetsDistribution {
         invalid_facets_name: BTreeSet<String>,
@@ -133,6 +143,8 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
         }
     )]
     InvalidSortableAttribute { field: String, valid_fields: BTreeSet<String>, hidden_fields: bool },
+    #[error("Unit `{unit}` can't be used to sort on `{field}`. Available units are: `{}`.", .valid_units.join(", "))]
+    InvalidSortUnit { field: String, unit: String, valid_units: Vec<String> },
     #[error("Attribute `{}` is not facet-searchable. {}",
         .field,
         match .valid_fields.is_empty() {
@@ -148,6 +160,39 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
         valid_fields: BTreeSet<String>,
         hidden_fields: bool,
     },
+    #[error("Attribute `{}` can't be used to group the results. {}",
+        .field,
+        match .valid_fields.is_empty() {
+            true => "This index does not have configured filterable attributes. To group the results by an attribute add it to the `filterableAttributes` index settings.".to_string(),
+            false => format!("Available filterable attributes are: `{}{}`. To group the results by an attribute add it to the `filterableAttributes` index settings.",
+                    valid_fields.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", "),
+                    .hidden_fields.then_some(", <..hidden-attributes>").unwrap_or(""),
+                ),
+        }
+    )]
+    InvalidGroupByAttribute { field: String, valid_fields: BTreeSet<String>, hidden_fields: bool },
+    #[error("Attribute `{}` can't be aggregated. {}",
+        .field,
+        match .valid_fields.is_empty() {
+            true => "This index does not have configured filterable attributes. To aggregate an attribute add it to the `filterableAttributes` index settings.".to_string(),
+            false => format!("Available filterable attributes are: `{}{}`. To aggregate an attribute add it to the `filterableAttributes` index settings.",
+                    valid_fields.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", "),
+                    .hidden_fields.then_some(", <..hidden-attributes>").unwrap_or(""),
+                ),
+        }
+    )]
+    InvalidAggregateAttribute { field: String, valid_fields: BTreeSet<String>, hidden_fields: bool },
+    #[error("Attribute `{}` can't be used as a nested attribute. {}",
+        .field,
+        match .valid_fields.is_empty() {
+            true => "This index does not have configured filterable attributes. To filter the elements of an attribute add it to the `filterableAttributes` index settings.".to_string(),
+            false => format!("Available filterable attributes are: `{}{}`. To filter the elements of an attribute add it to the `filterableAttributes` index settings.",
+                    valid_fields.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", "),
+                    .hidden_fields.then_some(", <..hidden-attributes>").unwrap_or(""),
+                ),
+        }
+    )]
+    InvalidNestedAttribute { field: String, valid_fields: BTreeSet<String>, hidden_fields: bool },
     #[error("Attribute `{}` is not searchable. Available searchable attributes are: `{}{}`.",
         .field,
         .valid_fields.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", "),
@@ -186,6 +231,16 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
     UnknownInternalDocumentId { document_id: DocumentId },
     #[error("`minWordSizeForTypos` setting is invalid. `oneTypo` and `twoTypos` fields should be between `0` and `255`, and `twoTypos` should be greater or equals to `oneTypo` but found `oneTypo: {0}` and twoTypos: {1}`.")]
     InvalidMinTypoWordLenSetting(u8, u8),
+    #[error("`enrichmentWebhook.url` must be an HTTP or HTTPS URL, but found `{0}`.")]
+    InvalidEnrichmentWebhookUrl(String),
+    #[error("`enrichmentWebhook.batchSize` must be greater than zero.")]
+    InvalidEnrichmentWebhookBatchSize,
+    #[error("`enrichmentWebhook.url` must be set to configure the enrichment webhook.")]
+    MissingEnrichmentWebhookUrl,
+    #[error("The conversion of `{0}` must have at least one rate.")]
+    MissingUnitConversionRates(String),
+    #[error("The rate of the `{unit}` unit in the conversion of `{field}` must be a positive number, but found `{rate}`.")]
+    InvalidUnitConversionRate { field: String, unit: String, rate: f64 },
     #[error(transparent)]
     VectorEmbeddingError(#[from] crate::vector::Error),
     #[error(transparent)]
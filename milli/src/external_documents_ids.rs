@@ -1,9 +1,63 @@
 use std::collections::HashMap;
+use std::fmt;
 
 use heed::types::Str;
 use heed::{Database, RoIter, RoTxn, RwTxn};
+use serde_json::Value;
 
-use crate::{DocumentId, BEU32};
+use crate::{DocumentId, UserError, BEU32};
+
+/// The typed value of a document identifier, as found in the primary key of a document.
+///
+/// The external documents ids database is keyed by the [`Display`](fmt::Display) of this type.
+/// Integers are therefore stored in their canonical decimal form: `7` and `"7"` designate
+/// the same document, while `"007"` designates another one and is never coerced to `7`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExternalDocumentId {
+    String(String),
+    Integer(i64),
+    /// An integer too large to be represented by an `i64`.
+    UnsignedInteger(u64),
+}
+
+impl TryFrom<Value> for ExternalDocumentId {
+    type Error = UserError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::String(string) if is_valid_document_id(&string) => {
+                Ok(ExternalDocumentId::String(string))
+            }
+            Value::Number(number) => {
+                if let Some(integer) = number.as_i64() {
+                    Ok(ExternalDocumentId::Integer(integer))
+                } else if let Some(integer) = number.as_u64() {
+                    Ok(ExternalDocumentId::UnsignedInteger(integer))
+                } else {
+                    Err(UserError::InvalidFloatDocumentId { document_id: Value::Number(number) })
+                }
+            }
+            document_id => Err(UserError::InvalidDocumentId { document_id }),
+        }
+    }
+}
+
+impl fmt::Display for ExternalDocumentId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExternalDocumentId::String(string) => f.write_str(string),
+            ExternalDocumentId::Integer(integer) => write!(f, "{integer}"),
+            ExternalDocumentId::UnsignedInteger(integer) => write!(f, "{integer}"),
+        }
+    }
+}
+
+fn is_valid_document_id(document_id: &str) -> bool {
+    !document_id.is_empty()
+        && document_id
+            .chars()
+            .all(|c| matches!(c, 'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' | ':'))
+}
 
 pub enum DocumentOperationKind {
     Create,
@@ -73,3 +127,31 @@ impl ExternalDocumentsIds {
         self.0.iter(rtxn)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn typed_external_document_id() {
+        let id = |value: Value| ExternalDocumentId::try_from(value);
+
+        assert_eq!(id(json!("007")).unwrap(), ExternalDocumentId::String("007".to_string()));
+        assert_eq!(id(json!(-7)).unwrap(), ExternalDocumentId::Integer(-7));
+        assert_eq!(id(json!(u64::MAX)).unwrap(), ExternalDocumentId::UnsignedInteger(u64::MAX));
+
+        // integers are stored in their canonical form, strings are kept as is
+        assert_eq!(id(json!(7)).unwrap().to_string(), "7");
+        assert_eq!(id(json!("007")).unwrap().to_string(), "007");
+        assert_eq!(id(json!(u64::MAX)).unwrap().to_string(), "18446744073709551615");
+
+        assert!(matches!(id(json!(1e5)), Err(UserError::InvalidFloatDocumentId { .. })));
+        assert!(matches!(id(json!(1.5)), Err(UserError::InvalidFloatDocumentId { .. })));
+        assert!(matches!(id(json!("foo & bar")), Err(UserError::InvalidDocumentId { .. })));
+        assert_eq!(id(json!("tenant:42")).unwrap().to_string(), "tenant:42");
+        assert!(matches!(id(json!("")), Err(UserError::InvalidDocumentId { .. })));
+        assert!(matches!(id(json!(true)), Err(UserError::InvalidDocumentId { .. })));
+    }
+}
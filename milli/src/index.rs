@@ -2,12 +2,14 @@ use std::borrow::Cow;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs::File;
 use std::path::Path;
+use std::sync::Arc;
 
 use charabia::{Language, Script};
 use heed::types::*;
 use heed::{CompactionOption, Database, RoTxn, RwTxn, Unspecified};
 use roaring::RoaringBitmap;
 use rstar::RTree;
+use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
 use crate::documents::PrimaryKey;
@@ -20,18 +22,33 @@ use crate::heed_codec::facet::{
 use crate::heed_codec::{
     BEU16StrCodec, FstSetCodec, ScriptLanguageCodec, StrBEU16Codec, StrRefCodec,
 };
+use crate::localized_attributes_rules::LocalizedAttributesRule;
 use crate::proximity::ProximityPrecision;
+use crate::search::new::QueryCache;
+use crate::unit_conversion::UnitConversion;
+use crate::update::EnrichmentWebhook;
 use crate::vector::EmbeddingConfig;
 use crate::{
     default_criteria, CboRoaringBitmapCodec, Criterion, DocumentId, ExternalDocumentsIds,
     FacetDistribution, FieldDistribution, FieldId, FieldIdWordCountCodec, GeoPoint, ObkvCodec,
-    OrderBy, Result, RoaringBitmapCodec, RoaringBitmapLenCodec, Search, U8StrStrCodec, BEU16,
-    BEU32, BEU64,
+    OrderBy, QueryPlanHints, Result, RoaringBitmapCodec, RoaringBitmapLenCodec, Search,
+    SearchExclusions, U8StrStrCodec, BEU16, BEU32, BEU64,
 };
 
 pub const DEFAULT_MIN_WORD_LEN_ONE_TYPO: u8 = 5;
 pub const DEFAULT_MIN_WORD_LEN_TWO_TYPOS: u8 = 9;
 
+/// Whether the last word of a query matches the words it is a prefix of.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum PrefixSearch {
+    /// The prefixes are computed at indexing time, in the words prefixes databases.
+    #[default]
+    IndexingTime,
+    /// The words of the query only match themselves, the words prefixes databases are emptied.
+    Disabled,
+}
+
 pub mod main_key {
     pub const CRITERIA_KEY: &str = "criteria";
     pub const DISPLAYED_FIELDS_KEY: &str = "displayed-fields";
@@ -48,6 +65,9 @@ pub mod main_key {
     pub const SEARCHABLE_FIELDS_KEY: &str = "searchable-fields";
     pub const USER_DEFINED_SEARCHABLE_FIELDS_KEY: &str = "user-defined-searchable-fields";
     pub const STOP_WORDS_KEY: &str = "stop-words";
+    pub const LOCALIZED_STOP_WORDS_KEY: &str = "localized-stop-words";
+    pub const STEMMING_KEY: &str = "stemming";
+    pub const DECOMPOUNDING_KEY: &str = "decompounding";
     pub const NON_SEPARATOR_TOKENS_KEY: &str = "non-separator-tokens";
     pub const SEPARATOR_TOKENS_KEY: &str = "separator-tokens";
     pub const DICTIONARY_KEY: &str = "dictionary";
@@ -62,10 +82,19 @@ pub mod main_key {
     pub const TWO_TYPOS_WORD_LEN: &str = "two-typos-word-len";
     pub const EXACT_WORDS: &str = "exact-words";
     pub const EXACT_ATTRIBUTES: &str = "exact-attributes";
+    pub const DISABLE_TYPOS_ON_NUMBERS: &str = "disable-typos-on-numbers";
     pub const MAX_VALUES_PER_FACET: &str = "max-values-per-facet";
     pub const SORT_FACET_VALUES_BY: &str = "sort-facet-values-by";
     pub const PAGINATION_MAX_TOTAL_HITS: &str = "pagination-max-total-hits";
     pub const PROXIMITY_PRECISION: &str = "proximity-precision";
+    pub const PREFIX_SEARCH: &str = "prefix-search";
+    pub const QUERY_PLAN_HINTS: &str = "query-plan-hints";
+    pub const ENRICHMENT_WEBHOOK: &str = "enrichment-webhook";
+    pub const UNIT_CONVERSIONS: &str = "unit-conversions";
+    pub const SEARCH_CUTOFF: &str = "search-cutoff";
+    pub const SEARCH_TEMPLATES: &str = "search-templates";
+    pub const LOCALIZED_ATTRIBUTES_RULES: &str = "localized-attributes-rules";
+    pub const SEARCH_EXCLUSIONS: &str = "search-exclusions";
     pub const EMBEDDING_CONFIGS: &str = "embedding_configs";
 }
 
@@ -167,6 +196,9 @@ pub struct Index {
 
     /// Maps the document id to the document as an obkv store.
     pub(crate) documents: Database<BEU32, ObkvCodec>,
+
+    /// The query graphs of the last searches made on this index.
+    pub(crate) query_cache: Arc<QueryCache>,
 }
 
 impl Index {
@@ -255,6 +287,7 @@ impl Index {
             vector_arroy,
             embedder_category_id,
             documents,
+            query_cache: Arc::default(),
         })
     }
 
@@ -336,6 +369,7 @@ impl Index {
         wtxn: &mut RwTxn,
         docids: &RoaringBitmap,
     ) -> heed::Result<()> {
+        self.set_updated_at(wtxn, &OffsetDateTime::now_utc())?;
         self.main.remap_types::<Str, RoaringBitmapCodec>().put(
             wtxn,
             main_key::DOCUMENTS_IDS_KEY,
@@ -533,14 +567,27 @@ impl Index {
     }
 
     /// Identical to `displayed_fields`, but returns the ids instead.
+    ///
+    /// The names containing a `*` wildcard, e.g. `address.*` or `*_en`, are replaced by the
+    /// fields matching them in the order of the `FieldsIdsMap`.
     pub fn displayed_fields_ids(&self, rtxn: &RoTxn) -> Result<Option<Vec<FieldId>>> {
         match self.displayed_fields(rtxn)? {
             Some(fields) => {
                 let fields_ids_map = self.fields_ids_map(rtxn)?;
                 let mut fields_ids = Vec::new();
                 for name in fields.into_iter() {
-                    if let Some(field_id) = fields_ids_map.id(name) {
-                        fields_ids.push(field_id);
+                    if name.contains('*') {
+                        for (field_id, field_name) in fields_ids_map.iter() {
+                            if crate::match_pattern(name, field_name)
+                                && !fields_ids.contains(&field_id)
+                            {
+                                fields_ids.push(field_id);
+                            }
+                        }
+                    } else if let Some(field_id) = fields_ids_map.id(name) {
+                        if !fields_ids.contains(&field_id) {
+                            fields_ids.push(field_id);
+                        }
                     }
                 }
                 Ok(Some(fields_ids))
@@ -561,8 +608,8 @@ impl Index {
         let fields_len = valid_fields.len();
 
         if let Some(dn) = self.displayed_fields(rtxn)? {
-            let displayable_names = dn.iter().map(|s| s.to_string()).collect();
-            valid_fields = &valid_fields & &displayable_names;
+            valid_fields
+                .retain(|field| dn.iter().any(|pattern| crate::match_pattern(pattern, field)));
         }
 
         let hidden_fields = fields_len > valid_fields.len();
@@ -800,14 +847,17 @@ impl Index {
 
     /// Returns the user defined faceted fields names.
     ///
-    /// The user faceted fields are the union of all the filterable, sortable, distinct, and Asc/Desc fields.
+    /// The user faceted fields are the union of all the filterable, sortable, distinct, Asc/Desc
+    /// and recency fields.
     pub fn user_defined_faceted_fields(&self, rtxn: &RoTxn) -> Result<HashSet<String>> {
         let filterable_fields = self.filterable_fields(rtxn)?;
         let sortable_fields = self.sortable_fields(rtxn)?;
         let distinct_field = self.distinct_field(rtxn)?;
         let asc_desc_fields =
             self.criteria(rtxn)?.into_iter().filter_map(|criterion| match criterion {
-                Criterion::Asc(field) | Criterion::Desc(field) => Some(field),
+                Criterion::Asc(field)
+                | Criterion::Desc(field)
+                | Criterion::Recency { field, .. } => Some(field),
                 _otherwise => None,
             });
 
@@ -969,6 +1019,78 @@ impl Index {
         }
     }
 
+    /* localized stop words */
+
+    pub(crate) fn put_localized_stop_words(
+        &self,
+        wtxn: &mut RwTxn,
+        stop_words: &BTreeMap<String, BTreeSet<String>>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<_>>().put(
+            wtxn,
+            main_key::LOCALIZED_STOP_WORDS_KEY,
+            stop_words,
+        )
+    }
+
+    pub(crate) fn delete_localized_stop_words(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::LOCALIZED_STOP_WORDS_KEY)
+    }
+
+    /// Returns the stop words of each language, keyed by the ISO 639-3 code of the language.
+    pub fn localized_stop_words(
+        &self,
+        rtxn: &RoTxn,
+    ) -> heed::Result<Option<BTreeMap<String, BTreeSet<String>>>> {
+        self.main.remap_types::<Str, SerdeJson<_>>().get(rtxn, main_key::LOCALIZED_STOP_WORDS_KEY)
+    }
+
+    /* stemming */
+
+    pub(crate) fn put_stemming(
+        &self,
+        wtxn: &mut RwTxn,
+        languages: &BTreeSet<String>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<_>>().put(wtxn, main_key::STEMMING_KEY, languages)
+    }
+
+    pub(crate) fn delete_stemming(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::STEMMING_KEY)
+    }
+
+    /// Returns the languages whose words are stemmed, given by their ISO 639-3 code.
+    pub fn stemming(&self, rtxn: &RoTxn) -> heed::Result<Option<BTreeSet<String>>> {
+        self.main.remap_types::<Str, SerdeJson<_>>().get(rtxn, main_key::STEMMING_KEY)
+    }
+
+    /* decompounding */
+
+    pub(crate) fn put_decompounding(
+        &self,
+        wtxn: &mut RwTxn,
+        dictionaries: &BTreeMap<String, BTreeSet<String>>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<_>>().put(
+            wtxn,
+            main_key::DECOMPOUNDING_KEY,
+            dictionaries,
+        )
+    }
+
+    pub(crate) fn delete_decompounding(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::DECOMPOUNDING_KEY)
+    }
+
+    /// Returns the words the compound words of each language are made of, keyed by the
+    /// ISO 639-3 code of the language.
+    pub fn decompounding(
+        &self,
+        rtxn: &RoTxn,
+    ) -> heed::Result<Option<BTreeMap<String, BTreeSet<String>>>> {
+        self.main.remap_types::<Str, SerdeJson<_>>().get(rtxn, main_key::DECOMPOUNDING_KEY)
+    }
+
     /* non separator tokens */
 
     pub(crate) fn put_non_separator_tokens(
@@ -1361,6 +1483,27 @@ impl Index {
         self.main.remap_key_type::<Str>().delete(txn, main_key::EXACT_ATTRIBUTES)
     }
 
+    /// Whether the words containing a digit, e.g. `1984` or `A1023`, are only matched without typos.
+    pub fn disable_typos_on_numbers(&self, txn: &RoTxn) -> heed::Result<bool> {
+        // The absence of a value is false, because by default, we authorize typos on numbers.
+        match self.main.remap_types::<Str, U8>().get(txn, main_key::DISABLE_TYPOS_ON_NUMBERS)? {
+            Some(0) | None => Ok(false),
+            _ => Ok(true),
+        }
+    }
+
+    pub(crate) fn put_disable_typos_on_numbers(
+        &self,
+        txn: &mut RwTxn,
+        flag: bool,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, U8>().put(
+            txn,
+            main_key::DISABLE_TYPOS_ON_NUMBERS,
+            &(flag as u8),
+        )
+    }
+
     pub fn max_values_per_facet(&self, txn: &RoTxn) -> heed::Result<Option<u64>> {
         self.main.remap_types::<Str, BEU64>().get(txn, main_key::MAX_VALUES_PER_FACET)
     }
@@ -1434,6 +1577,183 @@ impl Index {
         self.main.remap_key_type::<Str>().delete(txn, main_key::PROXIMITY_PRECISION)
     }
 
+    pub fn prefix_search(&self, txn: &RoTxn) -> heed::Result<Option<PrefixSearch>> {
+        self.main.remap_types::<Str, SerdeBincode<PrefixSearch>>().get(txn, main_key::PREFIX_SEARCH)
+    }
+
+    pub(crate) fn put_prefix_search(&self, txn: &mut RwTxn, val: PrefixSearch) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeBincode<PrefixSearch>>().put(
+            txn,
+            main_key::PREFIX_SEARCH,
+            &val,
+        )
+    }
+
+    pub(crate) fn delete_prefix_search(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::PREFIX_SEARCH)
+    }
+
+    pub fn query_plan_hints(&self, txn: &RoTxn) -> heed::Result<Option<QueryPlanHints>> {
+        self.main
+            .remap_types::<Str, SerdeJson<QueryPlanHints>>()
+            .get(txn, main_key::QUERY_PLAN_HINTS)
+    }
+
+    pub(crate) fn put_query_plan_hints(
+        &self,
+        txn: &mut RwTxn,
+        val: &QueryPlanHints,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<QueryPlanHints>>().put(
+            txn,
+            main_key::QUERY_PLAN_HINTS,
+            val,
+        )
+    }
+
+    pub(crate) fn delete_query_plan_hints(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::QUERY_PLAN_HINTS)
+    }
+
+    pub fn search_exclusions(&self, txn: &RoTxn) -> heed::Result<Option<SearchExclusions>> {
+        self.main
+            .remap_types::<Str, SerdeJson<SearchExclusions>>()
+            .get(txn, main_key::SEARCH_EXCLUSIONS)
+    }
+
+    pub(crate) fn put_search_exclusions(
+        &self,
+        txn: &mut RwTxn,
+        val: &SearchExclusions,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<SearchExclusions>>().put(
+            txn,
+            main_key::SEARCH_EXCLUSIONS,
+            val,
+        )
+    }
+
+    pub(crate) fn delete_search_exclusions(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::SEARCH_EXCLUSIONS)
+    }
+
+    pub fn enrichment_webhook(&self, txn: &RoTxn) -> heed::Result<Option<EnrichmentWebhook>> {
+        self.main
+            .remap_types::<Str, SerdeJson<EnrichmentWebhook>>()
+            .get(txn, main_key::ENRICHMENT_WEBHOOK)
+    }
+
+    pub(crate) fn put_enrichment_webhook(
+        &self,
+        txn: &mut RwTxn,
+        val: &EnrichmentWebhook,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<EnrichmentWebhook>>().put(
+            txn,
+            main_key::ENRICHMENT_WEBHOOK,
+            val,
+        )
+    }
+
+    pub(crate) fn delete_enrichment_webhook(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::ENRICHMENT_WEBHOOK)
+    }
+
+    /// Returns the conversions of the numeric fields, by field name.
+    pub fn unit_conversions(&self, txn: &RoTxn) -> heed::Result<BTreeMap<String, UnitConversion>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<BTreeMap<String, UnitConversion>>>()
+            .get(txn, main_key::UNIT_CONVERSIONS)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_unit_conversions(
+        &self,
+        txn: &mut RwTxn,
+        val: &BTreeMap<String, UnitConversion>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<BTreeMap<String, UnitConversion>>>().put(
+            txn,
+            main_key::UNIT_CONVERSIONS,
+            val,
+        )
+    }
+
+    pub(crate) fn delete_unit_conversions(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::UNIT_CONVERSIONS)
+    }
+
+    /// Returns the number of milliseconds a search is allowed to spend ranking the documents.
+    pub fn search_cutoff(&self, txn: &RoTxn) -> heed::Result<Option<u64>> {
+        self.main.remap_types::<Str, BEU64>().get(txn, main_key::SEARCH_CUTOFF)
+    }
+
+    pub(crate) fn put_search_cutoff(&self, txn: &mut RwTxn, cutoff: u64) -> heed::Result<()> {
+        self.main.remap_types::<Str, BEU64>().put(txn, main_key::SEARCH_CUTOFF, &cutoff)
+    }
+
+    pub(crate) fn delete_search_cutoff(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::SEARCH_CUTOFF)
+    }
+
+    /// Returns the search parameters of the search templates, by template name.
+    ///
+    /// The parameters are opaque to the engine, they are merged into the searches
+    /// executed with a template.
+    pub fn search_templates(
+        &self,
+        txn: &RoTxn,
+    ) -> heed::Result<BTreeMap<String, serde_json::Value>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<BTreeMap<String, serde_json::Value>>>()
+            .get(txn, main_key::SEARCH_TEMPLATES)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_search_templates(
+        &self,
+        txn: &mut RwTxn,
+        val: &BTreeMap<String, serde_json::Value>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<BTreeMap<String, serde_json::Value>>>().put(
+            txn,
+            main_key::SEARCH_TEMPLATES,
+            val,
+        )
+    }
+
+    pub(crate) fn delete_search_templates(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::SEARCH_TEMPLATES)
+    }
+
+    /// Returns the rules giving the locales of the fields, the first rule matching a field wins.
+    pub fn localized_attributes_rules(
+        &self,
+        txn: &RoTxn,
+    ) -> heed::Result<Option<Vec<LocalizedAttributesRule>>> {
+        self.main
+            .remap_types::<Str, SerdeJson<Vec<LocalizedAttributesRule>>>()
+            .get(txn, main_key::LOCALIZED_ATTRIBUTES_RULES)
+    }
+
+    pub(crate) fn put_localized_attributes_rules(
+        &self,
+        txn: &mut RwTxn,
+        val: &[LocalizedAttributesRule],
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<&[LocalizedAttributesRule]>>().put(
+            txn,
+            main_key::LOCALIZED_ATTRIBUTES_RULES,
+            &val,
+        )
+    }
+
+    pub(crate) fn delete_localized_attributes_rules(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::LOCALIZED_ATTRIBUTES_RULES)
+    }
+
     /* script  language docids */
     /// Retrieve all the documents ids that correspond with (Script, Language) key, `None` if it is any.
     pub fn script_language_documents_ids(
@@ -2423,6 +2743,9 @@ pub(crate) mod tests {
             candidates: _,
             document_scores: _,
             mut documents_ids,
+            degraded: _,
+            diagnostics: _,
+            groups: _,
         } = search.execute().unwrap();
         let primary_key_id = index.fields_ids_map(&rtxn).unwrap().id("primary_key").unwrap();
         documents_ids.sort_unstable();
@@ -10,16 +10,23 @@ pub mod documents;
 
 mod asc_desc;
 mod criterion;
+pub mod decompounding;
 mod error;
 mod external_documents_ids;
 pub mod facet;
 mod fields_ids_map;
 pub mod heed_codec;
 pub mod index;
+pub mod localized_attributes_rules;
+pub mod localized_stop_words;
+#[cfg(feature = "plugins")]
+pub mod plugins;
 pub mod prompt;
 pub mod proximity;
 pub mod score_details;
 mod search;
+pub mod stemming;
+pub mod unit_conversion;
 pub mod update;
 pub mod vector;
 
@@ -32,12 +39,13 @@ use std::convert::{TryFrom, TryInto};
 use std::hash::BuildHasherDefault;
 
 use charabia::normalizer::{CharNormalizer, CompatibilityDecompositionNormalizer};
-pub use filter_parser::{Condition, FilterCondition, Span, Token};
+pub use filter_parser::{Condition, FilterCondition, Span, Token, MAX_FILTER_CONDITION_DEPTH};
 use fxhash::{FxHasher32, FxHasher64};
 pub use grenad::CompressionType;
 pub use search::new::{
-    execute_search, filtered_universe, DefaultSearchLogger, GeoSortStrategy, SearchContext,
-    SearchLogger, VisualSearchLogger,
+    execute_search, filtered_universe, CandidatesStatistics, DefaultSearchLogger,
+    DiagnosticsSearchLogger, ExplainedTerm, GeoSortStrategy, RankingRuleDiagnostics, SearchContext,
+    SearchDiagnostics, SearchExplanation, SearchLogger, VisualSearchLogger,
 };
 use serde_json::Value;
 pub use {charabia as tokenizer, heed};
@@ -47,7 +55,7 @@ pub use self::criterion::{default_criteria, Criterion, CriterionError};
 pub use self::error::{
     Error, FieldIdMapMissingEntry, InternalError, SerializationError, UserError,
 };
-pub use self::external_documents_ids::ExternalDocumentsIds;
+pub use self::external_documents_ids::{ExternalDocumentId, ExternalDocumentsIds};
 pub use self::fields_ids_map::FieldsIdsMap;
 pub use self::heed_codec::{
     BEU16StrCodec, BEU32StrCodec, BoRoaringBitmapCodec, BoRoaringBitmapLenCodec,
@@ -55,11 +63,13 @@ pub use self::heed_codec::{
     RoaringBitmapCodec, RoaringBitmapLenCodec, StrBEU32Codec, U8StrStrCodec,
     UncheckedU8StrStrCodec,
 };
-pub use self::index::Index;
+pub use self::index::{Index, PrefixSearch};
 pub use self::search::{
-    FacetDistribution, FacetValueHit, Filter, FormatOptions, MatchBounds, MatcherBuilder,
-    MatchingWords, OrderBy, Search, SearchForFacetValues, SearchResult, TermsMatchingStrategy,
-    DEFAULT_VALUES_PER_FACET,
+    sort_documents, ExclusionMode, FacetAggregate, FacetAggregates, FacetBuckets,
+    FacetDistribution, FacetValueHit, Filter, FilterResolution, FormatOptions, GroupBy,
+    MatchBounds, MatcherBuilder, MatchingWords, NestedFilter, OrderBy, QueryPlanHints, ScoreBoost,
+    Search, SearchExclusions, SearchForFacetValues, SearchGroup, SearchResult,
+    TermsMatchingStrategy, TimeBudget, DEFAULT_VALUES_PER_FACET,
 };
 
 pub type Result<T> = std::result::Result<T, error::Error>;
@@ -284,6 +294,32 @@ pub fn is_faceted_by(field: &str, facet: &str) -> bool {
         && field[facet.len()..].chars().next().map(|c| c == '.').unwrap_or(true)
 }
 
+/// Returns whether a field name matches a pattern, which can start or end with a `*` wildcard.
+///
+/// ```rust
+/// use milli::match_pattern;
+///
+/// assert!(match_pattern("*", "title"));
+/// assert!(match_pattern("address.*", "address.city"));
+/// assert!(match_pattern("*_en", "title_en"));
+/// assert!(match_pattern("*tle*", "subtitles"));
+/// assert!(!match_pattern("*_en", "title_fr"));
+/// assert!(!match_pattern("address.*", "address"));
+/// ```
+pub fn match_pattern(pattern: &str, field_name: &str) -> bool {
+    if pattern == "*" {
+        true
+    } else if let Some(infix) = pattern.strip_prefix('*').and_then(|p| p.strip_suffix('*')) {
+        field_name.contains(infix)
+    } else if let Some(suffix) = pattern.strip_prefix('*') {
+        field_name.ends_with(suffix)
+    } else if let Some(prefix) = pattern.strip_suffix('*') {
+        field_name.starts_with(prefix)
+    } else {
+        pattern == field_name
+    }
+}
+
 pub fn normalize_facet(original: &str) -> String {
     CompatibilityDecompositionNormalizer.normalize_str(original.trim()).to_lowercase()
 }
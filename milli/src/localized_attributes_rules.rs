@@ -0,0 +1,159 @@
+//! The languages the text of some fields is written in.
+//!
+//! Short texts, and especially short CJK texts, are often detected as the wrong language and then
+//! segmented the wrong way. A rule forces the tokenizer to only consider its locales when the
+//! fields matching one of its patterns are indexed, the same way the `locales` search parameter
+//! does it for the query.
+
+use std::collections::HashMap;
+
+use charabia::{Language, Script};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{match_pattern, FieldId, FieldsIdsMap};
+
+/// The locales of the fields matching any of the patterns.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalizedAttributesRule {
+    /// The field names, optionally starting or ending with a `*` wildcard, e.g. `*_ja`.
+    pub attribute_patterns: Vec<String>,
+    #[serde(serialize_with = "serialize_languages", deserialize_with = "deserialize_languages")]
+    pub locales: Vec<Language>,
+}
+
+impl LocalizedAttributesRule {
+    pub fn match_str(&self, field_name: &str) -> bool {
+        self.attribute_patterns.iter().any(|pattern| match_pattern(pattern, field_name))
+    }
+}
+
+fn serialize_languages<S: Serializer>(
+    languages: &[Language],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(languages.iter().map(Language::name))
+}
+
+fn deserialize_languages<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<Language>, D::Error> {
+    let names = Vec::<String>::deserialize(deserializer)?;
+    Ok(names.iter().map(Language::from_name).collect())
+}
+
+/// The locales of the fields of an index, according to the first rule matching each of them.
+#[derive(Debug, Clone, Default)]
+pub struct LocalizedFieldIds {
+    field_id_to_locales: HashMap<FieldId, Vec<Language>>,
+}
+
+impl LocalizedFieldIds {
+    pub fn new(
+        rules: &[LocalizedAttributesRule],
+        fields_ids_map: &FieldsIdsMap,
+        fields_ids: impl Iterator<Item = FieldId>,
+    ) -> Self {
+        let mut field_id_to_locales = HashMap::new();
+        for field_id in fields_ids {
+            let Some(field_name) = fields_ids_map.name(field_id) else { continue };
+            if let Some(rule) = rules.iter().find(|rule| rule.match_str(field_name)) {
+                if !rule.locales.is_empty() {
+                    field_id_to_locales.insert(field_id, rule.locales.clone());
+                }
+            }
+        }
+        Self { field_id_to_locales }
+    }
+
+    pub fn locales(&self, field_id: FieldId) -> Option<&[Language]> {
+        self.field_id_to_locales.get(&field_id).map(Vec::as_slice)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (FieldId, &[Language])> {
+        self.field_id_to_locales.iter().map(|(field_id, locales)| (*field_id, locales.as_slice()))
+    }
+}
+
+/// Builds the allow list restricting the language detection of the tokenizer to the given locales.
+///
+/// The languages are grouped by the script they're written in, the detection of the text written
+/// in another script is left untouched.
+pub fn script_language_allow_list(locales: &[Language]) -> HashMap<Script, Vec<Language>> {
+    let mut allow_list: HashMap<Script, Vec<Language>> = HashMap::new();
+    for &language in locales {
+        for &script in scripts_of(language) {
+            let languages = allow_list.entry(script).or_default();
+            if !languages.contains(&language) {
+                languages.push(language);
+            }
+        }
+    }
+    allow_list
+}
+
+/// The scripts a language is written in, the languages with a script of their own are
+/// always detected correctly and are not listed.
+fn scripts_of(language: Language) -> &'static [Script] {
+    match language {
+        Language::Cmn | Language::Jpn => &[Script::Cj],
+        Language::Heb | Language::Yid => &[Script::Hebrew],
+        Language::Ara | Language::Urd | Language::Pes => &[Script::Arabic],
+        Language::Hin | Language::Mar | Language::Nep => &[Script::Devanagari],
+        Language::Rus | Language::Ukr | Language::Bel | Language::Bul | Language::Mkd => {
+            &[Script::Cyrillic]
+        }
+        Language::Srp => &[Script::Cyrillic, Script::Latin],
+        Language::Kor
+        | Language::Ell
+        | Language::Tha
+        | Language::Khm
+        | Language::Kat
+        | Language::Hye
+        | Language::Amh
+        | Language::Ben
+        | Language::Tam
+        | Language::Tel
+        | Language::Kan
+        | Language::Mal
+        | Language::Guj
+        | Language::Pan
+        | Language::Ori
+        | Language::Mya
+        | Language::Sin => &[],
+        _ => &[Script::Latin],
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn match_attribute_patterns() {
+        let rule = LocalizedAttributesRule {
+            attribute_patterns: vec!["title".to_string(), "*_ja".to_string()],
+            locales: vec![Language::Jpn],
+        };
+        assert!(rule.match_str("title"));
+        assert!(rule.match_str("description_ja"));
+        assert!(!rule.match_str("title_en"));
+        assert!(!rule.match_str("subtitle"));
+
+        let rule = LocalizedAttributesRule {
+            attribute_patterns: vec!["name.*".to_string(), "*body*".to_string()],
+            locales: vec![Language::Jpn],
+        };
+        assert!(rule.match_str("name.first"));
+        assert!(rule.match_str("the_body_text"));
+        assert!(!rule.match_str("name"));
+    }
+
+    #[test]
+    fn allow_list_by_script() {
+        let allow_list = script_language_allow_list(&[Language::Jpn, Language::Eng, Language::Kor]);
+        assert_eq!(allow_list.get(&Script::Cj), Some(&vec![Language::Jpn]));
+        assert_eq!(allow_list.get(&Script::Latin), Some(&vec![Language::Eng]));
+        assert_eq!(allow_list.get(&Script::Hangul), None);
+    }
+}
@@ -0,0 +1,42 @@
+//! The stop words of a single language.
+//!
+//! A multilingual index can't use one list of stop words for all its languages, a stop word of a
+//! language is often a meaningful word of another one, e.g. `die` in German and in English.
+//! The localized stop words are only ignored when the language of the token, either detected by
+//! the tokenizer or declared by the locales, is the language they are given for.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use charabia::{Language, Token, TokenKind};
+
+/// The stop words of each language, the words are expected to be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct LocalizedStopWords {
+    language_to_stop_words: HashMap<Language, BTreeSet<String>>,
+}
+
+impl LocalizedStopWords {
+    /// Builds the stop words from the lists keyed by the ISO 639-3 code of their language.
+    pub fn new(stop_words: &BTreeMap<String, BTreeSet<String>>) -> Self {
+        let language_to_stop_words = stop_words
+            .iter()
+            .filter(|(_, words)| !words.is_empty())
+            .map(|(name, words)| (Language::from_name(name), words.clone()))
+            .collect();
+        Self { language_to_stop_words }
+    }
+
+    /// Marks the word as a stop word when it is one of the stop words of its language.
+    pub fn classify<'o>(&self, mut token: Token<'o>) -> Token<'o> {
+        if token.kind == TokenKind::Word {
+            let is_stop_word = token
+                .language
+                .and_then(|language| self.language_to_stop_words.get(&language))
+                .map_or(false, |stop_words| stop_words.contains(token.lemma()));
+            if is_stop_word {
+                token.kind = TokenKind::StopWord;
+            }
+        }
+        token
+    }
+}
@@ -0,0 +1,72 @@
+//! Extension points allowing custom ranking rules and filters written in Rust
+//! to take part in a search without being upstreamed.
+//!
+//! Plugins are compiled in the binary and must be registered at startup,
+//! before any search is made, with [`register_ranking_rule`] and [`register_filter`].
+//!
+//! A registered ranking rule is used by adding `plugin:<name>` to the ranking rules
+//! of an index. It is executed by the bucket sort like any built-in ranking rule.
+//!
+//! A registered filter is used with the `_plugin(<name>, <argument>)` filter expression
+//! and can be combined with any other filter.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+use heed::RoTxn;
+use once_cell::sync::Lazy;
+use roaring::RoaringBitmap;
+
+use crate::{Index, Result};
+
+static RANKING_RULES: Lazy<RwLock<BTreeMap<String, Arc<dyn RankingRulePlugin>>>> =
+    Lazy::new(RwLock::default);
+static FILTERS: Lazy<RwLock<BTreeMap<String, Arc<dyn FilterPlugin>>>> = Lazy::new(RwLock::default);
+
+/// A custom ranking rule.
+pub trait RankingRulePlugin: Send + Sync {
+    /// The name of the ranking rule, referenced as `plugin:<name>` in the ranking rules.
+    fn name(&self) -> &str;
+
+    /// Splits the given universe into buckets, from the most relevant to the least relevant one.
+    ///
+    /// The buckets must be disjoint subsets of the universe. The documents of the universe
+    /// that are not part of any bucket are ranked after all the returned buckets.
+    fn buckets(
+        &self,
+        index: &Index,
+        rtxn: &RoTxn,
+        universe: &RoaringBitmap,
+    ) -> Result<Vec<RoaringBitmap>>;
+}
+
+/// A custom filter.
+pub trait FilterPlugin: Send + Sync {
+    /// The name of the filter, referenced as `_plugin(<name>, <argument>)` in the filters.
+    fn name(&self) -> &str;
+
+    /// Returns the documents of the index matching the filter for the given argument.
+    fn evaluate(&self, index: &Index, rtxn: &RoTxn, argument: &str) -> Result<RoaringBitmap>;
+}
+
+/// Registers a ranking rule, replacing any ranking rule previously registered under the same name.
+pub fn register_ranking_rule(plugin: impl RankingRulePlugin + 'static) {
+    let mut ranking_rules = RANKING_RULES.write().unwrap();
+    ranking_rules.insert(plugin.name().to_string(), Arc::new(plugin));
+}
+
+/// Registers a filter, replacing any filter previously registered under the same name.
+pub fn register_filter(plugin: impl FilterPlugin + 'static) {
+    let mut filters = FILTERS.write().unwrap();
+    filters.insert(plugin.name().to_string(), Arc::new(plugin));
+}
+
+/// Returns the ranking rule registered under this name.
+pub fn ranking_rule(name: &str) -> Option<Arc<dyn RankingRulePlugin>> {
+    RANKING_RULES.read().unwrap().get(name).cloned()
+}
+
+/// Returns the filter registered under this name.
+pub fn filter(name: &str) -> Option<Arc<dyn FilterPlugin>> {
+    FILTERS.read().unwrap().get(name).cloned()
+}
@@ -17,6 +17,10 @@ pub enum ScoreDetails {
     Sort(Sort),
     Vector(Vector),
     GeoSort(GeoSort),
+    Recency(Recency),
+    Boost(Boost),
+    #[cfg(feature = "plugins")]
+    Plugin(Plugin),
 }
 
 #[derive(Clone, Copy)]
@@ -50,6 +54,10 @@ impl ScoreDetails {
             ScoreDetails::Sort(_) => None,
             ScoreDetails::GeoSort(_) => None,
             ScoreDetails::Vector(_) => None,
+            ScoreDetails::Recency(details) => Some(details.rank),
+            ScoreDetails::Boost(_) => None,
+            #[cfg(feature = "plugins")]
+            ScoreDetails::Plugin(details) => Some(details.rank),
         }
     }
 
@@ -64,27 +72,55 @@ impl ScoreDetails {
             .unwrap_or(1.0f64)
     }
 
+    /// Returns the values the documents are ranked by, the first score being multiplied by the
+    /// factor of the boosts.
     pub fn score_values<'a>(
         details: impl Iterator<Item = &'a Self> + 'a,
     ) -> impl Iterator<Item = ScoreValue<'a>> + 'a {
+        let details: Vec<_> = details.collect();
+        let factor = Self::boost_factor(details.iter().copied());
+        // a document only ranked by sort values is given the factor of its boosts as score
+        let is_boosted = details.iter().any(|details| matches!(details, ScoreDetails::Boost(_)));
+        let is_scored = details
+            .iter()
+            .any(|details| details.rank().is_some() || matches!(details, ScoreDetails::Vector(_)));
+        let unscored_boost = (is_boosted && !is_scored).then_some(ScoreValue::Score(factor));
+
+        let mut boost = Some(factor);
         details
-            .map(ScoreDetails::rank_or_value)
+            .into_iter()
+            .filter_map(ScoreDetails::rank_or_value)
             .coalesce(|left, right| match (left, right) {
                 (RankOrValue::Rank(left), RankOrValue::Rank(right)) => {
                     Ok(RankOrValue::Rank(Rank::merge(left, right)))
                 }
                 (left, right) => Err((left, right)),
             })
-            .map(|rank_or_value| match rank_or_value {
-                RankOrValue::Rank(r) => ScoreValue::Score(r.local_score()),
-                RankOrValue::Sort(s) => ScoreValue::Sort(s),
-                RankOrValue::GeoSort(g) => ScoreValue::GeoSort(g),
-                RankOrValue::Score(s) => ScoreValue::Score(s),
+            .map(move |rank_or_value| {
+                let mut boosted =
+                    |score: f64| ScoreValue::Score(score * boost.take().unwrap_or(1.0));
+                match rank_or_value {
+                    RankOrValue::Rank(r) => boosted(r.local_score()),
+                    RankOrValue::Sort(s) => ScoreValue::Sort(s),
+                    RankOrValue::GeoSort(g) => ScoreValue::GeoSort(g),
+                    RankOrValue::Score(s) => boosted(s),
+                }
             })
+            .chain(unscored_boost)
     }
 
-    fn rank_or_value(&self) -> RankOrValue<'_> {
-        match self {
+    /// Returns the product of the factors of the boosts, `1.0` when there is none.
+    pub fn boost_factor<'a>(details: impl Iterator<Item = &'a Self>) -> f64 {
+        details
+            .filter_map(|details| match details {
+                ScoreDetails::Boost(boost) => Some(boost.factor),
+                _ => None,
+            })
+            .product()
+    }
+
+    fn rank_or_value(&self) -> Option<RankOrValue<'_>> {
+        let rank_or_value = match self {
             ScoreDetails::Words(w) => RankOrValue::Rank(w.rank()),
             ScoreDetails::Typo(t) => RankOrValue::Rank(t.rank()),
             ScoreDetails::Proximity(p) => RankOrValue::Rank(*p),
@@ -97,7 +133,13 @@ impl ScoreDetails {
             ScoreDetails::Vector(vector) => RankOrValue::Score(
                 vector.value_similarity.as_ref().map(|(_, s)| *s as f64).unwrap_or(0.0f64),
             ),
-        }
+            ScoreDetails::Recency(r) => RankOrValue::Rank(r.rank),
+            // the boosts multiply the score instead of being a value of their own
+            ScoreDetails::Boost(_) => return None,
+            #[cfg(feature = "plugins")]
+            ScoreDetails::Plugin(p) => RankOrValue::Rank(p.rank),
+        };
+        Some(rank_or_value)
     }
 
     /// Panics
@@ -256,6 +298,31 @@ impl ScoreDetails {
                     details_map.insert(vector, details);
                     order += 1;
                 }
+                ScoreDetails::Recency(recency) => {
+                    let recency_details = serde_json::json!({
+                        "order": order,
+                        "score": recency.rank.local_score(),
+                    });
+                    details_map.insert(format!("recency({})", recency.field_name), recency_details);
+                    order += 1;
+                }
+                ScoreDetails::Boost(boost) => {
+                    let boost_details = serde_json::json!({
+                        "order": order,
+                        "factor": boost.factor,
+                    });
+                    details_map.insert("boost".into(), boost_details);
+                    order += 1;
+                }
+                #[cfg(feature = "plugins")]
+                ScoreDetails::Plugin(plugin) => {
+                    let plugin_details = serde_json::json!({
+                        "order": order,
+                        "score": plugin.rank.local_score(),
+                    });
+                    details_map.insert(format!("plugin:{}", plugin.name), plugin_details);
+                    order += 1;
+                }
             }
         }
         details_map
@@ -408,6 +475,13 @@ impl ExactAttribute {
     }
 }
 
+#[cfg(feature = "plugins")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Plugin {
+    pub name: String,
+    pub rank: Rank,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Sort {
     pub field_name: String,
@@ -488,6 +562,20 @@ pub struct Vector {
     pub value_similarity: Option<(Vec<f32>, f32)>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recency {
+    pub field_name: String,
+    /// The newest documents have the highest rank, the documents without a timestamp the lowest.
+    pub rank: Rank,
+}
+
+/// The boosts of the search matched by a document.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Boost {
+    /// The product of the factors of the matched boosts.
+    pub factor: f64,
+}
+
 impl GeoSort {
     pub fn distance(&self) -> Option<f64> {
         self.value.map(|value| distance_between_two_points(&self.target_point, &value))
@@ -0,0 +1,41 @@
+use heed::RoTxn;
+use roaring::RoaringBitmap;
+
+use crate::score_details::{self, ScoreDetails};
+use crate::{DocumentId, Filter, Index, Result};
+
+/// A factor multiplying the ranking score of the documents matching a filter.
+///
+/// The boosts are applied once the ranking rules have ranked the documents, they can only
+/// reorder the documents of the requested page and of the ones before it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreBoost<'a> {
+    pub filter: Filter<'a>,
+    pub factor: f64,
+}
+
+/// Appends to the scores of each document the factor of the boosts it matches, if any.
+pub(crate) fn boost_document_scores(
+    rtxn: &RoTxn,
+    index: &Index,
+    boosts: &[ScoreBoost],
+    documents_ids: &[DocumentId],
+    document_scores: &mut [Vec<ScoreDetails>],
+) -> Result<()> {
+    let documents: RoaringBitmap = documents_ids.iter().copied().collect();
+    let mut boosted = Vec::with_capacity(boosts.len());
+    for ScoreBoost { filter, factor } in boosts {
+        boosted.push((filter.evaluate(rtxn, index)? & &documents, *factor));
+    }
+
+    for (docid, scores) in documents_ids.iter().zip(document_scores) {
+        let mut factors =
+            boosted.iter().filter(|(docids, _)| docids.contains(*docid)).map(|(_, f)| f).peekable();
+        if factors.peek().is_some() {
+            let factor = factors.product();
+            scores.push(ScoreDetails::Boost(score_details::Boost { factor }));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use heed::RoTxn;
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
+
+use crate::{Filter, Index, Result};
+
+/// The documents taken out of, or ranked after all the others in, every search made on an index.
+///
+/// They are resolved at search time, so the documents added after the exclusions were set
+/// are also concerned.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchExclusions {
+    #[serde(default)]
+    pub document_ids: Vec<String>,
+    #[serde(default)]
+    pub filter: Option<String>,
+    #[serde(default)]
+    pub mode: ExclusionMode,
+}
+
+/// What happens to the documents concerned by the exclusions.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ExclusionMode {
+    /// The documents are never returned.
+    #[default]
+    Exclude,
+    /// The documents are returned after all the other documents matching the search.
+    Deboost,
+}
+
+impl SearchExclusions {
+    pub fn is_empty(&self) -> bool {
+        self.document_ids.is_empty() && self.filter.is_none()
+    }
+
+    /// Returns the internal ids of the documents concerned by the exclusions,
+    /// the unknown document ids are ignored.
+    pub fn documents_ids(&self, rtxn: &RoTxn, index: &Index) -> Result<RoaringBitmap> {
+        let external_documents_ids = index.external_documents_ids();
+        let mut docids = RoaringBitmap::new();
+        for external_id in &self.document_ids {
+            if let Some(docid) = external_documents_ids.get(rtxn, external_id)? {
+                docids.insert(docid);
+            }
+        }
+
+        if let Some(filter) = self.filter.as_deref().map(Filter::from_str).transpose()?.flatten() {
+            docids |= filter.evaluate(rtxn, index)?;
+        }
+
+        Ok(docids)
+    }
+}
@@ -0,0 +1,165 @@
+use std::collections::BTreeMap;
+use std::ops::ControlFlow;
+
+use heed::BytesDecode;
+use roaring::RoaringBitmap;
+
+use crate::error::UserError;
+use crate::heed_codec::facet::{FacetGroupKeyCodec, OrderedF64Codec};
+use crate::heed_codec::BytesRefCodec;
+use crate::search::facet::facet_distribution_iter::lexicographically_iterate_over_facet_distribution;
+use crate::{Index, Result};
+
+/// The aggregation of the numeric values of a field among the candidates.
+///
+/// Every value of a document is aggregated, a document with an array of numbers counts once for
+/// each number of the array.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FacetAggregate {
+    /// The number of values aggregated.
+    pub count: u64,
+    pub sum: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl FacetAggregate {
+    pub fn avg(&self) -> f64 {
+        self.sum / self.count as f64
+    }
+}
+
+/// Computes the aggregates of numeric fields among the candidates from the facet number
+/// databases, without reading the documents.
+pub struct FacetAggregates<'a> {
+    fields: Vec<String>,
+    candidates: RoaringBitmap,
+    rtxn: &'a heed::RoTxn<'a>,
+    index: &'a Index,
+}
+
+impl<'a> FacetAggregates<'a> {
+    pub fn new(rtxn: &'a heed::RoTxn, index: &'a Index) -> FacetAggregates<'a> {
+        FacetAggregates { fields: Vec::new(), candidates: RoaringBitmap::new(), rtxn, index }
+    }
+
+    pub fn fields<I: IntoIterator<Item = A>, A: AsRef<str>>(&mut self, fields: I) -> &mut Self {
+        self.fields = fields.into_iter().map(|field| field.as_ref().to_string()).collect();
+        self
+    }
+
+    pub fn candidates(&mut self, candidates: RoaringBitmap) -> &mut Self {
+        self.candidates = candidates;
+        self
+    }
+
+    /// Returns the aggregate of each field, the fields without any numeric value among the
+    /// candidates are omitted.
+    pub fn execute(&self) -> Result<BTreeMap<String, FacetAggregate>> {
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let filterable_fields = self.index.filterable_fields(self.rtxn)?;
+
+        if let Some(field) = self.fields.iter().find(|f| !crate::is_faceted(f, &filterable_fields))
+        {
+            let (valid_fields, hidden_fields) =
+                self.index.remove_hidden_fields(self.rtxn, filterable_fields)?;
+            return Err(UserError::InvalidAggregateAttribute {
+                field: field.clone(),
+                valid_fields,
+                hidden_fields,
+            }
+            .into());
+        }
+
+        let db =
+            self.index.facet_id_f64_docids.remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>();
+        let mut aggregates = BTreeMap::new();
+        for field in &self.fields {
+            let Some(field_id) = fields_ids_map.id(field) else { continue };
+
+            // the values are iterated in ascending order along with their number of candidates
+            let mut aggregate: Option<FacetAggregate> = None;
+            lexicographically_iterate_over_facet_distribution(
+                self.rtxn,
+                db,
+                field_id,
+                &self.candidates,
+                |facet_key, count, _| {
+                    let value = OrderedF64Codec::bytes_decode(facet_key).unwrap();
+                    let aggregate = aggregate.get_or_insert(FacetAggregate {
+                        count: 0,
+                        sum: 0.0,
+                        min: value,
+                        max: value,
+                    });
+                    aggregate.count += count;
+                    aggregate.sum += value * count as f64;
+                    aggregate.max = value;
+                    Ok(ControlFlow::Continue(()))
+                },
+            )?;
+
+            if let Some(aggregate) = aggregate {
+                aggregates.insert(field.clone(), aggregate);
+            }
+        }
+
+        Ok(aggregates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use big_s::S;
+    use maplit::hashset;
+
+    use crate::index::tests::TempIndex;
+    use crate::{milli_snap, Error, FacetAggregates, UserError};
+
+    #[test]
+    fn aggregates_of_the_candidates() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("price"), S("stock"), S("name") })
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "name": "shirt", "price": 20.5, "stock": 3 },
+                { "id": 1, "name": "hat", "price": 10, "stock": [1, 2] },
+                { "id": 2, "name": "shoes", "price": 50 },
+                { "id": 3, "name": "socks", "price": "free", "stock": 10 },
+            ]))
+            .unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let aggregates = FacetAggregates::new(&txn, &index)
+            .fields(["price", "stock", "name"])
+            .candidates((0..4).collect())
+            .execute()
+            .unwrap();
+        milli_snap!(format!("{aggregates:?}"), @r###"{"price": FacetAggregate { count: 3, sum: 80.5, min: 10.0, max: 50.0 }, "stock": FacetAggregate { count: 4, sum: 16.0, min: 1.0, max: 10.0 }}"###);
+        assert_eq!(aggregates["stock"].avg(), 4.0);
+
+        let aggregates = FacetAggregates::new(&txn, &index)
+            .fields(["price", "stock"])
+            .candidates([0, 2].into_iter().collect())
+            .execute()
+            .unwrap();
+        milli_snap!(format!("{aggregates:?}"), @r###"{"price": FacetAggregate { count: 2, sum: 70.5, min: 20.5, max: 50.0 }, "stock": FacetAggregate { count: 1, sum: 3.0, min: 3.0, max: 3.0 }}"###);
+
+        let error = FacetAggregates::new(&txn, &index)
+            .fields(["id"])
+            .candidates((0..4).collect())
+            .execute()
+            .unwrap_err();
+        assert!(
+            matches!(&error, Error::UserError(UserError::InvalidAggregateAttribute { field, .. }) if field == "id"),
+            "{error}"
+        );
+    }
+}
@@ -1,22 +1,19 @@
-use std::collections::{BTreeMap, HashMap, HashSet};
-use std::ops::ControlFlow;
-use std::{fmt, mem};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::ops::{Bound, ControlFlow, Range};
+use std::fmt;
 
-use heed::types::Bytes;
-use heed::BytesDecode;
 use indexmap::IndexMap;
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
 
-use crate::error::UserError;
-use crate::facet::FacetType;
-use crate::heed_codec::facet::{
-    FacetGroupKeyCodec, FieldDocIdFacetF64Codec, FieldDocIdFacetStringCodec, OrderedF64Codec,
-};
+use crate::error::{InternalError, UserError};
+use crate::heed_codec::facet::{FacetGroupKeyCodec, OrderedF64Codec};
 use crate::heed_codec::{BytesRefCodec, StrRefCodec};
 use crate::search::facet::facet_distribution_iter::{
     count_iterate_over_facet_distribution, lexicographically_iterate_over_facet_distribution,
 };
+use crate::search::facet::{facet_max_value, facet_min_value, find_docids_of_facet_within_bounds};
+use crate::update::{extract_facet_values, truncate_string, FilterableValues};
 use crate::{FieldId, Index, Result};
 
 /// The default number of values by facets that will
@@ -37,6 +34,15 @@ pub enum OrderBy {
     Count,
 }
 
+/// The buckets the numeric values of a facet are counted in.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FacetBuckets {
+    /// The given ranges, each one including its start and excluding its end.
+    Ranges(Vec<Range<f64>>),
+    /// Contiguous ranges of the given width, covering the values of the candidates.
+    Interval(f64),
+}
+
 pub struct FacetDistribution<'a> {
     facets: Option<HashMap<String, OrderBy>>,
     candidates: Option<RoaringBitmap>,
@@ -88,72 +94,106 @@ impl<'a> FacetDistribution<'a> {
 
     /// There is a small amount of candidates OR we ask for facet string values so we
     /// decide to iterate over the facet values of each one of them, one by one.
-    fn facet_distribution_from_documents(
+    ///
+    /// Every candidate document is read once and its values are dispatched to all the given
+    /// fields, the distributions are returned in the same order as the fields.
+    fn facet_distributions_from_documents(
         &self,
-        field_id: FieldId,
-        facet_type: FacetType,
+        fields: &[&str],
         candidates: &RoaringBitmap,
-        distribution: &mut IndexMap<String, u64>,
-    ) -> heed::Result<()> {
-        match facet_type {
-            FacetType::Number => {
-                let mut key_buffer: Vec<_> = field_id.to_be_bytes().to_vec();
-
-                let distribution_prelength = distribution.len();
-                let db = self.index.field_id_docid_facet_f64s;
-                for docid in candidates {
-                    key_buffer.truncate(mem::size_of::<FieldId>());
-                    key_buffer.extend_from_slice(&docid.to_be_bytes());
-                    let iter = db
-                        .remap_key_type::<Bytes>()
-                        .prefix_iter(self.rtxn, &key_buffer)?
-                        .remap_key_type::<FieldDocIdFacetF64Codec>();
-
-                    for result in iter {
-                        let ((_, _, value), ()) = result?;
-                        *distribution.entry(value.to_string()).or_insert(0) += 1;
-
-                        if distribution.len() - distribution_prelength == self.max_values_per_facet
-                        {
-                            break;
-                        }
-                    }
+    ) -> Result<Vec<IndexMap<String, u64>>> {
+        struct FieldDistribution<'a> {
+            name: &'a str,
+            geo_field: bool,
+            numbers: IndexMap<String, u64>,
+            strings: BTreeMap<String, (String, u64)>,
+            strings_done: bool,
+        }
+
+        let mut distributions: Vec<_> = fields
+            .iter()
+            .map(|&name| FieldDistribution {
+                name,
+                geo_field: name == "_geo.lat" || name == "_geo.lng",
+                numbers: IndexMap::new(),
+                strings: BTreeMap::new(),
+                strings_done: false,
+            })
+            .collect();
+
+        // The fields of the documents holding the values of the facets, directly or nested.
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let holding_fields: HashMap<FieldId, &str> = fields_ids_map
+            .iter()
+            .filter(|(_, name)| fields.iter().any(|field| crate::is_faceted_by(field, name)))
+            .collect();
+
+        for docid in candidates {
+            let Some(document) = self.index.documents.get(self.rtxn, &docid)? else {
+                continue;
+            };
+            // the values are flattened the same way they are when indexed
+            let mut object = serde_json::Map::new();
+            for (field_id, value) in document.iter() {
+                if let Some(name) = holding_fields.get(&field_id) {
+                    let value = serde_json::from_slice(value).map_err(InternalError::SerdeJson)?;
+                    object.insert(name.to_string(), value);
                 }
             }
-            FacetType::String => {
-                let mut normalized_distribution = BTreeMap::new();
-                let mut key_buffer: Vec<_> = field_id.to_be_bytes().to_vec();
-
-                let db = self.index.field_id_docid_facet_strings;
-                'outer: for docid in candidates {
-                    key_buffer.truncate(mem::size_of::<FieldId>());
-                    key_buffer.extend_from_slice(&docid.to_be_bytes());
-                    let iter = db
-                        .remap_key_type::<Bytes>()
-                        .prefix_iter(self.rtxn, &key_buffer)?
-                        .remap_key_type::<FieldDocIdFacetStringCodec>();
-
-                    for result in iter {
-                        let ((_, _, normalized_value), original_value) = result?;
-                        let (_, count) = normalized_distribution
-                            .entry(normalized_value)
-                            .or_insert_with(|| (original_value, 0));
-                        *count += 1;
-
-                        if normalized_distribution.len() == self.max_values_per_facet {
-                            break 'outer;
-                        }
+            let document = flatten_serde_json::flatten(&object);
+
+            for field in &mut distributions {
+                let Some(value) = document.get(field.name) else { continue };
+                let FilterableValues::Values { mut numbers, mut strings } =
+                    extract_facet_values(value, field.geo_field)
+                else {
+                    continue;
+                };
+
+                // a value is only counted once by document
+                numbers.sort_unstable_by(f64::total_cmp);
+                numbers.dedup();
+                for number in numbers {
+                    *field.numbers.entry(number.to_string()).or_insert(0) += 1;
+
+                    if field.numbers.len() == self.max_values_per_facet {
+                        break;
                     }
                 }
 
-                let iter = normalized_distribution
-                    .into_iter()
-                    .map(|(_normalized, (original, count))| (original.to_string(), count));
-                distribution.extend(iter);
+                if field.strings_done {
+                    continue;
+                }
+                strings.sort_unstable();
+                strings.dedup();
+                let mut previous = None;
+                for (normalized, original) in strings {
+                    let normalized = truncate_string(normalized);
+                    if normalized.is_empty() || previous.as_ref() == Some(&normalized) {
+                        continue;
+                    }
+                    let (_, count) =
+                        field.strings.entry(normalized.clone()).or_insert_with(|| (original, 0));
+                    *count += 1;
+                    previous = Some(normalized);
+
+                    if field.strings.len() == self.max_values_per_facet {
+                        field.strings_done = true;
+                        break;
+                    }
+                }
             }
         }
 
-        Ok(())
+        Ok(distributions
+            .into_iter()
+            .map(|FieldDistribution { mut numbers, strings, .. }| {
+                let iter =
+                    strings.into_iter().map(|(_normalized, (original, count))| (original, count));
+                numbers.extend(iter);
+                numbers
+            })
+            .collect())
     }
 
     /// There is too much documents, we use the facet levels to move throught
@@ -225,46 +265,27 @@ impl<'a> FacetDistribution<'a> {
         )
     }
 
-    fn facet_values(
+    /// There is too much documents or the values must be ordered by count,
+    /// we use the facet levels of both the numbers and the strings.
+    fn facet_values_from_facet_levels(
         &self,
         field_id: FieldId,
+        candidates: &RoaringBitmap,
         order_by: OrderBy,
     ) -> heed::Result<IndexMap<String, u64>> {
-        use FacetType::{Number, String};
-
         let mut distribution = IndexMap::new();
-        match (order_by, &self.candidates) {
-            (OrderBy::Lexicographic, Some(cnd)) if cnd.len() <= CANDIDATES_THRESHOLD => {
-                // Classic search, candidates were specified, we must return facet values only related
-                // to those candidates. We also enter here for facet strings for performance reasons.
-                self.facet_distribution_from_documents(field_id, Number, cnd, &mut distribution)?;
-                self.facet_distribution_from_documents(field_id, String, cnd, &mut distribution)?;
-            }
-            _ => {
-                let universe;
-                let candidates = match &self.candidates {
-                    Some(cnd) => cnd,
-                    None => {
-                        universe = self.index.documents_ids(self.rtxn)?;
-                        &universe
-                    }
-                };
-
-                self.facet_numbers_distribution_from_facet_levels(
-                    field_id,
-                    candidates,
-                    order_by,
-                    &mut distribution,
-                )?;
-                self.facet_strings_distribution_from_facet_levels(
-                    field_id,
-                    candidates,
-                    order_by,
-                    &mut distribution,
-                )?;
-            }
-        };
-
+        self.facet_numbers_distribution_from_facet_levels(
+            field_id,
+            candidates,
+            order_by,
+            &mut distribution,
+        )?;
+        self.facet_strings_distribution_from_facet_levels(
+            field_id,
+            candidates,
+            order_by,
+            &mut distribution,
+        )?;
         Ok(distribution)
     }
 
@@ -353,6 +374,10 @@ impl<'a> FacetDistribution<'a> {
         };
 
         let mut distribution = BTreeMap::new();
+        // The fields whose values are fetched from the documents, they are computed together
+        // so the candidates are only iterated once.
+        let mut from_documents = Vec::new();
+        let mut universe = None;
         for (fid, name) in fields_ids_map.iter() {
             if crate::is_faceted(name, &fields) {
                 let order_by = self
@@ -360,13 +385,106 @@ impl<'a> FacetDistribution<'a> {
                     .as_ref()
                     .and_then(|facets| facets.get(name).copied())
                     .unwrap_or(self.default_order_by);
-                let values = self.facet_values(fid, order_by)?;
+                match (order_by, &self.candidates) {
+                    (OrderBy::Lexicographic, Some(cnd)) if cnd.len() <= CANDIDATES_THRESHOLD => {
+                        // Classic search, candidates were specified, we must return facet values only related
+                        // to those candidates. We also enter here for facet strings for performance reasons.
+                        from_documents.push(name);
+                    }
+                    (_, candidates) => {
+                        let candidates = match candidates {
+                            Some(cnd) => cnd,
+                            None => match universe {
+                                Some(ref universe) => universe,
+                                None => universe.insert(self.index.documents_ids(self.rtxn)?),
+                            },
+                        };
+                        let values =
+                            self.facet_values_from_facet_levels(fid, candidates, order_by)?;
+                        distribution.insert(name.to_string(), values);
+                    }
+                }
+            }
+        }
+
+        if let Some(candidates) = self.candidates.as_ref().filter(|_| !from_documents.is_empty()) {
+            let values = self.facet_distributions_from_documents(&from_documents, candidates)?;
+            for (name, values) in from_documents.into_iter().zip(values) {
                 distribution.insert(name.to_string(), values);
             }
         }
 
         Ok(distribution)
     }
+
+    /// Returns the number of candidates having a numeric value of the field in each bucket.
+    ///
+    /// The interval buckets start at the multiple of the interval below the smallest value of the
+    /// candidates, at most `max_values_per_facet` of them are returned.
+    pub fn bucket_distribution(
+        &self,
+        field: &str,
+        buckets: &FacetBuckets,
+    ) -> Result<Vec<(Range<f64>, u64)>> {
+        let filterable_fields = self.index.filterable_fields(self.rtxn)?;
+        if !crate::is_faceted(field, &filterable_fields) {
+            return Err(UserError::InvalidFacetsDistribution {
+                invalid_facets_name: BTreeSet::from([field.to_string()]),
+                valid_facets_name: filterable_fields.into_iter().collect(),
+            }
+            .into());
+        }
+
+        let candidates = match &self.candidates {
+            Some(candidates) => candidates.clone(),
+            None => self.index.documents_ids(self.rtxn)?,
+        };
+        let field_id = self.index.fields_ids_map(self.rtxn)?.id(field);
+
+        let ranges = match buckets {
+            FacetBuckets::Ranges(ranges) => ranges.clone(),
+            FacetBuckets::Interval(interval) => {
+                let extremes = match field_id {
+                    Some(fid) => facet_min_value(self.index, self.rtxn, fid, candidates.clone())?
+                        .zip(facet_max_value(self.index, self.rtxn, fid, candidates.clone())?),
+                    None => None,
+                };
+                let mut ranges = Vec::new();
+                if let Some((min, max)) = extremes {
+                    let first = (min / interval).floor() * interval;
+                    for i in 0..self.max_values_per_facet {
+                        let start = first + i as f64 * interval;
+                        if start > max {
+                            break;
+                        }
+                        ranges.push(start..start + interval);
+                    }
+                }
+                ranges
+            }
+        };
+
+        let mut distribution = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            let mut docids = RoaringBitmap::new();
+            if let Some(fid) = field_id {
+                let left = Bound::Included(range.start);
+                let right = Bound::Excluded(range.end);
+                find_docids_of_facet_within_bounds::<OrderedF64Codec>(
+                    self.rtxn,
+                    self.index.facet_id_f64_docids,
+                    fid,
+                    &left,
+                    &right,
+                    &mut docids,
+                )?;
+            }
+            let count = docids.intersection_len(&candidates);
+            distribution.push((range, count));
+        }
+
+        Ok(distribution)
+    }
 }
 
 impl fmt::Debug for FacetDistribution<'_> {
@@ -398,7 +516,7 @@ mod tests {
 
     use crate::documents::documents_batch_reader_from_objects;
     use crate::index::tests::TempIndex;
-    use crate::{milli_snap, FacetDistribution, OrderBy};
+    use crate::{milli_snap, FacetBuckets, FacetDistribution, OrderBy};
 
     #[test]
     fn few_candidates_few_facet_values() {
@@ -475,6 +593,41 @@ mod tests {
         milli_snap!(format!("{map:?}"), @r###"{"colour": {"Blue": 2}}"###);
     }
 
+    #[test]
+    fn few_candidates_multiple_facets() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.autogenerate_docids = true;
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("colour"), S("size"), S("shop") })
+            })
+            .unwrap();
+
+        let documents = documents!([
+            { "colour": ["Blue", "blue"], "size": 42, "shop": { "city": "Paris" } },
+            { "colour": "RED", "size": [42, 43], "shop": { "city": "paris" } },
+            { "colour": "Red", "shop": [{ "city": "Lyon" }, { "city": "Paris" }] }
+        ]);
+
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        // the values of all the facets, nested or not, are read from the same pass
+        let map = FacetDistribution::new(&txn, &index)
+            .facets([
+                ("colour", OrderBy::default()),
+                ("size", OrderBy::default()),
+                ("shop.city", OrderBy::default()),
+            ])
+            .candidates([0, 1, 2].iter().copied().collect())
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"colour": {"Blue": 1, "RED": 2}, "shop.city": {"Lyon": 1, "Paris": 3}, "size": {"42": 2, "43": 1}}"###);
+    }
+
     #[test]
     fn many_candidates_few_facet_values() {
         let mut index = TempIndex::new_with_map_size(4096 * 10_000);
@@ -832,4 +985,53 @@ mod tests {
 
         milli_snap!(format!("{map:?}"), "candidates_217_777", @r###"{"colour": (218.0, 1776.0)}"###);
     }
+
+    #[test]
+    fn facet_bucket_distribution() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.autogenerate_docids = true;
+
+        index
+            .update_settings(|settings| settings.set_filterable_fields(hashset! { S("price") }))
+            .unwrap();
+
+        let documents = documents!([
+            { "price": 5 },
+            { "price": 15 },
+            { "price": [25, 48] },
+            { "price": "free" },
+            { "price": 55 },
+            { "price": 99.5 },
+        ]);
+
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let ranges = FacetBuckets::Ranges(vec![0.0..50.0, 50.0..100.0]);
+        let buckets =
+            FacetDistribution::new(&txn, &index).bucket_distribution("price", &ranges).unwrap();
+        milli_snap!(format!("{buckets:?}"), @"[(0.0..50.0, 3), (50.0..100.0, 2)]");
+
+        let interval = FacetBuckets::Interval(20.0);
+        let buckets =
+            FacetDistribution::new(&txn, &index).bucket_distribution("price", &interval).unwrap();
+        milli_snap!(format!("{buckets:?}"), @"[(0.0..20.0, 2), (20.0..40.0, 1), (40.0..60.0, 2), (60.0..80.0, 0), (80.0..100.0, 1)]");
+
+        let buckets = FacetDistribution::new(&txn, &index)
+            .candidates((0..2).collect())
+            .bucket_distribution("price", &interval)
+            .unwrap();
+        milli_snap!(format!("{buckets:?}"), @"[(0.0..20.0, 2)]");
+
+        let buckets = FacetDistribution::new(&txn, &index)
+            .max_values_per_facet(2)
+            .bucket_distribution("price", &interval)
+            .unwrap();
+        milli_snap!(format!("{buckets:?}"), @"[(0.0..20.0, 2), (20.0..40.0, 1)]");
+
+        let error =
+            FacetDistribution::new(&txn, &index).bucket_distribution("name", &ranges).unwrap_err();
+        milli_snap!(error, @"Invalid facet distribution, attribute `name` is not filterable. The available filterable attribute is `price`.");
+    }
 }
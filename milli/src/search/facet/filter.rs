@@ -8,10 +8,12 @@ use roaring::RoaringBitmap;
 use serde_json::Value;
 
 use super::facet_range_search;
+use super::nested::{leaves, values_at};
 use crate::error::{Error, UserError};
 use crate::heed_codec::facet::{
     FacetGroupKey, FacetGroupKeyCodec, FacetGroupValueCodec, OrderedF64Codec,
 };
+use crate::unit_conversion::{split_converted_field, UnitConversion};
 use crate::{distance_between_two_points, lat_lng_to_xyz, FieldId, Index, Result};
 
 /// The maximum number of filters the filter AST can process.
@@ -55,6 +57,10 @@ impl Display for BadGeoError {
 enum FilterError<'a> {
     AttributeNotFilterable { attribute: &'a str, filterable_fields: HashSet<String> },
     ParseGeoError(BadGeoError),
+    UnknownUnit { field: &'a str, unit: &'a str, valid_units: Vec<String> },
+    NegativeTolerance(f64),
+    UnknownPlugin(&'a str),
+    NotOnValues(&'a str),
     TooDeep,
 }
 impl<'a> std::error::Error for FilterError<'a> {}
@@ -96,6 +102,22 @@ impl<'a> Display for FilterError<'a> {
                 MAX_FILTER_DEPTH
             ),
             Self::ParseGeoError(error) => write!(f, "{}", error),
+            Self::UnknownUnit { field, unit, valid_units } => write!(
+                f,
+                "Unit `{}` can't be used to filter on `{}`. Available units are: `{}`.",
+                unit,
+                field,
+                valid_units.join(", "),
+            ),
+            Self::NegativeTolerance(tolerance) => write!(
+                f,
+                "The tolerance of the `~=` operation must be positive, but found `{}`.",
+                tolerance
+            ),
+            Self::UnknownPlugin(name) => write!(f, "`{}` filter plugin is not registered.", name),
+            Self::NotOnValues(name) => {
+                write!(f, "`{}` can't filter the elements of an array of objects.", name)
+            }
         }
     }
 }
@@ -112,6 +134,12 @@ impl<'a> From<Filter<'a>> for FilterCondition<'a> {
     }
 }
 
+impl Display for Filter<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.condition.fmt(f)
+    }
+}
+
 impl<'a> Filter<'a> {
     pub fn from_json(facets: &'a Value) -> Result<Option<Self>> {
         match facets {
@@ -218,6 +246,26 @@ impl<'a> Filter<'a> {
 
         Ok(Some(Self { condition }))
     }
+
+    /// Returns the number of nested `OR` and `AND` levels of the filter.
+    pub fn depth(&self) -> usize {
+        self.condition.depth()
+    }
+
+    /// Returns the number of conditions the filter is made of.
+    pub fn complexity(&self) -> usize {
+        self.condition.complexity()
+    }
+
+    /// Returns the names of the fields the filter is made of.
+    pub fn fields(&self) -> Vec<&str> {
+        self.condition.fields()
+    }
+
+    /// Returns the filter without its top-level clauses made only of the specified field.
+    pub fn without_field(&self, field: &str) -> Option<Self> {
+        self.condition.without_field(field).map(|condition| Self { condition })
+    }
 }
 
 impl<'a> Filter<'a> {
@@ -255,6 +303,15 @@ impl<'a> Filter<'a> {
             Condition::Between { from, to } => {
                 (Included(from.parse_finite_float()?), Included(to.parse_finite_float()?))
             }
+            Condition::Approximately { value, tolerance: tolerance_token } => {
+                let value = value.parse_finite_float()?;
+                let tolerance = tolerance_token.parse_finite_float()?;
+                if tolerance < 0.0 {
+                    return Err(tolerance_token
+                        .as_external_error(FilterError::NegativeTolerance(tolerance)))?;
+                }
+                (Included(value - tolerance), Included(value + tolerance))
+            }
             Condition::Null => {
                 let is_null = index.null_faceted_documents_ids(rtxn, field_id)?;
                 return Ok(is_null);
@@ -302,6 +359,89 @@ impl<'a> Filter<'a> {
         Ok(output)
     }
 
+    /// Evaluates a condition on a numeric field converted into a given unit, e.g. `price:usd < 100`.
+    ///
+    /// The condition is evaluated for the documents expressed in each unit with its values
+    /// converted back into that unit, the documents without a known unit never match.
+    fn evaluate_converted_operator(
+        rtxn: &heed::RoTxn,
+        index: &Index,
+        filterable_fields: &HashSet<String>,
+        fid: &Token<'a>,
+        (field, unit, conversion): (&str, &str, &UnitConversion),
+        operator: &Condition<'a>,
+    ) -> Result<RoaringBitmap> {
+        let unit_attribute = conversion.unit_attribute.as_str();
+        if !crate::is_faceted(unit_attribute, filterable_fields) {
+            return Err(fid.as_external_error(FilterError::AttributeNotFilterable {
+                attribute: unit_attribute,
+                filterable_fields: filterable_fields.clone(),
+            }))?;
+        }
+        let Some(factors) = conversion.factors_to(unit) else {
+            return Err(fid.as_external_error(FilterError::UnknownUnit {
+                field,
+                unit,
+                valid_units: conversion.rates.keys().cloned().collect(),
+            }))?;
+        };
+
+        let field_ids_map = index.fields_ids_map(rtxn)?;
+        let (Some(field_id), Some(unit_field_id)) =
+            (field_ids_map.id(field), field_ids_map.id(unit_attribute))
+        else {
+            return Ok(RoaringBitmap::new());
+        };
+
+        // a value `v` expressed in a unit matches `v * factor < x` when `v < x / factor`
+        let convert = |token: &Token<'a>, factor: f64| -> Result<Token<'a>> {
+            let value = token.parse_finite_float()? / factor;
+            Ok(Token::new(token.original_span(), Some(value.to_string())))
+        };
+
+        let mut output = RoaringBitmap::new();
+        for (source_unit, factor) in factors {
+            let unit_docids = index
+                .facet_id_string_docids
+                .get(
+                    rtxn,
+                    &FacetGroupKey {
+                        field_id: unit_field_id,
+                        level: 0,
+                        left_bound: &crate::normalize_facet(source_unit),
+                    },
+                )?
+                .map(|v| v.bitmap)
+                .unwrap_or_default();
+            if unit_docids.is_empty() {
+                continue;
+            }
+
+            let operator = match operator {
+                Condition::GreaterThan(val) => Condition::GreaterThan(convert(val, factor)?),
+                Condition::GreaterThanOrEqual(val) => {
+                    Condition::GreaterThanOrEqual(convert(val, factor)?)
+                }
+                Condition::LowerThan(val) => Condition::LowerThan(convert(val, factor)?),
+                Condition::LowerThanOrEqual(val) => {
+                    Condition::LowerThanOrEqual(convert(val, factor)?)
+                }
+                Condition::Between { from, to } => {
+                    Condition::Between { from: convert(from, factor)?, to: convert(to, factor)? }
+                }
+                Condition::Approximately { value, tolerance } => Condition::Approximately {
+                    value: convert(value, factor)?,
+                    tolerance: convert(tolerance, factor)?,
+                },
+                Condition::Equal(val) => Condition::Equal(convert(val, factor)?),
+                Condition::NotEqual(val) => Condition::NotEqual(convert(val, factor)?),
+                Condition::Null | Condition::Empty | Condition::Exists => operator.clone(),
+            };
+            output |= Self::evaluate_operator(rtxn, index, field_id, &operator)? & unit_docids;
+        }
+        Ok(output)
+    }
+
     /// Aggregates the documents ids that are part of the specified range automatically
     /// going deeper through the levels.
     fn explore_facet_number_levels(
@@ -360,6 +500,23 @@ impl<'a> Filter<'a> {
                     } else {
                         Ok(RoaringBitmap::new())
                     }
+                } else if let Some((field, unit, conversion)) =
+                    split_converted_field(fid.value(), &index.unit_conversions(rtxn)?)
+                        .filter(|(field, _, _)| crate::is_faceted(field, filterable_fields))
+                {
+                    let mut bitmap = RoaringBitmap::new();
+                    for el in els {
+                        let op = Condition::Equal(el.clone());
+                        bitmap |= Self::evaluate_converted_operator(
+                            rtxn,
+                            index,
+                            filterable_fields,
+                            fid,
+                            (field, unit, conversion),
+                            &op,
+                        )?;
+                    }
+                    Ok(bitmap)
                 } else {
                     Err(fid.as_external_error(FilterError::AttributeNotFilterable {
                         attribute: fid.value(),
@@ -375,6 +532,18 @@ impl<'a> Filter<'a> {
                     } else {
                         Ok(RoaringBitmap::new())
                     }
+                } else if let Some(converted) =
+                    split_converted_field(fid.value(), &index.unit_conversions(rtxn)?)
+                        .filter(|(field, _, _)| crate::is_faceted(field, filterable_fields))
+                {
+                    Self::evaluate_converted_operator(
+                        rtxn,
+                        index,
+                        filterable_fields,
+                        fid,
+                        converted,
+                        op,
+                    )
                 } else {
                     Err(fid.as_external_error(FilterError::AttributeNotFilterable {
                         attribute: fid.value(),
@@ -580,10 +749,128 @@ impl<'a> Filter<'a> {
                     ))?
                 }
             }
+            #[cfg(feature = "plugins")]
+            FilterCondition::Plugin { name, argument } => {
+                match crate::plugins::filter(name.value()) {
+                    Some(plugin) => plugin.evaluate(index, rtxn, argument.value()),
+                    None => Err(name.as_external_error(FilterError::UnknownPlugin(name.value())))?,
+                }
+            }
+            #[cfg(not(feature = "plugins"))]
+            FilterCondition::Plugin { name, .. } => {
+                Err(name.as_external_error(FilterError::UnknownPlugin(name.value())))?
+            }
         }
     }
 }
 
+impl<'a> Filter<'a> {
+    /// Returns whether a JSON value, e.g. an element of an array of objects, matches the filter.
+    ///
+    /// The fields of the filter are looked up in the value itself, they don't need to be
+    /// filterable but the geo and plugin filters can't be used.
+    pub fn matches(&self, value: &Value) -> Result<bool> {
+        Self::condition_matches(&self.condition, value)
+    }
+
+    fn condition_matches(condition: &FilterCondition<'a>, value: &Value) -> Result<bool> {
+        match condition {
+            FilterCondition::Not(condition) => Ok(!Self::condition_matches(condition, value)?),
+            FilterCondition::Condition { fid, op } => {
+                let mut values = Vec::new();
+                values_at(value, fid.value(), &mut values);
+                let numbers = || leaves(&values).filter_map(Value::as_f64);
+
+                Ok(match op {
+                    Condition::GreaterThan(val) => {
+                        let val = val.parse_finite_float()?;
+                        numbers().any(|n| n > val)
+                    }
+                    Condition::GreaterThanOrEqual(val) => {
+                        let val = val.parse_finite_float()?;
+                        numbers().any(|n| n >= val)
+                    }
+                    Condition::LowerThan(val) => {
+                        let val = val.parse_finite_float()?;
+                        numbers().any(|n| n < val)
+                    }
+                    Condition::LowerThanOrEqual(val) => {
+                        let val = val.parse_finite_float()?;
+                        numbers().any(|n| n <= val)
+                    }
+                    Condition::Between { from, to } => {
+                        let (from, to) = (from.parse_finite_float()?, to.parse_finite_float()?);
+                        numbers().any(|n| from <= n && n <= to)
+                    }
+                    Condition::Approximately { value, tolerance: tolerance_token } => {
+                        let value = value.parse_finite_float()?;
+                        let tolerance = tolerance_token.parse_finite_float()?;
+                        if tolerance < 0.0 {
+                            return Err(tolerance_token
+                                .as_external_error(FilterError::NegativeTolerance(tolerance)))?;
+                        }
+                        numbers().any(|n| (n - value).abs() <= tolerance)
+                    }
+                    Condition::Null => values.iter().any(|value| value.is_null()),
+                    Condition::Empty => values.iter().any(|value| match value {
+                        Value::String(s) => s.is_empty(),
+                        Value::Array(values) => values.is_empty(),
+                        Value::Object(object) => object.is_empty(),
+                        _ => false,
+                    }),
+                    Condition::Exists => !values.is_empty(),
+                    Condition::Equal(val) => leaves(&values).any(|value| value_equals(value, val)),
+                    Condition::NotEqual(val) => {
+                        !leaves(&values).any(|value| value_equals(value, val))
+                    }
+                })
+            }
+            FilterCondition::In { fid, els } => {
+                let mut values = Vec::new();
+                values_at(value, fid.value(), &mut values);
+                Ok(leaves(&values).any(|value| els.iter().any(|el| value_equals(value, el))))
+            }
+            FilterCondition::Or(conditions) => {
+                for condition in conditions {
+                    if Self::condition_matches(condition, value)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            FilterCondition::And(conditions) => {
+                for condition in conditions {
+                    if !Self::condition_matches(condition, value)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            FilterCondition::GeoLowerThan { point, .. } => {
+                Err(point[0].as_external_error(FilterError::NotOnValues("_geoRadius")))?
+            }
+            FilterCondition::GeoBoundingBox { top_right_point, .. } => {
+                Err(top_right_point[0]
+                    .as_external_error(FilterError::NotOnValues("_geoBoundingBox")))?
+            }
+            FilterCondition::Plugin { name, .. } => {
+                Err(name.as_external_error(FilterError::NotOnValues(name.value())))?
+            }
+        }
+    }
+}
+
+/// Returns whether a JSON value is equal to the value of a filter, like in the facet databases
+/// the strings are normalized and the numbers compared as numbers.
+fn value_equals(value: &Value, token: &Token) -> bool {
+    match value {
+        Value::String(s) => crate::normalize_facet(s) == crate::normalize_facet(token.value()),
+        Value::Number(n) => n.as_f64().is_some() && n.as_f64() == token.parse_finite_float().ok(),
+        Value::Bool(b) => crate::normalize_facet(token.value()) == b.to_string(),
+        _ => false,
+    }
+}
+
 impl<'a> From<FilterCondition<'a>> for Filter<'a> {
     fn from(fc: FilterCondition<'a>) -> Self {
         Self { condition: fc }
@@ -1105,6 +1392,18 @@ mod tests {
                 assert_eq!(result, RoaringBitmap::from_iter(i..=j));
             }
         }
+        for i in 1..99 {
+            let filter_str = format!("id ~= {i} +- 1.5");
+            let filter = Filter::from_str(&filter_str).unwrap().unwrap();
+            let result = filter.evaluate(&rtxn, &index).unwrap();
+            assert_eq!(result, RoaringBitmap::from_iter((i - 1)..=(i + 1)));
+        }
+        let filter = Filter::from_str("id ~= 10 +- -1").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("The tolerance of the `~=` operation must be positive, but found `-1`."));
+
         let filter = Filter::from_str("one >= 0 OR one <= 0").unwrap().unwrap();
         let result = filter.evaluate(&rtxn, &index).unwrap();
         assert_eq!(result, RoaringBitmap::default());
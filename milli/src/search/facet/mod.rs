@@ -4,17 +4,24 @@ use heed::types::{Bytes, DecodeIgnore};
 use heed::{BytesDecode, RoTxn};
 use roaring::RoaringBitmap;
 
-pub use self::facet_distribution::{FacetDistribution, OrderBy, DEFAULT_VALUES_PER_FACET};
+pub use self::facet_aggregates::{FacetAggregate, FacetAggregates};
+pub use self::facet_distribution::{
+    FacetBuckets, FacetDistribution, OrderBy, DEFAULT_VALUES_PER_FACET,
+};
+pub(crate) use self::facet_range_search::find_docids_of_facet_within_bounds;
 pub use self::filter::{BadGeoError, Filter};
+pub use self::nested::NestedFilter;
 use crate::heed_codec::facet::{FacetGroupKeyCodec, FacetGroupValueCodec, OrderedF64Codec};
 use crate::heed_codec::BytesRefCodec;
 use crate::{Index, Result};
+mod facet_aggregates;
 mod facet_distribution;
 mod facet_distribution_iter;
 mod facet_range_search;
 mod facet_sort_ascending;
 mod facet_sort_descending;
 mod filter;
+mod nested;
 
 fn facet_extreme_value<'t>(
     mut extreme_it: impl Iterator<Item = heed::Result<(RoaringBitmap, &'t [u8])>> + 't,
@@ -98,6 +105,24 @@ where
     }
 }
 
+/// Get the documents having at least one value in the facet database
+pub(crate) fn get_faceted_documents_ids<'t>(
+    txn: &'t RoTxn<'t>,
+    db: heed::Database<FacetGroupKeyCodec<BytesRefCodec>, FacetGroupValueCodec>,
+    field_id: u16,
+) -> heed::Result<RoaringBitmap> {
+    let mut highest_level_prefix = vec![];
+    highest_level_prefix.extend_from_slice(&field_id.to_be_bytes());
+    highest_level_prefix.push(get_highest_level(txn, db, field_id)?);
+    // the groups of every level contain all the documents of the level below them
+    let mut docids = RoaringBitmap::new();
+    for result in db.remap_key_type::<Bytes>().prefix_iter(txn, highest_level_prefix.as_slice())? {
+        let (_, value) = result?;
+        docids |= value.bitmap;
+    }
+    Ok(docids)
+}
+
 /// Get the height of the highest level in the facet database
 pub(crate) fn get_highest_level<'t>(
     txn: &'t RoTxn<'t>,
@@ -0,0 +1,242 @@
+use std::collections::{BTreeMap, HashSet};
+
+use either::Either;
+use indexmap::IndexMap;
+use roaring::RoaringBitmap;
+use serde_json::Value;
+
+use crate::error::UserError;
+use crate::{obkv_to_json, DocumentId, Filter, Index, Result};
+
+/// Restricts the search to the documents having an element of an array of objects matching a
+/// filter, e.g. the documents having a `variants` element both red and of size M, where the
+/// `variants.color = red AND variants.size = M` filter also matches a document having a red
+/// variant of size S and a blue one of size M.
+///
+/// The fields of the filter are relative to the elements and evaluated on the stored documents,
+/// which are read for every candidate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NestedFilter<'a> {
+    pub attribute: String,
+    pub filter: Filter<'a>,
+}
+
+impl<'a> NestedFilter<'a> {
+    /// Returns the candidates having at least one matching element.
+    pub fn evaluate(
+        &self,
+        rtxn: &heed::RoTxn,
+        index: &Index,
+        candidates: &RoaringBitmap,
+    ) -> Result<RoaringBitmap> {
+        let mut documents = RoaringBitmap::new();
+        self.for_each_matching_elements(rtxn, index, candidates, |docid, elements| {
+            if !elements.is_empty() {
+                documents.insert(docid);
+            }
+        })?;
+        Ok(documents)
+    }
+
+    /// Returns the number of candidates having a matching element with each value of the fields
+    /// of the elements, e.g. `variants.color`, the fields not under the attribute are ignored.
+    pub fn facet_distribution(
+        &self,
+        rtxn: &heed::RoTxn,
+        index: &Index,
+        candidates: &RoaringBitmap,
+        fields: &[&str],
+        max_values_per_facet: usize,
+    ) -> Result<BTreeMap<String, IndexMap<String, u64>>> {
+        let prefix = format!("{}.", self.attribute);
+        let fields: Vec<_> = fields
+            .iter()
+            .filter_map(|field| Some((*field, field.strip_prefix(&prefix)?)))
+            .collect();
+
+        // the values are counted by normalized value, under the first original value found
+        let mut distribution: BTreeMap<&str, BTreeMap<String, (String, u64)>> =
+            fields.iter().map(|(field, _)| (*field, BTreeMap::new())).collect();
+        self.for_each_matching_elements(rtxn, index, candidates, |_, elements| {
+            for (field, path) in &fields {
+                let mut values = Vec::new();
+                for element in &elements {
+                    values_at(element, path, &mut values);
+                }
+                let counts = distribution.get_mut(field).unwrap();
+                let mut seen = HashSet::new();
+                for value in leaves(&values) {
+                    let original = match value {
+                        Value::String(s) => s.clone(),
+                        Value::Number(n) => n.to_string(),
+                        Value::Bool(b) => b.to_string(),
+                        _ => continue,
+                    };
+                    let normalized = crate::normalize_facet(&original);
+                    // a document is counted once for each of its values
+                    if seen.insert(normalized.clone()) {
+                        counts.entry(normalized).or_insert((original, 0)).1 += 1;
+                    }
+                }
+            }
+        })?;
+
+        Ok(distribution
+            .into_iter()
+            .map(|(field, counts)| {
+                let counts = counts.into_values().take(max_values_per_facet).collect();
+                (field.to_string(), counts)
+            })
+            .collect())
+    }
+
+    fn for_each_matching_elements(
+        &self,
+        rtxn: &heed::RoTxn,
+        index: &Index,
+        candidates: &RoaringBitmap,
+        mut f: impl FnMut(DocumentId, Vec<&Value>),
+    ) -> Result<()> {
+        let filterable_fields = index.filterable_fields(rtxn)?;
+        if !crate::is_faceted(&self.attribute, &filterable_fields) {
+            let (valid_fields, hidden_fields) =
+                index.remove_hidden_fields(rtxn, filterable_fields)?;
+            return Err(UserError::InvalidNestedAttribute {
+                field: self.attribute.clone(),
+                valid_fields,
+                hidden_fields,
+            }
+            .into());
+        }
+
+        let fields_ids_map = index.fields_ids_map(rtxn)?;
+        let top_level_field = self.attribute.split('.').next().unwrap_or_default();
+        let Some(field_id) = fields_ids_map.id(top_level_field) else { return Ok(()) };
+
+        for result in index.iter_documents(rtxn, candidates.iter())? {
+            let (docid, obkv) = result?;
+            let document = Value::Object(obkv_to_json(&[field_id], &fields_ids_map, obkv)?);
+            let mut values = Vec::new();
+            values_at(&document, &self.attribute, &mut values);
+
+            let mut elements = Vec::new();
+            for element in leaves(&values) {
+                if self.filter.matches(element)? {
+                    elements.push(element);
+                }
+            }
+            f(docid, elements);
+        }
+
+        Ok(())
+    }
+}
+
+/// Pushes the values found at the path of dot-separated keys, the arrays met along the path are
+/// traversed.
+pub(crate) fn values_at<'v>(value: &'v Value, path: &str, output: &mut Vec<&'v Value>) {
+    if path.is_empty() {
+        return output.push(value);
+    }
+    match value {
+        Value::Array(values) => values.iter().for_each(|value| values_at(value, path, output)),
+        Value::Object(object) => {
+            // the keys can contain dots themselves
+            for (key, value) in object {
+                match path.strip_prefix(key.as_str()) {
+                    Some("") => output.push(value),
+                    Some(rest) => {
+                        if let Some(rest) = rest.strip_prefix('.') {
+                            values_at(value, rest, output);
+                        }
+                    }
+                    None => (),
+                }
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Returns the values with the arrays replaced by their values.
+pub(crate) fn leaves<'v>(values: &[&'v Value]) -> impl Iterator<Item = &'v Value> + '_ {
+    values.iter().flat_map(|value| match value {
+        Value::Array(values) => Either::Left(values.iter()),
+        value => Either::Right(std::iter::once(*value)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use big_s::S;
+    use maplit::hashset;
+    use roaring::RoaringBitmap;
+
+    use crate::index::tests::TempIndex;
+    use crate::{milli_snap, Error, Filter, NestedFilter, UserError};
+
+    fn nested(filter: &str) -> NestedFilter {
+        NestedFilter {
+            attribute: S("variants"),
+            filter: Filter::from_str(filter).unwrap().unwrap(),
+        }
+    }
+
+    #[test]
+    fn nested_filter_keeps_the_pairing_of_the_elements() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("variants"), S("name") })
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "name": "shirt", "variants": [
+                    { "color": "Red", "size": "S", "stock": 2 },
+                    { "color": "blue", "size": "M", "stock": 0 },
+                ] },
+                { "id": 1, "name": "hat", "variants": [
+                    { "color": "red", "size": "M", "stock": 5 },
+                    { "color": "green", "size": ["S", "M"], "stock": 1 },
+                ] },
+                { "id": 2, "name": "socks", "variants": { "color": "red", "size": "M" } },
+                { "id": 3, "name": "shoes" },
+            ]))
+            .unwrap();
+
+        let txn = index.read_txn().unwrap();
+        let candidates: RoaringBitmap = (0..4).collect();
+
+        let documents = nested("color = red AND size = M").evaluate(&txn, &index, &candidates);
+        milli_snap!(format!("{:?}", documents.unwrap()), @"RoaringBitmap<[1, 2]>");
+
+        let documents = nested("size = M AND stock > 0").evaluate(&txn, &index, &candidates);
+        milli_snap!(format!("{:?}", documents.unwrap()), @"RoaringBitmap<[1]>");
+
+        let documents = nested("stock NOT EXISTS").evaluate(&txn, &index, &candidates);
+        milli_snap!(format!("{:?}", documents.unwrap()), @"RoaringBitmap<[2]>");
+
+        let distribution = nested("size = M").facet_distribution(
+            &txn,
+            &index,
+            &candidates,
+            &["variants.color", "name"],
+            100,
+        );
+        milli_snap!(format!("{:?}", distribution.unwrap()), @r###"{"variants.color": {"blue": 1, "green": 1, "red": 2}}"###);
+
+        let error = nested("_geoRadius(12, 13, 14)").evaluate(&txn, &index, &candidates);
+        assert!(matches!(&error, Err(Error::UserError(UserError::InvalidFilter(_)))), "{error:?}");
+
+        let error = NestedFilter { attribute: S("id"), ..nested("size = M") }
+            .evaluate(&txn, &index, &candidates)
+            .unwrap_err();
+        assert!(
+            matches!(&error, Error::UserError(UserError::InvalidNestedAttribute { field, .. }) if field == "id"),
+            "{error}"
+        );
+    }
+}
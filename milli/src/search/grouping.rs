@@ -0,0 +1,23 @@
+use crate::score_details::ScoreDetails;
+use crate::DocumentId;
+
+/// Collapses the results by the values of an attribute, each returned document is the best one
+/// of its group and comes with the best documents of the group.
+///
+/// The documents sharing a value with the best document of a group are part of the group, the
+/// documents without a value are a group on their own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupBy {
+    pub field: String,
+    /// The maximum number of documents returned for each group.
+    pub limit: usize,
+}
+
+/// The best documents of a group, starting with the document the group was collapsed into.
+#[derive(Debug, Clone, Default)]
+pub struct SearchGroup {
+    pub documents_ids: Vec<DocumentId>,
+    pub document_scores: Vec<Vec<ScoreDetails>>,
+    /// The number of documents of the group matching the query.
+    pub total_hits: u64,
+}
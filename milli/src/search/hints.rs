@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+/// Hints changing how the queries made on an index are resolved.
+///
+/// They are an escape hatch for the corpora on which the default query plan performs poorly.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryPlanHints {
+    #[serde(default)]
+    pub filter_resolution: FilterResolution,
+    /// The number of documents matching a query above which the proximity ranking rule is skipped.
+    #[serde(default)]
+    pub proximity_max_candidates: Option<u64>,
+}
+
+/// When the filter of a query is evaluated, relatively to the words of the query.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum FilterResolution {
+    /// The filter is evaluated first and restricts the documents in which the words are searched.
+    #[default]
+    BeforeWords,
+    /// The words are searched in all the documents and the filter is only evaluated
+    /// when some of them match the query.
+    AfterWords,
+}
@@ -4,19 +4,23 @@ use itertools::Itertools;
 use roaring::RoaringBitmap;
 
 use crate::score_details::{ScoreDetails, ScoreValue, ScoringStrategy};
-use crate::{MatchingWords, Result, Search, SearchResult};
+use crate::{MatchingWords, Result, Search, SearchDiagnostics, SearchResult};
 
 struct ScoreWithRatioResult {
     matching_words: MatchingWords,
     candidates: RoaringBitmap,
     document_scores: Vec<(u32, ScoreWithRatio)>,
+    degraded: bool,
+    diagnostics: Option<SearchDiagnostics>,
 }
 
 type ScoreWithRatio = (Vec<ScoreDetails>, f32);
 
-fn compare_scores(
-    &(ref left_scores, left_ratio): &ScoreWithRatio,
-    &(ref right_scores, right_ratio): &ScoreWithRatio,
+pub(crate) fn compare_scores(
+    left_scores: &[ScoreDetails],
+    left_ratio: f32,
+    right_scores: &[ScoreDetails],
+    right_ratio: f32,
 ) -> Ordering {
     let mut left_it = ScoreDetails::score_values(left_scores.iter());
     let mut right_it = ScoreDetails::score_values(right_scores.iter());
@@ -72,6 +76,8 @@ impl ScoreWithRatioResult {
             matching_words: results.matching_words,
             candidates: results.candidates,
             document_scores,
+            degraded: results.degraded,
+            diagnostics: results.diagnostics,
         }
     }
 
@@ -87,7 +93,7 @@ impl ScoreWithRatioResult {
             .into_iter()
             .merge_by(right.document_scores.into_iter(), |(_, left), (_, right)| {
                 // the first value is the one with the greatest score
-                compare_scores(left, right).is_ge()
+                compare_scores(&left.0, left.1, &right.0, right.1).is_ge()
             })
             // remove documents we already saw
             .filter(|(docid, _)| documents_seen.insert(*docid))
@@ -106,6 +112,9 @@ impl ScoreWithRatioResult {
             candidates: left.candidates | right.candidates,
             documents_ids,
             document_scores,
+            degraded: left.degraded | right.degraded,
+            diagnostics: right.diagnostics,
+            groups: Vec::new(),
         }
     }
 }
@@ -121,7 +130,9 @@ impl<'a> Search<'a> {
             offset: 0,
             limit: self.limit + self.offset,
             sort_criteria: self.sort_criteria.clone(),
+            sort_first: self.sort_first,
             searchable_attributes: self.searchable_attributes,
+            locales: self.locales.clone(),
             geo_strategy: self.geo_strategy,
             terms_matching_strategy: self.terms_matching_strategy,
             scoring_strategy: ScoringStrategy::Detailed,
@@ -131,6 +142,11 @@ impl<'a> Search<'a> {
             index: self.index,
             distribution_shift: self.distribution_shift,
             embedder_name: self.embedder_name.clone(),
+            time_budget: self.time_budget,
+            diagnostics: self.diagnostics,
+            boosts: self.boosts.clone(),
+            group_by: self.group_by.clone(),
+            nested_filter: self.nested_filter.clone(),
         };
 
         let vector_query = search.vector.take();
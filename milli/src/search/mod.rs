@@ -1,8 +1,9 @@
 use std::fmt;
 use std::ops::ControlFlow;
+use std::time::{Duration, Instant};
 
 use charabia::normalizer::NormalizerOption;
-use charabia::Normalize;
+use charabia::{Language, Normalize};
 use fst::automaton::{Automaton, Str};
 use fst::{IntoStreamer, Streamer};
 use levenshtein_automata::{LevenshteinAutomatonBuilder as LevBuilder, DFA};
@@ -10,16 +11,29 @@ use once_cell::sync::Lazy;
 use roaring::bitmap::RoaringBitmap;
 use tracing::error;
 
-pub use self::facet::{FacetDistribution, Filter, OrderBy, DEFAULT_VALUES_PER_FACET};
+use self::boosts::boost_document_scores;
+pub use self::boosts::ScoreBoost;
+pub use self::exclusions::{ExclusionMode, SearchExclusions};
+pub use self::facet::{
+    FacetAggregate, FacetAggregates, FacetBuckets, FacetDistribution, Filter, NestedFilter,
+    OrderBy, DEFAULT_VALUES_PER_FACET,
+};
+pub use self::grouping::{GroupBy, SearchGroup};
+pub use self::hints::{FilterResolution, QueryPlanHints};
+use self::hybrid::compare_scores;
 pub use self::new::matches::{FormatOptions, MatchBounds, MatcherBuilder, MatchingWords};
-use self::new::{execute_vector_search, PartialSearchResult};
+use self::new::{
+    compute_candidates_statistics, distinct_single_docid, execute_vector_search, explain_search,
+    is_number_like, search_loggers, DiagnosticsLoggers, PartialSearchResult, SearchDiagnostics,
+};
+pub use self::sort_documents::sort_documents;
 use crate::error::UserError;
 use crate::heed_codec::facet::{FacetGroupKey, FacetGroupValue};
 use crate::score_details::{ScoreDetails, ScoringStrategy};
 use crate::vector::DistributionShift;
 use crate::{
-    execute_search, filtered_universe, AscDesc, DefaultSearchLogger, DocumentId, FieldId, Index,
-    Result, SearchContext,
+    execute_search, filtered_universe, AscDesc, CandidatesStatistics, DefaultSearchLogger,
+    DocumentId, FieldId, Index, Result, SearchContext, SearchExplanation,
 };
 
 // Building these factories is not free.
@@ -30,10 +44,15 @@ static LEVDIST2: Lazy<LevBuilder> = Lazy::new(|| LevBuilder::new(2, true));
 /// The maximum number of values per facet returned by the facet search route.
 const DEFAULT_MAX_NUMBER_OF_VALUES_PER_FACET: usize = 100;
 
+mod boosts;
+mod exclusions;
 pub mod facet;
 mod fst_utils;
+mod grouping;
+mod hints;
 pub mod hybrid;
 pub mod new;
+mod sort_documents;
 
 pub struct Search<'a> {
     query: Option<String>,
@@ -43,7 +62,9 @@ pub struct Search<'a> {
     offset: usize,
     limit: usize,
     sort_criteria: Option<Vec<AscDesc>>,
+    sort_first: Option<bool>,
     searchable_attributes: Option<&'a [String]>,
+    locales: Option<Vec<Language>>,
     geo_strategy: new::GeoSortStrategy,
     terms_matching_strategy: TermsMatchingStrategy,
     scoring_strategy: ScoringStrategy,
@@ -54,6 +75,11 @@ pub struct Search<'a> {
     index: &'a Index,
     distribution_shift: Option<DistributionShift>,
     embedder_name: Option<String>,
+    time_budget: TimeBudget,
+    diagnostics: bool,
+    boosts: Vec<ScoreBoost<'a>>,
+    group_by: Option<GroupBy>,
+    nested_filter: Option<NestedFilter<'a>>,
 }
 
 impl<'a> Search<'a> {
@@ -65,7 +91,9 @@ impl<'a> Search<'a> {
             offset: 0,
             limit: 20,
             sort_criteria: None,
+            sort_first: None,
             searchable_attributes: None,
+            locales: None,
             geo_strategy: new::GeoSortStrategy::default(),
             terms_matching_strategy: TermsMatchingStrategy::default(),
             scoring_strategy: Default::default(),
@@ -75,6 +103,11 @@ impl<'a> Search<'a> {
             index,
             distribution_shift: None,
             embedder_name: None,
+            time_budget: TimeBudget::default(),
+            diagnostics: false,
+            boosts: Vec::new(),
+            group_by: None,
+            nested_filter: None,
         }
     }
 
@@ -103,11 +136,24 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Applies the sort criteria before all the other ranking rules when `true`, or after all of
+    /// them when `false`, instead of at the position of the `sort` ranking rule.
+    pub fn sort_first(&mut self, sort_first: bool) -> &mut Search<'a> {
+        self.sort_first = Some(sort_first);
+        self
+    }
+
     pub fn searchable_attributes(&mut self, searchable: &'a [String]) -> &mut Search<'a> {
         self.searchable_attributes = Some(searchable);
         self
     }
 
+    /// Restricts the languages the query can be detected as when it's tokenized.
+    pub fn locales(&mut self, locales: Vec<Language>) -> &mut Search<'a> {
+        self.locales = Some(locales);
+        self
+    }
+
     pub fn terms_matching_strategy(&mut self, value: TermsMatchingStrategy) -> &mut Search<'a> {
         self.terms_matching_strategy = value;
         self
@@ -154,10 +200,52 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Stops ranking the documents once the budget is exceeded, the search then returns the
+    /// best documents found so far and its result is marked as degraded.
+    pub fn time_budget(&mut self, time_budget: TimeBudget) -> &mut Search<'a> {
+        self.time_budget = time_budget;
+        self
+    }
+
+    /// Records the number of buckets and the time spent in each ranking rule in the diagnostics
+    /// of the result.
+    pub fn diagnostics(&mut self, diagnostics: bool) -> &mut Search<'a> {
+        self.diagnostics = diagnostics;
+        self
+    }
+
+    /// Multiplies the ranking score of the documents matching the filter of each boost by its
+    /// factor, the documents are then ranked by their boosted score.
+    pub fn boosts(&mut self, boosts: Vec<ScoreBoost<'a>>) -> &mut Search<'a> {
+        self.boosts = boosts;
+        self
+    }
+
+    /// Returns the best document of each group of documents sharing a value of an attribute, the
+    /// offset and limit then apply to the groups, and the number of candidates is exhaustive.
+    pub fn group_by(&mut self, group_by: GroupBy) -> &mut Search<'a> {
+        self.group_by = Some(group_by);
+        self
+    }
+
+    pub fn nested_filter(&mut self, nested_filter: NestedFilter<'a>) -> &mut Search<'a> {
+        self.nested_filter = Some(nested_filter);
+        self
+    }
+
     pub fn execute_for_candidates(&self, has_vector_search: bool) -> Result<RoaringBitmap> {
         if has_vector_search {
             let ctx = SearchContext::new(self.index, self.rtxn);
-            filtered_universe(&ctx, &self.filter)
+            let mut candidates = filtered_universe(&ctx, &self.filter)?;
+            if let Some(nested_filter) = &self.nested_filter {
+                candidates = nested_filter.evaluate(self.rtxn, self.index, &candidates)?;
+            }
+            if let Some(exclusions) = self.index.search_exclusions(self.rtxn)? {
+                if exclusions.mode == ExclusionMode::Exclude {
+                    candidates -= exclusions.documents_ids(self.rtxn, self.index)?;
+                }
+            }
+            Ok(candidates)
         } else {
             Ok(self.execute()?.candidates)
         }
@@ -174,50 +262,290 @@ impl<'a> Search<'a> {
         };
 
         let mut ctx = SearchContext::new(self.index, self.rtxn);
+        ctx.sort_first = self.sort_first;
+        ctx.time_budget = self.time_budget;
+        ctx.locales = self.locales.clone();
 
         if let Some(searchable_attributes) = self.searchable_attributes {
             ctx.searchable_attributes(searchable_attributes)?;
         }
 
-        let universe = filtered_universe(&ctx, &self.filter)?;
-        let PartialSearchResult { located_query_terms, candidates, documents_ids, document_scores } =
-            match self.vector.as_ref() {
-                Some(vector) => execute_vector_search(
-                    &mut ctx,
-                    vector,
-                    self.scoring_strategy,
-                    universe,
-                    &self.sort_criteria,
-                    self.geo_strategy,
-                    self.offset,
-                    self.limit,
-                    self.distribution_shift,
+        if let Some(group_by) = &self.group_by {
+            let filterable_fields = self.index.filterable_fields(self.rtxn)?;
+            if !crate::is_faceted(&group_by.field, &filterable_fields) {
+                let (valid_fields, hidden_fields) =
+                    self.index.remove_hidden_fields(self.rtxn, filterable_fields)?;
+                return Err(UserError::InvalidGroupByAttribute {
+                    field: group_by.field.clone(),
+                    valid_fields,
+                    hidden_fields,
+                }
+                .into());
+            }
+            ctx.distinct = Some(group_by.field.clone());
+        }
+
+        // The filter of a keyword search is evaluated after its words when the hints ask for it.
+        let hints = self.index.query_plan_hints(self.rtxn)?.unwrap_or_default();
+        let (universe, deferred_filter) = match hints.filter_resolution {
+            FilterResolution::AfterWords if self.vector.is_none() => {
+                (self.index.documents_ids(self.rtxn)?, self.filter.as_ref())
+            }
+            _ => (filtered_universe(&ctx, &self.filter)?, None),
+        };
+
+        // The nested filter reads the documents, it is evaluated among the filtered ones.
+        let universe = match &self.nested_filter {
+            Some(nested_filter) => nested_filter.evaluate(self.rtxn, self.index, &universe)?,
+            None => universe,
+        };
+
+        // The excluded documents are removed from the universe and the deboosted ones
+        // are searched separately, once all the other documents have been returned.
+        let (universe, deboosted) = match self.index.search_exclusions(self.rtxn)? {
+            Some(exclusions) => {
+                let excluded = exclusions.documents_ids(self.rtxn, self.index)?;
+                match exclusions.mode {
+                    ExclusionMode::Exclude => (universe - excluded, RoaringBitmap::new()),
+                    ExclusionMode::Deboost => {
+                        let deboosted = &universe & excluded;
+                        (universe - &deboosted, deboosted)
+                    }
+                }
+            }
+            None => (universe, RoaringBitmap::new()),
+        };
+
+        // The groups are searched among the same documents as their leaders.
+        let grouped_universe = match &self.group_by {
+            Some(_) => &universe | &deboosted,
+            None => RoaringBitmap::new(),
+        };
+
+        // The boosts reorder all the documents up to the requested page.
+        let (offset, limit) = if self.boosts.is_empty() {
+            (self.offset, self.limit)
+        } else {
+            (0, self.offset + self.limit)
+        };
+
+        let mut diagnostics = self.diagnostics.then(DiagnosticsLoggers::default);
+        let mut result = self.execute_in_universe(
+            &mut ctx,
+            universe,
+            deferred_filter,
+            offset,
+            limit,
+            embedder_name,
+            diagnostics.as_mut(),
+        )?;
+        if !deboosted.is_empty() {
+            let offset = offset.saturating_sub(result.candidates.len() as usize);
+            let limit = limit - result.documents_ids.len();
+            let deboosted = self.execute_in_universe(
+                &mut ctx,
+                deboosted.clone(),
+                deferred_filter,
+                offset,
+                limit,
+                embedder_name,
+                diagnostics.as_mut(),
+            )?;
+            result.candidates |= deboosted.candidates;
+            result.documents_ids.extend(deboosted.documents_ids);
+            result.document_scores.extend(deboosted.document_scores);
+            result.located_query_terms =
+                result.located_query_terms.or(deboosted.located_query_terms);
+            result.degraded |= deboosted.degraded;
+        }
+        if !self.boosts.is_empty() {
+            boost_document_scores(
+                self.rtxn,
+                self.index,
+                &self.boosts,
+                &result.documents_ids,
+                &mut result.document_scores,
+            )?;
+            // the deboosted documents stay after all the other ones
+            let mut documents: Vec<_> =
+                result.documents_ids.into_iter().zip(result.document_scores).collect();
+            documents.sort_by(|(left_id, left), (right_id, right)| {
+                deboosted
+                    .contains(*left_id)
+                    .cmp(&deboosted.contains(*right_id))
+                    .then_with(|| compare_scores(right, 1.0, left, 1.0))
+            });
+            (result.documents_ids, result.document_scores) =
+                documents.into_iter().skip(self.offset).take(self.limit).unzip();
+        }
+        let PartialSearchResult {
+            located_query_terms,
+            candidates,
+            documents_ids,
+            document_scores,
+            degraded,
+        } = result;
+
+        let groups = match &self.group_by {
+            Some(group_by) => self.execute_groups(
+                &mut ctx,
+                group_by,
+                &grouped_universe,
+                &documents_ids,
+                deferred_filter,
+                embedder_name,
+            )?,
+            None => Vec::new(),
+        };
+
+        // consume context and located_query_terms to build MatchingWords.
+        let matching_words = match located_query_terms {
+            Some(located_query_terms) => MatchingWords::new(ctx, located_query_terms),
+            None => MatchingWords::default(),
+        };
+
+        Ok(SearchResult {
+            matching_words,
+            candidates,
+            document_scores,
+            documents_ids,
+            degraded,
+            diagnostics: diagnostics.map(DiagnosticsLoggers::into_diagnostics),
+            groups,
+        })
+    }
+
+    /// Returns the best documents of the group of each leader, in the order of the leaders.
+    fn execute_groups(
+        &self,
+        ctx: &mut SearchContext<'a>,
+        group_by: &GroupBy,
+        universe: &RoaringBitmap,
+        leaders: &[DocumentId],
+        deferred_filter: Option<&Filter>,
+        embedder_name: &str,
+    ) -> Result<Vec<SearchGroup>> {
+        // the documents without the field are groups on their own
+        let field_id = ctx.distinct_fid()?;
+
+        // the documents of a group share their value, only the distinct attribute of the index
+        // can deduplicate them
+        ctx.distinct = None;
+        let mut groups = Vec::with_capacity(leaders.len());
+        for &leader in leaders {
+            let mut group = RoaringBitmap::new();
+            if let Some(field_id) = field_id {
+                distinct_single_docid(ctx.index, ctx.txn, field_id, leader, &mut group)?;
+            }
+            group.insert(leader);
+            group &= universe;
+
+            let PartialSearchResult { candidates, documents_ids, document_scores, .. } = self
+                .execute_in_universe(
+                    ctx,
+                    group,
+                    deferred_filter,
+                    0,
+                    group_by.limit,
                     embedder_name,
-                )?,
-                None => execute_search(
-                    &mut ctx,
+                    None,
+                )?;
+            let total_hits = candidates.len();
+            groups.push(SearchGroup { documents_ids, document_scores, total_hits });
+        }
+        ctx.distinct = Some(group_by.field.clone());
+
+        Ok(groups)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn execute_in_universe(
+        &self,
+        ctx: &mut SearchContext<'a>,
+        universe: RoaringBitmap,
+        deferred_filter: Option<&Filter>,
+        offset: usize,
+        limit: usize,
+        embedder_name: &str,
+        diagnostics: Option<&mut DiagnosticsLoggers>,
+    ) -> Result<PartialSearchResult> {
+        // The boosts multiply the scores, they must be computed.
+        let scoring_strategy =
+            if self.boosts.is_empty() { self.scoring_strategy } else { ScoringStrategy::Detailed };
+        match self.vector.as_ref() {
+            Some(vector) => execute_vector_search(
+                ctx,
+                vector,
+                scoring_strategy,
+                universe,
+                &self.sort_criteria,
+                self.geo_strategy,
+                offset,
+                limit,
+                self.distribution_shift,
+                embedder_name,
+            ),
+            None => {
+                let mut default_loggers = (DefaultSearchLogger, DefaultSearchLogger);
+                let (placeholder_search_logger, query_graph_logger) =
+                    search_loggers(diagnostics, &mut default_loggers);
+                execute_search(
+                    ctx,
                     self.query.as_deref(),
                     self.terms_matching_strategy,
-                    self.scoring_strategy,
-                    self.exhaustive_number_hits,
+                    scoring_strategy,
+                    // the number of groups is only known by counting them all
+                    self.exhaustive_number_hits || self.group_by.is_some(),
                     universe,
+                    deferred_filter,
                     &self.sort_criteria,
                     self.geo_strategy,
-                    self.offset,
-                    self.limit,
+                    offset,
+                    limit,
                     Some(self.words_limit),
-                    &mut DefaultSearchLogger,
-                    &mut DefaultSearchLogger,
-                )?,
-            };
+                    placeholder_search_logger,
+                    query_graph_logger,
+                )
+            }
+        }
+    }
 
-        // consume context and located_query_terms to build MatchingWords.
-        let matching_words = match located_query_terms {
-            Some(located_query_terms) => MatchingWords::new(ctx, located_query_terms),
-            None => MatchingWords::default(),
-        };
+    /// Returns the number of candidates of the query after each step of their resolution,
+    /// the vector, sort and pagination parameters are ignored.
+    pub fn candidates_statistics(&self) -> Result<CandidatesStatistics> {
+        let mut ctx = SearchContext::new(self.index, self.rtxn);
+        ctx.locales = self.locales.clone();
+
+        if let Some(searchable_attributes) = self.searchable_attributes {
+            ctx.searchable_attributes(searchable_attributes)?;
+        }
+
+        compute_candidates_statistics(
+            &mut ctx,
+            self.query.as_deref(),
+            self.terms_matching_strategy,
+            &self.filter,
+            Some(self.words_limit),
+        )
+    }
+
+    /// Returns how the query is tokenized, derived and filtered, and the number of candidates
+    /// after each step of their resolution.
+    pub fn explain(&self) -> Result<SearchExplanation> {
+        let mut ctx = SearchContext::new(self.index, self.rtxn);
+        ctx.locales = self.locales.clone();
+
+        if let Some(searchable_attributes) = self.searchable_attributes {
+            ctx.searchable_attributes(searchable_attributes)?;
+        }
 
-        Ok(SearchResult { matching_words, candidates, document_scores, documents_ids })
+        explain_search(
+            &mut ctx,
+            self.query.as_deref(),
+            self.terms_matching_strategy,
+            &self.filter,
+            Some(self.words_limit),
+        )
     }
 }
 
@@ -230,7 +558,9 @@ impl fmt::Debug for Search<'_> {
             offset,
             limit,
             sort_criteria,
+            sort_first,
             searchable_attributes,
+            locales,
             geo_strategy: _,
             terms_matching_strategy,
             scoring_strategy,
@@ -240,6 +570,11 @@ impl fmt::Debug for Search<'_> {
             index: _,
             distribution_shift,
             embedder_name,
+            time_budget,
+            diagnostics,
+            boosts,
+            group_by,
+            nested_filter,
         } = self;
         f.debug_struct("Search")
             .field("query", query)
@@ -248,13 +583,20 @@ impl fmt::Debug for Search<'_> {
             .field("offset", offset)
             .field("limit", limit)
             .field("sort_criteria", sort_criteria)
+            .field("sort_first", sort_first)
             .field("searchable_attributes", searchable_attributes)
+            .field("locales", locales)
             .field("terms_matching_strategy", terms_matching_strategy)
             .field("scoring_strategy", scoring_strategy)
             .field("exhaustive_number_hits", exhaustive_number_hits)
             .field("words_limit", words_limit)
             .field("distribution_shift", distribution_shift)
             .field("embedder_name", embedder_name)
+            .field("time_budget", time_budget)
+            .field("diagnostics", diagnostics)
+            .field("boosts", boosts)
+            .field("group_by", group_by)
+            .field("nested_filter", nested_filter)
             .finish()
     }
 }
@@ -265,6 +607,39 @@ pub struct SearchResult {
     pub candidates: RoaringBitmap,
     pub documents_ids: Vec<DocumentId>,
     pub document_scores: Vec<Vec<ScoreDetails>>,
+    /// Whether the time budget was exceeded before all the documents were ranked.
+    pub degraded: bool,
+    /// The work done by the ranking rules, only recorded when requested.
+    pub diagnostics: Option<SearchDiagnostics>,
+    /// The best documents of the group of each returned document, when grouping the results.
+    pub groups: Vec<SearchGroup>,
+}
+
+/// The time a search is allowed to spend ranking the documents.
+#[derive(Debug, Clone, Copy)]
+pub struct TimeBudget {
+    started_at: Instant,
+    budget: Option<Duration>,
+}
+
+impl TimeBudget {
+    pub fn new(budget: Duration) -> Self {
+        Self { started_at: Instant::now(), budget: Some(budget) }
+    }
+
+    pub fn unlimited() -> Self {
+        Self { started_at: Instant::now(), budget: None }
+    }
+
+    pub fn exceeded(&self) -> bool {
+        self.budget.map_or(false, |budget| self.started_at.elapsed() >= budget)
+    }
+}
+
+impl Default for TimeBudget {
+    fn default() -> Self {
+        Self::unlimited()
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -393,7 +768,9 @@ impl<'a> SearchForFacetValues<'a> {
 
                 if authorize_typos && field_authorizes_typos {
                     let exact_words_fst = self.search_query.index.exact_words(rtxn)?;
-                    if exact_words_fst.map_or(false, |fst| fst.contains(query)) {
+                    let exact_number = self.search_query.index.disable_typos_on_numbers(rtxn)?
+                        && is_number_like(query);
+                    if exact_number || exact_words_fst.map_or(false, |fst| fst.contains(query)) {
                         let mut results = vec![];
                         if fst.contains(query) {
                             self.fetch_original_facets_using_normalized(
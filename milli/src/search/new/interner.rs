@@ -45,6 +45,9 @@ impl<T> DedupInterner<T> {
     pub fn freeze(self) -> FixedSizeInterner<T> {
         FixedSizeInterner { stable_store: self.stable_store }
     }
+    pub fn len(&self) -> u16 {
+        self.stable_store.len() as u16
+    }
 }
 
 impl<T> DedupInterner<T>
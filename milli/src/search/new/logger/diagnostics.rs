@@ -0,0 +1,140 @@
+use std::any::Any;
+use std::time::{Duration, Instant};
+
+use roaring::RoaringBitmap;
+
+use crate::search::new::query_graph::QueryGraph;
+use crate::search::new::ranking_rules::{BoxRankingRule, PlaceholderQuery};
+use crate::search::new::{DefaultSearchLogger, RankingRule, RankingRuleQueryTrait, SearchLogger};
+
+/// The work done by each ranking rule of a search, in the order of the ranking rules.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchDiagnostics {
+    pub ranking_rules: Vec<RankingRuleDiagnostics>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RankingRuleDiagnostics {
+    /// The id of the ranking rule, e.g. `words` or `sort`.
+    pub name: String,
+    /// The number of buckets computed by the ranking rule.
+    pub buckets: usize,
+    /// The number of documents in the buckets computed by the ranking rule.
+    pub bucket_documents: u64,
+    /// The time spent computing the buckets.
+    pub duration: Duration,
+}
+
+/// A [`SearchLogger`] counting the buckets of each ranking rule and timing their computation.
+///
+/// It's cheap enough to be used on a sample of the production searches: the time spent between
+/// two events of the bucket sort is attributed to the ranking rule of the latter event.
+#[derive(Default)]
+pub struct DiagnosticsSearchLogger {
+    diagnostics: SearchDiagnostics,
+    last_event: Option<Instant>,
+}
+
+impl DiagnosticsSearchLogger {
+    pub fn into_diagnostics(self) -> SearchDiagnostics {
+        self.diagnostics
+    }
+
+    fn record(&mut self, ranking_rule_idx: usize, bucket_len: Option<u64>) {
+        let now = Instant::now();
+        let elapsed = self.last_event.map_or(Duration::ZERO, |last| now - last);
+        self.last_event = Some(now);
+        if let Some(rule) = self.diagnostics.ranking_rules.get_mut(ranking_rule_idx) {
+            rule.duration += elapsed;
+            if let Some(bucket_len) = bucket_len {
+                rule.buckets += 1;
+                rule.bucket_documents += bucket_len;
+            }
+        }
+    }
+}
+
+impl<Q: RankingRuleQueryTrait> SearchLogger<Q> for DiagnosticsSearchLogger {
+    fn initial_query(&mut self, _query: &Q) {
+        // the time spent between two bucket sorts is not attributed to any ranking rule.
+        self.last_event = None;
+    }
+
+    fn initial_universe(&mut self, _universe: &RoaringBitmap) {}
+
+    fn query_for_initial_universe(&mut self, _query: &Q) {}
+
+    fn ranking_rules(&mut self, rr: &[BoxRankingRule<Q>]) {
+        // the logger can be reused by the bucket sorts of a search, they use the same ranking rules.
+        if self.diagnostics.ranking_rules.is_empty() {
+            self.diagnostics.ranking_rules = rr
+                .iter()
+                .map(|rule| RankingRuleDiagnostics { name: rule.id(), ..Default::default() })
+                .collect();
+        }
+    }
+
+    fn start_iteration_ranking_rule(
+        &mut self,
+        ranking_rule_idx: usize,
+        _ranking_rule: &dyn RankingRule<Q>,
+        _query: &Q,
+        _universe: &RoaringBitmap,
+    ) {
+        self.record(ranking_rule_idx, None);
+    }
+
+    fn next_bucket_ranking_rule(
+        &mut self,
+        ranking_rule_idx: usize,
+        _ranking_rule: &dyn RankingRule<Q>,
+        _universe: &RoaringBitmap,
+        candidates: &RoaringBitmap,
+    ) {
+        self.record(ranking_rule_idx, Some(candidates.len()));
+    }
+
+    fn end_iteration_ranking_rule(
+        &mut self,
+        ranking_rule_idx: usize,
+        _ranking_rule: &dyn RankingRule<Q>,
+        _universe: &RoaringBitmap,
+    ) {
+        self.record(ranking_rule_idx, None);
+    }
+
+    fn add_to_results(&mut self, _docids: &[u32]) {}
+
+    fn log_internal_state(&mut self, _rr: &dyn Any) {}
+}
+
+/// The diagnostics loggers of a search, only one of them is used depending on whether the search
+/// is a placeholder search or not.
+#[derive(Default)]
+pub(crate) struct DiagnosticsLoggers {
+    placeholder: DiagnosticsSearchLogger,
+    query_graph: DiagnosticsSearchLogger,
+}
+
+impl DiagnosticsLoggers {
+    pub(crate) fn into_diagnostics(self) -> SearchDiagnostics {
+        let placeholder = self.placeholder.into_diagnostics();
+        if placeholder.ranking_rules.is_empty() {
+            self.query_graph.into_diagnostics()
+        } else {
+            placeholder
+        }
+    }
+}
+
+/// Returns the loggers to give to [`execute_search`](crate::execute_search), the default ones
+/// when the diagnostics are not recorded.
+pub(crate) fn search_loggers<'a>(
+    diagnostics: Option<&'a mut DiagnosticsLoggers>,
+    default: &'a mut (DefaultSearchLogger, DefaultSearchLogger),
+) -> (&'a mut dyn SearchLogger<PlaceholderQuery>, &'a mut dyn SearchLogger<QueryGraph>) {
+    match diagnostics {
+        Some(DiagnosticsLoggers { placeholder, query_graph }) => (placeholder, query_graph),
+        None => (&mut default.0, &mut default.1),
+    }
+}
@@ -1,3 +1,4 @@
+pub mod diagnostics;
 // #[cfg(test)]
 pub mod visual;
 
@@ -5,6 +5,8 @@ pub use matching_words::MatchingWords;
 use matching_words::{MatchType, PartialMatch, WordId};
 use serde::Serialize;
 
+use crate::stemming::Stemmers;
+
 pub mod matching_words;
 
 const DEFAULT_CROP_MARKER: &str = "…";
@@ -15,6 +17,7 @@ const DEFAULT_HIGHLIGHT_SUFFIX: &str = "</em>";
 pub struct MatcherBuilder<'m> {
     matching_words: MatchingWords,
     tokenizer: Tokenizer<'m>,
+    stemmers: Stemmers,
     crop_marker: Option<String>,
     highlight_prefix: Option<String>,
     highlight_suffix: Option<String>,
@@ -25,12 +28,19 @@ impl<'m> MatcherBuilder<'m> {
         Self {
             matching_words,
             tokenizer,
+            stemmers: Stemmers::default(),
             crop_marker: None,
             highlight_prefix: None,
             highlight_suffix: None,
         }
     }
 
+    /// Stems the words of the text the same way as the documents were indexed.
+    pub fn stemmers(&mut self, stemmers: Stemmers) -> &Self {
+        self.stemmers = stemmers;
+        self
+    }
+
     pub fn crop_marker(&mut self, marker: String) -> &Self {
         self.crop_marker = Some(marker);
         self
@@ -64,6 +74,7 @@ impl<'m> MatcherBuilder<'m> {
             text,
             matching_words: &self.matching_words,
             tokenizer: &self.tokenizer,
+            stemmers: &self.stemmers,
             crop_marker,
             highlight_prefix,
             highlight_suffix,
@@ -111,6 +122,7 @@ pub struct Matcher<'t, 'm> {
     text: &'t str,
     matching_words: &'m MatchingWords,
     tokenizer: &'m Tokenizer<'m>,
+    stemmers: &'m Stemmers,
     crop_marker: &'m str,
     highlight_prefix: &'m str,
     highlight_suffix: &'m str,
@@ -173,7 +185,8 @@ impl<'t> Matcher<'t, '_> {
             false
         }
 
-        let tokens: Vec<_> = self.tokenizer.tokenize(self.text).collect();
+        let tokens: Vec<_> =
+            self.tokenizer.tokenize(self.text).map(|token| self.stemmers.stem(token)).collect();
         let mut matches = Vec::new();
 
         let mut words_positions = tokens
@@ -515,6 +528,7 @@ mod tests {
                 crate::score_details::ScoringStrategy::Skip,
                 false,
                 universe,
+                None,
                 &None,
                 crate::search::new::GeoSortStrategy::default(),
                 0,
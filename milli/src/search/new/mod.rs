@@ -7,10 +7,15 @@ mod interner;
 mod limits;
 mod logger;
 pub mod matches;
+#[cfg(feature = "plugins")]
+mod plugin;
+mod query_cache;
 mod query_graph;
 mod query_term;
+mod random_sort;
 mod ranking_rule_graph;
 mod ranking_rules;
+mod recency;
 mod resolve_query_graph;
 mod small_bitmap;
 
@@ -24,35 +29,50 @@ mod tests;
 use std::collections::HashSet;
 
 use bucket_sort::{bucket_sort, BucketSortOutput};
-use charabia::TokenizerBuilder;
+use charabia::{Language, TokenizerBuilder};
 use db_cache::DatabaseCache;
 use exact_attribute::ExactAttribute;
 use graph_based_ranking_rule::{Exactness, Fid, Position, Proximity, Typo};
 use heed::RoTxn;
 use interner::{DedupInterner, Interner};
+pub(crate) use logger::diagnostics::{search_loggers, DiagnosticsLoggers};
+pub use logger::diagnostics::{DiagnosticsSearchLogger, RankingRuleDiagnostics, SearchDiagnostics};
 pub use logger::visual::VisualSearchLogger;
 pub use logger::{DefaultSearchLogger, SearchLogger};
-use query_graph::{QueryGraph, QueryNode};
+pub(crate) use query_cache::QueryCache;
+use query_cache::QueryCacheKey;
+use query_graph::{QueryGraph, QueryNode, QueryNodeData};
+pub(crate) use query_term::is_number_like;
+pub use query_term::ExplainedTerm;
 use query_term::{located_query_terms_from_tokens, LocatedQueryTerm, Phrase, QueryTerm};
+use random_sort::RandomSort;
 use ranking_rules::{
     BoxRankingRule, PlaceholderQuery, RankingRule, RankingRuleOutput, RankingRuleQueryTrait,
 };
+use recency::Recency;
 use resolve_query_graph::{compute_query_graph_docids, PhraseDocIdsCache};
 use roaring::RoaringBitmap;
 use sort::Sort;
 
+pub(crate) use self::distinct::distinct_single_docid;
 use self::distinct::facet_string_values;
 use self::geo_sort::GeoSort;
 pub use self::geo_sort::Strategy as GeoSortStrategy;
 use self::graph_based_ranking_rule::Words;
 use self::interner::Interned;
 use self::vector_sort::VectorSort;
+use crate::decompounding::Decompounder;
 use crate::error::FieldIdMapMissingEntry;
+use crate::localized_attributes_rules::script_language_allow_list;
+use crate::localized_stop_words::LocalizedStopWords;
 use crate::score_details::{ScoreDetails, ScoringStrategy};
 use crate::search::new::distinct::apply_distinct_rule;
+use crate::stemming::Stemmers;
+use crate::unit_conversion::split_converted_field;
 use crate::vector::DistributionShift;
 use crate::{
-    AscDesc, DocumentId, FieldId, Filter, Index, Member, Result, TermsMatchingStrategy, UserError,
+    AscDesc, DocumentId, FieldId, Filter, Index, Member, Result, TermsMatchingStrategy, TimeBudget,
+    UserError,
 };
 
 /// A structure used throughout the execution of a search query.
@@ -65,6 +85,16 @@ pub struct SearchContext<'ctx> {
     pub term_interner: Interner<QueryTerm>,
     pub phrase_docids: PhraseDocIdsCache,
     pub restricted_fids: Option<RestrictedFids>,
+    /// Whether the `sort` ranking rule is moved before or after all the other ranking rules.
+    pub sort_first: Option<bool>,
+    /// The time the ranking rules are allowed to spend sorting the documents.
+    pub time_budget: TimeBudget,
+    /// The languages the query can be detected as, overriding the languages of the index.
+    pub locales: Option<Vec<Language>>,
+    /// The attribute deduplicating the documents, overriding the distinct attribute of the index.
+    pub distinct: Option<String>,
+    /// The decompounder of the index, only loaded when a word of the query is split.
+    decompounder: Option<Decompounder>,
 }
 
 impl<'ctx> SearchContext<'ctx> {
@@ -78,9 +108,51 @@ impl<'ctx> SearchContext<'ctx> {
             term_interner: <_>::default(),
             phrase_docids: <_>::default(),
             restricted_fids: None,
+            sort_first: None,
+            time_budget: TimeBudget::default(),
+            locales: None,
+            distinct: None,
+            decompounder: None,
         }
     }
 
+    /// Returns the id of the attribute the documents are deduplicated by, if any.
+    pub fn distinct_fid(&self) -> Result<Option<FieldId>> {
+        let fields_ids_map = self.index.fields_ids_map(self.txn)?;
+        Ok(match &self.distinct {
+            Some(field) => fields_ids_map.id(field),
+            None => self.index.distinct_field(self.txn)?.and_then(|field| fields_ids_map.id(field)),
+        })
+    }
+
+    /// Returns the decompounder splitting the compound words of the query.
+    pub fn decompounder(&mut self) -> Result<&Decompounder> {
+        if self.decompounder.is_none() {
+            let dictionaries = self.index.decompounding(self.txn)?.unwrap_or_default();
+            self.decompounder = Some(Decompounder::new(&dictionaries));
+        }
+        Ok(self.decompounder.as_ref().unwrap())
+    }
+
+    /// The ranking rules of the index, with the `sort` ranking rule moved before or after all the
+    /// other ones when the search asks for it.
+    fn ranking_rules(&self) -> Result<Vec<crate::Criterion>> {
+        let mut ranking_rules = self.index.criteria(self.txn)?;
+        if let Some(sort_first) = self.sort_first {
+            if let Some(position) =
+                ranking_rules.iter().position(|rr| *rr == crate::Criterion::Sort)
+            {
+                let sort = ranking_rules.remove(position);
+                if sort_first {
+                    ranking_rules.insert(0, sort);
+                } else {
+                    ranking_rules.push(sort);
+                }
+            }
+        }
+        Ok(ranking_rules)
+    }
+
     pub fn searchable_attributes(&mut self, searchable_attributes: &'ctx [String]) -> Result<()> {
         let fids_map = self.index.fields_ids_map(self.txn)?;
         let searchable_names = self.index.searchable_fields(self.txn)?;
@@ -218,7 +290,7 @@ fn get_ranking_rules_for_placeholder_search<'ctx>(
     let mut sorted_fields = HashSet::new();
     let mut geo_sorted = false;
     let mut ranking_rules: Vec<BoxRankingRule<PlaceholderQuery>> = vec![];
-    let settings_ranking_rules = ctx.index.criteria(ctx.txn)?;
+    let settings_ranking_rules = ctx.ranking_rules()?;
     for rr in settings_ranking_rules {
         match rr {
             // These rules need a query to have an effect; ignore them in placeholder search
@@ -246,14 +318,23 @@ fn get_ranking_rules_for_placeholder_search<'ctx>(
                     continue;
                 }
                 sorted_fields.insert(field_name.clone());
-                ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, true)?));
+                ranking_rules
+                    .push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, true, false)?));
             }
             crate::Criterion::Desc(field_name) => {
                 if sorted_fields.contains(&field_name) {
                     continue;
                 }
                 sorted_fields.insert(field_name.clone());
-                ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
+                ranking_rules
+                    .push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false, false)?));
+            }
+            crate::Criterion::Recency { field, half_life } => {
+                ranking_rules.push(Box::new(Recency::new(field, half_life)));
+            }
+            #[cfg(feature = "plugins")]
+            crate::Criterion::Plugin(name) => {
+                ranking_rules.push(Box::new(plugin::PluginRankingRule::new(name)?));
             }
         }
     }
@@ -278,7 +359,7 @@ fn get_ranking_rules_for_vector<'ctx>(
     let mut vector = false;
     let mut ranking_rules: Vec<BoxRankingRule<PlaceholderQuery>> = vec![];
 
-    let settings_ranking_rules = ctx.index.criteria(ctx.txn)?;
+    let settings_ranking_rules = ctx.ranking_rules()?;
     for rr in settings_ranking_rules {
         match rr {
             crate::Criterion::Words
@@ -319,14 +400,23 @@ fn get_ranking_rules_for_vector<'ctx>(
                     continue;
                 }
                 sorted_fields.insert(field_name.clone());
-                ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, true)?));
+                ranking_rules
+                    .push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, true, false)?));
             }
             crate::Criterion::Desc(field_name) => {
                 if sorted_fields.contains(&field_name) {
                     continue;
                 }
                 sorted_fields.insert(field_name.clone());
-                ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
+                ranking_rules
+                    .push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false, false)?));
+            }
+            crate::Criterion::Recency { field, half_life } => {
+                ranking_rules.push(Box::new(Recency::new(field, half_life)));
+            }
+            #[cfg(feature = "plugins")]
+            crate::Criterion::Plugin(name) => {
+                ranking_rules.push(Box::new(plugin::PluginRankingRule::new(name)?));
             }
         }
     }
@@ -340,6 +430,7 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
     sort_criteria: &Option<Vec<AscDesc>>,
     geo_strategy: geo_sort::Strategy,
     terms_matching_strategy: TermsMatchingStrategy,
+    skip_proximity: bool,
 ) -> Result<Vec<BoxRankingRule<'ctx, QueryGraph>>> {
     // query graph search
     let mut words = false;
@@ -357,7 +448,7 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
     }
 
     let mut ranking_rules: Vec<BoxRankingRule<QueryGraph>> = vec![];
-    let settings_ranking_rules = ctx.index.criteria(ctx.txn)?;
+    let settings_ranking_rules = ctx.ranking_rules()?;
     for rr in settings_ranking_rules {
         // Add Words before any of: typo, proximity, attribute
         match rr {
@@ -388,7 +479,7 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
                 ranking_rules.push(Box::new(Typo::new(None)));
             }
             crate::Criterion::Proximity => {
-                if proximity {
+                if proximity || skip_proximity {
                     continue;
                 }
                 proximity = true;
@@ -429,14 +520,23 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
                     continue;
                 }
                 sorted_fields.insert(field_name.clone());
-                ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, true)?));
+                ranking_rules
+                    .push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, true, false)?));
             }
             crate::Criterion::Desc(field_name) => {
                 if sorted_fields.contains(&field_name) {
                     continue;
                 }
                 sorted_fields.insert(field_name.clone());
-                ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
+                ranking_rules
+                    .push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false, false)?));
+            }
+            crate::Criterion::Recency { field, half_life } => {
+                ranking_rules.push(Box::new(Recency::new(field, half_life)));
+            }
+            #[cfg(feature = "plugins")]
+            crate::Criterion::Plugin(name) => {
+                ranking_rules.push(Box::new(plugin::PluginRankingRule::new(name)?));
             }
         }
     }
@@ -454,22 +554,26 @@ fn resolve_sort_criteria<'ctx, Query: RankingRuleQueryTrait>(
     let sort_criteria = sort_criteria.clone().unwrap_or_default();
     ranking_rules.reserve(sort_criteria.len());
     for criterion in sort_criteria {
+        let (is_ascending, nulls_first) = (criterion.is_ascending(), criterion.nulls_first());
         match criterion {
-            AscDesc::Asc(Member::Field(field_name)) => {
-                if sorted_fields.contains(&field_name) {
-                    continue;
-                }
-                sorted_fields.insert(field_name.clone());
-                ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, true)?));
-            }
-            AscDesc::Desc(Member::Field(field_name)) => {
+            AscDesc::Asc(Member::Field(field_name))
+            | AscDesc::Desc(Member::Field(field_name))
+            | AscDesc::AscNullsFirst(Member::Field(field_name))
+            | AscDesc::DescNullsFirst(Member::Field(field_name)) => {
                 if sorted_fields.contains(&field_name) {
                     continue;
                 }
                 sorted_fields.insert(field_name.clone());
-                ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
+                ranking_rules.push(Box::new(Sort::new(
+                    ctx.index,
+                    ctx.txn,
+                    field_name,
+                    is_ascending,
+                    nulls_first,
+                )?));
             }
-            AscDesc::Asc(Member::Geo(point)) => {
+            // the documents without a geo point always come last
+            AscDesc::Asc(Member::Geo(point)) | AscDesc::AscNullsFirst(Member::Geo(point)) => {
                 if *geo_sorted {
                     continue;
                 }
@@ -481,7 +585,7 @@ fn resolve_sort_criteria<'ctx, Query: RankingRuleQueryTrait>(
                     true,
                 )?));
             }
-            AscDesc::Desc(Member::Geo(point)) => {
+            AscDesc::Desc(Member::Geo(point)) | AscDesc::DescNullsFirst(Member::Geo(point)) => {
                 if *geo_sorted {
                     continue;
                 }
@@ -493,6 +597,12 @@ fn resolve_sort_criteria<'ctx, Query: RankingRuleQueryTrait>(
                     false,
                 )?));
             }
+            AscDesc::Asc(Member::Random(seed)) | AscDesc::AscNullsFirst(Member::Random(seed)) => {
+                ranking_rules.push(Box::new(RandomSort::new(seed, true)));
+            }
+            AscDesc::Desc(Member::Random(seed)) | AscDesc::DescNullsFirst(Member::Random(seed)) => {
+                ranking_rules.push(Box::new(RandomSort::new(seed, false)));
+            }
         };
     }
     Ok(())
@@ -537,7 +647,7 @@ pub fn execute_vector_search(
     let placeholder_search_logger: &mut dyn SearchLogger<PlaceholderQuery> =
         &mut placeholder_search_logger;
 
-    let BucketSortOutput { docids, scores, all_candidates } = bucket_sort(
+    let BucketSortOutput { docids, scores, all_candidates, degraded } = bucket_sort(
         ctx,
         ranking_rules,
         &PlaceholderQuery,
@@ -553,6 +663,175 @@ pub fn execute_vector_search(
         document_scores: scores,
         documents_ids: docids,
         located_query_terms: None,
+        degraded,
+    })
+}
+
+/// Tokenize the query with the settings of the index and build its query terms.
+fn tokenize_query(
+    ctx: &mut SearchContext,
+    query: &str,
+    words_limit: Option<usize>,
+) -> Result<Vec<LocatedQueryTerm>> {
+    let span = tracing::trace_span!(target: "search::tokens", "tokenizer_builder");
+    let entered = span.enter();
+
+    // We make sure that the analyzer is aware of the stop words
+    // this ensures that the query builder is able to properly remove them.
+    let mut tokbuilder = TokenizerBuilder::new();
+    let stop_words = ctx.index.stop_words(ctx.txn)?;
+    if let Some(ref stop_words) = stop_words {
+        tokbuilder.stop_words(stop_words);
+    }
+
+    let separators = ctx.index.allowed_separators(ctx.txn)?;
+    let separators: Option<Vec<_>> =
+        separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
+    if let Some(ref separators) = separators {
+        tokbuilder.separators(separators);
+    }
+
+    let dictionary = ctx.index.dictionary(ctx.txn)?;
+    let dictionary: Option<Vec<_>> =
+        dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
+    if let Some(ref dictionary) = dictionary {
+        tokbuilder.words_dict(dictionary);
+    }
+
+    let script_lang_map = match &ctx.locales {
+        Some(locales) => script_language_allow_list(locales),
+        None => ctx.index.script_language(ctx.txn)?,
+    };
+    if !script_lang_map.is_empty() {
+        tokbuilder.allow_list(&script_lang_map);
+    }
+
+    let tokenizer = tokbuilder.build();
+    let localized_stop_words = ctx.index.localized_stop_words(ctx.txn)?.unwrap_or_default();
+    let localized_stop_words = LocalizedStopWords::new(&localized_stop_words);
+    let stemmers = Stemmers::new(&ctx.index.stemming(ctx.txn)?.unwrap_or_default());
+    drop(entered);
+
+    let span = tracing::trace_span!(target: "search::tokens", "tokenize");
+    let entered = span.enter();
+    let tokens =
+        tokenizer.tokenize(query).map(|token| stemmers.stem(localized_stop_words.classify(token)));
+    drop(entered);
+
+    located_query_terms_from_tokens(ctx, tokens, words_limit)
+}
+
+/// The number of documents matching a query at each step of the resolution of its candidates.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CandidatesStatistics {
+    /// The number of documents in the index.
+    pub documents: u64,
+    /// The number of documents containing the exact words of the query.
+    pub after_words: u64,
+    /// The number of documents containing the words of the query or their typos, prefixes and synonyms.
+    pub after_typos: u64,
+    /// The number of documents also matching the filter, the ranking rules sort these candidates.
+    pub after_filter: u64,
+}
+
+/// Compute the number of candidates of a query at each step of their resolution.
+///
+/// The terms matching strategy is applied to the words of the query, and a placeholder
+/// search matches all the documents of the index before the filter is applied.
+pub fn compute_candidates_statistics(
+    ctx: &mut SearchContext,
+    query: Option<&str>,
+    terms_matching_strategy: TermsMatchingStrategy,
+    filters: &Option<Filter>,
+    words_limit: Option<usize>,
+) -> Result<CandidatesStatistics> {
+    let documents = ctx.index.documents_ids(ctx.txn)?;
+
+    let query_terms = match query {
+        Some(query) => tokenize_query(ctx, query, words_limit)?,
+        None => Vec::new(),
+    };
+
+    let (after_words, after_typos) = if query_terms.is_empty() {
+        (documents.len(), documents.clone())
+    } else {
+        let (graph, _) = QueryGraph::from_query(ctx, &query_terms)?;
+        let mut exact_graph = graph.clone();
+        for (_, node) in exact_graph.nodes.iter_mut() {
+            if let QueryNodeData::Term(term) = &mut node.data {
+                let subset = &mut term.term_subset;
+                if subset.exact_term(ctx).is_some() {
+                    subset.keep_only_exact_term(ctx);
+                } else {
+                    // ngrams and words that are not in the index don't match any document exactly
+                    subset.clear_zero_typo_subset();
+                    subset.clear_one_typo_subset();
+                    subset.clear_two_typo_subset();
+                }
+            }
+        }
+
+        let after_words = resolve_maximally_reduced_query_graph(
+            ctx,
+            &documents,
+            &exact_graph,
+            terms_matching_strategy,
+            &mut DefaultSearchLogger,
+        )?;
+        let after_typos = resolve_maximally_reduced_query_graph(
+            ctx,
+            &documents,
+            &graph,
+            terms_matching_strategy,
+            &mut DefaultSearchLogger,
+        )?;
+        (after_words.len(), after_typos)
+    };
+
+    let after_filter = match filters {
+        Some(filters) => filters.evaluate(ctx.txn, ctx.index)? & &after_typos,
+        None => after_typos.clone(),
+    };
+
+    Ok(CandidatesStatistics {
+        documents: documents.len(),
+        after_words,
+        after_typos: after_typos.len(),
+        after_filter: after_filter.len(),
+    })
+}
+
+/// How a query is understood, to find out why it matches or misses some documents.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchExplanation {
+    /// The terms the query is tokenized into, with the words they are derived into.
+    pub terms: Vec<ExplainedTerm>,
+    /// The parsed filter.
+    pub filter: Option<String>,
+    pub candidates: CandidatesStatistics,
+}
+
+/// Explain how the query is tokenized and derived, and how many candidates it matches.
+pub fn explain_search(
+    ctx: &mut SearchContext,
+    query: Option<&str>,
+    terms_matching_strategy: TermsMatchingStrategy,
+    filters: &Option<Filter>,
+    words_limit: Option<usize>,
+) -> Result<SearchExplanation> {
+    let query_terms = match query {
+        Some(query) => tokenize_query(ctx, query, words_limit)?,
+        None => Vec::new(),
+    };
+    let terms = query_terms.iter().map(|term| term.explain(ctx)).collect::<Result<_>>()?;
+
+    let candidates =
+        compute_candidates_statistics(ctx, query, terms_matching_strategy, filters, words_limit)?;
+
+    Ok(SearchExplanation {
+        terms,
+        filter: filters.as_ref().map(|filter| filter.to_string()),
+        candidates,
     })
 }
 
@@ -565,6 +844,7 @@ pub fn execute_search(
     scoring_strategy: ScoringStrategy,
     exhaustive_number_hits: bool,
     mut universe: RoaringBitmap,
+    deferred_filter: Option<&Filter>,
     sort_criteria: &Option<Vec<AscDesc>>,
     geo_strategy: geo_sort::Strategy,
     from: usize,
@@ -574,72 +854,48 @@ pub fn execute_search(
     query_graph_logger: &mut dyn SearchLogger<QueryGraph>,
 ) -> Result<PartialSearchResult> {
     check_sort_criteria(ctx, sort_criteria.as_ref())?;
+    let hints = ctx.index.query_plan_hints(ctx.txn)?.unwrap_or_default();
 
     let mut located_query_terms = None;
-    let query_terms = if let Some(query) = query {
-        let span = tracing::trace_span!(target: "search::tokens", "tokenizer_builder");
-        let entered = span.enter();
-
-        // We make sure that the analyzer is aware of the stop words
-        // this ensures that the query builder is able to properly remove them.
-        let mut tokbuilder = TokenizerBuilder::new();
-        let stop_words = ctx.index.stop_words(ctx.txn)?;
-        if let Some(ref stop_words) = stop_words {
-            tokbuilder.stop_words(stop_words);
-        }
-
-        let separators = ctx.index.allowed_separators(ctx.txn)?;
-        let separators: Option<Vec<_>> =
-            separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
-        if let Some(ref separators) = separators {
-            tokbuilder.separators(separators);
-        }
-
-        let dictionary = ctx.index.dictionary(ctx.txn)?;
-        let dictionary: Option<Vec<_>> =
-            dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
-        if let Some(ref dictionary) = dictionary {
-            tokbuilder.words_dict(dictionary);
-        }
-
-        let script_lang_map = ctx.index.script_language(ctx.txn)?;
-        if !script_lang_map.is_empty() {
-            tokbuilder.allow_list(&script_lang_map);
-        }
-
-        let tokenizer = tokbuilder.build();
-        drop(entered);
-
-        let span = tracing::trace_span!(target: "search::tokens", "tokenize");
-        let entered = span.enter();
-        let tokens = tokenizer.tokenize(query);
-        drop(entered);
-
-        let query_terms = located_query_terms_from_tokens(ctx, tokens, words_limit)?;
+    let cache_key = match query {
+        Some(query) => QueryCacheKey::new(ctx, query, words_limit)?,
+        None => None,
+    };
+    let query_cache = ctx.index.query_cache.clone();
+    let cached = cache_key.as_ref().and_then(|key| query_cache.restore(key, ctx));
+    let parsed_query = if let Some(cached) = cached {
+        Some(cached)
+    } else if let Some(query) = query {
+        let query_terms = tokenize_query(ctx, query, words_limit)?;
         if query_terms.is_empty() {
             // Do a placeholder search instead
             None
         } else {
-            Some(query_terms)
+            Some(QueryGraph::from_query(ctx, &query_terms)?)
         }
     } else {
         None
     };
-    let bucket_sort_output = if let Some(query_terms) = query_terms {
-        let (graph, new_located_query_terms) = QueryGraph::from_query(ctx, &query_terms)?;
-        located_query_terms = Some(new_located_query_terms);
+    let bucket_sort_output = if let Some((graph, new_located_query_terms)) = parsed_query {
+        universe &=
+            resolve_universe(ctx, &universe, &graph, terms_matching_strategy, query_graph_logger)?;
+
+        // The filter is not evaluated when no document matches the words of the query.
+        if let Some(filter) = deferred_filter.filter(|_| !universe.is_empty()) {
+            universe &= filter.evaluate(ctx.txn, ctx.index)?;
+        }
 
+        let skip_proximity =
+            hints.proximity_max_candidates.map_or(false, |max| universe.len() > max);
         let ranking_rules = get_ranking_rules_for_query_graph_search(
             ctx,
             sort_criteria,
             geo_strategy,
             terms_matching_strategy,
+            skip_proximity,
         )?;
 
-        universe &=
-            resolve_universe(ctx, &universe, &graph, terms_matching_strategy, query_graph_logger)?;
-
-        bucket_sort(
+        let output = bucket_sort(
             ctx,
             ranking_rules,
             &graph,
@@ -648,8 +904,19 @@ pub fn execute_search(
             length,
             scoring_strategy,
             query_graph_logger,
-        )?
+        )?;
+
+        // The interners now contain the derivations computed by the ranking rules.
+        if let Some(key) = cache_key {
+            query_cache.insert(key, ctx, &graph, &new_located_query_terms);
+        }
+        located_query_terms = Some(new_located_query_terms);
+        output
     } else {
+        if let Some(filter) = deferred_filter {
+            universe &= filter.evaluate(ctx.txn, ctx.index)?;
+        }
+
         let ranking_rules =
             get_ranking_rules_for_placeholder_search(ctx, sort_criteria, geo_strategy)?;
         bucket_sort(
@@ -664,16 +931,13 @@ pub fn execute_search(
         )?
     };
 
-    let BucketSortOutput { docids, scores, mut all_candidates } = bucket_sort_output;
-    let fields_ids_map = ctx.index.fields_ids_map(ctx.txn)?;
+    let BucketSortOutput { docids, scores, mut all_candidates, degraded } = bucket_sort_output;
 
     // The candidates is the universe unless the exhaustive number of hits
     // is requested and a distinct attribute is set.
     if exhaustive_number_hits {
-        if let Some(f) = ctx.index.distinct_field(ctx.txn)? {
-            if let Some(distinct_fid) = fields_ids_map.id(f) {
-                all_candidates = apply_distinct_rule(ctx, distinct_fid, &all_candidates)?.remaining;
-            }
+        if let Some(distinct_fid) = ctx.distinct_fid()? {
+            all_candidates = apply_distinct_rule(ctx, distinct_fid, &all_candidates)?.remaining;
         }
     }
 
@@ -682,6 +946,7 @@ pub fn execute_search(
         document_scores: scores,
         documents_ids: docids,
         located_query_terms,
+        degraded,
     })
 }
 
@@ -706,9 +971,39 @@ fn check_sort_criteria(ctx: &SearchContext, sort_criteria: Option<&Vec<AscDesc>>
     // We check that we are allowed to use the sort criteria, we check
     // that they are declared in the sortable fields.
     let sortable_fields = ctx.index.sortable_fields(ctx.txn)?;
+    let unit_conversions = ctx.index.unit_conversions(ctx.txn)?;
     for asc_desc in sort_criteria {
         match asc_desc.member() {
             Member::Field(ref field) if !crate::is_faceted(field, &sortable_fields) => {
+                // a sortable field converted into a given unit, e.g. `price:usd`
+                if let Some((field, unit, conversion)) =
+                    split_converted_field(field, &unit_conversions)
+                        .filter(|(field, _, _)| crate::is_faceted(field, &sortable_fields))
+                {
+                    if crate::is_faceted(&conversion.unit_attribute, &sortable_fields) {
+                        if conversion.factors_to(unit).is_some() {
+                            continue;
+                        }
+                        return Err(UserError::InvalidSortUnit {
+                            field: field.to_string(),
+                            unit: unit.to_string(),
+                            valid_units: conversion.rates.keys().cloned().collect(),
+                        }
+                        .into());
+                    }
+
+                    // the documents are sorted by unit, the unit attribute must be sortable too
+                    let (valid_fields, hidden_fields) =
+                        ctx.index.remove_hidden_fields(ctx.txn, sortable_fields)?;
+
+                    return Err(UserError::InvalidSortableAttribute {
+                        field: conversion.unit_attribute.clone(),
+                        valid_fields,
+                        hidden_fields,
+                    }
+                    .into());
+                }
+
                 let (valid_fields, hidden_fields) =
                     ctx.index.remove_hidden_fields(ctx.txn, sortable_fields)?;
 
@@ -742,4 +1037,5 @@ pub struct PartialSearchResult {
     pub candidates: RoaringBitmap,
     pub documents_ids: Vec<DocumentId>,
     pub document_scores: Vec<Vec<ScoreDetails>>,
+    pub degraded: bool,
 }
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use roaring::RoaringBitmap;
+
+use super::ranking_rules::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait};
+use crate::plugins::RankingRulePlugin;
+use crate::score_details::{self, Rank, ScoreDetails};
+use crate::{CriterionError, Result, SearchContext, SearchLogger, UserError};
+
+/// A ranking rule returning the buckets computed by a [`RankingRulePlugin`].
+pub struct PluginRankingRule<Q: RankingRuleQueryTrait> {
+    name: String,
+    plugin: Arc<dyn RankingRulePlugin>,
+    query: Option<Q>,
+    buckets: std::vec::IntoIter<RoaringBitmap>,
+    rank: u32,
+    max_rank: u32,
+}
+
+impl<Q: RankingRuleQueryTrait> PluginRankingRule<Q> {
+    pub fn new(name: String) -> Result<Self> {
+        let plugin = crate::plugins::ranking_rule(&name).ok_or_else(|| {
+            UserError::CriterionError(CriterionError::UnknownPlugin { name: name.clone() })
+        })?;
+
+        Ok(Self { name, plugin, query: None, buckets: Default::default(), rank: 0, max_rank: 0 })
+    }
+
+    fn score(&self) -> ScoreDetails {
+        ScoreDetails::Plugin(score_details::Plugin {
+            name: self.name.clone(),
+            rank: Rank { rank: self.rank, max_rank: self.max_rank },
+        })
+    }
+}
+
+impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for PluginRankingRule<Q> {
+    fn id(&self) -> String {
+        format!("plugin:{}", self.name)
+    }
+
+    fn start_iteration(
+        &mut self,
+        ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Q>,
+        universe: &RoaringBitmap,
+        query: &Q,
+    ) -> Result<()> {
+        assert!(self.query.is_none());
+
+        let buckets = self.plugin.buckets(ctx.index, ctx.txn, universe)?;
+        // the documents that are not part of any bucket are returned in a last bucket
+        self.max_rank = buckets.len() as u32 + 1;
+        self.rank = self.max_rank;
+        self.buckets = buckets.into_iter();
+        self.query = Some(query.clone());
+        Ok(())
+    }
+
+    fn next_bucket(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Q>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RankingRuleOutput<Q>>> {
+        let query = self.query.as_ref().unwrap().clone();
+
+        for bucket in self.buckets.by_ref() {
+            let candidates = bucket & universe;
+            let score = self.score();
+            self.rank -= 1;
+            if !candidates.is_empty() {
+                return Ok(Some(RankingRuleOutput { query, candidates, score }));
+            }
+        }
+
+        self.rank = 1;
+        Ok(Some(RankingRuleOutput { query, candidates: universe.clone(), score: self.score() }))
+    }
+
+    fn end_iteration(&mut self, _ctx: &mut SearchContext<'ctx>, _logger: &mut dyn SearchLogger<Q>) {
+        self.query = None;
+        self.buckets = Default::default();
+    }
+}
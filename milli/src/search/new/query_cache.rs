@@ -0,0 +1,112 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use charabia::Language;
+use time::OffsetDateTime;
+
+use super::interner::{DedupInterner, Interner};
+use super::query_graph::QueryGraph;
+use super::query_term::{LocatedQueryTerm, Phrase, QueryTerm};
+use super::SearchContext;
+use crate::Result;
+
+/// The maximum number of queries kept in the cache of an index.
+const QUERY_CACHE_CAPACITY: usize = 64;
+
+/// Identifies a query parsed on a given version of an index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryCacheKey {
+    /// The last update of the index, the derivations of the words depend on its content and settings.
+    updated_at: OffsetDateTime,
+    query: String,
+    words_limit: Option<usize>,
+    locales: Option<Vec<Language>>,
+}
+
+impl QueryCacheKey {
+    /// Returns `None` when the search context already holds values, e.g. when it's the second pass of
+    /// a search, the interned values of a cached query can then not be restored into it.
+    pub fn new(
+        ctx: &SearchContext,
+        query: &str,
+        words_limit: Option<usize>,
+    ) -> Result<Option<Self>> {
+        if ctx.word_interner.len() != 0
+            || ctx.phrase_interner.len() != 0
+            || ctx.term_interner.len() != 0
+        {
+            return Ok(None);
+        }
+        let updated_at = ctx.index.updated_at(ctx.txn)?;
+        Ok(Some(Self {
+            updated_at,
+            query: query.to_string(),
+            words_limit,
+            locales: ctx.locales.clone(),
+        }))
+    }
+}
+
+/// A parsed query along with the interners its query terms and graph refer to.
+struct ParsedQuery {
+    graph: QueryGraph,
+    located_query_terms: Vec<LocatedQueryTerm>,
+    word_interner: DedupInterner<String>,
+    phrase_interner: DedupInterner<Phrase>,
+    term_interner: Interner<QueryTerm>,
+}
+
+/// A small LRU cache of the query graphs of the last searches made on an index.
+///
+/// The interners are stored at the end of the search, so the derivations of the words computed
+/// by the ranking rules are reused too.
+#[derive(Default)]
+pub struct QueryCache {
+    entries: Mutex<VecDeque<(QueryCacheKey, Arc<ParsedQuery>)>>,
+}
+
+impl QueryCache {
+    /// Restores the interners of the cached query into the search context
+    /// and returns its query graph and located query terms.
+    pub fn restore(
+        &self,
+        key: &QueryCacheKey,
+        ctx: &mut SearchContext,
+    ) -> Option<(QueryGraph, Vec<LocatedQueryTerm>)> {
+        let parsed = {
+            let mut entries = self.entries.lock().unwrap();
+            let position = entries.iter().position(|(k, _)| k == key)?;
+            let entry = entries.remove(position)?;
+            let parsed = entry.1.clone();
+            entries.push_front(entry);
+            parsed
+        };
+
+        ctx.word_interner = parsed.word_interner.clone();
+        ctx.phrase_interner = parsed.phrase_interner.clone();
+        ctx.term_interner = parsed.term_interner.clone();
+        Some((parsed.graph.clone(), parsed.located_query_terms.clone()))
+    }
+
+    /// Stores the query graph with the current interners of the search context.
+    pub fn insert(
+        &self,
+        key: QueryCacheKey,
+        ctx: &SearchContext,
+        graph: &QueryGraph,
+        located_query_terms: &[LocatedQueryTerm],
+    ) {
+        let parsed = Arc::new(ParsedQuery {
+            graph: graph.clone(),
+            located_query_terms: located_query_terms.to_vec(),
+            word_interner: ctx.word_interner.clone(),
+            phrase_interner: ctx.phrase_interner.clone(),
+            term_interner: ctx.term_interner.clone(),
+        });
+
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|(k, _)| *k != key);
+        entries.push_front((key, parsed));
+        entries.truncate(QUERY_CACHE_CAPACITY);
+    }
+}
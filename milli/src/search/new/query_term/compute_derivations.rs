@@ -266,7 +266,15 @@ pub fn partially_initialized_term_from_word(
 }
 
 fn find_split_words(ctx: &mut SearchContext, word: &str) -> Result<Option<Interned<Phrase>>> {
-    if let Some((l, r)) = split_best_frequency(ctx, word)? {
+    // The compound words are split into the words of the decompounding dictionaries first.
+    let parts: Option<Vec<String>> = ctx
+        .decompounder()?
+        .split_any(word)
+        .map(|parts| parts.into_iter().map(String::from).collect());
+    if let Some(parts) = parts {
+        let words = parts.into_iter().map(|part| Some(ctx.word_interner.insert(part))).collect();
+        Ok(Some(ctx.phrase_interner.insert(Phrase { words })))
+    } else if let Some((l, r)) = split_best_frequency(ctx, word)? {
         Ok(Some(ctx.phrase_interner.insert(Phrase { words: vec![Some(l), Some(r)] })))
     } else {
         Ok(None)
@@ -9,7 +9,9 @@ use std::ops::RangeInclusive;
 
 use either::Either;
 pub use ntypo_subset::NTypoTermSubset;
-pub use parse_query::{located_query_terms_from_tokens, make_ngram, number_of_typos_allowed};
+pub use parse_query::{
+    is_number_like, located_query_terms_from_tokens, make_ngram, number_of_typos_allowed,
+};
 pub use phrase::Phrase;
 
 use super::interner::{DedupInterner, Interned};
@@ -461,11 +463,66 @@ pub struct LocatedQueryTerm {
     pub positions: RangeInclusive<u16>,
 }
 
+/// A term of the search query and the words it's derived into, to debug the relevancy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExplainedTerm {
+    /// The word, or the words of the phrase, as they are once normalized.
+    pub original: String,
+    pub positions: RangeInclusive<u16>,
+    /// Whether the term is the last word of the query, that also matches the words it prefixes.
+    pub is_prefix: bool,
+    pub is_phrase: bool,
+    /// The word matching the term without any typo.
+    pub exact: Option<String>,
+    /// The words prefixed by the term, empty when the prefix databases are used instead.
+    pub prefix_of: Vec<String>,
+    pub one_typo: Vec<String>,
+    pub two_typos: Vec<String>,
+    /// The phrase made of the term split into two words, e.g. `sun flower` for `sunflower`.
+    pub split_words: Option<String>,
+    pub synonyms: Vec<String>,
+}
+
 impl LocatedQueryTerm {
     /// Return `true` iff the term is empty
     pub fn is_empty(&self, interner: &DedupInterner<QueryTerm>) -> bool {
         interner.get(self.value).is_empty()
     }
+
+    /// Describes the term and all its derivations, computing them if needed.
+    pub fn explain(&self, ctx: &mut SearchContext) -> Result<ExplainedTerm> {
+        self.value.compute_fully_if_needed(ctx)?;
+        let ctx: &SearchContext = ctx;
+        let term = ctx.term_interner.get(self.value);
+        let word = |word: &Interned<String>| ctx.word_interner.get(*word).clone();
+        let phrase = |phrase: &Interned<Phrase>| phrase.description(ctx);
+
+        let ZeroTypoTerm { phrase: original_phrase, exact, prefix_of, synonyms, use_prefix_db: _ } =
+            &term.zero_typo;
+        let (split_words, one_typo) = match &term.one_typo {
+            Lazy::Init(OneTypoTerm { split_words, one_typo }) => {
+                (split_words.as_ref().map(phrase), one_typo.iter().map(word).collect())
+            }
+            Lazy::Uninit => (None, Vec::new()),
+        };
+        let two_typos = match &term.two_typo {
+            Lazy::Init(TwoTypoTerm { two_typos }) => two_typos.iter().map(word).collect(),
+            Lazy::Uninit => Vec::new(),
+        };
+
+        Ok(ExplainedTerm {
+            original: word(&term.original),
+            positions: self.positions.clone(),
+            is_prefix: term.is_prefix,
+            is_phrase: original_phrase.is_some(),
+            exact: exact.as_ref().map(word),
+            prefix_of: prefix_of.iter().map(word).collect(),
+            one_typo,
+            two_typos,
+            split_words,
+            synonyms: synonyms.iter().map(phrase).collect(),
+        })
+    }
 }
 
 impl QueryTerm {
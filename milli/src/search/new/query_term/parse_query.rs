@@ -1,21 +1,21 @@
 use std::collections::BTreeSet;
 
-use charabia::normalizer::NormalizedTokenIter;
-use charabia::{SeparatorKind, TokenKind};
+use charabia::{SeparatorKind, Token, TokenKind};
 
 use super::compute_derivations::partially_initialized_term_from_word;
 use super::{LocatedQueryTerm, ZeroTypoTerm};
 use crate::search::new::query_term::{Lazy, Phrase, QueryTerm};
-use crate::{Result, SearchContext, MAX_WORD_LENGTH};
+use crate::{PrefixSearch, Result, SearchContext, MAX_WORD_LENGTH};
 
 /// Convert the tokenised search query into a list of located query terms.
 #[tracing::instrument(level = "trace", skip_all, target = "search::query")]
 pub fn located_query_terms_from_tokens(
     ctx: &mut SearchContext,
-    query: NormalizedTokenIter,
+    query: impl Iterator<Item = Token<'_>>,
     words_limit: Option<usize>,
 ) -> Result<Vec<LocatedQueryTerm>> {
     let nbr_typos = number_of_typos_allowed(ctx)?;
+    let prefix_search = ctx.index.prefix_search(ctx.txn)?.unwrap_or_default();
 
     let mut located_terms = Vec::new();
 
@@ -43,7 +43,8 @@ pub fn located_query_terms_from_tokens(
 
                 // 1. if the word is quoted we push it in a phrase-buffer waiting for the ending quote,
                 // 2. if the word is not the last token of the query and is not a stop_word we push it as a non-prefix word,
-                // 3. if the word is the last token of the query we push it as a prefix word.
+                // 3. if the word is the last token of the query we push it as a prefix word,
+                //    unless the prefix search is disabled.
                 if let Some(phrase) = &mut phrase {
                     phrase.push_word(ctx, &token, position)
                 } else if peekable.peek().is_some() {
@@ -71,7 +72,7 @@ pub fn located_query_terms_from_tokens(
                         ctx,
                         word,
                         nbr_typos(word),
-                        true,
+                        prefix_search == PrefixSearch::IndexingTime,
                         false,
                     )?;
                     let located_term = LocatedQueryTerm {
@@ -145,11 +146,13 @@ pub fn number_of_typos_allowed<'ctx>(
     let min_len_two_typos = ctx.index.min_word_len_two_typos(ctx.txn)?;
 
     let exact_words = ctx.index.exact_words(ctx.txn)?;
+    let disable_typos_on_numbers = ctx.index.disable_typos_on_numbers(ctx.txn)?;
 
     Ok(Box::new(move |word: &str| {
         if !authorize_typos
             || word.len() < min_len_one_typo as usize
             || exact_words.as_ref().map_or(false, |fst| fst.contains(word))
+            || (disable_typos_on_numbers && is_number_like(word))
         {
             0
         } else if word.len() < min_len_two_typos as usize {
@@ -160,6 +163,11 @@ pub fn number_of_typos_allowed<'ctx>(
     }))
 }
 
+/// Whether the word looks like a number or a code, e.g. `1984` or `A1023`: it contains a digit.
+pub fn is_number_like(word: &str) -> bool {
+    word.bytes().any(|byte| byte.is_ascii_digit())
+}
+
 pub fn make_ngram(
     ctx: &mut SearchContext,
     terms: &[LocatedQueryTerm],
@@ -0,0 +1,97 @@
+use std::collections::BinaryHeap;
+
+use roaring::RoaringBitmap;
+
+use super::ranking_rules::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait};
+use crate::score_details::{self, ScoreDetails};
+use crate::{DocumentId, Result, SearchContext, SearchLogger};
+
+/// A ranking rule returning the documents one by one in a pseudo-random order.
+///
+/// The position of a document only depends on the seed and on its internal id, so the same seed
+/// always gives the same order as long as the documents are not reindexed.
+pub struct RandomSort<Q: RankingRuleQueryTrait> {
+    seed: u64,
+    is_ascending: bool,
+    query: Option<Q>,
+    /// The documents of the universe along with their random key, the next one on top.
+    heap: BinaryHeap<(u64, DocumentId)>,
+}
+
+impl<Q: RankingRuleQueryTrait> RandomSort<Q> {
+    pub fn new(seed: u64, is_ascending: bool) -> Self {
+        Self { seed, is_ascending, query: None, heap: BinaryHeap::new() }
+    }
+
+    fn score(&self, value: serde_json::Value) -> ScoreDetails {
+        ScoreDetails::Sort(score_details::Sort {
+            field_name: format!("_random({})", self.seed),
+            ascending: self.is_ascending,
+            redacted: false,
+            value,
+        })
+    }
+}
+
+/// Returns the random key of a document, the `splitmix64` finalizer spreads the consecutive ids.
+fn random_key(seed: u64, docid: DocumentId) -> u64 {
+    let mut key = seed.wrapping_add((docid as u64).wrapping_mul(0x9e3779b97f4a7c15));
+    key = (key ^ (key >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    key = (key ^ (key >> 27)).wrapping_mul(0x94d049bb133111eb);
+    key ^ (key >> 31)
+}
+
+impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for RandomSort<Q> {
+    fn id(&self) -> String {
+        format!("_random({}):{}", self.seed, if self.is_ascending { "asc" } else { "desc" })
+    }
+
+    fn start_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Q>,
+        universe: &RoaringBitmap,
+        query: &Q,
+    ) -> Result<()> {
+        assert!(self.query.is_none());
+
+        // the heap pops the greatest key first, the keys are flipped to pop the smallest one
+        let is_ascending = self.is_ascending;
+        let seed = self.seed;
+        self.heap = universe
+            .iter()
+            .map(|docid| {
+                let key = random_key(seed, docid);
+                (if is_ascending { !key } else { key }, docid)
+            })
+            .collect();
+        self.query = Some(query.clone());
+        Ok(())
+    }
+
+    fn next_bucket(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Q>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RankingRuleOutput<Q>>> {
+        let query = self.query.as_ref().unwrap().clone();
+
+        while let Some((key, docid)) = self.heap.pop() {
+            if universe.contains(docid) {
+                let key = if self.is_ascending { !key } else { key };
+                let candidates = RoaringBitmap::from_iter([docid]);
+                let score = self.score(key.into());
+                return Ok(Some(RankingRuleOutput { query, candidates, score }));
+            }
+        }
+
+        let score = self.score(serde_json::Value::Null);
+        Ok(Some(RankingRuleOutput { query, candidates: universe.clone(), score }))
+    }
+
+    fn end_iteration(&mut self, _ctx: &mut SearchContext<'ctx>, _logger: &mut dyn SearchLogger<Q>) {
+        self.query = None;
+        self.heap.clear();
+    }
+}
@@ -0,0 +1,124 @@
+use std::ops::Bound::{self, Excluded, Included, Unbounded};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use roaring::RoaringBitmap;
+
+use super::ranking_rules::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait};
+use crate::heed_codec::facet::OrderedF64Codec;
+use crate::score_details::{self, Rank, ScoreDetails};
+use crate::search::facet::find_docids_of_facet_within_bounds;
+use crate::{Result, SearchContext, SearchLogger};
+
+/// The number of buckets the documents are spread into depending on their age.
+///
+/// The score of a document decays from `1.0` to `0.0` as it gets older, the documents
+/// whose scores round to the same tenth are ranked in the same bucket.
+const RECENCY_BUCKETS: u32 = 10;
+
+/// A ranking rule favouring the documents with the most recent Unix timestamp, in seconds.
+///
+/// The score of a document is halved every `half_life` seconds, unlike a sort, the documents of
+/// similar ages are in the same bucket and are then ranked by the following ranking rules.
+pub struct Recency<Q: RankingRuleQueryTrait> {
+    field_name: String,
+    half_life: u64,
+    query: Option<Q>,
+    /// The buckets from the newest to the oldest documents, along with their rank.
+    buckets: std::vec::IntoIter<(RoaringBitmap, u32)>,
+}
+
+impl<Q: RankingRuleQueryTrait> Recency<Q> {
+    pub fn new(field_name: String, half_life: u64) -> Self {
+        Self { field_name, half_life, query: None, buckets: Default::default() }
+    }
+
+    /// Returns the timestamp below which the score of a document is at most `rank` tenths.
+    fn oldest_timestamp(&self, now: f64, rank: u32) -> f64 {
+        now - self.half_life as f64 * (RECENCY_BUCKETS as f64 / rank as f64).log2()
+    }
+}
+
+fn score(field_name: &str, rank: u32) -> ScoreDetails {
+    ScoreDetails::Recency(score_details::Recency {
+        field_name: field_name.to_string(),
+        // the documents without a timestamp are ranked after all the others
+        rank: Rank { rank, max_rank: RECENCY_BUCKETS + 1 },
+    })
+}
+
+impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for Recency<Q> {
+    fn id(&self) -> String {
+        format!("recency({})", self.field_name)
+    }
+
+    fn start_iteration(
+        &mut self,
+        ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Q>,
+        universe: &RoaringBitmap,
+        query: &Q,
+    ) -> Result<()> {
+        assert!(self.query.is_none());
+
+        let mut buckets = Vec::new();
+        let fields_ids_map = ctx.index.fields_ids_map(ctx.txn)?;
+        if let Some(field_id) = fields_ids_map.id(&self.field_name) {
+            let now =
+                SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs_f64();
+            for rank in (1..=RECENCY_BUCKETS).rev() {
+                // the documents from the future are as recent as the documents of now
+                let right: Bound<f64> = match rank {
+                    RECENCY_BUCKETS => Unbounded,
+                    rank => Included(self.oldest_timestamp(now, rank)),
+                };
+                let left: Bound<f64> = match rank {
+                    1 => Unbounded,
+                    rank => Excluded(self.oldest_timestamp(now, rank - 1)),
+                };
+                let mut docids = RoaringBitmap::new();
+                find_docids_of_facet_within_bounds::<OrderedF64Codec>(
+                    ctx.txn,
+                    ctx.index.facet_id_f64_docids,
+                    field_id,
+                    &left,
+                    &right,
+                    &mut docids,
+                )?;
+                docids &= universe;
+                if !docids.is_empty() {
+                    buckets.push((docids, rank + 1));
+                }
+            }
+        }
+
+        self.buckets = buckets.into_iter();
+        self.query = Some(query.clone());
+        Ok(())
+    }
+
+    fn next_bucket(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Q>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RankingRuleOutput<Q>>> {
+        let query = self.query.as_ref().unwrap().clone();
+
+        for (bucket, rank) in self.buckets.by_ref() {
+            let candidates = bucket & universe;
+            if !candidates.is_empty() {
+                let score = score(&self.field_name, rank);
+                return Ok(Some(RankingRuleOutput { query, candidates, score }));
+            }
+        }
+
+        // the documents without a timestamp are returned in a last bucket
+        let score = score(&self.field_name, 1);
+        Ok(Some(RankingRuleOutput { query, candidates: universe.clone(), score }))
+    }
+
+    fn end_iteration(&mut self, _ctx: &mut SearchContext<'ctx>, _logger: &mut dyn SearchLogger<Q>) {
+        self.query = None;
+        self.buckets = Default::default();
+    }
+}
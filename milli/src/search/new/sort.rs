@@ -1,13 +1,18 @@
+use std::iter::Peekable;
+
 use heed::BytesDecode;
 use roaring::RoaringBitmap;
 
 use super::logger::SearchLogger;
 use super::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait, SearchContext};
-use crate::heed_codec::facet::{FacetGroupKeyCodec, OrderedF64Codec};
+use crate::heed_codec::facet::{FacetGroupKey, FacetGroupKeyCodec, OrderedF64Codec};
 use crate::heed_codec::{BytesRefCodec, StrRefCodec};
 use crate::score_details::{self, ScoreDetails};
-use crate::search::facet::{ascending_facet_sort, descending_facet_sort};
-use crate::{FieldId, Index, Result};
+use crate::search::facet::{
+    ascending_facet_sort, descending_facet_sort, get_faceted_documents_ids,
+};
+use crate::unit_conversion::split_converted_field;
+use crate::{normalize_facet, FieldId, Index, Result};
 
 pub trait RankingRuleOutputIter<'ctx, Query> {
     fn next_bucket(&mut self) -> Result<Option<RankingRuleOutput<Query>>>;
@@ -48,45 +53,109 @@ impl<'ctx, Query> RankingRuleOutputIter<'ctx, Query> for RankingRuleOutputIterWr
 pub struct Sort<'ctx, Query> {
     field_name: String,
     field_id: Option<FieldId>,
+    conversion: Option<SortConversion>,
     is_ascending: bool,
+    /// Whether the documents without a value are returned before the others.
+    nulls_first: bool,
     original_query: Option<Query>,
     iter: Option<RankingRuleOutputIterWrapper<'ctx, Query>>,
     must_redact: bool,
 }
+
+/// The conversion of the values of a field sorted in a given unit, e.g. `price:usd:asc`.
+struct SortConversion {
+    unit_field_id: Option<FieldId>,
+    /// The units along with the factor converting their values into the sorted unit.
+    factors: Vec<(String, f64)>,
+}
+
 impl<'ctx, Query> Sort<'ctx, Query> {
     pub fn new(
         index: &Index,
         rtxn: &'ctx heed::RoTxn,
         field_name: String,
         is_ascending: bool,
+        nulls_first: bool,
     ) -> Result<Self> {
         let fields_ids_map = index.fields_ids_map(rtxn)?;
-        let field_id = fields_ids_map.id(&field_name);
-        let must_redact = Self::must_redact(index, rtxn, &field_name)?;
+        let unit_conversions = index.unit_conversions(rtxn)?;
+        let converted = split_converted_field(&field_name, &unit_conversions)
+            .filter(|_| fields_ids_map.id(&field_name).is_none());
+
+        let (field_id, conversion, must_redact) = match converted {
+            Some((field, unit, conversion)) => {
+                let factors = conversion
+                    .factors_to(unit)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(unit, factor)| (unit.to_string(), factor))
+                    .collect();
+                let conversion = SortConversion {
+                    unit_field_id: fields_ids_map.id(&conversion.unit_attribute),
+                    factors,
+                };
+                (fields_ids_map.id(field), Some(conversion), Self::must_redact(index, rtxn, field)?)
+            }
+            None => {
+                (fields_ids_map.id(&field_name), None, Self::must_redact(index, rtxn, &field_name)?)
+            }
+        };
 
         Ok(Self {
             field_name,
             field_id,
+            conversion,
             is_ascending,
+            nulls_first,
             original_query: None,
             iter: None,
             must_redact,
         })
     }
 
+    /// Returns the documents having a value the field can be sorted by.
+    fn faceted_documents_ids(
+        &self,
+        ctx: &SearchContext<'ctx>,
+        field_id: FieldId,
+    ) -> Result<RoaringBitmap> {
+        let number_db =
+            ctx.index.facet_id_f64_docids.remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>();
+        let mut docids = get_faceted_documents_ids(ctx.txn, number_db, field_id)?;
+        // the converted values are only sorted by their numbers
+        if self.conversion.is_none() {
+            let string_db = ctx
+                .index
+                .facet_id_string_docids
+                .remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>();
+            docids |= get_faceted_documents_ids(ctx.txn, string_db, field_id)?;
+        }
+        Ok(docids)
+    }
+
+    fn missing_value_score(&self) -> ScoreDetails {
+        ScoreDetails::Sort(score_details::Sort {
+            field_name: self.field_name.clone(),
+            ascending: self.is_ascending,
+            redacted: self.must_redact,
+            value: serde_json::Value::Null,
+        })
+    }
+
     fn must_redact(index: &Index, rtxn: &'ctx heed::RoTxn, field_name: &str) -> Result<bool> {
         let Some(displayed_fields) = index.displayed_fields(rtxn)? else {
             return Ok(false);
         };
 
-        Ok(!displayed_fields.iter().any(|&field| field == field_name))
+        Ok(!displayed_fields.iter().any(|pattern| crate::match_pattern(pattern, field_name)))
     }
 }
 
 impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for Sort<'ctx, Query> {
     fn id(&self) -> String {
-        let Self { field_name, is_ascending, .. } = self;
-        format!("{field_name}:{}", if *is_ascending { "asc" } else { "desc" })
+        let Self { field_name, is_ascending, nulls_first, .. } = self;
+        let nulls = if *nulls_first { ":nulls_first" } else { "" };
+        format!("{field_name}:{}{nulls}", if *is_ascending { "asc" } else { "desc" })
     }
     fn start_iteration(
         &mut self,
@@ -95,8 +164,41 @@ impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for Sort<'ctx,
         parent_candidates: &RoaringBitmap,
         parent_query: &Query,
     ) -> Result<()> {
-        let iter: RankingRuleOutputIterWrapper<Query> = match self.field_id {
-            Some(field_id) => {
+        let iter: RankingRuleOutputIterWrapper<Query> = match (self.field_id, &self.conversion) {
+            (Some(field_id), Some(conversion)) => {
+                let values = converted_facet_sort(
+                    ctx,
+                    field_id,
+                    conversion,
+                    self.is_ascending,
+                    parent_candidates,
+                )?
+                .map(|r| -> Result<_> {
+                    let (docids, value) = r?;
+                    let value = serde_json::Number::from_f64(value)
+                        .map_or(serde_json::Value::Null, serde_json::Value::Number);
+                    Ok((docids, value))
+                });
+
+                let query_graph = parent_query.clone();
+                let ascending = self.is_ascending;
+                let field_name = self.field_name.clone();
+                let must_redact = self.must_redact;
+                RankingRuleOutputIterWrapper::new(Box::new(values.map(move |r| {
+                    let (docids, value) = r?;
+                    Ok(RankingRuleOutput {
+                        query: query_graph.clone(),
+                        candidates: docids,
+                        score: ScoreDetails::Sort(score_details::Sort {
+                            field_name: field_name.clone(),
+                            ascending,
+                            redacted: must_redact,
+                            value,
+                        }),
+                    })
+                })))
+            }
+            (Some(field_id), None) => {
                 let number_db = ctx
                     .index
                     .facet_id_f64_docids
@@ -179,7 +281,21 @@ impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for Sort<'ctx,
                     },
                 )))
             }
-            None => RankingRuleOutputIterWrapper::new(Box::new(std::iter::empty())),
+            (None, _) => RankingRuleOutputIterWrapper::new(Box::new(std::iter::empty())),
+        };
+        let iter = match self.field_id {
+            Some(field_id) if self.nulls_first => {
+                let missing = parent_candidates - self.faceted_documents_ids(ctx, field_id)?;
+                let bucket = RankingRuleOutput {
+                    query: parent_query.clone(),
+                    candidates: missing,
+                    score: self.missing_value_score(),
+                };
+                RankingRuleOutputIterWrapper::new(Box::new(
+                    std::iter::once(Ok(bucket)).chain(iter.iter),
+                ))
+            }
+            _ => iter,
         };
         self.original_query = Some(parent_query.clone());
         self.iter = Some(iter);
@@ -201,12 +317,7 @@ impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for Sort<'ctx,
             Ok(Some(RankingRuleOutput {
                 query,
                 candidates: universe.clone(),
-                score: ScoreDetails::Sort(score_details::Sort {
-                    field_name: self.field_name.clone(),
-                    ascending: self.is_ascending,
-                    redacted: self.must_redact,
-                    value: serde_json::Value::Null,
-                }),
+                score: self.missing_value_score(),
             }))
         }
     }
@@ -220,3 +331,102 @@ impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for Sort<'ctx,
         self.iter = None;
     }
 }
+
+type FacetValuesIter<'ctx> = Box<dyn Iterator<Item = Result<(RoaringBitmap, f64)>> + 'ctx>;
+
+/// Sorts the candidates by the values of a numeric field converted into the same unit.
+///
+/// The conversion keeps the values expressed in a unit ordered, so the documents of each unit are
+/// sorted separately and their buckets are merged. The documents without a known unit come last.
+fn converted_facet_sort<'ctx>(
+    ctx: &SearchContext<'ctx>,
+    field_id: FieldId,
+    conversion: &SortConversion,
+    is_ascending: bool,
+    candidates: &RoaringBitmap,
+) -> Result<MergedFacetValues<'ctx>> {
+    let mut iters = Vec::new();
+    let Some(unit_field_id) = conversion.unit_field_id else {
+        return Ok(MergedFacetValues { iters, is_ascending });
+    };
+
+    let number_db =
+        ctx.index.facet_id_f64_docids.remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>();
+    for (unit, factor) in &conversion.factors {
+        let unit_docids = ctx
+            .index
+            .facet_id_string_docids
+            .get(
+                ctx.txn,
+                &FacetGroupKey {
+                    field_id: unit_field_id,
+                    level: 0,
+                    left_bound: &normalize_facet(unit),
+                },
+            )?
+            .map(|v| v.bitmap)
+            .unwrap_or_default()
+            & candidates;
+        if unit_docids.is_empty() {
+            continue;
+        }
+
+        let factor = *factor;
+        let iter: FacetValuesIter<'ctx> = if is_ascending {
+            Box::new(ascending_facet_sort(ctx.txn, number_db, field_id, unit_docids)?.map(
+                move |r| {
+                    let (docids, bytes) = r?;
+                    Ok((
+                        docids,
+                        OrderedF64Codec::bytes_decode(bytes).expect("some number") * factor,
+                    ))
+                },
+            ))
+        } else {
+            Box::new(descending_facet_sort(ctx.txn, number_db, field_id, unit_docids)?.map(
+                move |r| {
+                    let (docids, bytes) = r?;
+                    Ok((
+                        docids,
+                        OrderedF64Codec::bytes_decode(bytes).expect("some number") * factor,
+                    ))
+                },
+            ))
+        };
+        iters.push(iter.peekable());
+    }
+
+    Ok(MergedFacetValues { iters, is_ascending })
+}
+
+/// Merges the buckets of facet values returned in the same order by several iterators.
+struct MergedFacetValues<'ctx> {
+    iters: Vec<Peekable<FacetValuesIter<'ctx>>>,
+    is_ascending: bool,
+}
+
+impl<'ctx> Iterator for MergedFacetValues<'ctx> {
+    type Item = Result<(RoaringBitmap, f64)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut next: Option<(usize, f64)> = None;
+        for (i, iter) in self.iters.iter_mut().enumerate() {
+            match iter.peek() {
+                Some(Ok((_, value))) => {
+                    let is_next = match next {
+                        Some((_, next_value)) if self.is_ascending => *value < next_value,
+                        Some((_, next_value)) => *value > next_value,
+                        None => true,
+                    };
+                    if is_next {
+                        next = Some((i, *value));
+                    }
+                }
+                Some(Err(_)) => return iter.next(),
+                None => (),
+            }
+        }
+        let (i, _) = next?;
+        self.iters[i].next()
+    }
+}
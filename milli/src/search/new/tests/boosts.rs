@@ -0,0 +1,101 @@
+/*!
+This module tests the query-time boosts:
+1. The boosts multiply the ranking score of the documents matching their filter
+2. The documents are ranked by their boosted score
+3. The factors of the boosts matched by a document are multiplied together
+4. The offset and limit are applied after the documents are reordered
+5. The boosts also rank the documents of a placeholder search
+*/
+
+use maplit::hashset;
+use meili_snap::insta;
+
+use crate::index::tests::TempIndex;
+use crate::score_details::ScoreDetails;
+use crate::{Criterion, Filter, ScoreBoost, Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_filterable_fields(hashset! { "id".to_owned(), "sponsored".to_owned() });
+            s.set_criteria(vec![Criterion::Words]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "the quick brown fox", "sponsored": false },
+            { "id": 1, "text": "the quick brown", "sponsored": false },
+            { "id": 2, "text": "the quick", "sponsored": true },
+        ]))
+        .unwrap();
+    index
+}
+
+fn boost(filter: &str, factor: f64) -> ScoreBoost {
+    ScoreBoost { filter: Filter::from_str(filter).unwrap().unwrap(), factor }
+}
+
+fn search(index: &TempIndex, query: Option<&str>, boosts: Vec<ScoreBoost>) -> (Vec<u32>, Vec<f64>) {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    if let Some(query) = query {
+        s.query(query);
+    }
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.boosts(boosts);
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+    let scores = document_scores.iter().map(|s| ScoreDetails::global_score(s.iter())).collect();
+    (documents_ids, scores)
+}
+
+#[test]
+fn test_boosts_rank_by_boosted_score() {
+    let index = create_index();
+
+    let (documents_ids, _) = search(&index, Some("quick brown fox"), vec![]);
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2]");
+
+    let boosts = vec![boost("sponsored = true", 2.5)];
+    let (documents_ids, scores) = search(&index, Some("quick brown fox"), boosts);
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 2, 1]");
+    assert!((scores[1] - 2.5 / 3.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn test_boosts_factors_are_multiplied() {
+    let index = create_index();
+
+    let boosts = vec![boost("sponsored = true", 2.0), boost("id = 2", 2.0), boost("id = 1", 0.5)];
+    let (documents_ids, scores) = search(&index, Some("quick brown fox"), boosts);
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2, 0, 1]");
+    assert_eq!(scores, vec![4.0 / 3.0, 1.0, 1.0 / 3.0]);
+}
+
+#[test]
+fn test_boosts_pagination() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("quick brown fox");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.boosts(vec![boost("sponsored = true", 2.5)]);
+    s.offset(1);
+    s.limit(1);
+    let SearchResult { documents_ids, candidates, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2]");
+    assert_eq!(candidates.len(), 3);
+}
+
+#[test]
+fn test_boosts_placeholder_search() {
+    let index = create_index();
+
+    let (documents_ids, _) = search(&index, None, vec![boost("sponsored = true", 1.5)]);
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2, 0, 1]");
+}
@@ -0,0 +1,69 @@
+/*!
+This module tests the `time_budget` of the search: once the budget is exceeded, the ranking rules
+stop sorting the documents and the remaining buckets are returned as they are.
+*/
+
+use std::time::Duration;
+
+use crate::index::tests::TempIndex;
+use crate::{Criterion, Search, SearchResult, TermsMatchingStrategy, TimeBudget};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_criteria(vec![Criterion::Words, Criterion::Typo]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "hallo" },
+            { "id": 1, "text": "hello world" },
+            { "id": 2, "text": "hello" },
+            { "id": 3, "text": "world" },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn unlimited_budget() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.query("hello world");
+    let SearchResult { documents_ids, degraded, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 2, 0]");
+    assert!(!degraded);
+}
+
+#[test]
+fn exceeded_budget() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.query("hello world");
+    s.time_budget(TimeBudget::new(Duration::from_millis(0)));
+    let SearchResult { documents_ids, candidates, degraded, .. } = s.execute().unwrap();
+    // The documents are not sorted but all of them are still returned.
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2]");
+    insta::assert_snapshot!(format!("{candidates:?}"), @"RoaringBitmap<[0, 1, 2]>");
+    assert!(degraded);
+
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.query("hello world");
+    s.time_budget(TimeBudget::new(Duration::from_millis(0)));
+    s.limit(2);
+    let SearchResult { documents_ids, degraded, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1]");
+    assert!(degraded);
+}
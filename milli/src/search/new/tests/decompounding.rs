@@ -0,0 +1,83 @@
+/*!
+This module tests the following properties about decompounding:
+- the compound words are indexed with the words they are made of
+- the words of a compound word can be linked by a linking morpheme
+- the compound words of the query match the documents containing their words next to each other
+- the words of the other languages are not split
+*/
+
+use charabia::Language;
+use maplit::{btreemap, btreeset};
+
+use crate::index::tests::TempIndex;
+use crate::localized_attributes_rules::LocalizedAttributesRule;
+use crate::{Search, SearchResult};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_localized_attributes_rules(vec![
+                LocalizedAttributesRule {
+                    attribute_patterns: vec!["*_de".to_owned()],
+                    locales: vec![Language::Deu],
+                },
+                LocalizedAttributesRule {
+                    attribute_patterns: vec!["*_en".to_owned()],
+                    locales: vec![Language::Eng],
+                },
+            ]);
+            s.set_decompounding(btreemap! {
+                "deu".to_owned() => btreeset! {
+                    "haftpflicht".to_owned(),
+                    "schutz".to_owned(),
+                    "versicherung".to_owned(),
+                },
+            });
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title_de": "Die Haftpflichtversicherung" },
+            { "id": 1, "title_de": "Der Versicherungsschutz" },
+            { "id": 2, "title_de": "Die Versicherung Schutz" },
+            { "id": 3, "title_en": "Haftpflichtversicherung" },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_compound_words_are_split() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let docids = index.word_docids.get(&txn, "versicherung").unwrap().unwrap();
+    insta::assert_snapshot!(format!("{docids:?}"), @"RoaringBitmap<[0, 1, 2]>");
+    let docids = index.word_docids.get(&txn, "haftpflicht").unwrap().unwrap();
+    insta::assert_snapshot!(format!("{docids:?}"), @"RoaringBitmap<[0]>");
+    // the compound word itself is still indexed
+    let docids = index.word_docids.get(&txn, "haftpflichtversicherung").unwrap().unwrap();
+    insta::assert_snapshot!(format!("{docids:?}"), @"RoaringBitmap<[0, 3]>");
+}
+
+#[test]
+fn test_query_compound_words_are_split() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("Versicherung");
+    let SearchResult { mut documents_ids, .. } = s.execute().unwrap();
+    documents_ids.sort_unstable();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("Versicherungsschutz");
+    let SearchResult { mut documents_ids, .. } = s.execute().unwrap();
+    documents_ids.sort_unstable();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 2]");
+}
@@ -0,0 +1,101 @@
+/*!
+This module tests the grouping of the results:
+1. The results are collapsed by the values of the grouping attribute, like with a distinct attribute
+2. Each returned document comes with the best documents of its group, itself included
+3. The documents without a value for the attribute are groups on their own
+4. The offset and limit are applied to the groups, and the number of groups is exhaustive
+5. The documents of a group must match the filter of the search
+6. The grouping attribute must be filterable
+*/
+
+use maplit::hashset;
+use meili_snap::insta;
+
+use crate::index::tests::TempIndex;
+use crate::{
+    Criterion, Error, Filter, GroupBy, Search, SearchResult, TermsMatchingStrategy, UserError,
+};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_filterable_fields(hashset! { "id".to_owned(), "family".to_owned() });
+            s.set_criteria(vec![Criterion::Words]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "the quick brown fox", "family": "a" },
+            { "id": 1, "text": "the quick brown fox", "family": "b" },
+            { "id": 2, "text": "the quick brown", "family": "a" },
+            { "id": 3, "text": "the quick" },
+            { "id": 4, "text": "the quick brown", "family": "b" },
+            { "id": 5, "text": "the quick", "family": "a" },
+            { "id": 6, "text": "the quick", "family": "c" },
+        ]))
+        .unwrap();
+    index
+}
+
+fn search(index: &TempIndex, build: impl FnOnce(&mut Search)) -> String {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.query("quick brown fox");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.group_by(GroupBy { field: "family".to_owned(), limit: 2 });
+    build(&mut s);
+    let SearchResult { candidates, documents_ids, groups, .. } = s.execute().unwrap();
+
+    let groups: Vec<_> = groups
+        .iter()
+        .map(|group| format!("{:?} / {}", group.documents_ids, group.total_hits))
+        .collect();
+    format!("{} groups {documents_ids:?}: {groups:?}", candidates.len())
+}
+
+#[test]
+fn test_grouping() {
+    let index = create_index();
+
+    insta::assert_snapshot!(search(&index, |_| ()), @r###"4 groups [0, 1, 3, 6]: ["[0, 2] / 3", "[1, 4] / 2", "[3] / 1", "[6] / 1"]"###);
+}
+
+#[test]
+fn test_grouping_pagination() {
+    let index = create_index();
+
+    let result = search(&index, |s| {
+        s.offset(1);
+        s.limit(2);
+    });
+    insta::assert_snapshot!(result, @r###"4 groups [1, 3]: ["[1, 4] / 2", "[3] / 1"]"###);
+}
+
+#[test]
+fn test_grouping_filtered_groups() {
+    let index = create_index();
+
+    let result = search(&index, |s| {
+        s.filter(Filter::from_str("id != 0 AND id != 4").unwrap().unwrap());
+    });
+    insta::assert_snapshot!(result, @r###"4 groups [1, 2, 3, 6]: ["[1] / 1", "[2, 5] / 2", "[3] / 1", "[6] / 1"]"###);
+}
+
+#[test]
+fn test_grouping_attribute_must_be_filterable() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.group_by(GroupBy { field: "text".to_owned(), limit: 2 });
+    let error = s.execute().unwrap_err();
+    assert!(
+        matches!(&error, Error::UserError(UserError::InvalidGroupByAttribute { field, .. }) if field == "text"),
+        "{error}"
+    );
+}
@@ -1,15 +1,25 @@
 pub mod attribute_fid;
 pub mod attribute_position;
+pub mod boosts;
+pub mod cutoff;
+pub mod decompounding;
 pub mod distinct;
 pub mod exactness;
 pub mod geo_sort;
+pub mod grouping;
 pub mod integration;
 #[cfg(feature = "all-tokenizations")]
 pub mod language;
 pub mod ngram_split_words;
+#[cfg(feature = "plugins")]
+pub mod plugin;
+pub mod prefix_search;
 pub mod proximity;
 pub mod proximity_typo;
+pub mod random_sort;
+pub mod recency;
 pub mod sort;
+pub mod stemming;
 pub mod stop_words;
 pub mod typo;
 pub mod typo_proximity;
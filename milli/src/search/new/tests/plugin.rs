@@ -0,0 +1,115 @@
+/*!
+This module tests the ranking rules and filters registered as plugins:
+1. a ranking rule plugin sorts the documents with its buckets, the documents outside
+of any bucket are returned last
+2. a filter plugin can be combined with the other filters
+3. the unknown plugins are rejected
+*/
+
+use heed::RoTxn;
+use roaring::RoaringBitmap;
+
+use crate::index::tests::TempIndex;
+use crate::plugins::{register_filter, register_ranking_rule, FilterPlugin, RankingRulePlugin};
+use crate::{Criterion, Filter, Index, Result, Search, SearchResult};
+
+struct OddFirst;
+
+impl RankingRulePlugin for OddFirst {
+    fn name(&self) -> &str {
+        "odd_first"
+    }
+
+    fn buckets(
+        &self,
+        _index: &Index,
+        _rtxn: &RoTxn,
+        universe: &RoaringBitmap,
+    ) -> Result<Vec<RoaringBitmap>> {
+        Ok(vec![universe.iter().filter(|docid| docid % 2 == 1).collect()])
+    }
+}
+
+struct MultipleOf;
+
+impl FilterPlugin for MultipleOf {
+    fn name(&self) -> &str {
+        "multiple_of"
+    }
+
+    fn evaluate(&self, index: &Index, rtxn: &RoTxn, argument: &str) -> Result<RoaringBitmap> {
+        let divisor: u32 = argument.parse().unwrap();
+        Ok(index.documents_ids(rtxn)?.iter().filter(|docid| docid % divisor == 0).collect())
+    }
+}
+
+fn create_index() -> TempIndex {
+    register_ranking_rule(OddFirst);
+    register_filter(MultipleOf);
+
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_filterable_fields(["id".to_owned()].into());
+            s.set_criteria(vec![Criterion::Plugin("odd_first".to_owned()), Criterion::Sort]);
+            s.set_sortable_fields(["id".to_owned()].into());
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0 },
+            { "id": 1 },
+            { "id": 2 },
+            { "id": 3 },
+            { "id": 4 },
+            { "id": 5 },
+            { "id": 6 },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn ranking_rule_plugin() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.sort_criteria(vec!["id:asc".parse().unwrap()]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 3, 5, 0, 2, 4, 6]");
+}
+
+#[test]
+fn filter_plugin() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.sort_criteria(vec!["id:asc".parse().unwrap()]);
+    s.filter(Filter::from_str("_plugin(multiple_of, 3) AND id > 0").unwrap().unwrap());
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[3, 6]");
+}
+
+#[test]
+fn unknown_plugins() {
+    let index = create_index();
+
+    let err = index
+        .update_settings(|s| s.set_criteria(vec![Criterion::Plugin("unknown".to_owned())]))
+        .unwrap_err();
+    insta::assert_snapshot!(err, @"`unknown` ranking rule plugin is not registered.");
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.filter(Filter::from_str("_plugin(unknown, 3)").unwrap().unwrap());
+    let err = s.execute().unwrap_err();
+    insta::assert_snapshot!(err, @r###"
+    `unknown` filter plugin is not registered.
+    9:16 _plugin(unknown, 3)
+    "###);
+}
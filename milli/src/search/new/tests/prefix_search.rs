@@ -0,0 +1,65 @@
+/*!
+This module tests the following properties about the prefix search setting:
+1. By default, the last word of the query matches the words it is a prefix of
+2. When the prefix search is disabled, the last word of the query only matches itself
+3. When the prefix search is disabled, the words prefixes databases are emptied
+*/
+
+use crate::index::tests::TempIndex;
+use crate::{PrefixSearch, Search, SearchResult};
+
+fn create_index() -> TempIndex {
+    let mut index = TempIndex::new();
+    // the prefixes shared by at least 50 words are stored in the words prefixes databases.
+    index.index_documents_config.words_prefix_threshold = Some(50);
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_autorize_typos(false);
+        })
+        .unwrap();
+
+    let mut documents: Vec<_> =
+        (0..60).map(|id| serde_json::json!({ "id": id, "text": format!("hello{id}") })).collect();
+    documents.push(serde_json::json!({ "id": 60, "text": "the isbn 9780134685991" }));
+    documents.push(serde_json::json!({ "id": 61, "text": "the isbn 9780134685" }));
+    index.add_documents(documents!(documents)).unwrap();
+    index
+}
+
+fn search(index: &TempIndex, query: &str) -> Vec<u32> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.query(query);
+    let SearchResult { mut documents_ids, .. } = s.execute().unwrap();
+    documents_ids.sort_unstable();
+    documents_ids
+}
+
+#[test]
+fn test_prefix_search_enabled_by_default() {
+    let index = create_index();
+
+    insta::assert_snapshot!(format!("{:?}", search(&index, "9780134685")), @"[60, 61]");
+    assert_eq!(search(&index, "hel").len(), 60);
+
+    let txn = index.read_txn().unwrap();
+    assert!(!index.words_prefixes_fst(&txn).unwrap().is_empty());
+    assert!(!index.word_prefix_docids.is_empty(&txn).unwrap());
+}
+
+#[test]
+fn test_prefix_search_disabled() {
+    let index = create_index();
+    index.update_settings(|s| s.set_prefix_search(PrefixSearch::Disabled)).unwrap();
+
+    insta::assert_snapshot!(format!("{:?}", search(&index, "9780134685")), @"[61]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "hel")), @"[]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "hello7")), @"[7]");
+
+    let txn = index.read_txn().unwrap();
+    assert!(index.words_prefixes_fst(&txn).unwrap().is_empty());
+    assert!(index.word_prefix_docids.is_empty(&txn).unwrap());
+    assert!(index.exact_word_prefix_docids.is_empty(&txn).unwrap());
+}
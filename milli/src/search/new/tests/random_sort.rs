@@ -0,0 +1,111 @@
+/*!
+This module tests the `_random(seed)` sort:
+1. all the documents are returned, in an order that only depends on the seed
+2. the order is stable across the pages of the results
+3. the documents keep their relative order when the candidates are filtered
+4. the random order only breaks the ties of the previous ranking rules
+*/
+
+use maplit::hashset;
+
+use crate::index::tests::TempIndex;
+use crate::{AscDesc, Criterion, Filter, Member, Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_filterable_fields(hashset! { "id".to_owned() });
+            s.set_criteria(vec![Criterion::Words, Criterion::Sort]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "the quick brown fox" },
+            { "id": 1, "text": "the quick brown fox" },
+            { "id": 2, "text": "the quick brown fox" },
+            { "id": 3, "text": "the quick brown fox" },
+            { "id": 4, "text": "the quick brown fox" },
+            { "id": 5, "text": "the quick" },
+            { "id": 6, "text": "the quick" },
+            { "id": 7, "text": "the quick" },
+            { "id": 8, "text": "the quick" },
+            { "id": 9, "text": "the quick" },
+        ]))
+        .unwrap();
+    index
+}
+
+fn search(index: &TempIndex, sort: &str, build: impl FnOnce(&mut Search)) -> Vec<u32> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.sort_criteria(vec![sort.parse().unwrap()]);
+    build(&mut s);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    documents_ids
+}
+
+#[test]
+fn test_random_sort_depends_on_the_seed() {
+    let index = create_index();
+
+    let documents_ids = search(&index, "_random(42)", |_| ());
+    let mut sorted = documents_ids.clone();
+    sorted.sort_unstable();
+    assert_eq!(sorted, (0..10).collect::<Vec<_>>());
+    assert_ne!(documents_ids, sorted);
+
+    assert_eq!(search(&index, "_random(42)", |_| ()), documents_ids);
+    assert_ne!(search(&index, "_random(43)", |_| ()), documents_ids);
+
+    let mut reversed = search(&index, "_random(42):desc", |_| ());
+    reversed.reverse();
+    assert_eq!(reversed, documents_ids);
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.sort_criteria(vec![AscDesc::Asc(Member::Random(42))]);
+    let SearchResult { documents_ids: ids, .. } = s.execute().unwrap();
+    assert_eq!(ids, documents_ids);
+}
+
+#[test]
+fn test_random_sort_pagination() {
+    let index = create_index();
+
+    let documents_ids = search(&index, "_random(7)", |_| ());
+    let page = search(&index, "_random(7)", |s| {
+        s.offset(3);
+        s.limit(4);
+    });
+    assert_eq!(page, documents_ids[3..7]);
+}
+
+#[test]
+fn test_random_sort_filtered_candidates() {
+    let index = create_index();
+
+    let documents_ids = search(&index, "_random(7)", |_| ());
+    let filtered = search(&index, "_random(7)", |s| {
+        s.filter(Filter::from_str("id != 3 AND id != 8").unwrap().unwrap());
+    });
+    let expected: Vec<_> = documents_ids.into_iter().filter(|id| ![3, 8].contains(id)).collect();
+    assert_eq!(filtered, expected);
+}
+
+#[test]
+fn test_random_sort_after_words() {
+    let index = create_index();
+
+    let documents_ids = search(&index, "_random(7)", |s| {
+        s.query("quick brown fox");
+    });
+    let (best, others) = documents_ids.split_at(5);
+    assert!(best.iter().all(|id| *id < 5), "{documents_ids:?}");
+    assert!(others.iter().all(|id| *id >= 5), "{documents_ids:?}");
+}
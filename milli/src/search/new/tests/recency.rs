@@ -0,0 +1,76 @@
+/*!
+This module tests the `recency` ranking rule:
+1. The documents with the most recent timestamps are ranked first
+2. The documents of similar ages are in the same bucket, they are ranked by the following ranking rules
+3. The documents without a timestamp are ranked last
+4. The field of the ranking rule doesn't need to be sortable nor filterable
+5. The score of a document decays with its age
+*/
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::index::tests::TempIndex;
+use crate::score_details::{ScoreDetails, ScoringStrategy};
+use crate::{Criterion, Search, SearchResult, TermsMatchingStrategy};
+
+const DAY: u64 = 86_400;
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_criteria(vec![
+                Criterion::Recency { field: "published_at".to_owned(), half_life: 30 * DAY },
+                Criterion::Words,
+            ]);
+        })
+        .unwrap();
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "the quick brown fox", "published_at": now - 90 * DAY },
+            { "id": 1, "text": "the quick brown fox", "published_at": now - 2 * DAY },
+            { "id": 2, "text": "the quick brown fox" },
+            { "id": 3, "text": "the quick brown fox", "published_at": now - 20 * DAY },
+            { "id": 4, "text": "the quick", "published_at": now - DAY },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_recency_placeholder_search() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 4, 3, 0, 2]");
+
+    let scores: Vec<_> =
+        document_scores.iter().map(|s| ScoreDetails::global_score(s.iter())).collect();
+    // 1 and 4 are in the newest bucket, the decay of 3 is about 0.63 and the one of 0 is 0.125
+    assert_eq!(scores[0], 1.0);
+    assert_eq!(scores[1], 1.0);
+    assert!(scores[2] > 0.6 && scores[2] < 0.8, "{scores:?}");
+    assert!(scores[3] > 0.1 && scores[3] < 0.3, "{scores:?}");
+    assert_eq!(scores[4], 1.0 / 11.0);
+}
+
+#[test]
+fn test_recency_ties_are_ranked_by_following_rules() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    // 4 is more recent than 1, but they are of similar ages and 1 matches more words
+    let mut s = Search::new(&txn, &index);
+    s.query("quick brown fox");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 4, 3, 0, 2]");
+}
@@ -9,6 +9,7 @@ This module tests the `sort` ranking rule:
 6. documents with either: (1) no value, (2) null, or (3) an object for the field-to-sort appear at the end of the bucket
 7. boolean values are translated to strings
 8. if a field contains an array, it is sorted by the best value in the array according to the sort rule
+9. the documents without a value for the field-to-sort appear at the start of the bucket if the field is sorted with `nulls_first`
 */
 
 use big_s::S;
@@ -325,6 +326,48 @@ fn test_sort() {
     "###);
 }
 
+#[test]
+fn test_sort_nulls_first() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.sort_criteria(vec![AscDesc::AscNullsFirst(Member::Field(S("vague")))]);
+
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[6, 7, 8, 9, 10, 14, 15, 16, 17, 18, 19, 0, 2, 4, 5, 22, 23, 13, 1, 3]");
+
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.sort_criteria(vec!["vague:desc:nulls_first".parse().unwrap()]);
+    s.offset(9);
+    s.limit(4);
+
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[18, 19, 4, 13]");
+
+    // the documents without a value are still sorted by the following fields-to-sort
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.sort_criteria(vec![
+        AscDesc::AscNullsFirst(Member::Field(S("vague"))),
+        AscDesc::Desc(Member::Field(S("rank"))),
+    ]);
+    s.limit(4);
+
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    let rank_values = collect_field_values(&index, &txn, "rank", &documents_ids);
+    insta::assert_debug_snapshot!(rank_values, @r###"
+    [
+        "5",
+        "2",
+        "2",
+        "1",
+    ]
+    "###);
+}
+
 #[test]
 fn test_redacted() {
     let index = create_index();
@@ -0,0 +1,74 @@
+/*!
+This module tests the following properties about stemming:
+- the words of the languages the stemming is enabled for are indexed by their stem
+- the words of the query are stemmed the same way
+- the words of the other languages are not stemmed
+*/
+
+use std::collections::BTreeSet;
+use std::iter::FromIterator;
+
+use charabia::Language;
+
+use crate::index::tests::TempIndex;
+use crate::localized_attributes_rules::LocalizedAttributesRule;
+use crate::{Search, SearchResult};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_localized_attributes_rules(vec![
+                LocalizedAttributesRule {
+                    attribute_patterns: vec!["*_en".to_owned()],
+                    locales: vec![Language::Eng],
+                },
+                LocalizedAttributesRule {
+                    attribute_patterns: vec!["*_de".to_owned()],
+                    locales: vec![Language::Deu],
+                },
+            ]);
+            s.set_stemming(BTreeSet::from_iter(["eng".to_owned()]));
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title_en": "The cat runs" },
+            { "id": 1, "title_en": "Running with the wolves" },
+            { "id": 2, "title_en": "The rune stone" },
+            { "id": 3, "title_de": "Die Katzen" },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_stemmed_words_are_indexed() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let run_docids = index.word_docids.get(&txn, "run").unwrap().unwrap();
+    insta::assert_snapshot!(format!("{run_docids:?}"), @"RoaringBitmap<[0, 1]>");
+    let running_docids = index.word_docids.get(&txn, "running").unwrap();
+    assert!(running_docids.is_none());
+
+    // the stemming is not enabled for German
+    let katzen_docids = index.word_docids.get(&txn, "katzen").unwrap().unwrap();
+    insta::assert_snapshot!(format!("{katzen_docids:?}"), @"RoaringBitmap<[3]>");
+}
+
+#[test]
+fn test_query_words_are_stemmed() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("running ");
+    s.locales(vec![Language::Eng]);
+    let SearchResult { mut documents_ids, .. } = s.execute().unwrap();
+    documents_ids.sort_unstable();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1]");
+}
@@ -7,12 +7,16 @@ This module tests the following properties about stop words:
 - If a query consists only of stop words, a placeholder query is used instead
 - A prefix word is never ignored, even if the prefix is a stop word
 - Phrases consisting only of stop words are ignored
+- Localized stop words are only ignored in the text of their language
 */
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::iter::FromIterator;
 
+use charabia::Language;
+
 use crate::index::tests::TempIndex;
+use crate::localized_attributes_rules::LocalizedAttributesRule;
 use crate::{db_snap, Search, SearchResult, TermsMatchingStrategy};
 
 fn create_index() -> TempIndex {
@@ -458,3 +462,54 @@ fn test_stop_words_in_phrase() {
     // As a result the scores are empty lists
     insta::assert_snapshot!(format!("{document_scores:#?}"));
 }
+
+#[test]
+fn test_localized_stop_words() {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_localized_attributes_rules(vec![
+                LocalizedAttributesRule {
+                    attribute_patterns: vec!["*_de".to_owned()],
+                    locales: vec![Language::Deu],
+                },
+                LocalizedAttributesRule {
+                    attribute_patterns: vec!["*_en".to_owned()],
+                    locales: vec![Language::Eng],
+                },
+            ]);
+            s.set_localized_stop_words(BTreeMap::from_iter([(
+                "deu".to_owned(),
+                BTreeSet::from_iter(["Die".to_owned()]),
+            )]));
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title_en": "Die Hard" },
+            { "id": 1, "title_de": "Die Brücke" },
+        ]))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+
+    // `die` is only a stop word in German
+    let die_docids = index.word_docids.get(&txn, "die").unwrap().unwrap();
+    insta::assert_snapshot!(format!("{die_docids:?}"), @"RoaringBitmap<[0]>");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("die ");
+    s.locales(vec![Language::Eng]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0]");
+
+    // The query only contains stop words, a placeholder search is used instead
+    let mut s = Search::new(&txn, &index);
+    s.query("die ");
+    s.locales(vec![Language::Deu]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1]");
+}
@@ -16,6 +16,7 @@ if `words` doesn't exist before it.
 12. Prefix tolerance costs nothing according to the typo ranking rule
 13. Split words cost 1 typo according to the typo ranking rule
 14. Synonyms cost nothing according to the typo ranking rule
+15. Words containing a digit are not typo tolerant when the typos are disabled on numbers
 */
 
 use std::collections::BTreeMap;
@@ -635,3 +636,43 @@ fn test_typo_synonyms() {
     ]
     "###);
 }
+
+#[test]
+fn test_typo_disabled_on_numbers() {
+    let index = TempIndex::new();
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_min_word_len_one_typo(4);
+        })
+        .unwrap();
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "part A1023" },
+            { "id": 1, "text": "part A1024" },
+            { "id": 2, "text": "model 20145" },
+            { "id": 3, "text": "the parts" },
+        ]))
+        .unwrap();
+
+    let search = |query: &str| {
+        let txn = index.read_txn().unwrap();
+        let mut s = Search::new(&txn, &index);
+        s.terms_matching_strategy(TermsMatchingStrategy::All);
+        s.query(query);
+        let SearchResult { mut documents_ids, .. } = s.execute().unwrap();
+        documents_ids.sort_unstable();
+        documents_ids
+    };
+
+    insta::assert_snapshot!(format!("{:?}", search("A1023")), @"[0, 1]");
+    insta::assert_snapshot!(format!("{:?}", search("20146")), @"[2]");
+
+    index.update_settings(|s| s.set_disable_typos_on_numbers(true)).unwrap();
+
+    insta::assert_snapshot!(format!("{:?}", search("A1023")), @"[0]");
+    insta::assert_snapshot!(format!("{:?}", search("20146")), @"[]");
+    // the other words are still typo tolerant
+    insta::assert_snapshot!(format!("{:?}", search("pert")), @"[0, 1, 3]");
+}
@@ -0,0 +1,179 @@
+use itertools::Either;
+use roaring::RoaringBitmap;
+
+use crate::error::UserError;
+use crate::heed_codec::facet::FacetGroupKeyCodec;
+use crate::heed_codec::BytesRefCodec;
+use crate::search::facet::{
+    ascending_facet_sort, descending_facet_sort, get_faceted_documents_ids,
+};
+use crate::{AscDesc, DocumentId, FieldId, Index, Member, Result, SortError};
+
+/// Returns at most `limit` ids of the candidates sorted by the given criteria.
+///
+/// Unlike a search, the ranking rules of the index are ignored: the documents are only sorted by
+/// the values of their sortable attributes, and the ones without a value for a criterion come
+/// after the ones that have one, unless the criterion puts them first.
+pub fn sort_documents(
+    index: &Index,
+    rtxn: &heed::RoTxn,
+    candidates: RoaringBitmap,
+    criteria: &[AscDesc],
+    limit: usize,
+) -> Result<Vec<DocumentId>> {
+    let sortable_fields = index.sortable_fields(rtxn)?;
+    let fields_ids_map = index.fields_ids_map(rtxn)?;
+
+    let mut fields = Vec::with_capacity(criteria.len());
+    for asc_desc in criteria {
+        let field = match asc_desc.member() {
+            Member::Field(field) => field,
+            Member::Geo(_) => {
+                let name = String::from("_geoPoint");
+                return Err(UserError::SortError(SortError::ReservedName { name }).into());
+            }
+            Member::Random(_) => {
+                let name = String::from("_random");
+                return Err(UserError::SortError(SortError::ReservedName { name }).into());
+            }
+        };
+        if !crate::is_faceted(field, &sortable_fields) {
+            let (valid_fields, hidden_fields) =
+                index.remove_hidden_fields(rtxn, sortable_fields)?;
+            return Err(UserError::InvalidSortableAttribute {
+                field: field.to_string(),
+                valid_fields,
+                hidden_fields,
+            }
+            .into());
+        }
+        // a field that no document contains doesn't change the order
+        fields.push(SortedField {
+            field_id: fields_ids_map.id(field),
+            ascending: asc_desc.is_ascending(),
+            nulls_first: asc_desc.nulls_first(),
+        });
+    }
+
+    let mut sorted = Vec::new();
+    sort_bucket(index, rtxn, candidates, &fields, limit, &mut sorted)?;
+    Ok(sorted)
+}
+
+struct SortedField {
+    field_id: Option<FieldId>,
+    ascending: bool,
+    nulls_first: bool,
+}
+
+/// Appends the ids of the bucket sorted by the fields to `sorted`, until it contains `limit` ids.
+fn sort_bucket(
+    index: &Index,
+    rtxn: &heed::RoTxn,
+    mut bucket: RoaringBitmap,
+    fields: &[SortedField],
+    limit: usize,
+    sorted: &mut Vec<DocumentId>,
+) -> Result<()> {
+    if sorted.len() >= limit || bucket.is_empty() {
+        return Ok(());
+    }
+
+    let (field, field_id, fields) = match fields.split_first() {
+        Some((field @ SortedField { field_id: Some(field_id), .. }, fields)) => {
+            (field, *field_id, fields)
+        }
+        Some((SortedField { field_id: None, .. }, fields)) => {
+            return sort_bucket(index, rtxn, bucket, fields, limit, sorted)
+        }
+        None => {
+            sorted.extend(bucket.into_iter().take(limit - sorted.len()));
+            return Ok(());
+        }
+    };
+
+    let number_db = index.facet_id_f64_docids.remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>();
+    let string_db =
+        index.facet_id_string_docids.remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>();
+
+    if field.nulls_first {
+        let faceted = get_faceted_documents_ids(rtxn, number_db, field_id)?
+            | get_faceted_documents_ids(rtxn, string_db, field_id)?;
+        let missing = &bucket - faceted;
+        bucket -= &missing;
+        sort_bucket(index, rtxn, missing, fields, limit, sorted)?;
+    }
+
+    // like the sort ranking rule, the numbers come before the strings
+    for db in [number_db, string_db] {
+        let values = if field.ascending {
+            Either::Left(ascending_facet_sort(rtxn, db, field_id, bucket.clone())?)
+        } else {
+            Either::Right(descending_facet_sort(rtxn, db, field_id, bucket.clone())?)
+        };
+        for result in values {
+            if sorted.len() >= limit {
+                return Ok(());
+            }
+            let (docids, _value) = result?;
+            // a document with several values is sorted by the first one
+            let docids = docids & &bucket;
+            bucket -= &docids;
+            sort_bucket(index, rtxn, docids, fields, limit, sorted)?;
+        }
+    }
+
+    // the documents without a value for the field come last, if they weren't already sorted
+    sort_bucket(index, rtxn, bucket, fields, limit, sorted)
+}
+
+#[cfg(test)]
+mod test {
+    use big_s::S;
+    use maplit::hashset;
+
+    use super::sort_documents;
+    use crate::index::tests::TempIndex;
+    use crate::AscDesc;
+
+    #[test]
+    fn sort_by_several_fields() {
+        let index = TempIndex::new();
+        index
+            .update_settings(|settings| {
+                settings.set_sortable_fields(hashset! { S("year"), S("title") })
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, "title": "b", "year": 2000 },
+                { "id": 1, "title": "a", "year": 2010 },
+                { "id": 2, "title": "c" },
+                { "id": 3, "title": "a", "year": 2000 },
+                { "id": 4, "title": "d", "year": "unknown" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let candidates = index.documents_ids(&rtxn).unwrap();
+        let criteria: Vec<AscDesc> = vec!["year:desc".parse().unwrap()];
+        let ids = sort_documents(&index, &rtxn, candidates.clone(), &criteria, 10).unwrap();
+        assert_eq!(ids, vec![1, 0, 3, 4, 2]);
+
+        let criteria: Vec<AscDesc> = vec!["year:desc:nulls_first".parse().unwrap()];
+        let ids = sort_documents(&index, &rtxn, candidates.clone(), &criteria, 10).unwrap();
+        assert_eq!(ids, vec![2, 1, 0, 3, 4]);
+
+        let criteria: Vec<AscDesc> =
+            vec!["year:asc".parse().unwrap(), "title:asc".parse().unwrap()];
+        let ids = sort_documents(&index, &rtxn, candidates.clone(), &criteria, 10).unwrap();
+        assert_eq!(ids, vec![3, 0, 1, 4, 2]);
+
+        let ids = sort_documents(&index, &rtxn, candidates.clone(), &criteria, 2).unwrap();
+        assert_eq!(ids, vec![3, 0]);
+
+        let criteria: Vec<AscDesc> = vec!["id:asc".parse().unwrap()];
+        let error = sort_documents(&index, &rtxn, candidates, &criteria, 10).unwrap_err();
+        assert!(error.to_string().starts_with("Attribute `id` is not sortable."), "{error}");
+    }
+}
@@ -0,0 +1,77 @@
+//! The reduction of the words to their stem.
+//!
+//! A stemmer reduces the inflected forms of a word to a common stem, e.g. `running` and `runs` to
+//! `run`, so that a query matches the documents using another form of its words without declaring
+//! them as synonyms. The stem depends on the language of the word, either detected by the
+//! tokenizer or declared by the locales, and only the words of the languages the stemming is
+//! enabled for are stemmed, the same way when indexing the documents and when parsing the query.
+//!
+//! The words are only indexed by their stem: the prefix of a word typed in the query matches the
+//! documents containing a stem starting with this prefix.
+
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashMap};
+
+use charabia::{Language, Token};
+use rust_stemmers::{Algorithm, Stemmer};
+
+/// Returns the stemming algorithm of the language, `None` if there is no stemmer for it.
+pub fn stemming_algorithm(language: Language) -> Option<Algorithm> {
+    let algorithm = match language {
+        Language::Ara => Algorithm::Arabic,
+        Language::Dan => Algorithm::Danish,
+        Language::Deu => Algorithm::German,
+        Language::Ell => Algorithm::Greek,
+        Language::Eng => Algorithm::English,
+        Language::Fin => Algorithm::Finnish,
+        Language::Fra => Algorithm::French,
+        Language::Hun => Algorithm::Hungarian,
+        Language::Ita => Algorithm::Italian,
+        Language::Nld => Algorithm::Dutch,
+        Language::Nob => Algorithm::Norwegian,
+        Language::Por => Algorithm::Portuguese,
+        Language::Ron => Algorithm::Romanian,
+        Language::Rus => Algorithm::Russian,
+        Language::Spa => Algorithm::Spanish,
+        Language::Swe => Algorithm::Swedish,
+        Language::Tam => Algorithm::Tamil,
+        Language::Tur => Algorithm::Turkish,
+        _ => return None,
+    };
+    Some(algorithm)
+}
+
+/// The stemmers of the languages the stemming is enabled for.
+#[derive(Default)]
+pub struct Stemmers {
+    language_to_stemmer: HashMap<Language, Stemmer>,
+}
+
+impl Stemmers {
+    /// Builds the stemmers of the languages given by their ISO 639-3 code, the languages without
+    /// stemmer are ignored.
+    pub fn new(languages: &BTreeSet<String>) -> Self {
+        let language_to_stemmer = languages
+            .iter()
+            .map(|name| Language::from_name(name))
+            .filter_map(|language| {
+                let algorithm = stemming_algorithm(language)?;
+                Some((language, Stemmer::create(algorithm)))
+            })
+            .collect();
+        Self { language_to_stemmer }
+    }
+
+    /// Replaces the lemma of the word by its stem when the stemming is enabled for its language.
+    pub fn stem<'o>(&self, mut token: Token<'o>) -> Token<'o> {
+        if token.is_word() {
+            let stemmer =
+                token.language.and_then(|language| self.language_to_stemmer.get(&language));
+            if let Some(stemmer) = stemmer {
+                let stem = stemmer.stem(token.lemma()).into_owned();
+                token.lemma = Cow::Owned(stem);
+            }
+        }
+        token
+    }
+}
@@ -0,0 +1,49 @@
+//! The conversion of the numeric values of a field expressed in different units.
+//!
+//! A field with a conversion, e.g. `price`, can be filtered and sorted in a given unit by suffixing
+//! its name with the unit: `price:usd < 100` or `price:usd:asc`. The values of every document are
+//! converted at query time according to the unit stored in another of its fields, e.g. `currency`.
+
+use std::collections::BTreeMap;
+
+use deserr::Deserr;
+use serde::{Deserialize, Serialize};
+
+use crate::normalize_facet;
+
+/// How to convert the values of a numeric field into a given unit.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct UnitConversion {
+    /// The field holding the unit the value of each document is expressed in.
+    pub unit_attribute: String,
+    /// The value of each unit in a common reference unit, e.g. the exchange rates of the
+    /// currencies against the dollar.
+    pub rates: BTreeMap<String, f64>,
+}
+
+// The settings only accept finite rates.
+impl Eq for UnitConversion {}
+
+impl UnitConversion {
+    /// Returns the units along with the factor converting the values expressed in each of them
+    /// into the target unit, `None` if the target unit is unknown.
+    ///
+    /// The units are compared like the facet values: case-insensitively.
+    pub fn factors_to(&self, target: &str) -> Option<Vec<(&str, f64)>> {
+        let target = normalize_facet(target);
+        let (_, target_rate) =
+            self.rates.iter().find(|(unit, _)| normalize_facet(unit) == target)?;
+        Some(self.rates.iter().map(|(unit, rate)| (unit.as_str(), rate / target_rate)).collect())
+    }
+}
+
+/// Splits a field name suffixed by a unit, e.g. `price:usd`, when there is a conversion for the field.
+pub fn split_converted_field<'a, 'c>(
+    name: &'a str,
+    conversions: &'c BTreeMap<String, UnitConversion>,
+) -> Option<(&'a str, &'a str, &'c UnitConversion)> {
+    let (field, unit) = name.rsplit_once(':')?;
+    conversions.get(field).map(|conversion| (field, unit, conversion))
+}
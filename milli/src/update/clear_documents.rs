@@ -51,6 +51,7 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
             vector_arroy,
             embedder_category_id: _,
             documents,
+            query_cache: _,
         } = self.index;
 
         let empty_roaring = RoaringBitmap::default();
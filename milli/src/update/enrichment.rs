@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+/// The default number of documents sent in a single request to the enrichment webhook.
+pub const DEFAULT_ENRICHMENT_BATCH_SIZE: usize = 100;
+
+/// An external service the documents added to an index are sent to before being indexed.
+///
+/// The webhook receives the documents as a JSON array and must answer with an array of the same
+/// length, the fields of its objects are merged into the corresponding documents.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct EnrichmentWebhook {
+    pub url: String,
+    #[serde(default = "default_batch_size")]
+    pub batch_size: usize,
+}
+
+fn default_batch_size() -> usize {
+    DEFAULT_ENRICHMENT_BATCH_SIZE
+}
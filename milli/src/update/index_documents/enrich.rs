@@ -110,7 +110,7 @@ pub fn enrich_documents_batch<R: Read + Seek>(
         let document_id = match fetch_or_generate_document_id(
             &document,
             &documents_batch_index,
-            primary_key,
+            &primary_key,
             autogenerate_docids,
             &mut uuid_buffer,
             count,
@@ -149,7 +149,7 @@ target = "indexing::documents")]
 fn fetch_or_generate_document_id(
     document: &obkv::KvReader<FieldId>,
     documents_batch_index: &DocumentsBatchIndex,
-    primary_key: PrimaryKey,
+    primary_key: &PrimaryKey,
     autogenerate_docids: bool,
     uuid_buffer: &mut [u8; uuid::fmt::Hyphenated::LENGTH],
     count: u32,
@@ -157,7 +157,10 @@ fn fetch_or_generate_document_id(
     Ok(match primary_key.document_id(document, documents_batch_index)? {
         Ok(document_id) => Ok(DocumentId::Retrieved { value: document_id }),
         Err(DocumentIdExtractionError::InvalidDocumentId(user_error)) => Err(user_error),
-        Err(DocumentIdExtractionError::MissingDocumentId) if autogenerate_docids => {
+        // a composite primary key isn't a field the generated id could be written into
+        Err(DocumentIdExtractionError::MissingDocumentId)
+            if autogenerate_docids && !primary_key.is_composite() =>
+        {
             let uuid = uuid::Uuid::new_v4().as_hyphenated().encode_lower(uuid_buffer);
             Ok(DocumentId::Generated { value: uuid.to_string(), document_nth: count })
         }
@@ -10,7 +10,11 @@ use roaring::RoaringBitmap;
 use serde_json::Value;
 
 use super::helpers::{create_sorter, keep_latest_obkv, sorter_into_reader, GrenadParameters};
+use crate::decompounding::Decompounder;
 use crate::error::{InternalError, SerializationError};
+use crate::localized_attributes_rules::{script_language_allow_list, LocalizedFieldIds};
+use crate::localized_stop_words::LocalizedStopWords;
+use crate::stemming::Stemmers;
 use crate::update::del_add::{del_add_from_two_obkvs, DelAdd, KvReaderDelAdd};
 use crate::{FieldId, Result, MAX_POSITION_PER_ATTRIBUTE, MAX_WORD_LENGTH};
 
@@ -21,6 +25,7 @@ pub type ScriptLanguageDocidsMap = HashMap<(Script, Language), (RoaringBitmap, R
 ///
 /// Returns the generated internal documents ids and a grenad reader
 /// with the list of extracted words from the given chunk of documents.
+#[allow(clippy::too_many_arguments)]
 #[tracing::instrument(level = "trace", skip_all, target = "indexing::extract")]
 pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
     obkv_documents: grenad::Reader<R>,
@@ -30,6 +35,10 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
     allowed_separators: Option<&[&str]>,
     dictionary: Option<&[&str]>,
     max_positions_per_attributes: Option<u32>,
+    localized_field_ids: &LocalizedFieldIds,
+    localized_stop_words: &LocalizedStopWords,
+    stemmers: &Stemmers,
+    decompounder: &Decompounder,
 ) -> Result<(grenad::Reader<BufReader<File>>, ScriptLanguageDocidsMap)> {
     puffin::profile_function!();
 
@@ -59,6 +68,25 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
     let mut builder = tokenizer_builder(stop_words, allowed_separators, dictionary, None);
     let tokenizer = builder.build();
 
+    // initialize the tokenizers of the fields with locales.
+    let localized_allow_lists: Vec<_> = localized_field_ids
+        .iter()
+        .map(|(field_id, locales)| (field_id, script_language_allow_list(locales)))
+        .filter(|(_, allow_list)| !allow_list.is_empty())
+        .collect();
+    let mut localized_builders: Vec<_> = localized_allow_lists
+        .iter()
+        .map(|(field_id, allow_list)| {
+            let builder =
+                tokenizer_builder(stop_words, allowed_separators, dictionary, Some(allow_list));
+            (*field_id, builder)
+        })
+        .collect();
+    let localized_tokenizers: HashMap<_, _> = localized_builders
+        .iter_mut()
+        .map(|(field_id, builder)| (*field_id, builder.build()))
+        .collect();
+
     // iterate over documents.
     let mut cursor = obkv_documents.into_cursor()?;
     while let Some((key, value)) = cursor.move_on_next()? {
@@ -87,6 +115,10 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
                     &obkv,
                     searchable_fields,
                     &tokenizer,
+                    &localized_tokenizers,
+                    localized_stop_words,
+                    stemmers,
+                    decompounder,
                     stop_words,
                     allowed_separators,
                     dictionary,
@@ -101,6 +133,10 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
                     &obkv,
                     searchable_fields,
                     &tokenizer,
+                    &localized_tokenizers,
+                    localized_stop_words,
+                    stemmers,
+                    decompounder,
                     stop_words,
                     allowed_separators,
                     dictionary,
@@ -211,6 +247,10 @@ fn lang_safe_tokens_from_document<'a>(
     obkv: &KvReader<FieldId>,
     searchable_fields: &Option<HashSet<FieldId>>,
     tokenizer: &Tokenizer,
+    localized_tokenizers: &HashMap<FieldId, Tokenizer>,
+    localized_stop_words: &LocalizedStopWords,
+    stemmers: &Stemmers,
+    decompounder: &Decompounder,
     stop_words: Option<&fst::Set<Vec<u8>>>,
     allowed_separators: Option<&[&str]>,
     dictionary: Option<&[&str]>,
@@ -224,6 +264,10 @@ fn lang_safe_tokens_from_document<'a>(
         obkv,
         searchable_fields,
         tokenizer,
+        localized_tokenizers,
+        localized_stop_words,
+        stemmers,
+        decompounder,
         max_positions_per_attributes,
         del_add,
         buffers,
@@ -261,6 +305,10 @@ fn lang_safe_tokens_from_document<'a>(
                 obkv,
                 searchable_fields,
                 &tokenizer,
+                localized_tokenizers,
+                localized_stop_words,
+                stemmers,
+                decompounder,
                 max_positions_per_attributes,
                 del_add,
                 buffers,
@@ -274,10 +322,19 @@ fn lang_safe_tokens_from_document<'a>(
 }
 
 /// Extract words mapped with their positions of a document.
+///
+/// The fields with locales are tokenized with their own tokenizer, the words are then
+/// checked against the stop words of their language, followed by the parts of the compound
+/// words and reduced to their stem.
+#[allow(clippy::too_many_arguments)]
 fn tokens_from_document<'a>(
     obkv: &KvReader<FieldId>,
     searchable_fields: &Option<HashSet<FieldId>>,
     tokenizer: &Tokenizer,
+    localized_tokenizers: &HashMap<FieldId, Tokenizer>,
+    localized_stop_words: &LocalizedStopWords,
+    stemmers: &Stemmers,
+    decompounder: &Decompounder,
     max_positions_per_attributes: u32,
     del_add: DelAdd,
     buffers: &'a mut Buffers,
@@ -301,8 +358,13 @@ fn tokens_from_document<'a>(
                 // convert json into a unique string.
                 buffers.field_buffer.clear();
                 if let Some(field) = json_to_string(&value, &mut buffers.field_buffer) {
+                    let tokenizer = localized_tokenizers.get(&field_id).unwrap_or(tokenizer);
                     // create an iterator of token with their positions.
-                    let tokens = process_tokens(tokenizer.tokenize(field))
+                    let tokens = tokenizer
+                        .tokenize(field)
+                        .flat_map(|t| decompounder.decompound(localized_stop_words.classify(t)))
+                        .map(|t| stemmers.stem(t));
+                    let tokens = process_tokens(tokens)
                         .take_while(|(p, _)| (*p as u32) < max_positions_per_attributes);
 
                     for (index, token) in tokens {
@@ -278,7 +278,7 @@ fn deladd_obkv_cbo_roaring_bitmaps(
 }
 
 /// Truncates a string to the biggest valid LMDB key size.
-fn truncate_string(s: String) -> String {
+pub(crate) fn truncate_string(s: String) -> String {
     s.char_indices()
         .take_while(|(idx, _)| idx + 4 < MAX_FACET_VALUE_LENGTH)
         .map(|(_, c)| c)
@@ -398,7 +398,7 @@ where
 }
 
 /// Represent what a document field contains.
-enum FilterableValues {
+pub(crate) enum FilterableValues {
     /// Corresponds to the JSON `null` value.
     Null,
     /// Corresponds to either, an empty string `""`, an empty array `[]`, or an empty object `{}`.
@@ -408,7 +408,7 @@ enum FilterableValues {
 }
 
 /// Extracts the facet values of a JSON field.
-fn extract_facet_values(value: &Value, geo_field: bool) -> FilterableValues {
+pub(crate) fn extract_facet_values(value: &Value, geo_field: bool) -> FilterableValues {
     fn inner_extract_facet_values(
         value: &Value,
         can_recurse: bool,
@@ -12,7 +12,7 @@ use itertools::EitherOrBoth;
 use ordered_float::OrderedFloat;
 use serde_json::{from_slice, Value};
 
-use super::helpers::{create_writer, writer_into_reader, GrenadParameters};
+use super::helpers::{create_writer, writer_into_reader, CancellationToken, GrenadParameters};
 use crate::error::UserError;
 use crate::prompt::Prompt;
 use crate::update::del_add::{DelAdd, KvReaderDelAdd, KvWriterDelAdd};
@@ -339,6 +339,7 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
     prompt_reader: grenad::Reader<R>,
     indexer: GrenadParameters,
     embedder: Arc<Embedder>,
+    cancellation_token: &CancellationToken,
 ) -> Result<grenad::Reader<BufReader<File>>> {
     puffin::profile_function!();
     let n_chunks = embedder.chunk_count_hint(); // chunk level parallelism
@@ -375,6 +376,8 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
         current_chunk_ids.push(docid);
 
         if chunks.len() == chunks.capacity() {
+            // the embedders can be slow, we stop as soon as the indexation is cancelled.
+            cancellation_token.check()?;
             let chunked_embeds = embedder
                 .embed_chunks(std::mem::replace(&mut chunks, Vec::with_capacity(n_chunks)))
                 .map_err(crate::vector::Error::from)
@@ -393,6 +396,7 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
 
     // send last chunk
     if !chunks.is_empty() {
+        cancellation_token.check()?;
         let chunked_embeds = embedder
             .embed_chunks(std::mem::take(&mut chunks))
             .map_err(crate::vector::Error::from)
@@ -407,6 +411,7 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
     }
 
     if !current_chunk.is_empty() {
+        cancellation_token.check()?;
         let embeds = embedder
             .embed_chunks(vec![std::mem::take(&mut current_chunk)])
             .map_err(crate::vector::Error::from)
@@ -19,6 +19,9 @@ use rayon::prelude::*;
 use self::extract_docid_word_positions::extract_docid_word_positions;
 use self::extract_facet_number_docids::extract_facet_number_docids;
 use self::extract_facet_string_docids::extract_facet_string_docids;
+pub(crate) use self::extract_fid_docid_facet_values::{
+    extract_facet_values, truncate_string, FilterableValues,
+};
 use self::extract_fid_docid_facet_values::{extract_fid_docid_facet_values, ExtractedFacetValues};
 use self::extract_fid_word_count_docids::extract_fid_word_count_docids;
 use self::extract_geo_points::extract_geo_points;
@@ -28,9 +31,15 @@ use self::extract_vector_points::{
 use self::extract_word_docids::extract_word_docids;
 use self::extract_word_pair_proximity_docids::extract_word_pair_proximity_docids;
 use self::extract_word_position_docids::extract_word_position_docids;
-use super::helpers::{as_cloneable_grenad, CursorClonableMmap, GrenadParameters};
+use super::helpers::{
+    as_cloneable_grenad, CancellationToken, CursorClonableMmap, GrenadParameters,
+};
 use super::{helpers, TypedChunk};
+use crate::decompounding::Decompounder;
+use crate::localized_attributes_rules::LocalizedFieldIds;
+use crate::localized_stop_words::LocalizedStopWords;
 use crate::proximity::ProximityPrecision;
+use crate::stemming::Stemmers;
 use crate::vector::EmbeddingConfigs;
 use crate::{FieldId, FieldsIdsMap, Result};
 
@@ -52,9 +61,14 @@ pub(crate) fn data_from_obkv_documents(
     allowed_separators: Option<&[&str]>,
     dictionary: Option<&[&str]>,
     max_positions_per_attributes: Option<u32>,
+    localized_field_ids: LocalizedFieldIds,
+    localized_stop_words: LocalizedStopWords,
+    stemmers: Stemmers,
+    decompounder: Decompounder,
     exact_attributes: HashSet<FieldId>,
     proximity_precision: ProximityPrecision,
     embedders: EmbeddingConfigs,
+    cancellation_token: CancellationToken,
 ) -> Result<()> {
     puffin::profile_function!();
 
@@ -69,6 +83,7 @@ pub(crate) fn data_from_obkv_documents(
                         lmdb_writer_sx.clone(),
                         field_id_map.clone(),
                         embedders.clone(),
+                        cancellation_token.clone(),
                     )
                 })
                 .collect::<Result<()>>()
@@ -89,6 +104,11 @@ pub(crate) fn data_from_obkv_documents(
                         &allowed_separators,
                         &dictionary,
                         max_positions_per_attributes,
+                        &localized_field_ids,
+                        &localized_stop_words,
+                        &stemmers,
+                        &decompounder,
+                        &cancellation_token,
                     )
                 })
                 .map(|result| {
@@ -101,6 +121,7 @@ pub(crate) fn data_from_obkv_documents(
                             docid_word_positions_chunk.clone(),
                             indexer,
                             lmdb_writer_sx.clone(),
+                            cancellation_token.clone(),
                             extract_fid_word_count_docids,
                             TypedChunk::FieldIdWordCountDocids,
                             "field-id-wordcount-docids",
@@ -119,6 +140,7 @@ pub(crate) fn data_from_obkv_documents(
                             docid_word_positions_chunk.clone(),
                             indexer,
                             lmdb_writer_sx.clone(),
+                            cancellation_token.clone(),
                             move |doc_word_pos, indexer| {
                                 extract_word_docids(doc_word_pos, indexer, &exact_attributes)
                             },
@@ -140,6 +162,7 @@ pub(crate) fn data_from_obkv_documents(
                             docid_word_positions_chunk.clone(),
                             indexer,
                             lmdb_writer_sx.clone(),
+                            cancellation_token.clone(),
                             extract_word_position_docids,
                             TypedChunk::WordPositionDocids,
                             "word-position-docids",
@@ -153,6 +176,7 @@ pub(crate) fn data_from_obkv_documents(
                             fid_docid_facet_strings_chunk.clone(),
                             indexer,
                             lmdb_writer_sx.clone(),
+                            cancellation_token.clone(),
                             extract_facet_string_docids,
                             TypedChunk::FieldIdFacetStringDocids,
                             "field-id-facet-string-docids",
@@ -162,6 +186,7 @@ pub(crate) fn data_from_obkv_documents(
                             fid_docid_facet_numbers_chunk.clone(),
                             indexer,
                             lmdb_writer_sx.clone(),
+                            cancellation_token.clone(),
                             extract_facet_number_docids,
                             TypedChunk::FieldIdFacetNumberDocids,
                             "field-id-facet-number-docids",
@@ -172,6 +197,7 @@ pub(crate) fn data_from_obkv_documents(
                                 docid_word_positions_chunk.clone(),
                                 indexer,
                                 lmdb_writer_sx.clone(),
+                                cancellation_token.clone(),
                                 extract_word_pair_proximity_docids,
                                 TypedChunk::WordPairProximityDocids,
                                 "word-pair-proximity-docids",
@@ -196,6 +222,7 @@ fn run_extraction_task<FE, FS, M>(
     chunk: grenad::Reader<CursorClonableMmap>,
     indexer: GrenadParameters,
     lmdb_writer_sx: Sender<Result<TypedChunk>>,
+    cancellation_token: CancellationToken,
     extract_fn: FE,
     serialize_fn: FS,
     name: &'static str,
@@ -213,6 +240,9 @@ fn run_extraction_task<FE, FS, M>(
         let child_span = tracing::trace_span!(target: "indexing::extract::details", parent: &current_span, "extract_multiple_chunks");
         let _entered = child_span.enter();
         puffin::profile_scope!("extract_multiple_chunks", name);
+        if cancellation_token.is_cancelled() {
+            return;
+        }
         match extract_fn(chunk, indexer) {
             Ok(chunk) => {
                 let _ = lmdb_writer_sx.send(Ok(serialize_fn(chunk)));
@@ -232,7 +262,9 @@ fn send_original_documents_data(
     lmdb_writer_sx: Sender<Result<TypedChunk>>,
     field_id_map: FieldsIdsMap,
     embedders: EmbeddingConfigs,
+    cancellation_token: CancellationToken,
 ) -> Result<()> {
+    cancellation_token.check()?;
     let original_documents_chunk =
         original_documents_chunk.and_then(|c| unsafe { as_cloneable_grenad(&c) })?;
 
@@ -240,6 +272,9 @@ fn send_original_documents_data(
     let lmdb_writer_sx_cloned = lmdb_writer_sx.clone();
     rayon::spawn(move || {
         for (name, (embedder, prompt)) in embedders {
+            if cancellation_token.is_cancelled() {
+                return;
+            }
             let result = extract_vector_points(
                 documents_chunk_cloned.clone(),
                 indexer,
@@ -249,7 +284,12 @@ fn send_original_documents_data(
             );
             match result {
                 Ok(ExtractedVectorPoints { manual_vectors, remove_vectors, prompts }) => {
-                    let embeddings = match extract_embeddings(prompts, indexer, embedder.clone()) {
+                    let embeddings = match extract_embeddings(
+                        prompts,
+                        indexer,
+                        embedder.clone(),
+                        &cancellation_token,
+                    ) {
                         Ok(results) => Some(results),
                         Err(error) => {
                             let _ = lmdb_writer_sx_cloned.send(Err(error));
@@ -303,10 +343,16 @@ fn send_and_extract_flattened_documents_data(
     allowed_separators: &Option<&[&str]>,
     dictionary: &Option<&[&str]>,
     max_positions_per_attributes: Option<u32>,
+    localized_field_ids: &LocalizedFieldIds,
+    localized_stop_words: &LocalizedStopWords,
+    stemmers: &Stemmers,
+    decompounder: &Decompounder,
+    cancellation_token: &CancellationToken,
 ) -> Result<(
     grenad::Reader<CursorClonableMmap>,
     (grenad::Reader<CursorClonableMmap>, grenad::Reader<CursorClonableMmap>),
 )> {
+    cancellation_token.check()?;
     let flattened_documents_chunk =
         flattened_documents_chunk.and_then(|c| unsafe { as_cloneable_grenad(&c) })?;
 
@@ -335,6 +381,10 @@ fn send_and_extract_flattened_documents_data(
                         *allowed_separators,
                         *dictionary,
                         max_positions_per_attributes,
+                        localized_field_ids,
+                        localized_stop_words,
+                        stemmers,
+                        decompounder,
                     )?;
 
                 // send docid_word_positions_chunk to DB writer
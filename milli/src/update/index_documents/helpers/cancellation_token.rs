@@ -0,0 +1,69 @@
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering::Relaxed;
+use std::sync::Arc;
+
+use crate::error::InternalError;
+use crate::{Error, Result};
+
+/// A flag shared with the extractors running in the background of an indexation.
+///
+/// The extractors are spawned on the thread pool and are not joined by the thread
+/// writing into LMDB. When it stops early, because the indexation was aborted or failed,
+/// it cancels the token so that the extractors stop at their next checkpoint instead of
+/// extracting, or embedding, documents that will never be written.
+#[derive(Debug, Default, Clone)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Relaxed)
+    }
+
+    /// Returns an `AbortedIndexation` error if the token has been cancelled.
+    pub fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            Err(Error::InternalError(InternalError::AbortedIndexation))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns a guard that cancels the token when it is dropped.
+    pub fn cancel_on_drop(&self) -> CancelOnDrop {
+        CancelOnDrop(self.clone())
+    }
+}
+
+pub struct CancelOnDrop(CancellationToken);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.cancel();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancel_on_drop() {
+        let token = CancellationToken::default();
+        let cloned = token.clone();
+        assert!(cloned.check().is_ok());
+
+        let guard = token.cancel_on_drop();
+        assert!(!cloned.is_cancelled());
+        drop(guard);
+
+        assert!(cloned.is_cancelled());
+        assert!(matches!(
+            cloned.check(),
+            Err(Error::InternalError(InternalError::AbortedIndexation))
+        ));
+    }
+}
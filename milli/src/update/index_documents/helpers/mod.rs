@@ -1,3 +1,4 @@
+mod cancellation_token;
 mod clonable_mmap;
 mod grenad_helpers;
 mod merge_functions;
@@ -5,6 +6,7 @@ mod merge_functions;
 use std::collections::HashSet;
 use std::convert::{TryFrom, TryInto};
 
+pub use cancellation_token::CancellationToken;
 pub use clonable_mmap::{ClonableMmap, CursorClonableMmap};
 use fst::{IntoStreamer, Streamer};
 pub use grenad_helpers::{
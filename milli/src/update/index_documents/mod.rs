@@ -4,7 +4,7 @@ mod helpers;
 mod transform;
 mod typed_chunk;
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::io::{Read, Seek};
 use std::iter::FromIterator;
 use std::num::NonZeroU32;
@@ -23,27 +23,36 @@ use typed_chunk::{write_typed_chunk_into_index, ChunkAccumulator, TypedChunk};
 
 use self::enrich::enrich_documents_batch;
 pub use self::enrich::{extract_finite_float_from_value, DocumentId};
+pub(crate) use self::extract::{extract_facet_values, truncate_string, FilterableValues};
 pub use self::helpers::{
     as_cloneable_grenad, create_sorter, create_writer, fst_stream_into_hashset,
     fst_stream_into_vec, merge_cbo_roaring_bitmaps, merge_deladd_cbo_roaring_bitmaps,
     merge_deladd_cbo_roaring_bitmaps_into_cbo_roaring_bitmap, merge_roaring_bitmaps,
     valid_lmdb_key, write_sorter_into_database, writer_into_reader, MergeFn,
 };
-use self::helpers::{grenad_obkv_into_chunks, GrenadParameters};
+use self::helpers::{grenad_obkv_into_chunks, CancellationToken, GrenadParameters};
 pub use self::transform::{Transform, TransformOutput};
+use crate::decompounding::Decompounder;
 use crate::documents::{obkv_to_object, DocumentsBatchReader};
 use crate::error::{Error, InternalError, UserError};
+use crate::localized_attributes_rules::LocalizedFieldIds;
+use crate::localized_stop_words::LocalizedStopWords;
+use crate::stemming::Stemmers;
 pub use crate::update::index_documents::helpers::CursorClonableMmap;
 use crate::update::{
     IndexerConfig, UpdateIndexingStep, WordPrefixDocids, WordPrefixIntegerDocids, WordsPrefixesFst,
 };
 use crate::vector::EmbeddingConfigs;
-use crate::{CboRoaringBitmapCodec, Index, Result};
+use crate::{CboRoaringBitmapCodec, Index, PrefixSearch, Result};
 
 static MERGED_DATABASE_COUNT: usize = 7;
 static PREFIX_DATABASE_COUNT: usize = 4;
 static TOTAL_POSTING_DATABASE_COUNT: usize = MERGED_DATABASE_COUNT + PREFIX_DATABASE_COUNT;
 
+/// The maximum number of words of an update for which the words prefixes are recomputed
+/// incrementally instead of being rebuilt from the whole words FST.
+const MAX_UPDATED_WORDS_FOR_INCREMENTAL_PREFIXES: usize = 10_000;
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DocumentAdditionResult {
     /// The number of documents that were indexed during the update
@@ -367,6 +376,15 @@ where
         let dictionary = self.index.dictionary(self.wtxn)?;
         let exact_attributes = self.index.exact_attributes_ids(self.wtxn)?;
         let proximity_precision = self.index.proximity_precision(self.wtxn)?.unwrap_or_default();
+        let localized_attributes_rules =
+            self.index.localized_attributes_rules(self.wtxn)?.unwrap_or_default();
+        let localized_field_ids =
+            LocalizedFieldIds::new(&localized_attributes_rules, &field_id_map, field_id_map.ids());
+        let localized_stop_words = self.index.localized_stop_words(self.wtxn)?.unwrap_or_default();
+        let localized_stop_words = LocalizedStopWords::new(&localized_stop_words);
+        let stemmers = Stemmers::new(&self.index.stemming(self.wtxn)?.unwrap_or_default());
+        let decompounder =
+            Decompounder::new(&self.index.decompounding(self.wtxn)?.unwrap_or_default());
 
         let pool_params = GrenadParameters {
             chunk_compression_type: self.indexer_config.chunk_compression_type,
@@ -408,10 +426,16 @@ where
         let mut word_fid_docids = None;
         let mut word_docids = None;
         let mut exact_word_docids = None;
+        let mut updated_words = Some(BTreeSet::new());
         let mut chunk_accumulator = ChunkAccumulator::default();
         let mut dimension = HashMap::new();
         let stop_words = stop_words.map(|sw| sw.map_data(Vec::from).unwrap());
 
+        // The extractors run in the background and are stopped as soon as we stop
+        // receiving their chunks, whether the indexation succeeded, failed or was aborted.
+        let cancellation_token = CancellationToken::default();
+        let _cancel_extraction = cancellation_token.cancel_on_drop();
+
         let current_span = tracing::Span::current();
 
         // Run extraction pipeline in parallel.
@@ -449,9 +473,14 @@ where
                         separators.as_deref(),
                         dictionary.as_deref(),
                         max_positions_per_attributes,
+                        localized_field_ids,
+                        localized_stop_words,
+                        stemmers,
+                        decompounder,
                         exact_attributes,
                         proximity_precision,
                         cloned_embedder,
+                        cancellation_token,
                     )
                 });
 
@@ -508,6 +537,12 @@ where
                                 exact_word_docids_reader,
                                 word_fid_docids_reader,
                             } => {
+                                extend_updated_words(&mut updated_words, unsafe {
+                                    as_cloneable_grenad(&word_docids_reader)?
+                                })?;
+                                extend_updated_words(&mut updated_words, unsafe {
+                                    as_cloneable_grenad(&exact_word_docids_reader)?
+                                })?;
                                 let cloneable_chunk =
                                     unsafe { as_cloneable_grenad(&word_docids_reader)? };
                                 let word_docids = word_docids.get_or_insert_with(|| {
@@ -608,6 +643,7 @@ where
             exact_word_docids.map(MergerBuilder::build),
             word_position_docids.map(MergerBuilder::build),
             word_fid_docids.map(MergerBuilder::build),
+            updated_words,
         )?;
 
         Ok(number_of_documents)
@@ -625,6 +661,7 @@ where
         exact_word_docids: Option<Merger<CursorClonableMmap, MergeFn>>,
         word_position_docids: Option<Merger<CursorClonableMmap, MergeFn>>,
         word_fid_docids: Option<Merger<CursorClonableMmap, MergeFn>>,
+        updated_words: Option<BTreeSet<String>>,
     ) -> Result<()>
     where
         FP: Fn(UpdateIndexingStep) + Sync,
@@ -652,15 +689,23 @@ where
         let previous_words_prefixes_fst =
             self.index.words_prefixes_fst(self.wtxn)?.map_data(|cow| cow.into_owned())?;
 
-        // Run the words prefixes update operation.
-        let mut builder = WordsPrefixesFst::new(self.wtxn, self.index);
-        if let Some(value) = self.config.words_prefix_threshold {
-            builder.threshold(value);
-        }
-        if let Some(value) = self.config.max_prefix_length {
-            builder.max_prefix_length(value);
+        // Run the words prefixes update operation, no prefix is kept when the prefix search is
+        // disabled and the words prefixes databases are emptied below.
+        if self.index.prefix_search(self.wtxn)? == Some(PrefixSearch::Disabled) {
+            self.index.put_words_prefixes_fst(self.wtxn, &fst::Set::default())?;
+        } else {
+            let mut builder = WordsPrefixesFst::new(self.wtxn, self.index);
+            if let Some(value) = self.config.words_prefix_threshold {
+                builder.threshold(value);
+            }
+            if let Some(value) = self.config.max_prefix_length {
+                builder.max_prefix_length(value);
+            }
+            match updated_words {
+                Some(updated_words) => builder.execute_incremental(&updated_words)?,
+                None => builder.execute()?,
+            }
         }
-        builder.execute()?;
 
         if (self.should_abort)() {
             return Err(Error::InternalError(InternalError::AbortedIndexation));
@@ -797,6 +842,24 @@ where
     }
 }
 
+/// Inserts the words of a word docids chunk into the updated words, or stops tracking
+/// them when there are too many to update the words prefixes incrementally.
+fn extend_updated_words(
+    updated_words: &mut Option<BTreeSet<String>>,
+    chunk: grenad::Reader<CursorClonableMmap>,
+) -> Result<()> {
+    let Some(words) = updated_words else { return Ok(()) };
+    let mut cursor = chunk.into_cursor()?;
+    while let Some((word, _)) = cursor.move_on_next()? {
+        if words.len() >= MAX_UPDATED_WORDS_FOR_INCREMENTAL_PREFIXES {
+            *updated_words = None;
+            return Ok(());
+        }
+        words.insert(std::str::from_utf8(word)?.to_string());
+    }
+    Ok(())
+}
+
 /// Run the word prefix docids update operation.
 #[allow(clippy::too_many_arguments)]
 #[tracing::instrument(
@@ -1529,6 +1592,56 @@ mod tests {
         assert_eq!(documents_ids, vec![3]);
     }
 
+    #[test]
+    fn index_documents_with_composite_primary_key() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_primary_key(crate::documents::composite_primary_key(&[
+                    "tenant_id".to_owned(),
+                    "item.sku".to_owned(),
+                ]));
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "tenant_id": "acme", "item": { "sku": 1 }, "title": "The first document" },
+                { "tenant_id": "acme", "item": { "sku": 2 }, "title": "The second document" },
+                { "tenant_id": "initech", "item": { "sku": 1 }, "title": "The third document" },
+                { "tenant_id": "acme", "item": { "sku": 1 }, "title": "The updated document" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let external_documents_ids = index.external_documents_ids();
+        let mut ids: Vec<_> =
+            external_documents_ids.to_hash_map(&rtxn).unwrap().into_keys().collect();
+        ids.sort_unstable();
+        assert_eq!(ids, ["acme:1", "acme:2", "initech:1"]);
+
+        let docid = external_documents_ids.get(&rtxn, "acme:1").unwrap().unwrap();
+        let external_ids: Vec<_> = index
+            .external_id_of(&rtxn, [docid])
+            .unwrap()
+            .into_iter()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(external_ids, ["acme:1"]);
+        drop(rtxn);
+
+        // every field of the primary key must be present and valid
+        let error = index
+            .add_documents(documents!({ "tenant_id": "acme", "title": "No sku" }))
+            .unwrap_err();
+        assert!(matches!(error, Error::UserError(UserError::MissingDocumentId { .. })));
+        let error = index
+            .add_documents(documents!({ "tenant_id": "a:b", "item": { "sku": 3 } }))
+            .unwrap_err();
+        assert!(matches!(error, Error::UserError(UserError::InvalidCompositeDocumentId { .. })));
+    }
+
     #[test]
     fn retrieve_a_b_nested_document_id() {
         let index = TempIndex::new();
@@ -3362,4 +3475,53 @@ mod tests {
         let crate::SearchResult { documents_ids, .. } = s.execute().unwrap();
         insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0]");
     }
+
+    #[test]
+    fn incremental_words_prefixes_update() {
+        let index = TempIndex::new_with_map_size(4096 * 10_000);
+
+        let letters = || 'a'..='z';
+        let words = |first: char| {
+            letters()
+                .flat_map(|b| letters().map(move |c| format!("{first}{b}{c}")))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        // one word short of making `q` and `qu` prefixes
+        let qu_words = letters()
+            .flat_map(|a| letters().map(move |b| format!("qu{a}{b}")))
+            .take(99)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "text": words('b') },
+                { "id": 1, "text": words('c') },
+                { "id": 2, "text": words('d') },
+                { "id": 3, "text": qu_words },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let prefixes = index.words_prefixes_fst(&rtxn).unwrap().into_stream().into_strs().unwrap();
+        assert!(prefixes.contains(&S("b")));
+        assert!(!prefixes.contains(&S("qu")));
+        drop(rtxn);
+
+        index.add_documents(documents!([{ "id": 4, "text": "quzzz" }])).unwrap();
+
+        let mut wtxn = index.write_txn().unwrap();
+        let prefixes = index.words_prefixes_fst(&wtxn).unwrap().into_stream().into_strs().unwrap();
+        assert!(prefixes.contains(&S("q")));
+        assert!(prefixes.contains(&S("qu")));
+        assert!(!prefixes.contains(&S("quz")));
+        let docids = index.word_prefix_docids.get(&wtxn, "qu").unwrap().unwrap();
+        assert_eq!(docids, RoaringBitmap::from_iter([3, 4]));
+
+        // the prefixes must be the same as the ones of a rebuild
+        WordsPrefixesFst::new(&mut wtxn, &index).execute().unwrap();
+        let rebuilt = index.words_prefixes_fst(&wtxn).unwrap().into_stream().into_strs().unwrap();
+        assert_eq!(prefixes, rebuilt);
+    }
 }
@@ -18,7 +18,10 @@ use super::helpers::{
     obkvs_merge_additions_and_deletions, sorter_into_reader, MergeFn,
 };
 use super::{IndexDocumentsMethod, IndexerConfig};
-use crate::documents::{DocumentsBatchIndex, EnrichedDocument, EnrichedDocumentsBatchReader};
+use crate::documents::{
+    composite_primary_key_fields, DocumentsBatchIndex, EnrichedDocument,
+    EnrichedDocumentsBatchReader,
+};
 use crate::error::{Error, InternalError, UserError};
 use crate::index::{db_name, main_key};
 use crate::update::del_add::{into_del_add_obkv, DelAdd, DelAddOperation, KvReaderDelAdd};
@@ -166,8 +169,12 @@ impl<'a, 'i> Transform<'a, 'i> {
         let mapping = create_fields_mapping(&mut self.fields_ids_map, &fields_index)?;
 
         let primary_key = cursor.primary_key().to_string();
-        let primary_key_id =
-            self.fields_ids_map.insert(&primary_key).ok_or(UserError::AttributeLimitReached)?;
+        // a composite primary key is made of other fields and its document ids are never generated
+        let primary_key_id = if composite_primary_key_fields(&primary_key).is_some() {
+            None
+        } else {
+            Some(self.fields_ids_map.insert(&primary_key).ok_or(UserError::AttributeLimitReached)?)
+        };
 
         let mut obkv_buffer = Vec::new();
         let mut document_sorter_value_buffer = Vec::new();
@@ -194,7 +201,7 @@ impl<'a, 'i> Transform<'a, 'i> {
             // When the document id has been auto-generated by the `enrich_documents_batch`
             // we must insert this document id into the remaped document.
             let external_id = document_id.value();
-            if document_id.is_generated() {
+            if let (true, Some(primary_key_id)) = (document_id.is_generated(), primary_key_id) {
                 serde_json::to_writer(&mut docid_buffer, external_id)
                     .map_err(InternalError::SerdeJson)?;
                 field_buffer_cache.push((primary_key_id, Cow::from(&docid_buffer)));
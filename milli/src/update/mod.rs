@@ -1,7 +1,9 @@
 pub use self::available_documents_ids::AvailableDocumentsIds;
 pub use self::clear_documents::ClearDocuments;
+pub use self::enrichment::{EnrichmentWebhook, DEFAULT_ENRICHMENT_BATCH_SIZE};
 pub use self::facet::bulk::FacetsUpdateBulk;
 pub use self::facet::incremental::FacetsUpdateIncrementalInner;
+pub(crate) use self::index_documents::{extract_facet_values, truncate_string, FilterableValues};
 pub use self::index_documents::{
     merge_cbo_roaring_bitmaps, merge_roaring_bitmaps, DocumentAdditionResult, DocumentId,
     IndexDocuments, IndexDocumentsConfig, IndexDocumentsMethod, MergeFn,
@@ -16,6 +18,7 @@ pub use self::words_prefixes_fst::WordsPrefixesFst;
 mod available_documents_ids;
 mod clear_documents;
 pub(crate) mod del_add;
+mod enrichment;
 pub(crate) mod facet;
 mod index_documents;
 mod indexer_config;
@@ -13,13 +13,20 @@ use super::index_documents::{IndexDocumentsConfig, Transform};
 use super::IndexerConfig;
 use crate::criterion::Criterion;
 use crate::error::UserError;
-use crate::index::{DEFAULT_MIN_WORD_LEN_ONE_TYPO, DEFAULT_MIN_WORD_LEN_TWO_TYPOS};
+use crate::index::{PrefixSearch, DEFAULT_MIN_WORD_LEN_ONE_TYPO, DEFAULT_MIN_WORD_LEN_TWO_TYPOS};
+use crate::localized_attributes_rules::LocalizedAttributesRule;
 use crate::proximity::ProximityPrecision;
 use crate::update::index_documents::IndexDocumentsMethod;
-use crate::update::{IndexDocuments, UpdateIndexingStep};
+use crate::unit_conversion::UnitConversion;
+use crate::update::{
+    EnrichmentWebhook, IndexDocuments, UpdateIndexingStep, DEFAULT_ENRICHMENT_BATCH_SIZE,
+};
 use crate::vector::settings::{check_set, check_unset, EmbedderSource, EmbeddingSettings};
 use crate::vector::{Embedder, EmbeddingConfig, EmbeddingConfigs};
-use crate::{FieldsIdsMap, Index, OrderBy, Result};
+use crate::{
+    ExclusionMode, FieldsIdsMap, FilterResolution, Index, OrderBy, QueryPlanHints, Result,
+    SearchExclusions,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
 pub enum Setting<T> {
@@ -132,6 +139,9 @@ pub struct Settings<'a, 't, 'i> {
     sortable_fields: Setting<HashSet<String>>,
     criteria: Setting<Vec<Criterion>>,
     stop_words: Setting<BTreeSet<String>>,
+    localized_stop_words: Setting<BTreeMap<String, BTreeSet<String>>>,
+    stemming: Setting<BTreeSet<String>>,
+    decompounding: Setting<BTreeMap<String, BTreeSet<String>>>,
     non_separator_tokens: Setting<BTreeSet<String>>,
     separator_tokens: Setting<BTreeSet<String>>,
     dictionary: Setting<BTreeSet<String>>,
@@ -144,10 +154,24 @@ pub struct Settings<'a, 't, 'i> {
     exact_words: Setting<BTreeSet<String>>,
     /// Attributes on which typo tolerance is disabled.
     exact_attributes: Setting<HashSet<String>>,
+    /// Whether typo tolerance is disabled on the words containing a digit.
+    disable_typos_on_numbers: Setting<bool>,
     max_values_per_facet: Setting<usize>,
     sort_facet_values_by: Setting<HashMap<String, OrderBy>>,
     pagination_max_total_hits: Setting<usize>,
     proximity_precision: Setting<ProximityPrecision>,
+    prefix_search: Setting<PrefixSearch>,
+    filter_resolution: Setting<FilterResolution>,
+    proximity_max_candidates: Setting<u64>,
+    excluded_document_ids: Setting<Vec<String>>,
+    exclusion_filter: Setting<String>,
+    exclusion_mode: Setting<ExclusionMode>,
+    enrichment_webhook_url: Setting<String>,
+    enrichment_webhook_batch_size: Setting<usize>,
+    unit_conversions: Setting<BTreeMap<String, UnitConversion>>,
+    search_cutoff: Setting<u64>,
+    search_templates: Setting<BTreeMap<String, serde_json::Value>>,
+    localized_attributes_rules: Setting<Vec<LocalizedAttributesRule>>,
     embedder_settings: Setting<BTreeMap<String, Setting<EmbeddingSettings>>>,
 }
 
@@ -166,6 +190,9 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             sortable_fields: Setting::NotSet,
             criteria: Setting::NotSet,
             stop_words: Setting::NotSet,
+            localized_stop_words: Setting::NotSet,
+            stemming: Setting::NotSet,
+            decompounding: Setting::NotSet,
             non_separator_tokens: Setting::NotSet,
             separator_tokens: Setting::NotSet,
             dictionary: Setting::NotSet,
@@ -177,10 +204,23 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             min_word_len_two_typos: Setting::NotSet,
             min_word_len_one_typo: Setting::NotSet,
             exact_attributes: Setting::NotSet,
+            disable_typos_on_numbers: Setting::NotSet,
             max_values_per_facet: Setting::NotSet,
             sort_facet_values_by: Setting::NotSet,
             pagination_max_total_hits: Setting::NotSet,
             proximity_precision: Setting::NotSet,
+            prefix_search: Setting::NotSet,
+            filter_resolution: Setting::NotSet,
+            proximity_max_candidates: Setting::NotSet,
+            excluded_document_ids: Setting::NotSet,
+            exclusion_filter: Setting::NotSet,
+            exclusion_mode: Setting::NotSet,
+            enrichment_webhook_url: Setting::NotSet,
+            enrichment_webhook_batch_size: Setting::NotSet,
+            unit_conversions: Setting::NotSet,
+            search_cutoff: Setting::NotSet,
+            search_templates: Setting::NotSet,
+            localized_attributes_rules: Setting::NotSet,
             embedder_settings: Setting::NotSet,
             indexer_config,
         }
@@ -235,6 +275,36 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             if stop_words.is_empty() { Setting::Reset } else { Setting::Set(stop_words) }
     }
 
+    pub fn reset_localized_stop_words(&mut self) {
+        self.localized_stop_words = Setting::Reset;
+    }
+
+    /// Sets the stop words of each language, keyed by the ISO 639-3 code of the language.
+    pub fn set_localized_stop_words(&mut self, stop_words: BTreeMap<String, BTreeSet<String>>) {
+        self.localized_stop_words =
+            if stop_words.is_empty() { Setting::Reset } else { Setting::Set(stop_words) }
+    }
+
+    pub fn reset_stemming(&mut self) {
+        self.stemming = Setting::Reset;
+    }
+
+    /// Enables the stemming of the languages given by their ISO 639-3 code.
+    pub fn set_stemming(&mut self, languages: BTreeSet<String>) {
+        self.stemming = if languages.is_empty() { Setting::Reset } else { Setting::Set(languages) }
+    }
+
+    pub fn reset_decompounding(&mut self) {
+        self.decompounding = Setting::Reset;
+    }
+
+    /// Sets the words the compound words of each language are made of, keyed by the
+    /// ISO 639-3 code of the language.
+    pub fn set_decompounding(&mut self, dictionaries: BTreeMap<String, BTreeSet<String>>) {
+        self.decompounding =
+            if dictionaries.is_empty() { Setting::Reset } else { Setting::Set(dictionaries) }
+    }
+
     pub fn reset_non_separator_tokens(&mut self) {
         self.non_separator_tokens = Setting::Reset;
     }
@@ -332,6 +402,14 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.exact_attributes = Setting::Reset;
     }
 
+    pub fn set_disable_typos_on_numbers(&mut self, val: bool) {
+        self.disable_typos_on_numbers = Setting::Set(val);
+    }
+
+    pub fn reset_disable_typos_on_numbers(&mut self) {
+        self.disable_typos_on_numbers = Setting::Reset;
+    }
+
     pub fn set_max_values_per_facet(&mut self, value: usize) {
         self.max_values_per_facet = Setting::Set(value);
     }
@@ -364,6 +442,103 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.proximity_precision = Setting::Reset;
     }
 
+    pub fn set_prefix_search(&mut self, value: PrefixSearch) {
+        self.prefix_search = Setting::Set(value);
+    }
+
+    pub fn reset_prefix_search(&mut self) {
+        self.prefix_search = Setting::Reset;
+    }
+
+    pub fn set_filter_resolution(&mut self, value: FilterResolution) {
+        self.filter_resolution = Setting::Set(value);
+    }
+
+    pub fn reset_filter_resolution(&mut self) {
+        self.filter_resolution = Setting::Reset;
+    }
+
+    pub fn set_proximity_max_candidates(&mut self, value: u64) {
+        self.proximity_max_candidates = Setting::Set(value);
+    }
+
+    pub fn reset_proximity_max_candidates(&mut self) {
+        self.proximity_max_candidates = Setting::Reset;
+    }
+
+    pub fn set_excluded_document_ids(&mut self, value: Vec<String>) {
+        self.excluded_document_ids = Setting::Set(value);
+    }
+
+    pub fn reset_excluded_document_ids(&mut self) {
+        self.excluded_document_ids = Setting::Reset;
+    }
+
+    pub fn set_exclusion_filter(&mut self, value: String) {
+        self.exclusion_filter = Setting::Set(value);
+    }
+
+    pub fn reset_exclusion_filter(&mut self) {
+        self.exclusion_filter = Setting::Reset;
+    }
+
+    pub fn set_exclusion_mode(&mut self, value: ExclusionMode) {
+        self.exclusion_mode = Setting::Set(value);
+    }
+
+    pub fn reset_exclusion_mode(&mut self) {
+        self.exclusion_mode = Setting::Reset;
+    }
+
+    pub fn set_enrichment_webhook_url(&mut self, value: String) {
+        self.enrichment_webhook_url = Setting::Set(value);
+    }
+
+    pub fn reset_enrichment_webhook_url(&mut self) {
+        self.enrichment_webhook_url = Setting::Reset;
+    }
+
+    pub fn set_enrichment_webhook_batch_size(&mut self, value: usize) {
+        self.enrichment_webhook_batch_size = Setting::Set(value);
+    }
+
+    pub fn reset_enrichment_webhook_batch_size(&mut self) {
+        self.enrichment_webhook_batch_size = Setting::Reset;
+    }
+
+    pub fn set_unit_conversions(&mut self, value: BTreeMap<String, UnitConversion>) {
+        self.unit_conversions = Setting::Set(value);
+    }
+
+    pub fn reset_unit_conversions(&mut self) {
+        self.unit_conversions = Setting::Reset;
+    }
+
+    pub fn set_search_cutoff(&mut self, value: u64) {
+        self.search_cutoff = Setting::Set(value);
+    }
+
+    pub fn reset_search_cutoff(&mut self) {
+        self.search_cutoff = Setting::Reset;
+    }
+
+    /// Sets the search parameters of the search templates, by template name.
+    pub fn set_search_templates(&mut self, value: BTreeMap<String, serde_json::Value>) {
+        self.search_templates = if value.is_empty() { Setting::Reset } else { Setting::Set(value) };
+    }
+
+    pub fn reset_search_templates(&mut self) {
+        self.search_templates = Setting::Reset;
+    }
+
+    pub fn set_localized_attributes_rules(&mut self, value: Vec<LocalizedAttributesRule>) {
+        self.localized_attributes_rules = Setting::Set(value);
+    }
+
+    pub fn reset_localized_attributes_rules(&mut self) {
+        self.localized_attributes_rules = Setting::Reset;
+    }
+
     pub fn set_embedder_settings(&mut self, value: BTreeMap<String, Setting<EmbeddingSettings>>) {
         self.embedder_settings = Setting::Set(value);
     }
@@ -561,6 +736,84 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         }
     }
 
+    fn update_localized_stop_words(&mut self) -> Result<bool> {
+        let changed = match self.localized_stop_words {
+            Setting::Set(ref stop_words) => {
+                // Apply the same unlossy normalization as the global stop words
+                let stop_words: BTreeMap<String, BTreeSet<String>> = stop_words
+                    .iter()
+                    .map(|(language, words)| {
+                        let words = words
+                            .iter()
+                            .map(|w| w.as_str().normalize(&Default::default()).into_owned())
+                            .collect();
+                        (language.clone(), words)
+                    })
+                    .collect();
+
+                let old = self.index.localized_stop_words(self.wtxn)?;
+                if old.as_ref() == Some(&stop_words) {
+                    false
+                } else {
+                    self.index.put_localized_stop_words(self.wtxn, &stop_words)?;
+                    true
+                }
+            }
+            Setting::Reset => self.index.delete_localized_stop_words(self.wtxn)?,
+            Setting::NotSet => false,
+        };
+
+        Ok(changed)
+    }
+
+    fn update_stemming(&mut self) -> Result<bool> {
+        let changed = match self.stemming {
+            Setting::Set(ref languages) => {
+                let old = self.index.stemming(self.wtxn)?;
+                if old.as_ref() == Some(languages) {
+                    false
+                } else {
+                    self.index.put_stemming(self.wtxn, languages)?;
+                    true
+                }
+            }
+            Setting::Reset => self.index.delete_stemming(self.wtxn)?,
+            Setting::NotSet => false,
+        };
+
+        Ok(changed)
+    }
+
+    fn update_decompounding(&mut self) -> Result<bool> {
+        let changed = match self.decompounding {
+            Setting::Set(ref dictionaries) => {
+                // The words are compared with the normalized words of the documents
+                let dictionaries: BTreeMap<String, BTreeSet<String>> = dictionaries
+                    .iter()
+                    .map(|(language, words)| {
+                        let words = words
+                            .iter()
+                            .map(|w| w.as_str().normalize(&Default::default()).into_owned())
+                            .collect();
+                        (language.clone(), words)
+                    })
+                    .collect();
+
+                let old = self.index.decompounding(self.wtxn)?;
+                if old.as_ref() == Some(&dictionaries) {
+                    false
+                } else {
+                    self.index.put_decompounding(self.wtxn, &dictionaries)?;
+                    true
+                }
+            }
+            Setting::Reset => self.index.delete_decompounding(self.wtxn)?,
+            Setting::NotSet => false,
+        };
+
+        Ok(changed)
+    }
+
     fn update_non_separator_tokens(&mut self) -> Result<bool> {
         let changes = match self.non_separator_tokens {
             Setting::Set(ref non_separator_tokens) => {
@@ -768,6 +1021,15 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
     fn update_criteria(&mut self) -> Result<()> {
         match &self.criteria {
             Setting::Set(criteria) => {
+                #[cfg(feature = "plugins")]
+                for criterion in criteria {
+                    if let Criterion::Plugin(name) = criterion {
+                        if crate::plugins::ranking_rule(name).is_none() {
+                            let name = name.clone();
+                            return Err(crate::CriterionError::UnknownPlugin { name }.into());
+                        }
+                    }
+                }
                 self.index.put_criteria(self.wtxn, criteria)?;
             }
             Setting::Reset => {
@@ -823,6 +1085,20 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         }
     }
 
+    fn update_disable_typos_on_numbers(&mut self) -> Result<()> {
+        match self.disable_typos_on_numbers {
+            Setting::Set(flag) => {
+                self.index.put_disable_typos_on_numbers(self.wtxn, flag)?;
+                Ok(())
+            }
+            Setting::Reset => {
+                self.index.put_disable_typos_on_numbers(self.wtxn, false)?;
+                Ok(())
+            }
+            Setting::NotSet => Ok(()),
+        }
+    }
+
     fn update_min_typo_word_len(&mut self) -> Result<()> {
         let one = self.min_word_len_one_typo.or_reset(DEFAULT_MIN_WORD_LEN_ONE_TYPO);
         let two = self.min_word_len_two_typos.or_reset(DEFAULT_MIN_WORD_LEN_TWO_TYPOS);
@@ -949,6 +1225,199 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         Ok(changed)
     }
 
+    fn update_prefix_search(&mut self) -> Result<bool> {
+        let old = self.index.prefix_search(self.wtxn)?.unwrap_or_default();
+        match self.prefix_search {
+            Setting::Set(new) => self.index.put_prefix_search(self.wtxn, new)?,
+            Setting::Reset => {
+                self.index.delete_prefix_search(self.wtxn)?;
+            }
+            Setting::NotSet => return Ok(false),
+        }
+
+        // The words prefixes databases only need to be recomputed when the behavior changes.
+        Ok(old != self.index.prefix_search(self.wtxn)?.unwrap_or_default())
+    }
+
+    fn update_query_plan_hints(&mut self) -> Result<()> {
+        if self.filter_resolution.is_not_set() && self.proximity_max_candidates.is_not_set() {
+            return Ok(());
+        }
+
+        let mut hints = self.index.query_plan_hints(self.wtxn)?.unwrap_or_default();
+        match self.filter_resolution {
+            Setting::Set(value) => hints.filter_resolution = value,
+            Setting::Reset => hints.filter_resolution = FilterResolution::default(),
+            Setting::NotSet => (),
+        }
+        match self.proximity_max_candidates {
+            Setting::Set(value) => hints.proximity_max_candidates = Some(value),
+            Setting::Reset => hints.proximity_max_candidates = None,
+            Setting::NotSet => (),
+        }
+
+        // the hints are only stored when they change the default query plan
+        if hints == QueryPlanHints::default() {
+            self.index.delete_query_plan_hints(self.wtxn)?;
+        } else {
+            self.index.put_query_plan_hints(self.wtxn, &hints)?;
+        }
+
+        Ok(())
+    }
+
+    fn update_search_exclusions(&mut self) -> Result<()> {
+        if self.excluded_document_ids.is_not_set()
+            && self.exclusion_filter.is_not_set()
+            && self.exclusion_mode.is_not_set()
+        {
+            return Ok(());
+        }
+
+        let mut exclusions = self.index.search_exclusions(self.wtxn)?.unwrap_or_default();
+        match std::mem::take(&mut self.excluded_document_ids) {
+            Setting::Set(value) => exclusions.document_ids = value,
+            Setting::Reset => exclusions.document_ids = Vec::new(),
+            Setting::NotSet => (),
+        }
+        match std::mem::take(&mut self.exclusion_filter) {
+            Setting::Set(value) => exclusions.filter = Some(value),
+            Setting::Reset => exclusions.filter = None,
+            Setting::NotSet => (),
+        }
+        match self.exclusion_mode {
+            Setting::Set(value) => exclusions.mode = value,
+            Setting::Reset => exclusions.mode = ExclusionMode::default(),
+            Setting::NotSet => (),
+        }
+
+        // the filter is checked now rather than failing every search made on the index
+        exclusions.documents_ids(self.wtxn, self.index)?;
+
+        if exclusions == SearchExclusions::default() {
+            self.index.delete_search_exclusions(self.wtxn)?;
+        } else {
+            self.index.put_search_exclusions(self.wtxn, &exclusions)?;
+        }
+
+        Ok(())
+    }
+
+    fn update_enrichment_webhook(&mut self) -> Result<()> {
+        if self.enrichment_webhook_url.is_not_set()
+            && self.enrichment_webhook_batch_size.is_not_set()
+        {
+            return Ok(());
+        }
+
+        let current = self.index.enrichment_webhook(self.wtxn)?;
+        let url = match std::mem::take(&mut self.enrichment_webhook_url) {
+            Setting::Set(url) => Some(url),
+            Setting::Reset => None,
+            Setting::NotSet => current.as_ref().map(|webhook| webhook.url.clone()),
+        };
+        let batch_size = match self.enrichment_webhook_batch_size {
+            Setting::Set(batch_size) => batch_size,
+            Setting::Reset => DEFAULT_ENRICHMENT_BATCH_SIZE,
+            Setting::NotSet => current.map_or(DEFAULT_ENRICHMENT_BATCH_SIZE, |w| w.batch_size),
+        };
+
+        // there is no webhook without an URL, so setting only its batch size is an error
+        let Some(url) = url else {
+            if matches!(self.enrichment_webhook_batch_size, Setting::Set(_)) {
+                return Err(UserError::MissingEnrichmentWebhookUrl.into());
+            }
+            self.index.delete_enrichment_webhook(self.wtxn)?;
+            return Ok(());
+        };
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            return Err(UserError::InvalidEnrichmentWebhookUrl(url).into());
+        }
+        if batch_size == 0 {
+            return Err(UserError::InvalidEnrichmentWebhookBatchSize.into());
+        }
+
+        self.index.put_enrichment_webhook(self.wtxn, &EnrichmentWebhook { url, batch_size })?;
+        Ok(())
+    }
+
+    fn update_unit_conversions(&mut self) -> Result<()> {
+        match self.unit_conversions {
+            Setting::Set(ref conversions) => {
+                for (field, conversion) in conversions {
+                    if conversion.rates.is_empty() {
+                        return Err(UserError::MissingUnitConversionRates(field.clone()).into());
+                    }
+                    // the converted values are compared with the facet values,
+                    // a conversion must keep them ordered.
+                    if let Some((unit, &rate)) =
+                        conversion.rates.iter().find(|(_, rate)| !(rate.is_finite() && **rate > 0.0))
+                    {
+                        return Err(UserError::InvalidUnitConversionRate {
+                            field: field.clone(),
+                            unit: unit.clone(),
+                            rate,
+                        }
+                        .into());
+                    }
+                }
+                self.index.put_unit_conversions(self.wtxn, conversions)?;
+            }
+            Setting::Reset => {
+                self.index.delete_unit_conversions(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    fn update_search_cutoff(&mut self) -> Result<()> {
+        match self.search_cutoff {
+            Setting::Set(cutoff) => {
+                self.index.put_search_cutoff(self.wtxn, cutoff)?;
+            }
+            Setting::Reset => {
+                self.index.delete_search_cutoff(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    fn update_search_templates(&mut self) -> Result<()> {
+        match self.search_templates {
+            Setting::Set(ref templates) => {
+                self.index.put_search_templates(self.wtxn, templates)?;
+            }
+            Setting::Reset => {
+                self.index.delete_search_templates(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    fn update_localized_attributes_rules(&mut self) -> Result<bool> {
+        let changed = match self.localized_attributes_rules {
+            Setting::Set(ref rules) => {
+                let old = self.index.localized_attributes_rules(self.wtxn)?;
+                if old.as_ref() == Some(rules) {
+                    false
+                } else {
+                    self.index.put_localized_attributes_rules(self.wtxn, rules)?;
+                    true
+                }
+            }
+            Setting::Reset => self.index.delete_localized_attributes_rules(self.wtxn)?,
+            Setting::NotSet => false,
+        };
+
+        Ok(changed)
+    }
+
     fn update_embedding_configs(&mut self) -> Result<bool> {
         let update = match std::mem::take(&mut self.embedder_settings) {
             Setting::Set(configs) => {
@@ -1042,11 +1511,18 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.update_criteria()?;
         self.update_primary_key()?;
         self.update_authorize_typos()?;
+        self.update_disable_typos_on_numbers()?;
         self.update_min_typo_word_len()?;
         self.update_exact_words()?;
         self.update_max_values_per_facet()?;
         self.update_sort_facet_values_by()?;
         self.update_pagination_max_total_hits()?;
+        self.update_query_plan_hints()?;
+        self.update_search_exclusions()?;
+        self.update_enrichment_webhook()?;
+        self.update_unit_conversions()?;
+        self.update_search_cutoff()?;
+        self.update_search_templates()?;
 
         // If there is new faceted fields we indicate that we must reindex as we must
         // index new fields as facets. It means that the distinct attribute,
@@ -1055,6 +1531,9 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         let faceted_updated = old_faceted_fields != new_faceted_fields;
 
         let stop_words_updated = self.update_stop_words()?;
+        let localized_stop_words_updated = self.update_localized_stop_words()?;
+        let stemming_updated = self.update_stemming()?;
+        let decompounding_updated = self.update_decompounding()?;
         let non_separator_tokens_updated = self.update_non_separator_tokens()?;
         let separator_tokens_updated = self.update_separator_tokens()?;
         let dictionary_updated = self.update_dictionary()?;
@@ -1062,6 +1541,8 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         let searchable_updated = self.update_searchable()?;
         let exact_attributes_updated = self.update_exact_attributes()?;
         let proximity_precision = self.update_proximity_precision()?;
+        let prefix_search_updated = self.update_prefix_search()?;
+        let localized_attributes_rules_updated = self.update_localized_attributes_rules()?;
         // TODO: very rough approximation of the needs for reindexing where any change will result in
         // a full reindexing.
         // What can be done instead:
@@ -1071,6 +1552,9 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         let embedding_configs_updated = self.update_embedding_configs()?;
 
         if stop_words_updated
+            || localized_stop_words_updated
+            || stemming_updated
+            || decompounding_updated
             || non_separator_tokens_updated
             || separator_tokens_updated
             || dictionary_updated
@@ -1079,6 +1563,8 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             || searchable_updated
             || exact_attributes_updated
             || proximity_precision
+            || prefix_search_updated
+            || localized_attributes_rules_updated
             || embedding_configs_updated
         {
             self.reindex(&progress_callback, &should_abort, old_fields_ids_map)?;
@@ -2010,6 +2496,9 @@ mod tests {
                     sortable_fields,
                     criteria,
                     stop_words,
+                    localized_stop_words,
+                    stemming,
+                    decompounding,
                     non_separator_tokens,
                     separator_tokens,
                     dictionary,
@@ -2021,10 +2510,23 @@ mod tests {
                     min_word_len_one_typo,
                     exact_words,
                     exact_attributes,
+                    disable_typos_on_numbers,
                     max_values_per_facet,
                     sort_facet_values_by,
                     pagination_max_total_hits,
                     proximity_precision,
+                    prefix_search,
+                    filter_resolution,
+                    proximity_max_candidates,
+                    excluded_document_ids,
+                    exclusion_filter,
+                    exclusion_mode,
+                    enrichment_webhook_url,
+                    enrichment_webhook_batch_size,
+                    unit_conversions,
+                    search_cutoff,
+                    search_templates,
+                    localized_attributes_rules,
                     embedder_settings,
                 } = settings;
                 assert!(matches!(searchable_fields, Setting::NotSet));
@@ -2033,6 +2535,9 @@ mod tests {
                 assert!(matches!(sortable_fields, Setting::NotSet));
                 assert!(matches!(criteria, Setting::NotSet));
                 assert!(matches!(stop_words, Setting::NotSet));
+                assert!(matches!(localized_stop_words, Setting::NotSet));
+                assert!(matches!(stemming, Setting::NotSet));
+                assert!(matches!(decompounding, Setting::NotSet));
                 assert!(matches!(non_separator_tokens, Setting::NotSet));
                 assert!(matches!(separator_tokens, Setting::NotSet));
                 assert!(matches!(dictionary, Setting::NotSet));
@@ -2044,10 +2549,23 @@ mod tests {
                 assert!(matches!(min_word_len_one_typo, Setting::NotSet));
                 assert!(matches!(exact_words, Setting::NotSet));
                 assert!(matches!(exact_attributes, Setting::NotSet));
+                assert!(matches!(disable_typos_on_numbers, Setting::NotSet));
                 assert!(matches!(max_values_per_facet, Setting::NotSet));
                 assert!(matches!(sort_facet_values_by, Setting::NotSet));
                 assert!(matches!(pagination_max_total_hits, Setting::NotSet));
                 assert!(matches!(proximity_precision, Setting::NotSet));
+                assert!(matches!(prefix_search, Setting::NotSet));
+                assert!(matches!(filter_resolution, Setting::NotSet));
+                assert!(matches!(proximity_max_candidates, Setting::NotSet));
+                assert!(matches!(excluded_document_ids, Setting::NotSet));
+                assert!(matches!(exclusion_filter, Setting::NotSet));
+                assert!(matches!(exclusion_mode, Setting::NotSet));
+                assert!(matches!(enrichment_webhook_url, Setting::NotSet));
+                assert!(matches!(enrichment_webhook_batch_size, Setting::NotSet));
+                assert!(matches!(unit_conversions, Setting::NotSet));
+                assert!(matches!(search_cutoff, Setting::NotSet));
+                assert!(matches!(search_templates, Setting::NotSet));
+                assert!(matches!(localized_attributes_rules, Setting::NotSet));
                 assert!(matches!(embedder_settings, Setting::NotSet));
             })
             .unwrap();
@@ -114,15 +114,15 @@ impl<'t, 'i> WordPrefixDocids<'t, 'i> {
         }
 
         // We remove all the entries that are no more required in this word prefix docids database.
-        let mut iter = self.word_prefix_docids.iter_mut(self.wtxn)?.lazily_decode_data();
-        while let Some((prefix, _)) = iter.next().transpose()? {
-            if del_prefix_fst_words.contains(prefix.as_bytes()) {
-                unsafe { iter.del_current()? };
+        if !del_prefix_fst_words.is_empty() {
+            let mut iter = self.word_prefix_docids.iter_mut(self.wtxn)?.lazily_decode_data();
+            while let Some((prefix, _)) = iter.next().transpose()? {
+                if del_prefix_fst_words.contains(prefix.as_bytes()) {
+                    unsafe { iter.del_current()? };
+                }
             }
         }
 
-        drop(iter);
-
         let database_is_empty = self.word_prefix_docids.is_empty(self.wtxn)?;
 
         // We finally write the word prefix docids into the LMDB database.
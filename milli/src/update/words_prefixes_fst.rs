@@ -1,7 +1,9 @@
+use std::collections::BTreeSet;
 use std::iter::{repeat_with, FromIterator};
 use std::str;
 
-use fst::{SetBuilder, Streamer};
+use fst::automaton::{Automaton, Str};
+use fst::{IntoStreamer, SetBuilder, Streamer};
 use heed::RwTxn;
 
 use crate::{Index, Result, SmallString32};
@@ -98,4 +100,76 @@ impl<'t, 'i> WordsPrefixesFst<'t, 'i> {
 
         Ok(())
     }
+
+    /// Updates the words prefixes FST by only recomputing the prefixes of the given words,
+    /// the words that may have been added to the words FST since the prefixes were computed.
+    ///
+    /// The FST is entirely rebuilt when there are too many words to update.
+    #[tracing::instrument(
+        level = "trace",
+        skip_all,
+        target = "indexing::prefix",
+        name = "words_prefix_fst_incremental"
+    )]
+    pub fn execute_incremental(self, updated_words: &BTreeSet<String>) -> Result<()> {
+        puffin::profile_function!();
+
+        let words_fst = self.index.words_fst(self.wtxn)?;
+
+        // Counting the words of a prefix stops at the threshold, updating a word reads at most
+        // `threshold` words for each one of its prefixes when a rebuild reads all of them.
+        if updated_words.len().saturating_mul(self.threshold as usize) >= words_fst.len() {
+            return self.execute();
+        }
+
+        let words_prefixes_fst = self.index.words_prefixes_fst(self.wtxn)?;
+
+        let prefixes: BTreeSet<&str> = updated_words
+            .iter()
+            .flat_map(|word| (0..self.max_prefix_length).filter_map(move |n| word.get(..=n)))
+            .collect();
+
+        let mut added_prefixes = BTreeSet::new();
+        let mut removed_prefixes = BTreeSet::new();
+        for prefix in prefixes {
+            let mut stream = words_fst.search(Str::new(prefix).starts_with()).into_stream();
+            let mut count = 0;
+            while count < self.threshold && stream.next().is_some() {
+                count += 1;
+            }
+
+            match (count >= self.threshold, words_prefixes_fst.contains(prefix)) {
+                (true, false) => {
+                    added_prefixes.insert(prefix);
+                }
+                (false, true) => {
+                    removed_prefixes.insert(prefix);
+                }
+                _ => (),
+            }
+        }
+
+        if added_prefixes.is_empty() && removed_prefixes.is_empty() {
+            return Ok(());
+        }
+
+        let added_prefixes = fst::Set::from_iter(added_prefixes)?;
+        let removed_prefixes = fst::Set::from_iter(removed_prefixes)?;
+
+        let mut builder = fst::SetBuilder::memory();
+        builder.extend_stream(words_prefixes_fst.op().add(&added_prefixes).r#union())?;
+        let prefix_fst = builder.into_set();
+
+        let mut builder = fst::SetBuilder::memory();
+        builder.extend_stream(prefix_fst.op().add(&removed_prefixes).difference())?;
+        let prefix_fst = builder.into_set();
+
+        drop(words_prefixes_fst);
+        drop(words_fst);
+
+        // Set the words prefixes FST in the dtabase.
+        self.index.put_words_prefixes_fst(self.wtxn, &prefix_fst)?;
+
+        Ok(())
+    }
 }
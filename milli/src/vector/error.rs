@@ -220,6 +220,10 @@ impl NewEmbedderError {
         Self { kind: NewEmbedderErrorKind::LoadModel(inner), fault: FaultSource::Runtime }
     }
 
+    pub fn hf_inference_pool(inner: rayon::ThreadPoolBuildError) -> NewEmbedderError {
+        Self { kind: NewEmbedderErrorKind::InferencePool(inner), fault: FaultSource::Runtime }
+    }
+
     pub fn hf_could_not_determine_dimension(inner: EmbedError) -> NewEmbedderError {
         Self {
             kind: NewEmbedderErrorKind::CouldNotDetermineDimension(inner),
@@ -283,6 +287,8 @@ pub enum NewEmbedderErrorKind {
     CouldNotDetermineDimension(EmbedError),
     #[error("loading model failed: {0}")]
     LoadModel(candle_core::Error),
+    #[error("could not spawn the inference threads: {0}")]
+    InferencePool(rayon::ThreadPoolBuildError),
     // openai
     #[error("The API key passed to Authorization error was in an invalid format: {0}")]
     InvalidApiKeyFormat(reqwest::header::InvalidHeaderValue),
@@ -4,6 +4,7 @@ use candle_transformers::models::bert::{BertModel, Config, DTYPE};
 // FIXME: currently we'll be using the hub to retrieve model, in the future we might want to embed it into Meilisearch itself
 use hf_hub::api::sync::Api;
 use hf_hub::{Repo, RepoType};
+use once_cell::sync::OnceCell;
 use tokenizers::{PaddingParams, Tokenizer};
 
 pub use super::error::{EmbedError, Error, NewEmbedderError};
@@ -50,12 +51,28 @@ impl Default for EmbedderOptions {
     }
 }
 
+/// The threads running the inferences of all the local embedders.
+///
+/// The indexing threads wait for an inference thread to be available rather than all running
+/// their inferences at once, which would slow every inference down and exhaust the memory.
+static INFERENCE_POOL: OnceCell<rayon::ThreadPool> = OnceCell::new();
+
+fn inference_pool() -> std::result::Result<&'static rayon::ThreadPool, NewEmbedderError> {
+    INFERENCE_POOL.get_or_try_init(|| {
+        rayon::ThreadPoolBuilder::new()
+            .thread_name(|index| format!("hf-inference-{index}"))
+            .build()
+            .map_err(NewEmbedderError::hf_inference_pool)
+    })
+}
+
 /// Perform embedding of documents and queries
 pub struct Embedder {
     model: BertModel,
     tokenizer: Tokenizer,
     options: EmbedderOptions,
     dimensions: usize,
+    pool: &'static rayon::ThreadPool,
 }
 
 impl std::fmt::Debug for Embedder {
@@ -127,7 +144,8 @@ impl Embedder {
             tokenizer.with_padding(Some(pp));
         }
 
-        let mut this = Self { model, tokenizer, options, dimensions: 0 };
+        let pool = inference_pool()?;
+        let mut this = Self { model, tokenizer, options, dimensions: 0, pool };
 
         let embeddings = this
             .embed(vec!["test".into()])
@@ -138,6 +156,13 @@ impl Embedder {
     }
 
     pub fn embed(
+        &self,
+        texts: Vec<String>,
+    ) -> std::result::Result<Vec<Embeddings<f32>>, EmbedError> {
+        self.pool.install(|| self.infer(texts))
+    }
+
+    fn infer(
         &self,
         mut texts: Vec<String>,
     ) -> std::result::Result<Vec<Embeddings<f32>>, EmbedError> {
@@ -177,7 +202,7 @@ impl Embedder {
         &self,
         text_chunks: Vec<Vec<String>>,
     ) -> std::result::Result<Vec<Vec<Embeddings<f32>>>, EmbedError> {
-        text_chunks.into_iter().map(|prompts| self.embed(prompts)).collect()
+        self.pool.install(|| text_chunks.into_iter().map(|prompts| self.infer(prompts)).collect())
     }
 
     pub fn chunk_count_hint(&self) -> usize {